@@ -1,10 +1,13 @@
 use getset::CopyGetters;
+use rand::prelude::*;
 
 use crate::domain::math::algebra::{Product, Vector};
 use crate::domain::math::geometry::{Direction, Distance, Point};
 use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::shape::def::BoundingBox;
 
-use super::{Offset, Resolution, Viewport};
+use super::{Offset, Projection, Resolution, Viewport};
 
 #[derive(Debug, Clone, PartialEq, CopyGetters)]
 pub struct Camera {
@@ -17,9 +20,21 @@ pub struct Camera {
     viewport: Viewport,
     viewport_horizontal_edge: Vector,
     viewport_vertical_edge: Vector,
+    #[getset(get_copy = "pub")]
+    projection: Projection,
+    #[getset(get_copy = "pub")]
+    shutter_open: Val,
+    #[getset(get_copy = "pub")]
+    shutter_close: Val,
+    #[getset(get_copy = "pub")]
+    lens_radius: Val,
+    #[getset(get_copy = "pub")]
+    focus_distance: Val,
 }
 
 impl Camera {
+    pub const FRAMING_FOV: Val = Val(0.6981317007977318);
+
     pub fn new(
         position: Point,
         orientation: Direction,
@@ -56,6 +71,101 @@ impl Camera {
             viewport,
             viewport_horizontal_edge,
             viewport_vertical_edge,
+            projection: Projection::default(),
+            shutter_open: Val(0.0),
+            shutter_close: Val(0.0),
+            lens_radius: Val(0.0),
+            focus_distance: Val(1.0),
+        }
+    }
+
+    pub fn frame_scene(
+        bounds: &BoundingBox,
+        orientation: Direction,
+        resolution: Resolution,
+        margin: Val,
+    ) -> Camera {
+        let center = bounds.centroid();
+        let radius = (Val(0.5) * (bounds.max() - bounds.min()).norm()).max(Val(Val::PRECISION));
+
+        let aspect_ratio = Val::from(resolution.width()) / Val::from(resolution.height());
+        let tan_vertical = (Val(0.5) * Self::FRAMING_FOV).tan();
+        let tan_half = tan_vertical.min(tan_vertical * aspect_ratio);
+        let sin_half = tan_half / (Val(1.0) + tan_half * tan_half).sqrt();
+
+        let distance = radius * (Val(1.0) + margin.max(Val(0.0))) / sin_half;
+        // Camera rays start on the viewport, which has to stay in front of the scene.
+        let focal_length = Val(0.5) * (distance - radius);
+        let height = Val(2.0) * focal_length * tan_vertical;
+
+        Camera::new(
+            center - distance * orientation,
+            orientation,
+            resolution,
+            Distance::new(height).expect("viewport height should be positive"),
+            Distance::new(focal_length).expect("focal length should be positive"),
+        )
+    }
+
+    pub fn with_projection(self, projection: Projection) -> Self {
+        Self { projection, ..self }
+    }
+
+    pub fn with_shutter(self, open: Val, close: Val) -> Self {
+        let open = open.clamp(Val(0.0), Val(1.0));
+        let close = close.clamp(Val(0.0), Val(1.0));
+        Self {
+            shutter_open: open.min(close),
+            shutter_close: open.max(close),
+            ..self
+        }
+    }
+
+    pub fn with_lens(self, lens_radius: Val, focus_distance: Distance) -> Self {
+        Self {
+            lens_radius: lens_radius.max(Val(0.0)),
+            focus_distance: focus_distance.value(),
+            ..self
+        }
+    }
+
+    pub fn sample_lens(&self, ray: Ray, rng: &mut dyn RngCore) -> Ray {
+        if self.lens_radius == Val(0.0) || self.projection != Projection::Perspective {
+            return ray;
+        }
+        let (x, y) = Self::sample_unit_disk(Val(rng.random()), Val(rng.random()));
+        let hdir = self.viewport_horizontal_edge / self.viewport.width().value();
+        let vdir = self.viewport_vertical_edge / self.viewport.height().value();
+        let lens = self.position + self.lens_radius * (x * hdir + y * vdir);
+
+        let cos = ray.direction().dot(self.orientation);
+        let focus = self.position + self.focus_distance / cos * ray.direction();
+        let direction =
+            Direction::normalize(focus - lens).expect("focus plane should be in front of lens");
+        let start = lens + self.focal_length.value() / direction.dot(self.orientation) * direction;
+        Ray::new(start, direction)
+    }
+
+    fn sample_unit_disk(u: Val, v: Val) -> (Val, Val) {
+        let (u, v) = (Val(2.0) * u - Val(1.0), Val(2.0) * v - Val(1.0));
+        if u == Val(0.0) && v == Val(0.0) {
+            return (Val(0.0), Val(0.0));
+        }
+        let (r, theta) = if u.abs() > v.abs() {
+            (u, Val::PI * Val(0.25) * (v / u))
+        } else {
+            (v, Val::PI * (Val(0.5) - Val(0.25) * (u / v)))
+        };
+        let (sin, cos) = theta.sin_cos();
+        (r * cos, r * sin)
+    }
+
+    pub fn sample_time(&self, rng: &mut dyn RngCore) -> Val {
+        if self.shutter_open == self.shutter_close {
+            self.shutter_open
+        } else {
+            let t = Val(rng.random());
+            self.shutter_open * (Val(1.0) - t) + self.shutter_close * t
         }
     }
 
@@ -63,6 +173,23 @@ impl Camera {
         self.viewport.resolution()
     }
 
+    pub fn pixel_spread_angle(&self) -> Val {
+        let pixel_size = self.viewport.pixel_size();
+        match self.projection {
+            Projection::Perspective => {
+                Val(2.0) * (Val(0.5) * pixel_size / self.focal_length.value()).atan()
+            }
+            Projection::Fisheye { fov } => {
+                let extent = self
+                    .viewport
+                    .width()
+                    .value()
+                    .min(self.viewport.height().value());
+                pixel_size * fov / extent
+            }
+        }
+    }
+
     pub fn calc_point_in_pixel(&self, row: usize, column: usize, offset: Offset) -> Option<Point> {
         let (vp, hp) = self.viewport.index_to_percentage(row, column, offset)?;
         let viewport_center = self.position + self.focal_length.value() * self.orientation;
@@ -71,10 +198,52 @@ impl Camera {
             + (vp - Val(0.5)) * self.viewport_vertical_edge;
         Some(point)
     }
+
+    pub fn calc_ray_in_pixel(&self, row: usize, column: usize, offset: Offset) -> Option<Ray> {
+        match self.projection {
+            Projection::Perspective => {
+                let point = self.calc_point_in_pixel(row, column, offset)?;
+                let direction = Direction::normalize(point - self.position)
+                    .expect("focal length should be positive");
+                Some(Ray::new(point, direction))
+            }
+            Projection::Fisheye { fov } => {
+                let (vp, hp) = self.viewport.index_to_percentage(row, column, offset)?;
+                let (width, height) = (
+                    self.viewport.width().value(),
+                    self.viewport.height().value(),
+                );
+                let x = (hp - Val(0.5)) * width;
+                let y = (vp - Val(0.5)) * height;
+                let radius = Val(0.5) * width.min(height);
+
+                let r = (x * x + y * y).sqrt() / radius;
+                if r > Val(1.0) {
+                    return None;
+                }
+                let (sin_theta, cos_theta) = (r * Val(0.5) * fov).sin_cos();
+                let radial = if r == Val(0.0) {
+                    Vector::zero()
+                } else {
+                    (x / width * self.viewport_horizontal_edge
+                        + y / height * self.viewport_vertical_edge)
+                        / (r * radius)
+                };
+                let direction =
+                    Direction::normalize(cos_theta * self.orientation + sin_theta * radial)
+                        .expect("direction should not be zero vector");
+                Some(Ray::new(self.position, direction))
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::domain::math::numeric::DisRange;
+    use crate::domain::shape::def::Shape;
+    use crate::domain::shape::primitive::Sphere;
+
     use super::*;
 
     #[test]
@@ -105,6 +274,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn camera_sample_time_succeeds_staying_within_shutter() {
+        let camera = Camera::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            -Direction::z_direction(),
+            Resolution::new(10, (2, 1)).unwrap(),
+            Distance::new(Val(1.0)).unwrap(),
+            Distance::new(Val(1.0)).unwrap(),
+        );
+        let mut rng = rand::rng();
+        assert_eq!(camera.sample_time(&mut rng), Val(0.0));
+
+        let camera = camera.with_shutter(Val(0.75), Val(0.25));
+        for _ in 0..32 {
+            let time = camera.sample_time(&mut rng);
+            assert!(Val(0.25) <= time && time <= Val(0.75));
+        }
+    }
+
+    #[test]
+    fn camera_sample_lens_succeeds_meeting_pinhole_ray_on_focus_plane() {
+        let camera = Camera::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            -Direction::z_direction(),
+            Resolution::new(10, (2, 1)).unwrap(),
+            Distance::new(Val(1.0)).unwrap(),
+            Distance::new(Val(1.0)).unwrap(),
+        );
+        let pinhole = camera.calc_ray_in_pixel(2, 3, Offset::center()).unwrap();
+        let mut rng = rand::rng();
+        assert_eq!(camera.sample_lens(pinhole.clone(), &mut rng), pinhole);
+
+        let camera = camera.with_lens(Val(0.2), Distance::new(Val(4.0)).unwrap());
+        let cos = pinhole.direction().dot(camera.orientation());
+        let focus = camera.position() + Val(4.0) / cos * pinhole.direction();
+        let mut starts = Vec::new();
+        for _ in 0..32 {
+            let ray = camera.sample_lens(pinhole.clone(), &mut rng);
+            assert_eq!(ray.start().z(), Val(-1.0));
+            let distance = Distance::new((focus - ray.start()).norm()).unwrap();
+            assert_eq!(ray.at(distance), focus);
+            starts.push(ray.start());
+        }
+        assert!(starts.iter().any(|&start| start != pinhole.start()));
+    }
+
+    #[test]
+    fn camera_calc_ray_in_pixel_succeeds_reaching_half_fov_at_fisheye_edge() {
+        let fov = Val::PI * Val(1.2);
+        let camera = Camera::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            -Direction::z_direction(),
+            Resolution::new(10, (2, 1)).unwrap(),
+            Distance::new(Val(1.0)).unwrap(),
+            Distance::new(Val(1.0)).unwrap(),
+        )
+        .with_projection(Projection::fisheye(fov).unwrap());
+
+        let edge = camera
+            .calc_ray_in_pixel(0, 9, Offset::new(Val(0.0), Val(1.0)).unwrap())
+            .unwrap();
+        let angle = edge.direction().dot(camera.orientation()).acos();
+        assert_eq!(angle, Val(0.5) * fov);
+        assert!(edge.direction().y() > Val(0.0));
+
+        let center = camera
+            .calc_ray_in_pixel(4, 9, Offset::new(Val(1.0), Val(1.0)).unwrap())
+            .unwrap();
+        assert_eq!(center.direction(), camera.orientation());
+
+        let corner = camera.calc_ray_in_pixel(0, 0, Offset::center());
+        assert!(corner.is_none());
+    }
+
+    #[test]
+    fn camera_frame_scene_succeeds_fitting_bounding_sphere() {
+        let bounds = BoundingBox::new(
+            Point::new(Val(-1.0), Val(-1.0), Val(-1.0)),
+            Point::new(Val(1.0), Val(1.0), Val(1.0)),
+        );
+        let sphere = Sphere::new(Point::default(), Val(3.0).sqrt()).unwrap();
+        let orientation = Direction::normalize(Vector::new(Val(1.0), Val(-1.0), Val(2.0))).unwrap();
+
+        for aspect_ratio in [(2, 1), (1, 2)] {
+            let resolution = Resolution::new(40, aspect_ratio).unwrap();
+            let (height, width) = (resolution.height(), resolution.width());
+            let camera = Camera::frame_scene(&bounds, orientation, resolution, Val(0.1));
+
+            let hits = |pixels: Vec<(usize, usize)>| {
+                (pixels.into_iter())
+                    .map(|(row, column)| {
+                        let ray = camera.calc_ray_in_pixel(row, column, Offset::center());
+                        let ray = ray.unwrap();
+                        sphere.hit(&ray, DisRange::positive()).is_some()
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let row = hits((0..width).map(|c| (height / 2, c)).collect());
+            let column = hits((0..height).map(|r| (r, width / 2)).collect());
+
+            let shorter = if width < height { &row } else { &column };
+            assert!(!shorter[0] && !shorter[shorter.len() - 1]);
+            let covered = shorter.iter().filter(|&&hit| hit).count();
+            assert!(covered * 10 >= shorter.len() * 8, "{covered}");
+        }
+    }
+
     #[test]
     fn camera_calc_point_in_pixel_succeeds() {
         let camera = Camera::new(
@@ -1,7 +1,9 @@
 mod camera;
+mod projection;
 mod resolution;
 mod viewport;
 
 pub use camera::Camera;
+pub use projection::{Projection, TryNewProjectionError};
 pub use resolution::Resolution;
 pub use viewport::{Offset, Viewport};
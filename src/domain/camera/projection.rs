@@ -0,0 +1,29 @@
+use snafu::prelude::*;
+
+use crate::domain::math::numeric::Val;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Projection {
+    #[default]
+    Perspective,
+    Fisheye {
+        fov: Val,
+    },
+}
+
+impl Projection {
+    pub fn fisheye(fov: Val) -> Result<Self, TryNewProjectionError> {
+        ensure!(
+            Val(0.0) < fov && fov <= Val(2.0) * Val::PI,
+            InvalidFieldOfViewSnafu
+        );
+        Ok(Self::Fisheye { fov })
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewProjectionError {
+    #[snafu(display("field of view should be in (0, 2 * pi]"))]
+    InvalidFieldOfView,
+}
@@ -26,11 +26,19 @@ impl Resolution {
             height,
         })
     }
+
+    pub fn from_dimensions(width: usize, height: usize) -> Result<Self, TryNewResolutionError> {
+        ensure!(width > 0, InvalidWidthSnafu);
+        ensure!(height > 0, InvalidHeightSnafu);
+        Ok(Self { width, height })
+    }
 }
 
 #[derive(Debug, Snafu, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum TryNewResolutionError {
+    #[snafu(display("width is not positive"))]
+    InvalidWidth,
     #[snafu(display("height is not positive"))]
     InvalidHeight,
     #[snafu(display("aspect ratio has a zero-valued component"))]
@@ -49,6 +57,24 @@ mod tests {
         assert_eq!(resolution.width, 2560);
     }
 
+    #[test]
+    fn resolution_from_dimensions_succeeds() {
+        let resolution = Resolution::from_dimensions(1920, 1080).unwrap();
+        assert_eq!(resolution.width(), 1920);
+        assert_eq!(resolution.height(), 1080);
+
+        let resolution = Resolution::from_dimensions(1366, 768).unwrap();
+        assert_eq!(resolution.width(), 1366);
+    }
+
+    #[test]
+    fn resolution_from_dimensions_fails_when_width_is_invalid() {
+        assert_eq!(
+            Resolution::from_dimensions(0, 1080),
+            Err(TryNewResolutionError::InvalidWidth),
+        );
+    }
+
     #[test]
     fn resolution_new_fails_when_height_is_invalid() {
         assert_eq!(
@@ -1,4 +1,5 @@
 use getset::{CopyGetters, Getters};
+use rand::prelude::*;
 use snafu::prelude::*;
 
 use crate::domain::math::geometry::Distance;
@@ -76,6 +77,24 @@ impl Offset {
         }
     }
 
+    pub fn stratified(index: usize, count: usize, rng: &mut dyn RngCore) -> Self {
+        let side = count.isqrt().max(1);
+        let (jitter_row, jitter_column) = (Val(rng.random()), Val(rng.random()));
+        if index >= side * side {
+            return Self {
+                row: jitter_row,
+                column: jitter_column,
+            };
+        }
+
+        let (cell_row, cell_column) = (index / side, index % side);
+        let side = Val::from(side);
+        Self {
+            row: (Val::from(cell_row) + jitter_row) / side,
+            column: (Val::from(cell_column) + jitter_column) / side,
+        }
+    }
+
     pub fn row(&self) -> Val {
         self.row
     }
@@ -90,3 +109,22 @@ pub enum TryNewOffsetError {
     #[snafu(display("offset is out of range [0, 1]"))]
     InvalidOffset,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_stratified_succeeds_covering_distinct_quadrants() {
+        let mut rng = rand::rng();
+        let mut quadrants = (0..4)
+            .map(|index| {
+                let offset = Offset::stratified(index, 4, &mut rng);
+                (offset.row() >= Val(0.5), offset.column() >= Val(0.5))
+            })
+            .collect::<Vec<_>>();
+        quadrants.sort();
+        quadrants.dedup();
+        assert_eq!(quadrants.len(), 4);
+    }
+}
@@ -0,0 +1,36 @@
+use rand::prelude::*;
+
+use crate::domain::math::numeric::Val;
+
+use super::Spectrum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpectralChannel {
+    Red,
+    Green,
+    Blue,
+}
+
+impl SpectralChannel {
+    pub const ALL: [Self; 3] = [Self::Red, Self::Green, Self::Blue];
+
+    pub fn sample(rng: &mut dyn RngCore) -> Self {
+        Self::ALL[rng.random_range(0..Self::ALL.len())]
+    }
+
+    pub fn wavelength(&self) -> Val {
+        match self {
+            Self::Red => Val(630.0),
+            Self::Green => Val(532.0),
+            Self::Blue => Val(465.0),
+        }
+    }
+
+    pub fn mask(&self) -> Spectrum {
+        match self {
+            Self::Red => Spectrum::new(Val(1.0), Val(0.0), Val(0.0)),
+            Self::Green => Spectrum::new(Val(0.0), Val(1.0), Val(0.0)),
+            Self::Blue => Spectrum::new(Val(0.0), Val(0.0), Val(1.0)),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::domain::math::numeric::Val;
+
+const XYZ_TO_LINEAR_SRGB: [[Val; 3]; 3] = [
+    [Val(3.2404542), Val(-1.5371385), Val(-0.4985314)],
+    [Val(-0.9692660), Val(1.8760108), Val(0.0415560)],
+    [Val(0.0556434), Val(-0.2040259), Val(1.0572252)],
+];
+
+pub(crate) fn cie_matching(wavelength: Val) -> [Val; 3] {
+    let lobe = |mean: Val, below: Val, above: Val| {
+        let sigma = if wavelength < mean { below } else { above };
+        (Val(-0.5) * ((wavelength - mean) / sigma).powi(2)).exp()
+    };
+    let x = Val(1.056) * lobe(Val(599.8), Val(37.9), Val(31.0))
+        + Val(0.362) * lobe(Val(442.0), Val(16.0), Val(26.7))
+        - Val(0.065) * lobe(Val(501.1), Val(20.4), Val(26.2));
+    let y = Val(0.821) * lobe(Val(568.8), Val(46.9), Val(40.5))
+        + Val(0.286) * lobe(Val(530.9), Val(16.3), Val(31.1));
+    let z = Val(1.217) * lobe(Val(437.0), Val(11.8), Val(36.0))
+        + Val(0.681) * lobe(Val(459.0), Val(26.0), Val(13.8));
+    [x, y, z]
+}
+
+pub(crate) fn xyz_to_linear_srgb(xyz: [Val; 3]) -> [Val; 3] {
+    XYZ_TO_LINEAR_SRGB.map(|row| (0..3).map(|k| row[k] * xyz[k]).sum())
+}
@@ -1,7 +1,13 @@
 mod albedo;
+mod channel;
+mod cie;
 mod def;
+mod space;
 mod spectrum;
 
 pub use albedo::Albedo;
+pub use channel::SpectralChannel;
+pub(crate) use cie::{cie_matching, xyz_to_linear_srgb};
 pub use def::Color;
+pub use space::ColorSpace;
 pub use spectrum::Spectrum;
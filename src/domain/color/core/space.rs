@@ -0,0 +1,66 @@
+use crate::domain::math::numeric::Val;
+
+use super::Spectrum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorSpace {
+    #[default]
+    Linear,
+    Srgb,
+}
+
+impl ColorSpace {
+    pub fn to_linear(&self, spectrum: Spectrum) -> Spectrum {
+        match self {
+            Self::Linear => spectrum,
+            Self::Srgb => Spectrum::new(
+                Self::decode_srgb(spectrum.red()),
+                Self::decode_srgb(spectrum.green()),
+                Self::decode_srgb(spectrum.blue()),
+            ),
+        }
+    }
+
+    pub fn from_linear(&self, spectrum: Spectrum) -> Spectrum {
+        match self {
+            Self::Linear => spectrum,
+            Self::Srgb => Spectrum::new(
+                Self::encode_srgb(spectrum.red()),
+                Self::encode_srgb(spectrum.green()),
+                Self::encode_srgb(spectrum.blue()),
+            ),
+        }
+    }
+
+    pub fn encode_srgb(linear: Val) -> Val {
+        if linear <= Val(0.0031308) {
+            Val(12.92) * linear
+        } else {
+            linear.powf(Val(1.0 / 2.4)).mul_add(Val(1.055), Val(-0.055))
+        }
+    }
+
+    pub fn decode_srgb(srgb: Val) -> Val {
+        if srgb <= Val(0.04045) {
+            srgb / Val(12.92)
+        } else {
+            ((srgb + Val(0.055)) / Val(1.055)).powf(Val(2.4))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_space_to_linear_succeeds_decoding_srgb() {
+        let decoded = ColorSpace::Srgb.to_linear(Spectrum::broadcast(Val(0.5)));
+        assert!((decoded.red() - Val(0.214)).abs() < Val(1e-3));
+        let encoded = ColorSpace::Srgb.from_linear(decoded);
+        assert_eq!(encoded, Spectrum::broadcast(Val(0.5)));
+
+        let linear = ColorSpace::Linear.to_linear(Spectrum::broadcast(Val(0.5)));
+        assert_eq!(linear, Spectrum::broadcast(Val(0.5)));
+    }
+}
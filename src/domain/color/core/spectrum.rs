@@ -5,7 +5,7 @@ use getset::CopyGetters;
 
 use crate::domain::math::numeric::Val;
 
-use super::Color;
+use super::{Color, cie_matching, xyz_to_linear_srgb};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, CopyGetters)]
 #[getset(get_copy = "pub")]
@@ -35,11 +35,44 @@ impl Spectrum {
         Self::broadcast(Val(0.0))
     }
 
+    pub fn blackbody(temperature: Val) -> Self {
+        // The second radiation constant `hc / k` in micrometer kelvins.
+        const C2: Val = Val(1.4387769e4);
+        if temperature <= Val(0.0) {
+            return Self::zero();
+        }
+        let mut xyz = [Val(0.0); 3];
+        for step in 0..=80 {
+            let wavelength = Val(380.0) + Val(5.0) * Val::from(step);
+            let micrometers = wavelength / Val(1000.0);
+            let exponent = C2 / (micrometers * temperature);
+            let planck = (micrometers.powi(5) * (exponent.exp() - Val(1.0))).recip();
+            for (sum, weight) in xyz.iter_mut().zip(cie_matching(wavelength)) {
+                *sum += planck * weight;
+            }
+        }
+        if xyz[1] == Val(0.0) {
+            return Self::zero();
+        }
+        let [red, green, blue] = xyz_to_linear_srgb(xyz.map(|v| v / xyz[1]));
+        Self::new(red, green, blue)
+    }
+
     #[inline]
     pub fn norm(&self) -> Val {
         (self.red.powi(2) + self.green.powi(2) + self.blue.powi(2)).sqrt()
     }
 
+    #[inline]
+    pub fn luminance(&self) -> Val {
+        Val(0.2126) * self.red + Val(0.7152) * self.green + Val(0.0722) * self.blue
+    }
+
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.red.is_finite() && self.green.is_finite() && self.blue.is_finite()
+    }
+
     pub fn channel(&self, index: usize) -> Val {
         match index {
             0 => self.red,
@@ -178,3 +211,21 @@ impl Sum for Spectrum {
         iter.fold(Spectrum::zero(), |sum, x| sum + x)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spectrum_blackbody_succeeds() {
+        let daylight = Spectrum::blackbody(Val(6500.0));
+        assert!((daylight.luminance() - Val(1.0)).abs() < Val(1e-2));
+        assert!((daylight.red() - daylight.blue()).abs() < Val(0.1));
+        assert!((daylight.red() - daylight.green()).abs() < Val(0.1));
+
+        let incandescent = Spectrum::blackbody(Val(2700.0));
+        assert!(incandescent.red() > Val(2.0) * incandescent.blue());
+
+        assert_eq!(Spectrum::blackbody(Val(0.0)), Spectrum::zero());
+    }
+}
@@ -0,0 +1,165 @@
+use std::sync::LazyLock;
+
+use rand::prelude::*;
+
+use crate::domain::math::numeric::Val;
+
+static TILE: LazyLock<Vec<Val>> = LazyLock::new(BlueNoiseDither::build_tile);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlueNoiseDither;
+
+impl BlueNoiseDither {
+    pub const TILE_SIZE: usize = 16;
+    const NUM_CELLS: usize = Self::TILE_SIZE * Self::TILE_SIZE;
+    const SIGMA: f64 = 1.5;
+
+    pub fn threshold(row: usize, column: usize) -> Val {
+        let (row, column) = (row % Self::TILE_SIZE, column % Self::TILE_SIZE);
+        TILE[row * Self::TILE_SIZE + column]
+    }
+
+    fn build_tile() -> Vec<Val> {
+        let initial = Self::build_initial_pattern();
+        let mut ranks = vec![0; Self::NUM_CELLS];
+        let num_initial = initial.iter().filter(|&&on| on).count();
+
+        let mut pattern = initial.clone();
+        let mut energy = Self::calc_energy(&pattern);
+        for rank in (0..num_initial).rev() {
+            let cluster = Self::find_tightest_cluster(&pattern, &energy);
+            Self::toggle(&mut pattern, &mut energy, cluster);
+            ranks[cluster] = rank;
+        }
+
+        let mut pattern = initial;
+        let mut energy = Self::calc_energy(&pattern);
+        for rank in num_initial..Self::NUM_CELLS {
+            let void = Self::find_largest_void(&pattern, &energy);
+            Self::toggle(&mut pattern, &mut energy, void);
+            ranks[void] = rank;
+        }
+
+        (ranks.into_iter())
+            .map(|rank| (Val::from(rank) + Val(0.5)) / Val::from(Self::NUM_CELLS))
+            .collect()
+    }
+
+    fn build_initial_pattern() -> Vec<bool> {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut pattern = vec![false; Self::NUM_CELLS];
+        for index in (0..Self::NUM_CELLS).choose_multiple(&mut rng, Self::NUM_CELLS / 10) {
+            pattern[index] = true;
+        }
+
+        let mut energy = Self::calc_energy(&pattern);
+        for _ in 0..Self::NUM_CELLS {
+            let cluster = Self::find_tightest_cluster(&pattern, &energy);
+            Self::toggle(&mut pattern, &mut energy, cluster);
+            let void = Self::find_largest_void(&pattern, &energy);
+            Self::toggle(&mut pattern, &mut energy, void);
+            if void == cluster {
+                break;
+            }
+        }
+        pattern
+    }
+
+    fn calc_energy(pattern: &[bool]) -> Vec<f64> {
+        (0..Self::NUM_CELLS)
+            .map(|i| {
+                (0..Self::NUM_CELLS)
+                    .filter(|&j| pattern[j])
+                    .map(|j| Self::calc_kernel(i, j))
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn calc_kernel(i: usize, j: usize) -> f64 {
+        let wrap = |a: usize, b: usize| {
+            let d = a.abs_diff(b);
+            d.min(Self::TILE_SIZE - d) as f64
+        };
+        let dy = wrap(i / Self::TILE_SIZE, j / Self::TILE_SIZE);
+        let dx = wrap(i % Self::TILE_SIZE, j % Self::TILE_SIZE);
+        (-(dx * dx + dy * dy) / (2.0 * Self::SIGMA * Self::SIGMA)).exp()
+    }
+
+    fn toggle(pattern: &mut [bool], energy: &mut [f64], index: usize) {
+        let sign = if pattern[index] { -1.0 } else { 1.0 };
+        pattern[index] = !pattern[index];
+        for (i, e) in energy.iter_mut().enumerate() {
+            *e += sign * Self::calc_kernel(i, index);
+        }
+    }
+
+    fn find_tightest_cluster(pattern: &[bool], energy: &[f64]) -> usize {
+        (0..Self::NUM_CELLS)
+            .filter(|&i| pattern[i])
+            .max_by(|&a, &b| energy[a].total_cmp(&energy[b]))
+            .expect("pattern should have at least one minority pixel")
+    }
+
+    fn find_largest_void(pattern: &[bool], energy: &[f64]) -> usize {
+        (0..Self::NUM_CELLS)
+            .filter(|&i| !pattern[i])
+            .min_by(|&a, &b| energy[a].total_cmp(&energy[b]))
+            .expect("pattern should have at least one majority pixel")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::core::{ColorSpace, Spectrum};
+    use crate::domain::color::external::SRgbColor;
+
+    use super::*;
+
+    #[test]
+    fn blue_noise_dither_threshold_succeeds_covering_every_rank_once() {
+        let size = BlueNoiseDither::TILE_SIZE;
+        let mut ranks = (0..size * size)
+            .map(|i| BlueNoiseDither::threshold(i / size, i % size))
+            .map(|t| usize::from(t * Val::from(size * size)))
+            .collect::<Vec<_>>();
+        ranks.sort();
+        assert_eq!(ranks, (0..size * size).collect::<Vec<_>>());
+        assert_eq!(
+            BlueNoiseDither::threshold(size + 3, 2 * size + 5),
+            BlueNoiseDither::threshold(3, 5),
+        );
+    }
+
+    #[test]
+    fn srgb_color_from_dithered_succeeds_breaking_up_bands_of_ramp() {
+        let size = BlueNoiseDither::TILE_SIZE;
+        let quantize = |dithered: bool| {
+            let mut levels = Vec::new();
+            for row in 0..size {
+                for column in 0..size {
+                    let encoded =
+                        (Val(128.1) + Val(0.3) * Val::from(column) / Val::from(size)) / Val(255.0);
+                    let color = Spectrum::broadcast(ColorSpace::decode_srgb(encoded));
+                    let color = if dithered {
+                        SRgbColor::from_dithered(color, BlueNoiseDither::threshold(row, column))
+                    } else {
+                        SRgbColor::from(color)
+                    };
+                    levels.push(color.red());
+                }
+            }
+            levels
+        };
+
+        let distinct = |mut levels: Vec<u8>| {
+            levels.sort();
+            levels.dedup();
+            levels.len()
+        };
+        let (plain, dithered) = (quantize(false), quantize(true));
+        let mean = dithered.iter().map(|&l| Val::from(l)).sum::<Val>() / Val::from(size * size);
+        assert!(distinct(dithered) > distinct(plain));
+        assert!((mean - Val(128.25)).abs() < Val(0.1), "{mean:?}");
+    }
+}
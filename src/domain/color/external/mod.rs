@@ -1,3 +1,5 @@
+mod dither;
 mod srgb;
 
+pub use dither::BlueNoiseDither;
 pub use srgb::SRgbColor;
@@ -1,6 +1,6 @@
 use getset::CopyGetters;
 
-use crate::domain::color::core::Spectrum;
+use crate::domain::color::core::{ColorSpace, Spectrum};
 use crate::domain::math::numeric::Val;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
@@ -16,28 +16,35 @@ impl SRgbColor {
         Self { red, green, blue }
     }
 
-    fn encode_gamma(linear: Val) -> Val {
-        if linear <= Val(0.0031308) {
-            Val(12.92) * linear
-        } else {
-            linear.powf(Val(1.0 / 2.4)).mul_add(Val(1.055), Val(-0.055))
+    pub fn from_dithered(value: Spectrum, threshold: Val) -> Self {
+        let quantize = |channel: Val| {
+            let encoded = ColorSpace::encode_srgb(channel).clamp(Val(0.0), Val(1.0));
+            (Val(255.0) * encoded + threshold)
+                .floor()
+                .min(Val(255.0))
+                .into()
+        };
+        Self {
+            red: quantize(value.red()),
+            green: quantize(value.green()),
+            blue: quantize(value.blue()),
         }
     }
 
-    fn decode_gamma(srgb: Val) -> Val {
-        if srgb <= Val(0.04045) {
-            srgb / Val(12.92)
-        } else {
-            ((srgb + Val(0.055)) / Val(1.055)).powf(Val(2.4))
-        }
+    pub fn to_encoded(&self) -> Spectrum {
+        Spectrum::new(
+            Val::from(self.red) / Val(255.0),
+            Val::from(self.green) / Val(255.0),
+            Val::from(self.blue) / Val(255.0),
+        )
     }
 }
 
 impl From<Spectrum> for SRgbColor {
     fn from(value: Spectrum) -> Self {
-        let red = Val(256.0) * Self::encode_gamma(value.red()).clamp(Val(0.0), Val(0.999));
-        let green = Val(256.0) * Self::encode_gamma(value.green()).clamp(Val(0.0), Val(0.999));
-        let blue = Val(256.0) * Self::encode_gamma(value.blue()).clamp(Val(0.0), Val(0.999));
+        let red = Val(256.0) * ColorSpace::encode_srgb(value.red()).clamp(Val(0.0), Val(0.999));
+        let green = Val(256.0) * ColorSpace::encode_srgb(value.green()).clamp(Val(0.0), Val(0.999));
+        let blue = Val(256.0) * ColorSpace::encode_srgb(value.blue()).clamp(Val(0.0), Val(0.999));
         SRgbColor {
             red: red.into(),
             green: green.into(),
@@ -48,9 +55,9 @@ impl From<Spectrum> for SRgbColor {
 
 impl From<SRgbColor> for Spectrum {
     fn from(value: SRgbColor) -> Self {
-        let red = SRgbColor::decode_gamma(Val::from(value.red) / Val(255.0));
-        let green = SRgbColor::decode_gamma(Val::from(value.green) / Val(255.0));
-        let blue = SRgbColor::decode_gamma(Val::from(value.blue) / Val(255.0));
+        let red = ColorSpace::decode_srgb(Val::from(value.red) / Val(255.0));
+        let green = ColorSpace::decode_srgb(Val::from(value.green) / Val(255.0));
+        let blue = ColorSpace::decode_srgb(Val::from(value.blue) / Val(255.0));
         Spectrum::new(red, green, blue)
     }
 }
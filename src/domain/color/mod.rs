@@ -1,3 +1,6 @@
 pub mod core;
 pub mod external;
 pub mod map;
+#[cfg(feature = "spectral")]
+pub mod spectral;
+pub mod tone;
@@ -0,0 +1,77 @@
+use std::sync::OnceLock;
+
+use crate::domain::color::core::{cie_matching, xyz_to_linear_srgb};
+use crate::domain::math::numeric::Val;
+
+use super::SampledSpectrum;
+
+#[derive(Debug)]
+pub(super) struct SpectralConversion {
+    pub(super) to_rgb: [[Val; 3]; SampledSpectrum::BINS],
+    pub(super) from_rgb: [[Val; 3]; SampledSpectrum::BINS],
+}
+
+impl SpectralConversion {
+    pub(super) fn get() -> &'static Self {
+        static CONVERSION: OnceLock<SpectralConversion> = OnceLock::new();
+        CONVERSION.get_or_init(Self::new)
+    }
+
+    fn new() -> Self {
+        let mut to_rgb = [[Val(0.0); 3]; SampledSpectrum::BINS];
+        for (i, rgb) in to_rgb.iter_mut().enumerate() {
+            *rgb = xyz_to_linear_srgb(cie_matching(SampledSpectrum::bin_wavelength(i)));
+        }
+        let white = (0..3)
+            .map(|c| to_rgb.iter().map(|rgb| rgb[c]).sum::<Val>())
+            .collect::<Vec<_>>();
+        for rgb in &mut to_rgb {
+            for (channel, white) in rgb.iter_mut().zip(&white) {
+                *channel /= *white;
+            }
+        }
+
+        let mut basis_to_rgb = [[Val(0.0); 3]; 3];
+        for (i, rgb) in to_rgb.iter().enumerate() {
+            let basis = Self::basis(SampledSpectrum::bin_wavelength(i));
+            for (c, row) in basis_to_rgb.iter_mut().enumerate() {
+                for (k, entry) in row.iter_mut().enumerate() {
+                    *entry += rgb[c] * basis[k];
+                }
+            }
+        }
+        let rgb_to_basis = Self::invert(basis_to_rgb);
+
+        let mut from_rgb = [[Val(0.0); 3]; SampledSpectrum::BINS];
+        for (i, weights) in from_rgb.iter_mut().enumerate() {
+            let basis = Self::basis(SampledSpectrum::bin_wavelength(i));
+            for (c, weight) in weights.iter_mut().enumerate() {
+                *weight = (0..3).map(|k| basis[k] * rgb_to_basis[k][c]).sum();
+            }
+        }
+
+        Self { to_rgb, from_rgb }
+    }
+
+    fn basis(wavelength: Val) -> [Val; 3] {
+        let blue = ((Val(520.0) - wavelength) / Val(40.0)).clamp(Val(0.0), Val(1.0));
+        let red = ((wavelength - Val(560.0)) / Val(40.0)).clamp(Val(0.0), Val(1.0));
+        [red, Val(1.0) - red - blue, blue]
+    }
+
+    fn invert(m: [[Val; 3]; 3]) -> [[Val; 3]; 3] {
+        let cofactor = |r: usize, c: usize| {
+            let (r0, r1) = ((r + 1) % 3, (r + 2) % 3);
+            let (c0, c1) = ((c + 1) % 3, (c + 2) % 3);
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+        let det = (0..3).map(|c| m[0][c] * cofactor(0, c)).sum::<Val>();
+        let mut inverse = [[Val(0.0); 3]; 3];
+        for (r, row) in inverse.iter_mut().enumerate() {
+            for (c, entry) in row.iter_mut().enumerate() {
+                *entry = cofactor(c, r) / det;
+            }
+        }
+        inverse
+    }
+}
@@ -0,0 +1,4 @@
+mod matching;
+mod spectrum;
+
+pub use spectrum::SampledSpectrum;
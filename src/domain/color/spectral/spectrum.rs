@@ -0,0 +1,143 @@
+use std::ops::{Add, Mul};
+
+use rand::prelude::*;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::math::numeric::Val;
+
+use super::matching::SpectralConversion;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampledSpectrum {
+    values: [Val; SampledSpectrum::BINS],
+}
+
+impl SampledSpectrum {
+    pub const BINS: usize = 30;
+    pub const MIN_WAVELENGTH: Val = Val(380.0);
+    pub const MAX_WAVELENGTH: Val = Val(730.0);
+
+    pub fn new(values: [Val; Self::BINS]) -> Self {
+        Self {
+            values: values.map(|value| value.max(Val(0.0))),
+        }
+    }
+
+    pub fn broadcast(value: Val) -> Self {
+        Self::new([value; Self::BINS])
+    }
+
+    pub fn zero() -> Self {
+        Self::broadcast(Val(0.0))
+    }
+
+    pub fn values(&self) -> &[Val; Self::BINS] {
+        &self.values
+    }
+
+    pub fn bin_wavelength(index: usize) -> Val {
+        let width = (Self::MAX_WAVELENGTH - Self::MIN_WAVELENGTH) / Val::from(Self::BINS);
+        Self::MIN_WAVELENGTH + (Val::from(index) + Val(0.5)) * width
+    }
+
+    pub fn sample_wavelength(rng: &mut dyn RngCore) -> Val {
+        Val::lerp(
+            Self::MIN_WAVELENGTH,
+            Self::MAX_WAVELENGTH,
+            Val(rng.random()),
+        )
+    }
+
+    pub fn at(&self, wavelength: Val) -> Val {
+        if wavelength < Self::MIN_WAVELENGTH || wavelength > Self::MAX_WAVELENGTH {
+            return Val(0.0);
+        }
+        let t = (wavelength - Self::MIN_WAVELENGTH) / (Self::MAX_WAVELENGTH - Self::MIN_WAVELENGTH);
+        let index = usize::from((t * Val::from(Self::BINS)).trunc());
+        self.values[index.min(Self::BINS - 1)]
+    }
+
+    pub fn from_rgb(rgb: Spectrum) -> Self {
+        let conversion = SpectralConversion::get();
+        let rgb = [rgb.red(), rgb.green(), rgb.blue()];
+        let mut values = [Val(0.0); Self::BINS];
+        for (value, weights) in values.iter_mut().zip(&conversion.from_rgb) {
+            *value = (0..3).map(|c| weights[c] * rgb[c]).sum();
+        }
+        Self::new(values)
+    }
+
+    pub fn to_rgb(&self) -> Spectrum {
+        let conversion = SpectralConversion::get();
+        let mut rgb = [Val(0.0); 3];
+        for (value, weights) in self.values.iter().zip(&conversion.to_rgb) {
+            for (channel, weight) in rgb.iter_mut().zip(weights) {
+                *channel += *value * *weight;
+            }
+        }
+        Spectrum::new(rgb[0], rgb[1], rgb[2])
+    }
+}
+
+impl From<Spectrum> for SampledSpectrum {
+    fn from(value: Spectrum) -> Self {
+        Self::from_rgb(value)
+    }
+}
+
+impl Add for SampledSpectrum {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut values = self.values;
+        for (value, rhs) in values.iter_mut().zip(rhs.values) {
+            *value += rhs;
+        }
+        Self::new(values)
+    }
+}
+
+impl Mul for SampledSpectrum {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut values = self.values;
+        for (value, rhs) in values.iter_mut().zip(rhs.values) {
+            *value *= rhs;
+        }
+        Self::new(values)
+    }
+}
+
+impl Mul<Val> for SampledSpectrum {
+    type Output = Self;
+
+    fn mul(self, rhs: Val) -> Self::Output {
+        Self::new(self.values.map(|value| value * rhs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sampled_spectrum_to_rgb_succeeds_round_tripping_flat_spectrum() {
+        let flat = SampledSpectrum::broadcast(Val(0.5));
+        let rgb = flat.to_rgb();
+        assert_eq!(rgb, Spectrum::broadcast(Val(0.5)));
+
+        let restored = SampledSpectrum::from_rgb(rgb);
+        for value in restored.values() {
+            assert!((*value - Val(0.5)).abs() < Val(1e-6));
+        }
+    }
+
+    #[test]
+    fn sampled_spectrum_from_rgb_succeeds_preserving_color() {
+        let rgb = Spectrum::new(Val(0.6), Val(0.4), Val(0.3));
+        let spectrum = SampledSpectrum::from_rgb(rgb);
+        assert!(spectrum.at(Val(650.0)) > spectrum.at(Val(450.0)));
+        assert_eq!(spectrum.to_rgb(), rgb);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::domain::math::numeric::Val;
+
+use super::ToneMapper;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcesFilmicToneMapper {}
+
+impl AcesFilmicToneMapper {
+    #[inline]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ToneMapper for AcesFilmicToneMapper {
+    fn map_channel(&self, value: Val) -> Val {
+        let x = value.max(Val(0.0));
+        let numerator = x * (Val(2.51) * x + Val(0.03));
+        let denominator = x * (Val(2.43) * x + Val(0.59)) + Val(0.14);
+        (numerator / denominator).clamp(Val(0.0), Val(1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::camera::Resolution;
+    use crate::domain::color::core::Spectrum;
+    use crate::domain::image::core::Image;
+
+    use super::*;
+
+    #[test]
+    fn aces_filmic_tone_mapper_map_succeeds() {
+        let mapper = AcesFilmicToneMapper::new();
+
+        let bright = mapper.map(Spectrum::new(Val(5.0), Val(2.0), Val(0.0)));
+        assert!(bright.red() < Val(1.0));
+        assert!(bright.green() < bright.red());
+        assert_eq!(bright.blue(), Val(0.0));
+
+        let grey = mapper.map_channel(Val(0.18));
+        assert!((grey - Val(0.18)).abs() < Val(0.1));
+    }
+
+    #[test]
+    fn aces_filmic_tone_mapper_map_image_succeeds_applying_exposure() {
+        let mut image = Image::new(Resolution::new(1, (1, 1)).unwrap());
+        image.set(0, 0, Spectrum::broadcast(Val(0.5)));
+
+        let mapper = AcesFilmicToneMapper::new();
+        let res = mapper.map_image(&image, Val(2.0));
+        let expected = mapper.map(Spectrum::broadcast(Val(1.0)));
+        assert_eq!(res.get(0, 0).unwrap(), expected);
+    }
+}
@@ -0,0 +1,29 @@
+use std::fmt::Debug;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::image::core::Image;
+use crate::domain::math::numeric::Val;
+
+pub trait ToneMapper: Debug + Send + Sync {
+    fn map_channel(&self, value: Val) -> Val;
+
+    fn map(&self, value: Spectrum) -> Spectrum {
+        Spectrum::new(
+            self.map_channel(value.red()),
+            self.map_channel(value.green()),
+            self.map_channel(value.blue()),
+        )
+    }
+
+    fn map_image(&self, image: &Image, exposure: Val) -> Image {
+        let resolution = image.resolution().clone();
+        let mut res = Image::new(resolution.clone());
+        for row in 0..resolution.height() {
+            for column in 0..resolution.width() {
+                let value = image.get(row, column).unwrap() * exposure;
+                res.set(row, column, self.map(value));
+            }
+        }
+        res
+    }
+}
@@ -0,0 +1,9 @@
+mod aces;
+mod def;
+mod reinhard;
+
+pub use aces::AcesFilmicToneMapper;
+pub use def::ToneMapper;
+pub use reinhard::{
+    ExtendedReinhardToneMapper, ReinhardToneMapper, TryNewExtendedReinhardToneMapperError,
+};
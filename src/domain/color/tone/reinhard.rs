@@ -0,0 +1,73 @@
+use snafu::prelude::*;
+
+use crate::domain::math::numeric::Val;
+
+use super::ToneMapper;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReinhardToneMapper {}
+
+impl ReinhardToneMapper {
+    #[inline]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ToneMapper for ReinhardToneMapper {
+    #[inline]
+    fn map_channel(&self, value: Val) -> Val {
+        let value = value.max(Val(0.0));
+        value / (Val(1.0) + value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedReinhardToneMapper {
+    white_inv_squared: Val,
+}
+
+impl ExtendedReinhardToneMapper {
+    pub fn new(white: Val) -> Result<Self, TryNewExtendedReinhardToneMapperError> {
+        ensure!(white > Val(0.0), InvalidWhiteSnafu);
+        Ok(Self {
+            white_inv_squared: white.powi(2).recip(),
+        })
+    }
+}
+
+impl ToneMapper for ExtendedReinhardToneMapper {
+    #[inline]
+    fn map_channel(&self, value: Val) -> Val {
+        let value = value.max(Val(0.0));
+        let res = value * (Val(1.0) + value * self.white_inv_squared) / (Val(1.0) + value);
+        res.min(Val(1.0))
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewExtendedReinhardToneMapperError {
+    #[snafu(display("white point should be positive"))]
+    InvalidWhite,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reinhard_tone_mapper_map_channel_succeeds() {
+        let mapper = ReinhardToneMapper::new();
+        assert_eq!(mapper.map_channel(Val(0.0)), Val(0.0));
+        assert_eq!(mapper.map_channel(Val(1.0)), Val(0.5));
+        assert!(mapper.map_channel(Val(1e6)) < Val(1.0));
+    }
+
+    #[test]
+    fn extended_reinhard_tone_mapper_map_channel_succeeds_reaching_white() {
+        let mapper = ExtendedReinhardToneMapper::new(Val(4.0)).unwrap();
+        assert_eq!(mapper.map_channel(Val(4.0)), Val(1.0));
+        assert!(mapper.map_channel(Val(1.0)) < Val(1.0));
+    }
+}
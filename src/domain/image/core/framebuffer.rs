@@ -0,0 +1,131 @@
+use std::ops::{Index, IndexMut};
+
+use getset::Getters;
+
+use crate::domain::camera::Resolution;
+use crate::domain::color::core::Spectrum;
+
+use super::Image;
+
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct Framebuffer<T> {
+    #[getset(get = "pub")]
+    resolution: Resolution,
+    data: Vec<T>,
+}
+
+impl<T> Framebuffer<T>
+where
+    T: Clone,
+{
+    pub fn new(resolution: Resolution, value: T) -> Self {
+        let data = vec![value; resolution.height() * resolution.width()];
+        Self { resolution, data }
+    }
+}
+
+impl<T> Framebuffer<T> {
+    #[inline]
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        self.index(row, column).map(|i| &self.data[i])
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, row: usize, column: usize) -> Option<&mut T> {
+        self.index(row, column).map(|i| &mut self.data[i])
+    }
+
+    #[inline]
+    pub fn set(&mut self, row: usize, column: usize, value: T) -> bool {
+        if let Some(slot) = self.get_mut(row, column) {
+            *slot = value;
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    fn index(&self, row: usize, column: usize) -> Option<usize> {
+        let (height, width) = (self.resolution.height(), self.resolution.width());
+        (row < height && column < width).then_some(row * width + column)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Framebuffer<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        self.get(row, column)
+            .expect("row and column should not be out of bound")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Framebuffer<T> {
+    #[inline]
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(row, column)
+            .expect("row and column should not be out of bound")
+    }
+}
+
+impl From<&Image> for Framebuffer<Spectrum> {
+    fn from(image: &Image) -> Self {
+        let resolution = image.resolution().clone();
+        let (height, width) = (resolution.height(), resolution.width());
+        let data = (0..height)
+            .flat_map(|row| (0..width).map(move |column| (row, column)))
+            .map(|(row, column)| image.get(row, column).unwrap())
+            .collect();
+        Self { resolution, data }
+    }
+}
+
+impl From<&Framebuffer<Spectrum>> for Image {
+    fn from(framebuffer: &Framebuffer<Spectrum>) -> Self {
+        let resolution = framebuffer.resolution().clone();
+        let mut image = Image::new(resolution.clone());
+        for row in 0..resolution.height() {
+            for column in 0..resolution.width() {
+                image.set(row, column, framebuffer[(row, column)]);
+            }
+        }
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::numeric::Val;
+
+    use super::*;
+
+    #[test]
+    fn framebuffer_set_succeeds() {
+        let mut framebuffer = Framebuffer::new(Resolution::new(2, (3, 2)).unwrap(), 0);
+        assert!(framebuffer.set(1, 2, 5));
+        assert!(!framebuffer.set(2, 0, 5));
+        assert_eq!(framebuffer.get(1, 2), Some(&5));
+        assert_eq!(framebuffer.data()[5], 5);
+        assert_eq!(framebuffer.get(0, 3), None);
+    }
+
+    #[test]
+    fn framebuffer_from_image_succeeds_preserving_radiance() {
+        let mut image = Image::new(Resolution::new(2, (2, 1)).unwrap());
+        image.set(1, 3, Spectrum::broadcast(Val(7.5)));
+
+        let mut framebuffer = Framebuffer::from(&image);
+        assert_eq!(framebuffer[(1, 3)], Spectrum::broadcast(Val(7.5)));
+        framebuffer[(0, 0)] = Spectrum::broadcast(Val(2.0));
+
+        let image = Image::from(&framebuffer);
+        assert_eq!(image.get(0, 0), Some(Spectrum::broadcast(Val(2.0))));
+        assert_eq!(image.get(1, 3), Some(Spectrum::broadcast(Val(7.5))));
+    }
+}
@@ -1,15 +1,17 @@
-use getset::Getters;
+use getset::{CopyGetters, Getters};
 
 use crate::domain::camera::Resolution;
-use crate::domain::color::core::Spectrum;
+use crate::domain::color::core::{ColorSpace, Spectrum};
 use crate::domain::math::numeric::Val;
 
 use super::BlockedArray;
 
-#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
 pub struct Image {
     #[getset(get = "pub")]
     resolution: Resolution,
+    #[getset(get_copy = "pub")]
+    color_space: ColorSpace,
     data: BlockedArray<Spectrum>,
 }
 
@@ -22,7 +24,19 @@ impl Image {
             resolution.width(),
             Self::IMAGE_BLOCK_LOG2_SIZE,
         );
-        Self { resolution, data }
+        Self {
+            resolution,
+            color_space: ColorSpace::Linear,
+            data,
+        }
+    }
+
+    #[inline]
+    pub fn with_color_space(self, color_space: ColorSpace) -> Self {
+        Self {
+            color_space,
+            ..self
+        }
     }
 
     #[inline]
@@ -30,6 +44,11 @@ impl Image {
         self.data.get(row, column).cloned()
     }
 
+    #[inline]
+    pub fn get_linear(&self, row: usize, column: usize) -> Option<Spectrum> {
+        (self.get(row, column)).map(|color| self.color_space.to_linear(color))
+    }
+
     #[inline]
     pub fn get_mut(&mut self, row: usize, column: usize) -> Option<&mut Spectrum> {
         self.data.get_mut(row, column)
@@ -67,7 +86,16 @@ impl ImageAccumulator {
         self.image.get(row, column)
     }
 
+    #[inline]
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    /// Non-finite colors are dropped without being counted.
     pub fn record(&mut self, row: usize, column: usize, color: Spectrum) -> bool {
+        if !color.is_finite() {
+            return self.count.get(row, column).is_some();
+        }
         if let (Some(count), Some(entry)) = (
             self.count.get_mut(row, column),
             self.image.get_mut(row, column),
@@ -81,6 +109,15 @@ impl ImageAccumulator {
         }
     }
 
+    #[inline]
+    pub fn count(&self, row: usize, column: usize) -> Option<usize> {
+        self.count.get(row, column).copied()
+    }
+
+    pub fn restore(&mut self, row: usize, column: usize, color: Spectrum, count: usize) -> bool {
+        self.image.set(row, column, color) && self.count.set(row, column, count)
+    }
+
     #[inline]
     pub fn into_inner(self) -> Image {
         self.image
@@ -141,4 +178,20 @@ mod tests {
             Some(Spectrum::broadcast(Val((0.5 * 2.0 + 1.0) / 3.0)))
         );
     }
+
+    #[test]
+    fn image_accumulator_record_succeeds_dropping_non_finite_color() {
+        let res = Resolution::new(10, (10, 1)).unwrap();
+        let mut acc = ImageAccumulator::new(Image::new(res));
+
+        assert!(acc.record(5, 5, Spectrum::broadcast(Val(1.0))));
+        assert!(acc.record(5, 5, Spectrum::new(Val::INFINITY, Val(0.5), Val(0.5))));
+        assert!(acc.record(5, 5, Spectrum::broadcast(Val(0.0))));
+
+        let color = acc.get(5, 5).unwrap();
+        assert!(color.is_finite());
+        assert_eq!(color, Spectrum::broadcast(Val(0.5)));
+        assert_eq!(acc.count(5, 5), Some(2));
+        assert!(!acc.record(10, 0, Spectrum::broadcast(Val::INFINITY)));
+    }
 }
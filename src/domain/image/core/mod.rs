@@ -1,5 +1,7 @@
 mod array;
+mod framebuffer;
 mod image;
 
 pub use array::BlockedArray;
+pub use framebuffer::Framebuffer;
 pub use image::{Image, ImageAccumulator};
@@ -4,12 +4,17 @@ use std::path::PathBuf;
 
 use snafu::prelude::*;
 
-use crate::domain::image::core::Image;
+use crate::domain::color::core::Spectrum;
+use crate::domain::image::core::{Framebuffer, Image};
 
 pub trait ImageResource: Send + Sync {
     fn load(&self) -> Result<Image, LoadImageError>;
 
     fn save(&self, image: &Image) -> Result<(), SaveImageError>;
+
+    fn save_framebuffer(&self, framebuffer: &Framebuffer<Spectrum>) -> Result<(), SaveImageError> {
+        self.save(&Image::from(framebuffer))
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -0,0 +1,161 @@
+use getset::CopyGetters;
+use snafu::prelude::*;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::image::core::Framebuffer;
+use crate::domain::math::numeric::Val;
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct Bloom {
+    threshold: Val,
+    radius: Val,
+    intensity: Val,
+}
+
+impl Bloom {
+    const SCALES: [Val; 3] = [Val(1.0), Val(2.0), Val(4.0)];
+
+    pub fn new(threshold: Val, radius: Val, intensity: Val) -> Result<Self, TryNewBloomError> {
+        ensure!(threshold >= Val(0.0), InvalidThresholdSnafu);
+        ensure!(radius > Val(0.0), InvalidRadiusSnafu);
+        ensure!(intensity >= Val(0.0), InvalidIntensitySnafu);
+        Ok(Self {
+            threshold,
+            radius,
+            intensity,
+        })
+    }
+
+    pub fn apply(&self, framebuffer: &Framebuffer<Spectrum>) -> Framebuffer<Spectrum> {
+        let resolution = framebuffer.resolution().clone();
+        let mut bright = Framebuffer::new(resolution.clone(), Spectrum::zero());
+        for row in 0..resolution.height() {
+            for column in 0..resolution.width() {
+                bright[(row, column)] = self.extract(framebuffer[(row, column)]);
+            }
+        }
+
+        let weight = self.intensity / Val::from(Self::SCALES.len());
+        let mut res = framebuffer.clone();
+        for scale in Self::SCALES {
+            let blurred = Self::blur(&bright, self.radius * scale);
+            for row in 0..resolution.height() {
+                for column in 0..resolution.width() {
+                    res[(row, column)] += blurred[(row, column)] * weight;
+                }
+            }
+        }
+        res
+    }
+
+    fn extract(&self, value: Spectrum) -> Spectrum {
+        let peak = value.red().max(value.green()).max(value.blue());
+        if peak > self.threshold {
+            value * ((peak - self.threshold) / peak)
+        } else {
+            Spectrum::zero()
+        }
+    }
+
+    fn blur(framebuffer: &Framebuffer<Spectrum>, sigma: Val) -> Framebuffer<Spectrum> {
+        let kernel = Self::kernel(sigma);
+        let horizontal = Self::convolve(framebuffer, &kernel, (0, 1));
+        Self::convolve(&horizontal, &kernel, (1, 0))
+    }
+
+    fn kernel(sigma: Val) -> Vec<Val> {
+        let half = usize::from((Val(3.0) * sigma).ceil());
+        let weights = (0..=2 * half)
+            .map(|i| {
+                let x = Val::from(i) - Val::from(half);
+                (-x.powi(2) / (Val(2.0) * sigma.powi(2))).exp()
+            })
+            .collect::<Vec<_>>();
+        let sum = weights.iter().copied().sum::<Val>();
+        weights.into_iter().map(|w| w / sum).collect()
+    }
+
+    fn convolve(
+        framebuffer: &Framebuffer<Spectrum>,
+        kernel: &[Val],
+        step: (usize, usize),
+    ) -> Framebuffer<Spectrum> {
+        let resolution = framebuffer.resolution().clone();
+        let half = kernel.len() / 2;
+        let mut res = Framebuffer::new(resolution.clone(), Spectrum::zero());
+        for row in 0..resolution.height() {
+            for column in 0..resolution.width() {
+                let value = framebuffer[(row, column)];
+                if value == Spectrum::zero() {
+                    continue;
+                }
+                for (i, &weight) in kernel.iter().enumerate() {
+                    let (Some(r), Some(c)) = (
+                        (row + i * step.0).checked_sub(half * step.0),
+                        (column + i * step.1).checked_sub(half * step.1),
+                    ) else {
+                        continue;
+                    };
+                    if let Some(slot) = res.get_mut(r, c) {
+                        *slot += value * weight;
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewBloomError {
+    #[snafu(display("threshold should not be negative"))]
+    InvalidThreshold,
+    #[snafu(display("radius should be positive"))]
+    InvalidRadius,
+    #[snafu(display("intensity should not be negative"))]
+    InvalidIntensity,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::camera::Resolution;
+
+    use super::*;
+
+    fn total(framebuffer: &Framebuffer<Spectrum>) -> Val {
+        framebuffer.data().iter().map(|s| s.red()).sum()
+    }
+
+    #[test]
+    fn bloom_new_fails_when_radius_is_invalid() {
+        assert!(matches!(
+            Bloom::new(Val(1.0), Val(0.0), Val(0.5)),
+            Err(TryNewBloomError::InvalidRadius),
+        ));
+    }
+
+    #[test]
+    fn bloom_apply_succeeds_spreading_bright_pixel_to_neighbors() {
+        let mut framebuffer =
+            Framebuffer::new(Resolution::new(48, (1, 1)).unwrap(), Spectrum::zero());
+        framebuffer[(24, 24)] = Spectrum::broadcast(Val(11.0));
+        framebuffer[(4, 4)] = Spectrum::broadcast(Val(0.5));
+
+        let bloom = Bloom::new(Val(1.0), Val(1.0), Val(0.5)).unwrap();
+        let res = bloom.apply(&framebuffer);
+
+        assert!(res[(24, 25)].red() > Val(0.0));
+        assert!(res[(21, 24)].red() > Val(0.0));
+        assert!(res[(24, 24)].red() > Val(11.0));
+        assert_eq!(res[(4, 4)], Spectrum::broadcast(Val(0.5)));
+        assert_eq!(res[(4, 5)], Spectrum::zero());
+
+        let extracted = Val(10.0);
+        assert_eq!(
+            total(&res),
+            total(&framebuffer) + extracted * bloom.intensity()
+        );
+    }
+}
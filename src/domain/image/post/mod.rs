@@ -0,0 +1,3 @@
+mod bloom;
+
+pub use bloom::{Bloom, TryNewBloomError};
@@ -0,0 +1,11 @@
+use enum_dispatch::enum_dispatch;
+
+use crate::domain::light::primitive::*;
+
+#[enum_dispatch(Light)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynLight {
+    Directional(DirectionalLight),
+    Point(PointLight),
+    Spot(SpotLight),
+}
@@ -0,0 +1,26 @@
+use std::fmt::Debug;
+
+use enum_dispatch::enum_dispatch;
+
+use crate::domain::light::primitive::*;
+use crate::domain::sampling::light::LightSampling;
+use crate::domain::sampling::photon::PhotonSampling;
+use crate::domain::shape::def::BoundingBox;
+
+use super::DynLight;
+
+#[enum_dispatch]
+pub trait Light: Debug + Send + Sync {
+    fn kind(&self) -> LightKind;
+
+    fn get_light_sampler(&self) -> Box<dyn LightSampling>;
+
+    fn get_photon_sampler(&self, scene_bounds: &BoundingBox) -> Option<Box<dyn PhotonSampling>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LightKind {
+    Directional,
+    Point,
+    Spot,
+}
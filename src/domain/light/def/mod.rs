@@ -0,0 +1,5 @@
+mod dispatch;
+mod light;
+
+pub use dispatch::DynLight;
+pub use light::{Light, LightKind};
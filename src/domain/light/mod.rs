@@ -0,0 +1,2 @@
+pub mod def;
+pub mod primitive;
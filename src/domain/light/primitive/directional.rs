@@ -0,0 +1,41 @@
+use getset::CopyGetters;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::light::def::{Light, LightKind};
+use crate::domain::math::geometry::Direction;
+use crate::domain::sampling::light::{DirectionalLightSampler, LightSampling};
+use crate::domain::sampling::photon::{DirectionalPhotonSampler, PhotonSampling};
+use crate::domain::shape::def::BoundingBox;
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct DirectionalLight {
+    direction: Direction,
+    radiance: Spectrum,
+}
+
+impl DirectionalLight {
+    pub fn new(direction: Direction, radiance: Spectrum) -> Self {
+        Self {
+            direction,
+            radiance,
+        }
+    }
+}
+
+impl Light for DirectionalLight {
+    fn kind(&self) -> LightKind {
+        LightKind::Directional
+    }
+
+    fn get_light_sampler(&self) -> Box<dyn LightSampling> {
+        Box::new(DirectionalLightSampler::new(self.clone()))
+    }
+
+    fn get_photon_sampler(&self, scene_bounds: &BoundingBox) -> Option<Box<dyn PhotonSampling>> {
+        Some(Box::new(DirectionalPhotonSampler::new(
+            self.clone(),
+            scene_bounds,
+        )))
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use crate::domain::camera::Resolution;
+use crate::domain::color::core::Spectrum;
+use crate::domain::image::core::Image;
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::geometry::Direction;
+use crate::domain::math::numeric::Val;
+use crate::domain::texture::def::UvCoordinate;
+use crate::domain::texture::primitive::ImageMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvironmentLight {
+    map: ImageMap,
+}
+
+impl EnvironmentLight {
+    pub fn new<I>(image: I) -> Self
+    where
+        I: Into<Arc<Image>>,
+    {
+        Self {
+            map: ImageMap::new(image),
+        }
+    }
+
+    pub fn radiance(&self, direction: Direction) -> Spectrum {
+        self.map.lookup_uv(Self::direction_to_uv(direction))
+    }
+
+    pub fn resolution(&self) -> &Resolution {
+        self.map.resolution()
+    }
+
+    pub fn direction_to_uv(direction: Direction) -> UvCoordinate {
+        let theta = direction.y().clamp(Val(-1.0), Val(1.0)).acos();
+        let phi = direction.z().atan2(direction.x());
+        let u = (phi + Val::PI) / (Val(2.0) * Val::PI);
+        let v = Val(1.0) - theta / Val::PI;
+        UvCoordinate::clamp(u, v)
+    }
+
+    pub fn uv_to_direction(uv: UvCoordinate) -> Direction {
+        let theta = (Val(1.0) - uv.v()) * Val::PI;
+        let phi = uv.u() * Val(2.0) * Val::PI - Val::PI;
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let dir = Vector::new(sin_theta * cos_phi, cos_theta, sin_theta * sin_phi);
+        Direction::normalize(dir).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn environment_light_direction_to_uv_succeeds() {
+        let uv = EnvironmentLight::direction_to_uv(Direction::y_direction());
+        assert_eq!(uv.v(), Val(1.0));
+
+        let uv = EnvironmentLight::direction_to_uv(-Direction::y_direction());
+        assert_eq!(uv.v(), Val(0.0));
+
+        let uv = EnvironmentLight::direction_to_uv(Direction::x_direction());
+        assert_eq!(uv, UvCoordinate::new(Val(0.5), Val(0.5)).unwrap());
+
+        let uv = EnvironmentLight::direction_to_uv(-Direction::z_direction());
+        assert_eq!(uv, UvCoordinate::new(Val(0.25), Val(0.5)).unwrap());
+    }
+
+    #[test]
+    fn environment_light_uv_to_direction_succeeds_inverting_direction_to_uv() {
+        let dir = Direction::normalize(Vector::new(Val(1.0), Val(2.0), Val(-3.0))).unwrap();
+        let uv = EnvironmentLight::direction_to_uv(dir);
+        assert_eq!(EnvironmentLight::uv_to_direction(uv), dir);
+    }
+}
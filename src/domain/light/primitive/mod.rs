@@ -0,0 +1,11 @@
+mod directional;
+mod environment;
+mod point;
+mod portal;
+mod spot;
+
+pub use directional::DirectionalLight;
+pub use environment::EnvironmentLight;
+pub use point::PointLight;
+pub use portal::{Portal, TryNewPortalError};
+pub use spot::{SpotLight, TryNewSpotLightError};
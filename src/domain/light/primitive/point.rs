@@ -0,0 +1,38 @@
+use getset::CopyGetters;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::light::def::{Light, LightKind};
+use crate::domain::math::geometry::Point;
+use crate::domain::sampling::light::{LightSampling, PointLightSampler};
+use crate::domain::sampling::photon::{PhotonSampling, PointPhotonSampler};
+use crate::domain::shape::def::BoundingBox;
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct PointLight {
+    position: Point,
+    intensity: Spectrum,
+}
+
+impl PointLight {
+    pub fn new(position: Point, intensity: Spectrum) -> Self {
+        Self {
+            position,
+            intensity,
+        }
+    }
+}
+
+impl Light for PointLight {
+    fn kind(&self) -> LightKind {
+        LightKind::Point
+    }
+
+    fn get_light_sampler(&self) -> Box<dyn LightSampling> {
+        Box::new(PointLightSampler::new(self.clone()))
+    }
+
+    fn get_photon_sampler(&self, _scene_bounds: &BoundingBox) -> Option<Box<dyn PhotonSampling>> {
+        Some(Box::new(PointPhotonSampler::new(self.clone())))
+    }
+}
@@ -0,0 +1,48 @@
+use snafu::prelude::*;
+
+use crate::domain::shape::primitive::Polygon;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Portal {
+    polygon: Polygon,
+}
+
+impl Portal {
+    pub fn new(polygon: Polygon) -> Result<Self, TryNewPortalError> {
+        ensure!(polygon.to_rectangle().is_some(), NotRectangleSnafu);
+        Ok(Self { polygon })
+    }
+
+    pub fn polygon(&self) -> &Polygon {
+        &self.polygon
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewPortalError {
+    #[snafu(display("portal is not a rectangle"))]
+    NotRectangle,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::Point;
+    use crate::domain::math::numeric::Val;
+
+    use super::*;
+
+    #[test]
+    fn portal_new_fails_when_polygon_is_not_rectangle() {
+        let polygon = Polygon::new([
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Point::new(Val(1.0), Val(0.0), Val(0.0)),
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+        ])
+        .unwrap();
+        assert!(matches!(
+            Portal::new(polygon),
+            Err(TryNewPortalError::NotRectangle),
+        ));
+    }
+}
@@ -0,0 +1,114 @@
+use getset::CopyGetters;
+use snafu::prelude::*;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::light::def::{Light, LightKind};
+use crate::domain::math::algebra::Product;
+use crate::domain::math::geometry::{Direction, Point, SpreadAngle};
+use crate::domain::math::numeric::Val;
+use crate::domain::sampling::light::{LightSampling, SpotLightSampler};
+use crate::domain::sampling::photon::{PhotonSampling, SpotPhotonSampler};
+use crate::domain::shape::def::BoundingBox;
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct SpotLight {
+    position: Point,
+    direction: Direction,
+    inner: SpreadAngle,
+    outer: SpreadAngle,
+    intensity: Spectrum,
+}
+
+impl SpotLight {
+    pub fn new(
+        position: Point,
+        direction: Direction,
+        inner: SpreadAngle,
+        outer: SpreadAngle,
+        intensity: Spectrum,
+    ) -> Result<Self, TryNewSpotLightError> {
+        ensure!(inner.cos_half() >= outer.cos_half(), InnerExceedsOuterSnafu);
+        Ok(Self {
+            position,
+            direction,
+            inner,
+            outer,
+            intensity,
+        })
+    }
+
+    pub fn falloff(&self, direction: Direction) -> Val {
+        let cos = direction.dot(self.direction);
+        let (cos_inner, cos_outer) = (self.inner.cos_half(), self.outer.cos_half());
+        if cos <= cos_outer {
+            Val(0.0)
+        } else if cos >= cos_inner {
+            Val(1.0)
+        } else {
+            let t = (cos - cos_outer) / (cos_inner - cos_outer);
+            t * t * (Val(3.0) - Val(2.0) * t)
+        }
+    }
+}
+
+impl Light for SpotLight {
+    fn kind(&self) -> LightKind {
+        LightKind::Spot
+    }
+
+    fn get_light_sampler(&self) -> Box<dyn LightSampling> {
+        Box::new(SpotLightSampler::new(self.clone()))
+    }
+
+    fn get_photon_sampler(&self, _scene_bounds: &BoundingBox) -> Option<Box<dyn PhotonSampling>> {
+        Some(Box::new(SpotPhotonSampler::new(self.clone())))
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewSpotLightError {
+    #[snafu(display("inner spread angle is larger than the outer one"))]
+    InnerExceedsOuter,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::algebra::Vector;
+
+    use super::*;
+
+    #[test]
+    fn spot_light_new_fails_when_inner_exceeds_outer() {
+        assert!(matches!(
+            SpotLight::new(
+                Point::default(),
+                -Direction::y_direction(),
+                SpreadAngle::new(Val::PI / Val(2.0)).unwrap(),
+                SpreadAngle::new(Val::PI / Val(4.0)).unwrap(),
+                Spectrum::broadcast(Val(1.0)),
+            ),
+            Err(TryNewSpotLightError::InnerExceedsOuter),
+        ));
+    }
+
+    #[test]
+    fn spot_light_falloff_succeeds() {
+        let light = SpotLight::new(
+            Point::default(),
+            -Direction::y_direction(),
+            SpreadAngle::new(Val::PI / Val(4.0)).unwrap(),
+            SpreadAngle::new(Val::PI / Val(2.0)).unwrap(),
+            Spectrum::broadcast(Val(1.0)),
+        )
+        .unwrap();
+        assert_eq!(light.falloff(-Direction::y_direction()), Val(1.0));
+        assert_eq!(light.falloff(Direction::y_direction()), Val(0.0));
+
+        let (sin, cos) = (Val(3.0) * Val::PI / Val(16.0)).sin_cos();
+        let midway = Direction::normalize(Vector::new(sin, -cos, Val(0.0))).unwrap();
+        let falloff = light.falloff(midway);
+        assert!(Val(0.0) < falloff && falloff < Val(1.0));
+    }
+}
@@ -6,14 +6,36 @@ use rand::prelude::*;
 use crate::domain::color::core::Spectrum;
 use crate::domain::math::algebra::Product;
 use crate::domain::math::numeric::{DisRange, Val};
+use crate::domain::medium::def::Medium;
+use crate::domain::medium::util::AggregateMedium;
 use crate::domain::ray::Ray;
-use crate::domain::ray::event::RayIntersection;
+use crate::domain::ray::event::{RayIntersection, RaySegment};
 use crate::domain::ray::photon::{Photon, PhotonRay, SearchPolicy};
 use crate::domain::ray::util::VisibilityTester;
 use crate::domain::renderer::{Contribution, PhotonInfo, PmContext, PmState, RtContext, RtState};
+use crate::domain::sampling::light::{EnvironmentLightSampler, LightSample, LightSampling};
 
 use super::BsdfMaterial;
 
+const SAMPLE_ENVIRONMENT_PROB: Val = Val(0.5);
+
+fn calc_scene_lights_prob(context: &RtContext<'_>) -> Val {
+    if context.environment().is_some() {
+        Val(1.0) - SAMPLE_ENVIRONMENT_PROB
+    } else {
+        Val(1.0)
+    }
+}
+
+fn calc_balance_weight(context: &RtContext<'_>, pdf: Val, pdf_other: Val) -> Val {
+    let config = context.config();
+    if config.next_event_estimation() && config.multiple_importance_sampling() {
+        pdf / (pdf + pdf_other)
+    } else {
+        Val(1.0)
+    }
+}
+
 pub trait BsdfMaterialExt: BsdfMaterial {
     fn shade_light(
         &self,
@@ -22,12 +44,21 @@ pub trait BsdfMaterialExt: BsdfMaterial {
         intersection: &RayIntersection,
     ) -> Contribution {
         const SAMPLE_LIGHT_PROB: Val = Val(0.5);
-        if Val(context.rng().random()) <= SAMPLE_LIGHT_PROB {
+        let config = context.config();
+        let light_prob = match (
+            config.next_event_estimation(),
+            config.multiple_importance_sampling(),
+        ) {
+            (false, _) => Val(0.0),
+            (true, false) => Val(1.0),
+            (true, true) => SAMPLE_LIGHT_PROB,
+        };
+        if Val(context.rng().random()) < light_prob {
             let radiance = self.shade_light_using_light_sampling(context, ray, intersection);
-            radiance * SAMPLE_LIGHT_PROB.recip()
+            radiance * light_prob.recip()
         } else {
             let radiance = self.shade_light_using_bsdf_sampling(context, ray, intersection);
-            radiance * (Val(1.0) - SAMPLE_LIGHT_PROB).recip()
+            radiance * (Val(1.0) - light_prob).recip()
         }
     }
 
@@ -41,6 +72,17 @@ pub trait BsdfMaterialExt: BsdfMaterial {
         let scene = context.entity_scene();
         let lights = scene.get_lights();
 
+        if let Some(environment) = context.environment() {
+            if Val(context.rng().random()) <= SAMPLE_ENVIRONMENT_PROB {
+                return self.shade_light_using_environment_sampling(
+                    context,
+                    ray,
+                    intersection,
+                    environment,
+                );
+            }
+        }
+
         let res = lights.sample_light_surface(intersection, *context.rng());
         let Some(sample) = res else {
             return Contribution::new();
@@ -48,16 +90,25 @@ pub trait BsdfMaterialExt: BsdfMaterial {
         if sample.pdf() == Val(0.0) {
             return Contribution::new();
         }
+        if let Some(radiance) = sample.radiance() {
+            return self.shade_light_using_delta_light(
+                context,
+                ray,
+                intersection,
+                &sample,
+                radiance,
+            ) * calc_scene_lights_prob(context).recip();
+        }
 
         let ray_next = sample.ray_next();
         let vtester = VisibilityTester::new(scene, ray_next);
-        let Some(target) = vtester.test(sample.distance(), sample.shape_id()) else {
+        let Some(target) = vtester.test(sample.distance(), sample.shape_id().unwrap()) else {
             return Contribution::new();
         };
 
-        let pdf_light = sample.pdf();
+        let pdf_light = sample.pdf() * calc_scene_lights_prob(context);
         let pdf_bsdf = self.pdf_bsdf(ray, intersection, ray_next);
-        let weight = pdf_light / (pdf_light + pdf_bsdf);
+        let weight = calc_balance_weight(context, pdf_light, pdf_bsdf);
 
         let bsdf = self.bsdf(-ray.direction(), intersection, ray_next.direction());
         let cos = intersection.normal().dot(ray_next.direction());
@@ -68,6 +119,66 @@ pub trait BsdfMaterialExt: BsdfMaterial {
         weight * coefficient * radiance
     }
 
+    fn shade_light_using_environment_sampling(
+        &self,
+        context: &mut RtContext<'_>,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        environment: &EnvironmentLightSampler,
+    ) -> Contribution {
+        let renderer = context.renderer();
+
+        let res = environment.sample_light_surface(intersection, *context.rng());
+        let Some(sample) = res else {
+            return Contribution::new();
+        };
+
+        let ray_next = sample.ray_next();
+        let vtester = VisibilityTester::new(context.entity_scene(), ray_next);
+        if !vtester.test_unblocked(sample.distance()) {
+            return Contribution::new();
+        }
+
+        let pdf_light = sample.pdf() * SAMPLE_ENVIRONMENT_PROB;
+        let pdf_bsdf = self.pdf_bsdf(ray, intersection, ray_next);
+        let weight = calc_balance_weight(context, pdf_light, pdf_bsdf);
+
+        let bsdf = self.bsdf(-ray.direction(), intersection, ray_next.direction());
+        let cos = intersection.normal().dot(ray_next.direction());
+        let coefficient = bsdf * cos / pdf_light;
+
+        let state = RtState::new().with_skip_medium_inscattering(true);
+        let radiance = renderer.trace_to(context, state, ray_next, None);
+        weight * coefficient * radiance
+    }
+
+    fn shade_light_using_delta_light(
+        &self,
+        context: &mut RtContext<'_>,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        sample: &LightSample,
+        radiance: Spectrum,
+    ) -> Contribution {
+        let ray_next = sample.ray_next();
+        let vtester = VisibilityTester::new(context.entity_scene(), ray_next);
+        if !vtester.test_unblocked(sample.distance()) {
+            return Contribution::new();
+        }
+
+        let volume_scene = context.volume_scene();
+        let range = DisRange::positive().shrink_end(sample.distance());
+        let segments = volume_scene.find_segments(ray_next, range);
+        let aggregator = AggregateMedium::new(volume_scene, &segments);
+        let transmittance =
+            aggregator.transmittance(ray_next, &RaySegment::from(range), *context.rng());
+
+        let bsdf = self.bsdf(-ray.direction(), intersection, ray_next.direction());
+        let cos = intersection.normal().dot(ray_next.direction());
+        let coefficient = bsdf * cos / sample.pdf();
+        Contribution::from_light(transmittance * coefficient * radiance)
+    }
+
     fn shade_light_using_bsdf_sampling(
         &self,
         context: &mut RtContext<'_>,
@@ -85,17 +196,25 @@ pub trait BsdfMaterialExt: BsdfMaterial {
 
         let ray_next = sample.ray_next();
         let vtester = VisibilityTester::new(scene, ray_next);
-        let Some(target) = vtester.cast() else {
-            return Contribution::new();
+        let (target, pdf_light) = match (vtester.cast_or_escape(), context.environment()) {
+            ((Some(target), _), _) => {
+                let pdf = lights.pdf_light_surface(intersection, ray_next);
+                (Some(target), pdf * calc_scene_lights_prob(context))
+            }
+            ((None, true), Some(environment)) => {
+                let pdf = environment.pdf_light_surface(intersection, ray_next);
+                (None, pdf * SAMPLE_ENVIRONMENT_PROB)
+            }
+            _ => return Contribution::new(),
         };
 
         let pdf_bsdf = sample.pdf();
-        let pdf_light = lights.pdf_light_surface(intersection, ray_next);
-        let weight = pdf_bsdf / (pdf_light + pdf_bsdf);
+        let weight = calc_balance_weight(context, pdf_bsdf, pdf_light);
 
         let coefficient = sample.coefficient();
         let state = RtState::new().with_skip_medium_inscattering(true);
-        let radiance = renderer.trace_to(context, state, ray_next, target.as_some());
+        let target = target.as_ref().and_then(|target| target.as_some());
+        let radiance = renderer.trace_to(context, state, ray_next, target);
         weight * coefficient * radiance
     }
 
@@ -114,6 +233,7 @@ pub trait BsdfMaterialExt: BsdfMaterial {
         }
 
         let ray_next = sample.ray_next();
+        let state_next = state_next.scale_throughput(sample.coefficient());
         let radiance = renderer.trace(context, state_next, ray_next, DisRange::positive());
         sample.coefficient() * radiance
     }
@@ -211,7 +331,9 @@ impl FluxEstimation {
     where
         I: Iterator<Item = &'a Self>,
     {
-        let estimations = estimations.filter(|e| !e.is_empty()).collect::<Vec<_>>();
+        let estimations = (estimations)
+            .filter(|e| !e.is_empty() && e.is_finite())
+            .collect::<Vec<_>>();
         if estimations.is_empty() {
             return Self::empty();
         }
@@ -233,6 +355,10 @@ impl FluxEstimation {
         self.num == Val(0.0)
     }
 
+    pub fn is_finite(&self) -> bool {
+        self.flux.is_finite() && self.num.is_finite() && self.radius.is_finite()
+    }
+
     pub fn clamp_radius(&mut self, max_radius: Val) {
         if self.radius <= max_radius {
             return;
@@ -1,6 +1,6 @@
 use rand::prelude::*;
 
-use crate::domain::color::core::Spectrum;
+use crate::domain::color::core::{Albedo, Spectrum};
 use crate::domain::math::algebra::Product;
 use crate::domain::math::geometry::Direction;
 use crate::domain::math::numeric::Val;
@@ -74,6 +74,14 @@ where
         MaterialKind::Refractive
     }
 
+    fn uses_footprint(&self) -> bool {
+        self.inner.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.inner.albedo(intersection)
+    }
+
     fn shade(
         &self,
         context: &mut RtContext<'_>,
@@ -81,10 +89,11 @@ where
         ray: &Ray,
         intersection: &RayIntersection,
     ) -> Contribution {
+        let weight = self.diffusion.bssrdf_diffusion() / self.diffusion.pdf();
         let light = self.shade_light(context, ray, intersection);
-        let state_next = state.with_skip_emissive(true);
+        let state_next = state.with_skip_emissive(true).scale_throughput(weight);
         let scattering = self.shade_scattering(context, state_next, ray, intersection);
-        (light + scattering) * (self.diffusion.bssrdf_diffusion() / self.diffusion.pdf())
+        (light + scattering) * weight
     }
 
     fn receive(
@@ -1,5 +1,6 @@
 use enum_dispatch::enum_dispatch;
 
+use crate::domain::color::core::Albedo;
 use crate::domain::material::primitive::*;
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::RayIntersection;
@@ -12,13 +13,22 @@ macro_rules! impl_dispatch {
     ($type:tt, $self:ident.$method:ident($($arg:ident),*)) => {
         match $self {
             $type::Blurry(s) => s.$method($($arg),*),
+            $type::Coated(s) => s.$method($($arg),*),
+            $type::Conductor(s) => s.$method($($arg),*),
             $type::Diffuse(s) => s.$method($($arg),*),
+            $type::Dispersive(s) => s.$method($($arg),*),
             $type::Emissive(s) => s.$method($($arg),*),
             $type::Glossy(s) => s.$method($($arg),*),
+            $type::GlossyAnisotropic(s) => s.$method($($arg),*),
+            $type::Phong(s) => s.$method($($arg),*),
+            $type::Principled(s) => s.$method($($arg),*),
             $type::Refractive(s) => s.$method($($arg),*),
             $type::Scattering(s) => s.$method($($arg),*),
             $type::Specular(s) => s.$method($($arg),*),
+            $type::ThinFilm(s) => s.$method($($arg),*),
             $type::Mixed(s) => s.$method($($arg),*),
+            $type::NormalMapped(s) => s.$method($($arg),*),
+            $type::BumpMapped(s) => s.$method($($arg),*),
         }
     };
 }
@@ -37,13 +47,22 @@ macro_rules! impl_from_ref_for_variant {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DynMaterial {
     Blurry(Blurry),
+    Coated(Coated),
+    Conductor(Conductor),
     Diffuse(Diffuse),
+    Dispersive(Dispersive),
     Emissive(Emissive),
     Glossy(Glossy),
+    GlossyAnisotropic(GlossyAnisotropic),
+    Phong(Phong),
+    Principled(Principled),
     Refractive(Refractive),
     Scattering(Scattering),
     Specular(Specular),
+    ThinFilm(ThinFilm),
     Mixed(Mixed),
+    NormalMapped(NormalMapped),
+    BumpMapped(BumpMapped),
 }
 
 impl<'a> From<&'a DynMaterial> for RefDynMaterial<'a> {
@@ -55,13 +74,22 @@ impl<'a> From<&'a DynMaterial> for RefDynMaterial<'a> {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RefDynMaterial<'a> {
     Blurry(&'a Blurry),
+    Coated(&'a Coated),
+    Conductor(&'a Conductor),
     Diffuse(&'a Diffuse),
+    Dispersive(&'a Dispersive),
     Emissive(&'a Emissive),
     Glossy(&'a Glossy),
+    GlossyAnisotropic(&'a GlossyAnisotropic),
+    Phong(&'a Phong),
+    Principled(&'a Principled),
     Refractive(&'a Refractive),
     Scattering(&'a Scattering),
     Specular(&'a Specular),
+    ThinFilm(&'a ThinFilm),
     Mixed(&'a Mixed),
+    NormalMapped(&'a NormalMapped),
+    BumpMapped(&'a BumpMapped),
 }
 
 impl<'a> Material for RefDynMaterial<'a> {
@@ -69,6 +97,14 @@ impl<'a> Material for RefDynMaterial<'a> {
         impl_dispatch!(Self, self.kind())
     }
 
+    fn uses_footprint(&self) -> bool {
+        impl_dispatch!(Self, self.uses_footprint())
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        impl_dispatch!(Self, self.albedo(intersection))
+    }
+
     fn shade(
         &self,
         context: &mut RtContext<'_>,
@@ -91,10 +127,19 @@ impl<'a> Material for RefDynMaterial<'a> {
 }
 
 impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Blurry);
+impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Coated);
+impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Conductor);
 impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Diffuse);
+impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Dispersive);
 impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Emissive);
 impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Glossy);
+impl_from_ref_for_variant!('a, RefDynMaterial<'a>, GlossyAnisotropic);
+impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Phong);
+impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Principled);
 impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Refractive);
 impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Scattering);
 impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Specular);
+impl_from_ref_for_variant!('a, RefDynMaterial<'a>, ThinFilm);
 impl_from_ref_for_variant!('a, RefDynMaterial<'a>, Mixed);
+impl_from_ref_for_variant!('a, RefDynMaterial<'a>, NormalMapped);
+impl_from_ref_for_variant!('a, RefDynMaterial<'a>, BumpMapped);
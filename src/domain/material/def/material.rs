@@ -2,7 +2,7 @@ use std::fmt::Debug;
 
 use enum_dispatch::enum_dispatch;
 
-use crate::domain::color::core::Spectrum;
+use crate::domain::color::core::{Albedo, Spectrum};
 use crate::domain::material::primitive::*;
 use crate::domain::math::geometry::Direction;
 use crate::domain::ray::Ray;
@@ -17,6 +17,10 @@ use super::DynMaterial;
 pub trait Material: Debug + Send + Sync {
     fn kind(&self) -> MaterialKind;
 
+    fn uses_footprint(&self) -> bool;
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo;
+
     fn shade(
         &self,
         context: &mut RtContext<'_>,
@@ -50,26 +54,44 @@ pub trait BssrdfMaterial: Material + BssrdfSampling {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum MaterialKind {
     Blurry,
+    Coated,
+    Conductor,
     Diffuse,
+    Dispersive,
     Emissive,
     Glossy,
+    GlossyAnisotropic,
+    Phong,
+    Principled,
     Refractive,
     Scattering,
     Specular,
+    ThinFilm,
     Mixed,
+    NormalMapped,
+    BumpMapped,
 }
 
 impl MaterialKind {
     pub fn category(&self) -> MaterialCategory {
         match self {
             Self::Blurry => MaterialCategory::Microfacet,
+            Self::Coated => MaterialCategory::Microfacet,
+            Self::Conductor => MaterialCategory::Microfacet,
             Self::Diffuse => MaterialCategory::Diffuse,
+            Self::Dispersive => MaterialCategory::Specular,
             Self::Emissive => MaterialCategory::Emissive,
             Self::Glossy => MaterialCategory::Microfacet,
+            Self::GlossyAnisotropic => MaterialCategory::Microfacet,
+            Self::Phong => MaterialCategory::Microfacet,
+            Self::Principled => MaterialCategory::Microfacet,
             Self::Refractive => MaterialCategory::Specular,
             Self::Scattering => MaterialCategory::Scattering,
             Self::Specular => MaterialCategory::Specular,
+            Self::ThinFilm => MaterialCategory::Specular,
             Self::Mixed => MaterialCategory::Mixed,
+            Self::NormalMapped => MaterialCategory::Mixed,
+            Self::BumpMapped => MaterialCategory::Mixed,
         }
     }
 }
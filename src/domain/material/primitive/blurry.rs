@@ -1,7 +1,7 @@
 use rand::prelude::*;
 use snafu::prelude::*;
 
-use crate::domain::color::core::Spectrum;
+use crate::domain::color::core::{Albedo, Spectrum};
 use crate::domain::material::def::{BsdfMaterial, BsdfMaterialExt, Material, MaterialKind};
 use crate::domain::math::algebra::Product;
 use crate::domain::math::geometry::{Direction, Normal};
@@ -14,35 +14,34 @@ use crate::domain::renderer::{
     Contribution, PmContext, PmState, RtContext, RtState, StoragePolicy,
 };
 use crate::domain::sampling::coefficient::{BsdfSample, BsdfSampling};
-use crate::domain::texture::def::DynAlbedoTexture;
+use crate::domain::texture::def::{DynAlbedoTexture, DynScalarTexture};
 
-use super::MicrofacetMaterial;
+use super::{MicrofacetMaterial, roughness_to_alpha, validate_roughness};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Blurry {
     albedo: DynAlbedoTexture,
     refractive_index: Val,
-    alpha: Val,
+    roughness: DynScalarTexture,
 }
 
 impl Blurry {
-    pub fn new<T>(
+    pub fn new<T, R>(
         albedo: T,
         refractive_index: Val,
-        roughness: Val,
+        roughness: R,
     ) -> Result<Self, TryNewBlurryError>
     where
         T: Into<DynAlbedoTexture>,
+        R: Into<DynScalarTexture>,
     {
+        let roughness = roughness.into();
         ensure!(refractive_index > Val(0.0), InvalidRefractiveIndexSnafu);
-        ensure!(
-            Val(0.0) < roughness && roughness <= Val(1.0),
-            InvalidRoughnessSnafu,
-        );
+        ensure!(validate_roughness(&roughness), InvalidRoughnessSnafu);
         Ok(Self {
             albedo: albedo.into(),
             refractive_index,
-            alpha: roughness.powi(2),
+            roughness,
         })
     }
 
@@ -60,6 +59,14 @@ impl Material for Blurry {
         MaterialKind::Blurry
     }
 
+    fn uses_footprint(&self) -> bool {
+        self.albedo.uses_footprint() || self.roughness.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.albedo.lookup(intersection)
+    }
+
     fn shade(
         &self,
         context: &mut RtContext<'_>,
@@ -100,8 +107,8 @@ impl MicrofacetMaterial for Blurry {
         Spectrum::broadcast(r0)
     }
 
-    fn alpha(&self) -> Val {
-        self.alpha
+    fn alpha(&self, intersection: &RayIntersection) -> Val {
+        roughness_to_alpha(self.roughness.lookup(intersection))
     }
 }
 
@@ -124,8 +131,8 @@ impl BsdfMaterial for Blurry {
             let reflectance = self.calc_reflectance(dir_out.dot(mn), intersection);
             let reflectance = reflectance.channel(0).min(Val(1.0));
 
-            let ndf = self.calc_ndf(normal, mn);
-            let g2 = self.calc_g2(dir_out, dir_in, normal);
+            let ndf = self.calc_ndf(intersection, mn);
+            let g2 = self.calc_g2(dir_out, dir_in, intersection);
             let (cos, cos_next) = (dir_out.dot(normal), dir_in.dot(normal));
 
             let albedo = self.albedo.lookup(intersection);
@@ -138,8 +145,8 @@ impl BsdfMaterial for Blurry {
             let reflectance = self.calc_reflectance(dir_out.dot(mn), intersection);
             let transmittance = Val(1.0) - reflectance.channel(0).min(Val(1.0));
 
-            let ndf = self.calc_ndf(normal, mn);
-            let g2 = self.calc_g2(dir_out, dir_in, normal);
+            let ndf = self.calc_ndf(intersection, mn);
+            let g2 = self.calc_g2(dir_out, dir_in, intersection);
             let (cos, cos_next) = (dir_out.dot(normal), dir_in.dot(normal));
             let (cos_mn, cos_mn_next) = (dir_out.dot(mn), dir_in.dot(mn));
 
@@ -161,15 +168,15 @@ impl BsdfSampling for Blurry {
         let dir = -ray.direction();
         let normal = intersection.normal();
         let ri = self.calc_current_refractive_index(intersection.side());
-        let mn = self.generate_microfacet_normal(dir, normal, rng);
+        let mn = self.generate_microfacet_normal(dir, intersection, rng);
 
         let (ray_next, scatter_kind) =
             ray_util::fresnel_refract_microfacet(ray, intersection, mn, ri, rng);
         let reflectance = scatter_kind.reflectance();
         let dir_next = ray_next.direction();
 
-        let g2 = self.calc_g2(dir, dir_next, normal);
-        let g1 = self.calc_g1(dir, normal);
+        let g2 = self.calc_g2(dir, dir_next, intersection);
+        let g1 = self.calc_g1(dir, intersection);
         let coefficient = if scatter_kind.is_reflective() {
             let albedo = self.albedo.lookup(intersection);
             albedo * g2 / g1
@@ -180,7 +187,7 @@ impl BsdfSampling for Blurry {
             albedo * extra * g2 / g1
         };
 
-        let ndf = self.calc_ndf(normal, mn);
+        let ndf = self.calc_ndf(intersection, mn);
         let pdf_vndf = g1 * ndf * Val(0.25) / dir.dot(normal);
         let pdf = if scatter_kind.is_reflective() {
             reflectance * pdf_vndf
@@ -212,8 +219,8 @@ impl BsdfSampling for Blurry {
         let reflectance = self.calc_reflectance(dir.dot(normal), intersection);
         let reflectance = reflectance.channel(0).min(Val(1.0));
 
-        let g1 = self.calc_g1(dir, normal);
-        let ndf = self.calc_ndf(normal, mn);
+        let g1 = self.calc_g1(dir, intersection);
+        let ndf = self.calc_ndf(intersection, mn);
         let pdf_vndf = g1 * ndf * Val(0.25) / dir.dot(normal);
         if is_reflective {
             reflectance * pdf_vndf
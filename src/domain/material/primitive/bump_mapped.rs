@@ -0,0 +1,204 @@
+use snafu::prelude::*;
+
+use crate::domain::color::core::Albedo;
+use crate::domain::material::def::{DynMaterial, Material, MaterialKind};
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::geometry::Normal;
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RayIntersection;
+use crate::domain::ray::photon::PhotonRay;
+use crate::domain::renderer::{Contribution, PmContext, PmState, RtContext, RtState};
+use crate::domain::texture::def::{DynTexture, Texture, UvCoordinate};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BumpMapped {
+    inner: Box<DynMaterial>,
+    height_map: DynTexture,
+    scale: Val,
+}
+
+impl BumpMapped {
+    const DELTA: Val = Val(1e-3);
+
+    pub fn new<M, T>(inner: M, height_map: T, scale: Val) -> Result<Self, TryNewBumpMappedError>
+    where
+        M: Into<DynMaterial>,
+        T: Into<DynTexture>,
+    {
+        ensure!(scale > Val(0.0), InvalidScaleSnafu);
+        Ok(Self {
+            inner: Box::new(inner.into()),
+            height_map: height_map.into(),
+            scale,
+        })
+    }
+
+    pub fn perturb(&self, intersection: &RayIntersection) -> RayIntersection {
+        let frame = intersection.tangent_frame();
+        let height = self.height(intersection);
+
+        let shifted = |offset: Vector, du: Val, dv: Val| {
+            let position = intersection.position() + offset * Self::DELTA;
+            let shifted = intersection.clone().with_position(position);
+            match intersection.uv() {
+                Some(uv) => {
+                    let u = uv.u() + du * Self::DELTA;
+                    let v = uv.v() + dv * Self::DELTA;
+                    shifted.with_uv(UvCoordinate::unbounded(u, v))
+                }
+                None => shifted,
+            }
+        };
+        let tangent = Vector::from(frame.tangent());
+        let cross = Vector::from(frame.cross());
+        let height_u = self.height(&shifted(tangent, Val(1.0), Val(0.0)));
+        let height_v = self.height(&shifted(cross, Val(0.0), Val(1.0)));
+
+        let grad_u = (height_u - height) / Self::DELTA;
+        let grad_v = (height_v - height) / Self::DELTA;
+        let local = Vector::new(-self.scale * grad_u, -self.scale * grad_v, Val(1.0));
+        match Normal::normalize(frame.to_canonical(local)) {
+            Ok(normal) => intersection.clone().with_normal(normal),
+            Err(_) => intersection.clone(),
+        }
+    }
+
+    fn height(&self, intersection: &RayIntersection) -> Val {
+        let value = self.height_map.lookup(intersection);
+        (value.red() + value.green() + value.blue()) / Val(3.0)
+    }
+}
+
+impl Material for BumpMapped {
+    fn kind(&self) -> MaterialKind {
+        self.inner.kind()
+    }
+
+    fn uses_footprint(&self) -> bool {
+        self.inner.uses_footprint() || self.height_map.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.inner.albedo(intersection)
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        state: RtState,
+        ray: &Ray,
+        intersection: &RayIntersection,
+    ) -> Contribution {
+        let intersection = self.perturb(intersection);
+        self.inner.shade(context, state, ray, &intersection)
+    }
+
+    fn receive(
+        &self,
+        context: &mut PmContext<'_>,
+        state: PmState,
+        photon: &PhotonRay,
+        intersection: &RayIntersection,
+    ) {
+        let intersection = self.perturb(intersection);
+        self.inner.receive(context, state, photon, &intersection)
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewBumpMappedError {
+    #[snafu(display("scale of the bump map should be positive"))]
+    InvalidScale,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::camera::Resolution;
+    use crate::domain::color::core::{Albedo, Spectrum};
+    use crate::domain::image::core::Image;
+    use crate::domain::material::primitive::Diffuse;
+    use crate::domain::math::algebra::Product;
+    use crate::domain::math::geometry::{Distance, Point};
+    use crate::domain::ray::event::SurfaceSide;
+    use crate::domain::texture::def::WrapMode;
+    use crate::domain::texture::primitive::{Constant, ImageMap};
+
+    use super::*;
+
+    fn intersection() -> RayIntersection {
+        RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::new(Val(0.3), Val(0.7), Val(0.0)),
+            Normal::z_direction(),
+            SurfaceSide::Front,
+        )
+    }
+
+    fn height_map(size: usize, value: impl Fn(usize, usize) -> Val) -> ImageMap {
+        let mut image = Image::new(Resolution::new(size, (1, 1)).unwrap());
+        for r in 0..size {
+            for c in 0..size {
+                image.set(r, c, Spectrum::broadcast(value(r, c)));
+            }
+        }
+        ImageMap::new(image)
+    }
+
+    #[test]
+    fn bump_mapped_new_fails_when_scale_is_invalid() {
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.8)).unwrap());
+        let height = Constant::new(Spectrum::broadcast(Val(0.5)));
+        assert!(matches!(
+            BumpMapped::new(diffuse, height, Val(0.0)),
+            Err(TryNewBumpMappedError::InvalidScale),
+        ));
+    }
+
+    #[test]
+    fn bump_mapped_perturb_succeeds_keeping_normal_under_constant_height() {
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.8)).unwrap());
+        let height = Constant::new(Spectrum::broadcast(Val(0.5)));
+        let material = BumpMapped::new(diffuse, height, Val(10.0)).unwrap();
+
+        let perturbed = material.perturb(&intersection());
+        assert_eq!(perturbed.normal(), Normal::z_direction());
+
+        let uv = UvCoordinate::new(Val(0.5), Val(0.5)).unwrap();
+        let perturbed = material.perturb(&intersection().with_uv(uv));
+        assert_eq!(perturbed.normal(), Normal::z_direction());
+    }
+
+    #[test]
+    fn bump_mapped_perturb_succeeds_wrapping_offsets_past_texture_edge() {
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.8)).unwrap());
+        let height = height_map(4, |_, c| Val::from(c) / Val(3.0));
+        let repeated = height.clone().with_wrap(WrapMode::Repeat, WrapMode::Repeat);
+        let clamped = BumpMapped::new(diffuse.clone(), height, Val(1.0)).unwrap();
+        let repeated = BumpMapped::new(diffuse, repeated, Val(1.0)).unwrap();
+
+        let uv = UvCoordinate::new(Val(1.0), Val(0.5)).unwrap();
+        let intersection = intersection().with_uv(uv);
+        assert_eq!(
+            clamped.perturb(&intersection).normal(),
+            Normal::z_direction()
+        );
+        assert_ne!(
+            repeated.perturb(&intersection).normal(),
+            Normal::z_direction()
+        );
+    }
+
+    #[test]
+    fn bump_mapped_perturb_succeeds_sampling_offsets_with_uv_footprint() {
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.8)).unwrap());
+        let height = height_map(64, |r, c| Val::from((r + c) % 2));
+        let material = BumpMapped::new(diffuse, height, Val(1.0)).unwrap();
+
+        let uv = UvCoordinate::new(Val(0.3), Val(0.6)).unwrap();
+        let intersection = intersection().with_uv(uv).with_uv_footprint(Val(1.0));
+        let perturbed = material.perturb(&intersection);
+        assert!(perturbed.normal().dot(Normal::z_direction()) > Val(0.999));
+    }
+}
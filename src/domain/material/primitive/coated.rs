@@ -0,0 +1,325 @@
+use getset::{CopyGetters, Getters};
+use rand::prelude::*;
+use snafu::prelude::*;
+
+use crate::domain::color::core::{Albedo, Spectrum};
+use crate::domain::material::def::{BsdfMaterial, BsdfMaterialExt, Material, MaterialKind};
+use crate::domain::math::algebra::Product;
+use crate::domain::math::geometry::Direction;
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RayIntersection;
+use crate::domain::ray::photon::PhotonRay;
+use crate::domain::renderer::{
+    Contribution, PmContext, PmState, RtContext, RtState, StoragePolicy,
+};
+use crate::domain::sampling::coefficient::{BsdfSample, BsdfSampling};
+
+use super::{Conductor, Diffuse, Glossy, GlossyAnisotropic, MicrofacetMaterial, Phong};
+
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+pub struct Coated {
+    #[getset(get = "pub")]
+    base: CoatedBase,
+    #[getset(skip)]
+    coat: Glossy,
+    #[getset(get_copy = "pub")]
+    ior: Val,
+}
+
+impl Coated {
+    pub fn new<M>(base: M, ior: Val, roughness: Val) -> Result<Self, TryNewCoatedError>
+    where
+        M: Into<CoatedBase>,
+    {
+        ensure!(ior >= Val(1.0), InvalidRefractiveIndexSnafu);
+        ensure!(
+            Val(0.0) < roughness && roughness <= Val(1.0),
+            InvalidRoughnessSnafu
+        );
+
+        let r0 = ((ior - Val(1.0)) / (ior + Val(1.0))).powi(2);
+        let coat = Glossy::new(Albedo::broadcast(r0).unwrap(), Val(1.0), roughness).unwrap();
+        let base = base.into();
+        Ok(Self { base, coat, ior })
+    }
+
+    fn coat_reflectance(&self, dir: Direction, intersection: &RayIntersection) -> Val {
+        let cos = dir.dot(intersection.normal()).clamp(Val(0.0), Val(1.0));
+        self.coat.calc_reflectance(cos, intersection).red()
+    }
+
+    fn coat_transmittance(
+        &self,
+        dir_out: Direction,
+        intersection: &RayIntersection,
+        dir_in: Direction,
+    ) -> Val {
+        let entry = Val(1.0) - self.coat_reflectance(dir_in, intersection);
+        let exit = Val(1.0) - self.coat_reflectance(dir_out, intersection);
+        entry * exit
+    }
+}
+
+impl Material for Coated {
+    fn kind(&self) -> MaterialKind {
+        MaterialKind::Coated
+    }
+
+    fn uses_footprint(&self) -> bool {
+        self.base.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.base.albedo(intersection)
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        state: RtState,
+        ray: &Ray,
+        intersection: &RayIntersection,
+    ) -> Contribution {
+        let light = self.shade_light(context, ray, intersection);
+        let state_next = state.with_skip_emissive(true);
+        let scattering = self.shade_scattering(context, state_next, ray, intersection);
+        light + scattering
+    }
+
+    fn receive(
+        &self,
+        context: &mut PmContext<'_>,
+        state: PmState,
+        photon: &PhotonRay,
+        intersection: &RayIntersection,
+    ) {
+        match state.policy() {
+            StoragePolicy::Global => {
+                self.maybe_bounce_next_photon(context, state, photon, intersection);
+            }
+            StoragePolicy::Caustic => {}
+        }
+    }
+}
+
+impl BsdfMaterial for Coated {
+    fn bsdf(
+        &self,
+        dir_out: Direction,
+        intersection: &RayIntersection,
+        dir_in: Direction,
+    ) -> Spectrum {
+        let coat = self
+            .coat
+            .calc_reflective_bsdf(dir_out, intersection, dir_in);
+        let base = self.base.bsdf(dir_out, intersection, dir_in);
+        coat + base * self.coat_transmittance(dir_out, intersection, dir_in)
+    }
+}
+
+impl BsdfSampling for Coated {
+    fn sample_bsdf(
+        &self,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        rng: &mut dyn RngCore,
+    ) -> BsdfSample {
+        let dir_out = -ray.direction();
+        let select_coat_prob = self.coat_reflectance(dir_out, intersection);
+        let ray_next = if Val(rng.random()) < select_coat_prob {
+            self.coat.sample_reflective_bsdf(ray, intersection, rng)
+        } else {
+            self.base.sample_bsdf(ray, intersection, rng)
+        }
+        .into_ray_next();
+
+        let pdf = self.pdf_bsdf(ray, intersection, &ray_next);
+        let dir_in = ray_next.direction();
+        let cos = dir_in.dot(intersection.normal());
+        if pdf > Val(0.0) && cos > Val(0.0) {
+            let bsdf = self.bsdf(dir_out, intersection, dir_in);
+            BsdfSample::new(ray_next, bsdf * cos / pdf, pdf)
+        } else {
+            BsdfSample::new(ray_next, Spectrum::zero(), Val(0.0))
+        }
+    }
+
+    fn pdf_bsdf(&self, ray: &Ray, intersection: &RayIntersection, ray_next: &Ray) -> Val {
+        let select_coat_prob = self.coat_reflectance(-ray.direction(), intersection);
+        let coat = self.coat.pdf_reflective_bsdf(ray, intersection, ray_next);
+        let base = self.base.pdf_bsdf(ray, intersection, ray_next);
+        select_coat_prob * coat + (Val(1.0) - select_coat_prob) * base
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoatedBase {
+    Conductor(Conductor),
+    Diffuse(Diffuse),
+    Glossy(Glossy),
+    GlossyAnisotropic(GlossyAnisotropic),
+    Phong(Phong),
+}
+
+macro_rules! impl_base_dispatch {
+    ($self:ident.$method:ident($($arg:ident),*)) => {
+        match $self {
+            CoatedBase::Conductor(s) => s.$method($($arg),*),
+            CoatedBase::Diffuse(s) => s.$method($($arg),*),
+            CoatedBase::Glossy(s) => s.$method($($arg),*),
+            CoatedBase::GlossyAnisotropic(s) => s.$method($($arg),*),
+            CoatedBase::Phong(s) => s.$method($($arg),*),
+        }
+    };
+}
+
+macro_rules! impl_from_for_base {
+    ($variant:tt) => {
+        impl From<$variant> for CoatedBase {
+            fn from(value: $variant) -> Self {
+                Self::$variant(value)
+            }
+        }
+    };
+}
+
+impl_from_for_base!(Conductor);
+impl_from_for_base!(Diffuse);
+impl_from_for_base!(Glossy);
+impl_from_for_base!(GlossyAnisotropic);
+impl_from_for_base!(Phong);
+
+impl Material for CoatedBase {
+    fn kind(&self) -> MaterialKind {
+        impl_base_dispatch!(self.kind())
+    }
+
+    fn uses_footprint(&self) -> bool {
+        impl_base_dispatch!(self.uses_footprint())
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        impl_base_dispatch!(self.albedo(intersection))
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        state: RtState,
+        ray: &Ray,
+        intersection: &RayIntersection,
+    ) -> Contribution {
+        impl_base_dispatch!(self.shade(context, state, ray, intersection))
+    }
+
+    fn receive(
+        &self,
+        context: &mut PmContext<'_>,
+        state: PmState,
+        photon: &PhotonRay,
+        intersection: &RayIntersection,
+    ) {
+        impl_base_dispatch!(self.receive(context, state, photon, intersection))
+    }
+}
+
+impl BsdfMaterial for CoatedBase {
+    fn bsdf(
+        &self,
+        dir_out: Direction,
+        intersection: &RayIntersection,
+        dir_in: Direction,
+    ) -> Spectrum {
+        impl_base_dispatch!(self.bsdf(dir_out, intersection, dir_in))
+    }
+}
+
+impl BsdfSampling for CoatedBase {
+    fn sample_bsdf(
+        &self,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        rng: &mut dyn RngCore,
+    ) -> BsdfSample {
+        impl_base_dispatch!(self.sample_bsdf(ray, intersection, rng))
+    }
+
+    fn pdf_bsdf(&self, ray: &Ray, intersection: &RayIntersection, ray_next: &Ray) -> Val {
+        impl_base_dispatch!(self.pdf_bsdf(ray, intersection, ray_next))
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewCoatedError {
+    #[snafu(display("refractive index of the coat should not be less than 1"))]
+    InvalidRefractiveIndex,
+    #[snafu(display("roughness should be in (0, 1]"))]
+    InvalidRoughness,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::algebra::Vector;
+    use crate::domain::math::geometry::{Distance, Normal, Point};
+    use crate::domain::ray::event::SurfaceSide;
+
+    use super::*;
+
+    fn intersection() -> RayIntersection {
+        RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Normal::z_direction(),
+            SurfaceSide::Front,
+        )
+    }
+
+    #[test]
+    fn coated_new_fails_when_refractive_index_is_invalid() {
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.8)).unwrap());
+        assert!(matches!(
+            Coated::new(diffuse, Val(0.5), Val(0.1)),
+            Err(TryNewCoatedError::InvalidRefractiveIndex),
+        ));
+    }
+
+    #[test]
+    fn coated_bsdf_succeeds_adding_specular_highlight_to_diffuse() {
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap());
+        let coated = Coated::new(diffuse.clone(), Val(1.5), Val(0.1)).unwrap();
+        let intersection = intersection();
+
+        let dir_out = Direction::normalize(Vector::new(Val(1.0), Val(0.0), Val(1.0))).unwrap();
+        let mirror = Direction::normalize(Vector::new(Val(-1.0), Val(0.0), Val(1.0))).unwrap();
+        let highlight = coated.bsdf(dir_out, &intersection, mirror).red();
+        let plain = diffuse.bsdf(dir_out, &intersection, mirror).red();
+        assert!(highlight > plain * Val(10.0));
+
+        let off = Direction::normalize(Vector::new(Val(1.0), Val(0.0), Val(0.5))).unwrap();
+        let coated_off = coated.bsdf(dir_out, &intersection, off).red();
+        let plain_off = diffuse.bsdf(dir_out, &intersection, off).red();
+        assert!(coated_off < plain_off);
+    }
+
+    #[test]
+    fn coated_sample_bsdf_succeeds_matching_evaluated_bsdf() {
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap());
+        let coated = Coated::new(diffuse, Val(1.5), Val(0.3)).unwrap();
+        let intersection = intersection();
+        let dir = Direction::normalize(Vector::new(Val(-1.0), Val(0.0), Val(-1.0))).unwrap();
+        let ray = Ray::new(Point::new(Val(1.0), Val(0.0), Val(1.0)), dir);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..64 {
+            let sample = coated.sample_bsdf(&ray, &intersection, &mut rng);
+            if sample.pdf() == Val(0.0) {
+                continue;
+            }
+            let pdf = coated.pdf_bsdf(&ray, &intersection, sample.ray_next());
+            assert_eq!(sample.pdf(), pdf);
+            assert!(sample.coefficient().red() >= Val(0.0));
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use rand::prelude::*;
+use snafu::prelude::*;
+
+use crate::domain::color::core::{Albedo, Spectrum};
+use crate::domain::material::def::{BsdfMaterial, BsdfMaterialExt, Material, MaterialKind};
+use crate::domain::math::geometry::Direction;
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RayIntersection;
+use crate::domain::ray::photon::PhotonRay;
+use crate::domain::renderer::{
+    Contribution, PmContext, PmState, RtContext, RtState, StoragePolicy,
+};
+use crate::domain::sampling::coefficient::{BsdfSample, BsdfSampling};
+use crate::domain::texture::def::DynScalarTexture;
+
+use super::{GlossyPredefinition, MicrofacetMaterial, roughness_to_alpha, validate_roughness};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conductor {
+    n: Spectrum,
+    k: Spectrum,
+    roughness: DynScalarTexture,
+}
+
+impl Conductor {
+    pub fn new<R>(n: Spectrum, k: Spectrum, roughness: R) -> Result<Self, TryNewConductorError>
+    where
+        R: Into<DynScalarTexture>,
+    {
+        let roughness = roughness.into();
+        ensure!(
+            n.red() > Val(0.0) && n.green() > Val(0.0) && n.blue() > Val(0.0),
+            InvalidRefractiveIndexSnafu
+        );
+        ensure!(
+            k.red() >= Val(0.0) && k.green() >= Val(0.0) && k.blue() >= Val(0.0),
+            InvalidExtinctionCoefficientSnafu
+        );
+        ensure!(validate_roughness(&roughness), InvalidRoughnessSnafu);
+
+        Ok(Self { n, k, roughness })
+    }
+
+    pub fn lookup<R>(
+        predefinition: GlossyPredefinition,
+        roughness: R,
+    ) -> Result<Self, TryNewConductorError>
+    where
+        R: Into<DynScalarTexture>,
+    {
+        let (n, k) = predefinition.complex_refractive_index();
+        Self::new(n, k, roughness)
+    }
+
+    pub fn fresnel(&self, cos: Val) -> Spectrum {
+        let channel = |n: Val, k: Val| {
+            let cos2 = cos.clamp(Val(0.0), Val(1.0)).powi(2);
+            let sin2 = Val(1.0) - cos2;
+            let t0 = n * n - k * k - sin2;
+            let a2b2 = (t0 * t0 + Val(4.0) * n * n * k * k).sqrt();
+            let a = (Val(0.5) * (a2b2 + t0)).max(Val(0.0)).sqrt();
+
+            let t1 = a2b2 + cos2;
+            let t2 = Val(2.0) * a * cos2.sqrt();
+            let rs = (t1 - t2) / (t1 + t2);
+
+            let t3 = cos2 * a2b2 + sin2 * sin2;
+            let t4 = t2 * sin2;
+            let rp = rs * (t3 - t4) / (t3 + t4);
+            Val(0.5) * (rs + rp)
+        };
+        Spectrum::new(
+            channel(self.n.red(), self.k.red()),
+            channel(self.n.green(), self.k.green()),
+            channel(self.n.blue(), self.k.blue()),
+        )
+    }
+}
+
+impl Material for Conductor {
+    fn kind(&self) -> MaterialKind {
+        MaterialKind::Conductor
+    }
+
+    fn uses_footprint(&self) -> bool {
+        self.roughness.uses_footprint()
+    }
+
+    fn albedo(&self, _intersection: &RayIntersection) -> Albedo {
+        Albedo::clamp(self.fresnel(Val(1.0)))
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        state: RtState,
+        ray: &Ray,
+        intersection: &RayIntersection,
+    ) -> Contribution {
+        let light = self.shade_light(context, ray, intersection);
+        let state_next = state.with_skip_emissive(true);
+        let scattering = self.shade_scattering(context, state_next, ray, intersection);
+        light + scattering
+    }
+
+    fn receive(
+        &self,
+        context: &mut PmContext<'_>,
+        state: PmState,
+        photon: &PhotonRay,
+        intersection: &RayIntersection,
+    ) {
+        match state.policy() {
+            StoragePolicy::Global => {
+                self.maybe_bounce_next_photon(context, state, photon, intersection);
+            }
+            StoragePolicy::Caustic => {}
+        }
+    }
+}
+
+impl MicrofacetMaterial for Conductor {
+    #[inline]
+    fn r0(&self, _intersection: &RayIntersection) -> Spectrum {
+        self.fresnel(Val(1.0))
+    }
+
+    #[inline]
+    fn alpha(&self, intersection: &RayIntersection) -> Val {
+        roughness_to_alpha(self.roughness.lookup(intersection))
+    }
+
+    #[inline]
+    fn calc_reflectance(&self, cos: Val, _intersection: &RayIntersection) -> Spectrum {
+        self.fresnel(cos)
+    }
+}
+
+impl BsdfMaterial for Conductor {
+    fn bsdf(
+        &self,
+        dir_out: Direction,
+        intersection: &RayIntersection,
+        dir_in: Direction,
+    ) -> Spectrum {
+        self.calc_reflective_bsdf(dir_out, intersection, dir_in)
+    }
+}
+
+impl BsdfSampling for Conductor {
+    fn sample_bsdf(
+        &self,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        rng: &mut dyn RngCore,
+    ) -> BsdfSample {
+        self.sample_reflective_bsdf(ray, intersection, rng)
+    }
+
+    fn pdf_bsdf(&self, ray: &Ray, intersection: &RayIntersection, ray_next: &Ray) -> Val {
+        self.pdf_reflective_bsdf(ray, intersection, ray_next)
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewConductorError {
+    #[snafu(display("refractive index should be positive"))]
+    InvalidRefractiveIndex,
+    #[snafu(display("extinction coefficient should not be negative"))]
+    InvalidExtinctionCoefficient,
+    #[snafu(display("roughness should be in (0, 1]"))]
+    InvalidRoughness,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conductor_fresnel_succeeds_matching_predefined_reflectance_at_normal_incidence() {
+        for predefinition in [
+            GlossyPredefinition::Copper,
+            GlossyPredefinition::Gold,
+            GlossyPredefinition::Silver,
+        ] {
+            let conductor = Conductor::lookup(predefinition, Val(0.5)).unwrap();
+            let expected = Spectrum::from(predefinition.albedo());
+            let actual = conductor.fresnel(Val(1.0));
+            assert!((actual.red() - expected.red()).abs() < Val(2e-3));
+            assert!((actual.green() - expected.green()).abs() < Val(2e-3));
+            assert!((actual.blue() - expected.blue()).abs() < Val(2e-3));
+        }
+    }
+
+    #[test]
+    fn conductor_fresnel_succeeds_approaching_one_at_grazing_angle() {
+        let conductor = Conductor::lookup(GlossyPredefinition::Copper, Val(0.5)).unwrap();
+        let reflectance = conductor.fresnel(Val(1e-4));
+        assert!(reflectance.red() > Val(0.99));
+        assert!(reflectance.green() > Val(0.99));
+        assert!(reflectance.blue() > Val(0.99));
+    }
+}
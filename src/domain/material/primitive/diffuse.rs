@@ -1,6 +1,6 @@
 use rand::prelude::*;
 
-use crate::domain::color::core::Spectrum;
+use crate::domain::color::core::{Albedo, Spectrum};
 use crate::domain::material::def::{BsdfMaterial, BsdfMaterialExt, Material, MaterialKind};
 use crate::domain::math::algebra::Product;
 use crate::domain::math::geometry::Direction;
@@ -9,7 +9,7 @@ use crate::domain::ray::Ray;
 use crate::domain::ray::event::RayIntersection;
 use crate::domain::ray::photon::PhotonRay;
 use crate::domain::renderer::{
-    Contribution, PmContext, PmState, RtContext, RtState, StoragePolicy,
+    Contribution, Integrator, PmContext, PmState, RtContext, RtState, StoragePolicy,
 };
 use crate::domain::sampling::coefficient::{BsdfSample, BsdfSampling};
 use crate::domain::texture::def::DynAlbedoTexture;
@@ -35,6 +35,14 @@ impl Material for Diffuse {
         MaterialKind::Diffuse
     }
 
+    fn uses_footprint(&self) -> bool {
+        self.albedo.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.albedo.lookup(intersection)
+    }
+
     fn shade(
         &self,
         context: &mut RtContext<'_>,
@@ -42,7 +50,12 @@ impl Material for Diffuse {
         ray: &Ray,
         intersection: &RayIntersection,
     ) -> Contribution {
-        if state.visible() {
+        if context.config().integrator() == Integrator::PathTracing {
+            let light = self.shade_light(context, ray, intersection);
+            let state_next = state.with_skip_emissive(true);
+            let scattering = self.shade_scattering(context, state_next, ray, intersection);
+            light + scattering
+        } else if state.visible() {
             let light = self.shade_light(context, ray, intersection);
             let caustic = self.estimate_flux(ray, intersection, context.photon_casutic());
             let scattering = self.shade_scattering(
@@ -0,0 +1,199 @@
+use rand::prelude::*;
+use snafu::prelude::*;
+
+use crate::domain::color::core::{Albedo, SpectralChannel, Spectrum};
+use crate::domain::material::def::{Material, MaterialKind};
+use crate::domain::math::numeric::{DisRange, Val};
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, SurfaceSide};
+use crate::domain::ray::photon::PhotonRay;
+use crate::domain::ray::util as ray_util;
+use crate::domain::renderer::{Contribution, PmContext, PmState, RtContext, RtState};
+use crate::domain::texture::def::DynAlbedoTexture;
+
+/// The first dispersive interface picks one channel whose wavelength the rest of the path uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dispersive {
+    albedo: DynAlbedoTexture,
+    cauchy_a: Val,
+    cauchy_b: Val,
+}
+
+impl Dispersive {
+    pub fn new<T>(albedo: T, cauchy_a: Val, cauchy_b: Val) -> Result<Self, TryNewDispersiveError>
+    where
+        T: Into<DynAlbedoTexture>,
+    {
+        ensure!(cauchy_a > Val(0.0), InvalidCauchyASnafu);
+        ensure!(cauchy_b >= Val(0.0), InvalidCauchyBSnafu);
+
+        Ok(Self {
+            albedo: albedo.into(),
+            cauchy_a,
+            cauchy_b,
+        })
+    }
+
+    pub fn refractive_index(&self, channel: Option<SpectralChannel>) -> Val {
+        match channel {
+            Some(channel) => {
+                let wavelength = channel.wavelength() / Val(1000.0);
+                self.cauchy_a + self.cauchy_b / (wavelength * wavelength)
+            }
+            None => self.cauchy_a,
+        }
+    }
+
+    fn select_channel(
+        &self,
+        current: Option<SpectralChannel>,
+        rng: &mut dyn RngCore,
+    ) -> (Option<SpectralChannel>, Spectrum) {
+        if self.cauchy_b == Val(0.0) || current.is_some() {
+            return (current, Spectrum::broadcast(Val(1.0)));
+        }
+        let channel = SpectralChannel::sample(rng);
+        let weight = channel.mask() * Val::from(SpectralChannel::ALL.len());
+        (Some(channel), weight)
+    }
+
+    fn scatter(
+        &self,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        channel: Option<SpectralChannel>,
+        rng: &mut dyn RngCore,
+    ) -> Ray {
+        let ri = self.refractive_index(channel);
+        let ri = if intersection.side() == SurfaceSide::Front {
+            ri
+        } else {
+            ri.recip()
+        };
+        ray_util::fresnel_refract(ray, intersection, ri, rng).0
+    }
+}
+
+impl Material for Dispersive {
+    fn kind(&self) -> MaterialKind {
+        MaterialKind::Dispersive
+    }
+
+    fn uses_footprint(&self) -> bool {
+        self.albedo.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.albedo.lookup(intersection)
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        state: RtState,
+        ray: &Ray,
+        intersection: &RayIntersection,
+    ) -> Contribution {
+        let renderer = context.renderer();
+        let (channel, weight) = self.select_channel(state.channel(), *context.rng());
+        let ray_next = self.scatter(ray, intersection, channel, *context.rng());
+
+        let coefficient = weight * Spectrum::from(self.albedo.lookup(intersection));
+        let state_next = (state.with_skip_emissive(false))
+            .with_channel(channel)
+            .scale_throughput(coefficient);
+        let radiance = renderer.trace(context, state_next, &ray_next, DisRange::positive());
+        coefficient * radiance
+    }
+
+    fn receive(
+        &self,
+        context: &mut PmContext<'_>,
+        state: PmState,
+        photon: &PhotonRay,
+        intersection: &RayIntersection,
+    ) {
+        let renderer = context.renderer();
+        let (channel, weight) = self.select_channel(state.channel(), *context.rng());
+
+        let coefficient = weight * Spectrum::from(self.albedo.lookup(intersection));
+        let mut throughput = coefficient * photon.throughput();
+        let continue_prob = (throughput.red())
+            .max(throughput.green())
+            .max(throughput.blue())
+            .clamp(Val(0.0), Val(0.9));
+        if Val(context.rng().random()) < continue_prob {
+            throughput /= continue_prob;
+        } else {
+            return;
+        }
+
+        let ray_next = self.scatter(photon.ray(), intersection, channel, *context.rng());
+        let photon_next = PhotonRay::new(ray_next, throughput);
+        let state_next = state.with_has_specular(true).with_channel(channel);
+        renderer.emit(context, state_next, &photon_next, DisRange::positive());
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewDispersiveError {
+    #[snafu(display("Cauchy coefficient `a` is not positive"))]
+    InvalidCauchyA,
+    #[snafu(display("Cauchy coefficient `b` is negative"))]
+    InvalidCauchyB,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::algebra::{Product, Vector};
+    use crate::domain::math::geometry::{Direction, Distance, Normal, Point};
+
+    use super::*;
+
+    fn prism_face() -> (Ray, RayIntersection) {
+        let ray = Ray::new(
+            Point::new(Val(-1.0), Val(1.0), Val(0.0)),
+            Direction::normalize(Vector::new(Val(1.0), Val(-1.0), Val(0.0))).unwrap(),
+        );
+        let intersection = RayIntersection::new(
+            Distance::new(Val(2.0).sqrt()).unwrap(),
+            Point::default(),
+            Normal::y_direction(),
+            SurfaceSide::Front,
+        );
+        (ray, intersection)
+    }
+
+    #[test]
+    fn dispersive_new_fails_when_cauchy_b_is_invalid() {
+        assert!(matches!(
+            Dispersive::new(Albedo::WHITE, Val(1.5), Val(-0.01)),
+            Err(TryNewDispersiveError::InvalidCauchyB),
+        ));
+    }
+
+    #[test]
+    fn dispersive_refractive_index_succeeds_bending_blue_more_than_red() {
+        let material = Dispersive::new(Albedo::WHITE, Val(1.5046), Val(0.0042)).unwrap();
+        let (ray, intersection) = prism_face();
+
+        let bend = |channel: SpectralChannel| {
+            let ri = material.refractive_index(Some(channel));
+            let ray_next = ray_util::pure_refract(&ray, &intersection, ri).unwrap();
+            ray_next.direction().dot(-Normal::y_direction())
+        };
+        assert!(bend(SpectralChannel::Blue) > bend(SpectralChannel::Red));
+    }
+
+    #[test]
+    fn dispersive_refractive_index_succeeds_degrading_without_dispersion() {
+        let material = Dispersive::new(Albedo::WHITE, Val(1.5), Val(0.0)).unwrap();
+        for channel in SpectralChannel::ALL {
+            assert_eq!(material.refractive_index(Some(channel)), Val(1.5));
+        }
+        let (channel, weight) = material.select_channel(None, &mut rand::rng());
+        assert_eq!(channel, None);
+        assert_eq!(weight, Spectrum::broadcast(Val(1.0)));
+    }
+}
@@ -1,20 +1,31 @@
+use std::sync::Arc;
+
 use getset::CopyGetters;
+use snafu::prelude::*;
 
-use crate::domain::color::core::Spectrum;
+use crate::domain::color::core::{Albedo, Spectrum};
 use crate::domain::material::def::{Material, MaterialKind};
+use crate::domain::material::util::IesProfile;
 use crate::domain::math::algebra::Product;
-use crate::domain::math::geometry::SpreadAngle;
+use crate::domain::math::geometry::{Direction, SpreadAngle};
+use crate::domain::math::numeric::Val;
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::{RayIntersection, SurfaceSide};
 use crate::domain::ray::photon::PhotonRay;
 use crate::domain::renderer::{Contribution, PmContext, PmState, RtContext, RtState};
-use crate::domain::texture::def::{DynTexture, Texture};
+use crate::domain::texture::def::{DynTexture, Texture, TextureKind};
 
 #[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
 pub struct Emissive {
     radiance: DynTexture,
     #[getset(get_copy = "pub")]
     beam_angle: SpreadAngle,
+    #[getset(get_copy = "pub")]
+    cosine_power: Val,
+    #[getset(skip)]
+    profile: Option<Arc<IesProfile>>,
+    #[getset(get_copy = "pub")]
+    two_sided: bool,
 }
 
 impl Emissive {
@@ -25,13 +36,72 @@ impl Emissive {
         Self {
             radiance: radiance.into(),
             beam_angle,
+            cosine_power: Val(1.0),
+            profile: None,
+            two_sided: false,
+        }
+    }
+
+    pub fn with_two_sided(self, two_sided: bool) -> Self {
+        Self { two_sided, ..self }
+    }
+
+    pub fn with_cosine_power(self, exponent: Val) -> Result<Self, TryNewEmissiveError> {
+        ensure!(exponent >= Val(1.0), InvalidCosinePowerSnafu);
+        Ok(Self {
+            cosine_power: exponent,
+            ..self
+        })
+    }
+
+    pub fn with_profile(self, profile: IesProfile) -> Self {
+        Self {
+            profile: Some(Arc::new(profile)),
+            ..self
         }
     }
 
+    pub fn profile(&self) -> Option<&IesProfile> {
+        self.profile.as_deref()
+    }
+
+    pub fn is_textured(&self) -> bool {
+        self.radiance.kind() != TextureKind::Constant
+    }
+
+    pub fn lambertian_exitance(&self) -> Option<Spectrum> {
+        let DynTexture::Constant(radiance) = &self.radiance else {
+            return None;
+        };
+        if self.cosine_power != Val(1.0)
+            || !self.beam_angle.is_hemisphere()
+            || self.profile.is_some()
+        {
+            return None;
+        }
+        let sides = if self.two_sided { Val(2.0) } else { Val(1.0) };
+        Some(radiance.value() * (Val::PI * sides))
+    }
+
     #[inline]
     pub fn radiance(&self, intersection: &RayIntersection) -> Spectrum {
         self.radiance.lookup(intersection)
     }
+
+    pub fn emission(&self, intersection: &RayIntersection, dir: Direction) -> Spectrum {
+        let cos = intersection.normal().dot(dir);
+        if !self.beam_angle.is_hemisphere() && cos < self.beam_angle.cos_half() {
+            return Spectrum::zero();
+        }
+        let mut radiance = self.radiance.lookup(intersection);
+        if self.cosine_power != Val(1.0) {
+            radiance *= cos.max(Val(0.0)).powf(self.cosine_power - Val(1.0));
+        }
+        match &self.profile {
+            Some(profile) => radiance * profile.lookup(&intersection.tangent_frame(), dir.into()),
+            None => radiance,
+        }
+    }
 }
 
 impl Material for Emissive {
@@ -39,6 +109,14 @@ impl Material for Emissive {
         MaterialKind::Emissive
     }
 
+    fn uses_footprint(&self) -> bool {
+        self.radiance.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        Albedo::clamp(self.radiance.lookup(intersection))
+    }
+
     fn shade(
         &self,
         _context: &mut RtContext<'_>,
@@ -46,17 +124,10 @@ impl Material for Emissive {
         ray: &Ray,
         intersection: &RayIntersection,
     ) -> Contribution {
-        if state.skip_emissive() || intersection.side() == SurfaceSide::Back {
+        if state.skip_emissive() || (intersection.side() == SurfaceSide::Back && !self.two_sided) {
             Contribution::new()
-        } else if self.beam_angle.is_hemisphere() {
-            Contribution::from_light(self.radiance.lookup(intersection))
         } else {
-            let cos = intersection.normal().dot(-ray.direction());
-            if cos >= self.beam_angle.cos_half() {
-                Contribution::from_light(self.radiance.lookup(intersection))
-            } else {
-                Contribution::new()
-            }
+            Contribution::from_light(self.emission(intersection, -ray.direction()))
         }
     }
 
@@ -69,3 +140,82 @@ impl Material for Emissive {
     ) {
     }
 }
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewEmissiveError {
+    #[snafu(display("cosine power is less than one"))]
+    InvalidCosinePower,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::algebra::Vector;
+    use crate::domain::math::geometry::{Distance, Normal, Point};
+
+    use super::*;
+
+    fn intersection() -> RayIntersection {
+        RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Normal::z_direction(),
+            SurfaceSide::Front,
+        )
+    }
+
+    #[test]
+    fn emissive_emission_succeeds_following_ies_profile() {
+        let profile = IesProfile::new(
+            vec![Val(0.0), Val(30.0), Val(40.0), Val(90.0)],
+            vec![Val(0.0)],
+            vec![Val(100.0), Val(100.0), Val(0.0), Val(0.0)],
+        )
+        .unwrap();
+        let emissive = Emissive::new(Spectrum::broadcast(Val(2.0)), SpreadAngle::hemisphere())
+            .with_profile(profile);
+        let intersection = intersection();
+
+        let axis = Direction::z_direction();
+        assert_eq!(emissive.emission(&intersection, axis).red(), Val(2.0));
+
+        let dark = Direction::normalize(Vector::new(Val(1.0), Val(0.0), Val(1.0))).unwrap();
+        assert_eq!(emissive.emission(&intersection, dark), Spectrum::zero());
+    }
+
+    #[test]
+    fn emissive_emission_succeeds_tapering_with_cosine_power() {
+        let emissive = Emissive::new(Spectrum::broadcast(Val(2.0)), SpreadAngle::hemisphere())
+            .with_cosine_power(Val(8.0))
+            .unwrap();
+        let intersection = intersection();
+
+        let axis = Direction::z_direction();
+        assert_eq!(emissive.emission(&intersection, axis).red(), Val(2.0));
+
+        // The intensity `radiance * cos` halves at `cos = 0.5^(1/n)`.
+        let cos_half = Val(0.5).powf(Val(8.0).recip());
+        let sin_half = (Val(1.0) - cos_half * cos_half).sqrt();
+        let dir = Direction::normalize(Vector::new(sin_half, Val(0.0), cos_half)).unwrap();
+        let radiance = emissive.emission(&intersection, dir).red();
+        assert_eq!(radiance * cos_half, Val(0.5) * Val(2.0));
+    }
+
+    #[test]
+    fn emissive_with_cosine_power_fails_when_exponent_is_invalid() {
+        let emissive = Emissive::new(Spectrum::broadcast(Val(1.0)), SpreadAngle::hemisphere());
+        assert!(matches!(
+            emissive.with_cosine_power(Val(0.5)),
+            Err(TryNewEmissiveError::InvalidCosinePower),
+        ));
+    }
+
+    #[test]
+    fn emissive_lambertian_exitance_succeeds() {
+        let emissive = Emissive::new(Spectrum::broadcast(Val(2.0)), SpreadAngle::hemisphere());
+        let exitance = emissive.clone().with_two_sided(true).lambertian_exitance();
+        assert_eq!(exitance, Some(Spectrum::broadcast(Val(4.0) * Val::PI)));
+        let tapered = emissive.with_cosine_power(Val(3.0)).unwrap();
+        assert_eq!(tapered.lambertian_exitance(), None);
+    }
+}
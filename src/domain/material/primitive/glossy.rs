@@ -14,33 +14,53 @@ use crate::domain::renderer::{
     Contribution, PmContext, PmState, RtContext, RtState, StoragePolicy,
 };
 use crate::domain::sampling::coefficient::{BsdfSample, BsdfSampling};
-use crate::domain::texture::def::DynAlbedoTexture;
+use crate::domain::texture::def::{DynAlbedoTexture, DynScalarTexture};
+
+pub(super) fn roughness_to_alpha(roughness: Val) -> Val {
+    roughness.clamp(MIN_ROUGHNESS, Val(1.0)).powi(2)
+}
+
+pub(super) fn validate_roughness(roughness: &DynScalarTexture) -> bool {
+    (roughness.constant()).is_none_or(|r| Val(0.0) < r && r <= Val(1.0))
+}
+
+const MIN_ROUGHNESS: Val = Val(1e-3);
 
 pub(super) trait MicrofacetMaterial: Material {
     fn r0(&self, intersection: &RayIntersection) -> Spectrum;
 
-    fn alpha(&self) -> Val;
+    fn alpha(&self, intersection: &RayIntersection) -> Val;
+
+    fn alpha_uv(&self, intersection: &RayIntersection) -> (Val, Val) {
+        let alpha = self.alpha(intersection);
+        (alpha, alpha)
+    }
 
     fn generate_microfacet_normal(
         &self,
         dir: Direction,
-        normal: Normal,
+        intersection: &RayIntersection,
         rng: &mut dyn RngCore,
     ) -> Normal {
-        let frame = Frame::new(normal);
+        let frame = intersection.tangent_frame();
         let local_dir = frame.to_local_unit(dir.into()).into();
-        let local_mn = self.generate_local_microfacet_normal(local_dir, rng);
+        let local_mn = self.generate_local_microfacet_normal(local_dir, intersection, rng);
         frame.to_canonical_unit(local_mn.to_unit_vector()).into()
     }
 
     fn generate_local_microfacet_normal(
         &self,
         local_dir: Direction,
+        intersection: &RayIntersection,
         rng: &mut dyn RngCore,
     ) -> Normal {
-        let alpha = self.alpha();
+        let (alpha_u, alpha_v) = self.alpha_uv(intersection);
 
-        let ldir_tr = Vector::new(alpha * local_dir.x(), alpha * local_dir.y(), local_dir.z());
+        let ldir_tr = Vector::new(
+            alpha_u * local_dir.x(),
+            alpha_v * local_dir.y(),
+            local_dir.z(),
+        );
 
         let r = Val(rng.random()).sqrt();
         let phi = Val(2.0) * Val::PI * Val(rng.random());
@@ -52,8 +72,8 @@ pub(super) trait MicrofacetMaterial: Material {
         let mn_tr =
             Frame::new(Normal::normalize(ldir_tr).unwrap()).to_canonical(Vector::new(t1, t2, t3));
         let mn = Vector::new(
-            alpha * mn_tr.x(),
-            alpha * mn_tr.y(),
+            alpha_u * mn_tr.x(),
+            alpha_v * mn_tr.y(),
             mn_tr.z().max(Val(0.0)),
         );
         Normal::normalize(mn).unwrap()
@@ -64,84 +84,138 @@ pub(super) trait MicrofacetMaterial: Material {
         r0 + (Spectrum::broadcast(Val(1.0)) - r0) * (Val(1.0) - cos).powi(5)
     }
 
-    fn calc_ndf(&self, normal: Normal, mn: Normal) -> Val {
-        let alpha_squared = self.alpha().powi(2);
-        let cos = normal.dot(mn);
-        alpha_squared / (Val::PI * (cos.powi(2) * (alpha_squared - Val(1.0)) + Val(1.0)).powi(2))
+    fn calc_ndf(&self, intersection: &RayIntersection, mn: Normal) -> Val {
+        let (alpha_u, alpha_v) = self.alpha_uv(intersection);
+        let local_mn = intersection.tangent_frame().to_local(mn.into());
+        let tmp = (local_mn.x() / alpha_u).powi(2)
+            + (local_mn.y() / alpha_v).powi(2)
+            + local_mn.z().powi(2);
+        (Val::PI * alpha_u * alpha_v * tmp.powi(2)).recip()
+    }
+
+    fn calc_lambda(&self, dir: Direction, intersection: &RayIntersection) -> Val {
+        let (alpha_u, alpha_v) = self.alpha_uv(intersection);
+        let local_dir = intersection.tangent_frame().to_local(dir.into());
+        let tan2 = ((alpha_u * local_dir.x()).powi(2) + (alpha_v * local_dir.y()).powi(2))
+            / local_dir.z().powi(2);
+        Val(0.5) * ((Val(1.0) + tan2).sqrt() - Val(1.0))
+    }
+
+    fn calc_g1(&self, dir: Direction, intersection: &RayIntersection) -> Val {
+        (Val(1.0) + self.calc_lambda(dir, intersection)).recip()
+    }
+
+    fn calc_g2(&self, dir: Direction, dir_next: Direction, intersection: &RayIntersection) -> Val {
+        let lambda = self.calc_lambda(dir, intersection);
+        let lambda_next = self.calc_lambda(dir_next, intersection);
+        (Val(1.0) + lambda + lambda_next).recip()
     }
 
-    fn calc_g1(&self, dir: Direction, normal: Normal) -> Val {
-        let tan = dir.dot(normal).abs().acos().tan();
-        let tmp = (Val(1.0) + (self.alpha() * tan).powi(2)).sqrt();
-        Val(2.0) / (Val(1.0) + tmp)
+    fn calc_reflective_bsdf(
+        &self,
+        dir_out: Direction,
+        intersection: &RayIntersection,
+        dir_in: Direction,
+    ) -> Spectrum {
+        let normal = intersection.normal();
+        if normal.dot(dir_in) > Val(0.0) {
+            let mn = Normal::normalize(dir_out + dir_in).unwrap();
+
+            let reflectance = self.calc_reflectance(dir_in.dot(mn), intersection);
+            let ndf = self.calc_ndf(intersection, mn);
+            let g2 = self.calc_g2(dir_out, dir_in, intersection);
+            let (cos, cos_next) = (dir_out.dot(normal), dir_in.dot(normal));
+
+            (reflectance * ndf * g2) / (Val(4.0) * cos * cos_next).abs()
+        } else {
+            Spectrum::zero()
+        }
     }
 
-    fn calc_g2(&self, dir: Direction, dir_next: Direction, normal: Normal) -> Val {
-        let alpha = self.alpha();
-        let tan = dir.dot(normal).abs().acos().tan();
-        let tan_next = dir_next.dot(normal).abs().acos().tan();
-        let tmp = (Val(1.0) + (alpha * tan).powi(2)).sqrt();
-        let tmp_next = (Val(1.0) + (alpha * tan_next).powi(2)).sqrt();
-        Val(2.0) / (tmp + tmp_next)
+    fn sample_reflective_bsdf(
+        &self,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        rng: &mut dyn RngCore,
+    ) -> BsdfSample {
+        let dir = -ray.direction();
+        let normal = intersection.normal();
+
+        let mn = self.generate_microfacet_normal(dir, intersection, rng);
+        let ray_next = ray_util::reflect_microfacet(ray, intersection, mn);
+        let dir_next = ray_next.direction();
+
+        let reflectance = self.calc_reflectance(dir.dot(mn), intersection);
+        let g2 = self.calc_g2(dir, dir_next, intersection);
+        let g1 = self.calc_g1(dir, intersection);
+        let coefficient = reflectance * g2 / g1;
+
+        let ndf = self.calc_ndf(intersection, mn);
+        let pdf = g1 * ndf * Val(0.25) / dir.dot(normal);
+
+        BsdfSample::new(ray_next, coefficient, pdf)
+    }
+
+    fn pdf_reflective_bsdf(
+        &self,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        ray_next: &Ray,
+    ) -> Val {
+        let (dir, dir_next) = (-ray.direction(), ray_next.direction());
+        let Ok(mn) = Normal::normalize(dir + dir_next) else {
+            return Val(0.0);
+        };
+
+        let normal = intersection.normal();
+        if dir_next.dot(normal) <= Val(0.0) {
+            return Val(0.0);
+        }
+
+        let g1 = self.calc_g1(dir, intersection);
+        let ndf = self.calc_ndf(intersection, mn);
+        g1 * ndf * Val(0.25) / dir.dot(normal)
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Glossy {
     albedo: DynAlbedoTexture,
-    metalness: Val,
-    alpha: Val,
+    metalness: DynScalarTexture,
+    roughness: DynScalarTexture,
 }
 
 impl Glossy {
     const DIELECTRIC_R0: Spectrum = Spectrum::broadcast(Val(0.04));
 
-    pub fn new<T>(albedo: T, metalness: Val, roughness: Val) -> Result<Self, TryNewGlossyError>
+    pub fn new<T, M, R>(albedo: T, metalness: M, roughness: R) -> Result<Self, TryNewGlossyError>
     where
         T: Into<DynAlbedoTexture>,
+        M: Into<DynScalarTexture>,
+        R: Into<DynScalarTexture>,
     {
+        let (metalness, roughness) = (metalness.into(), roughness.into());
         ensure!(
-            Val(0.0) <= metalness && metalness <= Val(1.0),
+            (metalness.constant()).is_none_or(|m| Val(0.0) <= m && m <= Val(1.0)),
             InvalidMetalnessSnafu
         );
-        ensure!(
-            Val(0.0) < roughness && roughness <= Val(1.0),
-            InvalidRoughnessSnafu
-        );
+        ensure!(validate_roughness(&roughness), InvalidRoughnessSnafu);
 
         Ok(Self {
             albedo: albedo.into(),
             metalness,
-            alpha: roughness.powi(2),
+            roughness,
         })
     }
 
-    pub fn lookup(
+    pub fn lookup<R>(
         predefinition: GlossyPredefinition,
-        roughness: Val,
-    ) -> Result<Self, TryNewGlossyError> {
-        ensure!(
-            Val(0.0) < roughness && roughness <= Val(1.0),
-            InvalidRoughnessSnafu
-        );
-
-        let (r0_r, r0_g, r0_b) = match predefinition {
-            GlossyPredefinition::Aluminum => (0.913, 0.922, 0.924),
-            GlossyPredefinition::Brass => (0.910, 0.778, 0.423),
-            GlossyPredefinition::Chromium => (0.549, 0.556, 0.554),
-            GlossyPredefinition::Copper => (0.955, 0.638, 0.538),
-            GlossyPredefinition::Gold => (1.000, 0.782, 0.344),
-            GlossyPredefinition::Iron => (0.562, 0.565, 0.578),
-            GlossyPredefinition::Mercury => (0.781, 0.780, 0.778),
-            GlossyPredefinition::Nickel => (0.660, 0.609, 0.526),
-            GlossyPredefinition::Palladium => (0.733, 0.697, 0.652),
-            GlossyPredefinition::Platinum => (0.673, 0.637, 0.585),
-            GlossyPredefinition::Silver => (0.972, 0.960, 0.915),
-            GlossyPredefinition::Titanium => (0.542, 0.497, 0.449),
-            GlossyPredefinition::Zinc => (0.664, 0.824, 0.850),
-        };
-        let albedo = Albedo::new(Val(r0_r), Val(r0_g), Val(r0_b)).unwrap();
-        Self::new(albedo, Val(1.0), roughness)
+        roughness: R,
+    ) -> Result<Self, TryNewGlossyError>
+    where
+        R: Into<DynScalarTexture>,
+    {
+        Self::new(predefinition.albedo(), Val(1.0), roughness)
     }
 }
 
@@ -150,6 +224,16 @@ impl Material for Glossy {
         MaterialKind::Glossy
     }
 
+    fn uses_footprint(&self) -> bool {
+        self.albedo.uses_footprint()
+            || self.metalness.uses_footprint()
+            || self.roughness.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.albedo.lookup(intersection)
+    }
+
     fn shade(
         &self,
         context: &mut RtContext<'_>,
@@ -183,12 +267,16 @@ impl MicrofacetMaterial for Glossy {
     #[inline]
     fn r0(&self, intersection: &RayIntersection) -> Spectrum {
         let albedo = self.albedo.lookup(intersection).into();
-        Spectrum::lerp(Self::DIELECTRIC_R0, albedo, self.metalness)
+        let metalness = self
+            .metalness
+            .lookup(intersection)
+            .clamp(Val(0.0), Val(1.0));
+        Spectrum::lerp(Self::DIELECTRIC_R0, albedo, metalness)
     }
 
     #[inline]
-    fn alpha(&self) -> Val {
-        self.alpha
+    fn alpha(&self, intersection: &RayIntersection) -> Val {
+        roughness_to_alpha(self.roughness.lookup(intersection))
     }
 }
 
@@ -199,19 +287,7 @@ impl BsdfMaterial for Glossy {
         intersection: &RayIntersection,
         dir_in: Direction,
     ) -> Spectrum {
-        let normal = intersection.normal();
-        if normal.dot(dir_in) > Val(0.0) {
-            let mn = Normal::normalize(dir_out + dir_in).unwrap();
-
-            let reflectance = self.calc_reflectance(dir_in.dot(mn), intersection);
-            let ndf = self.calc_ndf(normal, mn);
-            let g2 = self.calc_g2(dir_out, dir_in, normal);
-            let (cos, cos_next) = (dir_out.dot(normal), dir_in.dot(normal));
-
-            (reflectance * ndf * g2) / (Val(4.0) * cos * cos_next).abs()
-        } else {
-            Spectrum::zero()
-        }
+        self.calc_reflective_bsdf(dir_out, intersection, dir_in)
     }
 }
 
@@ -222,38 +298,11 @@ impl BsdfSampling for Glossy {
         intersection: &RayIntersection,
         rng: &mut dyn RngCore,
     ) -> BsdfSample {
-        let dir = -ray.direction();
-        let normal = intersection.normal();
-
-        let mn = self.generate_microfacet_normal(dir, normal, rng);
-        let ray_next = ray_util::reflect_microfacet(ray, intersection, mn);
-        let dir_next = ray_next.direction();
-
-        let reflectance = self.calc_reflectance(dir.dot(mn), intersection);
-        let g2 = self.calc_g2(dir, dir_next, normal);
-        let g1 = self.calc_g1(dir, normal);
-        let coefficient = reflectance * g2 / g1;
-
-        let ndf = self.calc_ndf(normal, mn);
-        let pdf = g1 * ndf * Val(0.25) / dir.dot(normal);
-
-        BsdfSample::new(ray_next, coefficient, pdf)
+        self.sample_reflective_bsdf(ray, intersection, rng)
     }
 
     fn pdf_bsdf(&self, ray: &Ray, intersection: &RayIntersection, ray_next: &Ray) -> Val {
-        let (dir, dir_next) = (-ray.direction(), ray_next.direction());
-        let Ok(mn) = Normal::normalize(dir + dir_next) else {
-            return Val(0.0);
-        };
-
-        let normal = intersection.normal();
-        if dir_next.dot(normal) <= Val(0.0) {
-            return Val(0.0);
-        }
-
-        let g1 = self.calc_g1(dir, normal);
-        let ndf = self.calc_ndf(normal, mn);
-        g1 * ndf * Val(0.25) / dir.dot(normal)
+        self.pdf_reflective_bsdf(ray, intersection, ray_next)
     }
 }
 
@@ -274,6 +323,43 @@ pub enum GlossyPredefinition {
     Zinc,
 }
 
+impl GlossyPredefinition {
+    pub fn albedo(&self) -> Albedo {
+        let (r0_r, r0_g, r0_b) = match self {
+            Self::Aluminum => (0.913, 0.922, 0.924),
+            Self::Brass => (0.910, 0.778, 0.423),
+            Self::Chromium => (0.549, 0.556, 0.554),
+            Self::Copper => (0.955, 0.638, 0.538),
+            Self::Gold => (1.000, 0.782, 0.344),
+            Self::Iron => (0.562, 0.565, 0.578),
+            Self::Mercury => (0.781, 0.780, 0.778),
+            Self::Nickel => (0.660, 0.609, 0.526),
+            Self::Palladium => (0.733, 0.697, 0.652),
+            Self::Platinum => (0.673, 0.637, 0.585),
+            Self::Silver => (0.972, 0.960, 0.915),
+            Self::Titanium => (0.542, 0.497, 0.449),
+            Self::Zinc => (0.664, 0.824, 0.850),
+        };
+        Albedo::new(Val(r0_r), Val(r0_g), Val(r0_b)).unwrap()
+    }
+
+    pub fn complex_refractive_index(&self) -> (Spectrum, Spectrum) {
+        let channel = |r: Val| {
+            let (r, g) = (r.min(Val(0.999)), r.min(Val(0.999)));
+            let r_sqrt = r.sqrt();
+            let n = g * (Val(1.0) - r) / (Val(1.0) + r)
+                + (Val(1.0) - g) * (Val(1.0) + r_sqrt) / (Val(1.0) - r_sqrt);
+            let k2 = (r * (n + Val(1.0)).powi(2) - (n - Val(1.0)).powi(2)) / (Val(1.0) - r);
+            (n, k2.max(Val(0.0)).sqrt())
+        };
+        let albedo = self.albedo();
+        let (nr, kr) = channel(albedo.red());
+        let (ng, kg) = channel(albedo.green());
+        let (nb, kb) = channel(albedo.blue());
+        (Spectrum::new(nr, ng, nb), Spectrum::new(kr, kg, kb))
+    }
+}
+
 #[derive(Debug, Snafu, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum TryNewGlossyError {
@@ -282,3 +368,52 @@ pub enum TryNewGlossyError {
     #[snafu(display("roughness should be in (0, 1]"))]
     InvalidRoughness,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::camera::Resolution;
+    use crate::domain::image::core::Image;
+    use crate::domain::math::geometry::{Distance, Point};
+    use crate::domain::ray::event::SurfaceSide;
+    use crate::domain::texture::def::{DynTexture, UvCoordinate};
+    use crate::domain::texture::primitive::{FilterMode, ImageMap};
+
+    use super::*;
+
+    fn intersection_at(u: Val) -> RayIntersection {
+        RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::default(),
+            Normal::z_direction(),
+            SurfaceSide::Front,
+        )
+        .with_uv(UvCoordinate::new(u, Val(0.5)).unwrap())
+    }
+
+    #[test]
+    fn glossy_new_fails_when_constant_roughness_is_out_of_range() {
+        assert!(matches!(
+            Glossy::new(Albedo::WHITE, Val(1.0), Val(0.0)),
+            Err(TryNewGlossyError::InvalidRoughness),
+        ));
+    }
+
+    #[test]
+    fn glossy_bsdf_succeeds_varying_highlight_with_roughness_texture() {
+        let mut image = Image::new(Resolution::new(1, (2, 1)).unwrap());
+        image.set(0, 0, Spectrum::broadcast(Val(0.1)));
+        image.set(0, 1, Spectrum::broadcast(Val(0.8)));
+        let roughness = ImageMap::new(image).with_filtering(FilterMode::Nearest);
+        let glossy = Glossy::new(Albedo::WHITE, Val(1.0), DynTexture::from(roughness)).unwrap();
+
+        let (smooth, rough) = (intersection_at(Val(0.25)), intersection_at(Val(0.75)));
+        let dir_out = Direction::z_direction();
+        let off_peak = Direction::normalize(Vector::new(Val(0.5), Val(0.0), Val(1.0))).unwrap();
+        let bsdf = |intersection: &RayIntersection, dir_in| {
+            glossy.bsdf(dir_out, intersection, dir_in).red()
+        };
+
+        assert!(bsdf(&smooth, dir_out) > bsdf(&rough, dir_out));
+        assert!(bsdf(&smooth, off_peak) < bsdf(&rough, off_peak));
+    }
+}
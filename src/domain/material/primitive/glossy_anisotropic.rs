@@ -0,0 +1,200 @@
+use rand::prelude::*;
+use snafu::prelude::*;
+
+use crate::domain::color::core::{Albedo, Spectrum};
+use crate::domain::material::def::{BsdfMaterial, BsdfMaterialExt, Material, MaterialKind};
+use crate::domain::math::geometry::Direction;
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RayIntersection;
+use crate::domain::ray::photon::PhotonRay;
+use crate::domain::renderer::{
+    Contribution, PmContext, PmState, RtContext, RtState, StoragePolicy,
+};
+use crate::domain::sampling::coefficient::{BsdfSample, BsdfSampling};
+use crate::domain::texture::def::DynAlbedoTexture;
+
+use super::{GlossyPredefinition, MicrofacetMaterial};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlossyAnisotropic {
+    albedo: DynAlbedoTexture,
+    alpha_u: Val,
+    alpha_v: Val,
+}
+
+impl GlossyAnisotropic {
+    pub fn new(
+        predefinition: GlossyPredefinition,
+        roughness_u: Val,
+        roughness_v: Val,
+    ) -> Result<Self, TryNewGlossyAnisotropicError> {
+        ensure!(
+            Val(0.0) < roughness_u && roughness_u <= Val(1.0),
+            InvalidRoughnessSnafu
+        );
+        ensure!(
+            Val(0.0) < roughness_v && roughness_v <= Val(1.0),
+            InvalidRoughnessSnafu
+        );
+
+        Ok(Self {
+            albedo: predefinition.albedo().into(),
+            alpha_u: roughness_u.powi(2),
+            alpha_v: roughness_v.powi(2),
+        })
+    }
+}
+
+impl Material for GlossyAnisotropic {
+    fn kind(&self) -> MaterialKind {
+        MaterialKind::GlossyAnisotropic
+    }
+
+    fn uses_footprint(&self) -> bool {
+        self.albedo.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.albedo.lookup(intersection)
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        state: RtState,
+        ray: &Ray,
+        intersection: &RayIntersection,
+    ) -> Contribution {
+        let light = self.shade_light(context, ray, intersection);
+        let state_next = state.with_skip_emissive(true);
+        let scattering = self.shade_scattering(context, state_next, ray, intersection);
+        light + scattering
+    }
+
+    fn receive(
+        &self,
+        context: &mut PmContext<'_>,
+        state: PmState,
+        photon: &PhotonRay,
+        intersection: &RayIntersection,
+    ) {
+        match state.policy() {
+            StoragePolicy::Global => {
+                self.maybe_bounce_next_photon(context, state, photon, intersection);
+            }
+            StoragePolicy::Caustic => {}
+        }
+    }
+}
+
+impl MicrofacetMaterial for GlossyAnisotropic {
+    #[inline]
+    fn r0(&self, intersection: &RayIntersection) -> Spectrum {
+        self.albedo.lookup(intersection).into()
+    }
+
+    #[inline]
+    fn alpha(&self, _intersection: &RayIntersection) -> Val {
+        (self.alpha_u * self.alpha_v).sqrt()
+    }
+
+    #[inline]
+    fn alpha_uv(&self, _intersection: &RayIntersection) -> (Val, Val) {
+        (self.alpha_u, self.alpha_v)
+    }
+}
+
+impl BsdfMaterial for GlossyAnisotropic {
+    fn bsdf(
+        &self,
+        dir_out: Direction,
+        intersection: &RayIntersection,
+        dir_in: Direction,
+    ) -> Spectrum {
+        self.calc_reflective_bsdf(dir_out, intersection, dir_in)
+    }
+}
+
+impl BsdfSampling for GlossyAnisotropic {
+    fn sample_bsdf(
+        &self,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        rng: &mut dyn RngCore,
+    ) -> BsdfSample {
+        self.sample_reflective_bsdf(ray, intersection, rng)
+    }
+
+    fn pdf_bsdf(&self, ray: &Ray, intersection: &RayIntersection, ray_next: &Ray) -> Val {
+        self.pdf_reflective_bsdf(ray, intersection, ray_next)
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewGlossyAnisotropicError {
+    #[snafu(display("roughness should be in (0, 1]"))]
+    InvalidRoughness,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::material::primitive::Glossy;
+    use crate::domain::math::algebra::Vector;
+    use crate::domain::math::geometry::{Distance, Normal, Point};
+    use crate::domain::ray::event::SurfaceSide;
+
+    use super::*;
+
+    #[test]
+    fn glossy_anisotropic_bsdf_succeeds_matching_isotropic_when_roughness_is_equal() {
+        let predefinition = GlossyPredefinition::Gold;
+        let anisotropic = GlossyAnisotropic::new(predefinition, Val(0.4), Val(0.4)).unwrap();
+        let isotropic = Glossy::lookup(predefinition, Val(0.4)).unwrap();
+
+        let intersection = RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::default(),
+            Normal::y_direction(),
+            SurfaceSide::Front,
+        )
+        .with_tangent(Vector::new(Val(1.0), Val(0.0), Val(1.0)));
+        let ray = Ray::new(
+            Point::new(Val(-1.0), Val(1.0), Val(0.5)),
+            Direction::normalize(Vector::new(Val(1.0), Val(-1.0), Val(-0.5))).unwrap(),
+        );
+        let dir_in = Direction::normalize(Vector::new(Val(0.6), Val(1.0), Val(0.3))).unwrap();
+        let ray_next = intersection.spawn(dir_in);
+
+        assert_eq!(
+            anisotropic.bsdf(-ray.direction(), &intersection, dir_in),
+            isotropic.bsdf(-ray.direction(), &intersection, dir_in),
+        );
+        assert_eq!(
+            anisotropic.pdf_bsdf(&ray, &intersection, &ray_next),
+            isotropic.pdf_bsdf(&ray, &intersection, &ray_next),
+        );
+    }
+
+    #[test]
+    fn glossy_anisotropic_bsdf_succeeds_stretching_along_tangent() {
+        let material =
+            GlossyAnisotropic::new(GlossyPredefinition::Silver, Val(0.8), Val(0.2)).unwrap();
+        let intersection = RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::default(),
+            Normal::y_direction(),
+            SurfaceSide::Front,
+        )
+        .with_tangent(Vector::new(Val(1.0), Val(0.0), Val(0.0)));
+
+        let dir_out = Direction::y_direction();
+        let along_u = Direction::normalize(Vector::new(Val(0.3), Val(1.0), Val(0.0))).unwrap();
+        let along_v = Direction::normalize(Vector::new(Val(0.0), Val(1.0), Val(0.3))).unwrap();
+        assert!(
+            material.bsdf(dir_out, &intersection, along_u).red()
+                > material.bsdf(dir_out, &intersection, along_v).red()
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use rand::prelude::*;
 use snafu::prelude::*;
 
+use crate::domain::color::core::{Albedo, Spectrum};
 use crate::domain::material::def::{DynMaterial, Material, MaterialCategory, MaterialKind};
 use crate::domain::math::numeric::Val;
 use crate::domain::ray::Ray;
@@ -33,6 +34,26 @@ impl Material for Mixed {
         MaterialKind::Mixed
     }
 
+    fn uses_footprint(&self) -> bool {
+        let other = match self.other.as_ref().map(AsRef::as_ref) {
+            Some(OtherMixed::Singleton { inner }) => inner.uses_footprint(),
+            Some(OtherMixed::Microfacet {
+                diffuse,
+                microfacet,
+            }) => diffuse.uses_footprint() || microfacet.uses_footprint(),
+            None => false,
+        };
+        other || (self.emissive.as_ref()).is_some_and(|e| e.uses_footprint())
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        match self.other.as_ref().map(AsRef::as_ref) {
+            Some(OtherMixed::Singleton { inner }) => inner.albedo(intersection),
+            Some(OtherMixed::Microfacet { diffuse, .. }) => diffuse.albedo(intersection),
+            None => (self.emissive.as_ref()).map_or(Albedo::BLACK, |e| e.albedo(intersection)),
+        }
+    }
+
     fn shade(
         &self,
         context: &mut RtContext<'_>,
@@ -56,11 +77,15 @@ impl Material for Mixed {
             }) => {
                 const SELECT_DIFFUSE_PROB: Val = Val(0.5);
                 if Val(context.rng().random()) < SELECT_DIFFUSE_PROB {
+                    let weight = SELECT_DIFFUSE_PROB.recip();
+                    let state = state.scale_throughput(Spectrum::broadcast(weight));
                     let diffuse_res = diffuse.shade(context, state, ray, intersection);
-                    res = res + diffuse_res * SELECT_DIFFUSE_PROB.recip();
+                    res = res + diffuse_res * weight;
                 } else {
+                    let weight = (Val(1.0) - SELECT_DIFFUSE_PROB).recip();
+                    let state = state.scale_throughput(Spectrum::broadcast(weight));
                     let microfacet_res = microfacet.shade(context, state, ray, intersection);
-                    res = res + microfacet_res * (Val(1.0) - SELECT_DIFFUSE_PROB).recip();
+                    res = res + microfacet_res * weight;
                 }
             }
             None => {}
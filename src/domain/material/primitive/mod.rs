@@ -1,19 +1,37 @@
 mod blurry;
+mod bump_mapped;
+mod coated;
+mod conductor;
 mod diffuse;
+mod dispersive;
 mod emissive;
 mod glossy;
+mod glossy_anisotropic;
 mod mixed;
+mod normal_mapped;
+mod phong;
+mod principled;
 mod refractive;
 mod scattering;
 mod specular;
+mod thin_film;
 
 pub use blurry::Blurry;
+pub use bump_mapped::{BumpMapped, TryNewBumpMappedError};
+pub use coated::{Coated, CoatedBase, TryNewCoatedError};
+pub use conductor::{Conductor, TryNewConductorError};
 pub use diffuse::Diffuse;
-pub use emissive::Emissive;
+pub use dispersive::{Dispersive, TryNewDispersiveError};
+pub use emissive::{Emissive, TryNewEmissiveError};
 pub use glossy::{Glossy, GlossyPredefinition, TryNewGlossyError};
+pub use glossy_anisotropic::{GlossyAnisotropic, TryNewGlossyAnisotropicError};
 pub use mixed::{Mixed, MixedBuilder, TryBuildMixedError};
+pub use normal_mapped::NormalMapped;
+pub use phong::{Phong, TryNewPhongError};
+pub use principled::{Principled, PrincipledBuilder, TryBuildPrincipledError};
 pub use refractive::{Refractive, TryNewRefractiveError};
 pub use scattering::Scattering;
 pub use specular::Specular;
+pub use thin_film::{ThinFilm, TryNewThinFilmError};
 
-use glossy::MicrofacetMaterial;
+use glossy::{MicrofacetMaterial, roughness_to_alpha, validate_roughness};
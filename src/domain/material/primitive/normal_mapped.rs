@@ -0,0 +1,121 @@
+use crate::domain::color::core::Albedo;
+use crate::domain::material::def::{DynMaterial, Material, MaterialKind};
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::geometry::Normal;
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RayIntersection;
+use crate::domain::ray::photon::PhotonRay;
+use crate::domain::renderer::{Contribution, PmContext, PmState, RtContext, RtState};
+use crate::domain::texture::def::{DynTexture, Texture};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalMapped {
+    inner: Box<DynMaterial>,
+    normal_map: DynTexture,
+}
+
+impl NormalMapped {
+    pub fn new<M, T>(inner: M, normal_map: T) -> Self
+    where
+        M: Into<DynMaterial>,
+        T: Into<DynTexture>,
+    {
+        Self {
+            inner: Box::new(inner.into()),
+            normal_map: normal_map.into(),
+        }
+    }
+
+    pub fn perturb(&self, intersection: &RayIntersection) -> RayIntersection {
+        let rgb = self.normal_map.lookup(intersection);
+        let decode = |c: Val| Val(2.0) * c - Val(1.0);
+        let local = Vector::new(decode(rgb.red()), decode(rgb.green()), decode(rgb.blue()));
+
+        let frame = intersection.tangent_frame();
+        match Normal::normalize(frame.to_canonical(local)) {
+            Ok(normal) => intersection.clone().with_normal(normal),
+            Err(_) => intersection.clone(),
+        }
+    }
+}
+
+impl Material for NormalMapped {
+    fn kind(&self) -> MaterialKind {
+        self.inner.kind()
+    }
+
+    fn uses_footprint(&self) -> bool {
+        self.inner.uses_footprint() || self.normal_map.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.inner.albedo(&self.perturb(intersection))
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        state: RtState,
+        ray: &Ray,
+        intersection: &RayIntersection,
+    ) -> Contribution {
+        let intersection = self.perturb(intersection);
+        self.inner.shade(context, state, ray, &intersection)
+    }
+
+    fn receive(
+        &self,
+        context: &mut PmContext<'_>,
+        state: PmState,
+        photon: &PhotonRay,
+        intersection: &RayIntersection,
+    ) {
+        let intersection = self.perturb(intersection);
+        self.inner.receive(context, state, photon, &intersection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::core::{Albedo, Spectrum};
+    use crate::domain::material::def::BsdfMaterial;
+    use crate::domain::material::primitive::Diffuse;
+    use crate::domain::math::algebra::Product;
+    use crate::domain::math::geometry::{Direction, Distance, Point};
+    use crate::domain::ray::event::SurfaceSide;
+    use crate::domain::texture::primitive::{Checkerboard, Constant};
+
+    use super::*;
+
+    #[test]
+    fn normal_mapped_perturb_succeeds_varying_shading_on_flat_plane() {
+        let flat = Constant::new(Spectrum::new(Val(0.5), Val(0.5), Val(1.0)));
+        let tilted = Constant::new(Spectrum::new(Val(0.8), Val(0.5), Val(0.9)));
+        let bumpy = Checkerboard::new(flat, tilted, Val(1.0)).unwrap();
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.8)).unwrap());
+        let material = NormalMapped::new(diffuse.clone(), bumpy);
+
+        let intersection_at = |x: Val| {
+            RayIntersection::new(
+                Distance::new(Val(1.0)).unwrap(),
+                Point::new(x, Val(0.5), Val(0.0)),
+                Normal::z_direction(),
+                SurfaceSide::Front,
+            )
+            .with_tangent(Vector::new(Val(1.0), Val(0.0), Val(0.0)))
+        };
+        let light = Direction::normalize(Vector::new(Val(1.0), Val(0.0), Val(1.0))).unwrap();
+        let dir_out = Direction::z_direction();
+        let shading = |intersection: &RayIntersection| {
+            let cos = intersection.normal().dot(light).max(Val(0.0));
+            diffuse.bsdf(dir_out, intersection, light).red() * cos
+        };
+
+        let flat = material.perturb(&intersection_at(Val(0.5)));
+        assert_eq!(flat.normal(), Normal::z_direction());
+        let tilted = material.perturb(&intersection_at(Val(1.5)));
+        assert!(tilted.normal().x() > Val(0.0));
+        assert!(shading(&tilted) > shading(&flat));
+    }
+}
@@ -0,0 +1,260 @@
+use getset::CopyGetters;
+use rand::prelude::*;
+use snafu::prelude::*;
+
+use crate::domain::color::core::{Albedo, Spectrum};
+use crate::domain::material::def::{BsdfMaterial, BsdfMaterialExt, Material, MaterialKind};
+use crate::domain::math::algebra::{Product, Vector};
+use crate::domain::math::geometry::{Direction, Frame, Normal};
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RayIntersection;
+use crate::domain::ray::photon::PhotonRay;
+use crate::domain::ray::util as ray_util;
+use crate::domain::renderer::{
+    Contribution, PmContext, PmState, RtContext, RtState, StoragePolicy,
+};
+use crate::domain::sampling::coefficient::{BsdfSample, BsdfSampling};
+use crate::domain::texture::def::DynAlbedoTexture;
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+pub struct Phong {
+    diffuse: DynAlbedoTexture,
+    #[getset(get_copy = "pub")]
+    specular: Albedo,
+    #[getset(get_copy = "pub")]
+    shininess: Val,
+}
+
+impl Phong {
+    pub fn new<T>(diffuse: T, specular: Albedo, shininess: Val) -> Result<Self, TryNewPhongError>
+    where
+        T: Into<DynAlbedoTexture>,
+    {
+        ensure!(shininess >= Val(0.0), InvalidShininessSnafu);
+        Ok(Self {
+            diffuse: diffuse.into(),
+            specular,
+            shininess,
+        })
+    }
+
+    fn select_specular_prob(&self, intersection: &RayIntersection) -> Val {
+        let diffuse = self.diffuse.lookup(intersection).to_spectrum().luminance();
+        let specular = self.specular.to_spectrum().luminance();
+        if diffuse + specular == Val(0.0) {
+            Val(0.0)
+        } else {
+            specular / (diffuse + specular)
+        }
+    }
+
+    fn half_vector(dir_out: Direction, dir_in: Direction) -> Option<Normal> {
+        Normal::normalize(dir_out.to_vector() + dir_in.to_vector()).ok()
+    }
+
+    fn specular_lobe(&self, cos_half: Val) -> Val {
+        let normalization = (self.shininess + Val(8.0)) / (Val(8.0) * Val::PI);
+        normalization * cos_half.max(Val(0.0)).powf(self.shininess)
+    }
+
+    fn sample_half_vector(&self, normal: Normal, rng: &mut dyn RngCore) -> Normal {
+        let cos = Val(rng.random::<f64>()).powf((self.shininess + Val(1.0)).recip());
+        let sin = (Val(1.0) - cos * cos).max(Val(0.0)).sqrt();
+        let (sin_phi, cos_phi) = (Val(2.0) * Val::PI * Val(rng.random())).sin_cos();
+        let local = Vector::new(sin * cos_phi, sin * sin_phi, cos);
+        Normal::normalize(Frame::new(normal).to_canonical(local)).unwrap_or(normal)
+    }
+
+    fn pdf_specular(&self, dir_out: Direction, normal: Normal, dir_in: Direction) -> Val {
+        let Some(half) = Self::half_vector(dir_out, dir_in) else {
+            return Val(0.0);
+        };
+        let cos_half = half.dot(normal).max(Val(0.0));
+        let cos_out = half.dot(dir_out).abs();
+        if cos_out == Val(0.0) {
+            return Val(0.0);
+        }
+        let pdf_half =
+            (self.shininess + Val(1.0)) / (Val(2.0) * Val::PI) * cos_half.powf(self.shininess);
+        pdf_half / (Val(4.0) * cos_out)
+    }
+}
+
+impl Material for Phong {
+    fn kind(&self) -> MaterialKind {
+        MaterialKind::Phong
+    }
+
+    fn uses_footprint(&self) -> bool {
+        self.diffuse.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.diffuse.lookup(intersection)
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        state: RtState,
+        ray: &Ray,
+        intersection: &RayIntersection,
+    ) -> Contribution {
+        let light = self.shade_light(context, ray, intersection);
+        let state_next = state.with_skip_emissive(true);
+        let scattering = self.shade_scattering(context, state_next, ray, intersection);
+        light + scattering
+    }
+
+    fn receive(
+        &self,
+        context: &mut PmContext<'_>,
+        state: PmState,
+        photon: &PhotonRay,
+        intersection: &RayIntersection,
+    ) {
+        match state.policy() {
+            StoragePolicy::Global => {
+                self.maybe_bounce_next_photon(context, state, photon, intersection);
+            }
+            StoragePolicy::Caustic => {}
+        }
+    }
+}
+
+impl BsdfMaterial for Phong {
+    fn bsdf(
+        &self,
+        dir_out: Direction,
+        intersection: &RayIntersection,
+        dir_in: Direction,
+    ) -> Spectrum {
+        let normal = intersection.normal();
+        if normal.dot(dir_in) <= Val(0.0) || normal.dot(dir_out) <= Val(0.0) {
+            return Spectrum::zero();
+        }
+        let diffuse = Val::FRAC_1_PI * self.diffuse.lookup(intersection).to_spectrum();
+        let specular = match Self::half_vector(dir_out, dir_in) {
+            Some(half) => self.specular_lobe(half.dot(normal)) * self.specular.to_spectrum(),
+            None => Spectrum::zero(),
+        };
+        diffuse + specular
+    }
+}
+
+impl BsdfSampling for Phong {
+    fn sample_bsdf(
+        &self,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        rng: &mut dyn RngCore,
+    ) -> BsdfSample {
+        let normal = intersection.normal();
+        let ray_next = if Val(rng.random()) < self.select_specular_prob(intersection) {
+            let half = self.sample_half_vector(normal, rng);
+            ray_util::reflect_microfacet(ray, intersection, half)
+        } else {
+            intersection.spawn(Direction::random_cosine_hemisphere(normal, rng))
+        };
+
+        let pdf = self.pdf_bsdf(ray, intersection, &ray_next);
+        let dir_in = ray_next.direction();
+        let cos = dir_in.dot(normal);
+        if pdf > Val(0.0) && cos > Val(0.0) {
+            let bsdf = self.bsdf(-ray.direction(), intersection, dir_in);
+            BsdfSample::new(ray_next, bsdf * cos / pdf, pdf)
+        } else {
+            BsdfSample::new(ray_next, Spectrum::zero(), Val(0.0))
+        }
+    }
+
+    fn pdf_bsdf(&self, ray: &Ray, intersection: &RayIntersection, ray_next: &Ray) -> Val {
+        let normal = intersection.normal();
+        let dir_in = ray_next.direction();
+        let cos = dir_in.dot(normal);
+        if cos <= Val(0.0) {
+            return Val(0.0);
+        }
+        let specular_prob = self.select_specular_prob(intersection);
+        let specular = self.pdf_specular(-ray.direction(), normal, dir_in);
+        let diffuse = cos * Val::FRAC_1_PI;
+        specular_prob * specular + (Val(1.0) - specular_prob) * diffuse
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewPhongError {
+    #[snafu(display("shininess is negative"))]
+    InvalidShininess,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::{Distance, Point};
+    use crate::domain::ray::event::SurfaceSide;
+
+    use super::*;
+
+    fn intersection() -> RayIntersection {
+        RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Normal::z_direction(),
+            SurfaceSide::Front,
+        )
+    }
+
+    #[test]
+    fn phong_new_fails_when_shininess_is_invalid() {
+        assert!(matches!(
+            Phong::new(Albedo::WHITE, Albedo::WHITE, Val(-1.0)),
+            Err(TryNewPhongError::InvalidShininess),
+        ));
+    }
+
+    #[test]
+    fn phong_bsdf_succeeds_narrowing_highlight_with_shininess() {
+        let intersection = intersection();
+        let dir_out = Direction::normalize(Vector::new(Val(1.0), Val(0.0), Val(1.0))).unwrap();
+        let mirror = Direction::normalize(Vector::new(Val(-1.0), Val(0.0), Val(1.0))).unwrap();
+        let off = Direction::normalize(Vector::new(Val(-1.0), Val(0.0), Val(1.5))).unwrap();
+
+        let ratio = |shininess: Val| {
+            let phong = Phong::new(Albedo::BLACK, Albedo::WHITE, shininess).unwrap();
+            let peak = phong.bsdf(dir_out, &intersection, mirror).red();
+            let side = phong.bsdf(dir_out, &intersection, off).red();
+            side / peak
+        };
+        let (broad, narrow) = (ratio(Val(10.0)), ratio(Val(200.0)));
+        assert!(broad < Val(1.0));
+        assert!(narrow < broad * Val(0.5), "{narrow:?} vs {broad:?}");
+    }
+
+    #[test]
+    fn phong_sample_bsdf_succeeds_matching_evaluated_bsdf() {
+        let phong = Phong::new(
+            Albedo::broadcast(Val(0.4)).unwrap(),
+            Albedo::broadcast(Val(0.4)).unwrap(),
+            Val(50.0),
+        )
+        .unwrap();
+        let intersection = intersection();
+        let dir = Direction::normalize(Vector::new(Val(-1.0), Val(0.0), Val(-1.0))).unwrap();
+        let ray = Ray::new(Point::new(Val(1.0), Val(0.0), Val(1.0)), dir);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut mean = Val(0.0);
+        for _ in 0..10000 {
+            let sample = phong.sample_bsdf(&ray, &intersection, &mut rng);
+            if sample.pdf() == Val(0.0) {
+                continue;
+            }
+            let pdf = phong.pdf_bsdf(&ray, &intersection, sample.ray_next());
+            assert_eq!(sample.pdf(), pdf);
+            mean += sample.coefficient().red() / Val(10000.0);
+        }
+        assert!(Val(0.4) < mean && mean < Val(0.8), "{mean:?}");
+    }
+}
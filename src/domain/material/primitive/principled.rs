@@ -0,0 +1,493 @@
+use getset::CopyGetters;
+use rand::prelude::*;
+use snafu::prelude::*;
+
+use crate::domain::color::core::{Albedo, Color, Spectrum};
+use crate::domain::material::def::{BsdfMaterial, BsdfMaterialExt, Material, MaterialKind};
+use crate::domain::math::algebra::Product;
+use crate::domain::math::geometry::{Direction, Normal};
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RayIntersection;
+use crate::domain::ray::photon::PhotonRay;
+use crate::domain::renderer::{
+    Contribution, PmContext, PmState, RtContext, RtState, StoragePolicy,
+};
+use crate::domain::sampling::coefficient::{BsdfSample, BsdfSampling};
+
+use super::{Blurry, Diffuse, Glossy, MicrofacetMaterial};
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct Principled {
+    base_color: Albedo,
+    metallic: Val,
+    transmission: Val,
+    clearcoat: Val,
+    #[getset(skip)]
+    sheen: Spectrum,
+    #[getset(skip)]
+    lobes: Box<PrincipledLobes>,
+}
+
+impl Principled {
+    const CLEARCOAT_WEIGHT: Val = Val(0.25);
+
+    pub fn new(base_color: Albedo) -> Self {
+        Self::builder(base_color)
+            .build()
+            .expect("default parameters should be valid")
+    }
+
+    pub fn builder(base_color: Albedo) -> PrincipledBuilder {
+        PrincipledBuilder::new(base_color)
+    }
+
+    fn calc_lobe_weights(&self) -> (Val, Val, Val, Val) {
+        let (metallic, transmission) = (self.metallic, self.transmission);
+        let dielectric = (Val(1.0) - metallic) * (Val(1.0) - transmission);
+        let transmissive = (Val(1.0) - metallic) * transmission;
+        let coat = Self::CLEARCOAT_WEIGHT * self.clearcoat;
+        (metallic, dielectric, transmissive, coat)
+    }
+
+    fn calc_dielectric_reflectance(
+        &self,
+        dir: Direction,
+        intersection: &RayIntersection,
+    ) -> Spectrum {
+        let cos = dir.dot(intersection.normal()).clamp(Val(0.0), Val(1.0));
+        self.lobes.dielectric.calc_reflectance(cos, intersection)
+    }
+
+    fn calc_base_bsdf(
+        &self,
+        dir_out: Direction,
+        intersection: &RayIntersection,
+        dir_in: Direction,
+    ) -> Spectrum {
+        let normal = intersection.normal();
+        if dir_out.dot(normal) <= Val(0.0) || dir_in.dot(normal) <= Val(0.0) {
+            return Spectrum::zero();
+        }
+        let diffuse = self.lobes.diffuse.bsdf(dir_out, intersection, dir_in);
+        let sheen = Normal::normalize(dir_out + dir_in).map_or(Spectrum::zero(), |half| {
+            self.sheen * (Val(1.0) - dir_in.dot(half).max(Val(0.0))).powi(5)
+        });
+
+        let white = Spectrum::broadcast(Val(1.0));
+        let entry = white - self.calc_dielectric_reflectance(dir_in, intersection);
+        let exit = white - self.calc_dielectric_reflectance(dir_out, intersection);
+        (diffuse + sheen) * entry * exit
+    }
+
+    fn calc_selection_probs(&self, dir_out: Direction, intersection: &RayIntersection) -> [Val; 5] {
+        let avg = |s: Spectrum| (s.red() + s.green() + s.blue()) / Val(3.0);
+        let (metallic, dielectric, transmissive, coat) = self.calc_lobe_weights();
+
+        let reflectance = avg(self.calc_dielectric_reflectance(dir_out, intersection));
+        let cos = dir_out.dot(intersection.normal()).clamp(Val(0.0), Val(1.0));
+        let coat = coat * avg(self.lobes.coat.calc_reflectance(cos, intersection));
+
+        let weights = [
+            metallic,
+            dielectric * reflectance,
+            dielectric * (Val(1.0) - reflectance),
+            transmissive,
+            coat,
+        ];
+        let sum = weights.iter().copied().sum::<Val>();
+        weights.map(|w| w / sum)
+    }
+}
+
+impl Material for Principled {
+    fn kind(&self) -> MaterialKind {
+        MaterialKind::Principled
+    }
+
+    fn uses_footprint(&self) -> bool {
+        false
+    }
+
+    fn albedo(&self, _intersection: &RayIntersection) -> Albedo {
+        self.base_color
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        state: RtState,
+        ray: &Ray,
+        intersection: &RayIntersection,
+    ) -> Contribution {
+        let light = self.shade_light(context, ray, intersection);
+        let state_next = state.with_skip_emissive(true);
+        let scattering = self.shade_scattering(context, state_next, ray, intersection);
+        light + scattering
+    }
+
+    fn receive(
+        &self,
+        context: &mut PmContext<'_>,
+        state: PmState,
+        photon: &PhotonRay,
+        intersection: &RayIntersection,
+    ) {
+        match state.policy() {
+            StoragePolicy::Global => {
+                self.maybe_bounce_next_photon(context, state, photon, intersection);
+            }
+            StoragePolicy::Caustic => {}
+        }
+    }
+}
+
+impl BsdfMaterial for Principled {
+    fn bsdf(
+        &self,
+        dir_out: Direction,
+        intersection: &RayIntersection,
+        dir_in: Direction,
+    ) -> Spectrum {
+        let (metallic, dielectric, transmissive, coat) = self.calc_lobe_weights();
+        let mut res = Spectrum::zero();
+        if metallic > Val(0.0) {
+            res += self.lobes.metal.bsdf(dir_out, intersection, dir_in) * metallic;
+        }
+        if dielectric > Val(0.0) {
+            let specular = self.lobes.dielectric.bsdf(dir_out, intersection, dir_in);
+            let base = self.calc_base_bsdf(dir_out, intersection, dir_in);
+            res += (specular + base) * dielectric;
+        }
+        if transmissive > Val(0.0) {
+            res += self.lobes.transmissive.bsdf(dir_out, intersection, dir_in) * transmissive;
+        }
+        if coat > Val(0.0) {
+            res += self.lobes.coat.bsdf(dir_out, intersection, dir_in) * coat;
+        }
+        res
+    }
+}
+
+impl BsdfSampling for Principled {
+    fn sample_bsdf(
+        &self,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        rng: &mut dyn RngCore,
+    ) -> BsdfSample {
+        let dir_out = -ray.direction();
+        let probs = self.calc_selection_probs(dir_out, intersection);
+
+        let mut u = Val(rng.random());
+        let mut lobe = probs.len() - 1;
+        for (i, &prob) in probs.iter().enumerate() {
+            if u < prob {
+                lobe = i;
+                break;
+            }
+            u -= prob;
+        }
+        let ray_next = match lobe {
+            0 => self.lobes.metal.sample_bsdf(ray, intersection, rng),
+            1 => self.lobes.dielectric.sample_bsdf(ray, intersection, rng),
+            2 => self.lobes.diffuse.sample_bsdf(ray, intersection, rng),
+            3 => self.lobes.transmissive.sample_bsdf(ray, intersection, rng),
+            _ => self.lobes.coat.sample_bsdf(ray, intersection, rng),
+        }
+        .into_ray_next();
+
+        let pdf = self.pdf_bsdf(ray, intersection, &ray_next);
+        let dir_in = ray_next.direction();
+        let cos = dir_in.dot(intersection.normal()).abs();
+        if pdf > Val(0.0) {
+            let bsdf = self.bsdf(dir_out, intersection, dir_in);
+            BsdfSample::new(ray_next, bsdf * cos / pdf, pdf)
+        } else {
+            BsdfSample::new(ray_next, Spectrum::zero(), Val(0.0))
+        }
+    }
+
+    fn pdf_bsdf(&self, ray: &Ray, intersection: &RayIntersection, ray_next: &Ray) -> Val {
+        let probs = self.calc_selection_probs(-ray.direction(), intersection);
+        let lobes: [&dyn BsdfSampling; 5] = [
+            &self.lobes.metal,
+            &self.lobes.dielectric,
+            &self.lobes.diffuse,
+            &self.lobes.transmissive,
+            &self.lobes.coat,
+        ];
+        (probs.into_iter().zip(lobes))
+            .filter(|(prob, _)| *prob > Val(0.0))
+            .map(|(prob, lobe)| prob * lobe.pdf_bsdf(ray, intersection, ray_next))
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PrincipledLobes {
+    diffuse: Diffuse,
+    dielectric: Glossy,
+    metal: Glossy,
+    transmissive: Blurry,
+    coat: Glossy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrincipledBuilder {
+    base_color: Albedo,
+    metallic: Val,
+    roughness: Val,
+    specular: Val,
+    specular_tint: Val,
+    sheen: Val,
+    sheen_tint: Val,
+    clearcoat: Val,
+    clearcoat_gloss: Val,
+    transmission: Val,
+    refractive_index: Val,
+}
+
+impl PrincipledBuilder {
+    pub fn new(base_color: Albedo) -> Self {
+        Self {
+            base_color,
+            metallic: Val(0.0),
+            roughness: Val(0.5),
+            specular: Val(0.5),
+            specular_tint: Val(0.0),
+            sheen: Val(0.0),
+            sheen_tint: Val(0.5),
+            clearcoat: Val(0.0),
+            clearcoat_gloss: Val(1.0),
+            transmission: Val(0.0),
+            refractive_index: Val(1.5),
+        }
+    }
+
+    pub fn with_metallic(self, metallic: Val) -> Self {
+        Self { metallic, ..self }
+    }
+
+    pub fn with_roughness(self, roughness: Val) -> Self {
+        Self { roughness, ..self }
+    }
+
+    pub fn with_specular(self, specular: Val) -> Self {
+        Self { specular, ..self }
+    }
+
+    pub fn with_specular_tint(self, specular_tint: Val) -> Self {
+        Self {
+            specular_tint,
+            ..self
+        }
+    }
+
+    pub fn with_sheen(self, sheen: Val) -> Self {
+        Self { sheen, ..self }
+    }
+
+    pub fn with_sheen_tint(self, sheen_tint: Val) -> Self {
+        Self { sheen_tint, ..self }
+    }
+
+    pub fn with_clearcoat(self, clearcoat: Val) -> Self {
+        Self { clearcoat, ..self }
+    }
+
+    pub fn with_clearcoat_gloss(self, clearcoat_gloss: Val) -> Self {
+        Self {
+            clearcoat_gloss,
+            ..self
+        }
+    }
+
+    pub fn with_transmission(self, transmission: Val) -> Self {
+        Self {
+            transmission,
+            ..self
+        }
+    }
+
+    pub fn with_refractive_index(self, refractive_index: Val) -> Self {
+        Self {
+            refractive_index,
+            ..self
+        }
+    }
+
+    pub fn build(self) -> Result<Principled, TryBuildPrincipledError> {
+        let factors = [
+            ("metallic", self.metallic),
+            ("specular", self.specular),
+            ("specular_tint", self.specular_tint),
+            ("sheen", self.sheen),
+            ("sheen_tint", self.sheen_tint),
+            ("clearcoat", self.clearcoat),
+            ("clearcoat_gloss", self.clearcoat_gloss),
+            ("transmission", self.transmission),
+        ];
+        for (name, value) in factors {
+            ensure!(
+                Val(0.0) <= value && value <= Val(1.0),
+                InvalidFactorSnafu { name }
+            );
+        }
+        ensure!(
+            Val(0.0) < self.roughness && self.roughness <= Val(1.0),
+            InvalidRoughnessSnafu
+        );
+        ensure!(
+            self.refractive_index >= Val(1.0),
+            InvalidRefractiveIndexSnafu
+        );
+
+        let base: Spectrum = self.base_color.into();
+        let luminance = Val(0.3) * base.red() + Val(0.6) * base.green() + Val(0.1) * base.blue();
+        let white = Spectrum::broadcast(Val(1.0));
+        let tint = if luminance > Val(0.0) {
+            base / luminance
+        } else {
+            white
+        };
+
+        let specular_color = Spectrum::lerp(white, tint, self.specular_tint);
+        let r0 = Albedo::clamp(specular_color * (Val(0.08) * self.specular));
+        let sheen = Spectrum::lerp(white, tint, self.sheen_tint) * self.sheen;
+        let coat_alpha = Val::lerp(Val(0.1), Val(0.001), self.clearcoat_gloss);
+
+        let lobes = PrincipledLobes {
+            diffuse: Diffuse::new(self.base_color),
+            dielectric: Glossy::new(r0, Val(1.0), self.roughness).unwrap(),
+            metal: Glossy::new(self.base_color, Val(1.0), self.roughness).unwrap(),
+            transmissive: Blurry::new(self.base_color, self.refractive_index, self.roughness)
+                .unwrap(),
+            coat: Glossy::new(
+                Albedo::broadcast(Val(0.04)).unwrap(),
+                Val(1.0),
+                coat_alpha.sqrt(),
+            )
+            .unwrap(),
+        };
+        Ok(Principled {
+            base_color: self.base_color,
+            metallic: self.metallic,
+            transmission: self.transmission,
+            clearcoat: self.clearcoat,
+            sheen,
+            lobes: Box::new(lobes),
+        })
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryBuildPrincipledError {
+    #[snafu(display("{name} should be in [0, 1]"))]
+    InvalidFactor { name: &'static str },
+    #[snafu(display("roughness should be in (0, 1]"))]
+    InvalidRoughness,
+    #[snafu(display("refractive index should not be less than 1"))]
+    InvalidRefractiveIndex,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::material::primitive::Coated;
+    use crate::domain::math::algebra::Vector;
+    use crate::domain::math::geometry::{Distance, Point};
+    use crate::domain::ray::event::SurfaceSide;
+
+    use super::*;
+
+    fn intersection() -> RayIntersection {
+        RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Normal::z_direction(),
+            SurfaceSide::Front,
+        )
+    }
+
+    fn directions() -> Vec<(Direction, Direction)> {
+        let dir_out = Direction::normalize(Vector::new(Val(1.0), Val(0.0), Val(1.0))).unwrap();
+        [
+            Vector::new(Val(-1.0), Val(0.0), Val(1.0)),
+            Vector::new(Val(0.3), Val(0.5), Val(1.0)),
+            Vector::new(Val(-1.0), Val(-1.0), Val(0.2)),
+        ]
+        .into_iter()
+        .map(|v| (dir_out, Direction::normalize(v).unwrap()))
+        .collect()
+    }
+
+    #[test]
+    fn principled_builder_build_fails_when_factor_is_out_of_range() {
+        let base_color = Albedo::broadcast(Val(0.5)).unwrap();
+        assert!(matches!(
+            Principled::builder(base_color).with_sheen(Val(1.5)).build(),
+            Err(TryBuildPrincipledError::InvalidFactor { name: "sheen" }),
+        ));
+    }
+
+    #[test]
+    fn principled_bsdf_succeeds_matching_conductor_when_metallic() {
+        let base_color = Albedo::new(Val(0.9), Val(0.6), Val(0.3)).unwrap();
+        let principled = Principled::builder(base_color)
+            .with_metallic(Val(1.0))
+            .with_roughness(Val(0.3))
+            .build()
+            .unwrap();
+        let metal = Glossy::new(base_color, Val(1.0), Val(0.3)).unwrap();
+
+        let intersection = intersection();
+        for (dir_out, dir_in) in directions() {
+            assert_eq!(
+                principled.bsdf(dir_out, &intersection, dir_in),
+                metal.bsdf(dir_out, &intersection, dir_in),
+            );
+        }
+    }
+
+    #[test]
+    fn principled_bsdf_succeeds_matching_plastic_when_dielectric() {
+        let base_color = Albedo::broadcast(Val(0.5)).unwrap();
+        let principled = Principled::builder(base_color)
+            .with_roughness(Val(0.3))
+            .build()
+            .unwrap();
+        let plastic = Coated::new(Diffuse::new(base_color), Val(1.5), Val(0.3)).unwrap();
+
+        let intersection = intersection();
+        for (dir_out, dir_in) in directions() {
+            assert_eq!(
+                principled.bsdf(dir_out, &intersection, dir_in),
+                plastic.bsdf(dir_out, &intersection, dir_in),
+            );
+        }
+    }
+
+    #[test]
+    fn principled_sample_bsdf_succeeds_matching_evaluated_pdf() {
+        let principled = Principled::builder(Albedo::broadcast(Val(0.8)).unwrap())
+            .with_metallic(Val(0.3))
+            .with_transmission(Val(0.5))
+            .with_clearcoat(Val(1.0))
+            .with_sheen(Val(0.5))
+            .build()
+            .unwrap();
+        let intersection = intersection();
+        let dir = Direction::normalize(Vector::new(Val(-1.0), Val(0.0), Val(-1.0))).unwrap();
+        let ray = Ray::new(Point::new(Val(1.0), Val(0.0), Val(1.0)), dir);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..64 {
+            let sample = principled.sample_bsdf(&ray, &intersection, &mut rng);
+            let pdf = principled.pdf_bsdf(&ray, &intersection, sample.ray_next());
+            assert_eq!(sample.pdf(), pdf);
+            assert!(sample.coefficient().red() >= Val(0.0));
+        }
+    }
+}
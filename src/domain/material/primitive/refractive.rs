@@ -1,7 +1,7 @@
 use rand::prelude::*;
 use snafu::prelude::*;
 
-use crate::domain::color::core::Spectrum;
+use crate::domain::color::core::{Albedo, Spectrum};
 use crate::domain::material::def::{BsdfMaterial, BsdfMaterialExt, Material, MaterialKind};
 use crate::domain::math::geometry::Direction;
 use crate::domain::math::numeric::Val;
@@ -38,6 +38,14 @@ impl Material for Refractive {
         MaterialKind::Refractive
     }
 
+    fn uses_footprint(&self) -> bool {
+        self.albedo.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.albedo.lookup(intersection)
+    }
+
     fn shade(
         &self,
         context: &mut RtContext<'_>,
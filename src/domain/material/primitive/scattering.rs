@@ -275,6 +275,14 @@ impl Material for Scattering {
         MaterialKind::Scattering
     }
 
+    fn uses_footprint(&self) -> bool {
+        self.albedo.uses_footprint()
+    }
+
+    fn albedo(&self, intersection: &RayIntersection) -> Albedo {
+        self.albedo.lookup(intersection)
+    }
+
     fn shade(
         &self,
         context: &mut RtContext<'_>,
@@ -444,6 +452,14 @@ impl<'a> Material for BackFaceTransmissionAdapter<'a> {
         MaterialKind::Refractive
     }
 
+    fn uses_footprint(&self) -> bool {
+        self.inner.uses_footprint()
+    }
+
+    fn albedo(&self, _intersection: &RayIntersection) -> Albedo {
+        self.albedo
+    }
+
     fn shade(
         &self,
         context: &mut RtContext<'_>,
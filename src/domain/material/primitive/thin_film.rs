@@ -0,0 +1,249 @@
+use rand::prelude::*;
+use snafu::prelude::*;
+
+use crate::domain::color::core::{Albedo, SpectralChannel, Spectrum};
+use crate::domain::material::def::{BsdfMaterial, BsdfMaterialExt, Material, MaterialKind};
+use crate::domain::math::algebra::Product;
+use crate::domain::math::geometry::Direction;
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, SurfaceSide};
+use crate::domain::ray::photon::PhotonRay;
+use crate::domain::ray::util as ray_util;
+use crate::domain::renderer::{Contribution, PmContext, PmState, RtContext, RtState};
+use crate::domain::sampling::coefficient::{BsdfSample, BsdfSampling};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThinFilm {
+    thickness: Val,
+    film_ior: Val,
+    substrate_ior: Val,
+}
+
+impl ThinFilm {
+    pub fn new(
+        thickness: Val,
+        film_ior: Val,
+        substrate_ior: Val,
+    ) -> Result<Self, TryNewThinFilmError> {
+        ensure!(thickness >= Val(0.0), InvalidThicknessSnafu);
+        ensure!(film_ior > Val(0.0), InvalidRefractiveIndexSnafu);
+        ensure!(substrate_ior > Val(0.0), InvalidRefractiveIndexSnafu);
+        Ok(Self {
+            thickness,
+            film_ior,
+            substrate_ior,
+        })
+    }
+
+    pub fn reflectance(&self, cos: Val, wavelength: Val) -> Val {
+        Self::calc_airy_reflectance(
+            Val(1.0),
+            self.film_ior,
+            self.substrate_ior,
+            self.thickness,
+            cos,
+            wavelength,
+        )
+    }
+
+    fn calc_reflectance_spectrum(&self, cos: Val, side: SurfaceSide) -> Spectrum {
+        let (outer, inner) = match side {
+            SurfaceSide::Front => (Val(1.0), self.substrate_ior),
+            SurfaceSide::Back => (self.substrate_ior, Val(1.0)),
+        };
+        let [r, g, b] = SpectralChannel::ALL.map(|channel| {
+            let wavelength = channel.wavelength();
+            Self::calc_airy_reflectance(
+                outer,
+                self.film_ior,
+                inner,
+                self.thickness,
+                cos,
+                wavelength,
+            )
+        });
+        Spectrum::new(r, g, b)
+    }
+
+    fn calc_airy_reflectance(
+        n1: Val,
+        n2: Val,
+        n3: Val,
+        thickness: Val,
+        cos1: Val,
+        wavelength: Val,
+    ) -> Val {
+        let cos1 = cos1.clamp(Val(0.0), Val(1.0));
+        let sin2_1 = Val(1.0) - cos1 * cos1;
+        let cos_in = |n: Val| {
+            let sin2 = sin2_1 * (n1 / n).powi(2);
+            (sin2 <= Val(1.0)).then(|| (Val(1.0) - sin2).sqrt())
+        };
+        let (Some(cos2), Some(cos3)) = (cos_in(n2), cos_in(n3)) else {
+            return Val(1.0);
+        };
+
+        let rs = |ni: Val, ci: Val, nj: Val, cj: Val| (ni * ci - nj * cj) / (ni * ci + nj * cj);
+        let rp = |ni: Val, ci: Val, nj: Val, cj: Val| (nj * ci - ni * cj) / (nj * ci + ni * cj);
+        let delta = Val(4.0) * Val::PI * n2 * thickness * cos2 / wavelength;
+        let airy = |r12: Val, r23: Val| {
+            let cross = Val(2.0) * r12 * r23 * delta.cos();
+            let num = r12 * r12 + r23 * r23 + cross;
+            let den = Val(1.0) + (r12 * r23).powi(2) + cross;
+            num / den
+        };
+
+        let s = airy(rs(n1, cos1, n2, cos2), rs(n2, cos2, n3, cos3));
+        let p = airy(rp(n1, cos1, n2, cos2), rp(n2, cos2, n3, cos3));
+        (Val(0.5) * (s + p)).clamp(Val(0.0), Val(1.0))
+    }
+
+    fn transmit(&self, ray: &Ray, intersection: &RayIntersection) -> Option<Ray> {
+        if self.substrate_ior == Val(1.0) {
+            return Some(intersection.spawn(ray.direction()));
+        }
+        let ri = if intersection.side() == SurfaceSide::Front {
+            self.substrate_ior
+        } else {
+            self.substrate_ior.recip()
+        };
+        ray_util::pure_refract(ray, intersection, ri)
+    }
+}
+
+impl Material for ThinFilm {
+    fn kind(&self) -> MaterialKind {
+        MaterialKind::ThinFilm
+    }
+
+    fn uses_footprint(&self) -> bool {
+        false
+    }
+
+    fn albedo(&self, _intersection: &RayIntersection) -> Albedo {
+        Albedo::WHITE
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        state: RtState,
+        ray: &Ray,
+        intersection: &RayIntersection,
+    ) -> Contribution {
+        let state_next = state.with_skip_emissive(false);
+        self.shade_scattering(context, state_next, ray, intersection)
+    }
+
+    fn receive(
+        &self,
+        context: &mut PmContext<'_>,
+        state: PmState,
+        photon: &PhotonRay,
+        intersection: &RayIntersection,
+    ) {
+        let state_next = state.with_has_specular(true);
+        self.maybe_bounce_next_photon(context, state_next, photon, intersection);
+    }
+}
+
+impl BsdfMaterial for ThinFilm {
+    fn bsdf(
+        &self,
+        _dir_out: Direction,
+        _intersection: &RayIntersection,
+        _dir_in: Direction,
+    ) -> Spectrum {
+        Spectrum::zero()
+    }
+}
+
+impl BsdfSampling for ThinFilm {
+    fn sample_bsdf(
+        &self,
+        ray: &Ray,
+        intersection: &RayIntersection,
+        rng: &mut dyn RngCore,
+    ) -> BsdfSample {
+        let cos = intersection.normal().dot(-ray.direction());
+        let reflectance = self.calc_reflectance_spectrum(cos, intersection.side());
+        let prob = (reflectance.red() + reflectance.green() + reflectance.blue()) / Val(3.0);
+
+        let transmitted = (Val(rng.random()) >= prob)
+            .then(|| self.transmit(ray, intersection))
+            .flatten();
+        match transmitted {
+            Some(ray_next) => {
+                let transmittance = Spectrum::broadcast(Val(1.0)) - reflectance;
+                BsdfSample::new(ray_next, transmittance / (Val(1.0) - prob), Val(1.0))
+            }
+            None => {
+                let ray_next = ray_util::reflect(ray, intersection);
+                let coefficient = if prob > Val(0.0) {
+                    reflectance / prob
+                } else {
+                    Spectrum::zero()
+                };
+                BsdfSample::new(ray_next, coefficient, Val(1.0))
+            }
+        }
+    }
+
+    fn pdf_bsdf(&self, _ray: &Ray, _intersection: &RayIntersection, _ray_next: &Ray) -> Val {
+        Val(1.0)
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewThinFilmError {
+    #[snafu(display("film thickness is negative"))]
+    InvalidThickness,
+    #[snafu(display("refractive index is not positive"))]
+    InvalidRefractiveIndex,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find_peak_thickness(ior: Val, cos: Val, wavelength: Val) -> Val {
+        (0..=300)
+            .map(Val::from)
+            .max_by_key(|&thickness| {
+                let film = ThinFilm::new(thickness, ior, Val(1.0)).unwrap();
+                film.reflectance(cos, wavelength)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn thin_film_new_fails_when_thickness_is_negative() {
+        assert!(matches!(
+            ThinFilm::new(Val(-1.0), Val(1.33), Val(1.0)),
+            Err(TryNewThinFilmError::InvalidThickness),
+        ));
+    }
+
+    #[test]
+    fn thin_film_reflectance_succeeds_moving_peak_with_thickness() {
+        let wavelength = Val(550.0);
+        let peak = find_peak_thickness(Val(1.33), Val(1.0), wavelength);
+        let expected = wavelength / (Val(4.0) * Val(1.33));
+        assert!((peak - expected).abs() <= Val(1.0));
+
+        let film = ThinFilm::new(expected, Val(1.33), Val(1.0)).unwrap();
+        let thinner = ThinFilm::new(expected * Val(0.5), Val(1.33), Val(1.0)).unwrap();
+        assert!(film.reflectance(Val(1.0), wavelength) > thinner.reflectance(Val(1.0), wavelength));
+
+        let oblique = find_peak_thickness(Val(1.33), Val(0.5), wavelength);
+        assert!(oblique > peak);
+    }
+
+    #[test]
+    fn thin_film_reflectance_succeeds_vanishing_without_film() {
+        let film = ThinFilm::new(Val(0.0), Val(1.33), Val(1.0)).unwrap();
+        assert_eq!(film.reflectance(Val(1.0), Val(550.0)), Val(0.0));
+    }
+}
@@ -0,0 +1,174 @@
+use getset::Getters;
+use snafu::prelude::*;
+
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::geometry::Frame;
+use crate::domain::math::numeric::Val;
+
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct IesProfile {
+    vertical: Vec<Val>,
+    horizontal: Vec<Val>,
+    #[getset(skip)]
+    intensity: Vec<Val>,
+}
+
+impl IesProfile {
+    pub fn new(
+        vertical: Vec<Val>,
+        horizontal: Vec<Val>,
+        candela: Vec<Val>,
+    ) -> Result<Self, TryNewIesProfileError> {
+        ensure!(
+            !vertical.is_empty() && !horizontal.is_empty(),
+            InvalidGridSizeSnafu
+        );
+        ensure!(
+            candela.len() == vertical.len() * horizontal.len(),
+            InvalidGridSizeSnafu
+        );
+        ensure!(
+            Self::is_valid_angles(&vertical, Val(180.0)),
+            InvalidVerticalAnglesSnafu
+        );
+        ensure!(
+            Self::is_valid_angles(&horizontal, Val(360.0)),
+            InvalidHorizontalAnglesSnafu
+        );
+        ensure!(candela.iter().all(|&c| c >= Val(0.0)), InvalidCandelaSnafu);
+
+        let max = candela.iter().copied().fold(Val(0.0), Val::max);
+        ensure!(max > Val(0.0), InvalidCandelaSnafu);
+        let intensity = candela.into_iter().map(|c| c / max).collect();
+
+        Ok(Self {
+            vertical,
+            horizontal,
+            intensity,
+        })
+    }
+
+    fn is_valid_angles(angles: &[Val], max: Val) -> bool {
+        let in_range = angles.iter().all(|&a| Val(0.0) <= a && a <= max);
+        let ascending = angles.windows(2).all(|w| w[0] < w[1]);
+        in_range && ascending
+    }
+
+    pub fn lookup(&self, frame: &Frame, dir: Vector) -> Val {
+        let local = frame.to_local(dir);
+        let norm = local.norm();
+        if norm == Val(0.0) {
+            return Val(0.0);
+        }
+        let cos = (local.z() / norm).clamp(Val(-1.0), Val(1.0));
+        let vertical = cos.acos().to_degrees();
+        let horizontal = local.y().atan2(local.x()).to_degrees();
+        self.lookup_angles(vertical, horizontal.rem_euclid(Val(360.0)))
+    }
+
+    pub fn lookup_angles(&self, vertical: Val, horizontal: Val) -> Val {
+        let last_vertical = *self.vertical.last().unwrap();
+        if vertical < self.vertical[0] || vertical > last_vertical {
+            return Val(0.0);
+        }
+        let horizontal = self.fold_horizontal(horizontal);
+
+        let (i0, i1, tv) = Self::locate(&self.vertical, vertical);
+        let (j0, j1, th) = Self::locate(&self.horizontal, horizontal);
+        let at = |i: usize, j: usize| self.intensity[j * self.vertical.len() + i];
+
+        let lower = at(i0, j0) * (Val(1.0) - tv) + at(i1, j0) * tv;
+        let upper = at(i0, j1) * (Val(1.0) - tv) + at(i1, j1) * tv;
+        lower * (Val(1.0) - th) + upper * th
+    }
+
+    fn fold_horizontal(&self, horizontal: Val) -> Val {
+        let last = *self.horizontal.last().unwrap();
+        if self.horizontal.len() == 1 {
+            self.horizontal[0]
+        } else if last == Val(90.0) {
+            let half = if horizontal > Val(180.0) {
+                Val(360.0) - horizontal
+            } else {
+                horizontal
+            };
+            if half > Val(90.0) {
+                Val(180.0) - half
+            } else {
+                half
+            }
+        } else if last == Val(180.0) && horizontal > Val(180.0) {
+            Val(360.0) - horizontal
+        } else {
+            horizontal
+        }
+    }
+
+    fn locate(angles: &[Val], angle: Val) -> (usize, usize, Val) {
+        let last = angles.len() - 1;
+        if angle <= angles[0] {
+            return (0, 0, Val(0.0));
+        }
+        if angle >= angles[last] {
+            return (last, last, Val(0.0));
+        }
+        let i1 = angles.partition_point(|&a| a <= angle);
+        let i0 = i1 - 1;
+        let t = (angle - angles[i0]) / (angles[i1] - angles[i0]);
+        (i0, i1, t)
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewIesProfileError {
+    #[snafu(display("number of candela values doesn't match the angle grid"))]
+    InvalidGridSize,
+    #[snafu(display("vertical angles should be ascending and in [0, 180]"))]
+    InvalidVerticalAngles,
+    #[snafu(display("horizontal angles should be ascending and in [0, 360]"))]
+    InvalidHorizontalAngles,
+    #[snafu(display("candela values should be non-negative and not all zero"))]
+    InvalidCandela,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> IesProfile {
+        IesProfile::new(
+            vec![Val(0.0), Val(45.0), Val(90.0)],
+            vec![Val(0.0), Val(90.0)],
+            vec![
+                Val(100.0),
+                Val(50.0),
+                Val(0.0),
+                Val(100.0),
+                Val(0.0),
+                Val(0.0),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn ies_profile_new_fails_when_grid_size_mismatches() {
+        assert!(matches!(
+            IesProfile::new(vec![Val(0.0), Val(90.0)], vec![Val(0.0)], vec![Val(1.0)]),
+            Err(TryNewIesProfileError::InvalidGridSize),
+        ));
+    }
+
+    #[test]
+    fn ies_profile_lookup_angles_succeeds_interpolating_bilinearly() {
+        let profile = profile();
+        assert_eq!(profile.lookup_angles(Val(0.0), Val(0.0)), Val(1.0));
+        assert_eq!(profile.lookup_angles(Val(22.5), Val(0.0)), Val(0.75));
+        assert_eq!(profile.lookup_angles(Val(45.0), Val(45.0)), Val(0.25));
+        assert_eq!(profile.lookup_angles(Val(45.0), Val(270.0)), Val(0.0));
+        assert_eq!(profile.lookup_angles(Val(45.0), Val(180.0)), Val(0.5));
+        assert_eq!(profile.lookup_angles(Val(120.0), Val(0.0)), Val(0.0));
+    }
+}
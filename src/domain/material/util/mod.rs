@@ -1,3 +1,5 @@
 mod container;
+mod ies;
 
 pub use container::{MaterialContainer, MaterialId};
+pub use ies::{IesProfile, TryNewIesProfileError};
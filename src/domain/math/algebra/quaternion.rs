@@ -32,6 +32,46 @@ impl Quaternion {
     pub fn conjugate(self) -> Self {
         Self::new(self.0, -self.1, -self.2, -self.3)
     }
+
+    pub fn dot(self, other: Self) -> Val {
+        self.0 * other.0 + self.1 * other.1 + self.2 * other.2 + self.3 * other.3
+    }
+
+    pub fn norm(self) -> Val {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(self) -> Option<Self> {
+        let norm = self.norm();
+        (norm > Val(0.0))
+            .then(|| Self::new(self.0 / norm, self.1 / norm, self.2 / norm, self.3 / norm))
+    }
+
+    pub fn slerp(self, other: Self, t: Val) -> Self {
+        let (other, cos) = match self.dot(other) {
+            cos if cos < Val(0.0) => (Self::new(-other.0, -other.1, -other.2, -other.3), -cos),
+            cos => (other, cos),
+        };
+
+        let (s1, s2) = if cos > Val(0.9995) {
+            (Val(1.0) - t, t)
+        } else {
+            let theta = cos.min(Val(1.0)).acos();
+            let sin = theta.sin();
+            (
+                ((Val(1.0) - t) * theta).sin() / sin,
+                (t * theta).sin() / sin,
+            )
+        };
+        Self::new(
+            s1 * self.0 + s2 * other.0,
+            s1 * self.1 + s2 * other.1,
+            s1 * self.2 + s2 * other.2,
+            s1 * self.3 + s2 * other.3,
+        )
+        .normalize()
+        .expect("interpolation of unit quaternions should not be zero")
+    }
 }
 
 impl From<Vector> for Quaternion {
@@ -73,4 +113,17 @@ mod tests {
             Quaternion::new(Val(-3.5), Val(2.5), Val(-0.5), Val(-7.5)),
         );
     }
+
+    #[test]
+    fn quaternion_slerp_succeeds_taking_shorter_arc() {
+        let (s, c) = (Val::PI / Val(4.0)).sin_cos();
+        let identity = Quaternion::new(Val(1.0), Val(0.0), Val(0.0), Val(0.0));
+        let quarter = Quaternion::new(c, Val(0.0), Val(0.0), s);
+        let flipped = Quaternion::new(-c, Val(0.0), Val(0.0), -s);
+
+        let (s, c) = (Val::PI / Val(8.0)).sin_cos();
+        let expected = Quaternion::new(c, Val(0.0), Val(0.0), s);
+        assert_eq!(identity.slerp(quarter, Val(0.5)), expected);
+        assert_eq!(identity.slerp(flipped, Val(0.5)), expected);
+    }
 }
@@ -3,6 +3,7 @@ use rand_distr::UnitSphere;
 use snafu::prelude::*;
 
 use crate::domain::math::numeric::Val;
+use crate::domain::math::transformation::{Matrix4, Scaling, Shear, Transform};
 
 use super::{Product, Vector};
 
@@ -102,6 +103,28 @@ crate::impl_div_for_wrapper_vector_and_scalar!(UnitVector, Val);
 
 crate::impl_common_transformation_for_wrapper_vector!(UnitVector);
 
+impl Transform<Scaling> for UnitVector {
+    #[inline]
+    fn transform_impl(self, transformation: &Scaling) -> Self {
+        Self::normalize(self.0.transform(transformation))
+            .expect("scaling factors should be positive")
+    }
+}
+
+impl Transform<Shear> for UnitVector {
+    #[inline]
+    fn transform_impl(self, transformation: &Shear) -> Self {
+        Self::normalize(self.0.transform(transformation)).expect("shear should be invertible")
+    }
+}
+
+impl Transform<Matrix4> for UnitVector {
+    #[inline]
+    fn transform_impl(self, transformation: &Matrix4) -> Self {
+        Self::normalize(self.0.transform(transformation)).expect("matrix should be invertible")
+    }
+}
+
 #[derive(Debug, Snafu, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum TryNormalizeVectorError {
@@ -2,7 +2,9 @@ use std::iter::Sum;
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use crate::domain::math::numeric::Val;
-use crate::domain::math::transformation::{Rotation, Scaling, Transform, Translation};
+use crate::domain::math::transformation::{
+    Matrix4, Rotation, Scaling, Shear, Transform, Translation,
+};
 
 use super::{Product, Quaternion};
 
@@ -217,7 +219,20 @@ impl Transform<Rotation> for Vector {
 
 impl Transform<Scaling> for Vector {
     fn transform_impl(self, transformation: &Scaling) -> Self {
-        transformation.scale() * self
+        let f = transformation.factors();
+        Self(self.0 * f.0, self.1 * f.1, self.2 * f.2)
+    }
+}
+
+impl Transform<Shear> for Vector {
+    fn transform_impl(self, transformation: &Shear) -> Self {
+        transformation.displace(self)
+    }
+}
+
+impl Transform<Matrix4> for Vector {
+    fn transform_impl(self, transformation: &Matrix4) -> Self {
+        transformation.apply_to_vector(self)
     }
 }
 
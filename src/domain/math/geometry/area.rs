@@ -3,7 +3,9 @@ use std::ops::{Add, Mul, Sub};
 use snafu::prelude::*;
 
 use crate::domain::math::numeric::Val;
-use crate::domain::math::transformation::{Rotation, Scaling, Transform, Translation};
+use crate::domain::math::transformation::{
+    Matrix4, Rotation, Scaling, Shear, Transform, Translation,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Area(Val);
@@ -94,7 +96,22 @@ impl Transform<Rotation> for Area {
 impl Transform<Scaling> for Area {
     #[inline]
     fn transform_impl(self, transformation: &Scaling) -> Self {
-        Self(self.0 * transformation.scale().powi(2))
+        Self(self.0 * transformation.mean_scale().powi(2))
+    }
+}
+
+impl Transform<Shear> for Area {
+    #[inline]
+    fn transform_impl(self, _transformation: &Shear) -> Self {
+        self
+    }
+}
+
+impl Transform<Matrix4> for Area {
+    #[inline]
+    fn transform_impl(self, transformation: &Matrix4) -> Self {
+        let mean_scale = transformation.determinant().abs().powf(Val(3.0).recip());
+        Self(self.0 * mean_scale.powi(2))
     }
 }
 
@@ -2,6 +2,7 @@ use rand::prelude::*;
 
 use crate::domain::math::algebra::{Product, TryNormalizeVectorError, UnitVector, Vector};
 use crate::domain::math::numeric::Val;
+use crate::domain::math::transformation::{Matrix4, Scaling, Shear, Transform};
 
 use super::Normal;
 
@@ -122,3 +123,24 @@ crate::impl_commutative_mul_for_wrapper_vector_and_scalar!(Direction, Val);
 crate::impl_div_for_wrapper_vector_and_scalar!(Direction, Val);
 
 crate::impl_common_transformation_for_wrapper_vector!(Direction);
+
+impl Transform<Scaling> for Direction {
+    #[inline]
+    fn transform_impl(self, transformation: &Scaling) -> Self {
+        Self(self.0.transform(transformation))
+    }
+}
+
+impl Transform<Shear> for Direction {
+    #[inline]
+    fn transform_impl(self, transformation: &Shear) -> Self {
+        Self(self.0.transform(transformation))
+    }
+}
+
+impl Transform<Matrix4> for Direction {
+    #[inline]
+    fn transform_impl(self, transformation: &Matrix4) -> Self {
+        Self(self.0.transform(transformation))
+    }
+}
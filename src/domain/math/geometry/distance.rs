@@ -4,7 +4,9 @@ use snafu::prelude::*;
 
 use crate::domain::math::algebra::Vector;
 use crate::domain::math::numeric::Val;
-use crate::domain::math::transformation::{Rotation, Scaling, Transform, Translation};
+use crate::domain::math::transformation::{
+    Matrix4, Rotation, Scaling, Shear, Transform, Translation,
+};
 
 use super::Point;
 
@@ -111,7 +113,21 @@ impl Transform<Rotation> for Distance {
 impl Transform<Scaling> for Distance {
     #[inline]
     fn transform_impl(self, transformation: &Scaling) -> Self {
-        Self(self.0 * transformation.scale())
+        Self(self.0 * transformation.mean_scale())
+    }
+}
+
+impl Transform<Shear> for Distance {
+    #[inline]
+    fn transform_impl(self, _transformation: &Shear) -> Self {
+        self
+    }
+}
+
+impl Transform<Matrix4> for Distance {
+    #[inline]
+    fn transform_impl(self, transformation: &Matrix4) -> Self {
+        Self(self.0 * transformation.determinant().abs().powf(Val(3.0).recip()))
     }
 }
 
@@ -23,6 +23,19 @@ impl Frame {
         }
     }
 
+    pub fn from_tangent(normal: Normal, tangent: Vector) -> Self {
+        let projected = tangent - tangent.dot(normal) * normal;
+        let Ok(tangent) = UnitVector::normalize(projected) else {
+            return Self::new(normal);
+        };
+        let cross = UnitVector::normalize(normal.cross(tangent)).unwrap();
+        Self {
+            tangent,
+            cross,
+            normal,
+        }
+    }
+
     #[inline]
     pub fn to_canonical(&self, coord: Vector) -> Vector {
         coord.x() * self.tangent + coord.y() * self.cross + coord.z() * self.normal
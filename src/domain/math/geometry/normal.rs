@@ -1,5 +1,6 @@
 use crate::domain::math::algebra::{Product, TryNormalizeVectorError, UnitVector, Vector};
 use crate::domain::math::numeric::Val;
+use crate::domain::math::transformation::{Matrix4, Scaling, Shear, Transform, Transformation};
 
 use super::direction::Direction;
 
@@ -106,3 +107,28 @@ crate::impl_commutative_mul_for_wrapper_vector_and_scalar!(Normal, Val);
 crate::impl_div_for_wrapper_vector_and_scalar!(Normal, Val);
 
 crate::impl_common_transformation_for_wrapper_vector!(Normal);
+
+impl Transform<Scaling> for Normal {
+    #[inline]
+    fn transform_impl(self, transformation: &Scaling) -> Self {
+        let inverse = transformation.clone().inverse();
+        Self::normalize(self.to_vector().transform(&inverse))
+            .expect("scaling factors should be positive")
+    }
+}
+
+impl Transform<Shear> for Normal {
+    #[inline]
+    fn transform_impl(self, transformation: &Shear) -> Self {
+        Self::normalize(transformation.displace_normal(self.to_vector()))
+            .expect("shear should be invertible")
+    }
+}
+
+impl Transform<Matrix4> for Normal {
+    #[inline]
+    fn transform_impl(self, transformation: &Matrix4) -> Self {
+        Self::normalize(transformation.apply_to_normal(self.to_vector()))
+            .expect("matrix should be invertible")
+    }
+}
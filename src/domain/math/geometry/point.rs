@@ -3,7 +3,9 @@ use std::ops::{Add, Sub};
 
 use crate::domain::math::algebra::{UnitVector, Vector};
 use crate::domain::math::numeric::Val;
-use crate::domain::math::transformation::{Rotation, Scaling, Transform, Translation};
+use crate::domain::math::transformation::{
+    Matrix4, Rotation, Scaling, Shear, Transform, Translation,
+};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Point(Vector);
@@ -125,6 +127,20 @@ impl Transform<Scaling> for Point {
     }
 }
 
+impl Transform<Shear> for Point {
+    #[inline]
+    fn transform_impl(self, transformation: &Shear) -> Self {
+        Self(self.0.transform(transformation))
+    }
+}
+
+impl Transform<Matrix4> for Point {
+    #[inline]
+    fn transform_impl(self, transformation: &Matrix4) -> Self {
+        transformation.apply_to_point(self)
+    }
+}
+
 impl Transform<Translation> for Point {
     #[inline]
     fn transform_impl(self, transformation: &Translation) -> Self {
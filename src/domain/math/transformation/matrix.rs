@@ -0,0 +1,269 @@
+use std::ops::Mul;
+
+use snafu::prelude::*;
+
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::geometry::Point;
+use crate::domain::math::numeric::Val;
+
+use super::{AtomTransformation, Sequential, Transform, Transformation};
+
+type Elements = [[Val; 4]; 4];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix4 {
+    elements: Elements,
+    inverse: Elements,
+}
+
+impl Matrix4 {
+    const IDENTITY: Elements = [
+        [Val(1.0), Val(0.0), Val(0.0), Val(0.0)],
+        [Val(0.0), Val(1.0), Val(0.0), Val(0.0)],
+        [Val(0.0), Val(0.0), Val(1.0), Val(0.0)],
+        [Val(0.0), Val(0.0), Val(0.0), Val(1.0)],
+    ];
+
+    pub fn new(elements: [[Val; 4]; 4]) -> Result<Self, TryNewMatrix4Error> {
+        ensure!(elements[3] == Self::IDENTITY[3], NotAffineSnafu);
+        let inverse = Self::invert_affine(&elements).context(SingularSnafu)?;
+        Ok(Self { elements, inverse })
+    }
+
+    #[inline]
+    pub fn elements(&self) -> &[[Val; 4]; 4] {
+        &self.elements
+    }
+
+    #[inline]
+    pub fn inverse_elements(&self) -> &[[Val; 4]; 4] {
+        &self.inverse
+    }
+
+    pub fn determinant(&self) -> Val {
+        Self::determinant3(&self.elements)
+    }
+
+    pub(crate) fn apply_to_point(&self, point: Point) -> Point {
+        let m = &self.elements;
+        let (x, y, z) = (point.x(), point.y(), point.z());
+        Point::new(
+            m[0][0] * x + m[0][1] * y + m[0][2] * z + m[0][3],
+            m[1][0] * x + m[1][1] * y + m[1][2] * z + m[1][3],
+            m[2][0] * x + m[2][1] * y + m[2][2] * z + m[2][3],
+        )
+    }
+
+    pub(crate) fn apply_to_vector(&self, vector: Vector) -> Vector {
+        Self::multiply3(&self.elements, vector)
+    }
+
+    pub(crate) fn apply_to_normal(&self, normal: Vector) -> Vector {
+        let m = &self.inverse;
+        let (x, y, z) = (normal.x(), normal.y(), normal.z());
+        Vector::new(
+            m[0][0] * x + m[1][0] * y + m[2][0] * z,
+            m[0][1] * x + m[1][1] * y + m[2][1] * z,
+            m[0][2] * x + m[1][2] * y + m[2][2] * z,
+        )
+    }
+
+    fn multiply3(m: &Elements, vector: Vector) -> Vector {
+        let (x, y, z) = (vector.x(), vector.y(), vector.z());
+        Vector::new(
+            m[0][0] * x + m[0][1] * y + m[0][2] * z,
+            m[1][0] * x + m[1][1] * y + m[1][2] * z,
+            m[2][0] * x + m[2][1] * y + m[2][2] * z,
+        )
+    }
+
+    fn multiply(lhs: &Elements, rhs: &Elements) -> Elements {
+        let mut res = [[Val(0.0); 4]; 4];
+        for (i, row) in res.iter_mut().enumerate() {
+            for (j, element) in row.iter_mut().enumerate() {
+                *element = (0..4).map(|k| lhs[i][k] * rhs[k][j]).sum();
+            }
+        }
+        res
+    }
+
+    fn determinant3(m: &Elements) -> Val {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    fn invert_affine(m: &Elements) -> Option<Elements> {
+        let det = Self::determinant3(m);
+        if det == Val(0.0) {
+            return None;
+        }
+        let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| {
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+        let mut res = Self::IDENTITY;
+        res[0][0] = cofactor(1, 2, 1, 2) / det;
+        res[0][1] = -cofactor(0, 2, 1, 2) / det;
+        res[0][2] = cofactor(0, 1, 1, 2) / det;
+        res[1][0] = -cofactor(1, 2, 0, 2) / det;
+        res[1][1] = cofactor(0, 2, 0, 2) / det;
+        res[1][2] = -cofactor(0, 1, 0, 2) / det;
+        res[2][0] = cofactor(1, 2, 0, 1) / det;
+        res[2][1] = -cofactor(0, 2, 0, 1) / det;
+        res[2][2] = cofactor(0, 1, 0, 1) / det;
+
+        let translation = Vector::new(m[0][3], m[1][3], m[2][3]);
+        let inv_translation = -Self::multiply3(&res, translation);
+        res[0][3] = inv_translation.x();
+        res[1][3] = inv_translation.y();
+        res[2][3] = inv_translation.z();
+        Some(res)
+    }
+
+    fn from_columns(x: Vector, y: Vector, z: Vector, origin: Point) -> Elements {
+        [
+            [x.x(), y.x(), z.x(), origin.x()],
+            [x.y(), y.y(), z.y(), origin.y()],
+            [x.z(), y.z(), z.z(), origin.z()],
+            Self::IDENTITY[3],
+        ]
+    }
+
+    fn bake(transformation: &Sequential) -> Elements {
+        let [x, y, z] = [
+            Vector::new(Val(1.0), Val(0.0), Val(0.0)),
+            Vector::new(Val(0.0), Val(1.0), Val(0.0)),
+            Vector::new(Val(0.0), Val(0.0), Val(1.0)),
+        ]
+        .map(|axis| axis.transform(transformation));
+        let origin = Point::default().transform(transformation);
+        Self::from_columns(x, y, z, origin)
+    }
+}
+
+impl Default for Matrix4 {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            elements: Self::IDENTITY,
+            inverse: Self::IDENTITY,
+        }
+    }
+}
+
+impl From<Sequential> for Matrix4 {
+    fn from(transformation: Sequential) -> Self {
+        let elements = Self::bake(&transformation);
+        let inverse = Self::bake(&transformation.inverse());
+        Self { elements, inverse }
+    }
+}
+
+impl Mul for Matrix4 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self {
+            elements: Self::multiply(&self.elements, &rhs.elements),
+            inverse: Self::multiply(&rhs.inverse, &self.inverse),
+        }
+    }
+}
+
+impl Transformation for Matrix4 {
+    #[inline]
+    fn is_identity(&self) -> bool {
+        self.elements == Self::IDENTITY
+    }
+
+    #[inline]
+    fn inverse(self) -> Self {
+        Self {
+            elements: self.inverse,
+            inverse: self.elements,
+        }
+    }
+}
+
+impl AtomTransformation for Matrix4 {}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewMatrix4Error {
+    #[snafu(display("last row of an affine matrix should be (0, 0, 0, 1)"))]
+    NotAffine,
+    #[snafu(display("matrix is not invertible"))]
+    Singular,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::{Direction, Normal};
+    use crate::domain::math::transformation::{Rotation, Scaling, Shear, Translation};
+
+    use super::*;
+
+    fn chain() -> Sequential {
+        Sequential::default()
+            .with_scaling(Scaling::new(Val(2.0), Val(0.5), Val(1.5)).unwrap())
+            .with_shear(Shear::new(2, 0, Val(0.3)).unwrap())
+            .with_rotation(Rotation::new(
+                Direction::x_direction(),
+                Direction::normalize(Vector::new(Val(1.0), Val(2.0), Val(-1.0))).unwrap(),
+                Val(0.7),
+            ))
+            .with_translation(Translation::new(Vector::new(Val(3.0), Val(-1.0), Val(2.0))))
+    }
+
+    #[test]
+    fn matrix4_from_sequential_succeeds_matching_atoms() {
+        let sequential = chain();
+        let matrix = Matrix4::from(sequential.clone());
+
+        let point = Point::new(Val(0.4), Val(-1.2), Val(2.5));
+        assert_eq!(point.transform(&matrix), point.transform(&sequential));
+        let baked_inv = point.transform(&matrix.clone().inverse());
+        assert_eq!(baked_inv, point.transform(&sequential.clone().inverse()));
+
+        let vector = Vector::new(Val(-0.3), Val(0.8), Val(1.1));
+        assert_eq!(vector.transform(&matrix), vector.transform(&sequential));
+
+        let normal = Normal::normalize(Vector::new(Val(1.0), Val(1.0), Val(-2.0))).unwrap();
+        assert_eq!(normal.transform(&matrix), normal.transform(&sequential));
+
+        assert_eq!(Matrix4::new(*matrix.elements()).unwrap(), matrix);
+    }
+
+    #[test]
+    fn matrix4_mul_succeeds_composing_transformations() {
+        let first = Matrix4::from(chain());
+        let second = Matrix4::from(
+            Sequential::default().with_translation(Translation::new(Vector::new(
+                Val(1.0),
+                Val(0.0),
+                Val(0.0),
+            ))),
+        );
+        let composed = second.clone() * first.clone();
+
+        let point = Point::new(Val(0.4), Val(-1.2), Val(2.5));
+        assert_eq!(
+            point.transform(&composed),
+            point.transform(&first).transform(&second),
+        );
+        assert_eq!(
+            point.transform(&composed).transform(&composed.inverse()),
+            point
+        );
+    }
+
+    #[test]
+    fn matrix4_new_fails_when_matrix_is_singular() {
+        let mut elements = Matrix4::IDENTITY;
+        elements[1][1] = Val(0.0);
+        assert!(matches!(
+            Matrix4::new(elements),
+            Err(TryNewMatrix4Error::Singular),
+        ));
+    }
+}
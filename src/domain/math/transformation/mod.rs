@@ -1,11 +1,15 @@
 mod def;
+mod matrix;
 mod rotation;
 mod scaling;
 mod sequential;
+mod shear;
 mod translation;
 
 pub use def::{AtomTransformation, Transform, Transformation};
-pub use rotation::Rotation;
+pub use matrix::{Matrix4, TryNewMatrix4Error};
+pub use rotation::{Rotation, TryNewRotationError};
 pub use scaling::{Scaling, TryNewScalingError};
 pub use sequential::Sequential;
+pub use shear::{Shear, TryNewShearError};
 pub use translation::Translation;
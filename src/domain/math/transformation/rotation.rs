@@ -1,3 +1,5 @@
+use snafu::prelude::*;
+
 use crate::domain::math::algebra::{Product, Quaternion};
 use crate::domain::math::geometry::Direction;
 use crate::domain::math::numeric::Val;
@@ -29,6 +31,11 @@ impl Rotation {
         }
     }
 
+    pub fn from_quaternion(quaternion: Quaternion) -> Result<Self, TryNewRotationError> {
+        let quaternion = quaternion.normalize().context(ZeroQuaternionSnafu)?;
+        Ok(Self { quaternion })
+    }
+
     fn get_rotation(axis: Direction, angle: Val) -> Quaternion {
         let (sa, ca) = (Val(0.5) * angle).sin_cos();
         Quaternion::new(ca, sa * axis.x(), sa * axis.y(), sa * axis.z())
@@ -70,9 +77,17 @@ impl Transformation for Rotation {
 
 impl AtomTransformation for Rotation {}
 
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewRotationError {
+    #[snafu(display("quaternion of a rotation should not be zero"))]
+    ZeroQuaternion,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::domain::math::algebra::Vector;
+    use crate::domain::math::transformation::Transform;
 
     use super::*;
 
@@ -113,4 +128,31 @@ mod tests {
             ),
         );
     }
+
+    #[test]
+    fn rotation_from_quaternion_succeeds_rotating_halfway_after_slerp() {
+        let quarter = Rotation::new(Direction::x_direction(), Direction::y_direction(), Val(0.0));
+        let halfway = Quaternion::new(Val(1.0), Val(0.0), Val(0.0), Val(0.0))
+            .slerp(quarter.quaternion(), Val(0.5));
+        let rotation = Rotation::from_quaternion(halfway).unwrap();
+
+        let rotated = Vector::new(Val(1.0), Val(0.0), Val(0.0)).transform(&rotation);
+        let expected = Vector::new(Val(0.5).sqrt(), Val(0.5).sqrt(), Val(0.0));
+        assert_eq!(rotated, expected);
+    }
+
+    #[test]
+    fn rotation_from_quaternion_succeeds_normalizing_input() {
+        let rotation =
+            Rotation::from_quaternion(Quaternion::new(Val(0.0), Val(0.0), Val(0.0), Val(2.0)))
+                .unwrap();
+        assert_eq!(
+            rotation.quaternion(),
+            Quaternion::new(Val(0.0), Val(0.0), Val(0.0), Val(1.0)),
+        );
+        assert!(
+            Rotation::from_quaternion(Quaternion::new(Val(0.0), Val(0.0), Val(0.0), Val(0.0)))
+                .is_err()
+        );
+    }
 }
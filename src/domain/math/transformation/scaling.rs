@@ -1,6 +1,7 @@
 use getset::CopyGetters;
 use snafu::prelude::*;
 
+use crate::domain::math::algebra::Vector;
 use crate::domain::math::numeric::Val;
 
 use super::{AtomTransformation, Transformation};
@@ -8,16 +9,42 @@ use super::{AtomTransformation, Transformation};
 #[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
 #[getset(get_copy = "pub")]
 pub struct Scaling {
-    scale: Val,
+    factors: Vector,
 }
 
 impl Scaling {
-    const IDENTITY_SCALE: Val = Val(1.0);
+    const IDENTITY_FACTORS: Vector = Vector::new(Val(1.0), Val(1.0), Val(1.0));
+
+    #[inline]
+    pub fn new(x: Val, y: Val, z: Val) -> Result<Self, TryNewScalingError> {
+        ensure!(
+            x > Val(0.0) && y > Val(0.0) && z > Val(0.0),
+            InvalidScaleSnafu
+        );
+        Ok(Self {
+            factors: Vector::new(x, y, z),
+        })
+    }
 
     #[inline]
     pub fn uniform(scale: Val) -> Result<Self, TryNewScalingError> {
-        ensure!(scale > Val(0.0), InvalidScaleSnafu);
-        Ok(Self { scale })
+        Self::new(scale, scale, scale)
+    }
+
+    #[inline]
+    pub fn is_uniform(&self) -> bool {
+        let f = self.factors;
+        f.x() == f.y() && f.y() == f.z()
+    }
+
+    #[inline]
+    pub fn determinant(&self) -> Val {
+        self.factors.x() * self.factors.y() * self.factors.z()
+    }
+
+    #[inline]
+    pub fn mean_scale(&self) -> Val {
+        self.determinant().powf(Val(3.0).recip())
     }
 }
 
@@ -25,7 +52,7 @@ impl Default for Scaling {
     #[inline]
     fn default() -> Self {
         Self {
-            scale: Self::IDENTITY_SCALE,
+            factors: Self::IDENTITY_FACTORS,
         }
     }
 }
@@ -33,13 +60,14 @@ impl Default for Scaling {
 impl Transformation for Scaling {
     #[inline]
     fn is_identity(&self) -> bool {
-        self.scale == Self::IDENTITY_SCALE
+        self.factors == Self::IDENTITY_FACTORS
     }
 
     #[inline]
     fn inverse(self) -> Self {
+        let f = self.factors;
         Self {
-            scale: self.scale.recip(),
+            factors: Vector::new(f.x().recip(), f.y().recip(), f.z().recip()),
         }
     }
 }
@@ -1,12 +1,18 @@
 use getset::{CopyGetters, Getters, WithSetters};
 
-use super::{Rotation, Scaling, Transform, Transformation, Translation};
+use crate::domain::math::algebra::{Product, Vector};
+use crate::domain::math::geometry::{Direction, Frame, Normal};
+use crate::domain::math::numeric::Val;
+
+use super::{Rotation, Scaling, Shear, Transform, Transformation, Translation};
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Getters, CopyGetters, WithSetters)]
 pub struct Sequential {
     #[getset(get = "pub", set_with = "pub")]
     scaling: Scaling,
     #[getset(get = "pub", set_with = "pub")]
+    shear: Shear,
+    #[getset(get = "pub", set_with = "pub")]
     rotation: Rotation,
     #[getset(get = "pub", set_with = "pub")]
     translation: Translation,
@@ -14,14 +20,65 @@ pub struct Sequential {
     inverted: bool,
 }
 
+impl Sequential {
+    pub fn interpolate(&self, other: &Self, t: Val) -> Self {
+        let factors = Vector::lerp(self.scaling.factors(), other.scaling.factors(), t);
+        let shear = if (self.shear.axis(), self.shear.source())
+            == (other.shear.axis(), other.shear.source())
+        {
+            let factor = Val::lerp(self.shear.factor(), other.shear.factor(), t);
+            Shear::new(self.shear.axis(), self.shear.source(), factor)
+                .expect("axes are taken from a valid shear")
+        } else {
+            self.shear.clone()
+        };
+        let quaternion = (self.rotation.quaternion()).slerp(other.rotation.quaternion(), t);
+        let displacement = Vector::lerp(
+            self.translation.displacement(),
+            other.translation.displacement(),
+            t,
+        );
+        Self {
+            scaling: Scaling::new(factors.x(), factors.y(), factors.z())
+                .expect("factors should be positive"),
+            shear,
+            rotation: Rotation::from(quaternion),
+            translation: Translation::new(displacement),
+            inverted: self.inverted,
+        }
+    }
+
+    pub fn calc_area_ratio(&self, normal: Normal) -> Val {
+        let frame = Frame::new(normal);
+        let tangent = frame.tangent().to_vector().transform(self);
+        let cross = frame.cross().to_vector().transform(self);
+        tangent.cross(cross).norm()
+    }
+
+    pub fn calc_solid_angle_ratio(&self, direction: Direction) -> Val {
+        let [x, y, z] = [
+            Vector::new(Val(1.0), Val(0.0), Val(0.0)),
+            Vector::new(Val(0.0), Val(1.0), Val(0.0)),
+            Vector::new(Val(0.0), Val(0.0), Val(1.0)),
+        ]
+        .map(|axis| axis.transform(self));
+        let determinant = x.dot(y.cross(z)).abs();
+        determinant / direction.to_vector().transform(self).norm().powi(3)
+    }
+}
+
 impl Transformation for Sequential {
     fn is_identity(&self) -> bool {
-        self.scaling.is_identity() && self.rotation.is_identity() && self.translation.is_identity()
+        self.scaling.is_identity()
+            && self.shear.is_identity()
+            && self.rotation.is_identity()
+            && self.translation.is_identity()
     }
 
     fn inverse(self) -> Self {
         Self {
             scaling: self.scaling.inverse(),
+            shear: self.shear.inverse(),
             rotation: self.rotation.inverse(),
             translation: self.translation.inverse(),
             inverted: !self.inverted,
@@ -32,6 +89,7 @@ impl Transformation for Sequential {
 impl<T> Transform<Sequential> for T
 where
     Self: Transform<Scaling>,
+    Self: Transform<Shear>,
     Self: Transform<Rotation>,
     Self: Transform<Translation>,
 {
@@ -39,11 +97,60 @@ where
         if transformation.inverted {
             self.transform(transformation.translation())
                 .transform(transformation.rotation())
+                .transform(transformation.shear())
                 .transform(transformation.scaling())
         } else {
             self.transform(transformation.scaling())
+                .transform(transformation.shear())
                 .transform(transformation.rotation())
                 .transform(transformation.translation())
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::Point;
+
+    use super::*;
+
+    #[test]
+    fn sequential_transform_succeeds_shearing_unit_cube() {
+        let shear = Shear::new(0, 1, Val(0.5)).unwrap();
+        let tr = Sequential::default().with_shear(shear);
+
+        let top = Point::new(Val(1.0), Val(1.0), Val(1.0)).transform(&tr);
+        assert_eq!(top, Point::new(Val(1.5), Val(1.0), Val(1.0)));
+        for (x, z) in [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)] {
+            let bottom = Point::new(Val(x), Val(0.0), Val(z));
+            assert_eq!(bottom.transform(&tr), bottom);
+        }
+        assert_eq!(
+            top.transform(&tr.inverse()),
+            Point::new(Val(1.0), Val(1.0), Val(1.0))
+        );
+    }
+
+    #[test]
+    fn sequential_transform_succeeds_keeping_sheared_normals_perpendicular() {
+        let shear = Shear::new(0, 1, Val(0.5)).unwrap();
+        let tr = Sequential::default().with_shear(shear);
+
+        let side = Vector::new(Val(0.0), Val(1.0), Val(0.0)).transform(&tr);
+        let normal = Normal::x_direction().transform(&tr);
+        assert_eq!(side.dot(normal), Val(0.0));
+        assert_eq!(tr.calc_area_ratio(Normal::y_direction()), Val(1.0));
+    }
+
+    #[test]
+    fn sequential_calc_area_ratio_succeeds_given_scaling() {
+        let scaling = Scaling::new(Val(2.0), Val(3.0), Val(4.0)).unwrap();
+        let tr = Sequential::default().with_scaling(scaling);
+        assert_eq!(tr.calc_area_ratio(Normal::z_direction()), Val(6.0));
+        assert_eq!(tr.calc_area_ratio(Normal::x_direction()), Val(12.0));
+        assert_eq!(
+            tr.calc_solid_angle_ratio(Direction::y_direction()),
+            Val(24.0) / Val(27.0),
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use getset::CopyGetters;
+use snafu::prelude::*;
+
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::numeric::Val;
+
+use super::{AtomTransformation, Transformation};
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct Shear {
+    axis: usize,
+    source: usize,
+    factor: Val,
+}
+
+impl Shear {
+    pub fn new(axis: usize, source: usize, factor: Val) -> Result<Self, TryNewShearError> {
+        ensure!(axis < 3 && source < 3, InvalidAxisSnafu);
+        ensure!(axis != source, IdenticalAxesSnafu);
+        Ok(Self {
+            axis,
+            source,
+            factor,
+        })
+    }
+
+    pub(crate) fn displace(&self, vector: Vector) -> Vector {
+        Self::add_to_axis(vector, self.axis, self.factor * vector.axis(self.source))
+    }
+
+    pub(crate) fn displace_normal(&self, vector: Vector) -> Vector {
+        Self::add_to_axis(vector, self.source, -self.factor * vector.axis(self.axis))
+    }
+
+    fn add_to_axis(vector: Vector, axis: usize, offset: Val) -> Vector {
+        match axis {
+            0 => Vector::new(vector.x() + offset, vector.y(), vector.z()),
+            1 => Vector::new(vector.x(), vector.y() + offset, vector.z()),
+            2 => Vector::new(vector.x(), vector.y(), vector.z() + offset),
+            _ => unreachable!("axis should be in [0, 3)"),
+        }
+    }
+}
+
+impl Default for Shear {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            axis: 0,
+            source: 1,
+            factor: Val(0.0),
+        }
+    }
+}
+
+impl Transformation for Shear {
+    #[inline]
+    fn is_identity(&self) -> bool {
+        self.factor == Val(0.0)
+    }
+
+    #[inline]
+    fn inverse(self) -> Self {
+        Self {
+            factor: -self.factor,
+            ..self
+        }
+    }
+}
+
+impl AtomTransformation for Shear {}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewShearError {
+    #[snafu(display("shear axes should be in [0, 3)"))]
+    InvalidAxis,
+    #[snafu(display("shear should not displace an axis along itself"))]
+    IdenticalAxes,
+}
@@ -1,7 +1,10 @@
 use enum_dispatch::enum_dispatch;
+use rand::prelude::*;
 
 use crate::domain::color::core::Spectrum;
-use crate::domain::medium::primitive::{HenyeyGreenstein, Isotropic, Vacuum};
+use crate::domain::medium::primitive::{
+    Atmospheric, GridMedium, HenyeyGreenstein, Isotropic, Vacuum,
+};
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::RaySegment;
 use crate::domain::renderer::{Contribution, RtContext, RtState};
@@ -11,6 +14,8 @@ use super::{Medium, MediumKind};
 macro_rules! impl_dispatch {
     ($type:tt, $self:ident.$method:ident($($arg:ident),*)) => {
         match $self {
+            $type::Atmospheric(s) => s.$method($($arg),*),
+            $type::Grid(s) => s.$method($($arg),*),
             $type::HenyeyGreenstein(s) => s.$method($($arg),*),
             $type::Isotropic(s) => s.$method($($arg),*),
             $type::Vacuum(s) => s.$method($($arg),*),
@@ -31,6 +36,8 @@ macro_rules! impl_from_ref_for_variant {
 #[enum_dispatch(Medium)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DynMedium {
+    Atmospheric(Atmospheric),
+    Grid(GridMedium),
     HenyeyGreenstein(HenyeyGreenstein),
     Isotropic(Isotropic),
     Vacuum(Vacuum),
@@ -44,6 +51,8 @@ impl<'a> From<&'a DynMedium> for RefDynMedium<'a> {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RefDynMedium<'a> {
+    Atmospheric(&'a Atmospheric),
+    Grid(&'a GridMedium),
     HenyeyGreenstein(&'a HenyeyGreenstein),
     Isotropic(&'a Isotropic),
     Vacuum(&'a Vacuum),
@@ -54,8 +63,8 @@ impl<'a> Medium for RefDynMedium<'a> {
         impl_dispatch!(Self, self.kind())
     }
 
-    fn transmittance(&self, ray: &Ray, segment: &RaySegment) -> Spectrum {
-        impl_dispatch!(Self, self.transmittance(ray, segment))
+    fn transmittance(&self, ray: &Ray, segment: &RaySegment, rng: &mut dyn RngCore) -> Spectrum {
+        impl_dispatch!(Self, self.transmittance(ray, segment, rng))
     }
 
     fn shade(
@@ -69,6 +78,13 @@ impl<'a> Medium for RefDynMedium<'a> {
     }
 }
 
+impl<'a> From<&'a GridMedium> for RefDynMedium<'a> {
+    fn from(value: &'a GridMedium) -> Self {
+        Self::Grid(value)
+    }
+}
+
+impl_from_ref_for_variant!('a, RefDynMedium<'a>, Atmospheric);
 impl_from_ref_for_variant!('a, RefDynMedium<'a>, HenyeyGreenstein);
 impl_from_ref_for_variant!('a, RefDynMedium<'a>, Isotropic);
 impl_from_ref_for_variant!('a, RefDynMedium<'a>, Vacuum);
@@ -1,19 +1,126 @@
+use rand::prelude::*;
+
+use crate::domain::color::core::Spectrum;
 use crate::domain::math::geometry::{Direction, Distance};
+use crate::domain::math::numeric::DisRange;
 use crate::domain::math::numeric::Val;
+use crate::domain::medium::util::AggregateMedium;
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::{RayScattering, RaySegment};
 use crate::domain::ray::util::VisibilityTester;
 use crate::domain::renderer::{Contribution, RtContext, RtState};
 use crate::domain::sampling::distance::{
-    DistanceSample, DistanceSampling, EquiAngularDistanceSampler, ExponentialDistanceSampler,
+    DistanceSample, DistanceSampling, EquiAngularDistanceSampler, SpectralDistanceSampler,
 };
 use crate::domain::sampling::light::LightSampling;
 use crate::domain::sampling::phase::{PhaseSample, PhaseSampling};
 use crate::domain::sampling::point::PointSample;
 
-use super::HomogeneousMedium;
+use super::{HomogeneousMedium, Medium};
 
 pub trait HomogeneousMediumExt: HomogeneousMedium {
+    fn transmittance_over(&self, distance: Distance) -> Spectrum {
+        let sigma_t = self.sigma_t();
+        Spectrum::new(
+            (-sigma_t.red() * distance.value()).exp(),
+            (-sigma_t.green() * distance.value()).exp(),
+            (-sigma_t.blue() * distance.value()).exp(),
+        )
+    }
+
+    fn sample_distance(
+        &self,
+        ray: &Ray,
+        segment: &RaySegment,
+        rng: &mut dyn RngCore,
+    ) -> DistanceSample {
+        SpectralDistanceSampler::new(self.sigma_t()).sample_distance(ray, segment, rng)
+    }
+
+    fn shade_anisotropic(
+        &self,
+        context: &mut RtContext<'_>,
+        ray: &Ray,
+        segment: &RaySegment,
+    ) -> Contribution
+    where
+        Self: Sized,
+    {
+        let light = context.entity_scene().get_lights();
+        let exp_sampler = SpectralDistanceSampler::new(self.sigma_t());
+
+        let light_surfaces = context.entity_scene().get_light_surfaces();
+        let point_lights = self.shade_point_lights(context, ray, segment);
+        let Some(preselected) = light_surfaces.sample_point(*context.rng()) else {
+            return point_lights;
+        };
+        let ea_sampler = EquiAngularDistanceSampler::new(preselected.point());
+
+        let exp_sample = exp_sampler.sample_distance(ray, segment, *context.rng());
+        let ea_sample = ea_sampler.sample_distance(ray, segment, *context.rng());
+        let exp_scattering = exp_sample.scattering();
+        let ea_scattering = ea_sample.scattering();
+
+        let phase_exp_sample = self.sample_phase(ray, exp_scattering, *context.rng());
+        let phase_ea_sample = self.sample_phase(ray, ea_scattering, *context.rng());
+
+        let exp_light_contribution = {
+            let radiance = self.shade_light_using_light_sampling(
+                context,
+                ray,
+                segment,
+                &exp_sample,
+                &preselected,
+            );
+            radiance
+                * Self::calc_exp_weight(ray, segment, &exp_sample, &ea_sampler)
+                * Self::calc_light_weight(ray, exp_scattering, &preselected, light, self)
+        };
+
+        let ea_light_contribution = {
+            let radiance = self.shade_light_using_light_sampling(
+                context,
+                ray,
+                segment,
+                &ea_sample,
+                &preselected,
+            );
+            radiance
+                * Self::calc_ea_weight(ray, segment, &ea_sample, &exp_sampler)
+                * Self::calc_light_weight(ray, ea_scattering, &preselected, light, self)
+        };
+
+        let exp_phase_contribution = {
+            let radiance = self.shade_light_using_phase_sampling(
+                context,
+                ray,
+                segment,
+                &exp_sample,
+                &phase_exp_sample,
+            );
+            radiance
+                * Self::calc_exp_weight(ray, segment, &exp_sample, &ea_sampler)
+                * Self::calc_phase_weight(&phase_exp_sample, light)
+        };
+
+        let ea_phase_contribution = {
+            let radiance = self.shade_light_using_phase_sampling(
+                context,
+                ray,
+                segment,
+                &ea_sample,
+                &phase_ea_sample,
+            );
+            radiance
+                * Self::calc_ea_weight(ray, segment, &ea_sample, &exp_sampler)
+                * Self::calc_phase_weight(&phase_ea_sample, light)
+        };
+
+        let light_contribution = exp_light_contribution + ea_light_contribution;
+        let phase_contribution = exp_phase_contribution + ea_phase_contribution;
+        light_contribution + phase_contribution + point_lights
+    }
+
     fn shade_light_using_light_sampling(
         &self,
         context: &mut RtContext<'_>,
@@ -40,12 +147,19 @@ pub trait HomogeneousMediumExt: HomogeneousMedium {
 
         let ray_next = light_sample.ray_next();
         let vtester = VisibilityTester::new(scene, ray_next);
-        let Some(target) = vtester.test(light_sample.distance(), light_sample.shape_id()) else {
+        let Some(shape_id) = light_sample.shape_id() else {
+            return Contribution::new();
+        };
+        let Some(target) = vtester.test(light_sample.distance(), shape_id) else {
             return Contribution::new();
         };
 
         let length = Distance::new(scattering.distance() - segment.start()).unwrap();
-        let tr = self.transmittance(ray, &RaySegment::new(segment.start(), length));
+        let tr = self.transmittance(
+            ray,
+            &RaySegment::new(segment.start(), length),
+            *context.rng(),
+        );
 
         let phase = self.phase(-ray.direction(), ray_next.direction());
 
@@ -57,6 +171,83 @@ pub trait HomogeneousMediumExt: HomogeneousMedium {
         self.sigma_s() * tr * phase * radiance * pdf_recip
     }
 
+    fn shade_point_lights(
+        &self,
+        context: &mut RtContext<'_>,
+        ray: &Ray,
+        segment: &RaySegment,
+    ) -> Contribution {
+        let lights = context.entity_scene().get_point_lights();
+        if lights.is_empty() {
+            return Contribution::new();
+        }
+        let light = lights[context.rng().random_range(0..lights.len())].as_ref();
+        let Some(position) = light.position() else {
+            return Contribution::new();
+        };
+        let select_prob = Val::from(lights.len()).recip();
+
+        let exp_sampler = SpectralDistanceSampler::new(self.sigma_t());
+        let exp_sample = exp_sampler.sample_distance(ray, segment, *context.rng());
+        let exp_radiance = self.shade_point_light(context, ray, segment, &exp_sample, light);
+        if !context.config().equi_angular_sampling() {
+            return exp_radiance * select_prob.recip();
+        }
+
+        let ea_sampler = EquiAngularDistanceSampler::new(position);
+        let ea_sample = ea_sampler.sample_distance(ray, segment, *context.rng());
+        let ea_radiance = self.shade_point_light(context, ray, segment, &ea_sample, light);
+
+        let exp_weight = Self::calc_exp_weight(ray, segment, &exp_sample, &ea_sampler);
+        let ea_weight = Self::calc_ea_weight(ray, segment, &ea_sample, &exp_sampler);
+        (exp_radiance * exp_weight + ea_radiance * ea_weight) * select_prob.recip()
+    }
+
+    fn shade_point_light(
+        &self,
+        context: &mut RtContext<'_>,
+        ray: &Ray,
+        segment: &RaySegment,
+        distance_sample: &DistanceSample,
+        light: &dyn LightSampling,
+    ) -> Contribution {
+        let pdf_distance = distance_sample.pdf();
+        if pdf_distance == Val(0.0) {
+            return Contribution::new();
+        }
+        let scattering = distance_sample.scattering();
+        let Some(light_sample) = light.sample_light_volume(scattering, None, *context.rng()) else {
+            return Contribution::new();
+        };
+        let Some(radiance) = light_sample.radiance() else {
+            return Contribution::new();
+        };
+
+        let ray_next = light_sample.ray_next();
+        let vtester = VisibilityTester::new(context.entity_scene(), ray_next);
+        if !vtester.test_unblocked(light_sample.distance()) {
+            return Contribution::new();
+        }
+
+        let volume_scene = context.volume_scene();
+        let range = DisRange::positive().shrink_end(light_sample.distance());
+        let segments = volume_scene.find_segments(ray_next, range);
+        let aggregator = AggregateMedium::new(volume_scene, &segments);
+        let tr_light = aggregator.transmittance(ray_next, &RaySegment::from(range), *context.rng());
+
+        let length = Distance::new(scattering.distance() - segment.start()).unwrap();
+        let tr = self.transmittance(
+            ray,
+            &RaySegment::new(segment.start(), length),
+            *context.rng(),
+        );
+        let phase = self.phase(-ray.direction(), ray_next.direction());
+
+        let pdf_recip = (pdf_distance * light_sample.pdf()).recip();
+        let radiance = self.sigma_s() * tr * phase * tr_light * radiance * pdf_recip;
+        Contribution::from_light(radiance)
+    }
+
     fn shade_light_using_phase_sampling(
         &self,
         context: &mut RtContext<'_>,
@@ -80,7 +271,11 @@ pub trait HomogeneousMediumExt: HomogeneousMedium {
 
         let scattering = distance_sample.scattering();
         let length = Distance::new(scattering.distance() - segment.start()).unwrap();
-        let tr = self.transmittance(ray, &RaySegment::new(segment.start(), length));
+        let tr = self.transmittance(
+            ray,
+            &RaySegment::new(segment.start(), length),
+            *context.rng(),
+        );
 
         let ray_next = phase_sample.ray_next();
         let phase = self.phase(-ray.direction(), ray_next.direction());
@@ -109,7 +304,7 @@ pub trait HomogeneousMediumExt: HomogeneousMedium {
         ray: &Ray,
         segment: &RaySegment,
         ea_dis_sampler: &DistanceSample,
-        exp_sampler: &ExponentialDistanceSampler,
+        exp_sampler: &dyn DistanceSampling,
     ) -> Val {
         let distance = ea_dis_sampler.distance();
         let pdf2_ea = ea_dis_sampler.pdf().powi(2);
@@ -129,10 +324,8 @@ pub trait HomogeneousMediumExt: HomogeneousMedium {
             return Val(0.0);
         };
         let ray_next = scattering.spawn(direction);
-        let pdf2_light = preselected_light.pdf()
-            * light_sampler
-                .pdf_light_volume(&ray_next, Some(preselected_light))
-                .powi(2);
+        let pdf_light = light_sampler.pdf_light_volume(&ray_next, Some(preselected_light));
+        let pdf2_light = (preselected_light.pdf() * pdf_light).powi(2);
         let pdf2_phase = phase_sampler
             .pdf_phase(-ray.direction(), ray_next.direction())
             .powi(2);
@@ -148,3 +341,50 @@ pub trait HomogeneousMediumExt: HomogeneousMedium {
 }
 
 impl<M> HomogeneousMediumExt for M where M: HomogeneousMedium {}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::core::Albedo;
+    use crate::domain::math::geometry::Point;
+    use crate::domain::medium::primitive::Isotropic;
+
+    use super::*;
+
+    #[test]
+    fn homogeneous_medium_ext_transmittance_over_succeeds_at_mean_free_path() {
+        let medium = Isotropic::new(
+            Albedo::broadcast(Val(0.5)).unwrap(),
+            Spectrum::broadcast(Val(2.0)),
+        )
+        .unwrap();
+        let transmittance = medium.transmittance_over(Distance::new(Val(2.0)).unwrap());
+        assert_eq!(transmittance.red(), Val(-1.0).exp());
+        assert_eq!(transmittance.blue(), Val(-1.0).exp());
+        assert_eq!(
+            medium.transmittance_over(Distance::zero()).green(),
+            Val(1.0)
+        );
+    }
+
+    #[test]
+    fn homogeneous_medium_ext_sample_distance_succeeds_averaging_mean_free_path() {
+        let medium = Isotropic::new(
+            Albedo::broadcast(Val(0.5)).unwrap(),
+            Spectrum::broadcast(Val(2.0)),
+        )
+        .unwrap();
+        let ray = Ray::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Direction::x_direction(),
+        );
+        let segment = RaySegment::new(Distance::zero(), Distance::new(Val(1000.0)).unwrap());
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut mean = Val(0.0);
+        for _ in 0..10000 {
+            let sample = medium.sample_distance(&ray, &segment, &mut rng);
+            mean += sample.distance().value() / Val(10000.0);
+        }
+        assert!((mean - Val(2.0)).abs() < Val(0.1), "{mean:?}");
+    }
+}
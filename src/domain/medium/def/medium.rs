@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 
 use enum_dispatch::enum_dispatch;
+use rand::prelude::*;
 
 use crate::domain::color::core::Spectrum;
 use crate::domain::math::geometry::Direction;
@@ -16,7 +17,7 @@ use super::DynMedium;
 pub trait Medium: Send + Sync {
     fn kind(&self) -> MediumKind;
 
-    fn transmittance(&self, ray: &Ray, segment: &RaySegment) -> Spectrum;
+    fn transmittance(&self, ray: &Ray, segment: &RaySegment, rng: &mut dyn RngCore) -> Spectrum;
 
     fn shade(
         &self,
@@ -30,11 +31,15 @@ pub trait Medium: Send + Sync {
 pub trait HomogeneousMedium: Medium + PhaseSampling {
     fn sigma_s(&self) -> Spectrum;
 
+    fn sigma_t(&self) -> Spectrum;
+
     fn phase(&self, dir_out: Direction, dir_in: Direction) -> Spectrum;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum MediumKind {
+    Atmospheric,
+    Grid,
     HenyeyGreenstein,
     Isotropic,
     Vacuum,
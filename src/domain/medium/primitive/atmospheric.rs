@@ -0,0 +1,178 @@
+use rand::prelude::*;
+use snafu::prelude::*;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::math::algebra::Product;
+use crate::domain::math::geometry::Direction;
+use crate::domain::math::numeric::Val;
+use crate::domain::medium::def::{HomogeneousMedium, HomogeneousMediumExt, Medium, MediumKind};
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayScattering, RaySegment};
+use crate::domain::renderer::{Contribution, RtContext, RtState};
+use crate::domain::sampling::phase::{MiePhase, PhaseSample, PhaseSampling, RayleighPhase};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Atmospheric {
+    rayleigh: Spectrum,
+    mie: Spectrum,
+    sigma_s: Spectrum,
+    sigma_t: Spectrum,
+    rayleigh_phase: RayleighPhase,
+    mie_phase: MiePhase,
+}
+
+impl Atmospheric {
+    pub fn new(
+        rayleigh: Spectrum,
+        mie: Spectrum,
+        mie_absorption: Spectrum,
+        mie_phase: MiePhase,
+    ) -> Result<Self, TryNewAtmosphericError> {
+        let sigma_s = rayleigh + mie;
+        let sigma_t = sigma_s + mie_absorption;
+        ensure!(sigma_t.red() > Val(0.0), InvalidExtinctionSnafu);
+        ensure!(sigma_t.green() > Val(0.0), InvalidExtinctionSnafu);
+        ensure!(sigma_t.blue() > Val(0.0), InvalidExtinctionSnafu);
+
+        Ok(Self {
+            rayleigh,
+            mie,
+            sigma_s,
+            sigma_t,
+            rayleigh_phase: RayleighPhase::new(),
+            mie_phase,
+        })
+    }
+
+    fn rayleigh_selection_prob(&self) -> Val {
+        let sum = |s: Spectrum| s.red() + s.green() + s.blue();
+        let total = sum(self.sigma_s);
+        if total == Val(0.0) {
+            Val(1.0)
+        } else {
+            sum(self.rayleigh) / total
+        }
+    }
+
+    fn mix_channel(&self, rayleigh: Val, mie: Val, pr: Val, pm: Val) -> Val {
+        let total = rayleigh + mie;
+        if total == Val(0.0) {
+            Val(0.0)
+        } else {
+            (rayleigh * pr + mie * pm) / total
+        }
+    }
+}
+
+impl Medium for Atmospheric {
+    fn kind(&self) -> MediumKind {
+        MediumKind::Atmospheric
+    }
+
+    fn transmittance(&self, _ray: &Ray, segment: &RaySegment, _rng: &mut dyn RngCore) -> Spectrum {
+        self.transmittance_over(segment.length())
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        _state: RtState,
+        ray: &Ray,
+        segment: &RaySegment,
+    ) -> Contribution {
+        self.shade_anisotropic(context, ray, segment)
+    }
+}
+
+impl HomogeneousMedium for Atmospheric {
+    fn sigma_s(&self) -> Spectrum {
+        self.sigma_s
+    }
+
+    fn sigma_t(&self) -> Spectrum {
+        self.sigma_t
+    }
+
+    fn phase(&self, dir_out: Direction, dir_in: Direction) -> Spectrum {
+        let pr = self.rayleigh_phase.pdf_phase(dir_out, dir_in);
+        let pm = self.mie_phase.evaluate(-dir_out.dot(dir_in));
+        let (r, m) = (self.rayleigh, self.mie);
+        Spectrum::new(
+            self.mix_channel(r.red(), m.red(), pr, pm),
+            self.mix_channel(r.green(), m.green(), pr, pm),
+            self.mix_channel(r.blue(), m.blue(), pr, pm),
+        )
+    }
+}
+
+impl PhaseSampling for Atmospheric {
+    fn sample_phase(
+        &self,
+        ray: &Ray,
+        scattering: &RayScattering,
+        rng: &mut dyn RngCore,
+    ) -> PhaseSample {
+        let ray_next = if Val(rng.random()) < self.rayleigh_selection_prob() {
+            self.rayleigh_phase.sample_phase(ray, scattering, rng)
+        } else {
+            self.mie_phase.sample_phase(ray, scattering, rng)
+        }
+        .into_ray_next();
+
+        let phase = self.phase(-ray.direction(), ray_next.direction());
+        let pdf = self.pdf_phase(-ray.direction(), ray_next.direction());
+        PhaseSample::new(ray_next, phase, pdf)
+    }
+
+    fn pdf_phase(&self, dir_out: Direction, dir_in: Direction) -> Val {
+        let prob = self.rayleigh_selection_prob();
+        let pr = self.rayleigh_phase.pdf_phase(dir_out, dir_in);
+        let pm = self.mie_phase.pdf_phase(dir_out, dir_in);
+        prob * pr + (Val(1.0) - prob) * pm
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewAtmosphericError {
+    #[snafu(display("extinction coefficient's each component should be positive"))]
+    InvalidExtinction,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn atmospheric_new_fails_when_extinction_is_zero() {
+        let mie = MiePhase::new(Val(0.76)).unwrap();
+        let rayleigh = Spectrum::new(Val(0.1), Val(0.2), Val(0.0));
+        assert!(matches!(
+            Atmospheric::new(rayleigh, Spectrum::zero(), Spectrum::zero(), mie),
+            Err(TryNewAtmosphericError::InvalidExtinction),
+        ));
+    }
+
+    #[test]
+    fn atmospheric_phase_succeeds_weighting_components_per_channel() {
+        let mie = MiePhase::new(Val(0.76)).unwrap();
+        let medium = Atmospheric::new(
+            Spectrum::new(Val(1.0), Val(0.0), Val(1.0)),
+            Spectrum::new(Val(0.0), Val(1.0), Val(1.0)),
+            Spectrum::zero(),
+            mie,
+        )
+        .unwrap();
+
+        let dir_out = -Direction::z_direction();
+        let dir_in = Direction::z_direction();
+        let phase = medium.phase(dir_out, dir_in);
+        let (pr, pm) = (
+            RayleighPhase::new().evaluate(Val(1.0)),
+            mie.evaluate(Val(1.0)),
+        );
+        assert_eq!(phase.red(), pr);
+        assert_eq!(phase.green(), pm);
+        assert_eq!(phase.blue(), (pr + pm) * Val(0.5));
+    }
+}
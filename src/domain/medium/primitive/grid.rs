@@ -0,0 +1,320 @@
+use rand::prelude::*;
+use snafu::prelude::*;
+
+use crate::domain::color::core::{Albedo, Spectrum};
+use crate::domain::math::geometry::{Direction, Distance, Point};
+use crate::domain::math::numeric::{DisRange, Val};
+use crate::domain::medium::def::{Medium, MediumKind};
+use crate::domain::medium::util::AggregateMedium;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayScattering, RaySegment};
+use crate::domain::ray::util::VisibilityTester;
+use crate::domain::renderer::{Contribution, RtContext, RtState};
+use crate::domain::sampling::phase::{PhaseSample, PhaseSampling};
+use crate::domain::shape::def::BoundingBox;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridMedium {
+    bounds: BoundingBox,
+    dimensions: (usize, usize, usize),
+    densities: Vec<Val>,
+    albedo: Albedo,
+    max_density: Val,
+}
+
+impl GridMedium {
+    pub fn new(
+        bounds: BoundingBox,
+        dimensions: (usize, usize, usize),
+        densities: Vec<Val>,
+        albedo: Albedo,
+    ) -> Result<Self, TryNewGridMediumError> {
+        let (nx, ny, nz) = dimensions;
+        ensure!(nx > 0 && ny > 0 && nz > 0, InvalidDimensionsSnafu);
+        ensure!(
+            densities.len() == nx * ny * nz,
+            MismatchedDensityCountSnafu {
+                expected: nx * ny * nz,
+                actual: densities.len(),
+            }
+        );
+        ensure!(
+            densities.iter().all(|d| *d >= Val(0.0)),
+            InvalidDensitySnafu
+        );
+
+        let max_density = densities.iter().copied().fold(Val(0.0), Val::max);
+        Ok(Self {
+            bounds,
+            dimensions,
+            densities,
+            albedo,
+            max_density,
+        })
+    }
+
+    pub fn density(&self, point: Point) -> Val {
+        let (min, max) = (self.bounds.min(), self.bounds.max());
+        let locate = |p: Val, min: Val, max: Val, n: usize| {
+            let extent = max - min;
+            let t = if extent > Val(0.0) {
+                (p - min) / extent
+            } else {
+                Val(0.0)
+            };
+            if !(Val(0.0)..=Val(1.0)).contains(&t) {
+                return None;
+            }
+            let f = t * Val::from(n - 1);
+            let i0 = (f.0.floor() as usize).min(n.saturating_sub(2));
+            let i1 = (i0 + 1).min(n - 1);
+            Some((i0, i1, f - Val::from(i0)))
+        };
+
+        let (nx, ny, nz) = self.dimensions;
+        let Some((x0, x1, tx)) = locate(point.x(), min.x(), max.x(), nx) else {
+            return Val(0.0);
+        };
+        let Some((y0, y1, ty)) = locate(point.y(), min.y(), max.y(), ny) else {
+            return Val(0.0);
+        };
+        let Some((z0, z1, tz)) = locate(point.z(), min.z(), max.z(), nz) else {
+            return Val(0.0);
+        };
+
+        let at = |x: usize, y: usize, z: usize| self.densities[x + nx * (y + ny * z)];
+        let lerp_x = |y: usize, z: usize| Val::lerp(at(x0, y, z), at(x1, y, z), tx);
+        let lerp_y = |z: usize| Val::lerp(lerp_x(y0, z), lerp_x(y1, z), ty);
+        Val::lerp(lerp_y(z0), lerp_y(z1), tz)
+    }
+
+    fn sample_collision(
+        &self,
+        ray: &Ray,
+        segment: &RaySegment,
+        rng: &mut dyn RngCore,
+    ) -> Option<RayScattering> {
+        if self.max_density == Val(0.0) {
+            return None;
+        }
+        let end = segment.end().value();
+        let mut distance = segment.start().value();
+        loop {
+            distance -= (Val(1.0) - Val(rng.random())).ln() / self.max_density;
+            if distance >= end {
+                return None;
+            }
+            let distance = Distance::new(distance).ok()?;
+            let position = ray.at(distance);
+            if Val(rng.random()) * self.max_density < self.density(position) {
+                return Some(RayScattering::new(distance, position).with_time(ray.time()));
+            }
+        }
+    }
+
+    fn shade_light_using_light_sampling(
+        &self,
+        context: &mut RtContext<'_>,
+        ray: &Ray,
+        scattering: &RayScattering,
+    ) -> Contribution {
+        let scene = context.entity_scene();
+        let lights = scene.get_lights();
+        let Some(sample) = lights.sample_light_volume(scattering, None, *context.rng()) else {
+            return Contribution::new();
+        };
+        if sample.pdf() == Val(0.0) {
+            return Contribution::new();
+        }
+
+        let ray_next = sample.ray_next();
+        let vtester = VisibilityTester::new(scene, ray_next);
+        let phase = self.pdf_phase(-ray.direction(), ray_next.direction());
+
+        if let Some(radiance) = sample.radiance() {
+            if !vtester.test_unblocked(sample.distance()) {
+                return Contribution::new();
+            }
+            let volume_scene = context.volume_scene();
+            let range = DisRange::positive().shrink_end(sample.distance());
+            let segments = volume_scene.find_segments(ray_next, range);
+            let aggregator = AggregateMedium::new(volume_scene, &segments);
+            let tr = aggregator.transmittance(ray_next, &RaySegment::from(range), *context.rng());
+            return Contribution::from_light(tr * radiance * (phase / sample.pdf()));
+        }
+
+        let Some(target) = vtester.test(sample.distance(), sample.shape_id().unwrap()) else {
+            return Contribution::new();
+        };
+        let pdf_light = sample.pdf();
+        let weight = pdf_light / (pdf_light + phase);
+
+        let renderer = context.renderer();
+        let state = RtState::new().with_skip_medium_inscattering(true);
+        let radiance = renderer.trace_to(context, state, ray_next, target.as_some());
+        radiance * (weight * phase / pdf_light)
+    }
+
+    fn shade_light_using_phase_sampling(
+        &self,
+        context: &mut RtContext<'_>,
+        ray: &Ray,
+        scattering: &RayScattering,
+    ) -> Contribution {
+        let scene = context.entity_scene();
+        let sample = self.sample_phase(ray, scattering, *context.rng());
+        let ray_next = sample.ray_next();
+        let vtester = VisibilityTester::new(scene, ray_next);
+        let Some(target) = vtester.cast() else {
+            return Contribution::new();
+        };
+
+        let pdf_phase = sample.pdf();
+        let pdf_light = scene.get_lights().pdf_light_volume(ray_next, None);
+        let weight = pdf_phase / (pdf_light + pdf_phase);
+
+        let renderer = context.renderer();
+        let state = RtState::new().with_skip_medium_inscattering(true);
+        let radiance = renderer.trace_to(context, state, ray_next, target.as_some());
+        radiance * weight
+    }
+}
+
+impl Medium for GridMedium {
+    fn kind(&self) -> MediumKind {
+        MediumKind::Grid
+    }
+
+    fn transmittance(&self, ray: &Ray, segment: &RaySegment, rng: &mut dyn RngCore) -> Spectrum {
+        if self.max_density == Val(0.0) {
+            return Spectrum::broadcast(Val(1.0));
+        }
+        let end = segment.end().value();
+        let mut distance = segment.start().value();
+        let mut tr = Val(1.0);
+        loop {
+            distance -= (Val(1.0) - Val(rng.random())).ln() / self.max_density;
+            if distance >= end {
+                return Spectrum::broadcast(tr);
+            }
+            let position = ray.at(Distance::clamp(distance));
+            tr *= Val(1.0) - self.density(position) / self.max_density;
+        }
+    }
+
+    fn shade(
+        &self,
+        context: &mut RtContext<'_>,
+        _state: RtState,
+        ray: &Ray,
+        segment: &RaySegment,
+    ) -> Contribution {
+        let Some(scattering) = self.sample_collision(ray, segment, *context.rng()) else {
+            return Contribution::new();
+        };
+        let light = self.shade_light_using_light_sampling(context, ray, &scattering);
+        let phase = self.shade_light_using_phase_sampling(context, ray, &scattering);
+        Spectrum::from(self.albedo) * (light + phase)
+    }
+}
+
+impl PhaseSampling for GridMedium {
+    fn sample_phase(
+        &self,
+        ray: &Ray,
+        scattering: &RayScattering,
+        rng: &mut dyn RngCore,
+    ) -> PhaseSample {
+        let ray_next = scattering.spawn(Direction::random(rng));
+        let pdf = self.pdf_phase(-ray.direction(), ray_next.direction());
+        PhaseSample::new(ray_next, Spectrum::broadcast(pdf), pdf)
+    }
+
+    fn pdf_phase(&self, _dir_out: Direction, _dir_in: Direction) -> Val {
+        Val(0.25) * Val::FRAC_1_PI
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewGridMediumError {
+    #[snafu(display("grid dimensions should be positive"))]
+    InvalidDimensions,
+    #[snafu(display("expected {expected} densities but got {actual}"))]
+    MismatchedDensityCount { expected: usize, actual: usize },
+    #[snafu(display("density should not be negative"))]
+    InvalidDensity,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp() -> GridMedium {
+        GridMedium::new(
+            BoundingBox::new(
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(1.0), Val(1.0), Val(1.0)),
+            ),
+            (2, 1, 1),
+            vec![Val(0.0), Val(2.0)],
+            Albedo::broadcast(Val(0.5)).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn grid_medium_new_fails_when_density_count_mismatches() {
+        assert!(matches!(
+            GridMedium::new(
+                BoundingBox::new(Point::default(), Point::new(Val(1.0), Val(1.0), Val(1.0))),
+                (2, 2, 1),
+                vec![Val(1.0); 3],
+                Albedo::broadcast(Val(0.5)).unwrap(),
+            ),
+            Err(TryNewGridMediumError::MismatchedDensityCount {
+                expected: 4,
+                actual: 3,
+            }),
+        ));
+    }
+
+    #[test]
+    fn grid_medium_density_succeeds() {
+        let medium = ramp();
+        assert_eq!(
+            medium.density(Point::new(Val(0.25), Val(0.5), Val(0.5))),
+            Val(0.5),
+        );
+        assert_eq!(
+            medium.density(Point::new(Val(1.0), Val(0.0), Val(1.0))),
+            Val(2.0),
+        );
+        assert_eq!(
+            medium.density(Point::new(Val(1.5), Val(0.5), Val(0.5))),
+            Val(0.0),
+        );
+    }
+
+    #[test]
+    fn grid_medium_transmittance_succeeds_along_density_ramp() {
+        let medium = ramp();
+        let segment = RaySegment::new(Distance::zero(), Distance::new(Val(1.0)).unwrap());
+
+        let mean_tr = |x: Val| {
+            let ray = Ray::new(Point::new(x, Val(0.5), Val(0.0)), Direction::z_direction());
+            let rng = &mut rand::rng();
+            let sum = (0..4000)
+                .map(|_| medium.transmittance(&ray, &segment, rng).red())
+                .fold(Val(0.0), |sum, tr| sum + tr);
+            sum / Val(4000.0)
+        };
+
+        let trs = [Val(0.1), Val(0.5), Val(0.9)].map(mean_tr);
+        assert!(trs[0] > trs[1] && trs[1] > trs[2]);
+        for (tr, x) in trs.into_iter().zip([Val(0.1), Val(0.5), Val(0.9)]) {
+            let expected = (-Val(2.0) * x).exp();
+            assert!((tr - expected).abs() < Val(0.05));
+        }
+    }
+}
@@ -2,23 +2,20 @@ use rand::prelude::*;
 use snafu::prelude::*;
 
 use crate::domain::color::core::{Albedo, Spectrum};
-use crate::domain::math::algebra::{Product, Vector};
-use crate::domain::math::geometry::{Direction, Frame};
+use crate::domain::math::algebra::Product;
+use crate::domain::math::geometry::Direction;
 use crate::domain::math::numeric::Val;
 use crate::domain::medium::def::{HomogeneousMedium, HomogeneousMediumExt, Medium, MediumKind};
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::{RayScattering, RaySegment};
 use crate::domain::renderer::{Contribution, RtContext, RtState};
-use crate::domain::sampling::distance::{
-    DistanceSampling, EquiAngularDistanceSampler, ExponentialDistanceSampler,
-};
-use crate::domain::sampling::phase::{PhaseSample, PhaseSampling};
+use crate::domain::sampling::phase::{HenyeyGreensteinPhase, PhaseSample, PhaseSampling};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HenyeyGreenstein {
     sigma_s: Spectrum,
     sigma_t: Spectrum,
-    asymmetric: Val,
+    phase: HenyeyGreensteinPhase,
 }
 
 impl HenyeyGreenstein {
@@ -30,10 +27,9 @@ impl HenyeyGreenstein {
         ensure!(mean_free_path.red() > Val(0.0), InvalidMeanFreePathSnafu);
         ensure!(mean_free_path.green() > Val(0.0), InvalidMeanFreePathSnafu);
         ensure!(mean_free_path.blue() > Val(0.0), InvalidMeanFreePathSnafu);
-        ensure!(
-            (Val(-1.0)..=Val(1.0)).contains(&asymmetric),
-            InvalidAsymmetricParameterSnafu
-        );
+        let phase = HenyeyGreensteinPhase::new(asymmetric)
+            .ok()
+            .context(InvalidAsymmetricParameterSnafu)?;
 
         let sigma_t = Spectrum::new(
             mean_free_path.red().recip(),
@@ -44,15 +40,27 @@ impl HenyeyGreenstein {
         Ok(Self {
             sigma_s,
             sigma_t,
-            asymmetric,
+            phase,
         })
     }
 
-    fn calc_hg(&self, cos: Val) -> Val {
-        let g = self.asymmetric;
-        let num = Val(1.0) - g * g;
-        let den = Val(4.0) * Val::PI * (Val(1.0) + g * g - Val(2.0) * g * cos).powf(Val(1.5));
-        num / den
+    pub fn from_coefficients(
+        sigma_s: Spectrum,
+        sigma_a: Spectrum,
+        asymmetric: Val,
+    ) -> Result<Self, TryNewHenyeyGreensteinError> {
+        let sigma_t = sigma_s + sigma_a;
+        ensure!(sigma_t.red() > Val(0.0), InvalidExtinctionSnafu);
+        ensure!(sigma_t.green() > Val(0.0), InvalidExtinctionSnafu);
+        ensure!(sigma_t.blue() > Val(0.0), InvalidExtinctionSnafu);
+        let phase = HenyeyGreensteinPhase::new(asymmetric)
+            .ok()
+            .context(InvalidAsymmetricParameterSnafu)?;
+        Ok(Self {
+            sigma_s,
+            sigma_t,
+            phase,
+        })
     }
 }
 
@@ -61,12 +69,8 @@ impl Medium for HenyeyGreenstein {
         MediumKind::HenyeyGreenstein
     }
 
-    fn transmittance(&self, _ray: &Ray, segment: &RaySegment) -> Spectrum {
-        Spectrum::new(
-            (-self.sigma_t.red() * segment.length().value()).exp(),
-            (-self.sigma_t.green() * segment.length().value()).exp(),
-            (-self.sigma_t.blue() * segment.length().value()).exp(),
-        )
+    fn transmittance(&self, _ray: &Ray, segment: &RaySegment, _rng: &mut dyn RngCore) -> Spectrum {
+        self.transmittance_over(segment.length())
     }
 
     fn shade(
@@ -76,79 +80,7 @@ impl Medium for HenyeyGreenstein {
         ray: &Ray,
         segment: &RaySegment,
     ) -> Contribution {
-        let light = context.entity_scene().get_lights();
-        let avg_sigma_t = self.sigma_t.norm() / Val(3.0).sqrt();
-        let exp_sampler = ExponentialDistanceSampler::new(avg_sigma_t);
-
-        let light_surfaces = context.entity_scene().get_light_surfaces();
-        let Some(preselected) = light_surfaces.sample_point(*context.rng()) else {
-            return Contribution::new();
-        };
-        let ea_sampler = EquiAngularDistanceSampler::new(preselected.point());
-
-        let exp_sample = exp_sampler.sample_distance(ray, segment, *context.rng());
-        let ea_sample = ea_sampler.sample_distance(ray, segment, *context.rng());
-        let exp_scattering = exp_sample.scattering();
-        let ea_scattering = ea_sample.scattering();
-
-        let phase_exp_sample = self.sample_phase(ray, exp_scattering, *context.rng());
-        let phase_ea_sample = self.sample_phase(ray, ea_scattering, *context.rng());
-
-        let exp_light_contribution = {
-            let radiance = self.shade_light_using_light_sampling(
-                context,
-                ray,
-                segment,
-                &exp_sample,
-                &preselected,
-            );
-            radiance
-                * Self::calc_exp_weight(ray, segment, &exp_sample, &ea_sampler)
-                * Self::calc_light_weight(ray, exp_scattering, &preselected, light, self)
-        };
-
-        let ea_light_contribution = {
-            let radiance = self.shade_light_using_light_sampling(
-                context,
-                ray,
-                segment,
-                &ea_sample,
-                &preselected,
-            );
-            radiance
-                * Self::calc_ea_weight(ray, segment, &ea_sample, &exp_sampler)
-                * Self::calc_light_weight(ray, exp_scattering, &preselected, light, self)
-        };
-
-        let exp_phase_contribution = {
-            let radiance = self.shade_light_using_phase_sampling(
-                context,
-                ray,
-                segment,
-                &exp_sample,
-                &phase_exp_sample,
-            );
-            radiance
-                * Self::calc_exp_weight(ray, segment, &exp_sample, &ea_sampler)
-                * Self::calc_phase_weight(&phase_exp_sample, light)
-        };
-
-        let ea_phase_contribution = {
-            let radiance = self.shade_light_using_phase_sampling(
-                context,
-                ray,
-                segment,
-                &ea_sample,
-                &phase_ea_sample,
-            );
-            radiance
-                * Self::calc_ea_weight(ray, segment, &ea_sample, &exp_sampler)
-                * Self::calc_phase_weight(&phase_ea_sample, light)
-        };
-
-        let light_contribution = exp_light_contribution + ea_light_contribution;
-        let phase_contribution = exp_phase_contribution + ea_phase_contribution;
-        light_contribution + phase_contribution
+        self.shade_anisotropic(context, ray, segment)
     }
 }
 
@@ -157,8 +89,12 @@ impl HomogeneousMedium for HenyeyGreenstein {
         self.sigma_s
     }
 
+    fn sigma_t(&self) -> Spectrum {
+        self.sigma_t
+    }
+
     fn phase(&self, dir_out: Direction, dir_in: Direction) -> Spectrum {
-        Spectrum::broadcast(self.calc_hg(-dir_out.dot(dir_in)))
+        Spectrum::broadcast(self.phase.evaluate(-dir_out.dot(dir_in)))
     }
 }
 
@@ -169,29 +105,11 @@ impl PhaseSampling for HenyeyGreenstein {
         scattering: &RayScattering,
         rng: &mut dyn RngCore,
     ) -> PhaseSample {
-        let phi = Val(2.0) * Val::PI * Val(rng.random());
-        let (cos_phi, sin_phi) = (phi.cos(), phi.sin());
-
-        let cos_theta = if self.asymmetric != Val(0.0) {
-            let g = self.asymmetric;
-            let s = Val(2.0) * Val(rng.random()) - Val(1.0);
-            (Val(0.5) / g) * (Val(1.0) + g * g - ((Val(1.0) - g * g) / (Val(1.0) + g * s)).powi(2))
-        } else {
-            Val(2.0) * Val(rng.random()) - Val(1.0)
-        };
-        let sin_theta = (Val(1.0) - cos_theta * cos_theta).sqrt();
-
-        let frame = Frame::new(ray.direction().into());
-        let dir_next_local = Vector::new(cos_phi * sin_theta, sin_phi * sin_theta, cos_theta);
-        let dir_next = Direction::normalize(frame.to_canonical(dir_next_local)).unwrap();
-
-        let ray_next = scattering.spawn(dir_next);
-        let hg = self.calc_hg(cos_theta);
-        PhaseSample::new(ray_next, Spectrum::broadcast(hg), hg)
+        self.phase.sample_phase(ray, scattering, rng)
     }
 
     fn pdf_phase(&self, dir_out: Direction, dir_in: Direction) -> Val {
-        self.calc_hg(-dir_out.dot(dir_in))
+        self.phase.pdf_phase(dir_out, dir_in)
     }
 }
 
@@ -200,6 +118,8 @@ impl PhaseSampling for HenyeyGreenstein {
 pub enum TryNewHenyeyGreensteinError {
     #[snafu(display("mean free path's each component should be positive"))]
     InvalidMeanFreePath,
+    #[snafu(display("extinction coefficient's each component should be positive"))]
+    InvalidExtinction,
     #[snafu(display("asymmetric parameter (g) should be in [-1, 1]"))]
     InvalidAsymmetricParameter,
 }
@@ -9,7 +9,7 @@ use crate::domain::ray::Ray;
 use crate::domain::ray::event::{RayScattering, RaySegment};
 use crate::domain::renderer::{Contribution, RtContext, RtState};
 use crate::domain::sampling::distance::{
-    DistanceSampling, EquiAngularDistanceSampler, ExponentialDistanceSampler,
+    DistanceSampling, EquiAngularDistanceSampler, SpectralDistanceSampler,
 };
 use crate::domain::sampling::phase::{PhaseSample, PhaseSampling};
 
@@ -33,6 +33,17 @@ impl Isotropic {
         let sigma_s = albedo * sigma_t;
         Ok(Self { sigma_s, sigma_t })
     }
+
+    pub fn from_coefficients(
+        sigma_s: Spectrum,
+        sigma_a: Spectrum,
+    ) -> Result<Self, TryNewIsotropicError> {
+        let sigma_t = sigma_s + sigma_a;
+        ensure!(sigma_t.red() > Val(0.0), InvalidExtinctionSnafu);
+        ensure!(sigma_t.green() > Val(0.0), InvalidExtinctionSnafu);
+        ensure!(sigma_t.blue() > Val(0.0), InvalidExtinctionSnafu);
+        Ok(Self { sigma_s, sigma_t })
+    }
 }
 
 impl Medium for Isotropic {
@@ -40,12 +51,8 @@ impl Medium for Isotropic {
         MediumKind::Isotropic
     }
 
-    fn transmittance(&self, _ray: &Ray, segment: &RaySegment) -> Spectrum {
-        Spectrum::new(
-            (-self.sigma_t.red() * segment.length().value()).exp(),
-            (-self.sigma_t.green() * segment.length().value()).exp(),
-            (-self.sigma_t.blue() * segment.length().value()).exp(),
-        )
+    fn transmittance(&self, _ray: &Ray, segment: &RaySegment, _rng: &mut dyn RngCore) -> Spectrum {
+        self.transmittance_over(segment.length())
     }
 
     fn shade(
@@ -55,12 +62,12 @@ impl Medium for Isotropic {
         ray: &Ray,
         segment: &RaySegment,
     ) -> Contribution {
-        let avg_sigma_t = self.sigma_t.norm() / Val(3.0).sqrt();
-        let exp_sampler = ExponentialDistanceSampler::new(avg_sigma_t);
+        let exp_sampler = SpectralDistanceSampler::new(self.sigma_t);
 
         let light_surfaces = context.entity_scene().get_light_surfaces();
+        let point_lights = self.shade_point_lights(context, ray, segment);
         let Some(preselected_light) = light_surfaces.sample_point(*context.rng()) else {
-            return Contribution::new();
+            return point_lights;
         };
         let ea_sampler = EquiAngularDistanceSampler::new(preselected_light.point());
 
@@ -87,7 +94,7 @@ impl Medium for Isotropic {
         let ea_weight = Self::calc_ea_weight(ray, segment, &ea_sample, &exp_sampler);
         let ea_contribution = ea_radiance * ea_weight;
 
-        exp_contribution + ea_contribution
+        exp_contribution + ea_contribution + point_lights
     }
 }
 
@@ -96,6 +103,10 @@ impl HomogeneousMedium for Isotropic {
         self.sigma_s
     }
 
+    fn sigma_t(&self) -> Spectrum {
+        self.sigma_t
+    }
+
     fn phase(&self, _dir_out: Direction, _dir_in: Direction) -> Spectrum {
         const PHASE: Spectrum = Spectrum::broadcast(Val(0.25 * Val::FRAC_1_PI.0));
         PHASE
@@ -125,4 +136,6 @@ impl PhaseSampling for Isotropic {
 pub enum TryNewIsotropicError {
     #[snafu(display("mean free path's each component should be positive"))]
     InvalidMeanFreePath,
+    #[snafu(display("extinction coefficient's each component should be positive"))]
+    InvalidExtinction,
 }
@@ -1,7 +1,11 @@
+mod atmospheric;
+mod grid;
 mod henyey_greenstein;
 mod isotropic;
 mod vacuum;
 
+pub use atmospheric::{Atmospheric, TryNewAtmosphericError};
+pub use grid::{GridMedium, TryNewGridMediumError};
 pub use henyey_greenstein::{HenyeyGreenstein, TryNewHenyeyGreensteinError};
 pub use isotropic::{Isotropic, TryNewIsotropicError};
 pub use vacuum::Vacuum;
@@ -1,3 +1,5 @@
+use rand::prelude::*;
+
 use crate::domain::color::core::Spectrum;
 use crate::domain::math::numeric::Val;
 use crate::domain::medium::def::{Medium, MediumKind};
@@ -23,7 +25,7 @@ impl Medium for Vacuum {
         MediumKind::Vacuum
     }
 
-    fn transmittance(&self, _ray: &Ray, _segment: &RaySegment) -> Spectrum {
+    fn transmittance(&self, _ray: &Ray, _segment: &RaySegment, _rng: &mut dyn RngCore) -> Spectrum {
         Spectrum::broadcast(Val(1.0))
     }
 
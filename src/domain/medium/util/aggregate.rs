@@ -29,7 +29,7 @@ impl<'a> Medium for AggregateMedium<'a> {
         unimplemented!("AggregateMedium doesn't have a unique kind")
     }
 
-    fn transmittance(&self, ray: &Ray, segment: &RaySegment) -> Spectrum {
+    fn transmittance(&self, ray: &Ray, segment: &RaySegment, rng: &mut dyn RngCore) -> Spectrum {
         (self.segments.iter()).fold(Spectrum::broadcast(Val(1.0)), |tr, (cur, id)| {
             let Some(seg_intersection) = segment.intersect(cur) else {
                 return tr;
@@ -37,7 +37,7 @@ impl<'a> Medium for AggregateMedium<'a> {
             let Some(medium) = self.volume_scene.get_boundaries().get_medium(*id) else {
                 return tr;
             };
-            tr * medium.transmittance(ray, &seg_intersection)
+            tr * medium.transmittance(ray, &seg_intersection, rng)
         })
     }
 
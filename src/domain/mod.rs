@@ -1,6 +1,7 @@
 pub mod camera;
 pub mod color;
 pub mod image;
+pub mod light;
 pub mod material;
 pub mod math;
 pub mod medium;
@@ -1,8 +1,11 @@
 use getset::CopyGetters;
 
-use crate::domain::math::geometry::{Direction, Distance, Normal, Point};
+use crate::domain::math::algebra::{Product, Vector};
+use crate::domain::math::geometry::{Direction, Distance, Frame, Normal, Point};
+use crate::domain::math::numeric::Val;
 use crate::domain::math::transformation::{AtomTransformation, Transform};
 use crate::domain::ray::Ray;
+use crate::domain::shape::util::ShapeId;
 use crate::domain::texture::def::UvCoordinate;
 
 #[derive(Debug, Clone, PartialEq, CopyGetters)]
@@ -10,11 +13,20 @@ use crate::domain::texture::def::UvCoordinate;
 pub struct RayIntersectionPart<'a> {
     distance: Distance,
     ray: &'a Ray,
+    face: Option<ShapeId>,
 }
 
 impl<'a> RayIntersectionPart<'a> {
     pub fn new(distance: Distance, ray: &'a Ray) -> Self {
-        Self { distance, ray }
+        Self {
+            distance,
+            ray,
+            face: None,
+        }
+    }
+
+    pub fn with_face(self, face: Option<ShapeId>) -> Self {
+        Self { face, ..self }
     }
 }
 
@@ -24,30 +36,113 @@ pub struct RayIntersection {
     distance: Distance,
     position: Point,
     uv: Option<UvCoordinate>,
+    uv_footprint: Option<Val>,
+    footprint: Option<Val>,
     normal: Normal,
+    geometric_normal: Normal,
+    tangent: Option<Vector>,
     side: SurfaceSide,
+    time: Val,
+    ray_offset: Val,
 }
 
 impl RayIntersection {
+    pub const DEFAULT_RAY_OFFSET: Val = Val(1e-9);
+
     pub fn new(distance: Distance, position: Point, normal: Normal, side: SurfaceSide) -> Self {
         Self {
             distance,
             position,
             uv: None,
+            uv_footprint: None,
+            footprint: None,
             normal,
+            geometric_normal: normal,
+            tangent: None,
             side,
+            time: Val(0.0),
+            ray_offset: Self::DEFAULT_RAY_OFFSET,
         }
     }
 
+    #[inline]
+    pub fn with_distance(self, distance: Distance) -> Self {
+        Self { distance, ..self }
+    }
+
+    #[inline]
+    pub fn with_position(self, position: Point) -> Self {
+        Self { position, ..self }
+    }
+
     #[inline]
     pub fn with_uv(self, uv: UvCoordinate) -> Self {
         let uv = Some(uv);
         Self { uv, ..self }
     }
 
+    #[inline]
+    pub fn with_uv_footprint(self, uv_footprint: Val) -> Self {
+        let uv_footprint = Some(uv_footprint);
+        Self {
+            uv_footprint,
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn with_footprint(self, footprint: Val) -> Self {
+        let footprint = Some(footprint);
+        Self { footprint, ..self }
+    }
+
+    #[inline]
+    pub fn with_normal(self, normal: Normal) -> Self {
+        Self { normal, ..self }
+    }
+
+    #[inline]
+    pub fn with_side(self, side: SurfaceSide) -> Self {
+        Self { side, ..self }
+    }
+
+    #[inline]
+    pub fn with_tangent(self, tangent: Vector) -> Self {
+        let tangent = Some(tangent);
+        Self { tangent, ..self }
+    }
+
+    #[inline]
+    pub fn with_time(self, time: Val) -> Self {
+        Self { time, ..self }
+    }
+
+    #[inline]
+    pub fn with_ray_offset(self, ray_offset: Val) -> Self {
+        Self { ray_offset, ..self }
+    }
+
+    pub fn tangent_frame(&self) -> Frame {
+        match self.tangent {
+            Some(tangent) => Frame::from_tangent(self.normal, tangent),
+            None => Frame::new(self.normal),
+        }
+    }
+
     #[inline]
     pub fn spawn(&self, direction: Direction) -> Ray {
-        Ray::new(self.position, direction)
+        let scale = (self.position.x().abs())
+            .max(self.position.y().abs())
+            .max(self.position.z().abs())
+            .max(self.distance.value())
+            .max(Val(1.0));
+        let normal = self.geometric_normal.to_vector();
+        let offset = if direction.dot(self.geometric_normal) < Val(0.0) {
+            -self.ray_offset * scale
+        } else {
+            self.ray_offset * scale
+        };
+        Ray::new(self.position + normal * offset, direction).with_time(self.time)
     }
 }
 
@@ -57,18 +152,16 @@ where
     Distance: Transform<T>,
     Point: Transform<T>,
     Normal: Transform<T>,
+    Vector: Transform<T>,
 {
     fn transform_impl(self, transformation: &T) -> Self {
-        let res = Self::new(
-            self.distance.transform(transformation),
-            self.position.transform(transformation),
-            self.normal.transform(transformation),
-            self.side,
-        );
-        if let Some(uv) = self.uv {
-            res.with_uv(uv)
-        } else {
-            res
+        Self {
+            distance: self.distance.transform(transformation),
+            position: self.position.transform(transformation),
+            normal: self.normal.transform(transformation),
+            geometric_normal: self.geometric_normal.transform(transformation),
+            tangent: self.tangent.map(|t| t.transform(transformation)),
+            ..self
         }
     }
 }
@@ -1,6 +1,7 @@
 use getset::CopyGetters;
 
 use crate::domain::math::geometry::{Direction, Distance, Point};
+use crate::domain::math::numeric::Val;
 use crate::domain::math::transformation::{AtomTransformation, Transform};
 use crate::domain::ray::Ray;
 
@@ -9,15 +10,24 @@ use crate::domain::ray::Ray;
 pub struct RayScattering {
     distance: Distance,
     position: Point,
+    time: Val,
 }
 
 impl RayScattering {
     pub fn new(distance: Distance, position: Point) -> Self {
-        Self { distance, position }
+        Self {
+            distance,
+            position,
+            time: Val(0.0),
+        }
+    }
+
+    pub fn with_time(self, time: Val) -> Self {
+        Self { time, ..self }
     }
 
     pub fn spawn(&self, direction: Direction) -> Ray {
-        Ray::new(self.position, direction)
+        Ray::new(self.position, direction).with_time(self.time)
     }
 }
 
@@ -32,5 +42,6 @@ where
             self.distance.transform(transformation),
             self.position.transform(transformation),
         )
+        .with_time(self.time)
     }
 }
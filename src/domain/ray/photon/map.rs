@@ -1,7 +1,8 @@
 use std::collections::BinaryHeap;
 
 use crate::domain::color::core::Spectrum;
-use crate::domain::math::geometry::{Direction, Point};
+use crate::domain::math::algebra::Product;
+use crate::domain::math::geometry::{Direction, Normal, Point};
 use crate::domain::math::numeric::Val;
 
 use super::Photon;
@@ -10,13 +11,29 @@ use super::Photon;
 pub struct PhotonMap {
     nodes: Vec<KdTreeNode>,
     root: Option<usize>,
+    photon_counts: Vec<usize>,
 }
 
 impl PhotonMap {
     pub fn build(mut photons: Vec<Photon>) -> Self {
         let mut nodes = vec![KdTreeNode::default(); photons.len()];
         let root = Self::build_impl(&mut photons, &mut nodes, 0);
-        Self { nodes, root }
+        Self {
+            nodes,
+            root,
+            photon_counts: Vec::new(),
+        }
+    }
+
+    pub fn with_photon_counts(self, photon_counts: Vec<usize>) -> Self {
+        Self {
+            photon_counts,
+            ..self
+        }
+    }
+
+    pub fn photon_counts(&self) -> &[usize] {
+        &self.photon_counts
     }
 
     fn build_impl(
@@ -163,6 +180,41 @@ impl PhotonMap {
         res.into_iter().map(|(_, p)| p).collect()
     }
 
+    pub fn estimate_irradiance(
+        &self,
+        position: Point,
+        normal: Normal,
+        num: usize,
+        max_radius: Val,
+        num_emitted: usize,
+    ) -> Spectrum {
+        if num_emitted == 0 {
+            return Spectrum::zero();
+        }
+        let max_radius2 = max_radius.powi(2);
+        let photons = (self.search_nearest(position, num).into_iter())
+            .map(|photon| ((position - photon.position()).norm_squared(), photon))
+            .filter(|(dis2, _)| *dis2 <= max_radius2)
+            .collect::<Vec<_>>();
+
+        let radius2 = if photons.len() < num {
+            max_radius2
+        } else {
+            (photons.iter())
+                .map(|(dis2, _)| *dis2)
+                .fold(Val(0.0), Val::max)
+        };
+        if radius2 == Val(0.0) || radius2 == Val::INFINITY {
+            return Spectrum::zero();
+        }
+
+        let flux = (photons.iter())
+            .filter(|(_, photon)| photon.direction().dot(normal) > Val(0.0))
+            .map(|(_, photon)| photon.throughput())
+            .sum::<Spectrum>();
+        flux / (Val::PI * radius2 * Val::from(num_emitted))
+    }
+
     pub fn len(&self) -> usize {
         self.nodes.len()
     }
@@ -282,6 +334,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn photon_map_estimate_irradiance_succeeds() {
+        let photons = (0..100)
+            .map(|i| {
+                let (x, z) = (Val::from(i % 10) * Val(0.1), Val::from(i / 10) * Val(0.1));
+                Photon::new(
+                    Point::new(x, Val(0.0), z),
+                    Direction::y_direction(),
+                    Spectrum::broadcast(Val(1.0)),
+                )
+            })
+            .collect();
+        let photon_map = PhotonMap::build(photons);
+
+        let center = Point::new(Val(0.45), Val(0.0), Val(0.45));
+        let irradiance =
+            photon_map.estimate_irradiance(center, Normal::y_direction(), 4, Val(1.0), 1);
+        let expected = Val(4.0) / (Val::PI * Val(0.05f64.powi(2) * 2.0));
+        assert_eq!(irradiance.red(), expected);
+
+        let back = photon_map.estimate_irradiance(center, -Normal::y_direction(), 4, Val(1.0), 1);
+        assert_eq!(back, Spectrum::zero());
+    }
+
     fn create_photon(x: WrappedVal, y: WrappedVal, z: WrappedVal) -> Photon {
         Photon::new(
             Point::new(Val(x), Val(y), Val(z)),
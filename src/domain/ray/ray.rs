@@ -1,6 +1,7 @@
 use getset::CopyGetters;
 
 use crate::domain::math::geometry::{Direction, Distance, Point};
+use crate::domain::math::numeric::Val;
 use crate::domain::math::transformation::{AtomTransformation, Transform};
 
 #[derive(Debug, Clone, PartialEq, CopyGetters)]
@@ -8,11 +9,21 @@ use crate::domain::math::transformation::{AtomTransformation, Transform};
 pub struct Ray {
     start: Point,
     direction: Direction,
+    time: Val,
 }
 
 impl Ray {
     pub fn new(start: Point, direction: Direction) -> Self {
-        Self { start, direction }
+        Self {
+            start,
+            direction,
+            time: Val(0.0),
+        }
+    }
+
+    #[inline]
+    pub fn with_time(self, time: Val) -> Self {
+        Self { time, ..self }
     }
 
     pub fn at(&self, distance: Distance) -> Point {
@@ -31,12 +42,12 @@ where
             self.start.transform(transformation),
             self.direction.transform(transformation),
         )
+        .with_time(self.time)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::math::numeric::Val;
     use crate::domain::math::transformation::{Scaling, Sequential};
 
     use super::*;
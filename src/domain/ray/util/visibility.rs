@@ -7,7 +7,7 @@ use crate::domain::math::geometry::Distance;
 use crate::domain::math::numeric::DisRange;
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::RayIntersection;
-use crate::domain::scene::entity::EntityScene;
+use crate::domain::scene::entity::{EntityScene, Visibility};
 use crate::domain::shape::util::ShapeId;
 
 pub struct VisibilityTester<'s, 'r> {
@@ -26,8 +26,8 @@ impl<'s, 'r> VisibilityTester<'s, 'r> {
         let range = (Bound::Excluded(Distance::zero()), Bound::Included(distance));
         let range = DisRange::from(range);
 
-        let res = scene.test_intersection(self.ray_next, range, target_id);
-        if let Some((intersection_next, id)) = res {
+        let res = scene.find_visible_intersection(self.ray_next, range, Visibility::SHADOW);
+        if let Some((intersection_next, id)) = res.filter(|(_, id)| id.shape_id() == target_id) {
             let id = id.material_id();
             let material = scene.get_entities().get_material(id).unwrap();
             if material.kind() == MaterialKind::Emissive {
@@ -40,21 +40,33 @@ impl<'s, 'r> VisibilityTester<'s, 'r> {
         }
     }
 
+    pub fn test_unblocked(&self, distance: Distance) -> bool {
+        let range = (Bound::Excluded(Distance::zero()), Bound::Excluded(distance));
+        let range = DisRange::from(range);
+        (self.scene)
+            .find_visible_intersection(self.ray_next, range, Visibility::SHADOW)
+            .is_none()
+    }
+
     pub fn cast(&self) -> Option<LightTarget<'s>> {
+        self.cast_or_escape().0
+    }
+
+    pub fn cast_or_escape(&self) -> (Option<LightTarget<'s>>, bool) {
         let scene = &self.scene;
         let range = DisRange::positive();
 
-        let res = scene.find_intersection(self.ray_next, range);
+        let res = scene.find_visible_intersection(self.ray_next, range, Visibility::SHADOW);
         if let Some((intersection_next, id)) = res {
             let id = id.material_id();
             let material = scene.get_entities().get_material(id).unwrap();
             if material.kind() == MaterialKind::Emissive {
-                Some(LightTarget::new(intersection_next, material))
+                (Some(LightTarget::new(intersection_next, material)), false)
             } else {
-                None
+                (None, false)
             }
         } else {
-            None
+            (None, true)
         }
     }
 }
@@ -0,0 +1,72 @@
+use getset::Getters;
+
+use crate::domain::color::core::{Albedo, Spectrum};
+use crate::domain::image::core::{Framebuffer, Image};
+use crate::domain::math::geometry::{Distance, Normal};
+use crate::domain::math::numeric::Val;
+
+#[derive(Debug, Clone, PartialEq, Getters)]
+#[getset(get = "pub")]
+pub struct AovBuffers {
+    beauty: Image,
+    albedo: Framebuffer<Albedo>,
+    normal: Framebuffer<Option<Normal>>,
+    depth: Framebuffer<Option<Distance>>,
+}
+
+impl AovBuffers {
+    pub fn new(
+        beauty: Image,
+        albedo: Framebuffer<Albedo>,
+        normal: Framebuffer<Option<Normal>>,
+        depth: Framebuffer<Option<Distance>>,
+    ) -> Self {
+        Self {
+            beauty,
+            albedo,
+            normal,
+            depth,
+        }
+    }
+
+    pub fn into_beauty(self) -> Image {
+        self.beauty
+    }
+
+    pub fn demodulate(&self) -> Image {
+        Self::map_albedo(&self.beauty, &self.albedo, |color, albedo| {
+            Spectrum::new(
+                color.red() / albedo.red(),
+                color.green() / albedo.green(),
+                color.blue() / albedo.blue(),
+            )
+        })
+    }
+
+    pub fn remodulate(irradiance: &Image, albedo: &Framebuffer<Albedo>) -> Image {
+        Self::map_albedo(irradiance, albedo, |color, albedo| color * albedo)
+    }
+
+    fn map_albedo<F>(image: &Image, albedo: &Framebuffer<Albedo>, f: F) -> Image
+    where
+        F: Fn(Spectrum, Spectrum) -> Spectrum,
+    {
+        const MIN_ALBEDO: Val = Val(1e-4);
+        let guard = |val: Val| if val < MIN_ALBEDO { Val(1.0) } else { val };
+
+        let resolution = image.resolution().clone();
+        let mut res = Image::new(resolution.clone());
+        for row in 0..resolution.height() {
+            for column in 0..resolution.width() {
+                let albedo = albedo[(row, column)].to_spectrum();
+                let albedo = Spectrum::new(
+                    guard(albedo.red()),
+                    guard(albedo.green()),
+                    guard(albedo.blue()),
+                );
+                res.set(row, column, f(image.get(row, column).unwrap(), albedo));
+            }
+        }
+        res
+    }
+}
@@ -0,0 +1,256 @@
+use std::io::Error as IoError;
+use std::path::PathBuf;
+
+use snafu::prelude::*;
+
+use crate::domain::camera::Resolution;
+use crate::domain::color::core::Spectrum;
+use crate::domain::image::core::{Image, ImageAccumulator};
+use crate::domain::math::numeric::Val;
+
+use super::CoreRendererConfigurationError;
+use super::core::{Observation, Pixel};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderCheckpoint {
+    pub(super) seed: u64,
+    pub(super) iterations: usize,
+    pub(super) num_global: usize,
+    pub(super) num_caustic: usize,
+    pub(super) image: ImageAccumulator,
+    pub(super) pixels: Vec<Vec<Pixel>>,
+}
+
+impl RenderCheckpoint {
+    const MAGIC: &[u8; 8] = b"FRCKPT01";
+    const PIXEL_SIZE_MIN: usize = 24 + 8 + 2;
+    const PIXEL_SIZE_MAX: usize = 24 + 8 + 2 * (1 + 24 + 8 + 8);
+
+    pub(super) fn new(resolution: Resolution, seed: u64) -> Self {
+        let (height, width) = (resolution.height(), resolution.width());
+        Self {
+            seed,
+            iterations: 0,
+            num_global: 0,
+            num_caustic: 0,
+            image: ImageAccumulator::new(Image::new(resolution)),
+            pixels: vec![vec![Pixel::new(); width]; height],
+        }
+    }
+
+    #[inline]
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    #[inline]
+    pub fn image(&self) -> &Image {
+        self.image.image()
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let resolution = self.image.resolution();
+        let (height, width) = (resolution.height(), resolution.width());
+
+        let mut writer = Writer(Vec::new());
+        writer.0.extend_from_slice(Self::MAGIC);
+        writer.u64(self.seed);
+        for value in [
+            self.iterations,
+            self.num_global,
+            self.num_caustic,
+            height,
+            width,
+        ] {
+            writer.u64(value as u64);
+        }
+        for row in 0..height {
+            for column in 0..width {
+                writer.spectrum(self.image.get(row, column).unwrap());
+                writer.u64(self.image.count(row, column).unwrap() as u64);
+                let pixel = &self.pixels[row][column];
+                writer.observation(pixel.global.as_ref());
+                writer.observation(pixel.caustic.as_ref());
+            }
+        }
+        writer.0
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, RenderCheckpointError> {
+        let mut reader = Reader(bytes);
+        ensure!(
+            reader.take(Self::MAGIC.len())? == Self::MAGIC,
+            InvalidFormatSnafu
+        );
+
+        let seed = reader.u64()?;
+        let iterations = reader.usize()?;
+        let num_global = reader.usize()?;
+        let num_caustic = reader.usize()?;
+        let (height, width) = (reader.usize()?, reader.usize()?);
+        let num_pixels = height.checked_mul(width).context(InvalidFormatSnafu)?;
+        let (min, max) = (
+            num_pixels.checked_mul(Self::PIXEL_SIZE_MIN),
+            num_pixels.checked_mul(Self::PIXEL_SIZE_MAX),
+        );
+        ensure!(
+            min.is_some_and(|min| min <= reader.0.len())
+                && max.is_none_or(|max| reader.0.len() <= max),
+            InvalidFormatSnafu
+        );
+        let resolution = Resolution::from_dimensions(width, height)
+            .ok()
+            .filter(|r| r.height() == height && r.width() == width)
+            .context(InvalidFormatSnafu)?;
+
+        let mut checkpoint = Self::new(resolution, seed);
+        checkpoint.iterations = iterations;
+        checkpoint.num_global = num_global;
+        checkpoint.num_caustic = num_caustic;
+        for row in 0..height {
+            for column in 0..width {
+                let color = reader.spectrum()?;
+                let count = reader.usize()?;
+                checkpoint.image.restore(row, column, color, count);
+                let pixel = &mut checkpoint.pixels[row][column];
+                pixel.global = reader.observation()?;
+                pixel.caustic = reader.observation()?;
+            }
+        }
+        ensure!(reader.0.is_empty(), InvalidFormatSnafu);
+        Ok(checkpoint)
+    }
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u64(&mut self, value: u64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn val(&mut self, value: Val) {
+        self.u64(value.0.to_bits());
+    }
+
+    fn spectrum(&mut self, value: Spectrum) {
+        self.val(value.red());
+        self.val(value.green());
+        self.val(value.blue());
+    }
+
+    fn observation(&mut self, observation: Option<&Observation>) {
+        match observation {
+            Some(observation) => {
+                self.0.push(1);
+                self.spectrum(observation.flux);
+                self.u64(observation.num as u64);
+                self.val(observation.radius);
+            }
+            None => self.0.push(0),
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], RenderCheckpointError> {
+        ensure!(self.0.len() >= len, InvalidFormatSnafu);
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u64(&mut self) -> Result<u64, RenderCheckpointError> {
+        let bytes = self.take(8)?.try_into().unwrap();
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn usize(&mut self) -> Result<usize, RenderCheckpointError> {
+        usize::try_from(self.u64()?)
+            .ok()
+            .context(InvalidFormatSnafu)
+    }
+
+    fn val(&mut self) -> Result<Val, RenderCheckpointError> {
+        Ok(Val(f64::from_bits(self.u64()?)))
+    }
+
+    fn spectrum(&mut self) -> Result<Spectrum, RenderCheckpointError> {
+        Ok(Spectrum::new(self.val()?, self.val()?, self.val()?))
+    }
+
+    fn observation(&mut self) -> Result<Option<Observation>, RenderCheckpointError> {
+        match self.take(1)?[0] {
+            0 => Ok(None),
+            1 => Ok(Some(Observation {
+                flux: self.spectrum()?,
+                num: self.usize()?,
+                radius: self.val()?,
+            })),
+            _ => InvalidFormatSnafu.fail(),
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(super)))]
+#[non_exhaustive]
+pub enum RenderCheckpointError {
+    #[snafu(display("IO operation failed on checkpoint `{}`", path.display()))]
+    Io { path: PathBuf, source: IoError },
+    #[snafu(display("checkpoint data is malformed"))]
+    InvalidFormat,
+    #[snafu(display("nothing has been rendered to checkpoint yet"))]
+    NothingRendered,
+    #[snafu(display("checkpoint resolution doesn't match the camera"))]
+    ResolutionMismatch,
+    #[snafu(display("could not create the renderer to resume"))]
+    Configuration {
+        source: CoreRendererConfigurationError,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_checkpoint_decode_succeeds_reversing_encode() {
+        let mut checkpoint = RenderCheckpoint::new(Resolution::from_dimensions(3, 2).unwrap(), 42);
+        checkpoint.iterations = 3;
+        checkpoint.image.record(1, 2, Spectrum::broadcast(Val(0.3)));
+        checkpoint.pixels[1][2].caustic = Some(Observation {
+            flux: Spectrum::broadcast(Val(1.5)),
+            num: 12,
+            radius: Val(0.01),
+        });
+
+        let decoded = RenderCheckpoint::decode(&checkpoint.encode()).unwrap();
+        assert_eq!(decoded, checkpoint);
+    }
+
+    #[test]
+    fn render_checkpoint_decode_fails_when_data_is_truncated() {
+        let checkpoint = RenderCheckpoint::new(Resolution::new(2, (1, 1)).unwrap(), 42);
+        let bytes = checkpoint.encode();
+        assert!(matches!(
+            RenderCheckpoint::decode(&bytes[..bytes.len() - 1]),
+            Err(RenderCheckpointError::InvalidFormat),
+        ));
+    }
+
+    #[test]
+    fn render_checkpoint_decode_fails_when_header_exceeds_payload() {
+        let checkpoint = RenderCheckpoint::new(Resolution::new(2, (1, 1)).unwrap(), 42);
+        let mut bytes = checkpoint.encode();
+        let header = RenderCheckpoint::MAGIC.len() + 4 * 8;
+        bytes[header..header + 8].copy_from_slice(&(1u64 << 31).to_le_bytes());
+        bytes[header + 8..header + 16].copy_from_slice(&(1u64 << 31).to_le_bytes());
+        assert!(matches!(
+            RenderCheckpoint::decode(&bytes),
+            Err(RenderCheckpointError::InvalidFormat),
+        ));
+    }
+}
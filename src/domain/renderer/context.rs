@@ -2,6 +2,7 @@ use getset::{CopyGetters, Getters};
 use rand::prelude::*;
 
 use crate::domain::ray::photon::{Photon, PhotonMap, SearchPolicy};
+use crate::domain::sampling::light::EnvironmentLightSampler;
 use crate::domain::scene::entity::EntityScene;
 use crate::domain::scene::volume::VolumeScene;
 
@@ -18,6 +19,8 @@ pub struct RtContext<'a> {
     rng: &'a mut dyn RngCore,
     #[getset(get_copy = "pub")]
     config: &'a CoreRendererConfiguration,
+    #[getset(get_copy = "pub")]
+    environment: Option<&'a EnvironmentLightSampler>,
     #[getset(get = "pub")]
     photon_global: PhotonInfo<'a>,
     #[getset(get = "pub")]
@@ -40,17 +43,25 @@ impl<'a> RtContext<'a> {
             volume_scene,
             rng,
             config,
+            environment: None,
             photon_global,
             photon_casutic,
         }
     }
 
+    pub fn with_environment(self, environment: &'a EnvironmentLightSampler) -> Self {
+        Self {
+            environment: Some(environment),
+            ..self
+        }
+    }
+
     pub fn rng(&mut self) -> &mut &'a mut dyn RngCore {
         &mut self.rng
     }
 }
 
-#[derive(CopyGetters)]
+#[derive(Clone, Copy, CopyGetters)]
 #[getset(get_copy = "pub")]
 pub struct PhotonInfo<'a> {
     photons: &'a PhotonMap,
@@ -1,37 +1,61 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
 use std::time::Duration;
 
 use getset::{CopyGetters, WithSetters};
 use indicatif::{ProgressBar, ProgressFinish, ProgressStyle};
 use rand::prelude::*;
 use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use snafu::prelude::*;
 
 use crate::domain::camera::{Camera, Offset};
-use crate::domain::color::core::Spectrum;
-use crate::domain::image::core::{Image, ImageAccumulator};
+use crate::domain::color::core::{Albedo, Spectrum};
+use crate::domain::image::core::{Framebuffer, Image, ImageAccumulator};
+use crate::domain::light::primitive::EnvironmentLight;
 use crate::domain::material::def::{FluxEstimation, Material, RefDynMaterial};
-use crate::domain::math::geometry::Direction;
+use crate::domain::math::algebra::{Product, Vector};
+use crate::domain::math::geometry::{Direction, Distance, Frame};
 use crate::domain::math::numeric::{DisRange, Val};
 use crate::domain::medium::def::Medium;
 use crate::domain::medium::util::AggregateMedium;
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::{RayIntersection, RaySegment};
 use crate::domain::ray::photon::{PhotonMap, PhotonRay, SearchPolicy};
-use crate::domain::scene::entity::EntityScene;
+use crate::domain::sampling::light::EnvironmentLightSampler;
+use crate::domain::sampling::sampler::{Sampler, SamplerKind};
+use crate::domain::scene::entity::{EntityId, EntityScene, Visibility};
 use crate::domain::scene::volume::VolumeScene;
 
+use super::checkpoint::{
+    ConfigurationSnafu, IoSnafu, NothingRenderedSnafu, ResolutionMismatchSnafu,
+};
+use super::debug::{self, DepthRange};
+use super::filter::{self, FilmSample};
+use super::passes::PassRadiance;
 use super::{
-    Contribution, PhotonInfo, PmContext, PmState, Renderer, RtContext, RtState, StoragePolicy,
+    AovBuffers, Contribution, LightPass, PhotonInfo, PixelFilter, PmContext, PmState,
+    RenderCheckpoint, RenderCheckpointError, RenderMode, RenderPasses, Renderer, RtContext,
+    RtState, StoragePolicy,
 };
 
+type SamplePasses = (Vec<PassRadiance>, PassRadiance);
+
 pub struct CoreRenderer {
     camera: Camera,
     entity_scene: Box<dyn EntityScene>,
     volume_scene: Box<dyn VolumeScene>,
     config: CoreRendererConfiguration,
+    environment: Option<EnvironmentLightSampler>,
+    depth_range: DepthRange,
+    resumed: Mutex<Option<RenderCheckpoint>>,
+    last_state: Mutex<Option<RenderCheckpoint>>,
 }
 
 impl CoreRenderer {
+    const MIN_FOOTPRINT_COS: Val = Val(0.05);
+
     pub fn new(
         camera: Camera,
         entity_scene: Box<dyn EntityScene>,
@@ -39,62 +63,483 @@ impl CoreRenderer {
         config: CoreRendererConfiguration,
     ) -> Result<Self, CoreRendererConfigurationError> {
         config.validate()?;
+        if let Some(crop) = config.crop_window {
+            let resolution = camera.resolution();
+            ensure!(
+                crop.x1 <= resolution.width() && crop.y1 <= resolution.height(),
+                CropWindowOutOfBoundSnafu,
+            );
+        }
+        let environment = (config.environment.clone()).map(|environment| {
+            EnvironmentLightSampler::new(environment).with_portals(entity_scene.get_portals())
+        });
+        let depth_range = DepthRange::new(&camera, entity_scene.as_ref());
         Ok(Self {
             camera,
             entity_scene,
             volume_scene,
             config,
+            environment,
+            depth_range,
+            resumed: Mutex::new(None),
+            last_state: Mutex::new(None),
         })
     }
 
+    /// Only the next render continues from the checkpoint, every later one starts afresh.
+    pub fn resume_from<P>(
+        path: P,
+        camera: Camera,
+        entity_scene: Box<dyn EntityScene>,
+        volume_scene: Box<dyn VolumeScene>,
+        config: CoreRendererConfiguration,
+    ) -> Result<Self, RenderCheckpointError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let bytes = fs::read(path).context(IoSnafu { path })?;
+        let checkpoint = RenderCheckpoint::decode(&bytes)?;
+        ensure!(
+            checkpoint.image().resolution() == camera.resolution(),
+            ResolutionMismatchSnafu
+        );
+
+        let renderer =
+            Self::new(camera, entity_scene, volume_scene, config).context(ConfigurationSnafu)?;
+        *renderer.resumed.lock().unwrap() = Some(checkpoint);
+        Ok(renderer)
+    }
+
+    pub fn save_state<P>(&self, path: P) -> Result<(), RenderCheckpointError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let bytes = (self.last_state.lock().unwrap().as_ref())
+            .context(NothingRenderedSnafu)?
+            .encode();
+        fs::write(path, bytes).context(IoSnafu { path })
+    }
+
     fn render_pixel(
         &self,
         pos: (usize, usize),
         pixel: &mut Pixel,
-        photon_global: PhotonInfo<'_>,
-        photon_caustic: PhotonInfo<'_>,
-    ) -> Spectrum {
-        let mut rng = rand::rng();
-        let mut context = RtContext::new(
-            self,
-            self.entity_scene.as_ref(),
-            self.volume_scene.as_ref(),
-            &mut rng,
-            &self.config,
-            photon_global,
-            photon_caustic,
-        );
+        sampler: &mut dyn Sampler,
+        first_sample: usize,
+        (photon_global, photon_caustic): (PhotonInfo<'_>, PhotonInfo<'_>),
+        with_passes: bool,
+    ) -> (Vec<FilmSample>, Spectrum, Option<SamplePasses>) {
+        let width = self.camera.resolution().width();
+        let pixel_index = (pos.0 * width + pos.1) as u64;
+
+        let mut passes = Vec::new();
+        let (offsets, contributions): (Vec<_>, Vec<_>) = (0..self.config.spp_per_iteration)
+            .map(|sample| {
+                sampler.start_sample(pixel_index, (first_sample + sample) as u64);
+                let mut context = RtContext::new(
+                    self,
+                    self.entity_scene.as_ref(),
+                    self.volume_scene.as_ref(),
+                    sampler,
+                    &self.config,
+                    photon_global,
+                    photon_caustic,
+                );
+                if let Some(environment) = &self.environment {
+                    context = context.with_environment(environment);
+                }
+                let offset = self.sample_offset(context.rng(), sample);
+                let Some(ray) = self.generate_ray(context.rng(), pos.0, pos.1, offset) else {
+                    if with_passes {
+                        passes.push(PassRadiance::default());
+                    }
+                    return (offset, Contribution::new());
+                };
+                let contribution = match self.config.render_mode {
+                    RenderMode::Beauty
+                        if self.config.integrator == Integrator::AmbientOcclusion =>
+                    {
+                        self.trace_occlusion(context.rng(), &ray)
+                    }
+                    mode if mode.is_shaded() => {
+                        self.trace(&mut context, RtState::new(), &ray, DisRange::positive())
+                    }
+                    mode => self.trace_debug(mode, &ray),
+                };
+                if with_passes {
+                    let (direct, indirect) = LightPass::classify(contribution.first_hit());
+                    let indirect_light = contribution.indirect_light();
+                    let mut pass = PassRadiance::default();
+                    pass.add(direct, contribution.light() - indirect_light);
+                    pass.add(indirect, indirect_light);
+                    passes.push(pass);
+                }
+                (offset, contribution.clamp())
+            })
+            .unzip();
 
-        let contributions = (0..self.config.spp_per_iteration)
-            .map(|_| self.start_tracing(&mut context, pos))
-            .map(|c| c.clamp())
+        let photon_only = matches!(
+            self.config.render_mode,
+            RenderMode::GlobalPhotons | RenderMode::CausticPhotons
+        );
+        let samples = (offsets.into_iter().zip(&contributions))
+            .map(|(offset, c)| {
+                let light = if photon_only {
+                    Spectrum::zero()
+                } else {
+                    c.light()
+                };
+                FilmSample::new(offset, light)
+            })
             .collect();
-        pixel.radiance(
-            Contribution::average(contributions),
-            context.photon_global().emitted(),
-            context.photon_casutic().emitted(),
-        )
+        let contribution = Contribution::average(contributions);
+        pixel.accumulate(&contribution, self.config.sppm_alpha);
+        let (global, caustic) =
+            pixel.photon_radiance(photon_global.emitted(), photon_caustic.emitted());
+        let photon = match self.config.render_mode {
+            RenderMode::GlobalPhotons => global,
+            RenderMode::CausticPhotons => caustic,
+            _ => global + caustic,
+        };
+        if !with_passes {
+            return (samples, photon, None);
+        }
+
+        let mut photon_passes = PassRadiance::default();
+        photon_passes.add(LightPass::IndirectDiffuse, global);
+        photon_passes.add(LightPass::Caustic, caustic);
+        (samples, photon, Some((passes, photon_passes)))
     }
 
-    fn start_tracing<'a>(
-        &'a self,
-        context: &mut RtContext<'a>,
-        (row, column): (usize, usize),
-    ) -> Contribution {
-        let ray = self.generate_ray(row, column);
-        self.trace(context, RtState::new(), &ray, DisRange::positive())
+    fn trace_occlusion(&self, rng: &mut dyn RngCore, ray: &Ray) -> Contribution {
+        let Some((intersection, _)) = (self.entity_scene).find_visible_intersection(
+            ray,
+            DisRange::positive(),
+            Visibility::PRIMARY,
+        ) else {
+            return Contribution::from_light(Spectrum::broadcast(Val(1.0)));
+        };
+        let intersection = intersection.with_ray_offset(self.config.ray_offset);
+        let range = DisRange::positive().shrink_end(Distance::clamp(self.config.ao_max_distance));
+
+        let unoccluded = (0..self.config.ao_samples)
+            .filter(|_| {
+                let direction = Direction::random_cosine_hemisphere(intersection.normal(), rng);
+                let ray_next = intersection.spawn(direction);
+                (self.entity_scene)
+                    .find_visible_intersection(&ray_next, range, Visibility::SHADOW)
+                    .is_none()
+            })
+            .count();
+        let visibility = Val::from(unoccluded) / Val::from(self.config.ao_samples);
+        Contribution::from_light(Spectrum::broadcast(visibility))
+    }
+
+    fn trace_debug(&self, mode: RenderMode, ray: &Ray) -> Contribution {
+        if mode == RenderMode::TraversalCost {
+            let stats = self.entity_scene.count_traversal(ray, DisRange::positive());
+            let cost = Val::from(stats.total()) / Val::from(self.config.heatmap_max_count);
+            return Contribution::from_light(debug::heatmap_color(cost));
+        }
+        let Some((intersection, id)) =
+            (self.entity_scene).find_intersection(ray, DisRange::positive())
+        else {
+            return Contribution::new();
+        };
+        let color = match mode {
+            RenderMode::Beauty | RenderMode::GlobalPhotons | RenderMode::CausticPhotons => {
+                unreachable!("shaded modes should be shaded by materials")
+            }
+            RenderMode::Normal => debug::normal_color(intersection.normal()),
+            RenderMode::Depth => {
+                let cos = ray.direction().dot(self.camera.orientation());
+                let depth = intersection.distance().value() * cos;
+                Spectrum::broadcast(self.depth_range.normalize(depth))
+            }
+            RenderMode::PrimitiveId => debug::false_color(id),
+            RenderMode::TraversalCost => unreachable!("traversal cost should be handled above"),
+        };
+        Contribution::from_light(color)
+    }
+
+    fn sample_offset(&self, rng: &mut dyn RngCore, sample: usize) -> Offset {
+        match self.config.sampler {
+            // Sobol points are already stratified over the first two dimensions.
+            SamplerKind::Sobol => Offset::new(Val(rng.random()), Val(rng.random()))
+                .expect("offset range should be bounded to [0, 1)"),
+            SamplerKind::Random => Offset::stratified(sample, self.config.spp_per_iteration, rng),
+        }
+    }
+
+    fn generate_ray(
+        &self,
+        rng: &mut dyn RngCore,
+        row: usize,
+        column: usize,
+        offset: Offset,
+    ) -> Option<Ray> {
+        let ray = self.camera.calc_ray_in_pixel(row, column, offset)?;
+        let ray = self.camera.sample_lens(ray, rng);
+        let time = self.camera.sample_time(rng);
+        Some(ray.with_time(time))
+    }
+
+    pub fn render_framebuffer(&self) -> Framebuffer<Spectrum> {
+        Framebuffer::from(&self.render())
+    }
+
+    pub fn render_with_aovs(&self) -> AovBuffers {
+        let beauty = self.render();
+
+        let resolution = self.camera.resolution().clone();
+        let mut albedo = Framebuffer::new(resolution.clone(), Albedo::BLACK);
+        let mut normal = Framebuffer::new(resolution.clone(), None);
+        let mut depth = Framebuffer::new(resolution.clone(), None);
+
+        let (height, width) = (resolution.height(), resolution.width());
+        let pool = self.build_thread_pool();
+        let res = pool.install(|| {
+            (0..height)
+                .into_par_iter()
+                .flat_map(|r| (0..width).into_par_iter().map(move |c| (r, c)))
+                .map(|(row, column)| {
+                    let res = (self.camera)
+                        .calc_ray_in_pixel(row, column, Offset::center())
+                        .and_then(|ray| {
+                            let range = DisRange::positive();
+                            (self.entity_scene).find_visible_intersection(
+                                &ray,
+                                range,
+                                Visibility::PRIMARY,
+                            )
+                        });
+                    let sample = res.map(|(intersection, id)| {
+                        let entities = self.entity_scene.get_entities();
+                        let material = entities.get_material(id.material_id()).unwrap();
+                        (material.albedo(&intersection), intersection)
+                    });
+                    ((row, column), sample)
+                })
+                .collect_vec_list()
+        });
+
+        for ((row, column), sample) in res.into_iter().flatten() {
+            if let Some((a, intersection)) = sample {
+                albedo.set(row, column, a);
+                normal.set(row, column, Some(intersection.normal()));
+                depth.set(row, column, Some(intersection.distance()));
+            }
+        }
+
+        AovBuffers::new(beauty, albedo, normal, depth)
+    }
+
+    pub fn render_progressive<F>(&self, callback: F) -> Image
+    where
+        F: FnMut(usize, &Image) -> RenderControl,
+    {
+        self.render_progressive_impl(None, callback)
+    }
+
+    /// The passes only cover this call's iterations and are always box filtered.
+    pub fn render_with_passes(&self) -> RenderPasses {
+        let resolution = self.camera.resolution().clone();
+        let mut passes = (LightPass::ALL.iter())
+            .map(|_| ImageAccumulator::new(Image::new(resolution.clone())))
+            .collect::<Vec<_>>();
+        let beauty =
+            self.render_progressive_impl(Some(&mut passes), |_, _| RenderControl::Continue);
+        let passes = passes.into_iter().map(|p| p.into_inner()).collect();
+        RenderPasses::new(beauty, passes)
+    }
+
+    fn render_progressive_impl<F>(
+        &self,
+        mut passes: Option<&mut Vec<ImageAccumulator>>,
+        mut callback: F,
+    ) -> Image
+    where
+        F: FnMut(usize, &Image) -> RenderControl,
+    {
+        let resolution = self.camera.resolution().clone();
+        let (height, width) = (resolution.height(), resolution.width());
+        let mut checkpoint = (self.resumed.lock().unwrap().take()).unwrap_or_else(|| {
+            let seed = (self.config.seed).unwrap_or_else(|| rand::rng().random());
+            RenderCheckpoint::new(resolution.clone(), seed)
+        });
+        let RenderCheckpoint {
+            seed,
+            iterations: first_iteration,
+            ref mut num_global,
+            ref mut num_caustic,
+            ref mut image,
+            ref mut pixels,
+        } = checkpoint;
+
+        let num_pixel = match self.config.crop_window {
+            Some(crop) => (crop.y1 - crop.y0) * (crop.x1 - crop.x0),
+            None => height * width,
+        };
+        let pool = self.build_thread_pool();
+        let pb = self.init_progress_bar(num_pixel);
+        let mut completed = first_iteration;
+        for iteration in first_iteration..(first_iteration + self.config.iterations) {
+            let seed_iteration = Self::derive_seed(seed, iteration);
+            let (seed_global, seed_caustic) = (
+                Self::derive_seed(seed_iteration, 0),
+                Self::derive_seed(seed_iteration, 1),
+            );
+            let (pmg, pmc) = pool.install(|| {
+                let pmg = self.build_photon_map(
+                    StoragePolicy::Global,
+                    self.config.photons_global,
+                    seed_global,
+                );
+                let pmc = self.build_photon_map(
+                    StoragePolicy::Caustic,
+                    self.config.photons_caustic,
+                    seed_caustic,
+                );
+                (pmg, pmc)
+            });
+            if self.config.uses_photons() {
+                *num_global += self.config.photons_global;
+                *num_caustic += self.config.photons_caustic;
+            }
+
+            let crop = self.config.crop_window;
+            let meshgrid = (pixels.par_iter_mut().enumerate())
+                .map(|(r, p)| (r, p.par_iter_mut().enumerate()))
+                .flat_map(|(r, pi)| pi.map(move |(c, p)| ((r, c), p)))
+                .filter(|((r, c), _)| crop.is_none_or(|crop| crop.contains(*r, *c)));
+            let res = pool.install(|| {
+                meshgrid
+                    .map(|(pos, pixel)| {
+                        pb.inc(1);
+                        let num = self.config.initial_num_nearest;
+                        let (ng, nc) = (*num_global, *num_caustic);
+                        let pg = PhotonInfo::new(&pmg, pixel.get_policy_global(num), ng);
+                        let pc = PhotonInfo::new(&pmc, pixel.get_policy_caustic(num), nc);
+                        let mut sampler = self.config.sampler.create(seed);
+                        let first_sample = iteration * self.config.spp_per_iteration;
+                        let with_passes = passes.is_some();
+                        let (samples, photon, pass) = self.render_pixel(
+                            pos,
+                            pixel,
+                            sampler.as_mut(),
+                            first_sample,
+                            (pg, pc),
+                            with_passes,
+                        );
+                        (pos, samples, photon, pass)
+                    })
+                    .collect_vec_list()
+            });
+
+            let mut samples = Framebuffer::new(resolution.clone(), Vec::new());
+            let mut pass_samples = Framebuffer::new(resolution.clone(), Vec::new());
+            let mut photons = Vec::with_capacity(num_pixel);
+            for ((row, column), s, photon, pass) in res.into_iter().flatten() {
+                samples.set(row, column, s);
+                let photon_passes = pass.map(|(pass, photon_passes)| {
+                    pass_samples.set(row, column, pass);
+                    photon_passes
+                });
+                photons.push(((row, column), photon, photon_passes));
+            }
+
+            let (filter, radius) = (self.config.pixel_filter, self.config.filter_radius());
+            let colors = pool.install(|| {
+                (photons.into_par_iter())
+                    .map(|(pos, photon, photon_passes)| {
+                        let light = filter::reconstruct(filter, radius, &samples, pos);
+                        let passes = photon_passes.map(|photon_passes| {
+                            let passes = filter::reconstruct_passes(
+                                filter,
+                                radius,
+                                &samples,
+                                &pass_samples,
+                                pos,
+                            );
+                            LightPass::ALL.map(|p| passes.get(p) + photon_passes.get(p))
+                        });
+                        (pos, light + photon, passes)
+                    })
+                    .collect_vec_list()
+            });
+            for ((row, column), color, pass) in colors.into_iter().flatten() {
+                image.record(row, column, color);
+                if let (Some(passes), Some(pass)) = (passes.as_deref_mut(), pass) {
+                    for (accumulator, radiance) in passes.iter_mut().zip(pass) {
+                        accumulator.record(row, column, radiance);
+                    }
+                }
+            }
+
+            completed = iteration + 1;
+            if callback(completed, image.image()) == RenderControl::Cancel {
+                pb.abandon_with_message("Cancelled");
+                break;
+            }
+        }
+
+        checkpoint.iterations = completed;
+        let res = checkpoint.image().clone();
+        *self.last_state.lock().unwrap() = Some(checkpoint);
+        res
+    }
+
+    /// Probes for the UV footprint only when the material samples prefiltered textures.
+    fn attach_footprint(
+        &self,
+        ray: &Ray,
+        intersection: RayIntersection,
+        id: EntityId,
+        material: &RefDynMaterial<'_>,
+    ) -> RayIntersection {
+        let position = intersection.position();
+        let distance = (position - self.camera.position()).norm();
+        let cos = (ray.direction().dot(intersection.normal()).abs()).max(Self::MIN_FOOTPRINT_COS);
+        let width = self.camera.pixel_spread_angle() * distance / cos;
+
+        let intersection = intersection.with_footprint(width);
+        let Some(uv) = intersection.uv().filter(|_| material.uses_footprint()) else {
+            return intersection;
+        };
+
+        let probe_uv = |offset: Vector| {
+            let direction = Direction::normalize(position + offset - ray.start()).ok()?;
+            let probe = Ray::new(ray.start(), direction).with_time(ray.time());
+            let (hit, hit_id) = (self.entity_scene).find_visible_intersection(
+                &probe,
+                DisRange::positive(),
+                Visibility::PRIMARY,
+            )?;
+            hit.uv()
+                .filter(|_| hit_id.material_id() == id.material_id())
+        };
+        let frame = Frame::new(intersection.normal());
+        let footprint = [frame.tangent(), frame.cross()]
+            .into_iter()
+            .filter_map(|tangent| {
+                let offset = tangent.to_vector() * width;
+                let hit_uv = probe_uv(offset).or_else(|| probe_uv(-offset))?;
+                Some((hit_uv.u() - uv.u()).abs().max((hit_uv.v() - uv.v()).abs()))
+            })
+            .reduce(Val::max);
+        match footprint {
+            Some(footprint) => intersection.with_uv_footprint(footprint),
+            None => intersection,
+        }
     }
 
-    fn generate_ray(&self, row: usize, column: usize) -> Ray {
-        let mut rng = rand::rng();
-        let offset = Offset::new(Val(rng.random()), Val(rng.random()))
-            .expect("offset range should be bounded to [0, 1)");
-        let point = (self.camera)
-            .calc_point_in_pixel(row, column, offset)
-            .expect("row and column should not be out of bound");
-        let direction = Direction::normalize(point - self.camera.position())
-            .expect("focal length should be positive");
-        Ray::new(point, direction)
+    fn build_thread_pool(&self) -> ThreadPool {
+        ThreadPoolBuilder::new()
+            .num_threads(self.config.threads)
+            .build()
+            .expect("thread pool should be able to be built")
     }
 
     fn init_progress_bar(&self, num_pixel: usize) -> ProgressBar {
@@ -112,64 +557,71 @@ impl CoreRenderer {
         bar
     }
 
-    fn build_photon_map(&self, policy: StoragePolicy, total: usize) -> PhotonMap {
-        let photons = (0..total)
+    pub fn bake_photon_map(&self, policy: StoragePolicy, seed: u64) -> PhotonMap {
+        let total = match policy {
+            StoragePolicy::Global => self.config.photons_global,
+            StoragePolicy::Caustic => self.config.photons_caustic,
+        };
+        self.build_thread_pool()
+            .install(|| self.trace_photons(policy, total, seed))
+    }
+
+    fn build_photon_map(&self, policy: StoragePolicy, total: usize, seed: u64) -> PhotonMap {
+        if !self.config.uses_photons() {
+            return PhotonMap::build(Vec::new());
+        }
+        self.trace_photons(policy, total, seed)
+    }
+
+    fn trace_photons(&self, policy: StoragePolicy, total: usize, seed: u64) -> PhotonMap {
+        let traced = (0..total)
             .into_par_iter()
-            .map(|_| {
+            .map(|index| {
                 let mut photons = Vec::new();
-                let mut rng = rand::rng();
-                if let Some(photon) = self.entity_scene.get_emitters().sample_photon(&mut rng) {
+                let mut rng = Self::derive_rng(seed, index);
+                let sample = self.entity_scene.get_emitters().sample_photon(&mut rng);
+                if let Some(photon) = &sample {
                     let mut context =
                         PmContext::new(self, self.entity_scene.as_ref(), &mut rng, &mut photons);
                     let state = PmState::new(false, policy);
                     self.emit(&mut context, state, photon.photon(), DisRange::positive());
                 }
-                photons
+                (photons, sample.map(|sample| sample.emitter()))
             })
-            .flatten()
-            .collect();
-        PhotonMap::build(photons)
+            .collect_vec_list();
+
+        let mut photons = Vec::new();
+        let mut counts = Vec::new();
+        for (traced, emitter) in traced.into_iter().flatten() {
+            if let Some(emitter) = emitter {
+                if counts.len() <= emitter {
+                    counts.resize(emitter + 1, 0);
+                }
+                counts[emitter] += 1;
+            }
+            photons.extend(traced);
+        }
+        PhotonMap::build(photons).with_photon_counts(counts)
+    }
+
+    fn derive_seed(seed: u64, index: usize) -> u64 {
+        let mix = |mut x: u64| {
+            x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            x ^ (x >> 31)
+        };
+        mix(mix(seed) ^ index as u64)
+    }
+
+    fn derive_rng(seed: u64, index: usize) -> StdRng {
+        StdRng::seed_from_u64(Self::derive_seed(seed, index))
     }
 }
 
 impl Renderer for CoreRenderer {
     fn render(&self) -> Image {
-        let image = Image::new(self.camera.resolution().clone());
-        let mut image = ImageAccumulator::new(image);
-
-        let height = image.resolution().height();
-        let width = image.resolution().width();
-
-        let mut pixels = vec![vec![Pixel::new(); width]; height];
-        let mut num_global = 0;
-        let mut num_caustic = 0;
-
-        let pb = self.init_progress_bar(height * width);
-        for _ in 0..self.config.iterations {
-            let pmg = self.build_photon_map(StoragePolicy::Global, self.config.photons_global);
-            let pmc = self.build_photon_map(StoragePolicy::Caustic, self.config.photons_caustic);
-            num_global += self.config.photons_global;
-            num_caustic += self.config.photons_caustic;
-
-            let meshgrid = (pixels.par_iter_mut().enumerate())
-                .map(|(r, p)| (r, p.par_iter_mut().enumerate()))
-                .flat_map(|(r, pi)| pi.map(move |(c, p)| ((r, c), p)));
-            let res = meshgrid
-                .map(|(pos, pixel)| {
-                    pb.inc(1);
-                    let num = self.config.initial_num_nearest;
-                    let pg = PhotonInfo::new(&pmg, pixel.get_policy_global(num), num_global);
-                    let pc = PhotonInfo::new(&pmc, pixel.get_policy_caustic(num), num_caustic);
-                    (pos, self.render_pixel(pos, pixel, pg, pc))
-                })
-                .collect_vec_list();
-
-            for ((row, column), color) in res.into_iter().flatten() {
-                image.record(row, column, color);
-            }
-        }
-
-        image.into_inner()
+        self.render_progressive(|_, _| RenderControl::Continue)
     }
 
     fn trace<'a>(
@@ -179,19 +631,59 @@ impl Renderer for CoreRenderer {
         ray: &Ray,
         range: DisRange,
     ) -> Contribution {
-        let state = state.increment_depth();
-        if state.depth() > self.config.max_depth {
+        let mut state = state.increment_depth();
+        if state.depth() > self.config.max_depth
+            || state.invisible_depth() > self.config.max_invisible_depth
+        {
             return Contribution::new();
         }
 
-        let res = self.entity_scene.find_intersection(ray, range);
-        if let Some((intersection, id)) = res {
+        let mut survival_prob = Val(1.0);
+        if let Some(min_depth) = self.config.russian_roulette {
+            if state.depth() > min_depth {
+                let throughput = state.throughput();
+                survival_prob = (throughput.red())
+                    .max(throughput.green())
+                    .max(throughput.blue())
+                    .clamp(Val(0.0), Val(1.0));
+                if Val(context.rng().random()) >= survival_prob {
+                    return Contribution::new();
+                }
+                state = state.with_throughput(throughput / survival_prob);
+            }
+        }
+
+        let depth = state.depth();
+        let visibility = if depth == 1 {
+            Visibility::PRIMARY
+        } else {
+            Visibility::INDIRECT
+        };
+        let res = (self.entity_scene).find_visible_intersection(ray, range, visibility);
+        let contribution = if let Some((intersection, id)) = res {
+            let mut intersection = intersection.with_ray_offset(self.config.ray_offset);
             let entities = self.entity_scene.get_entities();
             let material = entities.get_material(id.material_id()).unwrap();
+            if depth == 1 {
+                intersection = self.attach_footprint(ray, intersection, id, &material);
+            }
+            let kind = material.kind();
             let target = Some((&intersection, material));
-            self.trace_to(context, state, ray, target)
+            let contribution = self.trace_to(context, state, ray, target);
+            if depth == 1 {
+                contribution.with_first_hit(kind)
+            } else {
+                contribution
+            }
         } else {
             self.trace_to(context, state, ray, None)
+        };
+        // Terminated paths are already excluded from the flux estimations.
+        let contribution = contribution.scale_light(survival_prob.recip());
+        match self.config.indirect_clamp {
+            Some(max) if depth > 1 => contribution.clamp_light(max).into_indirect(),
+            _ if depth > 1 => contribution.into_indirect(),
+            _ => contribution,
         }
     }
 
@@ -202,24 +694,36 @@ impl Renderer for CoreRenderer {
         ray: &Ray,
         target: Option<(&RayIntersection, RefDynMaterial)>,
     ) -> Contribution {
-        let (surface_res, vis_range) = if let Some((intersection, material)) = target {
-            let res = material.shade(context, state.clone(), ray, intersection);
-            let vis_range = DisRange::positive().shrink_end(intersection.distance());
-            (res, vis_range)
-        } else {
-            let res = Contribution::from_light(self.config.background_color);
-            (res, DisRange::positive())
+        let vis_range = match target {
+            Some((intersection, _)) => DisRange::positive().shrink_end(intersection.distance()),
+            None => DisRange::positive(),
+        };
+        let shade_surface = |context: &mut RtContext<'a>, state: RtState| {
+            if let Some((intersection, material)) = target {
+                material.shade(context, state, ray, intersection)
+            } else {
+                // Direct lighting from the environment is already gathered by light sampling
+                // wherever emissive surfaces are skipped.
+                let background = match &self.config.environment {
+                    Some(_) if state.skip_emissive() => Spectrum::zero(),
+                    Some(environment) => environment.radiance(ray.direction()),
+                    None => self.config.background_color,
+                };
+                Contribution::from_light(background)
+            }
         };
 
         if !state.visible() {
-            return surface_res;
+            return shade_surface(context, state);
         }
         let segments = self.volume_scene.find_segments(ray, vis_range);
         let aggregator = AggregateMedium::new(self.volume_scene.as_ref(), &segments);
 
         let segment = RaySegment::from(vis_range);
+        let transmittance = aggregator.transmittance(ray, &segment, *context.rng());
+        let state_surface = state.clone().scale_throughput(transmittance);
+        let surface_res = shade_surface(context, state_surface);
         let volume_res = aggregator.shade(context, state, ray, &segment);
-        let transmittance = aggregator.transmittance(ray, &segment);
         transmittance * surface_res + volume_res
     }
 
@@ -230,8 +734,15 @@ impl Renderer for CoreRenderer {
         photon: &PhotonRay,
         range: DisRange,
     ) {
-        let res = context.scene().find_intersection(photon.ray(), range);
+        let state = state.increment_depth();
+        if state.depth() > self.config.max_depth {
+            return;
+        }
+
+        let res =
+            (context.scene()).find_visible_intersection(photon.ray(), range, Visibility::INDIRECT);
         if let Some((intersection, id)) = res {
+            let intersection = intersection.with_ray_offset(self.config.ray_offset);
             let entities = context.scene().get_entities();
             let material = entities.get_material(id.material_id()).unwrap();
             material.receive(context, state, photon, &intersection);
@@ -242,17 +753,113 @@ impl Renderer for CoreRenderer {
 #[derive(Debug, Clone, PartialEq, CopyGetters, WithSetters)]
 #[getset(get_copy = "pub", set_with = "pub")]
 pub struct CoreRendererConfiguration {
+    render_mode: RenderMode,
+    integrator: Integrator,
     iterations: usize,
     spp_per_iteration: usize,
     max_depth: usize,
     max_invisible_depth: usize,
+    #[getset(skip)]
+    russian_roulette: Option<usize>,
     photons_global: usize,
     photons_caustic: usize,
     initial_num_nearest: usize,
+    sppm_alpha: Val,
+    sampler: SamplerKind,
+    pixel_filter: PixelFilter,
+    #[getset(skip)]
+    filter_radius: Option<Val>,
+    threads: usize,
     background_color: Spectrum,
+    #[getset(skip)]
+    environment: Option<EnvironmentLight>,
+    #[getset(skip)]
+    seed: Option<u64>,
+    #[getset(skip)]
+    crop_window: Option<CropWindow>,
+    #[getset(skip)]
+    indirect_clamp: Option<Val>,
+    next_event_estimation: bool,
+    multiple_importance_sampling: bool,
+    equi_angular_sampling: bool,
+    ray_offset: Val,
+    ao_samples: usize,
+    ao_max_distance: Val,
+    heatmap_max_count: usize,
 }
 
 impl CoreRendererConfiguration {
+    pub fn russian_roulette(&self) -> Option<usize> {
+        self.russian_roulette
+    }
+
+    pub fn with_russian_roulette(self, min_depth: usize) -> Self {
+        Self {
+            russian_roulette: Some(min_depth),
+            ..self
+        }
+    }
+
+    pub fn environment(&self) -> Option<&EnvironmentLight> {
+        self.environment.as_ref()
+    }
+
+    pub fn with_environment(self, environment: EnvironmentLight) -> Self {
+        Self {
+            environment: Some(environment),
+            ..self
+        }
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            ..self
+        }
+    }
+
+    pub fn indirect_clamp(&self) -> Option<Val> {
+        self.indirect_clamp
+    }
+
+    /// Direct lighting on primary hits is left unclamped.
+    pub fn with_indirect_clamp(self, max: Val) -> Self {
+        Self {
+            indirect_clamp: Some(max),
+            ..self
+        }
+    }
+
+    pub fn filter_radius(&self) -> Val {
+        (self.filter_radius).unwrap_or_else(|| self.pixel_filter.default_radius())
+    }
+
+    pub fn with_filter_radius(self, radius: Val) -> Self {
+        Self {
+            filter_radius: Some(radius),
+            ..self
+        }
+    }
+
+    pub fn crop_window(&self) -> Option<CropWindow> {
+        self.crop_window
+    }
+
+    pub fn with_crop_window(self, x0: usize, y0: usize, x1: usize, y1: usize) -> Self {
+        Self {
+            crop_window: Some(CropWindow { x0, y0, x1, y1 }),
+            ..self
+        }
+    }
+
+    fn uses_photons(&self) -> bool {
+        self.render_mode.is_shaded() && self.integrator == Integrator::PhotonMapping
+    }
+
     pub fn validate(&self) -> Result<(), CoreRendererConfigurationError> {
         ensure!(self.iterations > 0, InvalidIterationsSnafu);
         ensure!(self.spp_per_iteration > 0, InvalidSppPerIterationSnafu);
@@ -265,6 +872,30 @@ impl CoreRendererConfiguration {
             self.max_invisible_depth <= self.max_depth,
             ExceededMaxInvisibleDepthSnafu,
         );
+        ensure!(
+            self.russian_roulette.is_none_or(|d| d <= self.max_depth),
+            ExceededRussianRouletteDepthSnafu,
+        );
+        ensure!(
+            Val(0.0) < self.sppm_alpha && self.sppm_alpha <= Val(1.0),
+            InvalidSppmAlphaSnafu,
+        );
+        ensure!(
+            self.indirect_clamp.is_none_or(|max| max > Val(0.0)),
+            InvalidIndirectClampSnafu,
+        );
+        ensure!(
+            self.filter_radius.is_none_or(|radius| radius > Val(0.0)),
+            InvalidFilterRadiusSnafu,
+        );
+        ensure!(self.ray_offset >= Val(0.0), NegativeRayOffsetSnafu);
+        ensure!(self.ao_samples > 0, InvalidAoSamplesSnafu);
+        ensure!(self.ao_max_distance > Val(0.0), InvalidAoMaxDistanceSnafu);
+        ensure!(self.heatmap_max_count > 0, InvalidHeatmapMaxCountSnafu);
+        ensure!(
+            self.crop_window.is_none_or(|c| c.x0 < c.x1 && c.y0 < c.y1),
+            InvalidCropWindowSnafu,
+        );
         Ok(())
     }
 }
@@ -272,18 +903,66 @@ impl CoreRendererConfiguration {
 impl Default for CoreRendererConfiguration {
     fn default() -> Self {
         Self {
+            render_mode: RenderMode::Beauty,
+            integrator: Integrator::PhotonMapping,
             iterations: 4,
             spp_per_iteration: 4,
             max_depth: 12,
             max_invisible_depth: 4,
+            russian_roulette: None,
             photons_global: 200000,
             photons_caustic: 1000000,
             initial_num_nearest: 100,
+            sppm_alpha: Val(0.75),
+            sampler: SamplerKind::Random,
+            pixel_filter: PixelFilter::Box,
+            filter_radius: None,
+            threads: 0,
             background_color: Spectrum::zero(),
+            environment: None,
+            seed: None,
+            crop_window: None,
+            indirect_clamp: None,
+            next_event_estimation: true,
+            multiple_importance_sampling: true,
+            equi_angular_sampling: true,
+            ray_offset: RayIntersection::DEFAULT_RAY_OFFSET,
+            ao_samples: 16,
+            ao_max_distance: Val::INFINITY,
+            heatmap_max_count: 64,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderControl {
+    Continue,
+    Cancel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Integrator {
+    PhotonMapping,
+    PathTracing,
+    AmbientOcclusion,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct CropWindow {
+    x0: usize,
+    y0: usize,
+    x1: usize,
+    y1: usize,
+}
+
+impl CropWindow {
+    #[inline]
+    pub fn contains(&self, row: usize, column: usize) -> bool {
+        (self.y0..self.y1).contains(&row) && (self.x0..self.x1).contains(&column)
+    }
+}
+
 #[derive(Debug, Snafu, Clone, PartialEq)]
 #[non_exhaustive]
 pub enum CoreRendererConfigurationError {
@@ -297,47 +976,72 @@ pub enum CoreRendererConfigurationError {
     NonPositiveMaxInvisibleDepth,
     #[snafu(display("max invisible depth is larger than max depth"))]
     ExceededMaxInvisibleDepth,
+    #[snafu(display("russian roulette depth is larger than max depth"))]
+    ExceededRussianRouletteDepth,
     #[snafu(display("initial number of nearest is not positive"))]
     InvalidInitialNumNearest,
+    #[snafu(display("SPPM alpha is not in (0, 1]"))]
+    InvalidSppmAlpha,
+    #[snafu(display("indirect clamp is not positive"))]
+    InvalidIndirectClamp,
+    #[snafu(display("filter radius is not positive"))]
+    InvalidFilterRadius,
+    #[snafu(display("ray offset is negative"))]
+    NegativeRayOffset,
+    #[snafu(display("number of ambient occlusion samples is not positive"))]
+    InvalidAoSamples,
+    #[snafu(display("ambient occlusion max distance is not positive"))]
+    InvalidAoMaxDistance,
+    #[snafu(display("heatmap max count is not positive"))]
+    InvalidHeatmapMaxCount,
+    #[snafu(display("crop window is empty"))]
+    InvalidCropWindow,
+    #[snafu(display("crop window exceeds the image resolution"))]
+    CropWindowOutOfBound,
 }
 
 #[derive(Debug, Clone, PartialEq)]
-struct Pixel {
-    global: Option<Observation>,
-    caustic: Option<Observation>,
+pub(super) struct Pixel {
+    pub(super) global: Option<Observation>,
+    pub(super) caustic: Option<Observation>,
 }
 
 impl Pixel {
-    fn new() -> Self {
+    pub(super) fn new() -> Self {
         Self {
             global: None,
             caustic: None,
         }
     }
 
-    fn radiance(
-        &mut self,
-        cont: Contribution,
-        emitted_global: usize,
-        emitted_caustic: usize,
-    ) -> Spectrum {
-        if let Some(flux) = cont.global() {
+    fn accumulate(&mut self, cont: &Contribution, alpha: Val) {
+        let usable = |flux: &&FluxEstimation| flux.is_empty() || flux.is_finite();
+        if let Some(flux) = cont.global().filter(usable) {
             if let Some(global) = &mut self.global {
-                global.accumulate(flux);
+                global.accumulate(flux, alpha);
             } else if !flux.is_empty() {
                 self.global = Some(Observation::new(flux));
             }
         }
-        if let Some(flux) = cont.caustic() {
+        if let Some(flux) = cont.caustic().filter(usable) {
             if let Some(caustic) = &mut self.caustic {
-                caustic.accumulate(flux);
+                caustic.accumulate(flux, alpha);
             } else if !flux.is_empty() {
                 self.caustic = Some(Observation::new(flux));
             }
         }
-        cont.light()
-            + (self.global.as_ref()).map_or(Spectrum::zero(), |o| o.radiance(emitted_global))
-            + (self.caustic.as_ref()).map_or(Spectrum::zero(), |o| o.radiance(emitted_caustic))
+    }
+
+    fn photon_radiance(
+        &self,
+        emitted_global: usize,
+        emitted_caustic: usize,
+    ) -> (Spectrum, Spectrum) {
+        let global =
+            (self.global.as_ref()).map_or(Spectrum::zero(), |o| o.radiance(emitted_global));
+        let caustic =
+            (self.caustic.as_ref()).map_or(Spectrum::zero(), |o| o.radiance(emitted_caustic));
+        (global, caustic)
     }
 
     fn get_policy_global(&self, default_num: usize) -> SearchPolicy {
@@ -358,15 +1062,13 @@ impl Pixel {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-struct Observation {
-    flux: Spectrum,
-    num: usize,
-    radius: Val,
+pub(super) struct Observation {
+    pub(super) flux: Spectrum,
+    pub(super) num: usize,
+    pub(super) radius: Val,
 }
 
 impl Observation {
-    const NUM_ATTENUATION: Val = Val(0.75);
-
     fn new(flux: &FluxEstimation) -> Self {
         Self {
             flux: flux.flux(),
@@ -375,8 +1077,8 @@ impl Observation {
         }
     }
 
-    fn accumulate(&mut self, flux: &FluxEstimation) {
-        let total = self.num + usize::from(flux.num() * Self::NUM_ATTENUATION);
+    fn accumulate(&mut self, flux: &FluxEstimation, alpha: Val) {
+        let total = self.num + usize::from(flux.num() * alpha);
         let fraction = Val::from(total) / (Val::from(self.num) + flux.num());
         self.flux = (self.flux + flux.flux()) * fraction;
         self.num = total;
@@ -388,3 +1090,1501 @@ impl Observation {
         self.flux / (area * Val::from(num_emitted))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::camera::Resolution;
+    use crate::domain::light::primitive::{DirectionalLight, PointLight, SpotLight};
+    use crate::domain::material::def::DynMaterial;
+    use crate::domain::material::primitive::{
+        Coated, Conductor, Diffuse, Emissive, GlossyPredefinition, NormalMapped, Refractive,
+        Scattering, Specular,
+    };
+    use crate::domain::math::geometry::{Normal, Point, SpreadAngle};
+    use crate::domain::medium::def::DynMedium;
+    use crate::domain::medium::primitive::{GridMedium, HenyeyGreenstein, Isotropic};
+    use crate::domain::scene::entity::{
+        BvhEntitySceneBuilder, EntitySceneBuilder, TypedEntitySceneBuilder,
+    };
+    use crate::domain::scene::volume::{
+        BvhVolumeSceneBuilder, TypedVolumeSceneBuilder, VolumeSceneBuilder,
+    };
+    use crate::domain::shape::def::BoundingBox;
+    use crate::domain::shape::mesh::MeshConstructor;
+    use crate::domain::shape::primitive::{Aabb, Plane, Polygon, Sphere};
+    use crate::domain::texture::def::{DynAlbedoTexture, UvCoordinate};
+    use crate::domain::texture::primitive::{Checkerboard, Constant, FilterMode, ImageMap};
+
+    use super::*;
+
+    fn camera(position: Point, orientation: Direction, size: usize) -> Camera {
+        Camera::new(
+            position,
+            orientation,
+            Resolution::new(size, (1, 1)).unwrap(),
+            Distance::new(Val(0.1)).unwrap(),
+            Distance::new(Val(0.5)).unwrap(),
+        )
+    }
+
+    fn scene_renderer<F>(camera: Camera, config: CoreRendererConfiguration, add: F) -> CoreRenderer
+    where
+        F: FnOnce(&mut BvhEntitySceneBuilder),
+    {
+        let volume_scene = BvhVolumeSceneBuilder::new().build();
+        scene_renderer_in(camera, volume_scene, config, add)
+    }
+
+    fn scene_renderer_in<F>(
+        camera: Camera,
+        volume_scene: Box<dyn VolumeScene>,
+        config: CoreRendererConfiguration,
+        add: F,
+    ) -> CoreRenderer
+    where
+        F: FnOnce(&mut BvhEntitySceneBuilder),
+    {
+        CoreRenderer::new(camera, entity_scene(add), volume_scene, config).unwrap()
+    }
+
+    fn entity_scene<F>(add: F) -> Box<dyn EntityScene>
+    where
+        F: FnOnce(&mut BvhEntitySceneBuilder),
+    {
+        let mut builder = BvhEntitySceneBuilder::new();
+        add(&mut builder);
+        builder.build()
+    }
+
+    fn medium_scene<M>(min: Point, max: Point, medium: M) -> Box<dyn VolumeScene>
+    where
+        M: Into<DynMedium>,
+    {
+        let mut builder = BvhVolumeSceneBuilder::new();
+        builder.add(Aabb::new(min, max), medium);
+        builder.build()
+    }
+
+    fn unit_square(z: Val) -> Polygon {
+        Polygon::new([
+            Point::new(Val(-1.0), Val(-1.0), z),
+            Point::new(Val(1.0), Val(-1.0), z),
+            Point::new(Val(1.0), Val(1.0), z),
+            Point::new(Val(-1.0), Val(1.0), z),
+        ])
+        .unwrap()
+    }
+
+    fn reds(image: &Image) -> Vec<Val> {
+        let resolution = image.resolution();
+        (0..resolution.height())
+            .flat_map(|row| (0..resolution.width()).map(move |column| (row, column)))
+            .map(|(row, column)| image.get(row, column).unwrap().red())
+            .collect()
+    }
+
+    fn mean(image: &Image) -> Spectrum {
+        let resolution = image.resolution();
+        let mut sum = Spectrum::zero();
+        for row in 0..resolution.height() {
+            for column in 0..resolution.width() {
+                sum += image.get(row, column).unwrap();
+            }
+        }
+        sum / Val::from(resolution.height() * resolution.width())
+    }
+
+    fn assert_identical(first: &Image, second: &Image) {
+        let resolution = first.resolution();
+        for row in 0..resolution.height() {
+            for column in 0..resolution.width() {
+                let (a, b) = (
+                    first.get(row, column).unwrap(),
+                    second.get(row, column).unwrap(),
+                );
+                assert_eq!(a.red().0.to_bits(), b.red().0.to_bits());
+                assert_eq!(a.green().0.to_bits(), b.green().0.to_bits());
+                assert_eq!(a.blue().0.to_bits(), b.blue().0.to_bits());
+            }
+        }
+    }
+
+    fn diffuse_box_renderer(config: CoreRendererConfiguration) -> CoreRenderer {
+        let (camera, entity_scene, volume_scene) = diffuse_box_scene();
+        CoreRenderer::new(camera, entity_scene, volume_scene, config).unwrap()
+    }
+
+    fn diffuse_box_scene() -> (Camera, Box<dyn EntityScene>, Box<dyn VolumeScene>) {
+        box_scene(Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()))
+    }
+
+    fn box_scene<M>(material: M) -> (Camera, Box<dyn EntityScene>, Box<dyn VolumeScene>)
+    where
+        M: Into<DynMaterial>,
+    {
+        let camera = Camera::new(
+            Point::new(Val(0.0), Val(0.0), Val(-0.5)),
+            Direction::z_direction(),
+            Resolution::new(8, (1, 1)).unwrap(),
+            Distance::new(Val(0.5)).unwrap(),
+            Distance::new(Val(0.25)).unwrap(),
+        );
+        let entity_scene = entity_scene(|builder| {
+            builder.add(
+                Aabb::new(
+                    Point::new(Val(-1.0), Val(-1.0), Val(-1.0)),
+                    Point::new(Val(1.0), Val(1.0), Val(1.0)),
+                ),
+                material,
+            );
+            builder.add(
+                Sphere::new(Point::new(Val(0.0), Val(0.0), Val(0.5)), Val(0.2)).unwrap(),
+                Emissive::new(Spectrum::broadcast(Val(4.0)), SpreadAngle::hemisphere()),
+            );
+        });
+        (camera, entity_scene, BvhVolumeSceneBuilder::new().build())
+    }
+
+    fn render_diffuse_box(config: CoreRendererConfiguration) -> Spectrum {
+        render_box(Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()), config)
+    }
+
+    fn render_box<M>(material: M, config: CoreRendererConfiguration) -> Spectrum
+    where
+        M: Into<DynMaterial>,
+    {
+        let (camera, entity_scene, volume_scene) = box_scene(material);
+        let renderer = CoreRenderer::new(camera, entity_scene, volume_scene, config).unwrap();
+        mean(&renderer.render())
+    }
+
+    fn textured_renderer<F>(camera: Camera, add: F) -> CoreRenderer
+    where
+        F: FnOnce(&mut BvhEntitySceneBuilder),
+    {
+        let config = CoreRendererConfiguration::default()
+            .with_integrator(Integrator::PathTracing)
+            .with_iterations(1)
+            .with_spp_per_iteration(4)
+            .with_seed(3);
+        scene_renderer(camera, config, add)
+    }
+
+    fn textured_quad_renderer(texture: DynAlbedoTexture) -> CoreRenderer {
+        let camera = Camera::new(
+            Point::new(Val(0.0), Val(0.0), Val(-30.0)),
+            Direction::z_direction(),
+            Resolution::new(16, (1, 1)).unwrap(),
+            Distance::new(Val(1.0)).unwrap(),
+            Distance::new(Val(1.5)).unwrap(),
+        );
+        let quad = MeshConstructor::new(
+            vec![
+                Point::new(Val(-10.0), Val(-10.0), Val(0.0)),
+                Point::new(Val(10.0), Val(-10.0), Val(0.0)),
+                Point::new(Val(10.0), Val(10.0), Val(0.0)),
+                Point::new(Val(-10.0), Val(10.0), Val(0.0)),
+            ],
+            vec![vec![0, 1, 2, 3]],
+        )
+        .unwrap()
+        .with_uvs(
+            vec![
+                UvCoordinate::new(Val(0.0), Val(0.0)).unwrap(),
+                UvCoordinate::new(Val(1.0), Val(0.0)).unwrap(),
+                UvCoordinate::new(Val(1.0), Val(1.0)).unwrap(),
+                UvCoordinate::new(Val(0.0), Val(1.0)).unwrap(),
+            ],
+            vec![vec![0, 1, 2, 3]],
+        )
+        .unwrap();
+
+        textured_renderer(camera, |builder| {
+            builder.add_constructor(quad, Diffuse::new(texture));
+            builder.add_light(DirectionalLight::new(
+                Direction::z_direction(),
+                Spectrum::broadcast(Val::PI),
+            ));
+        })
+    }
+
+    fn render_textured_quad(texture: DynAlbedoTexture) -> Vec<Val> {
+        reds(&textured_quad_renderer(texture).render())
+    }
+
+    fn filled_image(size: usize, value: impl Fn(usize, usize) -> Val) -> Arc<Image> {
+        let mut image = Image::new(Resolution::new(size, (1, 1)).unwrap());
+        for r in 0..size {
+            for c in 0..size {
+                image.set(r, c, Spectrum::broadcast(value(r, c)));
+            }
+        }
+        Arc::new(image)
+    }
+
+    fn max_difference(a: &[Val], b: &[Val]) -> Val {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| (*a - *b).abs())
+            .fold(Val(0.0), Val::max)
+    }
+
+    fn primary_uv_footprint(texture: DynAlbedoTexture) -> Option<Val> {
+        let renderer = textured_quad_renderer(texture);
+        let ray = Ray::new(
+            Point::new(Val(1.0), Val(2.0), Val(-30.0)),
+            Direction::z_direction(),
+        );
+        let (intersection, id) = (renderer.entity_scene)
+            .find_visible_intersection(&ray, DisRange::positive(), Visibility::PRIMARY)
+            .unwrap();
+        let entities = renderer.entity_scene.get_entities();
+        let material = entities.get_material(id.material_id()).unwrap();
+        (renderer.attach_footprint(&ray, intersection, id, &material)).uv_footprint()
+    }
+
+    #[test]
+    fn core_renderer_attach_footprint_succeeds_probing_only_for_prefiltered_textures() {
+        let image = filled_image(4, |_, _| Val(0.5));
+        let trilinear = ImageMap::new(image.clone());
+        let bilinear = ImageMap::new(image).with_filtering(FilterMode::Bilinear);
+
+        assert!(primary_uv_footprint(trilinear.into()).is_some_and(|f| f > Val(0.0)));
+        assert_eq!(primary_uv_footprint(bilinear.into()), None);
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_desaturating_coated_conductor() {
+        let gold = || Conductor::lookup(GlossyPredefinition::Gold, Val(0.3)).unwrap();
+        let config = CoreRendererConfiguration::default()
+            .with_integrator(Integrator::PathTracing)
+            .with_iterations(1)
+            .with_spp_per_iteration(16)
+            .with_seed(5);
+        let bare = render_box(gold(), config.clone());
+        let coated = render_box(Coated::new(gold(), Val(1.5), Val(0.1)).unwrap(), config);
+
+        let saturation = |s: Spectrum| s.blue() / s.red();
+        assert!(coated.blue() > Val(0.0) && coated.red().0.is_finite());
+        assert!(saturation(coated) > saturation(bare));
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_prefiltering_distant_image_map() {
+        let checker = filled_image(256, |r, c| Val::from((r + c) % 2));
+        let flat = render_textured_quad(ImageMap::new(filled_image(4, |_, _| Val(0.5))).into());
+
+        let nearest = ImageMap::new(checker.clone()).with_filtering(FilterMode::Nearest);
+        let nearest = render_textured_quad(nearest.into());
+        let trilinear = render_textured_quad(ImageMap::new(checker).into());
+
+        assert!(max_difference(&nearest, &flat) > Val(0.2));
+        assert!(max_difference(&trilinear, &flat) < Val(0.02));
+    }
+
+    fn render_floor(texture: DynAlbedoTexture) -> Vec<Val> {
+        let camera = Camera::new(
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            Direction::normalize(Vector::new(Val(0.0), Val(-0.18), Val(1.0))).unwrap(),
+            Resolution::new(16, (1, 1)).unwrap(),
+            Distance::new(Val(1.0)).unwrap(),
+            Distance::new(Val(1.5)).unwrap(),
+        );
+        let renderer = textured_renderer(camera, |builder| {
+            builder.add(
+                Plane::new(Point::default(), Normal::y_direction()),
+                Diffuse::new(texture),
+            );
+            builder.add_light(DirectionalLight::new(
+                -Direction::y_direction(),
+                Spectrum::broadcast(Val::PI),
+            ));
+        });
+        reds(&renderer.render())
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_prefiltering_receding_checkerboard() {
+        let checkerboard =
+            Checkerboard::new(Spectrum::broadcast(Val(1.0)), Spectrum::zero(), Val(0.5)).unwrap();
+        let checkerboard = render_floor(checkerboard.into());
+        let flat = render_floor(Spectrum::broadcast(Val(0.5)).into());
+
+        let (distant, near) = (16 * 3..16 * 9, 16 * 14..16 * 16);
+        assert!(max_difference(&checkerboard[distant.clone()], &flat[distant]) < Val(0.02));
+        assert!(max_difference(&checkerboard[near.clone()], &flat[near]) > Val(0.2));
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_with_uniform_environment() {
+        let mut environment = Image::new(Resolution::new(2, (2, 1)).unwrap());
+        for (row, column) in [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 0),
+            (1, 1),
+            (1, 2),
+            (1, 3),
+        ] {
+            environment.set(row, column, Spectrum::broadcast(Val(1.0)));
+        }
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(1)
+            .with_spp_per_iteration(1024)
+            .with_seed(0)
+            .with_photons_global(100)
+            .with_photons_caustic(100)
+            .with_environment(EnvironmentLight::new(environment));
+
+        let camera = camera(
+            Point::new(Val(0.0), Val(0.0), Val(-3.0)),
+            Direction::z_direction(),
+            4,
+        );
+        let renderer = scene_renderer(camera, config, |builder| {
+            builder.add(
+                Sphere::new(Point::new(Val(0.0), Val(0.0), Val(0.0)), Val(1.0)).unwrap(),
+                Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+            );
+        });
+        let image = renderer.render();
+        for row in 0..4 {
+            for column in 0..4 {
+                let pixel = image.get(row, column).unwrap();
+                assert!((pixel.red() - Val(0.5)).abs() < Val(0.05), "{pixel:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_with_directional_light() {
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(1)
+            .with_spp_per_iteration(64)
+            .with_photons_global(100)
+            .with_photons_caustic(100);
+        let camera = camera(
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            -Direction::y_direction(),
+            4,
+        );
+        let renderer = scene_renderer(camera, config, |builder| {
+            builder.add(
+                Plane::new(Point::default(), Normal::y_direction()),
+                Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+            );
+            builder.add_light(DirectionalLight::new(
+                -Direction::y_direction(),
+                Spectrum::broadcast(Val::PI),
+            ));
+        });
+
+        let mean = mean(&renderer.render());
+        assert!((mean.red() - Val(0.5)).abs() < Val(0.05));
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_without_acne_on_nearly_coincident_planes() {
+        let render = |ray_offset: Val| {
+            let center = Point::new(Val(3.1e9), Val(1.7e9), Val(7.3e9));
+            let vector = Vector::new(Val(0.3), Val(1.0), Val(0.2));
+            let (normal, direction) = (Normal::normalize(vector), Direction::normalize(vector));
+            let (normal, direction) = (normal.unwrap(), direction.unwrap());
+            let config = CoreRendererConfiguration::default()
+                .with_integrator(Integrator::PathTracing)
+                .with_iterations(1)
+                .with_spp_per_iteration(64)
+                .with_seed(0)
+                .with_ray_offset(ray_offset);
+            let camera = camera(center + direction.to_vector(), -direction, 8);
+            let renderer = scene_renderer(camera, config, |builder| {
+                for gap in [Val(0.0), Val(1e-3)] {
+                    builder.add(
+                        Plane::new(center - direction.to_vector() * gap, normal),
+                        Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+                    );
+                }
+                builder.add_light(DirectionalLight::new(
+                    -direction,
+                    Spectrum::broadcast(Val::PI),
+                ));
+            });
+            mean(&renderer.render()).red()
+        };
+        assert!(render(Val(0.0)) < Val(0.45));
+        assert!((render(RayIntersection::DEFAULT_RAY_OFFSET) - Val(0.5)).abs() < Val(0.05));
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_seeing_both_faces_of_two_sided_emissive() {
+        let render_from = |z: Val, two_sided: bool| {
+            let direction = if z < Val(0.0) {
+                Direction::z_direction()
+            } else {
+                -Direction::z_direction()
+            };
+            let config = CoreRendererConfiguration::default()
+                .with_iterations(1)
+                .with_spp_per_iteration(1)
+                .with_photons_global(10)
+                .with_photons_caustic(10);
+            let camera = camera(Point::new(Val(0.0), Val(0.0), z), direction, 2);
+            let renderer = scene_renderer(camera, config, |builder| {
+                builder.add(
+                    unit_square(Val(0.0)),
+                    Emissive::new(Spectrum::broadcast(Val(1.0)), SpreadAngle::hemisphere())
+                        .with_two_sided(two_sided),
+                );
+            });
+            renderer.render().get(0, 0).unwrap()
+        };
+
+        assert_eq!(render_from(Val(-2.0), true), Spectrum::broadcast(Val(1.0)));
+        assert_eq!(render_from(Val(2.0), true), Spectrum::broadcast(Val(1.0)));
+        let lit = render_from(Val(-2.0), false) != Spectrum::zero();
+        let unlit = render_from(Val(2.0), false) != Spectrum::zero();
+        assert!(lit != unlit);
+    }
+
+    #[test]
+    fn core_renderer_render_framebuffer_succeeds_keeping_emissive_radiance_unclamped() {
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(1)
+            .with_photons_global(100)
+            .with_photons_caustic(100);
+        let renderer = scene_renderer(
+            camera(Point::default(), Direction::z_direction(), 4),
+            config,
+            |builder| {
+                builder.add(
+                    Sphere::new(Point::default(), Val(5.0)).unwrap(),
+                    Emissive::new(Spectrum::broadcast(Val(8.0)), SpreadAngle::hemisphere())
+                        .with_two_sided(true),
+                );
+            },
+        );
+        let framebuffer = renderer.render_framebuffer();
+        assert_eq!(framebuffer.data().len(), 16);
+        for radiance in framebuffer.data() {
+            assert_eq!(*radiance, Spectrum::broadcast(Val(8.0)));
+        }
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_tinting_light_through_red_absorbing_medium() {
+        let volume_scene = medium_scene(
+            Point::new(Val(-2.0), Val(-2.0), Val(-0.5)),
+            Point::new(Val(2.0), Val(2.0), Val(0.5)),
+            Isotropic::from_coefficients(
+                Spectrum::broadcast(Val(0.01)),
+                Spectrum::new(Val(3.0), Val(0.0), Val(0.0)),
+            )
+            .unwrap(),
+        );
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(1)
+            .with_spp_per_iteration(4)
+            .with_photons_global(100)
+            .with_photons_caustic(100);
+        let camera = camera(
+            Point::new(Val(0.0), Val(0.0), Val(-2.0)),
+            Direction::z_direction(),
+            2,
+        );
+        let renderer = scene_renderer_in(camera, volume_scene, config, |builder| {
+            builder.add(
+                unit_square(Val(1.0)),
+                Emissive::new(Spectrum::broadcast(Val(1.0)), SpreadAngle::hemisphere())
+                    .with_two_sided(true),
+            );
+        });
+        let color = renderer.render().get(0, 0).unwrap();
+
+        assert!(color.red() < color.green() * Val(0.1));
+        assert!((color.green() - color.blue()).abs() < Val(0.05));
+        assert!(color.green() > Val(0.9));
+    }
+
+    fn fog_camera() -> Camera {
+        Camera::new(
+            Point::new(Val(0.0), Val(0.0), Val(-4.0)),
+            Direction::z_direction(),
+            Resolution::new(4, (1, 1)).unwrap(),
+            Distance::new(Val(0.2)).unwrap(),
+            Distance::new(Val(1.0)).unwrap(),
+        )
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_matching_isotropic_fog_with_symmetric_phase() {
+        let render = |medium: DynMedium| {
+            let volume_scene = medium_scene(
+                Point::new(Val(-5.0), Val(-5.0), Val(-2.0)),
+                Point::new(Val(5.0), Val(5.0), Val(2.0)),
+                medium,
+            );
+            let config = CoreRendererConfiguration::default()
+                .with_integrator(Integrator::PathTracing)
+                .with_iterations(1)
+                .with_spp_per_iteration(4096)
+                .with_max_depth(1)
+                .with_max_invisible_depth(1)
+                .with_seed(7);
+            let renderer = scene_renderer_in(fog_camera(), volume_scene, config, |builder| {
+                builder.add(
+                    Polygon::new([
+                        Point::new(Val(-0.2), Val(0.5), Val(-0.2)),
+                        Point::new(Val(0.2), Val(0.5), Val(-0.2)),
+                        Point::new(Val(0.2), Val(0.5), Val(0.2)),
+                        Point::new(Val(-0.2), Val(0.5), Val(0.2)),
+                    ])
+                    .unwrap(),
+                    Emissive::new(Spectrum::broadcast(Val(10.0)), SpreadAngle::hemisphere())
+                        .with_two_sided(true),
+                );
+            });
+            mean(&renderer.render()).red()
+        };
+        let coefficients = (Spectrum::broadcast(Val(0.2)), Spectrum::zero());
+        let isotropic = Isotropic::from_coefficients(coefficients.0, coefficients.1).unwrap();
+        let symmetric =
+            HenyeyGreenstein::from_coefficients(coefficients.0, coefficients.1, Val(0.0)).unwrap();
+
+        let (isotropic, symmetric) = (render(isotropic.into()), render(symmetric.into()));
+        assert!((symmetric - isotropic).abs() < isotropic * Val(0.05));
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_reducing_variance_of_spot_light_beam_in_fog() {
+        let render = |equi_angular_sampling: bool, seed: u64| {
+            let volume_scene = medium_scene(
+                Point::new(Val(-5.0), Val(-5.0), Val(-5.0)),
+                Point::new(Val(5.0), Val(5.0), Val(5.0)),
+                Isotropic::from_coefficients(Spectrum::broadcast(Val(0.1)), Spectrum::zero())
+                    .unwrap(),
+            );
+            let config = CoreRendererConfiguration::default()
+                .with_integrator(Integrator::PathTracing)
+                .with_iterations(1)
+                .with_spp_per_iteration(1)
+                .with_max_depth(1)
+                .with_max_invisible_depth(1)
+                .with_equi_angular_sampling(equi_angular_sampling)
+                .with_seed(seed);
+            let renderer = scene_renderer_in(fog_camera(), volume_scene, config, |builder| {
+                builder.add_light(
+                    SpotLight::new(
+                        Point::new(Val(0.0), Val(1.0), Val(0.0)),
+                        -Direction::y_direction(),
+                        SpreadAngle::new(Val(0.4)).unwrap(),
+                        SpreadAngle::new(Val(0.5)).unwrap(),
+                        Spectrum::broadcast(Val(10.0)),
+                    )
+                    .unwrap(),
+                );
+            });
+            renderer.render()
+        };
+        let variance = |equi_angular_sampling: bool| {
+            let images = (0..32)
+                .map(|seed| render(equi_angular_sampling, seed))
+                .collect::<Vec<_>>();
+            let mut total = Val(0.0);
+            for row in 0..4 {
+                for column in 0..4 {
+                    let values = (images.iter())
+                        .map(|image| image.get(row, column).unwrap().red())
+                        .collect::<Vec<_>>();
+                    let mean = values.iter().copied().sum::<Val>() / Val::from(values.len());
+                    let sum2 = values.iter().map(|v| (*v - mean).powi(2)).sum::<Val>();
+                    total += sum2 / Val::from(values.len());
+                }
+            }
+            total
+        };
+
+        let (equi_angular, exponential) = (variance(true), variance(false));
+        assert!(equi_angular > Val(0.0));
+        assert!(
+            equi_angular < exponential * Val(0.5),
+            "{equi_angular:?} vs {exponential:?}"
+        );
+    }
+
+    fn small_sphere_renderer(size: usize, config: CoreRendererConfiguration) -> CoreRenderer {
+        let camera = camera(
+            Point::new(Val(0.0), Val(0.0), Val(-3.0)),
+            Direction::z_direction(),
+            size,
+        );
+        scene_renderer(camera, config, |builder| {
+            builder.add(
+                Sphere::new(Point::new(Val(0.0), Val(0.0), Val(0.0)), Val(0.2)).unwrap(),
+                Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+            );
+        })
+    }
+
+    #[test]
+    fn core_renderer_render_with_aovs_succeeds_facing_sphere() {
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(1)
+            .with_spp_per_iteration(1)
+            .with_photons_global(10)
+            .with_photons_caustic(10);
+        let aovs = small_sphere_renderer(5, config).render_with_aovs();
+
+        let normal = aovs.normal().get(2, 2).unwrap().unwrap();
+        assert_eq!(normal, -Normal::z_direction());
+        let depth = aovs.depth().get(2, 2).unwrap().unwrap();
+        assert_eq!(depth, Distance::new(Val(2.3)).unwrap());
+        let albedo = aovs.albedo().get(2, 2).unwrap();
+        assert_eq!(*albedo, Albedo::broadcast(Val(0.5)).unwrap());
+        assert_eq!(aovs.normal().get(0, 0), Some(&None));
+    }
+
+    #[test]
+    fn aov_buffers_remodulate_succeeds_reconstructing_beauty() {
+        let config = CoreRendererConfiguration::default()
+            .with_integrator(Integrator::PathTracing)
+            .with_iterations(1)
+            .with_spp_per_iteration(4)
+            .with_seed(0);
+        let aovs = diffuse_box_renderer(config).render_with_aovs();
+
+        let irradiance = aovs.demodulate();
+        let remodulated = AovBuffers::remodulate(&irradiance, aovs.albedo());
+        let beauty = aovs.beauty();
+        for row in 0..8 {
+            for column in 0..8 {
+                let expected = beauty.get(row, column).unwrap();
+                let actual = remodulated.get(row, column).unwrap();
+                assert!(
+                    (expected - actual).norm() < Val(1e-6),
+                    "{expected:?} vs {actual:?}"
+                );
+            }
+        }
+
+        let wall = beauty.get(0, 0).unwrap();
+        assert!(wall.red() > Val(0.0));
+        assert_eq!(irradiance.get(0, 0).unwrap().red(), wall.red() / Val(0.5));
+    }
+
+    #[test]
+    fn core_renderer_render_with_passes_succeeds_summing_to_beauty() {
+        for integrator in [Integrator::PhotonMapping, Integrator::PathTracing] {
+            let config = CoreRendererConfiguration::default()
+                .with_integrator(integrator)
+                .with_iterations(2)
+                .with_spp_per_iteration(2)
+                .with_photons_global(1000)
+                .with_photons_caustic(1000)
+                .with_seed(7);
+            let passes = diffuse_box_renderer(config).render_with_passes();
+
+            let mut has_indirect = false;
+            for row in 0..8 {
+                for column in 0..8 {
+                    let get = |pass| passes.get(pass).get(row, column).unwrap();
+                    assert_eq!(get(LightPass::DirectSpecular), Spectrum::zero());
+                    assert_eq!(get(LightPass::IndirectSpecular), Spectrum::zero());
+                    has_indirect |= get(LightPass::IndirectDiffuse) != Spectrum::zero();
+
+                    let sum = LightPass::ALL.into_iter().map(get).sum::<Spectrum>();
+                    let beauty = passes.beauty().get(row, column).unwrap();
+                    assert!((sum - beauty).norm() < Val(1e-6));
+                    assert!((beauty - sum).norm() < Val(1e-6));
+                }
+            }
+            assert!(has_indirect);
+        }
+    }
+
+    #[test]
+    fn core_renderer_render_with_passes_succeeds_summing_to_beauty_under_wide_filter() {
+        for filter in [
+            PixelFilter::Tent,
+            PixelFilter::Gaussian,
+            PixelFilter::MitchellNetravali,
+        ] {
+            let config = CoreRendererConfiguration::default()
+                .with_integrator(Integrator::PathTracing)
+                .with_pixel_filter(filter)
+                .with_iterations(2)
+                .with_spp_per_iteration(2)
+                .with_photons_global(1000)
+                .with_photons_caustic(1000)
+                .with_seed(7);
+            let passes = diffuse_box_renderer(config).render_with_passes();
+
+            for row in 0..8 {
+                for column in 0..8 {
+                    let get = |pass| passes.get(pass).get(row, column).unwrap();
+                    let sum = LightPass::ALL.into_iter().map(get).sum::<Spectrum>();
+                    let beauty = passes.beauty().get(row, column).unwrap();
+                    assert!((sum - beauty).norm() < Val(1e-6), "{filter:?}");
+                }
+            }
+        }
+    }
+
+    fn overhead_renderer<F>(size: usize, width: Val, add: F) -> CoreRenderer
+    where
+        F: FnOnce(&mut BvhEntitySceneBuilder),
+    {
+        let camera = Camera::new(
+            Point::new(Val(0.0), Val(3.0), Val(0.0)),
+            -Direction::y_direction(),
+            Resolution::new(size, (1, 1)).unwrap(),
+            Distance::new(width).unwrap(),
+            Distance::new(Val(1.0)).unwrap(),
+        );
+        let config = CoreRendererConfiguration::default()
+            .with_integrator(Integrator::PathTracing)
+            .with_iterations(1)
+            .with_spp_per_iteration(4)
+            .with_seed(0);
+        scene_renderer(camera, config, add)
+    }
+
+    #[test]
+    fn core_renderer_render_with_passes_succeeds_skipping_entity_hidden_from_camera() {
+        let renderer = overhead_renderer(5, Val(1.0), |builder| {
+            builder.add(
+                Plane::new(Point::default(), Normal::y_direction()),
+                Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+            );
+            builder.add_with_visibility(
+                Sphere::new(Point::new(Val(0.0), Val(1.5), Val(0.0)), Val(0.5)).unwrap(),
+                Specular::new(Albedo::WHITE),
+                Visibility::ALL.without(Visibility::PRIMARY),
+            );
+            builder.add_light(PointLight::new(
+                Point::new(Val(0.0), Val(2.5), Val(1.5)),
+                Spectrum::broadcast(Val(4.0)),
+            ));
+        });
+        let passes = renderer.render_with_passes();
+
+        let get = |pass| passes.get(pass).get(2, 2).unwrap();
+        assert!(get(LightPass::DirectDiffuse).red() > Val(0.0));
+        assert_eq!(get(LightPass::DirectSpecular), Spectrum::zero());
+        assert_eq!(get(LightPass::IndirectSpecular), Spectrum::zero());
+    }
+
+    #[test]
+    fn core_renderer_render_with_passes_succeeds_classifying_normal_mapped_specular() {
+        let renderer = overhead_renderer(5, Val(1.0), |builder| {
+            let flat = Constant::new(Spectrum::new(Val(0.5), Val(0.5), Val(1.0)));
+            builder.add(
+                Plane::new(Point::default(), Normal::y_direction()),
+                NormalMapped::new(Specular::new(Albedo::WHITE), flat),
+            );
+            builder.add(
+                Plane::new(
+                    Point::new(Val(0.0), Val(5.0), Val(0.0)),
+                    -Normal::y_direction(),
+                ),
+                Emissive::new(Spectrum::broadcast(Val(1.0)), SpreadAngle::hemisphere())
+                    .with_two_sided(true),
+            );
+        });
+        let passes = renderer.render_with_passes();
+
+        let get = |pass| passes.get(pass).get(2, 2).unwrap();
+        let specular = get(LightPass::DirectSpecular) + get(LightPass::IndirectSpecular);
+        assert!(specular.red() > Val(0.5));
+        assert_eq!(get(LightPass::DirectDiffuse), Spectrum::zero());
+        assert_eq!(get(LightPass::IndirectDiffuse), Spectrum::zero());
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_reproducing_image_with_same_seed() {
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(2)
+            .with_spp_per_iteration(2)
+            .with_photons_global(1000)
+            .with_photons_caustic(1000)
+            .with_seed(42);
+        let render = || diffuse_box_renderer(config.clone()).render();
+        let (first, second) = (render(), render());
+        assert_identical(&first, &second);
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_reproducing_image_through_grid_medium_with_same_seed() {
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(2)
+            .with_spp_per_iteration(2)
+            .with_photons_global(1000)
+            .with_photons_caustic(1000)
+            .with_seed(42);
+        let render = || {
+            let (camera, entity_scene, _) = diffuse_box_scene();
+            let (min, max) = (
+                Point::new(Val(-0.5), Val(-0.5), Val(-0.2)),
+                Point::new(Val(0.5), Val(0.5), Val(0.2)),
+            );
+            let medium = GridMedium::new(
+                BoundingBox::new(min, max),
+                (2, 1, 1),
+                vec![Val(0.5), Val(4.0)],
+                Albedo::broadcast(Val(0.5)).unwrap(),
+            )
+            .unwrap();
+            let volume_scene = medium_scene(min, max, medium);
+            let renderer = CoreRenderer::new(camera, entity_scene, volume_scene, config.clone());
+            renderer.unwrap().render()
+        };
+        let (first, second) = (render(), render());
+        assert_identical(&first, &second);
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_matching_full_frame_within_crop_window() {
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(2)
+            .with_spp_per_iteration(2)
+            .with_photons_global(1000)
+            .with_photons_caustic(1000)
+            .with_seed(7);
+        let full = diffuse_box_renderer(config.clone()).render();
+        let crop = diffuse_box_renderer(config.with_crop_window(2, 1, 5, 6)).render();
+
+        for row in 0..8 {
+            for column in 0..8 {
+                let value = crop.get(row, column).unwrap();
+                if (1..6).contains(&row) && (2..5).contains(&column) {
+                    assert_eq!(value, full.get(row, column).unwrap());
+                } else {
+                    assert_eq!(value, Spectrum::zero());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn core_renderer_resume_from_succeeds_matching_uninterrupted_render() {
+        let config = CoreRendererConfiguration::default()
+            .with_spp_per_iteration(2)
+            .with_photons_global(1000)
+            .with_photons_caustic(1000)
+            .with_seed(7);
+        let full = diffuse_box_renderer(config.clone().with_iterations(8)).render();
+
+        let path = std::env::temp_dir().join(format!("fractured-ray-{}.ckpt", std::process::id()));
+        let first = diffuse_box_renderer(config.clone().with_iterations(4));
+        first.render();
+        first.save_state(&path).unwrap();
+
+        let (camera, entity_scene, volume_scene) = diffuse_box_scene();
+        let config = config.with_iterations(4).with_seed(0);
+        let resumed =
+            CoreRenderer::resume_from(&path, camera, entity_scene, volume_scene, config).unwrap();
+        let image = resumed.render();
+        std::fs::remove_file(&path).unwrap();
+
+        for row in 0..8 {
+            for column in 0..8 {
+                let (a, b) = (
+                    full.get(row, column).unwrap(),
+                    image.get(row, column).unwrap(),
+                );
+                assert_eq!(a.red().0.to_bits(), b.red().0.to_bits());
+                assert_eq!(a.green().0.to_bits(), b.green().0.to_bits());
+                assert_eq!(a.blue().0.to_bits(), b.blue().0.to_bits());
+            }
+        }
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_starting_afresh_after_previous_render() {
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(2)
+            .with_spp_per_iteration(2)
+            .with_photons_global(1000)
+            .with_photons_caustic(1000)
+            .with_seed(7);
+        let renderer = diffuse_box_renderer(config);
+        let first = renderer.render();
+        let second = renderer.render();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn core_renderer_save_state_fails_when_nothing_is_rendered() {
+        let renderer = diffuse_box_renderer(CoreRendererConfiguration::default());
+        assert!(matches!(
+            renderer.save_state(std::env::temp_dir().join("fractured-ray-unused.ckpt")),
+            Err(RenderCheckpointError::NothingRendered),
+        ));
+    }
+
+    #[test]
+    fn core_renderer_new_fails_when_crop_window_is_out_of_bound() {
+        let config = CoreRendererConfiguration::default().with_crop_window(0, 0, 9, 4);
+        let (camera, entity_scene, volume_scene) = diffuse_box_scene();
+        assert!(matches!(
+            CoreRenderer::new(camera, entity_scene, volume_scene, config),
+            Err(CoreRendererConfigurationError::CropWindowOutOfBound),
+        ));
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_matching_photon_mapping_with_path_tracing_on_diffuse_box() {
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(8)
+            .with_spp_per_iteration(16)
+            .with_photons_global(100000)
+            .with_photons_caustic(1000);
+
+        let photon_mapping = render_diffuse_box(config.clone());
+        let path_tracing = render_diffuse_box(config.with_integrator(Integrator::PathTracing));
+
+        let (pm, pt) = (photon_mapping.red(), path_tracing.red());
+        assert!(pm > Val(0.0));
+        assert!((pm - pt).abs() / pm < Val(0.15));
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_converging_with_nee_and_mis_toggles_on_diffuse_box() {
+        let config = CoreRendererConfiguration::default()
+            .with_integrator(Integrator::PathTracing)
+            .with_iterations(8)
+            .with_spp_per_iteration(16)
+            .with_seed(0);
+
+        let mis = render_diffuse_box(config.clone()).red();
+        let nee_only = render_diffuse_box(config.clone().with_multiple_importance_sampling(false));
+        let bsdf_only = render_diffuse_box(config.with_next_event_estimation(false));
+
+        assert!(mis > Val(0.0));
+        for other in [nee_only.red(), bsdf_only.red()] {
+            assert!((mis - other).abs() / mis < Val(0.1), "{mis:?} vs {other:?}");
+        }
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_missing_point_light_caustic_with_path_tracing() {
+        let render = |integrator: Integrator| {
+            let config = CoreRendererConfiguration::default()
+                .with_integrator(integrator)
+                .with_iterations(2)
+                .with_spp_per_iteration(64)
+                .with_max_depth(2)
+                .with_max_invisible_depth(1)
+                .with_photons_global(10000)
+                .with_photons_caustic(100000)
+                .with_seed(0);
+            let camera = camera(
+                Point::new(Val(0.0), Val(0.9), Val(0.0)),
+                -Direction::y_direction(),
+                4,
+            );
+            let renderer = scene_renderer(camera, config, |builder| {
+                builder.add(
+                    Plane::new(Point::default(), Normal::y_direction()),
+                    Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+                );
+                builder.add(
+                    Plane::new(
+                        Point::new(Val(0.0), Val(1.25), Val(0.0)),
+                        -Normal::y_direction(),
+                    ),
+                    Specular::new(Albedo::WHITE),
+                );
+                builder.add_light(PointLight::new(
+                    Point::new(Val(0.0), Val(1.0), Val(0.0)),
+                    Spectrum::broadcast(Val(1.0)),
+                ));
+            });
+            mean(&renderer.render()).red()
+        };
+
+        let photon_mapping = render(Integrator::PhotonMapping);
+        let path_tracing = render(Integrator::PathTracing);
+        assert!(photon_mapping > path_tracing * Val(1.2));
+    }
+
+    fn photon_floor_renderer<F>(config: CoreRendererConfiguration, add: F) -> CoreRenderer
+    where
+        F: FnOnce(&mut BvhEntitySceneBuilder),
+    {
+        let camera = camera(
+            Point::new(Val(0.0), Val(1.0), Val(-3.0)),
+            Direction::z_direction(),
+            1,
+        );
+        scene_renderer(camera, config, |builder| {
+            builder.add(
+                Plane::new(Point::default(), Normal::y_direction()),
+                Diffuse::new(Albedo::broadcast(Val(0.8)).unwrap()),
+            );
+            add(builder);
+        })
+    }
+
+    #[test]
+    fn core_renderer_bake_photon_map_succeeds_estimating_irradiance_near_emissive() {
+        let config = CoreRendererConfiguration::default().with_photons_global(20000);
+        let renderer = photon_floor_renderer(config, |builder| {
+            builder.add(
+                Aabb::new(
+                    Point::new(Val(1.5), Val(0.0), Val(-2.0)),
+                    Point::new(Val(1.7), Val(2.0), Val(2.0)),
+                ),
+                Diffuse::new(Albedo::broadcast(Val(0.8)).unwrap()),
+            );
+            builder.add(
+                Sphere::new(Point::new(Val(0.0), Val(0.5), Val(0.0)), Val(0.2)).unwrap(),
+                Emissive::new(Spectrum::broadcast(Val(4.0)), SpreadAngle::hemisphere()),
+            );
+        });
+        let photon_map = renderer.bake_photon_map(StoragePolicy::Global, 0);
+
+        let estimate = |x: Val| {
+            let position = Point::new(x, Val(0.0), Val(0.0));
+            photon_map.estimate_irradiance(position, Normal::y_direction(), 50, Val(0.5), 20000)
+        };
+        let (near, corner) = (estimate(Val(0.5)), estimate(Val(1.8)));
+        assert!(near.red() > Val(0.0));
+        assert!(
+            near.red() > corner.red() * Val(4.0),
+            "{near:?} vs {corner:?}"
+        );
+    }
+
+    #[test]
+    fn core_renderer_bake_photon_map_succeeds_counting_photons_per_map() {
+        let config = CoreRendererConfiguration::default().with_photons_global(4000);
+        let renderer = photon_floor_renderer(config, |builder| {
+            for (x, radiance) in [(Val(-1.0), Val(9.0)), (Val(1.0), Val(3.0))] {
+                builder.add(
+                    Sphere::new(Point::new(x, Val(0.5), Val(0.0)), Val(0.2)).unwrap(),
+                    Emissive::new(Spectrum::broadcast(radiance), SpreadAngle::hemisphere()),
+                );
+            }
+        });
+        for seed in 0..2 {
+            let counts = renderer
+                .bake_photon_map(StoragePolicy::Global, seed)
+                .photon_counts()
+                .to_vec();
+            assert_eq!(counts.iter().sum::<usize>(), 4000);
+            let ratio = Val::from(counts[0]) / Val::from(counts[1]);
+            assert!(Val(2.5) < ratio && ratio < Val(3.5), "ratio = {ratio:?}");
+        }
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_terminating_grazing_rays_between_scattering_planes() {
+        let camera = Camera::new(
+            Point::new(Val(0.0), Val(0.5), Val(0.0)),
+            Direction::normalize(Vector::new(Val(0.0), Val(-0.05), Val(1.0))).unwrap(),
+            Resolution::new(4, (1, 1)).unwrap(),
+            Distance::new(Val(0.01)).unwrap(),
+            Distance::new(Val(1.0)).unwrap(),
+        );
+
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(1)
+            .with_spp_per_iteration(4)
+            .with_max_depth(250)
+            .with_max_invisible_depth(2)
+            .with_photons_global(1000)
+            .with_photons_caustic(1000)
+            .with_seed(0);
+        let renderer = scene_renderer(camera, config, |builder| {
+            let scattering = Scattering::new(Albedo::WHITE, Val(10.0), Val(1.5)).unwrap();
+            builder.add(
+                Plane::new(Point::default(), Normal::y_direction()),
+                scattering.clone(),
+            );
+            builder.add(
+                Plane::new(
+                    Point::new(Val(0.0), Val(1.0), Val(0.0)),
+                    -Normal::y_direction(),
+                ),
+                scattering,
+            );
+            builder.add_light(PointLight::new(
+                Point::new(Val(0.0), Val(0.5), Val(1.0)),
+                Spectrum::broadcast(Val(1.0)),
+            ));
+        });
+        let pixels = reds(&renderer.render());
+        assert!(pixels.iter().all(|p| p.0.is_finite()));
+        assert!(pixels.iter().any(|&p| p > Val(0.0)));
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_separating_caustic_photons_from_global_photons() {
+        let render = |mode: RenderMode| {
+            let camera = Camera::new(
+                Point::new(Val(0.0), Val(1.0), Val(-3.0)),
+                Direction::normalize(Vector::new(Val(0.0), Val(-1.0), Val(3.0))).unwrap(),
+                Resolution::new(8, (1, 1)).unwrap(),
+                Distance::new(Val(1.0)).unwrap(),
+                Distance::new(Val(1.0)).unwrap(),
+            );
+
+            let config = CoreRendererConfiguration::default()
+                .with_render_mode(mode)
+                .with_iterations(2)
+                .with_spp_per_iteration(4)
+                .with_photons_global(20000)
+                .with_photons_caustic(100000)
+                .with_seed(0);
+            let renderer = scene_renderer(camera, config, |builder| {
+                builder.add(
+                    Plane::new(Point::default(), Normal::y_direction()),
+                    Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+                );
+                builder.add(
+                    Sphere::new(Point::new(Val(0.0), Val(0.9), Val(0.0)), Val(0.6)).unwrap(),
+                    Refractive::new(Albedo::WHITE, Val(1.5)).unwrap(),
+                );
+                builder.add_light(PointLight::new(
+                    Point::new(Val(0.0), Val(6.0), Val(0.0)),
+                    Spectrum::broadcast(Val(50.0)),
+                ));
+            });
+            renderer.render()
+        };
+
+        let caustic = render(RenderMode::CausticPhotons);
+        let global = render(RenderMode::GlobalPhotons);
+        let (peak, pos) = (0..8)
+            .flat_map(|row| (0..8).map(move |column| (row, column)))
+            .map(|(row, column)| (caustic.get(row, column).unwrap().red(), (row, column)))
+            .fold(
+                (Val(0.0), (0, 0)),
+                |acc, cur| if cur.0 > acc.0 { cur } else { acc },
+            );
+        let far = caustic.get(7, 0).unwrap().red();
+        assert!(peak > far * Val(5.0), "{peak:?} vs {far:?}");
+        let global_at_peak = global.get(pos.0, pos.1).unwrap().red();
+        assert!(
+            global_at_peak < peak * Val(0.25),
+            "{global_at_peak:?} vs {peak:?}"
+        );
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_hiding_shadow_of_entity_without_shadow_visibility() {
+        let render = |visibility: Visibility| {
+            let renderer = overhead_renderer(9, Val(2.0), |builder| {
+                builder.add(
+                    Plane::new(Point::default(), Normal::y_direction()),
+                    Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+                );
+                builder.add_with_visibility(
+                    Sphere::new(Point::new(Val(0.0), Val(1.0), Val(0.0)), Val(0.5)).unwrap(),
+                    Diffuse::new(Albedo::RED),
+                    visibility,
+                );
+                builder.add_light(PointLight::new(
+                    Point::new(Val(1.5), Val(2.0), Val(0.0)),
+                    Spectrum::broadcast(Val(4.0)),
+                ));
+            });
+            renderer.render()
+        };
+        let shadowed = render(Visibility::ALL);
+        let unshadowed = render(Visibility::ALL.without(Visibility::SHADOW));
+        let brightened = (0..9)
+            .flat_map(|row| (0..9).map(move |column| (row, column)))
+            .filter(|&(row, column)| {
+                let before = shadowed.get(row, column).unwrap().green();
+                let after = unshadowed.get(row, column).unwrap().green();
+                after > before * Val(2.0) + Val(0.01)
+            })
+            .count();
+        assert!(brightened > 0);
+
+        let center = unshadowed.get(4, 4).unwrap();
+        assert!(center.red() > Val(0.0));
+        assert_eq!(center.green(), Val(0.0));
+    }
+
+    #[test]
+    fn core_renderer_render_progressive_succeeds_invoking_callback_per_iteration() {
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(3)
+            .with_spp_per_iteration(1)
+            .with_photons_global(100)
+            .with_photons_caustic(100);
+        let renderer = diffuse_box_renderer(config);
+
+        let mut iterations = Vec::new();
+        renderer.render_progressive(|iteration, image| {
+            assert_eq!(image.resolution().height(), 8);
+            iterations.push(iteration);
+            RenderControl::Continue
+        });
+        assert_eq!(iterations, vec![1, 2, 3]);
+
+        let mut count = 0;
+        renderer.render_progressive(|_, _| {
+            count += 1;
+            RenderControl::Cancel
+        });
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_reproducing_image_with_different_threads() {
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(2)
+            .with_spp_per_iteration(2)
+            .with_photons_global(1000)
+            .with_photons_caustic(1000)
+            .with_seed(42);
+        let single = diffuse_box_renderer(config.clone().with_threads(1)).render();
+        let multiple = diffuse_box_renderer(config.with_threads(4)).render();
+
+        assert_identical(&single, &multiple);
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_reducing_max_pixel_with_indirect_clamp() {
+        let config = CoreRendererConfiguration::default()
+            .with_integrator(Integrator::PathTracing)
+            .with_iterations(1)
+            .with_spp_per_iteration(4)
+            .with_seed(3);
+        let max_pixel = |image: Image| {
+            let mut max = Val(0.0);
+            for row in 0..8 {
+                for column in 0..8 {
+                    max = max.max(image.get(row, column).unwrap().red());
+                }
+            }
+            max
+        };
+
+        let unclamped = max_pixel(diffuse_box_renderer(config.clone()).render());
+        let clamped = diffuse_box_renderer(config.with_indirect_clamp(Val(0.1))).render();
+        assert!(max_pixel(clamped) < unclamped);
+    }
+
+    #[test]
+    fn core_renderer_configuration_validate_fails_when_russian_roulette_exceeds_max_depth() {
+        let config = CoreRendererConfiguration::default()
+            .with_max_depth(4)
+            .with_russian_roulette(5);
+        assert!(matches!(
+            config.validate(),
+            Err(CoreRendererConfigurationError::ExceededRussianRouletteDepth),
+        ));
+    }
+
+    #[test]
+    fn core_renderer_configuration_validate_fails_when_sppm_alpha_is_invalid() {
+        let config = CoreRendererConfiguration::default().with_sppm_alpha(Val(0.0));
+        assert!(matches!(
+            config.validate(),
+            Err(CoreRendererConfigurationError::InvalidSppmAlpha),
+        ));
+    }
+
+    #[test]
+    fn observation_accumulate_succeeds_shrinking_radius_every_iteration() {
+        let estimation = FluxEstimation::new(Spectrum::broadcast(Val(1.0)), Val(100.0), Val(0.1));
+
+        let shrink = |alpha: Val| {
+            let mut observation = Observation::new(&estimation);
+            let mut radii = vec![observation.radius];
+            for _ in 0..8 {
+                observation.accumulate(&estimation, alpha);
+                radii.push(observation.radius);
+            }
+            assert!(radii.windows(2).all(|w| w[1] < w[0]));
+            *radii.last().unwrap()
+        };
+        assert!(shrink(Val(0.5)) < shrink(Val(0.75)));
+    }
+
+    #[test]
+    fn pixel_accumulate_succeeds_dropping_non_finite_flux() {
+        let estimation = FluxEstimation::new(Spectrum::broadcast(Val(1.0)), Val(100.0), Val(0.1));
+        let poisoned = [
+            FluxEstimation::new(Spectrum::broadcast(Val::INFINITY), Val(100.0), Val(0.1)),
+            FluxEstimation::new(Spectrum::broadcast(Val(1.0)), Val(f64::NAN), Val(0.1)),
+            FluxEstimation::new(Spectrum::broadcast(Val(1.0)), Val(100.0), Val(f64::NAN)),
+        ];
+
+        let mut pixel = Pixel::new();
+        pixel.accumulate(&Contribution::from_global(poisoned[0].clone()), Val(0.7));
+        assert!(pixel.global.is_none());
+
+        pixel.accumulate(&Contribution::from_global(estimation.clone()), Val(0.7));
+        let mut expected = Pixel::new();
+        expected.accumulate(&Contribution::from_global(estimation.clone()), Val(0.7));
+        for flux in poisoned {
+            pixel.accumulate(&Contribution::from_global(flux.clone()), Val(0.7));
+            pixel.accumulate(&Contribution::from_caustic(flux), Val(0.7));
+        }
+        assert_eq!(pixel, expected);
+
+        let (global, _) = pixel.photon_radiance(1000, 1000);
+        assert!(global.is_finite());
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_reducing_depth_of_field_error_with_sobol_sampler() {
+        let render = |config: CoreRendererConfiguration| {
+            let camera = Camera::new(
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Direction::z_direction(),
+                Resolution::new(8, (1, 1)).unwrap(),
+                Distance::new(Val(0.5)).unwrap(),
+                Distance::new(Val(0.5)).unwrap(),
+            )
+            .with_lens(Val(0.5), Distance::new(Val(8.0)).unwrap());
+
+            let renderer = scene_renderer(camera, config, |builder| {
+                builder.add(
+                    Polygon::new([
+                        Point::new(Val(-10.0), Val(-10.0), Val(2.0)),
+                        Point::new(Val(0.0), Val(-10.0), Val(2.0)),
+                        Point::new(Val(0.0), Val(10.0), Val(2.0)),
+                        Point::new(Val(-10.0), Val(10.0), Val(2.0)),
+                    ])
+                    .unwrap(),
+                    Emissive::new(Spectrum::broadcast(Val(0.5)), SpreadAngle::hemisphere())
+                        .with_two_sided(true),
+                );
+            });
+            reds(&renderer.render())
+        };
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(1)
+            .with_photons_global(100)
+            .with_photons_caustic(100);
+
+        let reference = render(config.clone().with_seed(100).with_spp_per_iteration(16384));
+        assert!(reference.iter().any(|&p| Val(0.05) < p && p < Val(0.45)));
+        let rmse = |sampler: SamplerKind| {
+            let mut sum = Val(0.0);
+            for seed in 0..8 {
+                let config = (config.clone().with_seed(seed))
+                    .with_spp_per_iteration(16)
+                    .with_sampler(sampler);
+                let pixels = render(config);
+                for (&p, &r) in pixels.iter().zip(&reference) {
+                    sum += (p - r) * (p - r);
+                }
+            }
+            (sum / Val::from(8 * reference.len())).sqrt()
+        };
+        let (sobol, random) = (rmse(SamplerKind::Sobol), rmse(SamplerKind::Random));
+        assert!(sobol < random * Val(0.7));
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_converging_with_russian_roulette() {
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(8)
+            .with_spp_per_iteration(16)
+            .with_photons_global(100000)
+            .with_photons_caustic(1000);
+
+        let fixed = render_diffuse_box(config.clone());
+        let roulette = render_diffuse_box(config.with_russian_roulette(1));
+
+        let (fixed, roulette) = (fixed.red(), roulette.red());
+        assert!(fixed > Val(0.0));
+        assert!((fixed - roulette).abs() / fixed < Val(0.15));
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_converging_with_russian_roulette_through_absorbing_medium() {
+        let render = |config: CoreRendererConfiguration| {
+            let (camera, entity_scene, _) = diffuse_box_scene();
+            let volume_scene = medium_scene(
+                Point::new(Val(-1.0), Val(-1.0), Val(-1.0)),
+                Point::new(Val(1.0), Val(1.0), Val(1.0)),
+                Isotropic::from_coefficients(
+                    Spectrum::broadcast(Val(0.01)),
+                    Spectrum::broadcast(Val(0.5)),
+                )
+                .unwrap(),
+            );
+            let renderer = CoreRenderer::new(camera, entity_scene, volume_scene, config).unwrap();
+            mean(&renderer.render()).red()
+        };
+        let config = CoreRendererConfiguration::default()
+            .with_iterations(8)
+            .with_spp_per_iteration(16)
+            .with_photons_global(100000)
+            .with_photons_caustic(1000);
+
+        let fixed = render(config.clone());
+        let roulette = render(config.with_russian_roulette(1));
+
+        assert!(fixed > Val(0.0));
+        assert!((fixed - roulette).abs() / fixed < Val(0.15));
+    }
+
+    #[test]
+    fn core_renderer_trace_occlusion_succeeds_darkening_contact_area() {
+        let config = CoreRendererConfiguration::default()
+            .with_integrator(Integrator::AmbientOcclusion)
+            .with_ao_samples(4096);
+        let camera = camera(
+            Point::new(Val(0.0), Val(4.0), Val(0.0)),
+            -Direction::y_direction(),
+            4,
+        );
+        let renderer = scene_renderer(camera, config, |builder| {
+            builder.add(
+                Plane::new(Point::default(), Normal::y_direction()),
+                Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+            );
+            builder.add(
+                Sphere::new(Point::new(Val(0.0), Val(1.01), Val(0.0)), Val(1.0)).unwrap(),
+                Emissive::new(Spectrum::broadcast(Val(4.0)), SpreadAngle::hemisphere()),
+            );
+        });
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut occlusion = |x: Val| {
+            let ray = Ray::new(Point::new(x, Val(1.0), Val(0.0)), -Direction::y_direction());
+            renderer.trace_occlusion(&mut rng, &ray).light().red()
+        };
+        let contact = occlusion(Val(1.1));
+        let open = occlusion(Val(20.0));
+        assert!(contact < Val(0.8), "{contact:?}");
+        assert!(open > Val(0.99), "{open:?}");
+    }
+
+    #[test]
+    fn core_renderer_configuration_validate_fails_when_ao_samples_is_invalid() {
+        let config = CoreRendererConfiguration::default().with_ao_samples(0);
+        assert!(matches!(
+            config.validate(),
+            Err(CoreRendererConfigurationError::InvalidAoSamples),
+        ));
+    }
+
+    #[test]
+    fn core_renderer_render_succeeds_in_normal_mode() {
+        let config = CoreRendererConfiguration::default()
+            .with_render_mode(RenderMode::Normal)
+            .with_iterations(1)
+            .with_spp_per_iteration(4);
+        let image = small_sphere_renderer(9, config).render();
+
+        let center = image.get(4, 4).unwrap();
+        assert!((center.red() - Val(0.5)).abs() < Val(0.05), "{center:?}");
+        assert!((center.green() - Val(0.5)).abs() < Val(0.05), "{center:?}");
+        assert!(center.blue() < Val(0.01), "{center:?}");
+        assert_eq!(image.get(0, 0).unwrap(), Spectrum::zero());
+    }
+}
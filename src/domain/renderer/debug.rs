@@ -0,0 +1,100 @@
+use crate::domain::camera::Camera;
+use crate::domain::color::core::Spectrum;
+use crate::domain::color::map::{Colormap, GradientColormap};
+use crate::domain::math::algebra::{Product, Vector};
+use crate::domain::math::geometry::{Normal, Point};
+use crate::domain::math::numeric::Val;
+use crate::domain::scene::entity::{EntityId, EntityScene};
+use crate::domain::shape::def::BoundingBox;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RenderMode {
+    #[default]
+    Beauty,
+    Normal,
+    Depth,
+    PrimitiveId,
+    TraversalCost,
+    GlobalPhotons,
+    CausticPhotons,
+}
+
+impl RenderMode {
+    pub fn is_shaded(&self) -> bool {
+        matches!(
+            self,
+            Self::Beauty | Self::GlobalPhotons | Self::CausticPhotons
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct DepthRange {
+    near: Val,
+    far: Val,
+}
+
+impl DepthRange {
+    pub(super) fn new(camera: &Camera, scene: &dyn EntityScene) -> Self {
+        let Some(bbox) = scene.bounding_box() else {
+            return Self {
+                near: Val(0.0),
+                far: Val(1.0),
+            };
+        };
+
+        let depths = Self::corners(&bbox)
+            .map(|corner| (corner - camera.position()).dot(camera.orientation().to_vector()));
+        let near = depths.iter().copied().fold(Val::INFINITY, Val::min);
+        let far = depths.iter().copied().fold(-Val::INFINITY, Val::max);
+        Self {
+            near: near.max(Val(0.0)),
+            far,
+        }
+    }
+
+    fn corners(bbox: &BoundingBox) -> [Point; 8] {
+        let (min, max) = (bbox.min(), bbox.max());
+        std::array::from_fn(|i| {
+            let pick = |bit: usize, axis: usize| {
+                if i & bit == 0 {
+                    min.axis(axis)
+                } else {
+                    max.axis(axis)
+                }
+            };
+            Point::new(pick(1, 0), pick(2, 1), pick(4, 2))
+        })
+    }
+
+    pub(super) fn normalize(&self, depth: Val) -> Val {
+        if self.far > self.near {
+            ((depth - self.near) / (self.far - self.near)).clamp(Val(0.0), Val(1.0))
+        } else {
+            Val(0.0)
+        }
+    }
+}
+
+pub(super) fn normal_color(normal: Normal) -> Spectrum {
+    let val = normal * Val(0.5) + Vector::broadcast(Val(0.5));
+    Spectrum::new(val.x(), val.y(), val.z())
+}
+
+pub(super) fn heatmap_color(cost: Val) -> Spectrum {
+    let colormap = GradientColormap::new(
+        Spectrum::new(Val(0.0), Val(0.0), Val(1.0)),
+        Spectrum::new(Val(1.0), Val(0.0), Val(0.0)),
+    );
+    colormap.lookup(cost)
+}
+
+pub(super) fn false_color(id: EntityId) -> Spectrum {
+    let shape_id = id.shape_id();
+    let mut x = ((shape_id.kind() as u64) << 32) | u64::from(shape_id.index());
+    x = (x ^ (x >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+    x = (x ^ (x >> 33)).wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    x ^= x >> 33;
+    let channel = |shift: u32| Val(((x >> shift) & 0xff) as f64 / 255.0);
+    Spectrum::new(channel(0), channel(8), channel(16))
+}
@@ -2,7 +2,7 @@ use std::ops::{Add, Mul};
 
 use crate::domain::color::core::Spectrum;
 use crate::domain::image::core::Image;
-use crate::domain::material::def::{FluxEstimation, RefDynMaterial};
+use crate::domain::material::def::{FluxEstimation, MaterialKind, RefDynMaterial};
 use crate::domain::math::numeric::{DisRange, Val};
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::RayIntersection;
@@ -76,6 +76,10 @@ impl Contribution {
 
         let light_sum = (estimations.iter()).map(|e| e.light()).sum::<Spectrum>();
         let light_avg = light_sum / Val::from(estimations.len());
+        let indirect_sum = (estimations.iter())
+            .map(|e| e.indirect_light())
+            .sum::<Spectrum>();
+        let indirect_avg = indirect_sum / Val::from(estimations.len());
 
         let iter_global = estimations.iter().flat_map(|e| e.global());
         let global_avg = FluxEstimation::average(iter_global);
@@ -83,9 +87,13 @@ impl Contribution {
         let iter_caustic = estimations.iter().flat_map(|e| e.caustic());
         let caustic_avg = FluxEstimation::average(iter_caustic);
 
-        Self::from_light(light_avg)
-            + Self::from_global(global_avg)
-            + Self::from_caustic(caustic_avg)
+        Self::All(Box::new(ContributionInner {
+            light: light_avg,
+            indirect: indirect_avg,
+            global: global_avg,
+            caustic: caustic_avg,
+            first_hit: None,
+        }))
     }
 
     pub fn light(&self) -> Spectrum {
@@ -96,6 +104,40 @@ impl Contribution {
         }
     }
 
+    pub fn indirect_light(&self) -> Spectrum {
+        match self {
+            Self::All(s) => s.indirect,
+            _ => Spectrum::zero(),
+        }
+    }
+
+    pub fn into_indirect(self) -> Self {
+        match self.into_all() {
+            Self::All(mut s) => {
+                s.indirect = s.light;
+                Self::All(s)
+            }
+            s => s,
+        }
+    }
+
+    pub fn with_first_hit(self, kind: MaterialKind) -> Self {
+        match self.into_all() {
+            Self::All(mut s) => {
+                s.first_hit = Some(kind);
+                Self::All(s)
+            }
+            s => s,
+        }
+    }
+
+    pub fn first_hit(&self) -> Option<MaterialKind> {
+        match self {
+            Self::All(s) => s.first_hit,
+            _ => None,
+        }
+    }
+
     pub fn global(&self) -> Option<&FluxEstimation> {
         match self {
             Self::Global(global) => Some(global),
@@ -130,6 +172,28 @@ impl Contribution {
         }
     }
 
+    pub fn scale_light(self, multiplier: Val) -> Self {
+        match self {
+            Self::Light(light) => Self::Light(light * multiplier),
+            Self::All(mut s) => {
+                s.light *= multiplier;
+                s.indirect *= multiplier;
+                Self::All(s)
+            }
+            s => s,
+        }
+    }
+
+    pub fn clamp_light(self, max: Val) -> Self {
+        let light = self.light();
+        let peak = light.red().max(light.green()).max(light.blue());
+        if peak > max {
+            self.scale_light(max / peak)
+        } else {
+            self
+        }
+    }
+
     pub fn clamp(mut self) -> Self {
         if let Self::All(s) = &mut self {
             let max_radius = s.global.radius();
@@ -159,8 +223,10 @@ impl Add for Contribution {
             Self::Caustic(caustic) => res.caustic += caustic,
             Self::All(rhs) => {
                 res.light += rhs.light;
+                res.indirect += rhs.indirect;
                 res.global += rhs.global;
                 res.caustic += rhs.caustic;
+                res.first_hit = res.first_hit.or(rhs.first_hit);
             }
         }
 
@@ -178,6 +244,7 @@ impl Mul<Val> for Contribution {
             Self::Caustic(caustic) => Self::Caustic(caustic * rhs),
             Self::All(mut s) => {
                 s.light *= rhs;
+                s.indirect *= rhs;
                 s.global *= rhs;
                 s.caustic *= rhs;
                 Self::All(s)
@@ -205,6 +272,7 @@ impl Mul<Spectrum> for Contribution {
             Self::Caustic(caustic) => Self::Caustic(caustic * rhs),
             Self::All(mut s) => {
                 s.light *= rhs;
+                s.indirect *= rhs;
                 s.global *= rhs;
                 s.caustic *= rhs;
                 Self::All(s)
@@ -225,8 +293,10 @@ impl Mul<Contribution> for Spectrum {
 #[derive(Debug, Clone, PartialEq)]
 pub struct ContributionInner {
     light: Spectrum,
+    indirect: Spectrum,
     global: FluxEstimation,
     caustic: FluxEstimation,
+    first_hit: Option<MaterialKind>,
 }
 
 impl ContributionInner {
@@ -234,8 +304,10 @@ impl ContributionInner {
     fn zero() -> Self {
         Self {
             light: Spectrum::zero(),
+            indirect: Spectrum::zero(),
             global: FluxEstimation::empty(),
             caustic: FluxEstimation::empty(),
+            first_hit: None,
         }
     }
 }
@@ -0,0 +1,216 @@
+use crate::domain::camera::Offset;
+use crate::domain::color::core::Spectrum;
+use crate::domain::image::core::Framebuffer;
+use crate::domain::math::numeric::Val;
+
+use super::passes::{LightPass, PassRadiance};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PixelFilter {
+    #[default]
+    Box,
+    Tent,
+    Gaussian,
+    MitchellNetravali,
+}
+
+impl PixelFilter {
+    const GAUSSIAN_ALPHA: Val = Val(2.0);
+    const MITCHELL_B: Val = Val(1.0 / 3.0);
+    const MITCHELL_C: Val = Val(1.0 / 3.0);
+
+    pub fn default_radius(&self) -> Val {
+        match self {
+            Self::Box => Val(0.5),
+            Self::Tent => Val(1.0),
+            Self::Gaussian => Val(1.5),
+            Self::MitchellNetravali => Val(2.0),
+        }
+    }
+
+    pub fn weight(&self, radius: Val, x: Val, y: Val) -> Val {
+        if x.abs() > radius || y.abs() > radius {
+            return Val(0.0);
+        }
+        match self {
+            Self::Box => Val(1.0),
+            Self::Tent => (Val(1.0) - x.abs() / radius) * (Val(1.0) - y.abs() / radius),
+            Self::Gaussian => Self::gaussian(radius, x) * Self::gaussian(radius, y),
+            Self::MitchellNetravali => Self::mitchell(radius, x) * Self::mitchell(radius, y),
+        }
+    }
+
+    fn gaussian(radius: Val, x: Val) -> Val {
+        let edge = (-Self::GAUSSIAN_ALPHA * radius * radius).exp();
+        ((-Self::GAUSSIAN_ALPHA * x * x).exp() - edge).max(Val(0.0))
+    }
+
+    fn mitchell(radius: Val, x: Val) -> Val {
+        let (b, c) = (Self::MITCHELL_B, Self::MITCHELL_C);
+        let t = (Val(2.0) * x / radius).abs();
+        let res = if t < Val(1.0) {
+            (Val(12.0) - Val(9.0) * b - Val(6.0) * c) * t.powi(3)
+                + (Val(-18.0) + Val(12.0) * b + Val(6.0) * c) * t.powi(2)
+                + (Val(6.0) - Val(2.0) * b)
+        } else if t < Val(2.0) {
+            (-b - Val(6.0) * c) * t.powi(3)
+                + (Val(6.0) * b + Val(30.0) * c) * t.powi(2)
+                + (Val(-12.0) * b - Val(48.0) * c) * t
+                + (Val(8.0) * b + Val(24.0) * c)
+        } else {
+            Val(0.0)
+        };
+        res / Val(6.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct FilmSample {
+    offset: Offset,
+    radiance: Spectrum,
+}
+
+impl FilmSample {
+    pub(super) fn new(offset: Offset, radiance: Spectrum) -> Self {
+        Self { offset, radiance }
+    }
+}
+
+pub(super) fn reconstruct(
+    filter: PixelFilter,
+    radius: Val,
+    samples: &Framebuffer<Vec<FilmSample>>,
+    pos: (usize, usize),
+) -> Spectrum {
+    let mut sum = Spectrum::zero();
+    let weight_sum = visit_weighted(filter, radius, samples, pos, |weight, sample, _| {
+        sum += sample.radiance * weight;
+    });
+    if weight_sum > Val(0.0) {
+        sum / weight_sum
+    } else {
+        Spectrum::zero()
+    }
+}
+
+pub(super) fn reconstruct_passes(
+    filter: PixelFilter,
+    radius: Val,
+    samples: &Framebuffer<Vec<FilmSample>>,
+    passes: &Framebuffer<Vec<PassRadiance>>,
+    pos: (usize, usize),
+) -> PassRadiance {
+    let mut sum = PassRadiance::default();
+    let weight_sum = visit_weighted(filter, radius, samples, pos, |weight, _, (r, c, i)| {
+        let radiance = passes.get(r, c).and_then(|passes| passes.get(i));
+        for pass in LightPass::ALL {
+            sum.add(
+                pass,
+                radiance.map_or(Spectrum::zero(), |p| p.get(pass)) * weight,
+            );
+        }
+    });
+    let mut res = PassRadiance::default();
+    if weight_sum > Val(0.0) {
+        for pass in LightPass::ALL {
+            res.add(pass, sum.get(pass) / weight_sum);
+        }
+    }
+    res
+}
+
+fn visit_weighted(
+    filter: PixelFilter,
+    radius: Val,
+    samples: &Framebuffer<Vec<FilmSample>>,
+    (row, column): (usize, usize),
+    mut visit: impl FnMut(Val, &FilmSample, (usize, usize, usize)),
+) -> Val {
+    let extent = isize::from((radius - Val(0.5)).max(Val(0.0)).ceil());
+
+    let mut weight_sum = Val(0.0);
+    for dr in -extent..=extent {
+        for dc in -extent..=extent {
+            let (Some(r), Some(c)) = (row.checked_add_signed(dr), column.checked_add_signed(dc))
+            else {
+                continue;
+            };
+            let Some(neighbor) = samples.get(r, c) else {
+                continue;
+            };
+            for (i, sample) in neighbor.iter().enumerate() {
+                let y = Val::from(dr) + sample.offset.row() - Val(0.5);
+                let x = Val::from(dc) + sample.offset.column() - Val(0.5);
+                let weight = filter.weight(radius, x, y);
+                if weight == Val(0.0) || !sample.radiance.is_finite() {
+                    continue;
+                }
+                visit(weight, sample, (r, c, i));
+                weight_sum += weight;
+            }
+        }
+    }
+    weight_sum
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::camera::Resolution;
+
+    use super::*;
+
+    fn single_bright_sample() -> Framebuffer<Vec<FilmSample>> {
+        let mut samples = Framebuffer::new(Resolution::new(3, (1, 1)).unwrap(), Vec::new());
+        for row in 0..3 {
+            for column in 0..3 {
+                let radiance = if (row, column) == (1, 1) {
+                    Spectrum::broadcast(Val(100.0))
+                } else {
+                    Spectrum::zero()
+                };
+                let offset = Offset::new(Val(0.6), Val(0.4)).unwrap();
+                samples.set(row, column, vec![FilmSample::new(offset, radiance)]);
+            }
+        }
+        samples
+    }
+
+    #[test]
+    fn reconstruct_succeeds_confining_sample_under_box_filter() {
+        let samples = single_bright_sample();
+        let filter = PixelFilter::Box;
+        let radius = filter.default_radius();
+        let center = reconstruct(filter, radius, &samples, (1, 1));
+        assert_eq!(center, Spectrum::broadcast(Val(100.0)));
+        for pos in [(0, 1), (1, 0), (1, 2), (2, 1), (0, 0)] {
+            assert_eq!(reconstruct(filter, radius, &samples, pos), Spectrum::zero());
+        }
+    }
+
+    #[test]
+    fn reconstruct_succeeds_spreading_sample_under_gaussian_filter() {
+        let samples = single_bright_sample();
+        let filter = PixelFilter::Gaussian;
+        let radius = filter.default_radius();
+        let center = reconstruct(filter, radius, &samples, (1, 1));
+        assert!(center.red() < Val(100.0));
+        for pos in [(0, 1), (1, 0), (1, 2), (2, 1), (0, 0)] {
+            let neighbor = reconstruct(filter, radius, &samples, pos);
+            assert!(neighbor.red() > Val(0.0), "{pos:?}");
+            assert!(neighbor.red() < center.red(), "{pos:?}");
+        }
+    }
+
+    #[test]
+    fn pixel_filter_weight_succeeds_vanishing_at_radius() {
+        for filter in [
+            PixelFilter::Tent,
+            PixelFilter::Gaussian,
+            PixelFilter::MitchellNetravali,
+        ] {
+            let radius = filter.default_radius();
+            assert_eq!(filter.weight(radius, radius, Val(0.0)), Val(0.0));
+            assert!(filter.weight(radius, Val(0.0), Val(0.0)) > Val(0.0));
+        }
+    }
+}
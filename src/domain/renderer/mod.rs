@@ -1,9 +1,22 @@
+mod aov;
+mod checkpoint;
 mod context;
 mod core;
+mod debug;
 mod def;
+mod filter;
+mod passes;
 mod state;
 
+pub use aov::AovBuffers;
+pub use checkpoint::{RenderCheckpoint, RenderCheckpointError};
 pub use context::{PhotonInfo, PmContext, RtContext};
-pub use core::{CoreRenderer, CoreRendererConfiguration, CoreRendererConfigurationError};
+pub use core::{
+    CoreRenderer, CoreRendererConfiguration, CoreRendererConfigurationError, CropWindow,
+    Integrator, RenderControl,
+};
+pub use debug::RenderMode;
 pub use def::{Contribution, Renderer};
+pub use filter::PixelFilter;
+pub use passes::{LightPass, RenderPasses};
 pub use state::{PmState, RtState, StoragePolicy};
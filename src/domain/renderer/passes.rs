@@ -0,0 +1,73 @@
+use crate::domain::color::core::Spectrum;
+use crate::domain::image::core::Image;
+use crate::domain::material::def::{MaterialCategory, MaterialKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LightPass {
+    Emission,
+    DirectDiffuse,
+    IndirectDiffuse,
+    DirectSpecular,
+    IndirectSpecular,
+    Caustic,
+}
+
+impl LightPass {
+    pub const ALL: [Self; 6] = [
+        Self::Emission,
+        Self::DirectDiffuse,
+        Self::IndirectDiffuse,
+        Self::DirectSpecular,
+        Self::IndirectSpecular,
+        Self::Caustic,
+    ];
+
+    pub(super) fn classify(kind: Option<MaterialKind>) -> (Self, Self) {
+        match kind.map(|kind| kind.category()) {
+            None | Some(MaterialCategory::Emissive) => (Self::Emission, Self::Emission),
+            Some(MaterialCategory::Microfacet | MaterialCategory::Specular) => {
+                (Self::DirectSpecular, Self::IndirectSpecular)
+            }
+            Some(
+                MaterialCategory::Diffuse | MaterialCategory::Scattering | MaterialCategory::Mixed,
+            ) => (Self::DirectDiffuse, Self::IndirectDiffuse),
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) struct PassRadiance([Spectrum; LightPass::ALL.len()]);
+
+impl PassRadiance {
+    pub(super) fn add(&mut self, pass: LightPass, radiance: Spectrum) {
+        self.0[pass.index()] += radiance;
+    }
+
+    pub(super) fn get(&self, pass: LightPass) -> Spectrum {
+        self.0[pass.index()]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderPasses {
+    beauty: Image,
+    passes: Vec<Image>,
+}
+
+impl RenderPasses {
+    pub(super) fn new(beauty: Image, passes: Vec<Image>) -> Self {
+        Self { beauty, passes }
+    }
+
+    pub fn beauty(&self) -> &Image {
+        &self.beauty
+    }
+
+    pub fn get(&self, pass: LightPass) -> &Image {
+        &self.passes[pass.index()]
+    }
+}
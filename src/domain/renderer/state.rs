@@ -1,5 +1,8 @@
 use getset::{CopyGetters, WithSetters};
 
+use crate::domain::color::core::{SpectralChannel, Spectrum};
+use crate::domain::math::numeric::Val;
+
 #[derive(Debug, Clone, PartialEq, Eq, CopyGetters, WithSetters)]
 pub struct RtState {
     #[getset(get_copy = "pub", set_with = "pub")]
@@ -10,6 +13,10 @@ pub struct RtState {
     skip_emissive: bool,
     #[getset(get_copy = "pub", set_with = "pub")]
     skip_medium_inscattering: bool,
+    #[getset(get_copy = "pub", set_with = "pub")]
+    throughput: Spectrum,
+    #[getset(get_copy = "pub", set_with = "pub")]
+    channel: Option<SpectralChannel>,
 }
 
 impl RtState {
@@ -20,24 +27,33 @@ impl RtState {
             invisible_depth: 0,
             skip_emissive: false,
             skip_medium_inscattering: false,
+            throughput: Spectrum::broadcast(Val(1.0)),
+            channel: None,
         }
     }
 
     pub fn increment_depth(self) -> Self {
         if self.visible {
             Self {
-                depth: self.depth + 1,
+                depth: self.depth.saturating_add(1),
                 ..self
             }
         } else {
             Self {
-                depth: self.depth + 1,
-                invisible_depth: self.invisible_depth + 1,
+                depth: self.depth.saturating_add(1),
+                invisible_depth: self.invisible_depth.saturating_add(1),
                 ..self
             }
         }
     }
 
+    pub fn scale_throughput(self, multiplier: Spectrum) -> Self {
+        Self {
+            throughput: self.throughput * multiplier,
+            ..self
+        }
+    }
+
     pub fn depth(&self) -> usize {
         self.depth as usize
     }
@@ -53,6 +69,10 @@ pub struct PmState {
     #[getset(set_with = "pub")]
     has_specular: bool,
     policy: StoragePolicy,
+    #[getset(set_with = "pub")]
+    channel: Option<SpectralChannel>,
+    #[getset(skip)]
+    depth: u8,
 }
 
 impl PmState {
@@ -60,8 +80,21 @@ impl PmState {
         Self {
             has_specular,
             policy,
+            channel: None,
+            depth: 0,
         }
     }
+
+    pub fn increment_depth(self) -> Self {
+        Self {
+            depth: self.depth.saturating_add(1),
+            ..self
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth as usize
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -40,7 +40,7 @@ impl DistanceSampling for EquiAngularDistanceSampler {
         let bottom_len = perp_dis * angle_sample.tan();
         let distance = Distance::new(vertex_proj + bottom_len).unwrap();
 
-        let scattering = RayScattering::new(distance, ray.at(distance));
+        let scattering = RayScattering::new(distance, ray.at(distance)).with_time(ray.time());
         let pdf = perp_dis / ((angle_end - angle_start) * (perp_dis.powi(2) + bottom_len.powi(2)));
         DistanceSample::new(scattering, pdf)
     }
@@ -33,7 +33,7 @@ impl DistanceSampling for ExponentialDistanceSampler {
         let distance = Distance::new(distance).unwrap();
         let position = ray.at(distance);
 
-        let scattering = RayScattering::new(distance, position);
+        let scattering = RayScattering::new(distance, position).with_time(ray.time());
         let pdf = self.pdf_distance(ray, segment, distance);
         DistanceSample::new(scattering, pdf)
     }
@@ -96,4 +96,15 @@ mod tests {
             Val(0.0)
         );
     }
+
+    #[test]
+    fn exponential_distance_sampler_sample_distance_succeeds_keeping_ray_time() {
+        let sampler = ExponentialDistanceSampler::new(Val(0.1));
+        let ray = Ray::new(Point::default(), Direction::x_direction()).with_time(Val(0.7));
+        let segment = RaySegment::new(Distance::zero(), Distance::new(Val(4.0)).unwrap());
+
+        let sample = sampler.sample_distance(&ray, &segment, &mut rand::rng());
+        let ray_next = sample.scattering().spawn(Direction::y_direction());
+        assert_eq!(ray_next.time(), Val(0.7));
+    }
 }
@@ -1,7 +1,9 @@
 mod angular;
 mod def;
 mod exponential;
+mod spectral;
 
 pub use angular::EquiAngularDistanceSampler;
 pub use def::{DistanceSample, DistanceSampling};
 pub use exponential::ExponentialDistanceSampler;
+pub use spectral::SpectralDistanceSampler;
@@ -0,0 +1,74 @@
+use rand::prelude::*;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::math::geometry::Distance;
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RaySegment;
+
+use super::{DistanceSample, DistanceSampling, ExponentialDistanceSampler};
+
+/// The reported pdf averages the pdfs of all channels.
+#[derive(Debug, Clone)]
+pub struct SpectralDistanceSampler {
+    channels: [ExponentialDistanceSampler; 3],
+}
+
+impl SpectralDistanceSampler {
+    pub fn new(sigma: Spectrum) -> Self {
+        Self {
+            channels: [sigma.red(), sigma.green(), sigma.blue()]
+                .map(ExponentialDistanceSampler::new),
+        }
+    }
+}
+
+impl DistanceSampling for SpectralDistanceSampler {
+    fn sample_distance(
+        &self,
+        ray: &Ray,
+        segment: &RaySegment,
+        rng: &mut dyn RngCore,
+    ) -> DistanceSample {
+        let hero = rng.random_range(0..self.channels.len());
+        let sample = self.channels[hero].sample_distance(ray, segment, rng);
+        let pdf = self.pdf_distance(ray, segment, sample.distance());
+        DistanceSample::new(sample.scattering().clone(), pdf)
+    }
+
+    fn pdf_distance(&self, ray: &Ray, segment: &RaySegment, distance: Distance) -> Val {
+        let sum = (self.channels.iter())
+            .map(|channel| channel.pdf_distance(ray, segment, distance))
+            .sum::<Val>();
+        sum / Val::from(self.channels.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::{Direction, Point};
+
+    use super::*;
+
+    #[test]
+    fn spectral_distance_sampler_pdf_distance_succeeds_integrating_to_one() {
+        let sampler = SpectralDistanceSampler::new(Spectrum::new(Val(4.0), Val(0.5), Val(0.1)));
+        let ray = Ray::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Direction::x_direction(),
+        );
+        let segment = RaySegment::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Distance::new(Val(3.0)).unwrap(),
+        );
+
+        let n = 10000;
+        let step = Val(3.0) / Val::from(n);
+        let integral = (0..n)
+            .map(|i| Val(1.0) + (Val::from(i) + Val(0.5)) * step)
+            .map(|d| sampler.pdf_distance(&ray, &segment, Distance::new(d).unwrap()))
+            .sum::<Val>()
+            * step;
+        assert!((integral - Val(1.0)).abs() < Val(1e-4));
+    }
+}
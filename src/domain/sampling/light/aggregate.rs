@@ -1,13 +1,13 @@
 use std::collections::HashMap;
 
 use rand::prelude::*;
-use rand_distr::Uniform;
+use rand_distr::weighted::WeightedIndex;
 
-use crate::domain::math::numeric::{DisRange, Val};
+use crate::domain::math::numeric::{DisRange, Val, WrappedVal};
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::{RayIntersection, RayScattering};
 use crate::domain::sampling::point::PointSample;
-use crate::domain::scene::bvh::Bvh;
+use crate::domain::scene::bvh::{Bvh, BvhConfig};
 use crate::domain::shape::def::{DynShape, RefDynShape, Shape};
 use crate::domain::shape::util::{ShapeContainer, ShapeId};
 
@@ -17,13 +17,30 @@ use super::{LightSample, LightSampling};
 pub struct AggregateLightSampler {
     lights: LightContainer,
     ids: Vec<ShapeId>,
+    deltas: Vec<Box<dyn LightSampling>>,
     bvh: Bvh<ShapeId>,
-    weight: Val,
+    probs: HashMap<ShapeId, Val>,
+    delta_probs: Vec<Val>,
+    index_sampler: WeightedIndex<WrappedVal>,
 }
 
 impl AggregateLightSampler {
-    pub fn new(samplers: Vec<Box<dyn LightSampling>>) -> Self {
-        let lights = LightContainer::new(samplers);
+    pub fn new(samplers: Vec<(Box<dyn LightSampling>, Val)>) -> Self {
+        let total = (samplers.iter())
+            .map(|&(_, weight)| weight.max(Val(0.0)))
+            .sum::<Val>()
+            .max(Val(Val::PRECISION));
+        let (samplers, deltas): (Vec<_>, Vec<_>) =
+            samplers.into_iter().partition(|(s, _)| s.id().is_some());
+
+        let probs = (samplers.iter())
+            .flat_map(|(s, weight)| s.id().map(|id| (id, (*weight).max(Val(0.0)) / total)))
+            .collect::<HashMap<_, _>>();
+        let (deltas, delta_probs): (Vec<_>, Vec<_>) = (deltas.into_iter())
+            .map(|(s, weight)| (s, weight.max(Val(0.0)) / total))
+            .unzip();
+
+        let lights = LightContainer::new(samplers.into_iter().map(|(s, _)| s).collect());
         let ids: Vec<_> = lights.lights.keys().cloned().collect();
         let bboxes = (lights.lights.iter())
             .filter_map(|(id, light)| {
@@ -35,15 +52,37 @@ impl AggregateLightSampler {
                 })
             })
             .collect();
-        let bvh = Bvh::new(bboxes, Vec::new());
-        let weight = Val::from(ids.len()).recip();
+        let bvh = Bvh::new(&BvhConfig::default(), bboxes, Vec::new());
+
+        let weights = (ids.iter().map(|id| probs[id]))
+            .chain(delta_probs.iter().copied())
+            .map(|prob| prob.0.max(Val::PRECISION));
+        let index_sampler = WeightedIndex::new(weights)
+            .expect("light weights should be positive and there should be at least one light");
         Self {
             lights,
             ids,
+            deltas,
             bvh,
-            weight,
+            probs,
+            delta_probs,
+            index_sampler,
+        }
+    }
+
+    fn select(&self, rng: &mut dyn RngCore) -> (&dyn LightSampling, Val) {
+        let which = self.index_sampler.sample(rng);
+        if let Some(id) = self.ids.get(which) {
+            (self.lights.lights.get(id).unwrap().as_ref(), self.probs[id])
+        } else {
+            let which = which - self.ids.len();
+            (self.deltas[which].as_ref(), self.delta_probs[which])
         }
     }
+
+    fn prob(&self, id: ShapeId) -> Val {
+        self.probs.get(&id).copied().unwrap_or(Val(0.0))
+    }
 }
 
 impl LightSampling for AggregateLightSampler {
@@ -60,18 +99,15 @@ impl LightSampling for AggregateLightSampler {
         intersection: &RayIntersection,
         rng: &mut dyn RngCore,
     ) -> Option<LightSample> {
-        let which = rng.sample(Uniform::new(0, self.ids.len()).unwrap());
-        let id = self.ids[which];
-        (self.lights.lights.get(&id))
-            .and_then(|light| light.sample_light_surface(intersection, rng))
-            .map(|sample| sample.scale_pdf(self.weight))
+        let (light, prob) = self.select(rng);
+        (light.sample_light_surface(intersection, rng)).map(|sample| sample.scale_pdf(prob))
     }
 
     fn pdf_light_surface(&self, intersection: &RayIntersection, ray_next: &Ray) -> Val {
         let res = (self.bvh).search(ray_next, DisRange::positive(), &self.lights);
         if let Some((_, id)) = res {
             let light = self.lights.lights.get(&id).unwrap();
-            light.pdf_light_surface(intersection, ray_next) * self.weight
+            light.pdf_light_surface(intersection, ray_next) * self.prob(id)
         } else {
             Val(0.0)
         }
@@ -84,29 +120,28 @@ impl LightSampling for AggregateLightSampler {
         rng: &mut dyn RngCore,
     ) -> Option<LightSample> {
         if let Some(sample) = preselected_light {
-            (self.lights.lights.get(&sample.shape_id()))
+            let id = sample.shape_id();
+            (self.lights.lights.get(&id))
                 .and_then(|light| light.sample_light_volume(scattering, preselected_light, rng))
-                .map(|sample| sample.scale_pdf(self.weight))
+                .map(|sample| sample.scale_pdf(self.prob(id)))
         } else {
-            let which = rng.sample(Uniform::new(0, self.ids.len()).unwrap());
-            let id = self.ids[which];
-            (self.lights.lights.get(&id))
-                .and_then(|light| light.sample_light_volume(scattering, None, rng))
-                .map(|sample| sample.scale_pdf(self.weight))
+            let (light, prob) = self.select(rng);
+            (light.sample_light_volume(scattering, None, rng)).map(|sample| sample.scale_pdf(prob))
         }
     }
 
     fn pdf_light_volume(&self, ray_next: &Ray, preselected_light: Option<&PointSample>) -> Val {
         if let Some(sample) = preselected_light {
-            (self.lights.lights.get(&sample.shape_id()))
+            let id = sample.shape_id();
+            (self.lights.lights.get(&id))
                 .map(|light| light.pdf_light_volume(ray_next, preselected_light))
-                .map(|pdf| pdf * self.weight)
+                .map(|pdf| pdf * self.prob(id))
                 .unwrap_or(Val(0.0))
         } else {
             let res = (self.bvh).search(ray_next, DisRange::positive(), &self.lights);
             if let Some((_, id)) = res {
                 let light = self.lights.lights.get(&id).unwrap();
-                light.pdf_light_volume(ray_next, None) * self.weight
+                light.pdf_light_volume(ray_next, None) * self.prob(id)
             } else {
                 Val(0.0)
             }
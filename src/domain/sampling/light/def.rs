@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use getset::{CopyGetters, Getters};
 use rand::prelude::*;
 
+use crate::domain::color::core::Spectrum;
 use crate::domain::math::algebra::Product;
 use crate::domain::math::geometry::{Direction, Distance, Normal, Point};
 use crate::domain::math::numeric::{DisRange, Val};
@@ -35,6 +36,10 @@ pub trait LightSampling: Debug + Send + Sync {
 
     fn pdf_light_volume(&self, ray_next: &Ray, preselected_light: Option<&PointSample>) -> Val;
 
+    fn position(&self) -> Option<Point> {
+        None
+    }
+
     fn has_nonzero_prob_given_preselected_light(
         &self,
         ray_next: &Ray,
@@ -57,7 +62,9 @@ pub struct LightSample {
     #[getset(get_copy = "pub")]
     distance: Distance,
     #[getset(get_copy = "pub")]
-    shape_id: ShapeId,
+    shape_id: Option<ShapeId>,
+    #[getset(get_copy = "pub")]
+    radiance: Option<Spectrum>,
 }
 
 impl LightSample {
@@ -66,10 +73,35 @@ impl LightSample {
             ray_next,
             pdf,
             distance,
-            shape_id,
+            shape_id: Some(shape_id),
+            radiance: None,
         }
     }
 
+    pub fn new_delta(ray_next: Ray, pdf: Val, distance: Distance, radiance: Spectrum) -> Self {
+        Self {
+            ray_next,
+            pdf,
+            distance,
+            shape_id: None,
+            radiance: Some(radiance),
+        }
+    }
+
+    pub fn new_infinite(ray_next: Ray, pdf: Val) -> Self {
+        Self {
+            ray_next,
+            pdf,
+            distance: Distance::infinity(),
+            shape_id: None,
+            radiance: None,
+        }
+    }
+
+    pub fn is_delta(&self) -> bool {
+        self.radiance.is_some()
+    }
+
     pub fn scale_pdf(self, multiplier: Val) -> Self {
         Self {
             pdf: self.pdf * multiplier,
@@ -136,14 +168,21 @@ impl<T> Transform<T> for LightSample
 where
     T: AtomTransformation,
     Ray: Transform<T>,
-    Distance: Transform<T>,
+    Point: Transform<T>,
 {
     fn transform_impl(self, transformation: &T) -> Self {
-        LightSample::new(
-            self.ray_next.transform(transformation),
-            self.pdf,
-            self.distance.transform(transformation),
-            self.shape_id,
-        )
+        let ray_next = self.ray_next.clone().transform(transformation);
+        // Transformed end points keep the distance exact under non-uniform scaling.
+        let distance = if self.distance == Distance::infinity() {
+            self.distance
+        } else {
+            let end = self.ray_next.at(self.distance).transform(transformation);
+            Distance::between(ray_next.start(), end)
+        };
+        Self {
+            ray_next,
+            distance,
+            ..self
+        }
     }
 }
@@ -0,0 +1,105 @@
+use rand::prelude::*;
+
+use crate::domain::light::primitive::DirectionalLight;
+use crate::domain::math::geometry::Distance;
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, RayScattering};
+use crate::domain::sampling::point::PointSample;
+use crate::domain::shape::def::RefDynShape;
+use crate::domain::shape::util::ShapeId;
+
+use super::{LightSample, LightSampling};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectionalLightSampler {
+    light: DirectionalLight,
+}
+
+impl DirectionalLightSampler {
+    pub fn new(light: DirectionalLight) -> Self {
+        Self { light }
+    }
+
+    fn sample_light_impl(&self, ray_next: Ray) -> LightSample {
+        LightSample::new_delta(
+            ray_next,
+            Val(1.0),
+            Distance::infinity(),
+            self.light.radiance(),
+        )
+    }
+}
+
+impl LightSampling for DirectionalLightSampler {
+    fn id(&self) -> Option<ShapeId> {
+        None
+    }
+
+    fn shape(&self) -> Option<RefDynShape> {
+        None
+    }
+
+    fn sample_light_surface(
+        &self,
+        intersection: &RayIntersection,
+        _rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        let ray_next = intersection.spawn(-self.light.direction());
+        Some(self.sample_light_impl(ray_next))
+    }
+
+    fn pdf_light_surface(&self, _intersection: &RayIntersection, _ray_next: &Ray) -> Val {
+        Val(0.0)
+    }
+
+    fn sample_light_volume(
+        &self,
+        scattering: &RayScattering,
+        preselected_light: Option<&PointSample>,
+        _rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        if preselected_light.is_some() {
+            return None;
+        }
+        let ray_next = scattering.spawn(-self.light.direction());
+        Some(self.sample_light_impl(ray_next))
+    }
+
+    fn pdf_light_volume(&self, _ray_next: &Ray, _preselected_light: Option<&PointSample>) -> Val {
+        Val(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::core::Spectrum;
+    use crate::domain::math::geometry::{Direction, Normal, Point};
+    use crate::domain::ray::event::SurfaceSide;
+
+    use super::*;
+
+    #[test]
+    fn directional_light_sampler_sample_light_surface_succeeds() {
+        let light = DirectionalLight::new(-Direction::y_direction(), Spectrum::broadcast(Val(2.0)));
+        let sampler = DirectionalLightSampler::new(light);
+
+        let intersection = RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::default(),
+            Normal::y_direction(),
+            SurfaceSide::Front,
+        );
+        let sample = (sampler)
+            .sample_light_surface(&intersection, &mut rand::rng())
+            .unwrap();
+        assert!(sample.is_delta());
+        assert_eq!(sample.ray_next().direction(), Direction::y_direction());
+        assert_eq!(sample.distance(), Distance::infinity());
+        assert_eq!(sample.radiance(), Some(Spectrum::broadcast(Val(2.0))));
+        assert_eq!(
+            sampler.pdf_light_surface(&intersection, sample.ray_next()),
+            Val(0.0),
+        );
+    }
+}
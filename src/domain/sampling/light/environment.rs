@@ -0,0 +1,362 @@
+use rand::prelude::*;
+use rand_distr::weighted::WeightedIndex;
+
+use crate::domain::light::primitive::{EnvironmentLight, Portal};
+use crate::domain::math::geometry::Direction;
+use crate::domain::math::numeric::{Val, WrappedVal};
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, RayScattering};
+use crate::domain::sampling::point::PointSample;
+use crate::domain::shape::def::{RefDynShape, ShapeKind};
+use crate::domain::shape::util::ShapeId;
+use crate::domain::texture::def::UvCoordinate;
+
+use super::{LightSample, LightSampling, RectangleLightSampler};
+
+/// With portals, directions are sampled uniformly over the solid angle of a random portal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvironmentLightSampler {
+    light: EnvironmentLight,
+    width: usize,
+    height: usize,
+    probs: Vec<Val>,
+    marginal: WeightedIndex<WrappedVal>,
+    conditionals: Vec<WeightedIndex<WrappedVal>>,
+    portals: Vec<RectangleLightSampler>,
+}
+
+impl EnvironmentLightSampler {
+    pub fn new(light: EnvironmentLight) -> Self {
+        let (width, height) = (light.resolution().width(), light.resolution().height());
+
+        let mut weights = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for column in 0..width {
+                let uv = Self::cell_to_uv(width, height, row, column, Val(0.5), Val(0.5));
+                let direction = EnvironmentLight::uv_to_direction(uv);
+                let sin_theta = (Val(1.0) - direction.y().powi(2)).max(Val(0.0)).sqrt();
+                weights.push(light.radiance(direction).luminance() * sin_theta);
+            }
+        }
+        let total = weights.iter().copied().sum::<Val>();
+        let probs = if total > Val(0.0) {
+            weights.into_iter().map(|w| w / total).collect::<Vec<_>>()
+        } else {
+            vec![Val::from(width * height).recip(); width * height]
+        };
+
+        let row_weights = (probs.chunks(width))
+            .map(|row| row.iter().copied().sum::<Val>())
+            .collect::<Vec<_>>();
+        let marginal = WeightedIndex::new(row_weights.iter().map(|w| w.0)).unwrap();
+        let conditionals = (probs.chunks(width))
+            .zip(row_weights.iter())
+            .map(|(row, &sum)| {
+                if sum > Val(0.0) {
+                    WeightedIndex::new(row.iter().map(|w| w.0)).unwrap()
+                } else {
+                    WeightedIndex::new(row.iter().map(|_| 1.0)).unwrap()
+                }
+            })
+            .collect();
+
+        Self {
+            light,
+            width,
+            height,
+            probs,
+            marginal,
+            conditionals,
+            portals: Vec::new(),
+        }
+    }
+
+    pub fn with_portals(self, portals: &[Portal]) -> Self {
+        let portals = (portals.iter().enumerate())
+            .filter_map(|(index, portal)| {
+                let id = ShapeId::new(ShapeKind::Polygon, index as u32);
+                RectangleLightSampler::new(id, portal.polygon().clone())
+            })
+            .collect();
+        Self { portals, ..self }
+    }
+
+    pub fn light(&self) -> &EnvironmentLight {
+        &self.light
+    }
+
+    fn cell_to_uv(
+        width: usize,
+        height: usize,
+        row: usize,
+        column: usize,
+        offset_u: Val,
+        offset_v: Val,
+    ) -> UvCoordinate {
+        let u = (Val::from(column) + offset_u) / Val::from(width);
+        let v = Val(1.0) - (Val::from(row) + offset_v) / Val::from(height);
+        UvCoordinate::clamp(u, v)
+    }
+
+    fn calc_pdf(&self, row: usize, column: usize, direction: Direction) -> Val {
+        let sin_theta = (Val(1.0) - direction.y().powi(2)).max(Val(0.0)).sqrt();
+        if sin_theta == Val(0.0) {
+            return Val(0.0);
+        }
+        let pdf_uv = self.probs[row * self.width + column] * Val::from(self.width * self.height);
+        pdf_uv / (Val(2.0) * Val::PI * Val::PI * sin_theta)
+    }
+
+    fn sample_light_impl(
+        &self,
+        ray_spawner: impl Fn(Direction) -> Ray,
+        rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        let row = self.marginal.sample(rng);
+        let column = self.conditionals[row].sample(rng);
+        let (offset_u, offset_v) = (Val(rng.random()), Val(rng.random()));
+        let uv = Self::cell_to_uv(self.width, self.height, row, column, offset_u, offset_v);
+
+        let direction = EnvironmentLight::uv_to_direction(uv);
+        let pdf = self.calc_pdf(row, column, direction);
+        if pdf == Val(0.0) {
+            return None;
+        }
+        Some(LightSample::new_infinite(ray_spawner(direction), pdf))
+    }
+
+    fn pdf_light_impl(&self, ray_next: &Ray) -> Val {
+        let direction = ray_next.direction();
+        let uv = EnvironmentLight::direction_to_uv(direction);
+        let column = usize::from((uv.u() * Val::from(self.width)).trunc());
+        let row = usize::from(((Val(1.0) - uv.v()) * Val::from(self.height)).trunc());
+        let (row, column) = (row.min(self.height - 1), column.min(self.width - 1));
+        self.calc_pdf(row, column, direction)
+    }
+
+    fn sample_portal_impl(
+        &self,
+        portal_sampler: impl Fn(&RectangleLightSampler, &mut dyn RngCore) -> Option<LightSample>,
+        portal_pdf: impl Fn(&RectangleLightSampler, &Ray) -> Val,
+        rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        let portal = self.portals.choose(rng)?;
+        let ray_next = portal_sampler(portal, rng)?.ray_next().clone();
+        let pdf = self.pdf_portal_impl(|portal| portal_pdf(portal, &ray_next));
+        if pdf == Val(0.0) {
+            return None;
+        }
+        Some(LightSample::new_infinite(ray_next, pdf))
+    }
+
+    fn pdf_portal_impl(&self, portal_pdf: impl Fn(&RectangleLightSampler) -> Val) -> Val {
+        let total = self.portals.iter().map(portal_pdf).sum::<Val>();
+        total / Val::from(self.portals.len())
+    }
+}
+
+impl LightSampling for EnvironmentLightSampler {
+    fn id(&self) -> Option<ShapeId> {
+        None
+    }
+
+    fn shape(&self) -> Option<RefDynShape> {
+        None
+    }
+
+    fn sample_light_surface(
+        &self,
+        intersection: &RayIntersection,
+        rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        if !self.portals.is_empty() {
+            return self.sample_portal_impl(
+                |portal, rng| portal.sample_light_surface(intersection, rng),
+                |portal, ray_next| portal.pdf_light_surface(intersection, ray_next),
+                rng,
+            );
+        }
+        self.sample_light_impl(|dir| intersection.spawn(dir), rng)
+    }
+
+    fn pdf_light_surface(&self, intersection: &RayIntersection, ray_next: &Ray) -> Val {
+        if !self.portals.is_empty() {
+            return self.pdf_portal_impl(|portal| portal.pdf_light_surface(intersection, ray_next));
+        }
+        self.pdf_light_impl(ray_next)
+    }
+
+    fn sample_light_volume(
+        &self,
+        scattering: &RayScattering,
+        preselected_light: Option<&PointSample>,
+        rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        if preselected_light.is_some() {
+            return None;
+        }
+        if !self.portals.is_empty() {
+            return self.sample_portal_impl(
+                |portal, rng| portal.sample_light_volume(scattering, None, rng),
+                |portal, ray_next| portal.pdf_light_volume(ray_next, None),
+                rng,
+            );
+        }
+        self.sample_light_impl(|dir| scattering.spawn(dir), rng)
+    }
+
+    fn pdf_light_volume(&self, ray_next: &Ray, preselected_light: Option<&PointSample>) -> Val {
+        if preselected_light.is_some() {
+            return Val(0.0);
+        }
+        if !self.portals.is_empty() {
+            return self.pdf_portal_impl(|portal| portal.pdf_light_volume(ray_next, None));
+        }
+        self.pdf_light_impl(ray_next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+
+    use crate::domain::camera::Resolution;
+    use crate::domain::color::core::{Albedo, Spectrum};
+    use crate::domain::image::core::Image;
+    use crate::domain::material::primitive::Diffuse;
+    use crate::domain::math::algebra::Product;
+    use crate::domain::math::geometry::{Distance, Normal, Point};
+    use crate::domain::ray::event::SurfaceSide;
+    use crate::domain::ray::util::VisibilityTester;
+    use crate::domain::scene::entity::{
+        BvhEntitySceneBuilder, EntitySceneBuilder, TypedEntitySceneBuilder,
+    };
+    use crate::domain::shape::primitive::{Plane, Polygon};
+
+    use super::*;
+
+    fn single_bright_pixel_map() -> (EnvironmentLight, Direction) {
+        let (height, width) = (16, 32);
+        let mut image = Image::new(Resolution::new(height, (2, 1)).unwrap());
+        for row in 0..height {
+            for column in 0..width {
+                image.set(row, column, Spectrum::broadcast(Val(0.01)));
+            }
+        }
+        let (row, column) = (5, 20);
+        image.set(row, column, Spectrum::broadcast(Val(1000.0)));
+
+        let u = Val::from(column) / Val::from(width - 1);
+        let v = Val(1.0) - Val::from(row) / Val::from(height - 1);
+        let direction = EnvironmentLight::uv_to_direction(UvCoordinate::new(u, v).unwrap());
+        (EnvironmentLight::new(image), direction)
+    }
+
+    fn intersection() -> RayIntersection {
+        RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::default(),
+            Normal::y_direction(),
+            SurfaceSide::Front,
+        )
+    }
+
+    #[test]
+    fn environment_light_sampler_sample_light_surface_succeeds_toward_bright_pixel() {
+        let (light, bright) = single_bright_pixel_map();
+        let sampler = EnvironmentLightSampler::new(light);
+        let intersection = intersection();
+
+        let cos_cone = Val(0.95);
+        let uniform_fraction = (Val(1.0) - cos_cone) / Val(2.0);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = 2000;
+        let hits = (0..n)
+            .filter_map(|_| sampler.sample_light_surface(&intersection, &mut rng))
+            .filter(|sample| sample.ray_next().direction().dot(bright) >= cos_cone)
+            .count();
+        let fraction = Val::from(hits) / Val::from(n);
+        assert!(fraction > Val(10.0) * uniform_fraction);
+    }
+
+    #[test]
+    fn environment_light_sampler_pdf_light_surface_succeeds_matching_sample() {
+        let (light, _) = single_bright_pixel_map();
+        let sampler = EnvironmentLightSampler::new(light);
+        let intersection = intersection();
+
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let sample = sampler
+                .sample_light_surface(&intersection, &mut rng)
+                .unwrap();
+            let pdf = sampler.pdf_light_surface(&intersection, sample.ray_next());
+            assert_eq!(pdf, sample.pdf());
+        }
+    }
+
+    fn rectangle(x: (Val, Val), z: (Val, Val)) -> Polygon {
+        Polygon::new([
+            Point::new(x.0, Val(1.0), z.0),
+            Point::new(x.1, Val(1.0), z.0),
+            Point::new(x.1, Val(1.0), z.1),
+            Point::new(x.0, Val(1.0), z.1),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn environment_light_sampler_sample_light_surface_succeeds_through_portal() {
+        let (far, window) = (Val(100.0), Val(0.25));
+        let mut builder = BvhEntitySceneBuilder::new();
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap());
+        builder.add(
+            Plane::new(
+                Point::new(Val(0.0), Val(-0.01), Val(0.0)),
+                Normal::y_direction(),
+            ),
+            diffuse.clone(),
+        );
+        for (x, z) in [
+            ((-far, -window), (-far, far)),
+            ((window, far), (-far, far)),
+            ((-window, window), (-far, -window)),
+            ((-window, window), (window, far)),
+        ] {
+            builder.add(rectangle(x, z), diffuse.clone());
+        }
+        let portal = Portal::new(rectangle((-window, window), (-window, window))).unwrap();
+        builder.add_portal(portal);
+        let scene = builder.build();
+
+        let mut image = Image::new(Resolution::new(8, (2, 1)).unwrap());
+        for row in 0..8 {
+            for column in 0..16 {
+                image.set(row, column, Spectrum::broadcast(Val(1.0)));
+            }
+        }
+        let light = EnvironmentLight::new(image);
+        let intersection = intersection();
+
+        let hit_rate = |sampler: &EnvironmentLightSampler| {
+            let mut rng = StdRng::seed_from_u64(2);
+            let n = 1000;
+            let hits = (0..n)
+                .filter_map(|_| sampler.sample_light_surface(&intersection, &mut rng))
+                .filter(|sample| {
+                    let pdf = sampler.pdf_light_surface(&intersection, sample.ray_next());
+                    assert_eq!(pdf, sample.pdf());
+                    VisibilityTester::new(scene.as_ref(), sample.ray_next())
+                        .test_unblocked(sample.distance())
+                })
+                .count();
+            Val::from(hits) / Val::from(n)
+        };
+
+        let without = hit_rate(&EnvironmentLightSampler::new(light.clone()));
+        let with = hit_rate(&EnvironmentLightSampler::new(light).with_portals(scene.get_portals()));
+        assert!(without < Val(0.1), "without = {without:?}");
+        assert!(with > Val(0.95), "with = {with:?}");
+    }
+}
@@ -30,6 +30,15 @@ impl InstanceLightSampler {
             inv_transformation,
         }
     }
+
+    fn calc_solid_angle_ratio(&self, ray_next: &Ray) -> Val {
+        (self.instance.transformation()).calc_solid_angle_ratio(ray_next.direction())
+    }
+
+    fn transform_sample(&self, sample: LightSample) -> LightSample {
+        let ratio = self.calc_solid_angle_ratio(sample.ray_next());
+        (sample.transform(self.instance.transformation())).scale_pdf(ratio.recip())
+    }
 }
 
 impl LightSampling for InstanceLightSampler {
@@ -50,7 +59,7 @@ impl LightSampling for InstanceLightSampler {
             let intersection = intersection.clone().transform(&self.inv_transformation);
             sampler
                 .sample_light_surface(&intersection, rng)
-                .map(|sample| sample.transform(self.instance.transformation()))
+                .map(|sample| self.transform_sample(sample))
         } else {
             None
         }
@@ -61,6 +70,7 @@ impl LightSampling for InstanceLightSampler {
             let intersection = intersection.clone().transform(&self.inv_transformation);
             let ray_next = ray_next.clone().transform(&self.inv_transformation);
             sampler.pdf_light_surface(&intersection, &ray_next)
+                / self.calc_solid_angle_ratio(&ray_next)
         } else {
             Val(0.0)
         }
@@ -78,7 +88,7 @@ impl LightSampling for InstanceLightSampler {
                 preselected_light.map(|l| l.clone().transform(&self.inv_transformation));
             sampler
                 .sample_light_volume(&scattering, preselected_light.as_ref(), rng)
-                .map(|sample| sample.transform(self.instance.transformation()))
+                .map(|sample| self.transform_sample(sample))
         } else {
             None
         }
@@ -90,6 +100,7 @@ impl LightSampling for InstanceLightSampler {
             let preselected_light =
                 preselected_light.map(|l| l.clone().transform(&self.inv_transformation));
             sampler.pdf_light_volume(&ray_next, preselected_light.as_ref())
+                / self.calc_solid_angle_ratio(&ray_next)
         } else {
             Val(0.0)
         }
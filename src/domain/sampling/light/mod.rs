@@ -1,11 +1,21 @@
 mod aggregate;
 mod def;
+mod directional;
+mod environment;
 mod instance;
+mod point;
+mod rectangle;
 mod sphere;
+mod spot;
 mod util;
 
 pub use aggregate::AggregateLightSampler;
 pub use def::{LightSample, LightSampling};
+pub use directional::DirectionalLightSampler;
+pub use environment::EnvironmentLightSampler;
 pub use instance::InstanceLightSampler;
+pub use point::PointLightSampler;
+pub use rectangle::RectangleLightSampler;
 pub use sphere::SphereLightSampler;
+pub use spot::SpotLightSampler;
 pub use util::{EmptyLightSampler, LightSamplerAdapter};
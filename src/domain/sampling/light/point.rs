@@ -0,0 +1,120 @@
+use rand::prelude::*;
+
+use crate::domain::light::primitive::PointLight;
+use crate::domain::math::geometry::{Direction, Distance, Point};
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, RayScattering};
+use crate::domain::sampling::point::PointSample;
+use crate::domain::shape::def::RefDynShape;
+use crate::domain::shape::util::ShapeId;
+
+use super::{LightSample, LightSampling};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointLightSampler {
+    light: PointLight,
+}
+
+impl PointLightSampler {
+    pub fn new(light: PointLight) -> Self {
+        Self { light }
+    }
+
+    fn sample_light_impl<F>(&self, position: Point, ray_spawner: F) -> Option<LightSample>
+    where
+        F: FnOnce(Direction) -> Ray,
+    {
+        let to_light = self.light.position() - position;
+        let direction = Direction::normalize(to_light).ok()?;
+        let distance = Distance::along(to_light);
+        let radiance = self.light.intensity() / to_light.norm_squared();
+        Some(LightSample::new_delta(
+            ray_spawner(direction),
+            Val(1.0),
+            distance,
+            radiance,
+        ))
+    }
+}
+
+impl LightSampling for PointLightSampler {
+    fn id(&self) -> Option<ShapeId> {
+        None
+    }
+
+    fn shape(&self) -> Option<RefDynShape> {
+        None
+    }
+
+    fn sample_light_surface(
+        &self,
+        intersection: &RayIntersection,
+        _rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        let ray_spawner = |dir| intersection.spawn(dir);
+        self.sample_light_impl(intersection.position(), ray_spawner)
+    }
+
+    fn pdf_light_surface(&self, _intersection: &RayIntersection, _ray_next: &Ray) -> Val {
+        Val(0.0)
+    }
+
+    fn sample_light_volume(
+        &self,
+        scattering: &RayScattering,
+        preselected_light: Option<&PointSample>,
+        _rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        if preselected_light.is_some() {
+            return None;
+        }
+        let ray_spawner = |dir| scattering.spawn(dir);
+        self.sample_light_impl(scattering.position(), ray_spawner)
+    }
+
+    fn pdf_light_volume(&self, _ray_next: &Ray, _preselected_light: Option<&PointSample>) -> Val {
+        Val(0.0)
+    }
+
+    fn position(&self) -> Option<Point> {
+        Some(self.light.position())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::core::Spectrum;
+    use crate::domain::math::geometry::Normal;
+    use crate::domain::ray::event::SurfaceSide;
+
+    use super::*;
+
+    #[test]
+    fn point_light_sampler_sample_light_surface_succeeds_with_inverse_square_falloff() {
+        let intersection = RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::default(),
+            Normal::y_direction(),
+            SurfaceSide::Front,
+        );
+        let sample_at = |height: Val| {
+            let light = PointLight::new(
+                Point::new(Val(0.0), height, Val(0.0)),
+                Spectrum::broadcast(Val(1.0)),
+            );
+            (PointLightSampler::new(light))
+                .sample_light_surface(&intersection, &mut rand::rng())
+                .unwrap()
+        };
+
+        let near = sample_at(Val(1.0));
+        assert!(near.is_delta());
+        assert_eq!(near.ray_next().direction(), Direction::y_direction());
+        assert_eq!(near.distance(), Distance::new(Val(1.0)).unwrap());
+
+        let far = sample_at(Val(2.0).sqrt());
+        assert_eq!(far.distance(), Distance::new(Val(2.0).sqrt()).unwrap());
+        assert_eq!(far.radiance().unwrap() * Val(2.0), near.radiance().unwrap(),);
+    }
+}
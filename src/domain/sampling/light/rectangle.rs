@@ -0,0 +1,302 @@
+use rand::prelude::*;
+
+use crate::domain::math::algebra::{Product, Vector};
+use crate::domain::math::geometry::{Direction, Distance, Point};
+use crate::domain::math::numeric::{DisRange, Val};
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, RayScattering};
+use crate::domain::sampling::point::{PointSample, PolygonPointSampler};
+use crate::domain::shape::def::{RefDynShape, Shape};
+use crate::domain::shape::primitive::Polygon;
+use crate::domain::shape::util::ShapeId;
+
+use super::{LightSample, LightSamplerAdapter, LightSampling};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RectangleLightSampler {
+    id: ShapeId,
+    polygon: Polygon,
+    corner: Point,
+    axes: [Vector; 3],
+    width: Val,
+    height: Val,
+    area_sampler: LightSamplerAdapter<PolygonPointSampler>,
+}
+
+impl RectangleLightSampler {
+    const MIN_SOLID_ANGLE: Val = Val(1e-6);
+
+    pub fn new(id: ShapeId, polygon: Polygon) -> Option<Self> {
+        let (corner, side1, side2) = polygon.to_rectangle()?;
+        let (width, height) = (side1.norm(), side2.norm());
+        let (axis_x, axis_y) = (side1 / width, side2 / height);
+        let axes = [axis_x, axis_y, axis_x.cross(axis_y)];
+        let area_sampler = LightSamplerAdapter::new(PolygonPointSampler::new(id, polygon.clone()));
+        Some(Self {
+            id,
+            polygon,
+            corner,
+            axes,
+            width,
+            height,
+            area_sampler,
+        })
+    }
+
+    fn calc_spherical_rectangle(&self, position: Point) -> Option<SphericalRectangle> {
+        let [axis_x, axis_y, mut axis_z] = self.axes;
+        let to_corner = self.corner - position;
+        let (x0, y0, mut z0) = (
+            to_corner.dot(axis_x),
+            to_corner.dot(axis_y),
+            to_corner.dot(axis_z),
+        );
+        if z0 > Val(0.0) {
+            z0 = -z0;
+            axis_z = -axis_z;
+        }
+        if z0 == Val(0.0) {
+            return None;
+        }
+        let (x1, y1) = (x0 + self.width, y0 + self.height);
+
+        let normalize = |v: Vector| v / v.norm();
+        let n0 = normalize(Vector::new(Val(0.0), z0, -y0));
+        let n1 = normalize(Vector::new(-z0, Val(0.0), x1));
+        let n2 = normalize(Vector::new(Val(0.0), -z0, y1));
+        let n3 = normalize(Vector::new(z0, Val(0.0), -x0));
+        let angle = |a: Vector, b: Vector| (-a.dot(b)).clamp(Val(-1.0), Val(1.0)).acos();
+        let (g0, g1, g2, g3) = (angle(n0, n1), angle(n1, n2), angle(n2, n3), angle(n3, n0));
+
+        let k = Val(2.0) * Val::PI - g2 - g3;
+        let solid_angle = g0 + g1 - k;
+        if solid_angle <= Self::MIN_SOLID_ANGLE {
+            return None;
+        }
+        Some(SphericalRectangle {
+            axes: [axis_x, axis_y, axis_z],
+            x0,
+            x1,
+            y0,
+            y1,
+            z0,
+            b0: n0.z(),
+            b1: n2.z(),
+            k,
+            solid_angle,
+        })
+    }
+
+    fn sample_light_impl(
+        &self,
+        rect: &SphericalRectangle,
+        position: Point,
+        ray_spawner: impl Fn(Direction) -> Ray,
+        rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        let (u, v) = (Val(rng.random()), Val(rng.random()));
+
+        let au = u * rect.solid_angle + rect.k;
+        let fu = (au.cos() * rect.b0 - rect.b1) / au.sin();
+        let cu = fu.signum() / (fu.powi(2) + rect.b0.powi(2)).sqrt();
+        let cu = cu.clamp(Val(-1.0), Val(1.0));
+        let xu = -(cu * rect.z0) / (Val(1.0) - cu.powi(2)).sqrt();
+        let xu = xu.clamp(rect.x0, rect.x1);
+
+        let d2 = xu.powi(2) + rect.z0.powi(2);
+        let h0 = rect.y0 / (d2 + rect.y0.powi(2)).sqrt();
+        let h1 = rect.y1 / (d2 + rect.y1.powi(2)).sqrt();
+        let hv = Val::lerp(h0, h1, v);
+        let yv = if hv.powi(2) < Val(1.0) - Self::MIN_SOLID_ANGLE {
+            (hv * d2.sqrt() / (Val(1.0) - hv.powi(2)).sqrt()).clamp(rect.y0, rect.y1)
+        } else {
+            rect.y1
+        };
+
+        let [axis_x, axis_y, axis_z] = rect.axes;
+        let to_light = xu * axis_x + yv * axis_y + rect.z0 * axis_z;
+        let Ok(direction) = Direction::normalize(to_light) else {
+            return None;
+        };
+        let ray_next = ray_spawner(direction);
+
+        let pdf = rect.solid_angle.recip();
+        let distance = Distance::between(position + to_light, position);
+        Some(LightSample::new(ray_next, pdf, distance, self.id))
+    }
+
+    fn pdf_light_impl(&self, rect: &SphericalRectangle, ray_next: &Ray) -> Val {
+        if self.polygon.hit(ray_next, DisRange::positive()).is_some() {
+            rect.solid_angle.recip()
+        } else {
+            Val(0.0)
+        }
+    }
+}
+
+impl LightSampling for RectangleLightSampler {
+    fn id(&self) -> Option<ShapeId> {
+        Some(self.id)
+    }
+
+    fn shape(&self) -> Option<RefDynShape> {
+        Some((&self.polygon).into())
+    }
+
+    fn sample_light_surface(
+        &self,
+        intersection: &RayIntersection,
+        rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        let position = intersection.position();
+        match self.calc_spherical_rectangle(position) {
+            Some(rect) => {
+                let ray_spawner = |dir| intersection.spawn(dir);
+                self.sample_light_impl(&rect, position, ray_spawner, rng)
+            }
+            None => self.area_sampler.sample_light_surface(intersection, rng),
+        }
+    }
+
+    fn pdf_light_surface(&self, intersection: &RayIntersection, ray_next: &Ray) -> Val {
+        match self.calc_spherical_rectangle(intersection.position()) {
+            Some(rect) => self.pdf_light_impl(&rect, ray_next),
+            None => self.area_sampler.pdf_light_surface(intersection, ray_next),
+        }
+    }
+
+    fn sample_light_volume(
+        &self,
+        scattering: &RayScattering,
+        preselected_light: Option<&PointSample>,
+        rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        if preselected_light.is_some() {
+            return (self.area_sampler).sample_light_volume(scattering, preselected_light, rng);
+        }
+        let position = scattering.position();
+        match self.calc_spherical_rectangle(position) {
+            Some(rect) => {
+                let ray_spawner = |dir| scattering.spawn(dir);
+                self.sample_light_impl(&rect, position, ray_spawner, rng)
+            }
+            None => self.area_sampler.sample_light_volume(scattering, None, rng),
+        }
+    }
+
+    fn pdf_light_volume(&self, ray_next: &Ray, preselected_light: Option<&PointSample>) -> Val {
+        if preselected_light.is_some() {
+            return (self.area_sampler).pdf_light_volume(ray_next, preselected_light);
+        }
+        match self.calc_spherical_rectangle(ray_next.start()) {
+            Some(rect) => self.pdf_light_impl(&rect, ray_next),
+            None => self.area_sampler.pdf_light_volume(ray_next, None),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SphericalRectangle {
+    axes: [Vector; 3],
+    x0: Val,
+    x1: Val,
+    y0: Val,
+    y1: Val,
+    z0: Val,
+    b0: Val,
+    b1: Val,
+    k: Val,
+    solid_angle: Val,
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+
+    use crate::domain::math::geometry::Normal;
+    use crate::domain::ray::event::SurfaceSide;
+    use crate::domain::shape::def::ShapeKind;
+
+    use super::*;
+
+    fn overhead_light() -> Polygon {
+        Polygon::new([
+            Point::new(Val(-2.0), Val(-2.0), Val(0.5)),
+            Point::new(Val(2.0), Val(-2.0), Val(0.5)),
+            Point::new(Val(2.0), Val(2.0), Val(0.5)),
+            Point::new(Val(-2.0), Val(2.0), Val(0.5)),
+        ])
+        .unwrap()
+    }
+
+    fn receiver() -> RayIntersection {
+        RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Normal::z_direction(),
+            SurfaceSide::Front,
+        )
+    }
+
+    fn estimate_irradiance(sampler: &dyn LightSampling, rng: &mut dyn RngCore) -> (Val, Val) {
+        let intersection = receiver();
+        let n = 20000;
+        let estimates = (0..n)
+            .map(|_| {
+                let sample = sampler.sample_light_surface(&intersection, rng).unwrap();
+                let cos = sample.ray_next().direction().dot(intersection.normal());
+                cos / sample.pdf()
+            })
+            .collect::<Vec<_>>();
+        let mean = estimates.iter().copied().sum::<Val>() / Val::from(n);
+        let variance = (estimates.iter()).map(|&e| (e - mean).powi(2)).sum::<Val>() / Val::from(n);
+        (mean, variance)
+    }
+
+    #[test]
+    fn rectangle_light_sampler_new_fails_when_polygon_is_not_rectangle() {
+        let polygon = Polygon::new([
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Point::new(Val(2.0), Val(0.0), Val(0.0)),
+            Point::new(Val(1.0), Val(1.0), Val(0.0)),
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+        ])
+        .unwrap();
+        let id = ShapeId::new(ShapeKind::Polygon, 0);
+        assert!(RectangleLightSampler::new(id, polygon).is_none());
+    }
+
+    #[test]
+    fn rectangle_light_sampler_sample_light_surface_succeeds_reducing_variance() {
+        let id = ShapeId::new(ShapeKind::Polygon, 0);
+        let polygon = overhead_light();
+        let rectangle = RectangleLightSampler::new(id, polygon.clone()).unwrap();
+        let area = LightSamplerAdapter::new(PolygonPointSampler::new(id, polygon));
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let (rectangle_mean, rectangle_variance) = estimate_irradiance(&rectangle, &mut rng);
+        let (area_mean, area_variance) = estimate_irradiance(&area, &mut rng);
+
+        assert!((rectangle_mean - area_mean).abs() < Val(0.05) * area_mean);
+        assert!(rectangle_variance < Val(0.5) * area_variance);
+    }
+
+    #[test]
+    fn rectangle_light_sampler_pdf_light_surface_succeeds_matching_sample() {
+        let id = ShapeId::new(ShapeKind::Polygon, 0);
+        let sampler = RectangleLightSampler::new(id, overhead_light()).unwrap();
+        let intersection = receiver();
+
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let sample = sampler
+                .sample_light_surface(&intersection, &mut rng)
+                .unwrap();
+            let pdf = sampler.pdf_light_surface(&intersection, sample.ray_next());
+            assert_eq!(pdf, sample.pdf());
+        }
+
+        let away = intersection.spawn(-Direction::z_direction());
+        assert_eq!(sampler.pdf_light_surface(&intersection, &away), Val(0.0));
+    }
+}
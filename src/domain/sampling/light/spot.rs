@@ -0,0 +1,123 @@
+use rand::prelude::*;
+
+use crate::domain::light::primitive::SpotLight;
+use crate::domain::math::geometry::{Direction, Distance, Point};
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, RayScattering};
+use crate::domain::sampling::point::PointSample;
+use crate::domain::shape::def::RefDynShape;
+use crate::domain::shape::util::ShapeId;
+
+use super::{LightSample, LightSampling};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpotLightSampler {
+    light: SpotLight,
+}
+
+impl SpotLightSampler {
+    pub fn new(light: SpotLight) -> Self {
+        Self { light }
+    }
+
+    fn sample_light_impl<F>(&self, position: Point, ray_spawner: F) -> Option<LightSample>
+    where
+        F: FnOnce(Direction) -> Ray,
+    {
+        let to_light = self.light.position() - position;
+        let direction = Direction::normalize(to_light).ok()?;
+        let falloff = self.light.falloff(-direction);
+        if falloff == Val(0.0) {
+            return None;
+        }
+        let distance = Distance::along(to_light);
+        let radiance = self.light.intensity() * falloff / to_light.norm_squared();
+        Some(LightSample::new_delta(
+            ray_spawner(direction),
+            Val(1.0),
+            distance,
+            radiance,
+        ))
+    }
+}
+
+impl LightSampling for SpotLightSampler {
+    fn id(&self) -> Option<ShapeId> {
+        None
+    }
+
+    fn shape(&self) -> Option<RefDynShape> {
+        None
+    }
+
+    fn sample_light_surface(
+        &self,
+        intersection: &RayIntersection,
+        _rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        let ray_spawner = |dir| intersection.spawn(dir);
+        self.sample_light_impl(intersection.position(), ray_spawner)
+    }
+
+    fn pdf_light_surface(&self, _intersection: &RayIntersection, _ray_next: &Ray) -> Val {
+        Val(0.0)
+    }
+
+    fn sample_light_volume(
+        &self,
+        scattering: &RayScattering,
+        preselected_light: Option<&PointSample>,
+        _rng: &mut dyn RngCore,
+    ) -> Option<LightSample> {
+        if preselected_light.is_some() {
+            return None;
+        }
+        let ray_spawner = |dir| scattering.spawn(dir);
+        self.sample_light_impl(scattering.position(), ray_spawner)
+    }
+
+    fn pdf_light_volume(&self, _ray_next: &Ray, _preselected_light: Option<&PointSample>) -> Val {
+        Val(0.0)
+    }
+
+    fn position(&self) -> Option<Point> {
+        Some(self.light.position())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::core::Spectrum;
+    use crate::domain::math::geometry::{Normal, SpreadAngle};
+    use crate::domain::ray::event::SurfaceSide;
+
+    use super::*;
+
+    #[test]
+    fn spot_light_sampler_sample_light_surface_fails_when_outside_outer_angle() {
+        let light = SpotLight::new(
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            -Direction::y_direction(),
+            SpreadAngle::new(Val::PI / Val(4.0)).unwrap(),
+            SpreadAngle::new(Val::PI / Val(2.0)).unwrap(),
+            Spectrum::broadcast(Val(1.0)),
+        )
+        .unwrap();
+        let sampler = SpotLightSampler::new(light);
+        let sample_at = |x: Val| {
+            let intersection = RayIntersection::new(
+                Distance::new(Val(1.0)).unwrap(),
+                Point::new(x, Val(0.0), Val(0.0)),
+                Normal::y_direction(),
+                SurfaceSide::Front,
+            );
+            sampler.sample_light_surface(&intersection, &mut rand::rng())
+        };
+
+        let center = sample_at(Val(0.0)).unwrap();
+        assert_eq!(center.radiance(), Some(Spectrum::broadcast(Val(1.0))));
+        assert!(sample_at(Val(0.99)).is_some());
+        assert!(sample_at(Val(1.01)).is_none());
+    }
+}
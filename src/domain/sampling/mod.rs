@@ -4,6 +4,7 @@ pub mod light;
 pub mod phase;
 pub mod photon;
 pub mod point;
+pub mod sampler;
 
 mod def;
 
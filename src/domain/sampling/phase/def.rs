@@ -36,4 +36,8 @@ impl PhaseSample {
             pdf,
         }
     }
+
+    pub fn into_ray_next(self) -> Ray {
+        self.ray_next
+    }
 }
@@ -0,0 +1,130 @@
+use getset::CopyGetters;
+use rand::prelude::*;
+use snafu::prelude::*;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::math::algebra::{Product, Vector};
+use crate::domain::math::geometry::{Direction, Frame};
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RayScattering;
+
+use super::{PhaseSample, PhaseSampling};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+pub struct HenyeyGreensteinPhase {
+    #[getset(get_copy = "pub")]
+    asymmetric: Val,
+}
+
+impl HenyeyGreensteinPhase {
+    pub fn new(asymmetric: Val) -> Result<Self, TryNewHenyeyGreensteinPhaseError> {
+        ensure!(
+            (Val(-1.0)..=Val(1.0)).contains(&asymmetric),
+            InvalidAsymmetricParameterSnafu
+        );
+        Ok(Self { asymmetric })
+    }
+
+    pub fn evaluate(&self, cos: Val) -> Val {
+        let g = self.asymmetric;
+        let num = Val(1.0) - g * g;
+        let den = Val(4.0) * Val::PI * (Val(1.0) + g * g - Val(2.0) * g * cos).powf(Val(1.5));
+        num / den
+    }
+
+    fn sample_cos(&self, rng: &mut dyn RngCore) -> Val {
+        let s = Val(2.0) * Val(rng.random()) - Val(1.0);
+        let cos = if self.asymmetric != Val(0.0) {
+            let g = self.asymmetric;
+            (Val(0.5) / g) * (Val(1.0) + g * g - ((Val(1.0) - g * g) / (Val(1.0) + g * s)).powi(2))
+        } else {
+            s
+        };
+        cos.clamp(Val(-1.0), Val(1.0))
+    }
+}
+
+impl PhaseSampling for HenyeyGreensteinPhase {
+    fn sample_phase(
+        &self,
+        ray: &Ray,
+        scattering: &RayScattering,
+        rng: &mut dyn RngCore,
+    ) -> PhaseSample {
+        let phi = Val(2.0) * Val::PI * Val(rng.random());
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let cos_theta = self.sample_cos(rng);
+        let sin_theta = (Val(1.0) - cos_theta * cos_theta).max(Val(0.0)).sqrt();
+
+        let frame = Frame::new(ray.direction().into());
+        let dir_next_local = Vector::new(cos_phi * sin_theta, sin_phi * sin_theta, cos_theta);
+        let dir_next = Direction::normalize(frame.to_canonical(dir_next_local)).unwrap();
+
+        let ray_next = scattering.spawn(dir_next);
+        let phase = self.evaluate(cos_theta);
+        PhaseSample::new(ray_next, Spectrum::broadcast(phase), phase)
+    }
+
+    fn pdf_phase(&self, dir_out: Direction, dir_in: Direction) -> Val {
+        self.evaluate(-dir_out.dot(dir_in))
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewHenyeyGreensteinPhaseError {
+    #[snafu(display("asymmetric parameter (g) should be in [-1, 1]"))]
+    InvalidAsymmetricParameter,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::{Distance, Point};
+
+    use super::*;
+
+    #[test]
+    fn henyey_greenstein_phase_sample_phase_succeeds_clustering_forward() {
+        let phase = HenyeyGreensteinPhase::new(Val(0.9)).unwrap();
+        let ray = Ray::new(Point::default(), Direction::z_direction());
+        let scattering = RayScattering::new(Distance::zero(), Point::default());
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = 10000;
+        let mut mean_cos = Val(0.0);
+        for _ in 0..n {
+            let sample = phase.sample_phase(&ray, &scattering, &mut rng);
+            let dir_in = sample.ray_next().direction();
+            assert_eq!(sample.pdf(), phase.pdf_phase(-ray.direction(), dir_in));
+            mean_cos += dir_in.z() / Val::from(n);
+        }
+        // The mean cosine of Henyey-Greenstein scattering equals `g`.
+        assert!((mean_cos - Val(0.9)).abs() < Val(0.01), "{mean_cos:?}");
+    }
+
+    #[test]
+    fn henyey_greenstein_phase_evaluate_succeeds_integrating_to_one() {
+        for g in [Val(-0.5), Val(0.0), Val(0.9)] {
+            let phase = HenyeyGreensteinPhase::new(g).unwrap();
+            let n = 100000;
+            let step = Val(2.0) / Val::from(n);
+            let sum = (0..n)
+                .map(|i| phase.evaluate(Val(-1.0) + (Val::from(i) + Val(0.5)) * step))
+                .sum::<Val>();
+            let integral = sum * step * Val(2.0) * Val::PI;
+            assert!(
+                (integral - Val(1.0)).abs() < Val(1e-3),
+                "{g:?}: {integral:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn henyey_greenstein_phase_new_fails_when_asymmetric_parameter_is_invalid() {
+        assert!(matches!(
+            HenyeyGreensteinPhase::new(Val(1.5)),
+            Err(TryNewHenyeyGreensteinPhaseError::InvalidAsymmetricParameter),
+        ));
+    }
+}
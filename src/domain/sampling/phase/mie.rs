@@ -0,0 +1,98 @@
+use getset::CopyGetters;
+use rand::prelude::*;
+use snafu::prelude::*;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::math::algebra::Product;
+use crate::domain::math::geometry::Direction;
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RayScattering;
+
+use super::{HenyeyGreensteinPhase, PhaseSample, PhaseSampling};
+
+/// Directions are sampled from a Henyey-Greenstein lobe, so the pdf differs from the phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+pub struct MiePhase {
+    #[getset(get_copy = "pub")]
+    asymmetric: Val,
+}
+
+impl MiePhase {
+    pub fn new(asymmetric: Val) -> Result<Self, TryNewMiePhaseError> {
+        ensure!(
+            Val(-1.0) < asymmetric && asymmetric < Val(1.0),
+            InvalidAsymmetricParameterSnafu
+        );
+        Ok(Self { asymmetric })
+    }
+
+    pub fn evaluate(&self, cos: Val) -> Val {
+        let g2 = self.asymmetric * self.asymmetric;
+        let num = Val(3.0) * (Val(1.0) - g2) * (Val(1.0) + cos * cos);
+        let den = Val(8.0) * Val::PI * (Val(2.0) + g2) * self.calc_base(cos).powf(Val(1.5));
+        num / den
+    }
+
+    fn lobe(&self) -> HenyeyGreensteinPhase {
+        HenyeyGreensteinPhase::new(self.asymmetric)
+            .expect("asymmetric parameter should be validated by MiePhase::new")
+    }
+
+    fn calc_base(&self, cos: Val) -> Val {
+        let g = self.asymmetric;
+        Val(1.0) + g * g - Val(2.0) * g * cos
+    }
+}
+
+impl PhaseSampling for MiePhase {
+    fn sample_phase(
+        &self,
+        ray: &Ray,
+        scattering: &RayScattering,
+        rng: &mut dyn RngCore,
+    ) -> PhaseSample {
+        let sample = self.lobe().sample_phase(ray, scattering, rng);
+        let cos = ray.direction().dot(sample.ray_next().direction());
+        let (phase, pdf) = (self.evaluate(cos), sample.pdf());
+        PhaseSample::new(sample.into_ray_next(), Spectrum::broadcast(phase), pdf)
+    }
+
+    fn pdf_phase(&self, dir_out: Direction, dir_in: Direction) -> Val {
+        self.lobe().pdf_phase(dir_out, dir_in)
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewMiePhaseError {
+    #[snafu(display("asymmetric parameter (g) should be in (-1, 1)"))]
+    InvalidAsymmetricParameter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mie_phase_evaluate_succeeds_peaking_forward_and_integrating_to_one() {
+        let phase = MiePhase::new(Val(0.76)).unwrap();
+        assert!(phase.evaluate(Val(1.0)) > phase.evaluate(Val(-1.0)) * Val(100.0));
+
+        let n = 100000;
+        let step = Val(2.0) / Val::from(n);
+        let sum = (0..n)
+            .map(|i| phase.evaluate(Val(-1.0) + (Val::from(i) + Val(0.5)) * step))
+            .sum::<Val>();
+        let integral = sum * step * Val(2.0) * Val::PI;
+        assert!((integral - Val(1.0)).abs() < Val(1e-4));
+    }
+
+    #[test]
+    fn mie_phase_new_fails_when_asymmetric_parameter_is_invalid() {
+        assert!(matches!(
+            MiePhase::new(Val(1.0)),
+            Err(TryNewMiePhaseError::InvalidAsymmetricParameter),
+        ));
+    }
+}
@@ -1,3 +1,9 @@
 mod def;
+mod henyey_greenstein;
+mod mie;
+mod rayleigh;
 
 pub use def::{PhaseSample, PhaseSampling};
+pub use henyey_greenstein::{HenyeyGreensteinPhase, TryNewHenyeyGreensteinPhaseError};
+pub use mie::{MiePhase, TryNewMiePhaseError};
+pub use rayleigh::RayleighPhase;
@@ -0,0 +1,95 @@
+use rand::prelude::*;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::math::algebra::{Product, Vector};
+use crate::domain::math::geometry::{Direction, Frame};
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RayScattering;
+
+use super::{PhaseSample, PhaseSampling};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RayleighPhase;
+
+impl RayleighPhase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn evaluate(&self, cos: Val) -> Val {
+        Val(3.0) / (Val(16.0) * Val::PI) * (Val(1.0) + cos * cos)
+    }
+
+    fn sample_cos(&self, rng: &mut dyn RngCore) -> Val {
+        // Inverts the CDF by solving `cos^3 + 3 cos - q = 0` with Cardano's formula.
+        let q = Val(4.0) * (Val(2.0) * Val(rng.random()) - Val(1.0));
+        let a = (Val(0.5) * q + (Val(0.25) * q * q + Val(1.0)).sqrt()).powf(Val(1.0 / 3.0));
+        (a - a.recip()).clamp(Val(-1.0), Val(1.0))
+    }
+}
+
+impl PhaseSampling for RayleighPhase {
+    fn sample_phase(
+        &self,
+        ray: &Ray,
+        scattering: &RayScattering,
+        rng: &mut dyn RngCore,
+    ) -> PhaseSample {
+        let cos_theta = self.sample_cos(rng);
+        let sin_theta = (Val(1.0) - cos_theta * cos_theta).max(Val(0.0)).sqrt();
+        let phi = Val(2.0) * Val::PI * Val(rng.random());
+        let (sin_phi, cos_phi) = phi.sin_cos();
+
+        let frame = Frame::new(ray.direction().into());
+        let dir_next_local = Vector::new(cos_phi * sin_theta, sin_phi * sin_theta, cos_theta);
+        let dir_next = Direction::normalize(frame.to_canonical(dir_next_local)).unwrap();
+
+        let ray_next = scattering.spawn(dir_next);
+        let phase = self.evaluate(cos_theta);
+        PhaseSample::new(ray_next, Spectrum::broadcast(phase), phase)
+    }
+
+    fn pdf_phase(&self, dir_out: Direction, dir_in: Direction) -> Val {
+        self.evaluate(-dir_out.dot(dir_in))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::{Distance, Point};
+
+    use super::*;
+
+    #[test]
+    fn rayleigh_phase_evaluate_succeeds_integrating_to_one_over_sphere() {
+        let phase = RayleighPhase::new();
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = 200000;
+        let sum = (0..n)
+            .map(|_| phase.evaluate(Direction::random(&mut rng).z()))
+            .sum::<Val>();
+        let integral = sum * Val(4.0) * Val::PI / Val::from(n);
+        assert!((integral - Val(1.0)).abs() < Val(1e-2));
+    }
+
+    #[test]
+    fn rayleigh_phase_sample_phase_succeeds_matching_pdf() {
+        let phase = RayleighPhase::new();
+        let ray = Ray::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Direction::z_direction(),
+        );
+        let scattering = RayScattering::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::new(Val(0.0), Val(0.0), Val(1.0)),
+        );
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..64 {
+            let sample = phase.sample_phase(&ray, &scattering, &mut rng);
+            let pdf = phase.pdf_phase(-ray.direction(), sample.ray_next().direction());
+            assert_eq!(sample.pdf(), pdf);
+        }
+    }
+}
@@ -19,7 +19,7 @@ impl AggregatePhotonSampler {
             samplers.push(Box::new(EmptyPhotonSampler::new()));
         }
         let weights = (samplers.iter())
-            .map(|sampler| sampler.area().value())
+            .map(|sampler| sampler.power())
             .map(|weight| weight.0.max(Val::PRECISION))
             .collect::<Vec<_>>();
         let index_sampler = WeightedIndex::new(weights).unwrap();
@@ -39,16 +39,22 @@ impl PhotonSampling for AggregatePhotonSampler {
         (self.samplers.iter()).fold(Area::zero(), |sum, s| sum + s.area())
     }
 
+    fn power(&self) -> Val {
+        self.samplers.iter().map(|s| s.power()).sum()
+    }
+
     fn sample_photon(&self, rng: &mut dyn RngCore) -> Option<PhotonSample> {
         let which = self.index_sampler.sample(rng);
         (self.samplers.get(which))
             .and_then(|sampler| sampler.sample_photon(rng))
-            .map(|sample| sample.scale_pdf(self.weights[which]))
+            .map(|sample| sample.scale_pdf(self.weights[which]).with_emitter(which))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use rand::rngs::StdRng;
+
     use crate::domain::color::core::Spectrum;
     use crate::domain::material::primitive::Emissive;
     use crate::domain::math::geometry::{Point, SpreadAngle};
@@ -60,6 +66,21 @@ mod tests {
 
     use super::*;
 
+    fn unit_triangle_emitter(radiance: Val) -> Box<dyn PhotonSampling> {
+        Box::new(PhotonSamplerAdapter::new(
+            TrianglePointSampler::new(
+                ShapeId::new(ShapeKind::Triangle, 0),
+                Triangle::new(
+                    Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                    Point::new(Val(1.0), Val(0.0), Val(0.0)),
+                    Point::new(Val(0.0), Val(1.0), Val(0.0)),
+                )
+                .unwrap(),
+            ),
+            Emissive::new(Spectrum::broadcast(radiance), SpreadAngle::hemisphere()),
+        ))
+    }
+
     #[test]
     fn aggregate_photon_sampler_sample_photon_succeeds() {
         let sampler1: Box<dyn PhotonSampling> = Box::new(PhotonSamplerAdapter::new(
@@ -93,4 +114,23 @@ mod tests {
         assert_eq!(photon.photon().throughput().green(), Val(2.5) * Val::PI);
         assert_eq!(photon.photon().throughput().blue(), Val(2.5) * Val::PI);
     }
+
+    #[test]
+    fn aggregate_photon_sampler_sample_photon_succeeds_balancing_power() {
+        let bright = unit_triangle_emitter(Val(9.0));
+        let dim = unit_triangle_emitter(Val(1.0));
+        let sampler = AggregatePhotonSampler::new(vec![bright, dim]);
+        assert_eq!(sampler.power(), Val(5.0) * Val::PI);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut counts = [0, 0];
+        for _ in 0..2000 {
+            let photon = sampler.sample_photon(&mut rng).unwrap();
+            assert_eq!(photon.photon().throughput().red(), Val(5.0) * Val::PI);
+            counts[photon.emitter()] += 1;
+        }
+
+        let ratio = Val::from(counts[0]) / Val::from(counts[1]);
+        assert!(Val(7.0) < ratio && ratio < Val(11.5), "ratio = {ratio:?}");
+    }
 }
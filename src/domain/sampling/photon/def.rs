@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use getset::Getters;
+use getset::{CopyGetters, Getters};
 use rand::prelude::*;
 
 use crate::domain::math::geometry::Area;
@@ -12,23 +12,32 @@ use crate::domain::ray::photon::PhotonRay;
 pub trait PhotonSampling: Debug + Send + Sync {
     fn area(&self) -> Area;
 
+    fn power(&self) -> Val;
+
     fn sample_photon(&self, rng: &mut dyn RngCore) -> Option<PhotonSample>;
 }
 
-#[derive(Debug, Clone, PartialEq, Getters)]
+#[derive(Debug, Clone, PartialEq, Getters, CopyGetters)]
 pub struct PhotonSample {
     #[getset(get = "pub")]
     photon: PhotonRay,
+    #[getset(get_copy = "pub")]
+    emitter: usize,
 }
 
 impl PhotonSample {
     pub fn new(photon: PhotonRay) -> Self {
-        Self { photon }
+        Self { photon, emitter: 0 }
+    }
+
+    pub fn with_emitter(self, emitter: usize) -> Self {
+        Self { emitter, ..self }
     }
 
     pub fn scale_pdf(self, multiplier: Val) -> Self {
         Self {
             photon: self.photon.scale_throughput(multiplier.recip()),
+            ..self
         }
     }
 }
@@ -39,6 +48,9 @@ where
     Ray: Transform<T>,
 {
     fn transform_impl(self, transformation: &T) -> Self {
-        Self::new(self.photon.transform(transformation))
+        Self {
+            photon: self.photon.transform(transformation),
+            ..self
+        }
     }
 }
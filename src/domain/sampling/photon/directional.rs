@@ -0,0 +1,84 @@
+use rand::prelude::*;
+
+use crate::domain::light::primitive::DirectionalLight;
+use crate::domain::math::geometry::{Area, Frame, Normal, Point};
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::photon::PhotonRay;
+use crate::domain::shape::def::BoundingBox;
+
+use super::{PhotonSample, PhotonSampling};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectionalPhotonSampler {
+    light: DirectionalLight,
+    center: Point,
+    radius: Val,
+    frame: Frame,
+    area: Area,
+}
+
+impl DirectionalPhotonSampler {
+    pub fn new(light: DirectionalLight, scene_bounds: &BoundingBox) -> Self {
+        let radius = (Val(0.5) * (scene_bounds.max() - scene_bounds.min()).norm()).max(Val(1e-3));
+        let center = scene_bounds.centroid() - radius * light.direction();
+        let frame = Frame::new(Normal::from(light.direction()));
+        let area = Area::new(Val::PI * radius.powi(2)).unwrap();
+        Self {
+            light,
+            center,
+            radius,
+            frame,
+            area,
+        }
+    }
+}
+
+impl PhotonSampling for DirectionalPhotonSampler {
+    fn area(&self) -> Area {
+        self.area
+    }
+
+    fn power(&self) -> Val {
+        self.light.radiance().luminance() * self.area.value()
+    }
+
+    fn sample_photon(&self, rng: &mut dyn RngCore) -> Option<PhotonSample> {
+        let rho = self.radius * Val(rng.random()).sqrt();
+        let (sin_phi, cos_phi) = (Val(2.0) * Val::PI * Val(rng.random())).sin_cos();
+        let offset = rho * cos_phi * self.frame.tangent() + rho * sin_phi * self.frame.cross();
+        let ray = Ray::new(self.center + offset, self.light.direction());
+        let throughput = self.light.radiance() * self.area.value();
+        Some(PhotonSample::new(PhotonRay::new(ray, throughput)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::core::Spectrum;
+    use crate::domain::math::geometry::Direction;
+
+    use super::*;
+
+    #[test]
+    fn directional_photon_sampler_sample_photon_succeeds() {
+        let light = DirectionalLight::new(-Direction::y_direction(), Spectrum::broadcast(Val(1.0)));
+        let bounds = BoundingBox::new(
+            Point::new(Val(-1.0), Val(-1.0), Val(-1.0)),
+            Point::new(Val(1.0), Val(1.0), Val(1.0)),
+        );
+        let sampler = DirectionalPhotonSampler::new(light, &bounds);
+        assert_eq!(sampler.area().value(), Val(3.0) * Val::PI);
+
+        let rng = &mut rand::rng();
+        for _ in 0..16 {
+            let sample = sampler.sample_photon(rng).unwrap();
+            let photon = sample.photon();
+            assert_eq!(photon.direction(), -Direction::y_direction());
+            assert_eq!(photon.start().y(), Val(3.0).sqrt());
+            let radial = photon.start() - Point::new(Val(0.0), photon.start().y(), Val(0.0));
+            assert!(radial.norm_squared() <= Val(3.0));
+            assert_eq!(photon.throughput().red(), Val(3.0) * Val::PI);
+        }
+    }
+}
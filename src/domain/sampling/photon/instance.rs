@@ -2,6 +2,7 @@ use rand::prelude::*;
 
 use crate::domain::material::primitive::Emissive;
 use crate::domain::math::geometry::Area;
+use crate::domain::math::numeric::Val;
 use crate::domain::math::transformation::{Sequential, Transform};
 use crate::domain::sampling::Sampleable;
 use crate::domain::shape::util::{Instance, ShapeId};
@@ -30,6 +31,18 @@ impl PhotonSampling for InstancePhotonSampler {
         self.sampler
             .as_ref()
             .map_or(Area::zero(), |sampler| sampler.area())
+            .transform(&self.transformation)
+    }
+
+    fn power(&self) -> Val {
+        let Some(sampler) = self.sampler.as_ref() else {
+            return Val(0.0);
+        };
+        let area = sampler.area();
+        if area == Area::zero() {
+            return sampler.power();
+        }
+        sampler.power() * (area.transform(&self.transformation).value() / area.value())
     }
 
     fn sample_photon(&self, rng: &mut dyn RngCore) -> Option<PhotonSample> {
@@ -1,9 +1,15 @@
 mod aggregate;
 mod def;
+mod directional;
 mod instance;
+mod point;
+mod spot;
 mod util;
 
 pub use aggregate::AggregatePhotonSampler;
 pub use def::{PhotonSample, PhotonSampling};
+pub use directional::DirectionalPhotonSampler;
 pub use instance::InstancePhotonSampler;
+pub use point::PointPhotonSampler;
+pub use spot::SpotPhotonSampler;
 pub use util::{EmptyPhotonSampler, PhotonSamplerAdapter};
@@ -0,0 +1,37 @@
+use rand::prelude::*;
+
+use crate::domain::light::primitive::PointLight;
+use crate::domain::math::geometry::{Area, Direction};
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::photon::PhotonRay;
+
+use super::{PhotonSample, PhotonSampling};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointPhotonSampler {
+    light: PointLight,
+}
+
+impl PointPhotonSampler {
+    pub fn new(light: PointLight) -> Self {
+        Self { light }
+    }
+}
+
+impl PhotonSampling for PointPhotonSampler {
+    fn area(&self) -> Area {
+        // A point light has no area, so a unit sphere is used as its selection weight.
+        Area::new(Val(4.0) * Val::PI).unwrap()
+    }
+
+    fn power(&self) -> Val {
+        self.light.intensity().luminance() * (Val(4.0) * Val::PI)
+    }
+
+    fn sample_photon(&self, rng: &mut dyn RngCore) -> Option<PhotonSample> {
+        let ray = Ray::new(self.light.position(), Direction::random(rng));
+        let throughput = self.light.intensity() * (Val(4.0) * Val::PI);
+        Some(PhotonSample::new(PhotonRay::new(ray, throughput)))
+    }
+}
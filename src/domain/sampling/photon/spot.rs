@@ -0,0 +1,59 @@
+use rand::prelude::*;
+
+use crate::domain::light::primitive::SpotLight;
+use crate::domain::math::algebra::{UnitVector, Vector};
+use crate::domain::math::geometry::{Area, Frame, Normal};
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::Ray;
+use crate::domain::ray::photon::PhotonRay;
+
+use super::util::estimate_power;
+use super::{PhotonSample, PhotonSampling};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpotPhotonSampler {
+    light: SpotLight,
+    frame: Frame,
+    solid_angle: Val,
+    power: Val,
+}
+
+impl SpotPhotonSampler {
+    pub fn new(light: SpotLight) -> Self {
+        let frame = Frame::new(Normal::from(light.direction()));
+        let solid_angle = Val(2.0) * Val::PI * (Val(1.0) - light.outer().cos_half());
+        let mut sampler = Self {
+            light,
+            frame,
+            solid_angle,
+            power: Val(0.0),
+        };
+        sampler.power = estimate_power(&sampler);
+        sampler
+    }
+}
+
+impl PhotonSampling for SpotPhotonSampler {
+    fn area(&self) -> Area {
+        // A spot light has no area, so its cone cut from a unit sphere is used instead.
+        Area::new(self.solid_angle).unwrap()
+    }
+
+    fn power(&self) -> Val {
+        self.power
+    }
+
+    fn sample_photon(&self, rng: &mut dyn RngCore) -> Option<PhotonSample> {
+        let cos_outer = self.light.outer().cos_half();
+        let cos_theta = Val(1.0) - Val(rng.random()) * (Val(1.0) - cos_outer);
+        let sin_theta = (Val(1.0) - cos_theta.powi(2)).max(Val(0.0)).sqrt();
+        let (sin_phi, cos_phi) = (Val(2.0) * Val::PI * Val(rng.random())).sin_cos();
+        let local = Vector::new(sin_theta * cos_phi, sin_theta * sin_phi, cos_theta);
+        let direction = (self.frame).to_canonical_unit(UnitVector::normalize(local).ok()?);
+
+        let throughput =
+            self.light.intensity() * (self.light.falloff(direction.into()) * self.solid_angle);
+        let ray = Ray::new(self.light.position(), direction.into());
+        Some(PhotonSample::new(PhotonRay::new(ray, throughput)))
+    }
+}
@@ -1,4 +1,5 @@
 use rand::prelude::*;
+use rand::rngs::StdRng;
 
 use crate::domain::color::core::Spectrum;
 use crate::domain::material::primitive::Emissive;
@@ -27,6 +28,10 @@ impl PhotonSampling for EmptyPhotonSampler {
         Area::zero()
     }
 
+    fn power(&self) -> Val {
+        Val(0.0)
+    }
+
     fn sample_photon(&self, _rng: &mut dyn RngCore) -> Option<PhotonSample> {
         None
     }
@@ -40,6 +45,7 @@ where
     inner: PS,
     emissive: Emissive,
     area: Area,
+    power: Val,
 }
 
 impl<PS> PhotonSamplerAdapter<PS>
@@ -48,11 +54,17 @@ where
 {
     pub fn new(inner: PS, emissive: Emissive) -> Self {
         let area = inner.shape().map_or(Area::zero(), |shape| shape.area());
-        Self {
+        let mut sampler = Self {
             inner,
             emissive,
             area,
-        }
+            power: Val(0.0),
+        };
+        sampler.power = match sampler.emissive.lambertian_exitance() {
+            Some(exitance) => exitance.luminance() * area.value(),
+            None => estimate_power(&sampler),
+        };
+        sampler
     }
 }
 
@@ -64,46 +76,66 @@ where
         self.area
     }
 
+    fn power(&self) -> Val {
+        self.power
+    }
+
     fn sample_photon(&self, rng: &mut dyn RngCore) -> Option<PhotonSample> {
         let sample = self.inner.sample_point(rng)?;
         let point = sample.point();
         let pdf_point = sample.pdf();
 
-        let normal = sample.normal();
+        let (normal, pdf_side) = if !self.emissive.two_sided() {
+            (sample.normal(), Val(1.0))
+        } else if rng.random::<bool>() {
+            (sample.normal(), Val(0.5))
+        } else {
+            (-sample.normal(), Val(0.5))
+        };
         let beam_angle = self.emissive.beam_angle();
-        let (dir, pdf_dir_div_cos) = if beam_angle.is_hemisphere() {
-            let dir = Direction::random_cosine_hemisphere(normal, rng);
-            (dir, Val::FRAC_1_PI)
-        } else if beam_angle.is_directional() {
+        let (dir, pdf_dir_div_cos) = if beam_angle.is_directional() {
             (normal.into(), Val(1.0))
         } else {
-            let sin2_beam = beam_angle.angle().sin().powi(2);
-            let (u1_sin, u2) = (Val(rng.random()) * sin2_beam, Val(rng.random()));
-            let (sin_theta, cos_theta) = (u1_sin.sqrt(), (Val(1.0) - u1_sin).sqrt());
-            let (sin_phi, cos_phi) = (Val(0.5) * u2 * Val::FRAC_1_PI).sin_cos();
+            let exponent = self.emissive.cosine_power() + Val(1.0);
+            let coverage = Val(1.0) - beam_angle.cos_half().powf(exponent);
+            let (u1, u2) = (Val(rng.random()), Val(rng.random()));
+            let cos_theta = (Val(1.0) - u1 * coverage).powf(exponent.recip());
+            let sin_theta = (Val(1.0) - cos_theta * cos_theta).max(Val(0.0)).sqrt();
+            let (sin_phi, cos_phi) = (Val(2.0) * Val::PI * u2).sin_cos();
             let (x, y, z) = (sin_theta * cos_phi, sin_theta * sin_phi, cos_theta);
             let local_dir = Direction::normalize(Vector::new(x, y, z)).unwrap();
 
             let frame = Frame::new(normal);
-            let dir = frame.to_canonical_unit(local_dir.into()).into();
-            (dir, Val::FRAC_1_PI / sin2_beam)
+            let dir: Direction = frame.to_canonical_unit(local_dir.into()).into();
+            let pdf_dir = exponent * cos_theta.powf(exponent - Val(1.0)) / (Val(2.0) * Val::PI);
+            (dir, pdf_dir / (coverage * cos_theta))
         };
 
         let tmp_ray = Ray::new(point, -dir);
         let part = RayIntersectionPart::new(Distance::zero(), &tmp_ray);
         let intersection = self.inner.shape()?.complete_part(part);
-        let radiance = self.emissive.radiance(&intersection);
+        let radiance = self.emissive.emission(&intersection, dir);
         if radiance == Spectrum::zero() {
             return None;
         }
 
         let ray = Ray::new(point, dir);
-        let throughput = radiance / (pdf_point * pdf_dir_div_cos);
+        let throughput = radiance / (pdf_point * pdf_side * pdf_dir_div_cos);
         let photon = PhotonRay::new(ray, throughput);
         Some(PhotonSample::new(photon))
     }
 }
 
+pub(crate) fn estimate_power(sampler: &dyn PhotonSampling) -> Val {
+    const SAMPLES: usize = 256;
+    let mut rng = StdRng::seed_from_u64(0);
+    let total = (0..SAMPLES)
+        .filter_map(|_| sampler.sample_photon(&mut rng))
+        .map(|sample| sample.photon().throughput().luminance())
+        .sum::<Val>();
+    total / Val::from(SAMPLES)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::domain::color::core::Spectrum;
@@ -135,4 +167,41 @@ mod tests {
         assert_eq!(photon.photon().throughput().green(), Val::PI * Val(0.5));
         assert_eq!(photon.photon().throughput().blue(), Val::PI * Val(0.5));
     }
+
+    #[test]
+    fn photon_sampler_adapter_power_succeeds() {
+        let sampler = PhotonSamplerAdapter::new(
+            TrianglePointSampler::new(
+                ShapeId::new(ShapeKind::Triangle, 0),
+                Triangle::new(
+                    Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                    Point::new(Val(1.0), Val(0.0), Val(0.0)),
+                    Point::new(Val(0.0), Val(1.0), Val(0.0)),
+                )
+                .unwrap(),
+            ),
+            Emissive::new(Spectrum::broadcast(Val(2.0)), SpreadAngle::hemisphere())
+                .with_two_sided(true),
+        );
+        assert_eq!(sampler.power(), Val(2.0) * Val::PI);
+    }
+
+    #[test]
+    fn photon_sampler_adapter_power_succeeds_with_cosine_power() {
+        let sampler = PhotonSamplerAdapter::new(
+            TrianglePointSampler::new(
+                ShapeId::new(ShapeKind::Triangle, 0),
+                Triangle::new(
+                    Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                    Point::new(Val(1.0), Val(0.0), Val(0.0)),
+                    Point::new(Val(0.0), Val(1.0), Val(0.0)),
+                )
+                .unwrap(),
+            ),
+            Emissive::new(Spectrum::broadcast(Val(2.0)), SpreadAngle::hemisphere())
+                .with_cosine_power(Val(3.0))
+                .unwrap(),
+        );
+        assert_eq!(sampler.power(), Val::PI * Val(0.5));
+    }
 }
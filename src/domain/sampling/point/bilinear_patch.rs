@@ -0,0 +1,91 @@
+use rand::prelude::*;
+
+use crate::domain::math::algebra::Product;
+use crate::domain::math::geometry::Point;
+use crate::domain::math::numeric::Val;
+use crate::domain::shape::def::RefDynShape;
+use crate::domain::shape::primitive::BilinearPatch;
+use crate::domain::shape::util::ShapeId;
+
+use super::{PointSample, PointSampling};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BilinearPatchPointSampler {
+    id: ShapeId,
+    patch: BilinearPatch,
+}
+
+impl BilinearPatchPointSampler {
+    pub fn new(id: ShapeId, patch: BilinearPatch) -> Self {
+        Self { id, patch }
+    }
+
+    fn pdf_at(&self, u: Val, v: Val) -> Val {
+        let (du, dv) = self.patch.partial_derivatives(u, v);
+        du.cross(dv).norm().recip()
+    }
+}
+
+impl PointSampling for BilinearPatchPointSampler {
+    fn id(&self) -> Option<ShapeId> {
+        Some(self.id)
+    }
+
+    fn shape(&self) -> Option<RefDynShape> {
+        Some((&self.patch).into())
+    }
+
+    fn sample_point(&self, rng: &mut dyn RngCore) -> Option<PointSample> {
+        let (u, v) = (Val(rng.random()), Val(rng.random()));
+        let point = self.patch.point_at(u, v);
+        let normal = self.patch.normal_at(u, v);
+        Some(PointSample::new(point, normal, self.pdf_at(u, v), self.id))
+    }
+
+    fn pdf_point(&self, point: Point, checked_inside: bool) -> Val {
+        let (u, v) = self.patch.locate(point);
+        let inside = checked_inside
+            || ((Val(0.0)..=Val(1.0)).contains(&u)
+                && (Val(0.0)..=Val(1.0)).contains(&v)
+                && self.patch.point_at(u, v) == point);
+        if inside {
+            self.pdf_at(u.clamp(Val(0.0), Val(1.0)), v.clamp(Val(0.0), Val(1.0)))
+        } else {
+            Val(0.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::shape::def::{Shape, ShapeKind};
+
+    use super::*;
+
+    #[test]
+    fn bilinear_patch_point_sampler_pdf_point_succeeds() {
+        let patch = BilinearPatch::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Point::new(Val(1.0), Val(0.0), Val(1.0)),
+            Point::new(Val(0.0), Val(1.0), Val(1.0)),
+            Point::new(Val(1.0), Val(1.0), Val(0.0)),
+        )
+        .unwrap();
+        let sampler =
+            BilinearPatchPointSampler::new(ShapeId::new(ShapeKind::BilinearPatch, 0), patch);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut area = Val(0.0);
+        for _ in 0..10000 {
+            let sample = sampler.sample_point(&mut rng).unwrap();
+            assert_eq!(sampler.pdf_point(sample.point(), false), sample.pdf());
+            area += sample.pdf().recip();
+        }
+        area /= Val(10000.0);
+        let expected = sampler.patch.area().value();
+        assert!((area - expected).abs() < Val(0.01) * expected, "{area:?}");
+
+        let outside = Point::new(Val(0.5), Val(0.5), Val(2.0));
+        assert_eq!(sampler.pdf_point(outside, false), Val(0.0));
+    }
+}
@@ -0,0 +1,118 @@
+use rand::prelude::*;
+
+use crate::domain::math::algebra::Product;
+use crate::domain::math::geometry::{Frame, Normal, Point};
+use crate::domain::math::numeric::Val;
+use crate::domain::shape::def::{RefDynShape, Shape};
+use crate::domain::shape::primitive::Cylinder;
+use crate::domain::shape::util::ShapeId;
+
+use super::{PointSample, PointSampling};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CylinderPointSampler {
+    id: ShapeId,
+    cylinder: Cylinder,
+    frame: Frame,
+    area_inv: Val,
+    side_prob: Val,
+}
+
+impl CylinderPointSampler {
+    pub fn new(id: ShapeId, cylinder: Cylinder) -> Self {
+        let frame = Frame::new(cylinder.axis().into());
+        let area = cylinder.area().value();
+        let side_area = Val(2.0) * Val::PI * cylinder.radius() * cylinder.height();
+        Self {
+            id,
+            cylinder,
+            frame,
+            area_inv: area.recip(),
+            side_prob: side_area / area,
+        }
+    }
+}
+
+impl PointSampling for CylinderPointSampler {
+    fn id(&self) -> Option<ShapeId> {
+        Some(self.id)
+    }
+
+    fn shape(&self) -> Option<RefDynShape> {
+        Some((&self.cylinder).into())
+    }
+
+    fn sample_point(&self, rng: &mut dyn RngCore) -> Option<PointSample> {
+        let (sin_phi, cos_phi) = (Val(2.0) * Val::PI * Val(rng.random())).sin_cos();
+        let radial = cos_phi * self.frame.tangent() + sin_phi * self.frame.cross();
+        let axis = self.cylinder.axis();
+
+        let (point, normal) = if Val(rng.random()) < self.side_prob {
+            let axial = self.cylinder.height() * Val(rng.random());
+            let point = self.cylinder.endpoint0() + axial * axis + self.cylinder.radius() * radial;
+            (point, Normal::normalize(radial).ok()?)
+        } else {
+            let rho = self.cylinder.radius() * Val(rng.random()).sqrt();
+            if rng.random::<bool>() {
+                let point = self.cylinder.endpoint0() + rho * radial;
+                (point, -Normal::from(axis))
+            } else {
+                let point = self.cylinder.endpoint1() + rho * radial;
+                (point, Normal::from(axis))
+            }
+        };
+        Some(PointSample::new(point, normal, self.area_inv, self.id))
+    }
+
+    fn pdf_point(&self, point: Point, checked_inside: bool) -> Val {
+        if checked_inside {
+            return self.area_inv;
+        }
+        let offset = point - self.cylinder.endpoint0();
+        let axial = offset.dot(self.cylinder.axis());
+        let perp2 = (offset - axial * self.cylinder.axis()).norm_squared();
+        let radius2 = self.cylinder.radius().powi(2);
+
+        let on_side = (Val(0.0)..=self.cylinder.height()).contains(&axial) && perp2 == radius2;
+        let on_cap = self.cylinder.capped()
+            && (axial == Val(0.0) || axial == self.cylinder.height())
+            && perp2 <= radius2;
+        if on_side || on_cap {
+            self.area_inv
+        } else {
+            Val(0.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::shape::def::ShapeKind;
+
+    use super::*;
+
+    #[test]
+    fn cylinder_point_sampler_pdf_point_succeeds() {
+        let cylinder = Cylinder::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Point::new(Val(0.0), Val(0.0), Val(2.0)),
+            Val(1.0),
+        )
+        .unwrap();
+        let pdf = cylinder.area().value().recip();
+        let sampler = CylinderPointSampler::new(ShapeId::new(ShapeKind::Cylinder, 0), cylinder);
+
+        assert_eq!(
+            sampler.pdf_point(Point::new(Val(1.0), Val(0.0), Val(1.0)), false),
+            pdf,
+        );
+        assert_eq!(
+            sampler.pdf_point(Point::new(Val(0.5), Val(0.0), Val(2.0)), false),
+            pdf,
+        );
+        assert_eq!(
+            sampler.pdf_point(Point::new(Val(0.5), Val(0.0), Val(1.0)), false),
+            Val(0.0),
+        );
+    }
+}
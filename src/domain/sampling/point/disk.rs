@@ -0,0 +1,87 @@
+use rand::prelude::*;
+
+use crate::domain::math::geometry::{Frame, Normal, Point};
+use crate::domain::math::numeric::Val;
+use crate::domain::shape::def::{RefDynShape, Shape};
+use crate::domain::shape::primitive::Disk;
+use crate::domain::shape::util::ShapeId;
+
+use super::{PointSample, PointSampling};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskPointSampler {
+    id: ShapeId,
+    disk: Disk,
+    normal: Normal,
+    frame: Frame,
+    area_inv: Val,
+}
+
+impl DiskPointSampler {
+    pub fn new(id: ShapeId, disk: Disk) -> Self {
+        let normal = disk.normal(disk.center());
+        let frame = Frame::new(normal);
+        let area_inv = disk.area().recip();
+        Self {
+            id,
+            disk,
+            normal,
+            frame,
+            area_inv,
+        }
+    }
+}
+
+impl PointSampling for DiskPointSampler {
+    fn id(&self) -> Option<ShapeId> {
+        Some(self.id)
+    }
+
+    fn shape(&self) -> Option<RefDynShape> {
+        Some((&self.disk).into())
+    }
+
+    fn sample_point(&self, rng: &mut dyn RngCore) -> Option<PointSample> {
+        let rho = self.disk.radius() * Val(rng.random()).sqrt();
+        let (sin_phi, cos_phi) = (Val(2.0) * Val::PI * Val(rng.random())).sin_cos();
+        let offset = rho * cos_phi * self.frame.tangent() + rho * sin_phi * self.frame.cross();
+        let point = self.disk.center() + offset;
+        Some(PointSample::new(point, self.normal, self.area_inv, self.id))
+    }
+
+    fn pdf_point(&self, point: Point, checked_inside: bool) -> Val {
+        if checked_inside {
+            return self.area_inv;
+        }
+        let offset = point - self.disk.center();
+        let inside = offset.is_perpendicular_to(self.normal)
+            && offset.norm_squared() <= self.disk.radius().powi(2);
+        if inside { self.area_inv } else { Val(0.0) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::shape::def::ShapeKind;
+
+    use super::*;
+
+    #[test]
+    fn disk_point_sampler_pdf_point_succeeds() {
+        let disk = Disk::new(Point::default(), Normal::z_direction(), Val(1.0)).unwrap();
+        let sampler = DiskPointSampler::new(ShapeId::new(ShapeKind::Disk, 0), disk);
+
+        assert_eq!(
+            sampler.pdf_point(Point::new(Val(0.5), Val(0.5), Val(0.0)), false),
+            Val::FRAC_1_PI,
+        );
+        assert_eq!(
+            sampler.pdf_point(Point::new(Val(0.5), Val(0.5), Val(0.1)), false),
+            Val(0.0),
+        );
+        assert_eq!(
+            sampler.pdf_point(Point::new(Val(1.0), Val(1.0), Val(0.0)), false),
+            Val(0.0),
+        );
+    }
+}
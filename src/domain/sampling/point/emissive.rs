@@ -0,0 +1,284 @@
+use rand::prelude::*;
+use rand_distr::weighted::WeightedIndex;
+
+use crate::domain::material::primitive::Emissive;
+use crate::domain::math::algebra::Product;
+use crate::domain::math::geometry::{Distance, Point};
+use crate::domain::math::numeric::{Val, WrappedVal};
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::RayIntersectionPart;
+use crate::domain::shape::def::{DynShape, RefDynShape, Shape};
+use crate::domain::shape::primitive::Triangle;
+use crate::domain::shape::util::ShapeId;
+
+use super::{PointSample, PointSampling};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmissivePointSampler {
+    id: ShapeId,
+    shape: DynShape,
+    triangles: Vec<Triangle>,
+    cell_areas: Vec<Val>,
+    probs: Vec<Val>,
+    index_sampler: WeightedIndex<WrappedVal>,
+}
+
+impl EmissivePointSampler {
+    const SUBDIVISIONS: usize = 16;
+    const CELLS: usize = 2 * Self::SUBDIVISIONS * Self::SUBDIVISIONS;
+    const UNIFORM_WEIGHT: Val = Val(0.1);
+
+    pub fn new(id: ShapeId, shape: RefDynShape, emissive: &Emissive) -> Option<Self> {
+        let (shape, triangles): (DynShape, _) = match shape {
+            RefDynShape::Triangle(s) => (s.clone().into(), vec![s.clone()]),
+            RefDynShape::Polygon(s) => (s.clone().into(), s.triangulate().to_vec()),
+            RefDynShape::MeshTriangle(s) => (s.clone().into(), vec![s.to_triangle()]),
+            RefDynShape::MeshPolygon(s) => {
+                (s.clone().into(), s.to_polygon().triangulate().to_vec())
+            }
+            _ => return None,
+        };
+
+        let total_area = (triangles.iter()).map(|t| t.area().value()).sum::<Val>();
+        let mut cell_areas = Vec::with_capacity(triangles.len());
+        let mut luminances = Vec::with_capacity(triangles.len() * Self::CELLS);
+        let mut areas = Vec::with_capacity(triangles.len() * Self::CELLS);
+        for triangle in &triangles {
+            let cell_area = triangle.area().value() / Val::from(Self::CELLS / 2);
+            cell_areas.push(cell_area);
+            for cell in 0..Self::CELLS {
+                let Some(vertices) = Self::cell_vertices(cell) else {
+                    luminances.push(Val(0.0));
+                    areas.push(Val(0.0));
+                    continue;
+                };
+                let (b1, b2) = (vertices.iter())
+                    .fold((Val(0.0), Val(0.0)), |(s1, s2), (b1, b2)| {
+                        (s1 + *b1, s2 + *b2)
+                    });
+                let centroid = Self::to_point(triangle, b1 / Val(3.0), b2 / Val(3.0));
+                luminances.push(Self::evaluate(&shape, triangle, centroid, emissive) * cell_area);
+                areas.push(cell_area);
+            }
+        }
+
+        let total_luminance = luminances.iter().copied().sum::<Val>();
+        let probs = (luminances.into_iter().zip(areas))
+            .map(|(luminance, area)| {
+                let uniform = area / total_area;
+                if total_luminance > Val(0.0) {
+                    Val::lerp(luminance / total_luminance, uniform, Self::UNIFORM_WEIGHT)
+                } else {
+                    uniform
+                }
+            })
+            .collect::<Vec<_>>();
+        let index_sampler = WeightedIndex::new(probs.iter().map(|p| p.0)).ok()?;
+
+        Some(Self {
+            id,
+            shape,
+            triangles,
+            cell_areas,
+            probs,
+            index_sampler,
+        })
+    }
+
+    fn evaluate(shape: &DynShape, triangle: &Triangle, point: Point, emissive: &Emissive) -> Val {
+        let tmp_ray = Ray::new(point, (-triangle.normal(point)).into());
+        let part = RayIntersectionPart::new(Distance::zero(), &tmp_ray);
+        let intersection = shape.complete_part(part);
+        emissive.radiance(&intersection).luminance()
+    }
+
+    fn cell_vertices(cell: usize) -> Option<[(Val, Val); 3]> {
+        let n = Self::SUBDIVISIONS;
+        let (i, j, upper) = (cell / (2 * n), (cell / 2) % n, cell % 2 == 1);
+        let limit = if upper { n - 1 } else { n };
+        if i + j >= limit {
+            return None;
+        }
+        let n = Val::from(n);
+        let (i0, j0) = (Val::from(i) / n, Val::from(j) / n);
+        let (i1, j1) = (Val::from(i + 1) / n, Val::from(j + 1) / n);
+        if upper {
+            Some([(i1, j1), (i0, j1), (i1, j0)])
+        } else {
+            Some([(i0, j0), (i1, j0), (i0, j1)])
+        }
+    }
+
+    fn locate_cell(b1: Val, b2: Val) -> usize {
+        let n = Self::SUBDIVISIONS;
+        let (s, t) = (b1 * Val::from(n), b2 * Val::from(n));
+        let i = usize::from(s.trunc()).min(n - 1);
+        let j = usize::from(t.trunc()).min(n - 1);
+        let (i, j, upper) = if i + j >= n {
+            (i, n - 1 - i, false)
+        } else {
+            let upper = (s - Val::from(i)) + (t - Val::from(j)) > Val(1.0);
+            (i, j, upper && i + j < n - 1)
+        };
+        2 * (i * n + j) + usize::from(upper)
+    }
+
+    fn to_point(triangle: &Triangle, b1: Val, b2: Val) -> Point {
+        let v0 = triangle.vertex0();
+        v0 + b1 * (triangle.vertex1() - v0) + b2 * (triangle.vertex2() - v0)
+    }
+
+    fn to_barycentric(triangle: &Triangle, point: Point) -> Option<(Val, Val)> {
+        let v0 = triangle.vertex0();
+        let (e1, e2, d) = (triangle.vertex1() - v0, triangle.vertex2() - v0, point - v0);
+        if !d.is_perpendicular_to(e1.cross(e2)) {
+            return None;
+        }
+        let (d11, d12, d22) = (e1.dot(e1), e1.dot(e2), e2.dot(e2));
+        let (d1, d2) = (d.dot(e1), d.dot(e2));
+        let denom = d11 * d22 - d12 * d12;
+        let b1 = (d22 * d1 - d12 * d2) / denom;
+        let b2 = (d11 * d2 - d12 * d1) / denom;
+        let inside = b1 >= Val(0.0) && b2 >= Val(0.0) && b1 + b2 <= Val(1.0);
+        inside.then_some((b1.max(Val(0.0)), b2.max(Val(0.0))))
+    }
+}
+
+impl PointSampling for EmissivePointSampler {
+    fn id(&self) -> Option<ShapeId> {
+        Some(self.id)
+    }
+
+    fn shape(&self) -> Option<RefDynShape> {
+        Some((&self.shape).into())
+    }
+
+    fn sample_point(&self, rng: &mut dyn RngCore) -> Option<PointSample> {
+        let which = self.index_sampler.sample(rng);
+        let (triangle_index, cell) = (which / Self::CELLS, which % Self::CELLS);
+        let triangle = &self.triangles[triangle_index];
+        let [(a1, a2), (b1, b2), (c1, c2)] = Self::cell_vertices(cell)?;
+
+        let (mut r1, mut r2) = (Val(rng.random()), Val(rng.random()));
+        if r1 + r2 > Val(1.0) {
+            r1 = Val(1.0) - r1;
+            r2 = Val(1.0) - r2;
+        }
+        let u1 = a1 + r1 * (b1 - a1) + r2 * (c1 - a1);
+        let u2 = a2 + r1 * (b2 - a2) + r2 * (c2 - a2);
+        let point = Self::to_point(triangle, u1, u2);
+
+        let pdf = self.probs[which] / self.cell_areas[triangle_index];
+        Some(PointSample::new(
+            point,
+            triangle.normal(point),
+            pdf,
+            self.id,
+        ))
+    }
+
+    fn pdf_point(&self, point: Point, _checked_inside: bool) -> Val {
+        for (index, triangle) in self.triangles.iter().enumerate() {
+            if let Some((b1, b2)) = Self::to_barycentric(triangle, point) {
+                let which = index * Self::CELLS + Self::locate_cell(b1, b2);
+                return self.probs[which] / self.cell_areas[index];
+            }
+        }
+        Val(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::core::Spectrum;
+    use crate::domain::math::geometry::{Normal, SpreadAngle};
+    use crate::domain::ray::event::{RayIntersection, SurfaceSide};
+    use crate::domain::shape::def::ShapeKind;
+    use crate::domain::shape::primitive::Polygon;
+    use crate::domain::texture::primitive::{Checkerboard, Constant};
+
+    use super::*;
+
+    fn striped_panel() -> (Polygon, Emissive) {
+        let polygon = Polygon::new([
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Point::new(Val(4.0), Val(0.0), Val(0.0)),
+            Point::new(Val(4.0), Val(0.5), Val(0.0)),
+            Point::new(Val(0.0), Val(0.5), Val(0.0)),
+        ])
+        .unwrap();
+        let radiance = Checkerboard::new(
+            Constant::new(Spectrum::broadcast(Val(10.0))),
+            Constant::new(Spectrum::broadcast(Val(0.1))),
+            Val(1.0),
+        )
+        .unwrap();
+        (polygon, Emissive::new(radiance, SpreadAngle::hemisphere()))
+    }
+
+    fn is_bright(point: Point) -> bool {
+        usize::from(point.x().trunc()) % 2 == 0
+    }
+
+    fn sampler() -> (EmissivePointSampler, Emissive) {
+        let (polygon, emissive) = striped_panel();
+        let id = ShapeId::new(ShapeKind::Polygon, 0);
+        let sampler = EmissivePointSampler::new(id, (&polygon).into(), &emissive).unwrap();
+        (sampler, emissive)
+    }
+
+    #[test]
+    fn emissive_point_sampler_sample_point_succeeds_favoring_bright_stripes() {
+        let (sampler, _) = sampler();
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = 4000;
+        let bright = (0..n)
+            .filter_map(|_| sampler.sample_point(&mut rng))
+            .filter(|sample| is_bright(sample.point()))
+            .count();
+        assert!(Val::from(bright) / Val::from(n) > Val(0.85));
+    }
+
+    #[test]
+    fn emissive_point_sampler_pdf_point_succeeds_matching_sample() {
+        let (sampler, _) = sampler();
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..200 {
+            let sample = sampler.sample_point(&mut rng).unwrap();
+            assert_eq!(sampler.pdf_point(sample.point(), false), sample.pdf());
+        }
+        let outside = Point::new(Val(5.0), Val(0.25), Val(0.0));
+        assert_eq!(sampler.pdf_point(outside, false), Val(0.0));
+    }
+
+    #[test]
+    fn emissive_point_sampler_sample_point_succeeds_casting_striped_illumination() {
+        let (sampler, emissive) = sampler();
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut irradiance = |x: Val| {
+            let receiver = Point::new(x, Val(0.25), Val(-0.1));
+            let n = 4000;
+            let sum = (0..n)
+                .map(|_| {
+                    let sample = sampler.sample_point(&mut rng).unwrap();
+                    let to_light = sample.point() - receiver;
+                    let (distance2, dir) = (to_light.norm_squared(), to_light / to_light.norm());
+                    let intersection = RayIntersection::new(
+                        Distance::new(Val(1.0)).unwrap(),
+                        sample.point(),
+                        Normal::z_direction(),
+                        SurfaceSide::Back,
+                    );
+                    let radiance = emissive.radiance(&intersection).red();
+                    let cos = dir.z();
+                    radiance * cos * cos / (distance2 * sample.pdf())
+                })
+                .sum::<Val>();
+            sum / Val::from(n)
+        };
+
+        let (bright, dim) = (irradiance(Val(0.5)), irradiance(Val(1.5)));
+        assert!(bright > Val(3.0) * dim);
+        assert!(irradiance(Val(2.5)) > Val(3.0) * irradiance(Val(3.5)));
+    }
+}
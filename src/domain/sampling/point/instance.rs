@@ -4,7 +4,7 @@ use crate::domain::math::geometry::Point;
 use crate::domain::math::numeric::Val;
 use crate::domain::math::transformation::{Sequential, Transform, Transformation};
 use crate::domain::sampling::Sampleable;
-use crate::domain::shape::def::RefDynShape;
+use crate::domain::shape::def::{RefDynShape, Shape};
 use crate::domain::shape::util::{Instance, ShapeId};
 
 use super::{PointSample, PointSampling};
@@ -20,7 +20,7 @@ pub struct InstancePointSampler {
 impl InstancePointSampler {
     pub fn new(id: ShapeId, instance: Instance) -> Self {
         let inv_transformation = instance.transformation().clone().inverse();
-        let sampler = instance.get_point_sampler(id);
+        let sampler = instance.prototype().get_point_sampler(id);
         Self {
             id,
             instance,
@@ -40,14 +40,25 @@ impl PointSampling for InstancePointSampler {
     }
 
     fn sample_point(&self, rng: &mut dyn RngCore) -> Option<PointSample> {
+        let tr = self.instance.transformation();
         (self.sampler.as_ref())
             .and_then(|sampler| sampler.sample_point(rng))
-            .map(|sample| sample.transform(self.instance.transformation()))
+            .map(|sample| {
+                let ratio = tr.calc_area_ratio(sample.normal());
+                sample.transform(tr).scale_pdf(ratio.recip())
+            })
     }
 
     fn pdf_point(&self, point: Point, checked_inside: bool) -> Val {
         self.sampler.as_ref().map_or(Val(0.0), |sampler| {
-            sampler.pdf_point(point.transform(&self.inv_transformation), checked_inside)
+            let point = point.transform(&self.inv_transformation);
+            let pdf = sampler.pdf_point(point, checked_inside);
+            let tr = self.instance.transformation();
+            if pdf == Val(0.0) || tr.is_identity() {
+                pdf
+            } else {
+                pdf / tr.calc_area_ratio(self.instance.prototype().normal(point))
+            }
         })
     }
 }
@@ -1,6 +1,10 @@
 mod aabb;
 mod aggregate;
+mod bilinear_patch;
+mod cylinder;
 mod def;
+mod disk;
+mod emissive;
 mod instance;
 mod polygon;
 mod sphere;
@@ -9,7 +13,11 @@ mod util;
 
 pub use aabb::AabbPointSampler;
 pub use aggregate::AggregatePointSampler;
+pub use bilinear_patch::BilinearPatchPointSampler;
+pub use cylinder::CylinderPointSampler;
 pub use def::{PointSample, PointSampling};
+pub use disk::DiskPointSampler;
+pub use emissive::EmissivePointSampler;
 pub use instance::InstancePointSampler;
 pub use polygon::PolygonPointSampler;
 pub use sphere::SpherePointSampler;
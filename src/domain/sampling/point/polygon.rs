@@ -22,17 +22,16 @@ pub struct PolygonPointSampler {
 
 impl PolygonPointSampler {
     pub fn new(id: ShapeId, polygon: Polygon) -> Self {
-        let triangles = polygon.triangulate();
-
         let area_inv = polygon.area().recip();
+        let triangles = polygon.triangulate();
         let weights = (triangles.iter())
             .map(|triangle| triangle.area() * area_inv)
             .collect::<Vec<_>>();
         let normal = polygon.normal(triangles[0].vertex0());
         let index_sampler = WeightedIndex::new(weights.iter().map(|v| v.0)).unwrap();
 
-        let triangles = (triangles.into_iter())
-            .map(|triangle| TrianglePointSampler::new(id, triangle))
+        let triangles = (triangles.iter())
+            .map(|triangle| TrianglePointSampler::new(id, triangle.clone()))
             .collect::<Vec<_>>();
 
         Self {
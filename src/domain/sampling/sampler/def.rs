@@ -0,0 +1,29 @@
+use rand::prelude::*;
+
+pub trait Sampler: RngCore + Send {
+    fn start_sample(&mut self, pixel: u64, index: u64);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SamplerKind {
+    Random,
+    /// Only the first `SobolSampler::DIMENSIONS` dimensions of a path come from the sequence;
+    /// deeper ones fall back to an independent random stream.
+    Sobol,
+}
+
+impl SamplerKind {
+    pub fn create(&self, seed: u64) -> Box<dyn Sampler> {
+        match self {
+            Self::Random => Box::new(super::RandomSampler::new(seed)),
+            Self::Sobol => Box::new(super::SobolSampler::new(seed)),
+        }
+    }
+}
+
+pub(super) fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
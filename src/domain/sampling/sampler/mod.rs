@@ -0,0 +1,7 @@
+mod def;
+mod random;
+mod sobol;
+
+pub use def::{Sampler, SamplerKind};
+pub use random::RandomSampler;
+pub use sobol::SobolSampler;
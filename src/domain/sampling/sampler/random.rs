@@ -0,0 +1,40 @@
+use rand::prelude::*;
+
+use super::Sampler;
+use super::def::mix;
+
+#[derive(Debug, Clone)]
+pub struct RandomSampler {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl RandomSampler {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Sampler for RandomSampler {
+    fn start_sample(&mut self, pixel: u64, index: u64) {
+        let seed = mix(mix(mix(self.seed) ^ pixel) ^ index);
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+impl RngCore for RandomSampler {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.rng.fill_bytes(dst)
+    }
+}
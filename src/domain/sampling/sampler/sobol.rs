@@ -0,0 +1,175 @@
+use std::sync::LazyLock;
+
+use rand::prelude::*;
+
+use super::Sampler;
+use super::def::mix;
+
+const PRIMITIVE_POLYNOMIALS: [(u32, u32, &[u32]); 7] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+];
+
+static DIRECTIONS: LazyLock<Vec<[u32; 32]>> = LazyLock::new(SobolSampler::build_directions);
+
+/// Dimensions past [`Self::DIMENSIONS`] are padded with a per-sample random stream.
+#[derive(Debug, Clone)]
+pub struct SobolSampler {
+    seed: u64,
+    pixel_seed: u64,
+    index: u32,
+    dimension: usize,
+    padding: StdRng,
+}
+
+impl SobolSampler {
+    pub const DIMENSIONS: usize = PRIMITIVE_POLYNOMIALS.len() + 1;
+
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            pixel_seed: mix(seed),
+            index: 0,
+            dimension: 0,
+            padding: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn build_directions() -> Vec<[u32; 32]> {
+        let mut directions = Vec::with_capacity(Self::DIMENSIONS);
+        directions.push(std::array::from_fn(|i| 1 << (31 - i)));
+        for (degree, coefficients, initial) in PRIMITIVE_POLYNOMIALS {
+            let s = degree as usize;
+            let mut v = [0u32; 32];
+            for i in 0..32 {
+                v[i] = if i < s {
+                    initial[i] << (31 - i)
+                } else {
+                    let mut value = v[i - s] ^ (v[i - s] >> s);
+                    for k in 1..s {
+                        if (coefficients >> (s - 1 - k)) & 1 == 1 {
+                            value ^= v[i - k];
+                        }
+                    }
+                    value
+                };
+            }
+            directions.push(v);
+        }
+        directions
+    }
+
+    pub fn sobol(index: u32, dimension: usize) -> u32 {
+        let directions = &DIRECTIONS[dimension];
+        let mut value = 0;
+        let mut index = index;
+        let mut bit = 0;
+        while index != 0 {
+            if index & 1 == 1 {
+                value ^= directions[bit];
+            }
+            index >>= 1;
+            bit += 1;
+        }
+        value
+    }
+
+    fn scramble(value: u32, seed: u32) -> u32 {
+        let mut x = value.reverse_bits();
+        x = x.wrapping_add(seed);
+        x ^= x.wrapping_mul(0x6c50_b47c);
+        x ^= x.wrapping_mul(0xb82f_1e52);
+        x ^= x.wrapping_mul(0xc7af_e638);
+        x ^= x.wrapping_mul(0x8d22_f6e6);
+        x.reverse_bits()
+    }
+
+    fn next_dimension(&mut self) -> u32 {
+        let dimension = self.dimension;
+        self.dimension += 1;
+        if dimension < Self::DIMENSIONS {
+            let seed = mix(self.pixel_seed ^ dimension as u64) as u32;
+            Self::scramble(Self::sobol(self.index, dimension), seed)
+        } else {
+            self.padding.next_u32()
+        }
+    }
+}
+
+impl Sampler for SobolSampler {
+    fn start_sample(&mut self, pixel: u64, index: u64) {
+        self.pixel_seed = mix(mix(self.seed) ^ pixel);
+        self.index = index as u32;
+        self.dimension = 0;
+        self.padding = StdRng::seed_from_u64(mix(self.pixel_seed ^ index));
+    }
+}
+
+impl RngCore for SobolSampler {
+    fn next_u32(&mut self) -> u32 {
+        self.next_dimension()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let high = u64::from(self.next_dimension()) << 32;
+        let low = u64::from(self.padding.next_u32());
+        high | low
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.padding.fill_bytes(dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::numeric::Val;
+
+    use super::super::RandomSampler;
+    use super::*;
+
+    fn rmse<S: Sampler>(mut sampler: S, num_samples: u64) -> Val {
+        // The integral of `x * y` over the unit square is 1/4.
+        let trials = 64;
+        let mut sum = Val(0.0);
+        for pixel in 0..trials {
+            let mut estimate = Val(0.0);
+            for index in 0..num_samples {
+                sampler.start_sample(pixel, index);
+                let (x, y) = (Val(sampler.random()), Val(sampler.random()));
+                estimate += x * y;
+            }
+            let error = estimate / Val::from(num_samples as usize) - Val(0.25);
+            sum += error * error;
+        }
+        (sum / Val::from(trials as usize)).sqrt()
+    }
+
+    #[test]
+    fn sobol_sampler_sobol_succeeds_matching_known_points() {
+        let points = (0..4)
+            .map(|i| (SobolSampler::sobol(i, 0), SobolSampler::sobol(i, 1)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            vec![
+                (0, 0),
+                (0x8000_0000, 0x8000_0000),
+                (0x4000_0000, 0xc000_0000),
+                (0xc000_0000, 0x4000_0000),
+            ],
+        );
+    }
+
+    #[test]
+    fn sobol_sampler_random_succeeds_reducing_error_over_random_sampler() {
+        let sobol = rmse(SobolSampler::new(1), 64);
+        let random = rmse(RandomSampler::new(1), 64);
+        assert!(sobol < random * Val(0.5));
+    }
+}
@@ -1,11 +1,88 @@
+use getset::CopyGetters;
 use smallvec::SmallVec;
 
+use crate::domain::math::geometry::Point;
 use crate::domain::math::numeric::{DisRange, Val};
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::{RayIntersection, RayIntersectionPart};
 use crate::domain::shape::def::{BoundingBox, Shape};
 use crate::domain::shape::util::{ShapeContainer, ShapeId};
 
+#[derive(Debug, Clone, PartialEq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct BvhConfig {
+    sah_partition: usize,
+    traversal_cost: Val,
+    intersection_cost: Val,
+}
+
+impl BvhConfig {
+    pub fn with_sah_partition(self, sah_partition: usize) -> Self {
+        Self {
+            sah_partition: sah_partition.max(2),
+            ..self
+        }
+    }
+
+    pub fn with_traversal_cost(self, traversal_cost: Val) -> Self {
+        Self {
+            traversal_cost,
+            ..self
+        }
+    }
+
+    pub fn with_intersection_cost(self, intersection_cost: Val) -> Self {
+        Self {
+            intersection_cost,
+            ..self
+        }
+    }
+}
+
+impl Default for BvhConfig {
+    fn default() -> Self {
+        Self {
+            sah_partition: 12,
+            traversal_cost: Val(1.0),
+            intersection_cost: Val(8.0),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct BvhTraversalStats {
+    nodes: usize,
+    tests: usize,
+}
+
+impl BvhTraversalStats {
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.nodes + self.tests
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct BvhStats {
+    depth: usize,
+    leaves: usize,
+    max_leaf_size: usize,
+    bounded: usize,
+    unbounded: usize,
+}
+
+impl BvhStats {
+    pub fn mean_leaf_size(&self) -> Val {
+        if self.leaves == 0 {
+            Val(0.0)
+        } else {
+            Val::from(self.bounded) / Val::from(self.leaves)
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Bvh<SI>
 where
@@ -19,21 +96,52 @@ impl<SI> Bvh<SI>
 where
     SI: Eq + Copy + Into<ShapeId>,
 {
-    const SAH_PARTITION: usize = 12;
-    const TRAVERSAL_COST: Val = Val(1.0);
-    const INTERSECTION_COST: Val = Val(8.0);
-
-    pub fn new(bboxes: Vec<(SI, BoundingBox)>, unboundeds: Vec<SI>) -> Self {
+    pub fn new(config: &BvhConfig, bboxes: Vec<(SI, BoundingBox)>, unboundeds: Vec<SI>) -> Self {
         let mut nodes = Vec::with_capacity(bboxes.len() * 2);
 
         if !bboxes.is_empty() {
-            Self::build(&mut nodes, bboxes);
+            Self::build(config, &mut nodes, bboxes);
         }
 
         Self { nodes, unboundeds }
     }
 
-    fn build(nodes: &mut Vec<BvhNode<SI>>, bboxes: Vec<(SI, BoundingBox)>) -> usize {
+    pub fn bounding_box(&self) -> Option<&BoundingBox> {
+        self.nodes.first().map(|node| node.bounding_box())
+    }
+
+    pub fn stats(&self) -> BvhStats {
+        let mut stats = BvhStats {
+            unbounded: self.unboundeds.len(),
+            ..BvhStats::default()
+        };
+        if self.nodes.is_empty() {
+            return stats;
+        }
+
+        let mut stack = vec![(0, 1)];
+        while let Some((current, depth)) = stack.pop() {
+            stats.depth = stats.depth.max(depth);
+            let size = match &self.nodes[current] {
+                BvhNode::Internal { right, .. } => {
+                    stack.extend([(current + 1, depth + 1), (*right, depth + 1)]);
+                    continue;
+                }
+                BvhNode::Leaf { .. } => 1,
+                BvhNode::ClusterLeaf { ids, .. } => ids.len(),
+            };
+            stats.leaves += 1;
+            stats.bounded += size;
+            stats.max_leaf_size = stats.max_leaf_size.max(size);
+        }
+        stats
+    }
+
+    fn build(
+        config: &BvhConfig,
+        nodes: &mut Vec<BvhNode<SI>>,
+        bboxes: Vec<(SI, BoundingBox)>,
+    ) -> usize {
         if bboxes.len() == 1 {
             let (id, bbox) = bboxes
                 .into_iter()
@@ -47,18 +155,18 @@ where
         let node_bbox = Self::merge_bboxes(bboxes.iter().map(|bbox| &bbox.1))
             .expect("bboxes should have at least one element");
         let axis = Self::select_bbox_partition_axis(&node_bbox);
-        let mut partition = Self::partition_bboxes(axis, &node_bbox, bboxes);
+        let mut partition = Self::partition_bboxes(config, axis, &node_bbox, bboxes);
 
-        if let Some(mid) =
-            Self::calc_split_point(&partition, bbox_num, node_bbox.surface_area().value())
+        let total_surface_area = node_bbox.surface_area().value();
+        if let Some(mid) = Self::calc_split_point(config, &partition, bbox_num, total_surface_area)
         {
             nodes.push(BvhNode::internal(node_bbox));
             let node_id = nodes.len() - 1;
 
             let right_bboxes = partition.drain(mid..).flat_map(|t| t.items).collect();
             let left_bboxes = partition.into_iter().flat_map(|t| t.items).collect();
-            let _left = Self::build(nodes, left_bboxes);
-            let right = Self::build(nodes, right_bboxes);
+            let _left = Self::build(config, nodes, left_bboxes);
+            let right = Self::build(config, nodes, right_bboxes);
 
             let BvhNode::Internal { right: r, .. } = &mut nodes[node_id] else {
                 unreachable!("nodes[node_id] was constructed as BvhNode::Internal")
@@ -86,18 +194,19 @@ where
     }
 
     fn partition_bboxes(
+        config: &BvhConfig,
         axis: usize,
         node_bbox: &BoundingBox,
         bboxes: Vec<(SI, BoundingBox)>,
     ) -> Vec<PartitionBucket<SI>> {
         let mut buckets = Vec::new();
-        buckets.resize(Self::SAH_PARTITION, PartitionBucket::new());
+        buckets.resize(config.sah_partition, PartitionBucket::new());
         let range = (node_bbox.min().axis(axis), node_bbox.max().axis(axis));
-        let bucket_span = (range.1 - range.0) / Self::SAH_PARTITION.into();
+        let bucket_span = (range.1 - range.0) / config.sah_partition.into();
 
         for (id, bbox) in bboxes {
             let fraction = (bbox.centroid().axis(axis) - range.0) / bucket_span;
-            let index = usize::from(fraction).clamp(0, Self::SAH_PARTITION - 1);
+            let index = usize::from(fraction).clamp(0, config.sah_partition - 1);
             buckets[index].items.push((id, bbox));
         }
 
@@ -109,17 +218,19 @@ where
     }
 
     fn calc_split_point(
+        config: &BvhConfig,
         partition: &[PartitionBucket<SI>],
         bbox_num: usize,
         total_surface_area: Val,
     ) -> Option<usize> {
-        assert_eq!(partition.len(), Self::SAH_PARTITION);
-        let mut cost = [Self::TRAVERSAL_COST; Bvh::<ShapeId>::SAH_PARTITION - 1];
+        let sah_partition = config.sah_partition;
+        assert_eq!(partition.len(), sah_partition);
+        let mut cost = vec![config.traversal_cost; sah_partition - 1];
 
         let mut merged_bbox: Option<BoundingBox> = None;
         let mut num = 0;
-        let mut num_pre = [0; Bvh::<ShapeId>::SAH_PARTITION - 1];
-        for i in 0..Self::SAH_PARTITION - 1 {
+        let mut num_pre = vec![0; sah_partition - 1];
+        for i in 0..sah_partition - 1 {
             num += partition[i].items.len();
             num_pre[i] = num;
             merged_bbox = merged_bbox
@@ -128,13 +239,14 @@ where
             let surface_area = merged_bbox
                 .as_ref()
                 .map_or(Val(0.0), |b| b.surface_area().value());
-            cost[i] += Self::INTERSECTION_COST * Val::from(num) * surface_area / total_surface_area;
+            cost[i] +=
+                config.intersection_cost * Val::from(num) * surface_area / total_surface_area;
         }
 
         num = 0;
         merged_bbox = None;
-        let mut num_suf = [0; Bvh::<ShapeId>::SAH_PARTITION - 1];
-        for i in (0..Self::SAH_PARTITION - 1).rev() {
+        let mut num_suf = vec![0; sah_partition - 1];
+        for i in (0..sah_partition - 1).rev() {
             num += partition[i + 1].items.len();
             num_suf[i] = num;
             merged_bbox = merged_bbox
@@ -143,11 +255,12 @@ where
             let surface_area = merged_bbox
                 .as_ref()
                 .map_or(Val(0.0), |b| b.surface_area().value());
-            cost[i] += Self::INTERSECTION_COST * Val::from(num) * surface_area / total_surface_area;
+            cost[i] +=
+                config.intersection_cost * Val::from(num) * surface_area / total_surface_area;
         }
 
         let mut res = 0;
-        for i in 1..Self::SAH_PARTITION - 1 {
+        for i in 1..sah_partition - 1 {
             if cost[i] < cost[res] {
                 res = i
             } else if cost[i] == cost[res] {
@@ -160,7 +273,7 @@ where
             }
         }
 
-        let leaf_cost = Val::from(bbox_num) * Self::TRAVERSAL_COST;
+        let leaf_cost = Val::from(bbox_num) * config.traversal_cost;
         if num_pre[res] != 0 && num_suf[res] != 0 && cost[res] < leaf_cost {
             Some(res + 1)
         } else {
@@ -186,113 +299,183 @@ where
     where
         SC: ShapeContainer,
     {
-        self.search_unboundeds(ray, range, shapes)
+        self.search_with_stats(ray, range, shapes, &mut BvhTraversalStats::default())
+    }
+
+    pub fn search_with_stats<SC>(
+        &self,
+        ray: &Ray,
+        range: DisRange,
+        shapes: &SC,
+        stats: &mut BvhTraversalStats,
+    ) -> Option<(RayIntersection, SI)>
+    where
+        SC: ShapeContainer,
+    {
+        self.search_where(ray, range, shapes, &|_| true, stats)
+    }
+
+    pub fn search_filtered<SC, F>(
+        &self,
+        ray: &Ray,
+        range: DisRange,
+        shapes: &SC,
+        filter: F,
+    ) -> Option<(RayIntersection, SI)>
+    where
+        SC: ShapeContainer,
+        F: Fn(SI) -> bool,
+    {
+        let mut stats = BvhTraversalStats::default();
+        self.search_where(ray, range, shapes, &filter, &mut stats)
+    }
+
+    fn search_where<SC, F>(
+        &self,
+        ray: &Ray,
+        range: DisRange,
+        shapes: &SC,
+        filter: &F,
+        stats: &mut BvhTraversalStats,
+    ) -> Option<(RayIntersection, SI)>
+    where
+        SC: ShapeContainer,
+        F: Fn(SI) -> bool,
+    {
+        self.search_unboundeds(ray, range, shapes, filter, stats)
             .map(|res| {
                 let range = range.shrink_end(res.0.distance());
-                self.search_boundeds(ray, range, shapes).unwrap_or(res)
+                self.search_boundeds(ray, range, shapes, filter, stats)
+                    .unwrap_or(res)
             })
-            .or_else(|| self.search_boundeds(ray, range, shapes))
+            .or_else(|| self.search_boundeds(ray, range, shapes, filter, stats))
             .map(|(part, id)| {
                 let shape = shapes.get_shape(id.into()).unwrap();
                 (shape.complete_part(part), id)
             })
     }
 
-    fn search_boundeds<'a, SC>(
+    fn search_boundeds<'a, SC, F>(
         &self,
         ray: &'a Ray,
         range: DisRange,
         shapes: &SC,
+        filter: &F,
+        stats: &mut BvhTraversalStats,
     ) -> Option<(RayIntersectionPart<'a>, SI)>
     where
         SC: ShapeContainer,
+        F: Fn(SI) -> bool,
     {
         if !self.nodes.is_empty() {
+            stats.nodes += 1;
             if self.nodes[0].bounding_box().try_hit(ray, range).is_some() {
-                return self.search_impl(0, ray, range, shapes);
+                return self.search_impl(ray, range, shapes, filter, stats);
             }
         }
         None
     }
 
-    fn search_impl<'a, SC>(
+    fn search_impl<'a, SC, F>(
         &self,
-        current: usize,
         ray: &'a Ray,
-        range: DisRange,
+        mut range: DisRange,
         shapes: &SC,
+        filter: &F,
+        stats: &mut BvhTraversalStats,
     ) -> Option<(RayIntersectionPart<'a>, SI)>
     where
         SC: ShapeContainer,
+        F: Fn(SI) -> bool,
     {
-        assert!(current < self.nodes.len());
-
-        match &self.nodes[current] {
-            BvhNode::Internal { right, .. } => {
-                let (left, right) = (current + 1, *right);
-                let hit_left = self.nodes[left].bounding_box().try_hit(ray, range);
-                let hit_right = self.nodes[right].bounding_box().try_hit(ray, range);
-
-                match (hit_left, hit_right) {
-                    (Some(_), None) => self.search_impl(left, ray, range, shapes),
-                    (None, Some(_)) => self.search_impl(right, ray, range, shapes),
-                    (Some(dis1), Some(dis2)) => {
-                        if dis1 <= dis2 {
-                            self.search_impl(left, ray, range, shapes)
-                                .map(|res| {
-                                    let range = range.shrink_end(res.0.distance());
-                                    self.search_impl(right, ray, range, shapes).unwrap_or(res)
-                                })
-                                .or_else(|| self.search_impl(right, ray, range, shapes))
-                        } else {
-                            self.search_impl(right, ray, range, shapes)
-                                .map(|res| {
-                                    let range = range.shrink_end(res.0.distance());
-                                    self.search_impl(left, ray, range, shapes).unwrap_or(res)
-                                })
-                                .or_else(|| self.search_impl(left, ray, range, shapes))
+        let mut closest: Option<(RayIntersectionPart<'a>, SI)> = None;
+        let mut stack: SmallVec<[usize; 64]> = SmallVec::new();
+        stack.push(0);
+
+        while let Some(current) = stack.pop() {
+            if closest.is_some() {
+                let bbox = self.nodes[current].bounding_box();
+                if bbox.try_hit(ray, range).is_none() {
+                    continue;
+                }
+            }
+
+            let res = match &self.nodes[current] {
+                BvhNode::Internal { right, .. } => {
+                    let (left, right) = (current + 1, *right);
+                    let hit_left = self.nodes[left].bounding_box().try_hit(ray, range);
+                    let hit_right = self.nodes[right].bounding_box().try_hit(ray, range);
+                    stats.nodes += 2;
+
+                    match (hit_left, hit_right) {
+                        (Some(_), None) => stack.push(left),
+                        (None, Some(_)) => stack.push(right),
+                        (Some(dis1), Some(dis2)) => {
+                            if dis1 <= dis2 {
+                                stack.extend([right, left]);
+                            } else {
+                                stack.extend([left, right]);
+                            }
                         }
+                        (None, None) => {}
                     }
-                    (None, None) => None,
+                    None
                 }
-            }
-            BvhNode::Leaf { id, .. } => {
-                let shape = shapes.get_shape((*id).into()).unwrap();
-                shape.hit_part(ray, range).map(|res| (res, *id))
-            }
-            BvhNode::ClusterLeaf { ids, .. } => {
-                let ids = ids.iter();
-                self.intersect_for_each(ray, range, ids, shapes)
+                BvhNode::Leaf { id, .. } if filter(*id) => {
+                    stats.tests += 1;
+                    let shape = shapes.get_shape((*id).into()).unwrap();
+                    shape.hit_part(ray, range).map(|res| (res, *id))
+                }
+                BvhNode::Leaf { .. } => None,
+                BvhNode::ClusterLeaf { ids, .. } => {
+                    let ids = ids.iter();
+                    self.intersect_for_each(ray, range, ids, shapes, filter, stats)
+                }
+            };
+
+            if let Some(res) = res {
+                range = range.shrink_end(res.0.distance());
+                closest = Some(res);
             }
         }
+
+        closest
     }
 
-    fn search_unboundeds<'a, SC>(
+    fn search_unboundeds<'a, SC, F>(
         &self,
         ray: &'a Ray,
         range: DisRange,
         shapes: &SC,
+        filter: &F,
+        stats: &mut BvhTraversalStats,
     ) -> Option<(RayIntersectionPart<'a>, SI)>
     where
         SC: ShapeContainer,
+        F: Fn(SI) -> bool,
     {
-        self.intersect_for_each(ray, range, self.unboundeds.iter(), shapes)
+        let ids = self.unboundeds.iter();
+        self.intersect_for_each(ray, range, ids, shapes, filter, stats)
     }
 
-    fn intersect_for_each<'a, 'b, SC, I>(
+    fn intersect_for_each<'a, 'b, SC, I, F>(
         &self,
         ray: &'b Ray,
         mut range: DisRange,
         ids: I,
         shapes: &SC,
+        filter: &F,
+        stats: &mut BvhTraversalStats,
     ) -> Option<(RayIntersectionPart<'b>, SI)>
     where
         I: Iterator<Item = &'a SI>,
         SC: ShapeContainer,
+        F: Fn(SI) -> bool,
         SI: 'a,
     {
         let mut closet: Option<(RayIntersectionPart, SI)> = None;
-        for id in ids {
+        for id in ids.filter(|id| filter(**id)) {
+            stats.tests += 1;
             let shape = shapes.get_shape((*id).into()).unwrap();
             if let Some((closet, _)) = &closet {
                 range = range.shrink_end(closet.distance());
@@ -304,6 +487,32 @@ where
         closet
     }
 
+    pub fn search_containing(&self, point: Point) -> Vec<SI> {
+        let mut res = Vec::new();
+        let mut stack: SmallVec<[usize; 64]> = SmallVec::new();
+        if self
+            .nodes
+            .first()
+            .is_some_and(|node| node.bounding_box().contains(point))
+        {
+            stack.push(0);
+        }
+        while let Some(current) = stack.pop() {
+            match &self.nodes[current] {
+                BvhNode::Internal { right, .. } => {
+                    let children = [current + 1, *right];
+                    let children = children.into_iter();
+                    stack
+                        .extend(children.filter(|c| self.nodes[*c].bounding_box().contains(point)));
+                }
+                BvhNode::Leaf { id, .. } => res.push(*id),
+                BvhNode::ClusterLeaf { ids, .. } => res.extend(ids.iter().copied()),
+            }
+        }
+        res.extend(self.unboundeds.iter().copied());
+        res
+    }
+
     pub fn search_all<SC>(
         &self,
         ray: &Ray,
@@ -316,7 +525,7 @@ where
         let mut res = Vec::new();
         if !self.nodes.is_empty() {
             if self.nodes[0].bounding_box().try_hit(ray, range).is_some() {
-                self.search_all_impl(0, ray, range, shapes, &mut res);
+                self.search_all_impl(ray, range, shapes, &mut res);
             }
         }
         self.intersect_all_for_each(ray, range, self.unboundeds.iter(), shapes, &mut res);
@@ -325,7 +534,6 @@ where
 
     fn search_all_impl<SC>(
         &self,
-        current: usize,
         ray: &Ray,
         range: DisRange,
         shapes: &SC,
@@ -333,29 +541,32 @@ where
     ) where
         SC: ShapeContainer,
     {
-        assert!(current < self.nodes.len());
+        let mut stack: SmallVec<[usize; 64]> = SmallVec::new();
+        stack.push(0);
 
-        match &self.nodes[current] {
-            BvhNode::Internal { right, .. } => {
-                let (left, right) = (current + 1, *right);
+        while let Some(current) = stack.pop() {
+            match &self.nodes[current] {
+                BvhNode::Internal { right, .. } => {
+                    let (left, right) = (current + 1, *right);
 
-                let bbox_left = self.nodes[left].bounding_box();
-                if bbox_left.try_hit(ray, range).is_some() {
-                    self.search_all_impl(left, ray, range, shapes, res);
-                }
+                    let bbox_right = self.nodes[right].bounding_box();
+                    if bbox_right.try_hit(ray, range).is_some() {
+                        stack.push(right);
+                    }
 
-                let bbox_right = self.nodes[right].bounding_box();
-                if bbox_right.try_hit(ray, range).is_some() {
-                    self.search_all_impl(right, ray, range, shapes, res);
+                    let bbox_left = self.nodes[left].bounding_box();
+                    if bbox_left.try_hit(ray, range).is_some() {
+                        stack.push(left);
+                    }
+                }
+                BvhNode::Leaf { id, .. } => {
+                    let shape = shapes.get_shape((*id).into()).unwrap();
+                    let intersections = shape.hit_all(ray, range).into_iter().map(|i| (i, *id));
+                    res.extend(intersections);
+                }
+                BvhNode::ClusterLeaf { ids, .. } => {
+                    self.intersect_all_for_each(ray, range, ids.iter(), shapes, res);
                 }
-            }
-            BvhNode::Leaf { id, .. } => {
-                let shape = shapes.get_shape((*id).into()).unwrap();
-                let intersections = shape.hit_all(ray, range).into_iter().map(|i| (i, *id));
-                res.extend(intersections);
-            }
-            BvhNode::ClusterLeaf { ids, .. } => {
-                self.intersect_all_for_each(ray, range, ids.iter(), shapes, res);
             }
         }
     }
@@ -465,12 +676,14 @@ where
 
 #[cfg(test)]
 mod tests {
+    use rand::prelude::*;
+
     use crate::domain::math::algebra::Vector;
     use crate::domain::math::geometry::{Direction, Distance, Point};
     use crate::domain::math::numeric::Val;
     use crate::domain::scene::pool::ShapePool;
     use crate::domain::shape::def::Shape;
-    use crate::domain::shape::primitive::{Polygon, Sphere, Triangle};
+    use crate::domain::shape::primitive::{Aabb, Polygon, Sphere, Triangle};
 
     use super::*;
 
@@ -518,6 +731,145 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bvh_search_succeeds_matching_brute_force_on_test_scene() {
+        let (shapes, bvh) = get_test_bvh();
+        let ids = (bvh.nodes.iter())
+            .flat_map(|node| match node {
+                BvhNode::Leaf { id, .. } => vec![*id],
+                BvhNode::ClusterLeaf { ids, .. } => ids.to_vec(),
+                BvhNode::Internal { .. } => Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 3);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..256 {
+            let start = Point::new(Val(-3.0), Val(0.0), Val(0.0));
+            let dir = Direction::random(&mut rng);
+            let ray = Ray::new(start, dir);
+
+            let expected = bvh
+                .intersect_for_each(
+                    &ray,
+                    DisRange::positive(),
+                    ids.iter(),
+                    &shapes,
+                    &|_| true,
+                    &mut BvhTraversalStats::default(),
+                )
+                .map(|(part, _)| part.distance());
+            let actual = bvh
+                .search(&ray, DisRange::positive(), &shapes)
+                .map(|(intersection, _)| intersection.distance());
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn bvh_search_succeeds_traversing_many_tiny_boxes() {
+        let mut shapes = ShapePool::default();
+        let mut bboxes = Vec::new();
+        for i in 0..100_000 {
+            let x = Val::from(i % 400) * Val(0.01);
+            let y = Val::from(i / 400) * Val(0.01);
+            let aabb = Aabb::new(
+                Point::new(x, y, Val(0.0)),
+                Point::new(x + Val(0.001), y + Val(0.001), Val(0.001)),
+            );
+            let bbox = aabb.bounding_box().unwrap();
+            bboxes.push((shapes.add_shape(aabb.into()), bbox));
+        }
+        let bvh = Bvh::new(&BvhConfig::default(), bboxes, Vec::new());
+
+        let ray = Ray::new(
+            Point::new(Val(1.0005), Val(1.0005), Val(-1.0)),
+            Direction::z_direction(),
+        );
+        let (intersection, _) = bvh.search(&ray, DisRange::positive(), &shapes).unwrap();
+        assert_eq!(intersection.distance(), Distance::new(Val(1.0)).unwrap());
+
+        let all = bvh.search_all(&ray, DisRange::positive(), &shapes);
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn bvh_new_succeeds_returning_same_closest_hits_with_more_sah_buckets() {
+        let mut shapes = ShapePool::default();
+        let mut bboxes = Vec::new();
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..200 {
+            let center = Point::new(
+                Val(rng.random_range(-5.0..5.0)),
+                Val(rng.random_range(-5.0..5.0)),
+                Val(rng.random_range(-5.0..5.0)),
+            );
+            let sphere = Sphere::new(center, Val(rng.random_range(0.05..0.5))).unwrap();
+            let bbox = sphere.bounding_box().unwrap();
+            bboxes.push((shapes.add_shape(sphere.into()), bbox));
+        }
+        let default = Bvh::new(&BvhConfig::default(), bboxes.clone(), Vec::new());
+        let config = BvhConfig::default().with_sah_partition(48);
+        let fine = Bvh::new(&config, bboxes, Vec::new());
+
+        for _ in 0..256 {
+            let ray = Ray::new(Point::default(), Direction::random(&mut rng));
+            let expected = default.search(&ray, DisRange::positive(), &shapes);
+            let actual = fine.search(&ray, DisRange::positive(), &shapes);
+            assert_eq!(actual.map(|r| r.1), expected.map(|r| r.1));
+        }
+    }
+
+    #[test]
+    fn bvh_search_with_stats_succeeds_counting_more_work_in_dense_geometry() {
+        let mut shapes = ShapePool::default();
+        let mut bboxes = Vec::new();
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..1000 {
+            let center = Point::new(
+                Val(rng.random_range(-1.0..1.0)),
+                Val(rng.random_range(-1.0..1.0)),
+                Val(rng.random_range(-1.0..1.0)),
+            );
+            let sphere = Sphere::new(center, Val(0.2)).unwrap();
+            let bbox = sphere.bounding_box().unwrap();
+            bboxes.push((shapes.add_shape(sphere.into()), bbox));
+        }
+        let bvh = Bvh::new(&BvhConfig::default(), bboxes, Vec::new());
+
+        let count = |ray: Ray| {
+            let mut stats = BvhTraversalStats::default();
+            bvh.search_with_stats(&ray, DisRange::positive(), &shapes, &mut stats);
+            stats
+        };
+        let empty = count(Ray::new(
+            Point::new(Val(0.0), Val(5.0), Val(-5.0)),
+            Direction::z_direction(),
+        ));
+        let dense = count(Ray::new(
+            Point::new(Val(0.0), Val(0.0), Val(-5.0)),
+            Direction::z_direction(),
+        ));
+        assert_eq!(empty.total(), 1);
+        assert!(dense.total() > 10, "{dense:?}");
+        assert!(dense.tests() > 0 && dense.nodes() > 0);
+    }
+
+    #[test]
+    fn bvh_stats_succeeds() {
+        let (_, bvh) = get_test_bvh();
+        let stats = bvh.stats();
+        assert_eq!(stats.bounded(), 3);
+        assert_eq!(stats.unbounded(), 0);
+        assert!(stats.leaves() >= 1 && stats.leaves() <= 3);
+        assert!(stats.depth() >= 1 && stats.depth() <= 3);
+        assert!(stats.max_leaf_size() <= 3);
+
+        let empty = Bvh::<ShapeId>::new(&BvhConfig::default(), Vec::new(), Vec::new());
+        assert_eq!(empty.stats(), BvhStats::default());
+        assert_eq!(empty.bounding_box(), None);
+    }
+
     fn get_test_bvh() -> (ShapePool, Bvh<ShapeId>) {
         let mut shapes = ShapePool::default();
         let mut nodes = Vec::new();
@@ -545,7 +897,7 @@ mod tests {
         let bbox_polygon = polygon.bounding_box().unwrap();
         nodes.push((shapes.add_shape(polygon.into()), bbox_polygon));
 
-        let bvh = Bvh::new(nodes, Vec::new());
+        let bvh = Bvh::new(&BvhConfig::default(), nodes, Vec::new());
         (shapes, bvh)
     }
 }
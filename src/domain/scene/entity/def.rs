@@ -1,5 +1,10 @@
 use std::fmt::Debug;
+use std::ops::BitOr;
 
+use getset::{CopyGetters, Getters};
+
+use crate::domain::light::def::DynLight;
+use crate::domain::light::primitive::Portal;
 use crate::domain::material::def::{DynMaterial, MaterialKind};
 use crate::domain::material::util::{MaterialContainer, MaterialId};
 use crate::domain::math::numeric::DisRange;
@@ -8,7 +13,8 @@ use crate::domain::ray::event::RayIntersection;
 use crate::domain::sampling::light::LightSampling;
 use crate::domain::sampling::photon::PhotonSampling;
 use crate::domain::sampling::point::PointSampling;
-use crate::domain::shape::def::{DynShape, ShapeKind};
+use crate::domain::scene::bvh::{BvhStats, BvhTraversalStats};
+use crate::domain::shape::def::{BoundingBox, DynShape, Shape, ShapeKind};
 use crate::domain::shape::util::{ShapeConstructor, ShapeContainer, ShapeId};
 
 pub trait EntityScene: Send + Sync {
@@ -20,8 +26,35 @@ pub trait EntityScene: Send + Sync {
 
     fn get_emitters(&self) -> &dyn PhotonSampling;
 
+    fn get_point_lights(&self) -> &[Box<dyn LightSampling>];
+
+    fn get_portals(&self) -> &[Portal];
+
+    fn bounding_box(&self) -> Option<BoundingBox>;
+
+    fn stats(&self) -> SceneStats;
+
+    fn entities(&self) -> Box<dyn Iterator<Item = EntityDescriptor> + '_> {
+        let entities = self.get_entities();
+        Box::new((entities.get_ids().iter()).map(move |id| {
+            let bounding_box = (entities.get_shape(id.shape_id()))
+                .expect("entity should refer to a registered shape")
+                .bounding_box();
+            EntityDescriptor::new(*id, bounding_box)
+        }))
+    }
+
     fn find_intersection(&self, ray: &Ray, range: DisRange) -> Option<(RayIntersection, EntityId)>;
 
+    fn find_visible_intersection(
+        &self,
+        ray: &Ray,
+        range: DisRange,
+        visibility: Visibility,
+    ) -> Option<(RayIntersection, EntityId)>;
+
+    fn count_traversal(&self, ray: &Ray, range: DisRange) -> BvhTraversalStats;
+
     fn test_intersection(
         &self,
         ray: &Ray,
@@ -41,7 +74,16 @@ pub trait EntityScene: Send + Sync {
 }
 
 pub trait EntitySceneBuilder: Send + Sync {
-    fn add_dyn(&mut self, shape: DynShape, material: DynMaterial);
+    fn add_dyn(&mut self, shape: DynShape, material: DynMaterial) {
+        self.add_dyn_with_visibility(shape, material, Visibility::ALL);
+    }
+
+    fn add_dyn_with_visibility(
+        &mut self,
+        shape: DynShape,
+        material: DynMaterial,
+        visibility: Visibility,
+    );
 
     fn add_constructor_dyn(
         &mut self,
@@ -49,7 +91,15 @@ pub trait EntitySceneBuilder: Send + Sync {
         material: DynMaterial,
     );
 
-    fn build(self: Box<Self>) -> Box<dyn EntityScene>;
+    fn add_light_dyn(&mut self, light: DynLight);
+
+    fn add_portal(&mut self, portal: Portal);
+
+    fn build(self: Box<Self>) -> Box<dyn EntityScene> {
+        self.build_with_warnings().0
+    }
+
+    fn build_with_warnings(self: Box<Self>) -> (Box<dyn EntityScene>, Vec<SceneWarning>);
 }
 
 pub trait TypedEntitySceneBuilder: EntitySceneBuilder {
@@ -61,6 +111,14 @@ pub trait TypedEntitySceneBuilder: EntitySceneBuilder {
         self.add_dyn(shape.into(), material.into());
     }
 
+    fn add_with_visibility<S, M>(&mut self, shape: S, material: M, visibility: Visibility)
+    where
+        S: Into<DynShape>,
+        M: Into<DynMaterial>,
+    {
+        self.add_dyn_with_visibility(shape.into(), material.into(), visibility);
+    }
+
     fn add_constructor<C, M>(&mut self, constructor: C, material: M)
     where
         C: ShapeConstructor,
@@ -68,6 +126,13 @@ pub trait TypedEntitySceneBuilder: EntitySceneBuilder {
     {
         self.add_constructor_dyn(Box::new(constructor), material.into());
     }
+
+    fn add_light<L>(&mut self, light: L)
+    where
+        L: Into<DynLight>,
+    {
+        self.add_light_dyn(light.into());
+    }
 }
 
 impl<T> TypedEntitySceneBuilder for T where T: EntitySceneBuilder + ?Sized {}
@@ -111,8 +176,99 @@ impl From<EntityId> for MaterialId {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Visibility(u8);
+
+impl Visibility {
+    pub const NONE: Self = Self(0);
+    pub const PRIMARY: Self = Self(1);
+    pub const SHADOW: Self = Self(1 << 1);
+    pub const INDIRECT: Self = Self(1 << 2);
+    pub const ALL: Self = Self(Self::PRIMARY.0 | Self::SHADOW.0 | Self::INDIRECT.0);
+
+    #[inline]
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[inline]
+    pub fn without(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+}
+
+impl BitOr for Visibility {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl Default for Visibility {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, CopyGetters, Getters)]
+pub struct EntityDescriptor {
+    #[getset(get_copy = "pub")]
+    id: EntityId,
+    #[getset(get = "pub")]
+    bounding_box: Option<BoundingBox>,
+}
+
+impl EntityDescriptor {
+    pub fn new(id: EntityId, bounding_box: Option<BoundingBox>) -> Self {
+        Self { id, bounding_box }
+    }
+
+    pub fn shape_kind(&self) -> ShapeKind {
+        self.id.shape_id().kind()
+    }
+
+    pub fn material_kind(&self) -> MaterialKind {
+        self.id.material_id().kind()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum SceneWarning {
+    DegenerateShape {
+        kind: ShapeKind,
+        bounding_box: Option<BoundingBox>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct SceneStats {
+    entities: usize,
+    triangles: usize,
+    lights: usize,
+    bvh: BvhStats,
+}
+
+impl SceneStats {
+    pub fn new(entities: usize, triangles: usize, lights: usize, bvh: BvhStats) -> Self {
+        Self {
+            entities,
+            triangles,
+            lights,
+            bvh,
+        }
+    }
+}
+
 pub trait EntityContainer: ShapeContainer + MaterialContainer {
     fn register_id(&mut self, id: EntityId);
 
     fn get_ids(&self) -> &[EntityId];
+
+    fn set_visibility(&mut self, id: EntityId, visibility: Visibility);
+
+    fn get_visibility(&self, id: EntityId) -> Visibility;
 }
@@ -2,6 +2,7 @@ mod def;
 mod scene;
 
 pub use def::{
-    EntityContainer, EntityId, EntityScene, EntitySceneBuilder, TypedEntitySceneBuilder,
+    EntityContainer, EntityDescriptor, EntityId, EntityScene, EntitySceneBuilder, SceneStats,
+    SceneWarning, TypedEntitySceneBuilder, Visibility,
 };
 pub use scene::{BvhEntityScene, BvhEntitySceneBuilder};
@@ -1,35 +1,115 @@
+use crate::domain::light::def::{DynLight, Light};
+use crate::domain::light::primitive::Portal;
 use crate::domain::material::def::{DynMaterial, MaterialKind, RefDynMaterial};
 use crate::domain::material::primitive::Emissive;
 use crate::domain::material::util::MaterialContainer;
+use crate::domain::math::geometry::Point;
 use crate::domain::math::numeric::{DisRange, Val};
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::RayIntersection;
 use crate::domain::sampling::Sampleable;
-use crate::domain::sampling::light::{AggregateLightSampler, EmptyLightSampler, LightSampling};
-use crate::domain::sampling::photon::{AggregatePhotonSampler, EmptyPhotonSampler, PhotonSampling};
-use crate::domain::sampling::point::{AggregatePointSampler, EmptyPointSampler, PointSampling};
-use crate::domain::scene::bvh::Bvh;
+use crate::domain::sampling::light::{
+    AggregateLightSampler, EmptyLightSampler, LightSamplerAdapter, LightSampling,
+};
+use crate::domain::sampling::photon::{
+    AggregatePhotonSampler, EmptyPhotonSampler, PhotonSamplerAdapter, PhotonSampling,
+};
+use crate::domain::sampling::point::{
+    AggregatePointSampler, EmissivePointSampler, EmptyPointSampler, PointSampling,
+};
+use crate::domain::scene::bvh::{Bvh, BvhConfig, BvhTraversalStats};
 use crate::domain::scene::pool::EntityPool;
-use crate::domain::shape::def::{DynShape, RefDynShape, Shape};
+use crate::domain::shape::def::{BoundingBox, DynShape, RefDynShape, Shape, ShapeKind};
 use crate::domain::shape::util::{ShapeConstructor, ShapeContainer, ShapeId};
 
-use super::{EntityContainer, EntityId, EntityScene, EntitySceneBuilder};
+use super::{
+    EntityContainer, EntityId, EntityScene, EntitySceneBuilder, SceneStats, SceneWarning,
+    Visibility,
+};
 
 #[derive(Debug)]
 pub struct BvhEntitySceneBuilder {
     entities: Box<EntityPool>,
     light_surfaces: Vec<Box<dyn PointSampling>>,
-    lights: Vec<Box<dyn LightSampling>>,
+    lights: Vec<(Box<dyn LightSampling>, Val)>,
     emitters: Vec<Box<dyn PhotonSampling>>,
+    point_lights: Vec<Box<dyn LightSampling>>,
+    analytic_lights: Vec<DynLight>,
+    portals: Vec<Portal>,
+    bvh_config: BvhConfig,
+    warnings: Vec<SceneWarning>,
 }
 
 impl BvhEntitySceneBuilder {
+    const MIN_AREA_RATIO: Val = Val(1e-6);
+
+    const MIN_RELATIVE_EXTENT: Val = Val(1e3 * f64::EPSILON);
+
     pub fn new() -> Box<Self> {
         Box::new(Self {
             entities: Box::new(EntityPool::new()),
             light_surfaces: Vec::new(),
             lights: Vec::new(),
             emitters: Vec::new(),
+            point_lights: Vec::new(),
+            analytic_lights: Vec::new(),
+            portals: Vec::new(),
+            bvh_config: BvhConfig::default(),
+            warnings: Vec::new(),
+        })
+    }
+
+    pub fn with_bvh_config(mut self: Box<Self>, bvh_config: BvhConfig) -> Box<Self> {
+        self.bvh_config = bvh_config;
+        self
+    }
+
+    fn register_analytic_lights(&mut self) {
+        let bounds = (self.entities.get_ids().iter())
+            .filter_map(|id| {
+                let shape = self.entities.get_shape(id.shape_id()).unwrap();
+                shape.bounding_box()
+            })
+            .reduce(|a, b| a.merge(&b))
+            .unwrap_or(BoundingBox::new(Point::default(), Point::default()));
+
+        for light in std::mem::take(&mut self.analytic_lights) {
+            let sampler = light.get_light_sampler();
+            if sampler.position().is_some() {
+                self.point_lights.push(light.get_light_sampler());
+            }
+            self.lights.push((sampler, Val(1.0)));
+            if let Some(sampler) = light.get_photon_sampler(&bounds) {
+                self.emitters.push(sampler);
+            }
+        }
+    }
+
+    fn check_degenerate<S: Shape>(shape: &S) -> Option<SceneWarning> {
+        let area = shape.area().value();
+        let bounding_box = shape.bounding_box();
+        let is_flat = matches!(
+            shape.kind(),
+            ShapeKind::Triangle
+                | ShapeKind::Polygon
+                | ShapeKind::MeshTriangle
+                | ShapeKind::MeshPolygon
+        );
+        let is_degenerate = area.is_nan()
+            || bounding_box.as_ref().is_some_and(|bbox| {
+                let (min, max) = (bbox.min(), bbox.max());
+                let diagonal = (max - min).norm_squared();
+                let scale = [min.x(), min.y(), min.z(), max.x(), max.y(), max.z()]
+                    .into_iter()
+                    .map(Val::abs)
+                    .fold(Val(0.0), Val::max);
+                area.0 == 0.0
+                    || (is_flat && (area / diagonal).0 < Self::MIN_AREA_RATIO.0)
+                    || diagonal.sqrt().0 < (scale * Self::MIN_RELATIVE_EXTENT).0
+            });
+        is_degenerate.then(|| SceneWarning::DegenerateShape {
+            kind: shape.kind(),
+            bounding_box,
         })
     }
 
@@ -39,11 +119,21 @@ impl BvhEntitySceneBuilder {
 
     fn register_emissive(&mut self, entity_id: EntityId) {
         Self::inspect_emissive(self.entities.as_ref(), entity_id, |id, shape, emissive| {
+            if emissive.is_textured()
+                && let Some(sampler) = EmissivePointSampler::new(id, shape, &emissive)
+            {
+                self.light_surfaces.push(Box::new(sampler.clone()));
+                let light = LightSamplerAdapter::new(sampler.clone());
+                self.lights.push((Box::new(light), Val(1.0)));
+                let emitter = PhotonSamplerAdapter::new(sampler, emissive);
+                self.emitters.push(Box::new(emitter));
+                return;
+            }
             if let Some(sampler) = shape.get_point_sampler(id) {
                 self.light_surfaces.push(sampler);
             }
             if let Some(sampler) = shape.get_light_sampler(id) {
-                self.lights.push(sampler);
+                self.lights.push((sampler, Val(1.0)));
             }
             if let Some(sampler) = shape.get_photon_sampler(id, emissive) {
                 self.emitters.push(sampler);
@@ -51,6 +141,20 @@ impl BvhEntitySceneBuilder {
         });
     }
 
+    fn weight_by_area(lights: &mut [(Box<dyn LightSampling>, Val)]) {
+        let area = |light: &dyn LightSampling| light.shape().map_or(Val(0.0), |s| s.area().value());
+        let total = lights
+            .iter()
+            .map(|(light, _)| area(light.as_ref()))
+            .sum::<Val>();
+        if total == Val(0.0) {
+            return;
+        }
+        for (light, weight) in lights {
+            *weight = area(light.as_ref()) / total;
+        }
+    }
+
     fn inspect_emissive<F>(entities: &dyn EntityContainer, entity_id: EntityId, mut callback: F)
     where
         F: FnMut(ShapeId, RefDynShape, Emissive),
@@ -74,11 +178,21 @@ impl BvhEntitySceneBuilder {
 }
 
 impl EntitySceneBuilder for BvhEntitySceneBuilder {
-    fn add_dyn(&mut self, shape: DynShape, material: DynMaterial) {
+    fn add_dyn_with_visibility(
+        &mut self,
+        shape: DynShape,
+        material: DynMaterial,
+        visibility: Visibility,
+    ) {
+        if let Some(warning) = Self::check_degenerate(&shape) {
+            self.warnings.push(warning);
+            return;
+        }
         let shape_id = self.entities.add_shape(shape);
         let material_id = self.entities.add_material(material);
         let entity_id = EntityId::new(shape_id, material_id);
         self.entities.register_id(entity_id);
+        self.entities.set_visibility(entity_id, visibility);
         self.post_add_entity(entity_id);
     }
 
@@ -90,14 +204,31 @@ impl EntitySceneBuilder for BvhEntitySceneBuilder {
         let shape_ids = constructor.construct(self.entities.as_mut());
         let material_id = self.entities.add_material(material);
 
+        let first_light = self.lights.len();
         for shape_id in shape_ids {
+            let shape = self.entities.get_shape(shape_id).unwrap();
+            if let Some(warning) = Self::check_degenerate(&shape) {
+                self.warnings.push(warning);
+                continue;
+            }
             let entity_id = EntityId::new(shape_id, material_id);
             self.entities.register_id(entity_id);
             self.post_add_entity(entity_id);
         }
+        Self::weight_by_area(&mut self.lights[first_light..]);
+    }
+
+    fn add_light_dyn(&mut self, light: DynLight) {
+        self.analytic_lights.push(light);
+    }
+
+    fn add_portal(&mut self, portal: Portal) {
+        self.portals.push(portal);
     }
 
-    fn build(self: Box<Self>) -> Box<dyn EntityScene> {
+    fn build_with_warnings(mut self: Box<Self>) -> (Box<dyn EntityScene>, Vec<SceneWarning>) {
+        self.register_analytic_lights();
+
         let light_surfaces: Box<dyn PointSampling> = if self.light_surfaces.len() > 1 {
             let samplers = (self.light_surfaces.into_iter())
                 .map(|s| (s, Val(1.0)))
@@ -109,12 +240,13 @@ impl EntitySceneBuilder for BvhEntitySceneBuilder {
                 .unwrap_or(Box::new(EmptyPointSampler::new()))
         };
 
+        let num_lights = self.lights.len();
         let lights: Box<dyn LightSampling> = if self.lights.len() > 1 {
             Box::new(AggregateLightSampler::new(self.lights))
         } else {
             (self.lights.into_iter())
                 .next()
-                .unwrap_or(Box::new(EmptyLightSampler::new()))
+                .map_or(Box::new(EmptyLightSampler::new()), |(light, _)| light)
         };
 
         let emitters: Box<dyn PhotonSampling> = if self.emitters.len() > 1 {
@@ -125,12 +257,17 @@ impl EntitySceneBuilder for BvhEntitySceneBuilder {
                 .unwrap_or(Box::new(EmptyPhotonSampler::new()))
         };
 
-        Box::new(BvhEntityScene::new(
+        let scene = BvhEntityScene::new(
+            &self.bvh_config,
             self.entities,
             light_surfaces,
             lights,
             emitters,
-        ))
+            self.portals,
+            num_lights,
+        )
+        .with_point_lights(self.point_lights);
+        (Box::new(scene), self.warnings)
     }
 }
 
@@ -141,14 +278,20 @@ pub struct BvhEntityScene {
     light_surfaces: Box<dyn PointSampling>,
     lights: Box<dyn LightSampling>,
     emitters: Box<dyn PhotonSampling>,
+    point_lights: Vec<Box<dyn LightSampling>>,
+    portals: Vec<Portal>,
+    num_lights: usize,
 }
 
 impl BvhEntityScene {
     fn new(
+        bvh_config: &BvhConfig,
         entities: Box<EntityPool>,
         light_surfaces: Box<dyn PointSampling>,
         lights: Box<dyn LightSampling>,
         emitters: Box<dyn PhotonSampling>,
+        portals: Vec<Portal>,
+        num_lights: usize,
     ) -> Self {
         let ids = entities.get_ids();
         let mut bboxes = Vec::with_capacity(ids.len());
@@ -161,7 +304,7 @@ impl BvhEntityScene {
                 None => unboundeds.push(*id),
             }
         }
-        let bvh = Bvh::new(bboxes, unboundeds);
+        let bvh = Bvh::new(bvh_config, bboxes, unboundeds);
 
         Self {
             entities,
@@ -169,6 +312,16 @@ impl BvhEntityScene {
             light_surfaces,
             lights,
             emitters,
+            point_lights: Vec::new(),
+            portals,
+            num_lights,
+        }
+    }
+
+    fn with_point_lights(self, point_lights: Vec<Box<dyn LightSampling>>) -> Self {
+        Self {
+            point_lights,
+            ..self
         }
     }
 }
@@ -190,7 +343,294 @@ impl EntityScene for BvhEntityScene {
         &*self.emitters
     }
 
+    fn get_point_lights(&self) -> &[Box<dyn LightSampling>] {
+        &self.point_lights
+    }
+
+    fn get_portals(&self) -> &[Portal] {
+        &self.portals
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        self.bvh.bounding_box().cloned()
+    }
+
+    fn stats(&self) -> SceneStats {
+        let ids = self.entities.get_ids();
+        let triangles = (ids.iter())
+            .filter(|id| {
+                let kind = id.shape_id().kind();
+                kind == ShapeKind::Triangle || kind == ShapeKind::MeshTriangle
+            })
+            .count();
+        SceneStats::new(ids.len(), triangles, self.num_lights, self.bvh.stats())
+    }
+
     fn find_intersection(&self, ray: &Ray, range: DisRange) -> Option<(RayIntersection, EntityId)> {
-        self.bvh.search(ray, range, &*self.entities)
+        let (intersection, id) = self.bvh.search(ray, range, &*self.entities)?;
+        Some((intersection.with_time(ray.time()), id))
+    }
+
+    fn find_visible_intersection(
+        &self,
+        ray: &Ray,
+        range: DisRange,
+        visibility: Visibility,
+    ) -> Option<(RayIntersection, EntityId)> {
+        let entities = &*self.entities;
+        let filter = |id| entities.get_visibility(id).contains(visibility);
+        let (intersection, id) = self.bvh.search_filtered(ray, range, entities, filter)?;
+        Some((intersection.with_time(ray.time()), id))
+    }
+
+    fn count_traversal(&self, ray: &Ray, range: DisRange) -> BvhTraversalStats {
+        let mut stats = BvhTraversalStats::default();
+        (self.bvh).search_with_stats(ray, range, &*self.entities, &mut stats);
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::domain::color::core::{Albedo, Spectrum};
+    use crate::domain::light::primitive::DirectionalLight;
+    use crate::domain::material::primitive::Diffuse;
+    use crate::domain::math::algebra::Vector;
+    use crate::domain::math::geometry::{Direction, Distance, Normal, SpreadAngle};
+    use crate::domain::math::transformation::{Sequential, Translation};
+    use crate::domain::ray::event::SurfaceSide;
+    use crate::domain::ray::util::VisibilityTester;
+    use crate::domain::shape::mesh::{MeshConstructor, SharedMesh};
+    use crate::domain::shape::primitive::{Plane, Sphere, Triangle};
+    use crate::domain::shape::util::Instance;
+
+    use super::super::TypedEntitySceneBuilder;
+    use super::*;
+
+    #[test]
+    fn bvh_entity_scene_bounding_box_succeeds_excluding_planes() {
+        let mut builder = BvhEntitySceneBuilder::new();
+        builder.add(
+            Plane::new(Point::default(), Normal::y_direction()),
+            Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+        );
+        for x in [Val(0.0), Val(4.0)] {
+            builder.add(
+                Sphere::new(Point::new(x, Val(0.0), Val(0.0)), Val(1.0)).unwrap(),
+                Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+            );
+        }
+        builder.add_light(DirectionalLight::new(
+            -Direction::y_direction(),
+            Spectrum::broadcast(Val(1.0)),
+        ));
+        let scene = builder.build();
+
+        let bbox = scene.bounding_box().unwrap();
+        assert_eq!(bbox.min(), Point::new(Val(-1.0), Val(-1.0), Val(-1.0)));
+        assert_eq!(bbox.max(), Point::new(Val(5.0), Val(1.0), Val(1.0)));
+
+        let stats = scene.stats();
+        assert_eq!(stats.entities(), 3);
+        assert_eq!(stats.triangles(), 0);
+        assert_eq!(stats.lights(), 1);
+        assert_eq!(stats.bvh().bounded(), 2);
+        assert_eq!(stats.bvh().unbounded(), 1);
+    }
+
+    #[test]
+    fn bvh_entity_scene_entities_succeeds_describing_sphere_and_plane() {
+        let mut builder = BvhEntitySceneBuilder::new();
+        builder.add(
+            Sphere::new(Point::new(Val(0.0), Val(2.0), Val(0.0)), Val(1.0)).unwrap(),
+            Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+        );
+        builder.add(
+            Plane::new(Point::default(), Normal::y_direction()),
+            Emissive::new(Spectrum::broadcast(Val(1.0)), SpreadAngle::hemisphere()),
+        );
+        let scene = builder.build();
+
+        let entities = scene.entities().collect::<Vec<_>>();
+        assert_eq!(entities.len(), 2);
+        assert_eq!(entities[0].shape_kind(), ShapeKind::Sphere);
+        assert_eq!(entities[0].material_kind(), MaterialKind::Diffuse);
+        let bbox = entities[0].bounding_box().as_ref().unwrap();
+        assert_eq!(bbox.min(), Point::new(Val(-1.0), Val(1.0), Val(-1.0)));
+        assert_eq!(bbox.max(), Point::new(Val(1.0), Val(3.0), Val(1.0)));
+        assert_eq!(entities[1].shape_kind(), ShapeKind::Plane);
+        assert_eq!(entities[1].material_kind(), MaterialKind::Emissive);
+        assert!(entities[1].bounding_box().is_none());
+    }
+
+    #[test]
+    fn bvh_entity_scene_builder_build_with_warnings_succeeds_rejecting_degenerate_shapes() {
+        let mut builder = BvhEntitySceneBuilder::new();
+        builder.add(
+            Triangle::new(
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(1000.0), Val(0.0), Val(0.0)),
+                Point::new(Val(500.0), Val(0.001), Val(0.0)),
+            )
+            .unwrap(),
+            Diffuse::new(Albedo::WHITE),
+        );
+        builder.add(
+            Sphere::new(Point::new(Val(1e9), Val(0.0), Val(0.0)), Val(1e-6)).unwrap(),
+            Diffuse::new(Albedo::WHITE),
+        );
+        builder.add(
+            Sphere::new(Point::default(), Val(1e-6)).unwrap(),
+            Diffuse::new(Albedo::WHITE),
+        );
+        builder.add(
+            Triangle::new(
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(1.0), Val(0.0), Val(0.0)),
+                Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            )
+            .unwrap(),
+            Diffuse::new(Albedo::WHITE),
+        );
+        builder.add(
+            Plane::new(Point::default(), Normal::y_direction()),
+            Diffuse::new(Albedo::WHITE),
+        );
+        let (scene, warnings) = builder.build_with_warnings();
+
+        assert_eq!(scene.stats().entities(), 3);
+        assert_eq!(warnings.len(), 2);
+        assert!(matches!(
+            &warnings[0],
+            SceneWarning::DegenerateShape {
+                kind: ShapeKind::Triangle,
+                bounding_box: Some(_)
+            },
+        ));
+        assert!(matches!(
+            &warnings[1],
+            SceneWarning::DegenerateShape {
+                kind: ShapeKind::Sphere,
+                ..
+            },
+        ));
+    }
+
+    #[test]
+    fn bvh_entity_scene_builder_build_with_warnings_succeeds_keeping_sparse_compound_shapes() {
+        let mut builder = BvhEntitySceneBuilder::new();
+        let sphere = Sphere::new(Point::default(), Val(0.002)).unwrap();
+        builder.add(
+            Instance::animated(
+                Arc::new(sphere.into()),
+                Sequential::default(),
+                Sequential::default().with_translation(Translation::new(Vector::new(
+                    Val(10.0),
+                    Val(0.0),
+                    Val(0.0),
+                ))),
+            ),
+            Diffuse::new(Albedo::WHITE),
+        );
+        let mesh = MeshConstructor::new(
+            vec![
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(0.1), Val(0.0), Val(0.0)),
+                Point::new(Val(0.0), Val(0.1), Val(0.0)),
+                Point::new(Val(100.0), Val(100.0), Val(100.0)),
+                Point::new(Val(100.1), Val(100.0), Val(100.0)),
+                Point::new(Val(100.0), Val(100.1), Val(100.0)),
+            ],
+            vec![vec![0, 1, 2], vec![3, 4, 5]],
+        )
+        .unwrap();
+        builder.add(SharedMesh::new(mesh), Diffuse::new(Albedo::WHITE));
+        let (scene, warnings) = builder.build_with_warnings();
+
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(scene.stats().entities(), 2);
+    }
+
+    #[test]
+    fn bvh_entity_scene_directional_light_casts_crisp_shadow() {
+        let mut builder = BvhEntitySceneBuilder::new();
+        builder.add(
+            Plane::new(Point::default(), Normal::y_direction()),
+            Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+        );
+        builder.add(
+            Sphere::new(Point::new(Val(0.0), Val(2.0), Val(0.0)), Val(1.0)).unwrap(),
+            Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap()),
+        );
+        builder.add_light(DirectionalLight::new(
+            -Direction::y_direction(),
+            Spectrum::broadcast(Val(1.0)),
+        ));
+        let scene = builder.build();
+
+        let is_lit = |x: Val| {
+            let intersection = RayIntersection::new(
+                Distance::new(Val(1.0)).unwrap(),
+                Point::new(x, Val(0.0), Val(0.0)),
+                Normal::y_direction(),
+                SurfaceSide::Front,
+            );
+            let sample = (scene.get_lights())
+                .sample_light_surface(&intersection, &mut rand::rng())
+                .unwrap();
+            assert!(sample.is_delta());
+            VisibilityTester::new(scene.as_ref(), sample.ray_next())
+                .test_unblocked(sample.distance())
+        };
+        assert!(!is_lit(Val(0.0)));
+        assert!(!is_lit(Val(0.99)));
+        assert!(is_lit(Val(1.01)));
+        assert!(is_lit(Val(-1.01)));
+        assert!(scene.get_emitters().area().value() > Val(0.0));
+    }
+
+    #[test]
+    fn bvh_entity_scene_light_sampling_succeeds_selecting_faces_by_area() {
+        let vertices = vec![
+            Point::new(Val(-3.0), Val(1.0), Val(0.0)),
+            Point::new(Val(-2.0), Val(1.0), Val(0.0)),
+            Point::new(Val(-3.0), Val(1.0), Val(2.0)),
+            Point::new(Val(2.0), Val(1.0), Val(0.0)),
+            Point::new(Val(4.0), Val(1.0), Val(0.0)),
+            Point::new(Val(2.0), Val(1.0), Val(3.0)),
+        ];
+        let mesh = MeshConstructor::new(vertices, vec![vec![0, 1, 2], vec![3, 4, 5]]).unwrap();
+        let mut builder = BvhEntitySceneBuilder::new();
+        builder.add_constructor(
+            mesh,
+            Emissive::new(Spectrum::broadcast(Val(1.0)), SpreadAngle::hemisphere()),
+        );
+        let scene = builder.build();
+
+        let intersection = RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::default(),
+            Normal::y_direction(),
+            SurfaceSide::Front,
+        );
+        let lights = scene.get_lights();
+        let mut rng = rand::rng();
+        let (mut small, mut large) = (0, 0);
+        for _ in 0..10000 {
+            let sample = lights
+                .sample_light_surface(&intersection, &mut rng)
+                .unwrap();
+            let pdf = lights.pdf_light_surface(&intersection, sample.ray_next());
+            assert!((pdf - sample.pdf()).abs() < Val(1e-6) * sample.pdf());
+            if sample.ray_next().direction().x() < Val(0.0) {
+                small += 1;
+            } else {
+                large += 1;
+            }
+        }
+        let ratio = Val::from(large) / Val::from(small);
+        assert!(Val(2.7) < ratio && ratio < Val(3.3), "ratio = {ratio:?}");
     }
 }
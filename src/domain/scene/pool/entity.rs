@@ -1,8 +1,9 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 use crate::domain::material::def::{DynMaterial, RefDynMaterial};
 use crate::domain::material::util::{MaterialContainer, MaterialId};
-use crate::domain::scene::entity::{EntityContainer, EntityId};
+use crate::domain::scene::entity::{EntityContainer, EntityId, Visibility};
 use crate::domain::shape::def::{DynShape, RefDynShape};
 use crate::domain::shape::util::{ShapeContainer, ShapeId};
 
@@ -13,6 +14,7 @@ pub struct EntityPool {
     ids: Vec<EntityId>,
     shapes: ShapePool,
     materials: MaterialPool,
+    visibilities: HashMap<EntityId, Visibility>,
 }
 
 impl EntityPool {
@@ -52,6 +54,21 @@ impl EntityContainer for EntityPool {
     fn get_ids(&self) -> &[EntityId] {
         &self.ids
     }
+
+    fn set_visibility(&mut self, id: EntityId, visibility: Visibility) {
+        if visibility == Visibility::ALL {
+            self.visibilities.remove(&id);
+        } else {
+            self.visibilities.insert(id, visibility);
+        }
+    }
+
+    fn get_visibility(&self, id: EntityId) -> Visibility {
+        if self.visibilities.is_empty() {
+            return Visibility::ALL;
+        }
+        self.visibilities.get(&id).copied().unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -85,5 +102,10 @@ mod tests {
             pool.get_material(id.material_id()).unwrap().kind(),
             MaterialKind::Diffuse,
         );
+
+        assert_eq!(pool.get_visibility(id), Visibility::ALL);
+        pool.set_visibility(id, Visibility::ALL.without(Visibility::SHADOW));
+        assert!(!pool.get_visibility(id).contains(Visibility::SHADOW));
+        assert!(pool.get_visibility(id).contains(Visibility::PRIMARY));
     }
 }
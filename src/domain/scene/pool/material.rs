@@ -7,13 +7,22 @@ use crate::domain::material::util::{MaterialContainer, MaterialId};
 #[derive(Debug, Default)]
 pub struct MaterialPool {
     blurry: Vec<Blurry>,
+    coated: Vec<Coated>,
+    conductor: Vec<Conductor>,
     diffuse: Vec<Diffuse>,
+    dispersive: Vec<Dispersive>,
     emissive: Vec<Emissive>,
     glossy: Vec<Glossy>,
+    glossy_anisotropic: Vec<GlossyAnisotropic>,
+    phong: Vec<Phong>,
+    principled: Vec<Principled>,
     refractive: Vec<Refractive>,
     scattering: Vec<Scattering>,
     specular: Vec<Specular>,
+    thin_film: Vec<ThinFilm>,
     mixed: Vec<Mixed>,
+    normal_mapped: Vec<NormalMapped>,
+    bump_mapped: Vec<BumpMapped>,
 }
 
 impl MaterialPool {
@@ -21,7 +30,10 @@ impl MaterialPool {
     where
         M: Material,
     {
-        let kind = material.kind();
+        Self::push_as(material.kind(), material, collection)
+    }
+
+    fn push_as<M>(kind: MaterialKind, material: M, collection: &mut Vec<M>) -> MaterialId {
         collection.push(material);
         MaterialId::new(kind, collection.len() as u32 - 1)
     }
@@ -31,13 +43,26 @@ impl MaterialContainer for MaterialPool {
     fn add_material(&mut self, material: DynMaterial) -> MaterialId {
         match material {
             DynMaterial::Blurry(s) => Self::push(s, &mut self.blurry),
+            DynMaterial::Coated(s) => Self::push(s, &mut self.coated),
+            DynMaterial::Conductor(s) => Self::push(s, &mut self.conductor),
             DynMaterial::Diffuse(s) => Self::push(s, &mut self.diffuse),
+            DynMaterial::Dispersive(s) => Self::push(s, &mut self.dispersive),
             DynMaterial::Emissive(s) => Self::push(s, &mut self.emissive),
             DynMaterial::Glossy(s) => Self::push(s, &mut self.glossy),
+            DynMaterial::GlossyAnisotropic(s) => Self::push(s, &mut self.glossy_anisotropic),
+            DynMaterial::Phong(s) => Self::push(s, &mut self.phong),
+            DynMaterial::Principled(s) => Self::push(s, &mut self.principled),
             DynMaterial::Refractive(s) => Self::push(s, &mut self.refractive),
             DynMaterial::Scattering(s) => Self::push(s, &mut self.scattering),
             DynMaterial::Specular(s) => Self::push(s, &mut self.specular),
+            DynMaterial::ThinFilm(s) => Self::push(s, &mut self.thin_film),
             DynMaterial::Mixed(s) => Self::push(s, &mut self.mixed),
+            DynMaterial::NormalMapped(s) => {
+                Self::push_as(MaterialKind::NormalMapped, s, &mut self.normal_mapped)
+            }
+            DynMaterial::BumpMapped(s) => {
+                Self::push_as(MaterialKind::BumpMapped, s, &mut self.bump_mapped)
+            }
         }
     }
 
@@ -45,13 +70,22 @@ impl MaterialContainer for MaterialPool {
         let index = material_id.index() as usize;
         match material_id.kind() {
             MaterialKind::Blurry => self.blurry.get(index).map(Into::into),
+            MaterialKind::Coated => self.coated.get(index).map(Into::into),
+            MaterialKind::Conductor => self.conductor.get(index).map(Into::into),
             MaterialKind::Diffuse => self.diffuse.get(index).map(Into::into),
+            MaterialKind::Dispersive => self.dispersive.get(index).map(Into::into),
             MaterialKind::Emissive => self.emissive.get(index).map(Into::into),
             MaterialKind::Glossy => self.glossy.get(index).map(Into::into),
+            MaterialKind::GlossyAnisotropic => self.glossy_anisotropic.get(index).map(Into::into),
+            MaterialKind::Phong => self.phong.get(index).map(Into::into),
+            MaterialKind::Principled => self.principled.get(index).map(Into::into),
             MaterialKind::Refractive => self.refractive.get(index).map(Into::into),
             MaterialKind::Scattering => self.scattering.get(index).map(Into::into),
             MaterialKind::Specular => self.specular.get(index).map(Into::into),
+            MaterialKind::ThinFilm => self.thin_film.get(index).map(Into::into),
             MaterialKind::Mixed => self.mixed.get(index).map(Into::into),
+            MaterialKind::NormalMapped => self.normal_mapped.get(index).map(Into::into),
+            MaterialKind::BumpMapped => self.bump_mapped.get(index).map(Into::into),
         }
     }
 }
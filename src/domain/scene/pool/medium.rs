@@ -6,6 +6,8 @@ use crate::domain::medium::util::{MediumContainer, MediumId};
 
 #[derive(Debug, Default)]
 pub struct MediumPool {
+    atmospheric: Vec<Atmospheric>,
+    grid: Vec<GridMedium>,
     henyey_greenstein: Vec<HenyeyGreenstein>,
     isotropic: Vec<Isotropic>,
     vacuum: Vec<Vacuum>,
@@ -25,6 +27,8 @@ impl MediumPool {
 impl MediumContainer for MediumPool {
     fn add_medium(&mut self, medium: DynMedium) -> MediumId {
         match medium {
+            DynMedium::Atmospheric(s) => Self::push(s, &mut self.atmospheric),
+            DynMedium::Grid(s) => Self::push(s, &mut self.grid),
             DynMedium::HenyeyGreenstein(s) => Self::push(s, &mut self.henyey_greenstein),
             DynMedium::Isotropic(s) => Self::push(s, &mut self.isotropic),
             DynMedium::Vacuum(s) => Self::push(s, &mut self.vacuum),
@@ -34,6 +38,8 @@ impl MediumContainer for MediumPool {
     fn get_medium(&self, medium_id: MediumId) -> Option<RefDynMedium> {
         let index = medium_id.index() as usize;
         match medium_id.kind() {
+            MediumKind::Atmospheric => self.atmospheric.get(index).map(Into::into),
+            MediumKind::Grid => self.grid.get(index).map(Into::into),
             MediumKind::HenyeyGreenstein => self.henyey_greenstein.get(index).map(Into::into),
             MediumKind::Isotropic => self.isotropic.get(index).map(Into::into),
             MediumKind::Vacuum => self.vacuum.get(index).map(Into::into),
@@ -1,12 +1,17 @@
 use std::fmt::Debug;
 
 use crate::domain::shape::def::{DynShape, RefDynShape, Shape, ShapeKind};
+use crate::domain::shape::mesh::SharedMesh;
 use crate::domain::shape::primitive::*;
-use crate::domain::shape::util::{Instance, ShapeContainer, ShapeId};
+use crate::domain::shape::sdf::Sdf;
+use crate::domain::shape::util::{Csg, Instance, ShapeContainer, ShapeId};
 
 #[derive(Debug, Default)]
 pub struct ShapePool {
     aabbs: Vec<Aabb>,
+    bilinear_patches: Vec<BilinearPatch>,
+    cylinders: Vec<Cylinder>,
+    disks: Vec<Disk>,
     mesh_polygons: Vec<MeshPolygon>,
     mesh_triangles: Vec<MeshTriangle>,
     planes: Vec<Plane>,
@@ -14,6 +19,9 @@ pub struct ShapePool {
     spheres: Vec<Sphere>,
     triangles: Vec<Triangle>,
     instances: Vec<Instance>,
+    csgs: Vec<Csg>,
+    sdfs: Vec<Sdf>,
+    shared_meshes: Vec<SharedMesh>,
 }
 
 impl ShapePool {
@@ -31,6 +39,9 @@ impl ShapeContainer for ShapePool {
     fn add_shape(&mut self, shape: DynShape) -> ShapeId {
         match shape {
             DynShape::Aabb(s) => Self::push(s, &mut self.aabbs),
+            DynShape::BilinearPatch(s) => Self::push(s, &mut self.bilinear_patches),
+            DynShape::Cylinder(s) => Self::push(s, &mut self.cylinders),
+            DynShape::Disk(s) => Self::push(s, &mut self.disks),
             DynShape::MeshPolygon(s) => Self::push(s, &mut self.mesh_polygons),
             DynShape::MeshTriangle(s) => Self::push(s, &mut self.mesh_triangles),
             DynShape::Plane(s) => Self::push(s, &mut self.planes),
@@ -38,6 +49,9 @@ impl ShapeContainer for ShapePool {
             DynShape::Sphere(s) => Self::push(s, &mut self.spheres),
             DynShape::Triangle(s) => Self::push(s, &mut self.triangles),
             DynShape::Instance(s) => Self::push(s, &mut self.instances),
+            DynShape::Csg(s) => Self::push(s, &mut self.csgs),
+            DynShape::Sdf(s) => Self::push(s, &mut self.sdfs),
+            DynShape::SharedMesh(s) => Self::push(s, &mut self.shared_meshes),
         }
     }
 
@@ -45,6 +59,9 @@ impl ShapeContainer for ShapePool {
         let index = shape_id.index() as usize;
         match shape_id.kind() {
             ShapeKind::Aabb => self.aabbs.get(index).map(Into::into),
+            ShapeKind::BilinearPatch => self.bilinear_patches.get(index).map(Into::into),
+            ShapeKind::Cylinder => self.cylinders.get(index).map(Into::into),
+            ShapeKind::Disk => self.disks.get(index).map(Into::into),
             ShapeKind::MeshPolygon => self.mesh_polygons.get(index).map(Into::into),
             ShapeKind::MeshTriangle => self.mesh_triangles.get(index).map(Into::into),
             ShapeKind::Plane => self.planes.get(index).map(Into::into),
@@ -52,6 +69,9 @@ impl ShapeContainer for ShapePool {
             ShapeKind::Triangle => self.triangles.get(index).map(Into::into),
             ShapeKind::Sphere => self.spheres.get(index).map(Into::into),
             ShapeKind::Instance => self.instances.get(index).map(Into::into),
+            ShapeKind::Csg => self.csgs.get(index).map(Into::into),
+            ShapeKind::Sdf => self.sdfs.get(index).map(Into::into),
+            ShapeKind::SharedMesh => self.shared_meshes.get(index).map(Into::into),
         }
     }
 }
@@ -11,7 +11,7 @@ use crate::domain::medium::util::{MediumContainer, MediumId};
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::{RayIntersection, RaySegment, SurfaceSide};
 use crate::domain::sampling::Sampleable;
-use crate::domain::scene::bvh::Bvh;
+use crate::domain::scene::bvh::{Bvh, BvhConfig};
 use crate::domain::scene::pool::BoundaryPool;
 use crate::domain::shape::def::{DynShape, Shape};
 use crate::domain::shape::util::{ShapeConstructor, ShapeContainer};
@@ -21,14 +21,21 @@ use super::{BoundaryContainer, BoundaryId, VolumeScene, VolumeSceneBuilder};
 #[derive(Debug)]
 pub struct BvhVolumeSceneBuilder {
     boundaries: Box<BoundaryPool>,
+    bvh_config: BvhConfig,
 }
 
 impl BvhVolumeSceneBuilder {
     pub fn new() -> Box<Self> {
         Box::new(Self {
             boundaries: Box::new(BoundaryPool::new()),
+            bvh_config: BvhConfig::default(),
         })
     }
+
+    pub fn with_bvh_config(mut self: Box<Self>, bvh_config: BvhConfig) -> Box<Self> {
+        self.bvh_config = bvh_config;
+        self
+    }
 }
 
 impl VolumeSceneBuilder for BvhVolumeSceneBuilder {
@@ -49,7 +56,7 @@ impl VolumeSceneBuilder for BvhVolumeSceneBuilder {
     }
 
     fn build(self: Box<Self>) -> Box<dyn VolumeScene> {
-        Box::new(BvhVolumeScene::new(self.boundaries))
+        Box::new(BvhVolumeScene::new(&self.bvh_config, self.boundaries))
     }
 }
 
@@ -63,7 +70,7 @@ pub struct BvhVolumeScene {
 impl BvhVolumeScene {
     const OUTER_MEDIUM_MAX_DETECTION_COUNT: usize = 16;
 
-    fn new(boundaries: Box<BoundaryPool>) -> Self {
+    fn new(bvh_config: &BvhConfig, boundaries: Box<BoundaryPool>) -> Self {
         let ids = boundaries.get_ids();
         let mut bboxes = Vec::with_capacity(ids.len());
 
@@ -74,7 +81,7 @@ impl BvhVolumeScene {
             }
         }
 
-        let bvh = Bvh::new(bboxes, Vec::new());
+        let bvh = Bvh::new(bvh_config, bboxes, Vec::new());
         let outer_media = Self::determine_outer_media(&boundaries, ids, &bvh);
 
         Self {
@@ -322,7 +329,7 @@ mod tests {
         boundaries.register_id(BoundaryId::new(shape_id, medium_id));
 
         let ids = boundaries.get_ids().to_owned();
-        let scene = BvhVolumeScene::new(boundaries);
+        let scene = BvhVolumeScene::new(&BvhConfig::default(), boundaries);
         (scene, ids)
     }
 }
@@ -49,6 +49,13 @@ impl BoundingBox {
         self.0.area()
     }
 
+    pub fn contains(&self, point: Point) -> bool {
+        (0..3).all(|axis| {
+            let value = point.axis(axis);
+            self.min().axis(axis) <= value && value <= self.max().axis(axis)
+        })
+    }
+
     pub fn try_hit(&self, ray: &Ray, range: DisRange) -> Option<Distance> {
         if let Some((left, right)) = self.0.hit_range(ray) {
             let range = range.intersect(DisRange::inclusive(left, right));
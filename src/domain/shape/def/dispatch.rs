@@ -9,8 +9,10 @@ use crate::domain::sampling::Sampleable;
 use crate::domain::sampling::light::LightSampling;
 use crate::domain::sampling::photon::PhotonSampling;
 use crate::domain::sampling::point::PointSampling;
+use crate::domain::shape::mesh::SharedMesh;
 use crate::domain::shape::primitive::*;
-use crate::domain::shape::util::{Instance, ShapeId};
+use crate::domain::shape::sdf::Sdf;
+use crate::domain::shape::util::{Csg, Instance, ShapeId};
 
 use super::{BoundingBox, Shape, ShapeKind};
 
@@ -18,6 +20,9 @@ macro_rules! impl_dispatch {
     ($type:tt, $self:ident.$method:ident($($arg:ident),*)) => {
         match $self {
             $type::Aabb(s) => s.$method($($arg),*),
+            $type::BilinearPatch(s) => s.$method($($arg),*),
+            $type::Cylinder(s) => s.$method($($arg),*),
+            $type::Disk(s) => s.$method($($arg),*),
             $type::MeshPolygon(s) => s.$method($($arg),*),
             $type::MeshTriangle(s) => s.$method($($arg),*),
             $type::Plane(s) => s.$method($($arg),*),
@@ -25,6 +30,9 @@ macro_rules! impl_dispatch {
             $type::Sphere(s) => s.$method($($arg),*),
             $type::Triangle(s) => s.$method($($arg),*),
             $type::Instance(s) => s.$method($($arg),*),
+            $type::Csg(s) => s.$method($($arg),*),
+            $type::Sdf(s) => s.$method($($arg),*),
+            $type::SharedMesh(s) => s.$method($($arg),*),
         }
     };
 }
@@ -43,6 +51,9 @@ macro_rules! impl_from_ref_for_variant {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DynShape {
     Aabb(Aabb),
+    BilinearPatch(BilinearPatch),
+    Cylinder(Cylinder),
+    Disk(Disk),
     MeshPolygon(MeshPolygon),
     MeshTriangle(MeshTriangle),
     Plane(Plane),
@@ -50,6 +61,9 @@ pub enum DynShape {
     Sphere(Sphere),
     Triangle(Triangle),
     Instance(Instance),
+    Csg(Csg),
+    Sdf(Sdf),
+    SharedMesh(SharedMesh),
 }
 
 impl<'a> From<&'a DynShape> for RefDynShape<'a> {
@@ -61,6 +75,9 @@ impl<'a> From<&'a DynShape> for RefDynShape<'a> {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RefDynShape<'a> {
     Aabb(&'a Aabb),
+    BilinearPatch(&'a BilinearPatch),
+    Cylinder(&'a Cylinder),
+    Disk(&'a Disk),
     MeshPolygon(&'a MeshPolygon),
     MeshTriangle(&'a MeshTriangle),
     Plane(&'a Plane),
@@ -68,6 +85,9 @@ pub enum RefDynShape<'a> {
     Sphere(&'a Sphere),
     Triangle(&'a Triangle),
     Instance(&'a Instance),
+    Csg(&'a Csg),
+    Sdf(&'a Sdf),
+    SharedMesh(&'a SharedMesh),
 }
 
 impl<'a> Shape for RefDynShape<'a> {
@@ -123,6 +143,9 @@ impl<'a> Sampleable for RefDynShape<'a> {
 }
 
 impl_from_ref_for_variant!('a, RefDynShape<'a>, Aabb);
+impl_from_ref_for_variant!('a, RefDynShape<'a>, BilinearPatch);
+impl_from_ref_for_variant!('a, RefDynShape<'a>, Cylinder);
+impl_from_ref_for_variant!('a, RefDynShape<'a>, Disk);
 impl_from_ref_for_variant!('a, RefDynShape<'a>, MeshPolygon);
 impl_from_ref_for_variant!('a, RefDynShape<'a>, MeshTriangle);
 impl_from_ref_for_variant!('a, RefDynShape<'a>, Plane);
@@ -130,3 +153,6 @@ impl_from_ref_for_variant!('a, RefDynShape<'a>, Polygon);
 impl_from_ref_for_variant!('a, RefDynShape<'a>, Sphere);
 impl_from_ref_for_variant!('a, RefDynShape<'a>, Triangle);
 impl_from_ref_for_variant!('a, RefDynShape<'a>, Instance);
+impl_from_ref_for_variant!('a, RefDynShape<'a>, Csg);
+impl_from_ref_for_variant!('a, RefDynShape<'a>, Sdf);
+impl_from_ref_for_variant!('a, RefDynShape<'a>, SharedMesh);
@@ -49,11 +49,17 @@ pub trait Shape: Sampleable + Debug + Send + Sync {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ShapeKind {
     Aabb,
+    BilinearPatch,
+    Csg,
+    Cylinder,
+    Disk,
     Instance,
     MeshPolygon,
     MeshTriangle,
     Plane,
     Polygon,
+    Sdf,
+    SharedMesh,
     Sphere,
     Triangle,
 }
@@ -1,17 +1,21 @@
 use std::sync::Arc;
 
-use crate::domain::math::geometry::Point;
+use crate::domain::math::geometry::{Normal, Point};
 use crate::domain::math::transformation::Sequential;
 use crate::domain::shape::primitive::{MeshPolygon, MeshTriangle};
 use crate::domain::shape::util::{ShapeConstructor, ShapeContainer, ShapeId};
 use crate::domain::texture::def::UvCoordinate;
 
-use super::{MeshData, MeshDataComponent, TryAddMeshUvCoordinateError, TryNewMeshError};
+use super::{
+    MeshData, MeshDataComponent, TryAddMeshNormalError, TryAddMeshUvCoordinateError,
+    TryNewMeshError,
+};
 
 #[derive(Debug, Clone)]
 pub struct MeshConstructor {
     vertices: MeshDataComponent<Point>,
     uvs: Option<MeshDataComponent<UvCoordinate>>,
+    normals: Option<MeshDataComponent<Normal>>,
 }
 
 impl MeshConstructor {
@@ -22,6 +26,7 @@ impl MeshConstructor {
         Ok(Self {
             vertices: MeshDataComponent::<Point>::new(vertices, indices)?,
             uvs: None,
+            normals: None,
         })
     }
 
@@ -44,6 +49,30 @@ impl MeshConstructor {
         })
     }
 
+    pub fn with_normals<N>(
+        self,
+        normals: N,
+        indices: Vec<Vec<usize>>,
+    ) -> Result<Self, TryAddMeshNormalError>
+    where
+        N: Into<Arc<[Normal]>>,
+    {
+        Ok(Self {
+            normals: Some(MeshDataComponent::<Normal>::new(
+                normals,
+                indices,
+                self.vertices.triangles().len(),
+                self.vertices.polygons().len(),
+            )?),
+            ..self
+        })
+    }
+
+    pub fn with_smooth_normals(self) -> Self {
+        let normals = Some(MeshDataComponent::<Normal>::smooth(&self.vertices));
+        Self { normals, ..self }
+    }
+
     pub fn construct_impl(
         self,
         transformation: Option<Sequential>,
@@ -51,6 +80,7 @@ impl MeshConstructor {
         let data = Arc::new(MeshData::new(
             self.vertices.clone(),
             self.uvs.clone(),
+            self.normals.clone(),
             transformation,
         ));
 
@@ -83,7 +113,9 @@ impl ShapeConstructor for MeshConstructor {
 
 #[cfg(test)]
 mod tests {
+    use crate::domain::math::algebra::Vector;
     use crate::domain::math::numeric::Val;
+    use crate::domain::shape::def::Shape;
 
     use super::*;
 
@@ -153,4 +185,25 @@ mod tests {
         assert_eq!(triangles.len(), 4);
         assert_eq!(polygons.len(), 1);
     }
+
+    #[test]
+    fn mesh_constructor_with_smooth_normals_succeeds_averaging_adjacent_faces() {
+        let (triangles, _) = MeshConstructor::new(
+            vec![
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(0.0), Val(1.0), Val(0.0)),
+                Point::new(Val(1.0), Val(0.0), Val(0.0)),
+                Point::new(Val(0.0), Val(0.0), Val(1.0)),
+            ],
+            vec![vec![0, 1, 2], vec![0, 3, 1]],
+        )
+        .unwrap()
+        .with_smooth_normals()
+        .construct_impl(None);
+
+        let expected = Normal::normalize(Vector::new(Val(-1.0), Val(0.0), Val(-1.0))).unwrap();
+        let shared = Point::new(Val(0.0), Val(0.5), Val(0.0));
+        assert_eq!(triangles[0].normal(shared), expected);
+        assert_eq!(triangles[1].normal(shared), expected);
+    }
 }
@@ -1,10 +1,13 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use smallvec::SmallVec;
 use snafu::prelude::*;
 
-use crate::domain::math::geometry::Point;
-use crate::domain::math::transformation::Sequential;
+use crate::domain::math::algebra::{Product, Vector};
+use crate::domain::math::geometry::{Normal, Point};
+use crate::domain::math::numeric::Val;
+use crate::domain::math::transformation::{Matrix4, Sequential};
 use crate::domain::shape::primitive::{Polygon, Triangle, TryNewPolygonError, TryNewTriangleError};
 use crate::domain::texture::def::UvCoordinate;
 
@@ -15,22 +18,48 @@ pub type PolygonIndices = SmallVec<[u32; 5]>;
 pub struct MeshData {
     vertices: MeshDataComponent<Point>,
     uvs: Option<MeshDataComponent<UvCoordinate>>,
-    transformation: Option<Sequential>,
+    normals: Option<MeshDataComponent<Normal>>,
+    transformation: Option<Matrix4>,
+    bounds: (Point, Point),
+    shared_edges: Arc<[[bool; 3]]>,
 }
 
 impl MeshData {
     pub fn new(
         vertices: MeshDataComponent<Point>,
         uvs: Option<MeshDataComponent<UvCoordinate>>,
+        normals: Option<MeshDataComponent<Normal>>,
         transformation: Option<Sequential>,
     ) -> Self {
+        let points = vertices.data();
+        let init = points.first().cloned().unwrap_or_default();
+        let bounds = (points.iter()).fold((init, init), |(min, max), vertex| {
+            (min.component_min(vertex), max.component_max(vertex))
+        });
+        let shared_edges = Self::find_shared_edges(vertices.triangles());
         Self {
             vertices,
             uvs,
-            transformation,
+            normals,
+            transformation: transformation.map(Matrix4::from),
+            bounds,
+            shared_edges,
         }
     }
 
+    fn find_shared_edges(triangles: &[TriangleIndices]) -> Arc<[[bool; 3]]> {
+        let edges = |&(i0, i1, i2): &TriangleIndices| {
+            [(i0, i1), (i1, i2), (i2, i0)].map(|(a, b)| (a.min(b), a.max(b)))
+        };
+        let mut counts = HashMap::new();
+        for edge in triangles.iter().flat_map(edges) {
+            *counts.entry(edge).or_insert(0usize) += 1;
+        }
+        (triangles.iter())
+            .map(|triangle| edges(triangle).map(|edge| counts[&edge] > 1))
+            .collect()
+    }
+
     #[inline]
     pub fn vertices(&self) -> &MeshDataComponent<Point> {
         &self.vertices
@@ -42,9 +71,40 @@ impl MeshData {
     }
 
     #[inline]
-    pub fn transformation(&self) -> Option<&Sequential> {
+    pub fn normals(&self) -> Option<&MeshDataComponent<Normal>> {
+        self.normals.as_ref()
+    }
+
+    #[inline]
+    pub fn shared_edges(&self, index: usize) -> [bool; 3] {
+        self.shared_edges[index]
+    }
+
+    #[inline]
+    pub fn transformation(&self) -> Option<&Matrix4> {
         self.transformation.as_ref()
     }
+
+    pub fn planar_uv(&self, position: Point) -> UvCoordinate {
+        let (min, max) = self.bounds;
+        let extent = max - min;
+        let flat = (0..3)
+            .min_by_key(|&axis| extent.axis(axis))
+            .expect("there should be three axes");
+        let (axis_u, axis_v) = match flat {
+            0 => (1, 2),
+            1 => (0, 2),
+            _ => (0, 1),
+        };
+        let normalize = |axis: usize| {
+            if extent.axis(axis) > Val(0.0) {
+                (position.axis(axis) - min.axis(axis)) / extent.axis(axis)
+            } else {
+                Val(0.0)
+            }
+        };
+        UvCoordinate::clamp(normalize(axis_u), normalize(axis_v))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -142,6 +202,35 @@ impl MeshDataComponent<Point> {
     }
 }
 
+impl<T> MeshDataComponent<T>
+where
+    T: Send + Sync,
+{
+    fn new_attribute(
+        data: Arc<[T]>,
+        indices: &[Vec<usize>],
+        num_triangles: usize,
+        num_polygons: usize,
+    ) -> Result<Self, AttributeIndexError> {
+        let mut triangles = Vec::with_capacity(num_triangles);
+        let mut polygons = Vec::with_capacity(num_polygons);
+        for (face, indices) in indices.iter().enumerate() {
+            if let Some(index) = indices.iter().cloned().find(|&index| index >= data.len()) {
+                return Err(AttributeIndexError::OutOfBound { face, index });
+            }
+            if indices.len() == 3 {
+                triangles.push((indices[0] as u32, indices[1] as u32, indices[2] as u32));
+            } else {
+                polygons.push(indices.iter().map(|&i| i as u32).collect());
+            }
+        }
+        if triangles.len() != num_triangles || polygons.len() != num_polygons {
+            return Err(AttributeIndexError::MismatchedNumber);
+        }
+        Ok(Self::new_impl(data, triangles.into(), polygons.into()))
+    }
+}
+
 impl MeshDataComponent<UvCoordinate> {
     pub fn new<U>(
         uvs: U,
@@ -152,50 +241,73 @@ impl MeshDataComponent<UvCoordinate> {
     where
         U: Into<Arc<[UvCoordinate]>>,
     {
-        let uvs = uvs.into();
-        let triangles = Self::create_triangles_uv(&uvs, &indices, num_triangles)?.into();
-        let polygons = Self::create_polygons_uv(&uvs, &indices, num_polygons)?.into();
-        Ok(Self::new_impl(uvs, triangles, polygons))
+        Self::new_attribute(uvs.into(), &indices, num_triangles, num_polygons).map_err(|err| {
+            match err {
+                AttributeIndexError::MismatchedNumber => MismatchedNumberUvSnafu.build(),
+                AttributeIndexError::OutOfBound { face, index } => {
+                    OutOfBoundUvSnafu { face, index }.build()
+                }
+            }
+        })
     }
+}
 
-    fn create_triangles_uv(
-        uvs: &[UvCoordinate],
-        indices: &[Vec<usize>],
-        expected_num: usize,
-    ) -> Result<Vec<TriangleIndices>, TryAddMeshUvCoordinateError> {
-        let mut res = Vec::with_capacity(indices.len());
-        for (face, triangle) in indices.iter().enumerate().filter(|(_, s)| s.len() == 3) {
-            if let Some(index) = triangle.iter().cloned().find(|&index| index >= uvs.len()) {
-                return OutOfBoundUvSnafu { face, index }.fail();
+impl MeshDataComponent<Normal> {
+    pub fn new<N>(
+        normals: N,
+        indices: Vec<Vec<usize>>,
+        num_triangles: usize,
+        num_polygons: usize,
+    ) -> Result<Self, TryAddMeshNormalError>
+    where
+        N: Into<Arc<[Normal]>>,
+    {
+        Self::new_attribute(normals.into(), &indices, num_triangles, num_polygons).map_err(|err| {
+            match err {
+                AttributeIndexError::MismatchedNumber => MismatchedNumberNormalSnafu.build(),
+                AttributeIndexError::OutOfBound { face, index } => {
+                    OutOfBoundNormalSnafu { face, index }.build()
+                }
             }
-
-            assert!(triangle.len() == 3);
-            res.push((triangle[0] as u32, triangle[1] as u32, triangle[2] as u32));
-        }
-        ensure!(res.len() == expected_num, MismatchedNumberUvSnafu);
-        res.shrink_to_fit();
-        Ok(res)
+        })
     }
 
-    fn create_polygons_uv(
-        uvs: &[UvCoordinate],
-        indices: &[Vec<usize>],
-        expected_num: usize,
-    ) -> Result<Vec<PolygonIndices>, TryAddMeshUvCoordinateError> {
-        let mut res = Vec::with_capacity(indices.len());
-        for (face, polygon) in indices.iter().enumerate().filter(|(_, s)| s.len() != 3) {
-            if let Some(index) = polygon.iter().cloned().find(|&index| index >= uvs.len()) {
-                return OutOfBoundUvSnafu { face, index }.fail();
+    pub fn smooth(vertices: &MeshDataComponent<Point>) -> Self {
+        let points = vertices.data();
+        let mut sums = vec![Vector::zero(); points.len()];
+        let faces = (vertices.triangles().iter())
+            .map(|&(i0, i1, i2)| SmallVec::<[u32; 5]>::from_slice(&[i0, i1, i2]))
+            .chain(vertices.polygons().iter().cloned());
+        for face in faces {
+            let v0 = points[face[0] as usize];
+            let mut weighted = Vector::zero();
+            for i in 1..(face.len() - 1) {
+                let side1 = points[face[i] as usize] - v0;
+                let side2 = points[face[i + 1] as usize] - v0;
+                weighted += side1.cross(side2);
+            }
+            for &index in &face {
+                sums[index as usize] += weighted;
             }
-
-            res.push(polygon.iter().map(|&i| i as u32).collect());
         }
-        ensure!(res.len() == expected_num, MismatchedNumberUvSnafu);
-        res.shrink_to_fit();
-        Ok(res)
+
+        let normals = (sums.into_iter())
+            .map(|sum| Normal::normalize(sum).unwrap_or(Normal::z_direction()))
+            .collect::<Vec<_>>();
+        Self::new_impl(
+            normals.into(),
+            Arc::clone(&vertices.triangles),
+            Arc::clone(&vertices.polygons),
+        )
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AttributeIndexError {
+    MismatchedNumber,
+    OutOfBound { face: usize, index: usize },
+}
+
 #[derive(Debug, Snafu, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum TryNewMeshError {
@@ -222,3 +334,13 @@ pub enum TryAddMeshUvCoordinateError {
     #[snafu(display("index {index} for UV coordinate in face {face} is out of bound"))]
     OutOfBound { face: usize, index: usize },
 }
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[snafu(context(suffix(NormalSnafu)))]
+#[non_exhaustive]
+pub enum TryAddMeshNormalError {
+    #[snafu(display("the number of normals is not same as vertices"))]
+    MismatchedNumber,
+    #[snafu(display("index {index} for normal in face {face} is out of bound"))]
+    OutOfBound { face: usize, index: usize },
+}
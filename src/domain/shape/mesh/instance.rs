@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::domain::math::transformation::{Rotation, Scaling, Sequential, Translation};
+use crate::domain::math::transformation::{Rotation, Scaling, Sequential, Shear, Translation};
 use crate::domain::shape::mesh::MeshConstructor;
 use crate::domain::shape::util::{ShapeConstructor, ShapeContainer, ShapeId};
 
@@ -36,6 +36,13 @@ impl MeshInstanceConstructor {
         }
     }
 
+    pub fn shear(self, shear: Shear) -> Self {
+        Self {
+            transformation: self.transformation.with_shear(shear),
+            ..self
+        }
+    }
+
     pub fn rotate(self, rotation: Rotation) -> Self {
         Self {
             transformation: self.transformation.with_rotation(rotation),
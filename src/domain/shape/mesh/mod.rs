@@ -1,7 +1,12 @@
 mod constructor;
 mod data;
 mod instance;
+mod shared;
 
 pub use constructor::MeshConstructor;
-pub use data::{MeshData, MeshDataComponent, TryAddMeshUvCoordinateError, TryNewMeshError};
+pub use data::{
+    MeshData, MeshDataComponent, TryAddMeshNormalError, TryAddMeshUvCoordinateError,
+    TryNewMeshError,
+};
 pub use instance::MeshInstanceConstructor;
+pub use shared::SharedMesh;
@@ -0,0 +1,296 @@
+use std::sync::Arc;
+
+use crate::domain::material::primitive::Emissive;
+use crate::domain::math::geometry::{Area, Direction, Distance, Normal, Point};
+use crate::domain::math::numeric::{DisRange, Val};
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, RayIntersectionPart};
+use crate::domain::sampling::Sampleable;
+use crate::domain::sampling::light::LightSampling;
+use crate::domain::sampling::photon::PhotonSampling;
+use crate::domain::sampling::point::PointSampling;
+use crate::domain::scene::bvh::{Bvh, BvhConfig};
+use crate::domain::scene::pool::ShapePool;
+use crate::domain::shape::def::{BoundingBox, RefDynShape, Shape, ShapeKind};
+use crate::domain::shape::util::{ShapeConstructor, ShapeContainer, ShapeId};
+
+/// Light sampling is not supported, so emissive shared meshes are only found by hitting them.
+#[derive(Debug, Clone)]
+pub struct SharedMesh {
+    inner: Arc<SharedMeshInner>,
+}
+
+#[derive(Debug)]
+struct SharedMeshInner {
+    faces: ShapePool,
+    ids: Vec<ShapeId>,
+    bvh: Bvh<ShapeId>,
+    bounding_box: Option<BoundingBox>,
+    area: Area,
+}
+
+impl SharedMesh {
+    pub fn new<C>(constructor: C) -> Self
+    where
+        C: ShapeConstructor,
+    {
+        Self::with_bvh_config(constructor, &BvhConfig::default())
+    }
+
+    pub fn with_bvh_config<C>(constructor: C, config: &BvhConfig) -> Self
+    where
+        C: ShapeConstructor,
+    {
+        let mut faces = ShapePool::default();
+        let ids = Box::new(constructor).construct(&mut faces);
+
+        let mut bboxes = Vec::with_capacity(ids.len());
+        let mut unboundeds = Vec::new();
+        let mut area = Area::zero();
+        for id in &ids {
+            let face = faces.get_shape(*id).unwrap();
+            area = area + face.area();
+            match face.bounding_box() {
+                Some(bbox) => bboxes.push((*id, bbox)),
+                None => unboundeds.push(*id),
+            }
+        }
+        let bvh = Bvh::new(config, bboxes, unboundeds.clone());
+        let bounding_box = if unboundeds.is_empty() {
+            bvh.bounding_box().cloned()
+        } else {
+            None
+        };
+
+        Self {
+            inner: Arc::new(SharedMeshInner {
+                faces,
+                ids,
+                bvh,
+                bounding_box,
+                area,
+            }),
+        }
+    }
+
+    pub fn num_faces(&self) -> usize {
+        self.inner.ids.len()
+    }
+
+    fn lies_on(face: &RefDynShape, position: Point) -> bool {
+        const OFFSET: Val = Val(1e-6);
+        let normal = face.normal(position);
+        let ray = Ray::new(
+            position + normal.to_vector() * OFFSET,
+            -Direction::from(normal),
+        );
+        let range =
+            DisRange::inclusive(Distance::zero(), Distance::new(OFFSET * Val(2.0)).unwrap());
+        face.hit_part(&ray, range).is_some()
+    }
+
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Shape for SharedMesh {
+    fn kind(&self) -> ShapeKind {
+        ShapeKind::SharedMesh
+    }
+
+    fn hit_part<'a>(&self, ray: &'a Ray, range: DisRange) -> Option<RayIntersectionPart<'a>> {
+        let (intersection, id) = self.inner.bvh.search(ray, range, &self.inner.faces)?;
+        Some(RayIntersectionPart::new(intersection.distance(), ray).with_face(Some(id)))
+    }
+
+    fn complete_part(&self, part: RayIntersectionPart) -> RayIntersection {
+        let face = (part.face())
+            .and_then(|id| self.inner.faces.get_shape(id))
+            .expect("part should be produced by SharedMesh::hit_part");
+        let range = DisRange::inclusive(part.distance(), part.distance());
+        face.hit(part.ray(), range)
+            .expect("face should be hit again at the same distance")
+    }
+
+    fn area(&self) -> Area {
+        self.inner.area
+    }
+
+    fn normal(&self, position: Point) -> Normal {
+        let candidates = self.inner.bvh.search_containing(position);
+        let mut faces = (candidates.into_iter()).map(|id| self.inner.faces.get_shape(id).unwrap());
+        let face = faces.clone().find(|face| Self::lies_on(face, position));
+        (face.or_else(|| faces.next()))
+            .or_else(|| self.inner.faces.get_shape(self.inner.ids[0]))
+            .expect("shared mesh should have at least one face")
+            .normal(position)
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        self.inner.bounding_box.clone()
+    }
+}
+
+impl Sampleable for SharedMesh {
+    fn get_point_sampler(&self, _shape_id: ShapeId) -> Option<Box<dyn PointSampling>> {
+        None
+    }
+
+    fn get_light_sampler(&self, _shape_id: ShapeId) -> Option<Box<dyn LightSampling>> {
+        None
+    }
+
+    fn get_photon_sampler(
+        &self,
+        _shape_id: ShapeId,
+        _emissive: Emissive,
+    ) -> Option<Box<dyn PhotonSampling>> {
+        None
+    }
+}
+
+impl PartialEq for SharedMesh {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other)
+    }
+}
+
+impl Eq for SharedMesh {}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::core::Albedo;
+    use crate::domain::material::primitive::Diffuse;
+    use crate::domain::math::algebra::{Product, Vector};
+    use crate::domain::math::geometry::{Direction, Distance};
+    use crate::domain::math::transformation::{Sequential, Translation};
+    use crate::domain::scene::entity::{
+        BvhEntitySceneBuilder, EntitySceneBuilder, TypedEntitySceneBuilder,
+    };
+    use crate::domain::shape::def::DynShape;
+    use crate::domain::shape::mesh::MeshConstructor;
+    use crate::domain::shape::util::Instance;
+
+    use super::*;
+
+    fn pyramid() -> MeshConstructor {
+        MeshConstructor::new(
+            vec![
+                Point::new(Val(0.5), Val(0.0), Val(0.5)),
+                Point::new(Val(-0.5), Val(0.0), Val(0.5)),
+                Point::new(Val(-0.5), Val(0.0), Val(-0.5)),
+                Point::new(Val(0.5), Val(0.0), Val(-0.5)),
+                Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            ],
+            vec![
+                vec![0, 1, 2, 3],
+                vec![0, 1, 4],
+                vec![1, 2, 4],
+                vec![2, 3, 4],
+                vec![3, 0, 4],
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn shared_mesh_hit_succeeds_matching_mesh_faces() {
+        let mesh = SharedMesh::new(pyramid());
+        assert_eq!(mesh.num_faces(), 5);
+        let bbox = mesh.bounding_box().unwrap();
+        assert_eq!(bbox.max(), Point::new(Val(0.5), Val(1.0), Val(0.5)));
+
+        let ray = Ray::new(
+            Point::new(Val(0.0), Val(2.0), Val(0.0)),
+            -Direction::y_direction(),
+        );
+        let intersection = mesh.hit(&ray, DisRange::positive()).unwrap();
+        assert_eq!(intersection.distance(), Distance::new(Val(1.0)).unwrap());
+        assert_eq!(
+            intersection.position(),
+            Point::new(Val(0.0), Val(1.0), Val(0.0))
+        );
+
+        let ray = Ray::new(
+            Point::new(Val(0.0), Val(-1.0), Val(0.0)),
+            Direction::y_direction(),
+        );
+        let intersection = mesh.hit(&ray, DisRange::positive()).unwrap();
+        assert_eq!(intersection.normal(), -Normal::y_direction());
+
+        let ray = Ray::new(
+            Point::new(Val(2.0), Val(-1.0), Val(0.0)),
+            Direction::y_direction(),
+        );
+        assert!(mesh.hit(&ray, DisRange::positive()).is_none());
+    }
+
+    #[test]
+    fn shared_mesh_normal_succeeds_picking_face_under_overlapping_bounds() {
+        let mesh = SharedMesh::new(pyramid());
+        let third = Val(1.0) / Val(3.0);
+        let cases = [
+            (
+                Point::new(Val(0.0), third, third),
+                Vector::new(Val(0.0), Val(1.0), Val(2.0)),
+            ),
+            (
+                Point::new(-third, third, Val(0.0)),
+                Vector::new(Val(-2.0), Val(1.0), Val(0.0)),
+            ),
+            (
+                Point::new(Val(0.0), third, -third),
+                Vector::new(Val(0.0), Val(1.0), Val(-2.0)),
+            ),
+            (
+                Point::new(third, third, Val(0.0)),
+                Vector::new(Val(2.0), Val(1.0), Val(0.0)),
+            ),
+        ];
+        for (position, expected) in cases {
+            let expected = Direction::normalize(expected).unwrap();
+            let normal = Direction::from(mesh.normal(position));
+            assert_eq!(normal.dot(expected).abs(), Val(1.0));
+        }
+    }
+
+    #[test]
+    fn shared_mesh_succeeds_instancing_without_duplicating_faces() {
+        let prototype = Arc::new(DynShape::from(SharedMesh::new(pyramid())));
+        let mut builder = BvhEntitySceneBuilder::new();
+        for i in 0..1000 {
+            let offset = Vector::new(
+                Val::from(i % 40) * Val(2.0),
+                Val(0.0),
+                Val::from(i / 40) * Val(2.0),
+            );
+            let transformation = Sequential::default().with_translation(Translation::new(offset));
+            builder.add(
+                Instance::new(prototype.clone(), transformation),
+                Diffuse::new(Albedo::WHITE),
+            );
+        }
+        let scene = builder.build();
+
+        assert_eq!(Arc::strong_count(&prototype), 1001);
+        let stats = scene.stats();
+        assert_eq!(stats.entities(), 1000);
+        assert_eq!(stats.triangles(), 0);
+
+        for i in 0..1000 {
+            let apex = Point::new(
+                Val::from(i % 40) * Val(2.0),
+                Val(1.0),
+                Val::from(i / 40) * Val(2.0),
+            );
+            let ray = Ray::new(
+                apex + Vector::new(Val(0.0), Val(1.0), Val(0.0)),
+                -Direction::y_direction(),
+            );
+            let (intersection, id) = scene.find_intersection(&ray, DisRange::positive()).unwrap();
+            assert_eq!(id.shape_id().kind(), ShapeKind::Instance);
+            assert_eq!(intersection.position(), apex);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod def;
 pub mod mesh;
 pub mod primitive;
+pub mod sdf;
 pub mod util;
@@ -98,21 +98,18 @@ impl Shape for Aabb {
     }
 
     fn normal(&self, position: Point) -> Normal {
-        if position.x() == self.min.x() {
-            -Normal::x_direction()
-        } else if position.x() == self.max.x() {
-            Normal::x_direction()
-        } else if position.y() == self.min.y() {
-            -Normal::y_direction()
-        } else if position.y() == self.max.y() {
-            Normal::y_direction()
-        } else if position.z() == self.min.z() {
-            -Normal::z_direction()
-        } else if position.z() == self.max.z() {
-            Normal::z_direction()
-        } else {
-            Normal::x_direction()
-        }
+        let faces = [
+            (position.x() - self.min.x(), -Normal::x_direction()),
+            (self.max.x() - position.x(), Normal::x_direction()),
+            (position.y() - self.min.y(), -Normal::y_direction()),
+            (self.max.y() - position.y(), Normal::y_direction()),
+            (position.z() - self.min.z(), -Normal::z_direction()),
+            (self.max.z() - position.z(), Normal::z_direction()),
+        ];
+        (faces.into_iter())
+            .min_by_key(|(offset, _)| offset.abs())
+            .map(|(_, normal)| normal)
+            .unwrap()
     }
 
     fn bounding_box(&self) -> Option<BoundingBox> {
@@ -0,0 +1,323 @@
+use std::ops::RangeBounds;
+
+use getset::CopyGetters;
+use snafu::prelude::*;
+
+use crate::domain::material::primitive::Emissive;
+use crate::domain::math::algebra::{Product, Vector};
+use crate::domain::math::geometry::{Area, Distance, Normal, Point};
+use crate::domain::math::numeric::{DisRange, Val};
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, RayIntersectionPart, SurfaceSide};
+use crate::domain::sampling::Sampleable;
+use crate::domain::sampling::light::{LightSamplerAdapter, LightSampling};
+use crate::domain::sampling::photon::{PhotonSamplerAdapter, PhotonSampling};
+use crate::domain::sampling::point::{BilinearPatchPointSampler, PointSampling};
+use crate::domain::shape::def::{BoundingBox, Shape, ShapeKind};
+use crate::domain::shape::util::ShapeId;
+use crate::domain::texture::def::UvCoordinate;
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct BilinearPatch {
+    corner00: Point,
+    corner10: Point,
+    corner01: Point,
+    corner11: Point,
+    #[getset(skip)]
+    area: Val,
+}
+
+impl BilinearPatch {
+    const AREA_RESOLUTION: usize = 16;
+    const LOCATE_ITERATIONS: usize = 16;
+
+    pub fn new(
+        corner00: Point,
+        corner10: Point,
+        corner01: Point,
+        corner11: Point,
+    ) -> Result<Self, TryNewBilinearPatchError> {
+        let mut patch = Self {
+            corner00,
+            corner10,
+            corner01,
+            corner11,
+            area: Val(0.0),
+        };
+        for (u, v) in [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)] {
+            let (du, dv) = patch.partial_derivatives(Val(u), Val(v));
+            ensure!(!du.is_parallel_to(dv), DegenerateCornerSnafu);
+        }
+        patch.area = patch.integrate_area();
+        Ok(patch)
+    }
+
+    pub fn point_at(&self, u: Val, v: Val) -> Point {
+        let bottom = self.corner00.into_vector() * (Val(1.0) - u) + self.corner10.into_vector() * u;
+        let top = self.corner01.into_vector() * (Val(1.0) - u) + self.corner11.into_vector() * u;
+        Point::from(bottom * (Val(1.0) - v) + top * v)
+    }
+
+    pub fn partial_derivatives(&self, u: Val, v: Val) -> (Vector, Vector) {
+        let du =
+            (self.corner10 - self.corner00) * (Val(1.0) - v) + (self.corner11 - self.corner01) * v;
+        let dv =
+            (self.corner01 - self.corner00) * (Val(1.0) - u) + (self.corner11 - self.corner10) * u;
+        (du, dv)
+    }
+
+    pub fn normal_at(&self, u: Val, v: Val) -> Normal {
+        let (du, dv) = self.partial_derivatives(u, v);
+        Normal::normalize(du.cross(dv)).unwrap_or(Normal::z_direction())
+    }
+
+    pub fn locate(&self, position: Point) -> (Val, Val) {
+        let (mut u, mut v) = (Val(0.5), Val(0.5));
+        for _ in 0..Self::LOCATE_ITERATIONS {
+            let residual = self.point_at(u, v) - position;
+            let (du, dv) = self.partial_derivatives(u, v);
+            let (a11, a12, a22) = (du.dot(du), du.dot(dv), dv.dot(dv));
+            let (b1, b2) = (du.dot(residual), dv.dot(residual));
+            let det = a11 * a22 - a12 * a12;
+            if det == Val(0.0) {
+                break;
+            }
+            u -= (a22 * b1 - a12 * b2) / det;
+            v -= (a11 * b2 - a12 * b1) / det;
+        }
+        (u, v)
+    }
+
+    fn calc_ray_intersection(&self, ray: &Ray, range: DisRange) -> Option<(Distance, Val, Val)> {
+        let direction = ray.direction().to_vector();
+        let e10 = self.corner10 - self.corner00;
+        let e11 = self.corner11 - self.corner10;
+        let e00 = self.corner01 - self.corner00;
+        let qn = e10.cross(self.corner01 - self.corner11);
+        let q00 = self.corner00 - ray.start();
+        let q10 = self.corner10 - ray.start();
+
+        let a = q00.cross(direction).dot(e00);
+        let c = qn.dot(direction);
+        let b = q10.cross(direction).dot(e11) - (a + c);
+        let discriminant = b * b - Val(4.0) * a * c;
+        if discriminant < Val(0.0) {
+            return None;
+        }
+
+        let roots = if c == Val(0.0) {
+            [(b != Val(0.0)).then(|| -a / b), None]
+        } else {
+            let sqrt = discriminant.sqrt();
+            let q = (-b - if b < Val(0.0) { -sqrt } else { sqrt }) / Val(2.0);
+            [Some(q / c), (q != Val(0.0)).then(|| a / q)]
+        };
+
+        (roots.into_iter().flatten())
+            .filter(|u| (Val(0.0)..=Val(1.0)).contains(u))
+            .filter_map(|u| {
+                let pa = q00 + (q10 - q00) * u;
+                let pb = e00 + (e11 - e00) * u;
+                let n = direction.cross(pb);
+                let det = n.dot(n);
+                if det == Val(0.0) {
+                    return None;
+                }
+                let n = n.cross(pa);
+                let v = n.dot(direction) / det;
+                if !(Val(0.0)..=Val(1.0)).contains(&v) {
+                    return None;
+                }
+                let distance = Distance::new(n.dot(pb) / det).ok()?;
+                range.contains(&distance).then_some((distance, u, v))
+            })
+            .min_by_key(|(distance, _, _)| *distance)
+    }
+
+    fn integrate_area(&self) -> Val {
+        let n = Self::AREA_RESOLUTION;
+        let step = Val::from(n).recip();
+        let mut area = Val(0.0);
+        for i in 0..n {
+            for j in 0..n {
+                let u = (Val::from(i) + Val(0.5)) * step;
+                let v = (Val::from(j) + Val(0.5)) * step;
+                let (du, dv) = self.partial_derivatives(u, v);
+                area += du.cross(dv).norm() * step * step;
+            }
+        }
+        area
+    }
+}
+
+impl Shape for BilinearPatch {
+    fn kind(&self) -> ShapeKind {
+        ShapeKind::BilinearPatch
+    }
+
+    fn hit_part<'a>(&self, ray: &'a Ray, range: DisRange) -> Option<RayIntersectionPart<'a>> {
+        let (distance, _, _) = self.calc_ray_intersection(ray, range)?;
+        Some(RayIntersectionPart::new(distance, ray))
+    }
+
+    fn complete_part(&self, part: RayIntersectionPart) -> RayIntersection {
+        let position = part.ray().at(part.distance());
+        let range = DisRange::inclusive(part.distance(), part.distance());
+        let (u, v) = match self.calc_ray_intersection(part.ray(), range) {
+            Some((_, u, v)) => (u, v),
+            None => self.locate(position),
+        };
+
+        let normal = self.normal_at(u, v);
+        let (normal, side) = if part.ray().direction().dot(normal) < Val(0.0) {
+            (normal, SurfaceSide::Front)
+        } else {
+            (-normal, SurfaceSide::Back)
+        };
+        let (du, _) = self.partial_derivatives(u, v);
+        RayIntersection::new(part.distance(), position, normal, side)
+            .with_uv(UvCoordinate::clamp(u, v))
+            .with_tangent(du)
+    }
+
+    fn area(&self) -> Area {
+        Area::new(self.area).unwrap()
+    }
+
+    fn normal(&self, position: Point) -> Normal {
+        let (u, v) = self.locate(position);
+        self.normal_at(u.clamp(Val(0.0), Val(1.0)), v.clamp(Val(0.0), Val(1.0)))
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        let corners = [self.corner00, self.corner10, self.corner01, self.corner11];
+        let min = (corners.iter()).fold(corners[0], |min, c| min.component_min(c));
+        let max = (corners.iter()).fold(corners[0], |max, c| max.component_max(c));
+        Some(BoundingBox::new(min, max))
+    }
+}
+
+impl Sampleable for BilinearPatch {
+    fn get_point_sampler(&self, shape_id: ShapeId) -> Option<Box<dyn PointSampling>> {
+        Some(Box::new(BilinearPatchPointSampler::new(
+            shape_id,
+            self.clone(),
+        )))
+    }
+
+    fn get_light_sampler(&self, shape_id: ShapeId) -> Option<Box<dyn LightSampling>> {
+        let inner = BilinearPatchPointSampler::new(shape_id, self.clone());
+        Some(Box::new(LightSamplerAdapter::new(inner)))
+    }
+
+    fn get_photon_sampler(
+        &self,
+        shape_id: ShapeId,
+        emissive: Emissive,
+    ) -> Option<Box<dyn PhotonSampling>> {
+        let inner = BilinearPatchPointSampler::new(shape_id, self.clone());
+        let sampler = PhotonSamplerAdapter::new(inner, emissive);
+        Some(Box::new(sampler))
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewBilinearPatchError {
+    #[snafu(display("bilinear patch has a corner with parallel or zero edges"))]
+    DegenerateCorner,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::Direction;
+    use crate::domain::shape::primitive::Polygon;
+
+    use super::*;
+
+    fn saddle() -> BilinearPatch {
+        BilinearPatch::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Point::new(Val(1.0), Val(0.0), Val(1.0)),
+            Point::new(Val(0.0), Val(1.0), Val(1.0)),
+            Point::new(Val(1.0), Val(1.0), Val(0.0)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn bilinear_patch_new_fails_when_corner_is_degenerate() {
+        assert!(matches!(
+            BilinearPatch::new(
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(1.0), Val(0.0), Val(0.0)),
+                Point::new(Val(2.0), Val(0.0), Val(0.0)),
+                Point::new(Val(1.0), Val(1.0), Val(0.0)),
+            ),
+            Err(TryNewBilinearPatchError::DegenerateCorner),
+        ));
+    }
+
+    #[test]
+    fn bilinear_patch_hit_succeeds_where_flat_polygon_misses() {
+        let patch = saddle();
+        let ray = Ray::new(
+            Point::new(Val(0.25), Val(0.5), Val(2.0)),
+            -Direction::z_direction(),
+        );
+
+        let intersection = patch.hit(&ray, DisRange::positive()).unwrap();
+        let expected = patch.point_at(Val(0.25), Val(0.5));
+        assert_eq!(intersection.position(), expected);
+        assert_eq!(intersection.distance().value(), Val(2.0) - expected.z());
+        let uv = intersection.uv().unwrap();
+        assert_eq!((uv.u(), uv.v()), (Val(0.25), Val(0.5)));
+        assert_eq!(intersection.side(), SurfaceSide::Front);
+
+        let polygon = Polygon::new([
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Point::new(Val(1.0), Val(0.0), Val(0.0)),
+            Point::new(Val(1.0), Val(1.0), Val(0.0)),
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+        ])
+        .unwrap();
+        let ray = Ray::new(
+            Point::new(Val(-1.0), Val(0.25), Val(0.5)),
+            Direction::x_direction(),
+        );
+        assert!(polygon.hit(&ray, DisRange::positive()).is_none());
+        let intersection = patch.hit(&ray, DisRange::positive()).unwrap();
+        assert_eq!(
+            intersection.position(),
+            Point::new(Val(0.5), Val(0.25), Val(0.5))
+        );
+    }
+
+    #[test]
+    fn bilinear_patch_area_succeeds_for_planar_patch() {
+        let patch = BilinearPatch::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Point::new(Val(2.0), Val(0.0), Val(0.0)),
+            Point::new(Val(0.0), Val(3.0), Val(0.0)),
+            Point::new(Val(2.0), Val(3.0), Val(0.0)),
+        )
+        .unwrap();
+        assert_eq!(patch.area(), Area::new(Val(6.0)).unwrap());
+        assert_eq!(
+            patch.normal(Point::new(Val(1.0), Val(1.0), Val(0.0))),
+            Normal::z_direction()
+        );
+    }
+
+    #[test]
+    fn bilinear_patch_bounding_box_succeeds() {
+        assert_eq!(
+            saddle().bounding_box(),
+            Some(BoundingBox::new(
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(1.0), Val(1.0), Val(1.0)),
+            )),
+        );
+    }
+}
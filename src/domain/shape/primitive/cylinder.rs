@@ -0,0 +1,290 @@
+use std::ops::RangeBounds;
+
+use getset::CopyGetters;
+use snafu::prelude::*;
+
+use crate::domain::material::primitive::Emissive;
+use crate::domain::math::algebra::{Product, UnitVector, Vector};
+use crate::domain::math::geometry::{Area, Distance, Normal, Point};
+use crate::domain::math::numeric::{DisRange, Val};
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, RayIntersectionPart, SurfaceSide};
+use crate::domain::sampling::Sampleable;
+use crate::domain::sampling::light::{LightSamplerAdapter, LightSampling};
+use crate::domain::sampling::photon::{PhotonSamplerAdapter, PhotonSampling};
+use crate::domain::sampling::point::{CylinderPointSampler, PointSampling};
+use crate::domain::shape::def::{BoundingBox, Shape, ShapeKind};
+use crate::domain::shape::util::ShapeId;
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct Cylinder {
+    endpoint0: Point,
+    endpoint1: Point,
+    radius: Val,
+    axis: UnitVector,
+    height: Val,
+    capped: bool,
+}
+
+impl Cylinder {
+    pub fn new(
+        endpoint0: Point,
+        endpoint1: Point,
+        radius: Val,
+    ) -> Result<Self, TryNewCylinderError> {
+        ensure!(radius > Val(0.0), InvalidRadiusSnafu);
+        let axis = UnitVector::normalize(endpoint1 - endpoint0)
+            .ok()
+            .context(ZeroLengthAxisSnafu)?;
+        let height = (endpoint1 - endpoint0).norm();
+        Ok(Self {
+            endpoint0,
+            endpoint1,
+            radius,
+            axis,
+            height,
+            capped: true,
+        })
+    }
+
+    pub fn with_capped(self, capped: bool) -> Self {
+        Self { capped, ..self }
+    }
+
+    fn decompose(&self, vector: Vector) -> (Val, Vector) {
+        let axial = vector.dot(self.axis);
+        (axial, vector - axial * self.axis)
+    }
+
+    fn hit_side(&self, ray: &Ray, range: DisRange) -> Option<Distance> {
+        let (dir_axial, dir_perp) = self.decompose(ray.direction().into());
+        let (offset_axial, offset_perp) = self.decompose(ray.start() - self.endpoint0);
+
+        let a = dir_perp.norm_squared();
+        if a == Val(0.0) {
+            return None;
+        }
+        let b = Val(2.0) * dir_perp.dot(offset_perp);
+        let c = offset_perp.norm_squared() - self.radius.powi(2);
+        let discriminant = b * b - Val(4.0) * a * c;
+        if discriminant < Val(0.0) {
+            return None;
+        }
+
+        let discriminant_sqrt = discriminant.max(Val(0.0)).sqrt();
+        let x1 = (-b - discriminant_sqrt) / (Val(2.0) * a);
+        let x2 = (-b + discriminant_sqrt) / (Val(2.0) * a);
+        [x1, x2].into_iter().find_map(|x| {
+            let axial = offset_axial + x * dir_axial;
+            Distance::new(x)
+                .ok()
+                .filter(|d| range.contains(d))
+                .filter(|_| (Val(0.0)..=self.height).contains(&axial))
+        })
+    }
+
+    fn hit_cap(&self, ray: &Ray, range: DisRange, cap_axial: Val) -> Option<Distance> {
+        let (dir_axial, dir_perp) = self.decompose(ray.direction().into());
+        let (offset_axial, offset_perp) = self.decompose(ray.start() - self.endpoint0);
+        if dir_axial == Val(0.0) {
+            return None;
+        }
+
+        let x = (cap_axial - offset_axial) / dir_axial;
+        let perp = offset_perp + x * dir_perp;
+        Distance::new(x)
+            .ok()
+            .filter(|d| range.contains(d))
+            .filter(|_| perp.norm_squared() <= self.radius.powi(2))
+    }
+}
+
+impl Shape for Cylinder {
+    fn kind(&self) -> ShapeKind {
+        ShapeKind::Cylinder
+    }
+
+    fn hit_part<'a>(&self, ray: &'a Ray, range: DisRange) -> Option<RayIntersectionPart<'a>> {
+        let side = self.hit_side(ray, range);
+        let distance = if self.capped {
+            let cap0 = self.hit_cap(ray, range, Val(0.0));
+            let cap1 = self.hit_cap(ray, range, self.height);
+            [side, cap0, cap1].into_iter().flatten().min()?
+        } else {
+            side?
+        };
+        Some(RayIntersectionPart::new(distance, ray))
+    }
+
+    fn complete_part(&self, part: RayIntersectionPart) -> RayIntersection {
+        let position = part.ray().at(part.distance());
+        let normal = self.normal(position);
+        let (normal, side) = if part.ray().direction().dot(normal) < Val(0.0) {
+            (normal, SurfaceSide::Front)
+        } else {
+            (-normal, SurfaceSide::Back)
+        };
+        RayIntersection::new(part.distance(), position, normal, side)
+    }
+
+    fn area(&self) -> Area {
+        let side = Val(2.0) * Val::PI * self.radius * self.height;
+        let caps = if self.capped {
+            Val(2.0) * Val::PI * self.radius.powi(2)
+        } else {
+            Val(0.0)
+        };
+        Area::new(side + caps).unwrap()
+    }
+
+    fn normal(&self, position: Point) -> Normal {
+        let (axial, perp) = self.decompose(position - self.endpoint0);
+        if self.capped && perp.norm_squared() < self.radius.powi(2) {
+            if axial < Val(0.5) * self.height {
+                -Normal::from(self.axis)
+            } else {
+                Normal::from(self.axis)
+            }
+        } else {
+            Normal::normalize(perp).unwrap_or(Normal::from(self.axis.orthonormal_basis().0))
+        }
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        let extent = |a: Val| self.radius * (Val(1.0) - a.powi(2)).max(Val(0.0)).sqrt();
+        let d = Vector::new(
+            extent(self.axis.x()),
+            extent(self.axis.y()),
+            extent(self.axis.z()),
+        );
+        let min = self.endpoint0.component_min(&self.endpoint1);
+        let max = self.endpoint0.component_max(&self.endpoint1);
+        Some(BoundingBox::new(min - d, max + d))
+    }
+}
+
+impl Sampleable for Cylinder {
+    fn get_point_sampler(&self, shape_id: ShapeId) -> Option<Box<dyn PointSampling>> {
+        Some(Box::new(CylinderPointSampler::new(shape_id, self.clone())))
+    }
+
+    fn get_light_sampler(&self, shape_id: ShapeId) -> Option<Box<dyn LightSampling>> {
+        let inner = CylinderPointSampler::new(shape_id, self.clone());
+        Some(Box::new(LightSamplerAdapter::new(inner)))
+    }
+
+    fn get_photon_sampler(
+        &self,
+        shape_id: ShapeId,
+        emissive: Emissive,
+    ) -> Option<Box<dyn PhotonSampling>> {
+        let inner = CylinderPointSampler::new(shape_id, self.clone());
+        let sampler = PhotonSamplerAdapter::new(inner, emissive);
+        Some(Box::new(sampler))
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewCylinderError {
+    #[snafu(display("radius is not positive"))]
+    InvalidRadius,
+    #[snafu(display("endpoints of the axis coincide"))]
+    ZeroLengthAxis,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::Direction;
+
+    use super::*;
+
+    #[test]
+    fn cylinder_new_fails_when_radius_is_invalid() {
+        assert!(matches!(
+            Cylinder::new(
+                Point::default(),
+                Point::new(Val(0.0), Val(1.0), Val(0.0)),
+                Val(0.0)
+            ),
+            Err(TryNewCylinderError::InvalidRadius),
+        ));
+    }
+
+    #[test]
+    fn cylinder_new_fails_when_axis_has_zero_length() {
+        assert!(matches!(
+            Cylinder::new(Point::default(), Point::default(), Val(1.0)),
+            Err(TryNewCylinderError::ZeroLengthAxis),
+        ));
+    }
+
+    #[test]
+    fn cylinder_hit_all_succeeds_returning_side_intersections() {
+        let cylinder = Cylinder::new(
+            Point::new(Val(0.0), Val(-1.0), Val(0.0)),
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            Val(1.0),
+        )
+        .unwrap();
+        let ray = Ray::new(
+            Point::new(Val(-3.0), Val(0.0), Val(0.0)),
+            Direction::x_direction(),
+        );
+
+        let intersections = cylinder.hit_all(&ray, DisRange::positive());
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(
+            intersections[0].distance(),
+            Distance::new(Val(2.0)).unwrap()
+        );
+        assert_eq!(intersections[0].normal(), -Normal::x_direction());
+        assert_eq!(intersections[0].side(), SurfaceSide::Front);
+        assert_eq!(
+            intersections[1].distance(),
+            Distance::new(Val(4.0)).unwrap()
+        );
+        assert_eq!(intersections[1].normal(), -Normal::x_direction());
+        assert_eq!(intersections[1].side(), SurfaceSide::Back);
+    }
+
+    #[test]
+    fn cylinder_hit_succeeds_returning_cap_intersection_inside() {
+        let cylinder = Cylinder::new(
+            Point::new(Val(0.0), Val(-1.0), Val(0.0)),
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            Val(1.0),
+        )
+        .unwrap();
+        let ray = Ray::new(
+            Point::new(Val(0.5), Val(0.0), Val(0.0)),
+            Direction::y_direction(),
+        );
+
+        let intersection = cylinder.hit(&ray, DisRange::positive()).unwrap();
+        assert_eq!(intersection.distance(), Distance::new(Val(1.0)).unwrap());
+        assert_eq!(intersection.normal(), -Normal::y_direction());
+        assert_eq!(intersection.side(), SurfaceSide::Back);
+
+        let uncapped = cylinder.with_capped(false);
+        assert!(uncapped.hit(&ray, DisRange::positive()).is_none());
+    }
+
+    #[test]
+    fn cylinder_bounding_box_succeeds() {
+        let cylinder = Cylinder::new(
+            Point::new(Val(0.0), Val(-1.0), Val(0.0)),
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            Val(0.5),
+        )
+        .unwrap();
+        assert_eq!(
+            cylinder.bounding_box(),
+            Some(BoundingBox::new(
+                Point::new(Val(-0.5), Val(-1.0), Val(-0.5)),
+                Point::new(Val(0.5), Val(1.0), Val(0.5)),
+            )),
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use getset::CopyGetters;
+use snafu::prelude::*;
+
+use crate::domain::material::primitive::Emissive;
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::geometry::{Area, Normal, Point};
+use crate::domain::math::numeric::{DisRange, Val};
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, RayIntersectionPart};
+use crate::domain::sampling::Sampleable;
+use crate::domain::sampling::light::{LightSamplerAdapter, LightSampling};
+use crate::domain::sampling::photon::{PhotonSamplerAdapter, PhotonSampling};
+use crate::domain::sampling::point::{DiskPointSampler, PointSampling};
+use crate::domain::shape::def::{BoundingBox, Shape, ShapeKind};
+use crate::domain::shape::util::ShapeId;
+
+use super::Plane;
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+pub struct Disk {
+    #[getset(get_copy = "pub")]
+    center: Point,
+    normal: Normal,
+    #[getset(get_copy = "pub")]
+    radius: Val,
+}
+
+impl Disk {
+    pub fn new(center: Point, normal: Normal, radius: Val) -> Result<Self, TryNewDiskError> {
+        ensure!(radius > Val(0.0), InvalidRadiusSnafu);
+        Ok(Self {
+            center,
+            normal,
+            radius,
+        })
+    }
+}
+
+impl Shape for Disk {
+    fn kind(&self) -> ShapeKind {
+        ShapeKind::Disk
+    }
+
+    fn hit_part<'a>(&self, ray: &'a Ray, range: DisRange) -> Option<RayIntersectionPart<'a>> {
+        let part = Plane::calc_ray_intersection_part(ray, range, &self.center, &self.normal)?;
+        let position = ray.at(part.distance());
+        if (position - self.center).norm_squared() <= self.radius.powi(2) {
+            Some(part)
+        } else {
+            None
+        }
+    }
+
+    fn complete_part(&self, part: RayIntersectionPart) -> RayIntersection {
+        Plane::complete_ray_intersection_part(part, &self.normal)
+    }
+
+    fn area(&self) -> Area {
+        Area::new(Val::PI * self.radius.powi(2)).unwrap()
+    }
+
+    fn normal(&self, _position: Point) -> Normal {
+        self.normal
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        let extent = |n: Val| self.radius * (Val(1.0) - n.powi(2)).max(Val(0.0)).sqrt();
+        let d = Vector::new(
+            extent(self.normal.x()),
+            extent(self.normal.y()),
+            extent(self.normal.z()),
+        );
+        Some(BoundingBox::new(self.center - d, self.center + d))
+    }
+}
+
+impl Sampleable for Disk {
+    fn get_point_sampler(&self, shape_id: ShapeId) -> Option<Box<dyn PointSampling>> {
+        Some(Box::new(DiskPointSampler::new(shape_id, self.clone())))
+    }
+
+    fn get_light_sampler(&self, shape_id: ShapeId) -> Option<Box<dyn LightSampling>> {
+        let inner = DiskPointSampler::new(shape_id, self.clone());
+        Some(Box::new(LightSamplerAdapter::new(inner)))
+    }
+
+    fn get_photon_sampler(
+        &self,
+        shape_id: ShapeId,
+        emissive: Emissive,
+    ) -> Option<Box<dyn PhotonSampling>> {
+        let inner = DiskPointSampler::new(shape_id, self.clone());
+        let sampler = PhotonSamplerAdapter::new(inner, emissive);
+        Some(Box::new(sampler))
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewDiskError {
+    #[snafu(display("radius is not positive"))]
+    InvalidRadius,
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use crate::domain::color::core::Spectrum;
+    use crate::domain::math::algebra::Product;
+    use crate::domain::math::geometry::{Direction, Distance, SpreadAngle};
+    use crate::domain::ray::event::SurfaceSide;
+
+    use super::*;
+
+    fn overhead_disk() -> Disk {
+        let center = Point::new(Val(0.0), Val(0.0), Val(1.0));
+        Disk::new(center, -Normal::z_direction(), Val(0.5)).unwrap()
+    }
+
+    fn lies_on(disk: &Disk, point: Point) -> bool {
+        let offset = point - disk.center();
+        offset.dot(disk.normal(point).to_vector()).abs() < Val(1e-6)
+            && offset.norm() <= disk.radius() + Val(1e-6)
+    }
+
+    #[test]
+    fn disk_new_fails_when_radius_is_invalid() {
+        assert!(matches!(
+            Disk::new(Point::default(), Normal::z_direction(), Val(-1.0)),
+            Err(TryNewDiskError::InvalidRadius),
+        ));
+    }
+
+    #[test]
+    fn disk_hit_succeeds() {
+        let disk = Disk::new(Point::default(), Normal::z_direction(), Val(1.0)).unwrap();
+
+        let ray = Ray::new(
+            Point::new(Val(0.5), Val(0.5), Val(2.0)),
+            -Direction::z_direction(),
+        );
+        let intersection = disk.hit(&ray, DisRange::positive()).unwrap();
+        assert_eq!(intersection.distance(), Distance::new(Val(2.0)).unwrap());
+        assert_eq!(intersection.normal(), Normal::z_direction());
+        assert_eq!(intersection.side(), SurfaceSide::Front);
+
+        let ray = Ray::new(
+            Point::new(Val(0.8), Val(0.8), Val(2.0)),
+            -Direction::z_direction(),
+        );
+        assert!(disk.hit(&ray, DisRange::positive()).is_none());
+    }
+
+    #[test]
+    fn disk_bounding_box_succeeds() {
+        let disk = Disk::new(Point::default(), Normal::y_direction(), Val(2.0)).unwrap();
+        assert_eq!(
+            disk.bounding_box(),
+            Some(BoundingBox::new(
+                Point::new(Val(-2.0), Val(0.0), Val(-2.0)),
+                Point::new(Val(2.0), Val(0.0), Val(2.0)),
+            )),
+        );
+    }
+
+    #[test]
+    fn disk_get_light_sampler_succeeds_sampling_by_area() {
+        let disk = overhead_disk();
+        let sampler = disk
+            .get_light_sampler(ShapeId::new(ShapeKind::Disk, 0))
+            .unwrap();
+        let intersection = RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::default(),
+            Normal::z_direction(),
+            SurfaceSide::Front,
+        );
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let sample = sampler
+                .sample_light_surface(&intersection, &mut rng)
+                .unwrap();
+            let ray = sample.ray_next();
+            let point = ray.at(sample.distance());
+            assert!(lies_on(&disk, point));
+
+            let cos = ray.direction().dot(disk.normal(point)).abs();
+            let pdf_area = sample.pdf() * cos / sample.distance().value().powi(2);
+            assert!((pdf_area * disk.area().value() - Val(1.0)).abs() < Val(1e-6));
+            assert_eq!(sampler.pdf_light_surface(&intersection, ray), sample.pdf());
+        }
+    }
+
+    #[test]
+    fn disk_get_photon_sampler_succeeds_emitting_lambertian_flux() {
+        let disk = overhead_disk();
+        let emissive = Emissive::new(Spectrum::broadcast(Val(2.0)), SpreadAngle::hemisphere());
+        let id = ShapeId::new(ShapeKind::Disk, 0);
+        let sampler = disk.get_photon_sampler(id, emissive).unwrap();
+
+        let flux = Val(2.0) * Val::PI * disk.area().value();
+        assert_eq!(sampler.area(), disk.area());
+        assert_eq!(sampler.power(), flux);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let sample = sampler.sample_photon(&mut rng).unwrap();
+            let photon = sample.photon();
+            assert!(lies_on(&disk, photon.start()));
+            assert!(photon.direction().dot(disk.normal(photon.start())) > Val(0.0));
+            assert_eq!(photon.throughput(), Spectrum::broadcast(flux));
+        }
+    }
+}
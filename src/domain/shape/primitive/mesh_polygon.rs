@@ -3,7 +3,7 @@ use std::sync::Arc;
 use smallvec::SmallVec;
 
 use crate::domain::material::primitive::Emissive;
-use crate::domain::math::algebra::Product;
+use crate::domain::math::algebra::{Product, Vector};
 use crate::domain::math::geometry::{Area, Normal, Point};
 use crate::domain::math::numeric::{DisRange, Val};
 use crate::domain::math::transformation::Transform;
@@ -16,9 +16,9 @@ use crate::domain::sampling::point::PointSampling;
 use crate::domain::shape::def::{BoundingBox, Shape, ShapeKind};
 use crate::domain::shape::mesh::MeshData;
 use crate::domain::shape::util::ShapeId;
-use crate::domain::texture::def::{UvCoordinate, UvCoordinateInterpolation};
+use crate::domain::texture::def::UvCoordinate;
 
-use super::Polygon;
+use super::{Polygon, Triangle};
 
 #[derive(Debug, Clone)]
 pub struct MeshPolygon {
@@ -39,18 +39,67 @@ impl MeshPolygon {
             .collect()
     }
 
-    fn get_uvs(&self) -> Option<(UvCoordinate, UvCoordinate, UvCoordinate)> {
-        let uv_component = self.data.uvs()?;
-        let uvs = uv_component.data();
-        let polygon = &uv_component.polygons()[self.index];
-        assert!(polygon.len() >= 3);
-        let uv0 = uvs[polygon[0] as usize];
-        let uv1 = uvs[polygon[1] as usize];
-        let uv2 = uvs[polygon[2] as usize];
-        Some((uv0, uv1, uv2))
+    fn locate_in_fan(&self, position: Point) -> (usize, (Val, Val, Val)) {
+        let vertices = self.get_transformed_vertices();
+        (1..(vertices.len() - 1))
+            .map(|i| {
+                let (v0, v1, v2) = (&vertices[0], &vertices[i], &vertices[i + 1]);
+                (i, Triangle::calc_barycentric(&position, v0, v1, v2))
+            })
+            .max_by_key(|(_, (w0, w1, w2))| (*w0).min(*w1).min(*w2))
+            .expect("polygon should have at least one fan triangle")
     }
 
-    fn to_polygon(&self) -> Polygon {
+    fn get_transformed_vertices(&self) -> SmallVec<[Point; 5]> {
+        let tr = self.data.transformation();
+        (self.get_vertices().into_iter())
+            .map(|v| tr.map_or(*v, |tr| v.transform(tr)))
+            .collect()
+    }
+
+    fn interpolate_normal(&self, position: Point) -> Option<Normal> {
+        let normal_component = self.data.normals()?;
+        let normals = normal_component.data();
+        let polygon = &normal_component.polygons()[self.index];
+        let tr = self.data.transformation();
+
+        let (i, (w0, w1, w2)) = self.locate_in_fan(position);
+        let normal_at = |k: usize| {
+            let normal = normals[polygon[k] as usize];
+            tr.map_or(normal, |tr| normal.transform(tr)).to_vector()
+        };
+        let normal = w0 * normal_at(0) + w1 * normal_at(i) + w2 * normal_at(i + 1);
+        Normal::normalize(normal).ok()
+    }
+
+    fn interpolate_uv(&self, position: Point) -> (UvCoordinate, Option<Vector>) {
+        let (i, (w0, w1, w2)) = self.locate_in_fan(position);
+        if let Some(uv_component) = self.data.uvs() {
+            let uvs = uv_component.data();
+            let polygon = &uv_component.polygons()[self.index];
+            let (uv0, uv1, uv2) = (
+                uvs[polygon[0] as usize],
+                uvs[polygon[i] as usize],
+                uvs[polygon[i + 1] as usize],
+            );
+            let u = w0 * uv0.u() + w1 * uv1.u() + w2 * uv2.u();
+            let v = w0 * uv0.v() + w1 * uv1.v() + w2 * uv2.v();
+
+            let vertices = self.get_transformed_vertices();
+            let fan = (&vertices[0], &vertices[i], &vertices[i + 1]);
+            let tangent = Triangle::calc_uv_tangent(fan, (uv0, uv1, uv2));
+            (UvCoordinate::unbounded(u, v), tangent)
+        } else {
+            let vertices = self.get_vertices();
+            let (p0, p1, p2) = (*vertices[0], *vertices[i], *vertices[i + 1]);
+            (
+                self.data.planar_uv(p0 + w1 * (p1 - p0) + w2 * (p2 - p0)),
+                None,
+            )
+        }
+    }
+
+    pub fn to_polygon(&self) -> Polygon {
         if let Some(tr) = self.data.transformation() {
             let vertices = self.get_vertices().into_iter().map(|v| v.transform(tr));
             Polygon::new(vertices).unwrap()
@@ -94,33 +143,19 @@ impl Shape for MeshPolygon {
             Normal::normalize((*vertices[1] - *vertices[0]).cross(*vertices[2] - *vertices[1]))
                 .expect("normal existence has been checked during mesh construction");
 
-        if let Some(tr) = self.data.transformation() {
-            let normal_tr = normal.transform(tr);
-            let res = Polygon::complete_ray_intersection_part(part, &normal_tr);
-
-            if let Some((uv0, uv1, uv2)) = self.get_uvs() {
-                let uv = UvCoordinateInterpolation::new()
-                    .push(vertices[0].transform(tr), uv0)
-                    .push(vertices[1].transform(tr), uv1)
-                    .push(vertices[2].transform(tr), uv2)
-                    .interpolate(res.position());
-                res.with_uv(uv)
-            } else {
-                res
-            }
-        } else {
-            let res = Polygon::complete_ray_intersection_part(part, &normal);
-
-            if let Some((uv0, uv1, uv2)) = self.get_uvs() {
-                let uv = UvCoordinateInterpolation::new()
-                    .push(*vertices[0], uv0)
-                    .push(*vertices[1], uv1)
-                    .push(*vertices[2], uv2)
-                    .interpolate(res.position());
-                res.with_uv(uv)
-            } else {
-                res
-            }
+        let res = match self.data.transformation() {
+            Some(tr) => Polygon::complete_ray_intersection_part(part, &normal.transform(tr)),
+            None => Polygon::complete_ray_intersection_part(part, &normal),
+        };
+        let res = match self.interpolate_uv(res.position()) {
+            (uv, Some(tangent)) => res.with_uv(uv).with_tangent(tangent),
+            (uv, None) => res.with_uv(uv),
+        };
+
+        match self.interpolate_normal(res.position()) {
+            Some(normal) if normal.dot(res.normal()) < Val(0.0) => res.with_normal(-normal),
+            Some(normal) => res.with_normal(normal),
+            None => res,
         }
     }
 
@@ -191,6 +226,7 @@ impl Eq for MeshPolygon {}
 
 #[cfg(test)]
 mod tests {
+    use crate::domain::math::geometry::Direction;
     use crate::domain::math::numeric::Val;
     use crate::domain::shape::mesh::MeshConstructor;
 
@@ -218,4 +254,36 @@ mod tests {
             )),
         );
     }
+
+    #[test]
+    fn mesh_polygon_complete_part_succeeds_deriving_tangent_from_uvs() {
+        let uv = |u, v| UvCoordinate::new(Val(u), Val(v)).unwrap();
+        let (_, polygons) = MeshConstructor::new(
+            vec![
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(2.0), Val(0.0), Val(0.0)),
+                Point::new(Val(2.0), Val(2.0), Val(0.0)),
+                Point::new(Val(0.0), Val(2.0), Val(0.0)),
+            ],
+            vec![vec![0, 1, 2, 3]],
+        )
+        .unwrap()
+        .with_uvs(
+            vec![uv(0.0, 0.0), uv(0.0, 1.0), uv(1.0, 1.0), uv(1.0, 0.0)],
+            vec![vec![0, 1, 2, 3]],
+        )
+        .unwrap()
+        .construct_impl(None);
+
+        let ray = Ray::new(
+            Point::new(Val(0.5), Val(1.5), Val(1.0)),
+            -Direction::z_direction(),
+        );
+        let part = polygons[0].hit_part(&ray, DisRange::positive()).unwrap();
+        let frame = polygons[0].complete_part(part).tangent_frame();
+        assert_eq!(
+            frame.tangent().to_vector(),
+            Vector::new(Val(0.0), Val(1.0), Val(0.0))
+        );
+    }
 }
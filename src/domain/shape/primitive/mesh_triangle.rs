@@ -14,7 +14,7 @@ use crate::domain::sampling::point::PointSampling;
 use crate::domain::shape::def::{BoundingBox, Shape, ShapeKind};
 use crate::domain::shape::mesh::MeshData;
 use crate::domain::shape::util::ShapeId;
-use crate::domain::texture::def::{UvCoordinate, UvCoordinateInterpolation};
+use crate::domain::texture::def::UvCoordinate;
 
 use super::Triangle;
 
@@ -48,7 +48,37 @@ impl MeshTriangle {
         Some((uv0, uv1, uv2))
     }
 
-    fn to_triangle(&self) -> Triangle {
+    fn get_normals(&self) -> Option<(Normal, Normal, Normal)> {
+        let normal_component = self.data.normals()?;
+        let normals = normal_component.data();
+        let triangle = &normal_component.triangles()[self.index];
+        let n0 = normals[triangle.0 as usize];
+        let n1 = normals[triangle.1 as usize];
+        let n2 = normals[triangle.2 as usize];
+        match self.data.transformation() {
+            Some(tr) => Some((n0.transform(tr), n1.transform(tr), n2.transform(tr))),
+            None => Some((n0, n1, n2)),
+        }
+    }
+
+    fn get_transformed_vertices(&self) -> (Point, Point, Point) {
+        let (v0, v1, v2) = self.get_vertices();
+        if let Some(tr) = self.data.transformation() {
+            (v0.transform(tr), v1.transform(tr), v2.transform(tr))
+        } else {
+            (*v0, *v1, *v2)
+        }
+    }
+
+    fn interpolate_normal(&self, position: Point) -> Option<Normal> {
+        let (n0, n1, n2) = self.get_normals()?;
+        let (v0, v1, v2) = self.get_transformed_vertices();
+        let (w0, w1, w2) = Triangle::calc_barycentric(&position, &v0, &v1, &v2);
+        let normal = w0 * n0.to_vector() + w1 * n1.to_vector() + w2 * n2.to_vector();
+        Normal::normalize(normal).ok()
+    }
+
+    pub fn to_triangle(&self) -> Triangle {
         let (v0, v1, v2) = self.get_vertices();
         if let Some(tr) = self.data.transformation() {
             Triangle::new(v0.transform(tr), v1.transform(tr), v2.transform(tr)).unwrap()
@@ -65,43 +95,37 @@ impl Shape for MeshTriangle {
 
     fn hit_part<'a>(&self, ray: &'a Ray, range: DisRange) -> Option<RayIntersectionPart<'a>> {
         let (v0, v1, v2) = self.get_vertices();
+        let shared = self.data.shared_edges(self.index);
         if let Some(tr) = self.data.transformation() {
             let (v0_tr, v1_tr, v2_tr) = (v0.transform(tr), v1.transform(tr), v2.transform(tr));
-            Triangle::calc_ray_intersection_part(ray, range, &v0_tr, &v1_tr, &v2_tr)
+            Triangle::calc_ray_intersection_part(ray, range, &v0_tr, &v1_tr, &v2_tr, shared)
         } else {
-            Triangle::calc_ray_intersection_part(ray, range, v0, v1, v2)
+            Triangle::calc_ray_intersection_part(ray, range, v0, v1, v2, shared)
         }
     }
 
     fn complete_part(&self, part: RayIntersectionPart) -> RayIntersection {
-        let (v0, v1, v2) = self.get_vertices();
-        if let Some(tr) = self.data.transformation() {
-            let (v0_tr, v1_tr, v2_tr) = (v0.transform(tr), v1.transform(tr), v2.transform(tr));
-            let res = Triangle::complete_ray_intersection_part(part, &v0_tr, &v1_tr, &v2_tr);
-
-            if let Some((uv0, uv1, uv2)) = self.get_uvs() {
-                let uv = UvCoordinateInterpolation::new()
-                    .push(v0_tr, uv0)
-                    .push(v1_tr, uv1)
-                    .push(v2_tr, uv2)
-                    .interpolate(res.position());
-                res.with_uv(uv)
-            } else {
-                res
+        let (v0, v1, v2) = self.get_transformed_vertices();
+        let res = Triangle::complete_ray_intersection_part(part, &v0, &v1, &v2);
+        let res = match self.interpolate_normal(res.position()) {
+            Some(normal) if normal.dot(res.normal()) < Val(0.0) => res.with_normal(-normal),
+            Some(normal) => res.with_normal(normal),
+            None => res,
+        };
+
+        let (w0, w1, w2) = Triangle::calc_barycentric(&res.position(), &v0, &v1, &v2);
+        if let Some((uv0, uv1, uv2)) = self.get_uvs() {
+            let u = w0 * uv0.u() + w1 * uv1.u() + w2 * uv2.u();
+            let v = w0 * uv0.v() + w1 * uv1.v() + w2 * uv2.v();
+            let res = res.with_uv(UvCoordinate::unbounded(u, v));
+            match Triangle::calc_uv_tangent((&v0, &v1, &v2), (uv0, uv1, uv2)) {
+                Some(tangent) => res.with_tangent(tangent),
+                None => res,
             }
         } else {
-            let res = Triangle::complete_ray_intersection_part(part, v0, v1, v2);
-
-            if let Some((uv0, uv1, uv2)) = self.get_uvs() {
-                let uv = UvCoordinateInterpolation::new()
-                    .push(*v0, uv0)
-                    .push(*v1, uv1)
-                    .push(*v2, uv2)
-                    .interpolate(res.position());
-                res.with_uv(uv)
-            } else {
-                res
-            }
+            let (p0, p1, p2) = self.get_vertices();
+            let position = *p0 + w1 * (*p1 - *p0) + w2 * (*p2 - *p0);
+            res.with_uv(self.data.planar_uv(position))
         }
     }
 
@@ -110,7 +134,10 @@ impl Shape for MeshTriangle {
         Area::new(Val(0.5) * (*v1 - *v0).cross(*v2 - *v0).norm()).unwrap()
     }
 
-    fn normal(&self, _position: Point) -> Normal {
+    fn normal(&self, position: Point) -> Normal {
+        if let Some(normal) = self.interpolate_normal(position) {
+            return normal;
+        }
         let (v0, v1, v2) = self.get_vertices();
         Normal::normalize((*v1 - *v0).cross(*v2 - *v0))
             .expect("triangle's two sides should not parallel")
@@ -156,6 +183,8 @@ impl Eq for MeshTriangle {}
 
 #[cfg(test)]
 mod tests {
+    use crate::domain::math::algebra::Vector;
+    use crate::domain::math::geometry::Direction;
     use crate::domain::math::numeric::Val;
     use crate::domain::shape::mesh::MeshConstructor;
 
@@ -182,4 +211,138 @@ mod tests {
             )),
         );
     }
+
+    #[test]
+    fn mesh_triangle_hit_part_succeeds_hitting_shared_edge_once_and_boundary_always() {
+        let (triangles, _) = MeshConstructor::new(
+            vec![
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(1.0), Val(0.0), Val(0.0)),
+                Point::new(Val(1.0), Val(1.0), Val(0.0)),
+                Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            ],
+            vec![vec![0, 1, 2], vec![2, 3, 0]],
+        )
+        .unwrap()
+        .construct_impl(None);
+
+        let count_hits = |x, y| {
+            let ray = Ray::new(
+                Point::new(Val(x), Val(y), Val(1.0)),
+                -Direction::z_direction(),
+            );
+            (triangles.iter())
+                .filter(|triangle| triangle.hit_part(&ray, DisRange::positive()).is_some())
+                .count()
+        };
+        assert_eq!(count_hits(0.5, 0.5), 1);
+        assert_eq!(count_hits(0.25, 0.25), 1);
+        assert_eq!(count_hits(0.5, 0.0), 1);
+        assert_eq!(count_hits(1.0, 0.5), 1);
+        assert_eq!(count_hits(1.0, 0.0), 1);
+        assert_eq!(count_hits(0.0, 0.0), 1);
+    }
+
+    #[test]
+    fn mesh_triangle_complete_part_succeeds_interpolating_vertex_normals() {
+        let tilted = |x, y| Normal::normalize(Vector::new(Val(x), Val(y), Val(1.0))).unwrap();
+        let (triangles, _) = MeshConstructor::new(
+            vec![
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(1.0), Val(0.0), Val(0.0)),
+                Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            ],
+            vec![vec![0, 1, 2]],
+        )
+        .unwrap()
+        .with_normals(
+            vec![tilted(0.0, 0.0), tilted(1.0, 0.0), tilted(0.0, 1.0)],
+            vec![vec![0, 1, 2]],
+        )
+        .unwrap()
+        .construct_impl(None);
+        let triangle = &triangles[0];
+
+        let normal_at = |x, y| {
+            let ray = Ray::new(
+                Point::new(Val(x), Val(y), Val(1.0)),
+                -Direction::z_direction(),
+            );
+            let part = triangle.hit_part(&ray, DisRange::positive()).unwrap();
+            triangle.complete_part(part).normal()
+        };
+
+        let near_v1 = normal_at(0.8, 0.1);
+        let near_v2 = normal_at(0.1, 0.8);
+        assert!(near_v1.x() > Val(0.3) && near_v1.y() < Val(0.2));
+        assert!(near_v2.y() > Val(0.3) && near_v2.x() < Val(0.2));
+        assert_eq!(
+            triangle.normal(Point::new(Val(0.0), Val(0.0), Val(0.0))),
+            tilted(0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn mesh_triangle_complete_part_succeeds_producing_planar_uv_without_uvs() {
+        let (triangles, _) = MeshConstructor::new(
+            vec![
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(2.0), Val(0.0), Val(0.0)),
+                Point::new(Val(2.0), Val(1.0), Val(0.0)),
+            ],
+            vec![vec![0, 1, 2]],
+        )
+        .unwrap()
+        .construct_impl(None);
+
+        let ray = Ray::new(
+            Point::new(Val(1.5), Val(0.5), Val(1.0)),
+            -Direction::z_direction(),
+        );
+        let part = triangles[0].hit_part(&ray, DisRange::positive()).unwrap();
+        let uv = triangles[0].complete_part(part).uv().unwrap();
+        assert_eq!(uv, UvCoordinate::new(Val(0.75), Val(0.5)).unwrap());
+    }
+
+    #[test]
+    fn mesh_triangle_complete_part_succeeds_producing_orthonormal_tangent_frame() {
+        let tilted = |x, y| Normal::normalize(Vector::new(Val(x), Val(y), Val(1.0))).unwrap();
+        let uv = |u, v| UvCoordinate::new(Val(u), Val(v)).unwrap();
+        let (triangles, _) = MeshConstructor::new(
+            vec![
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(1.0), Val(0.0), Val(0.0)),
+                Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            ],
+            vec![vec![0, 1, 2]],
+        )
+        .unwrap()
+        .with_uvs(
+            vec![uv(0.0, 0.0), uv(1.0, 0.0), uv(0.0, 1.0)],
+            vec![vec![0, 1, 2]],
+        )
+        .unwrap()
+        .with_normals(
+            vec![tilted(0.0, 0.0), tilted(1.0, 0.0), tilted(0.0, 1.0)],
+            vec![vec![0, 1, 2]],
+        )
+        .unwrap()
+        .construct_impl(None);
+
+        let ray = Ray::new(
+            Point::new(Val(0.6), Val(0.2), Val(1.0)),
+            -Direction::z_direction(),
+        );
+        let part = triangles[0].hit_part(&ray, DisRange::positive()).unwrap();
+        let intersection = triangles[0].complete_part(part);
+        let frame = intersection.tangent_frame();
+
+        assert_eq!(frame.normal(), intersection.normal());
+        assert_eq!(frame.tangent().norm(), Val(1.0));
+        assert_eq!(frame.cross().norm(), Val(1.0));
+        assert_eq!(frame.tangent().dot(frame.cross()), Val(0.0));
+        assert_eq!(frame.tangent().dot(frame.normal()), Val(0.0));
+        assert_eq!(frame.cross().dot(frame.normal()), Val(0.0));
+        assert!(frame.tangent().x() > Val(0.8));
+    }
 }
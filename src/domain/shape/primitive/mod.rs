@@ -1,4 +1,7 @@
 mod aabb;
+mod bilinear_patch;
+mod cylinder;
+mod disk;
 mod mesh_polygon;
 mod mesh_triangle;
 mod plane;
@@ -7,6 +10,9 @@ mod sphere;
 mod triangle;
 
 pub use aabb::Aabb;
+pub use bilinear_patch::{BilinearPatch, TryNewBilinearPatchError};
+pub use cylinder::{Cylinder, TryNewCylinderError};
+pub use disk::{Disk, TryNewDiskError};
 pub use mesh_polygon::MeshPolygon;
 pub use mesh_triangle::MeshTriangle;
 pub use plane::Plane;
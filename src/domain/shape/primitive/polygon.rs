@@ -1,16 +1,15 @@
-use smallvec::SmallVec;
 use snafu::prelude::*;
-use spade::{DelaunayTriangulation, Point2, Triangulation};
+use spade::{ConstrainedDelaunayTriangulation, Point2, Triangulation};
 
 use crate::domain::material::primitive::Emissive;
-use crate::domain::math::algebra::Product;
+use crate::domain::math::algebra::{Product, Vector};
 use crate::domain::math::geometry::{Area, Direction, Normal, Point};
 use crate::domain::math::numeric::{DisRange, Val, WrappedVal};
 use crate::domain::math::transformation::{Rotation, Transform, Transformation};
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::{RayIntersection, RayIntersectionPart};
 use crate::domain::sampling::Sampleable;
-use crate::domain::sampling::light::{LightSamplerAdapter, LightSampling};
+use crate::domain::sampling::light::{LightSamplerAdapter, LightSampling, RectangleLightSampler};
 use crate::domain::sampling::photon::{PhotonSamplerAdapter, PhotonSampling};
 use crate::domain::sampling::point::{PointSampling, PolygonPointSampler};
 use crate::domain::shape::def::{BoundingBox, Shape, ShapeKind};
@@ -27,6 +26,7 @@ pub enum PolygonInner {
     General {
         vertices: Vec<Point>,
         normal: Normal,
+        triangles: Vec<Triangle>,
     },
 }
 
@@ -89,7 +89,71 @@ impl Polygon {
         let is_flat = sides.iter().all(|s| s.is_perpendicular_to(normal));
         ensure!(is_flat, NotFlatSnafu);
 
-        Ok(Self(PolygonInner::General { vertices, normal }))
+        let triangles = Self::triangulate_general_polygon(&vertices, normal)?;
+        Ok(Self(PolygonInner::General {
+            vertices,
+            normal,
+            triangles,
+        }))
+    }
+
+    fn triangulate_general_polygon(
+        vertices: &[Point],
+        normal: Normal,
+    ) -> Result<Vec<Triangle>, TryNewPolygonError> {
+        let tr = Rotation::new(normal.into(), Direction::z_direction(), Val(0.0));
+        let z = vertices[0].transform(&tr).z();
+        let vertices_2d = vertices
+            .iter()
+            .map(|v| v.transform(&tr))
+            .map(|v| (v.x(), v.y()))
+            .collect::<Vec<_>>();
+
+        let mut triangulation = ConstrainedDelaunayTriangulation::<Point2<WrappedVal>>::new();
+        let handles = (vertices_2d.iter())
+            .map(|(x, y)| triangulation.insert(Point2::new(x.0, y.0)))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("vertices should have finite coordinates");
+        for (from, to) in handles.iter().zip(handles.iter().cycle().skip(1)) {
+            let sides = triangulation.try_add_constraint(*from, *to);
+            ensure!(!sides.is_empty(), SelfIntersectingSnafu);
+        }
+
+        let inv_tr = tr.inverse();
+        let mut triangles = Vec::with_capacity(vertices.len() - 2);
+        for face in triangulation.inner_faces() {
+            let corners = face.vertices().map(|vertex| vertex.position());
+            let cx = Val(corners.iter().map(|c| c.x).sum::<WrappedVal>() / 3.0);
+            let cy = Val(corners.iter().map(|c| c.y).sum::<WrappedVal>() / 3.0);
+            let to_vertices = (vertices_2d.iter())
+                .map(|(x, y)| (*x - cx, *y - cy))
+                .collect::<Vec<_>>();
+            if Self::calc_angle_sum(to_vertices) == Val(0.0) {
+                continue;
+            }
+
+            let [v0, v1, v2] =
+                corners.map(|pos| Point::new(Val(pos.x), Val(pos.y), z).transform(&inv_tr));
+            let triangle = if (v1 - v0).cross(v2 - v0).dot(normal) > Val(0.0) {
+                Triangle::new(v0, v1, v2)
+            } else {
+                Triangle::new(v0, v2, v1)
+            };
+            triangles.extend(triangle.ok());
+        }
+        Ok(triangles)
+    }
+
+    fn is_inside_triangles(position: &Point, triangles: &[Triangle]) -> bool {
+        triangles.iter().any(|triangle| {
+            let (w0, w1, w2) = Triangle::calc_barycentric(
+                position,
+                &triangle.vertex0(),
+                &triangle.vertex1(),
+                &triangle.vertex2(),
+            );
+            w0 >= Val(0.0) && w1 >= Val(0.0) && w2 >= Val(0.0)
+        })
     }
 
     pub fn calc_ray_intersection_part<'a>(
@@ -167,39 +231,22 @@ impl Polygon {
             .sum::<Val>()
     }
 
-    pub fn triangulate(&self) -> Vec<Triangle> {
+    pub fn to_rectangle(&self) -> Option<(Point, Vector, Vector)> {
+        let PolygonInner::General { vertices, .. } = &self.0 else {
+            return None;
+        };
+        let [v0, v1, v2, v3] = vertices.as_slice() else {
+            return None;
+        };
+        let (side1, side2) = (*v1 - *v0, *v3 - *v0);
+        let is_rectangle = side1.is_perpendicular_to(side2) && *v2 == *v1 + side2;
+        is_rectangle.then_some((*v0, side1, side2))
+    }
+
+    pub fn triangulate(&self) -> &[Triangle] {
         match &self.0 {
-            PolygonInner::Triangle(triangle) => vec![triangle.clone(); 1],
-            PolygonInner::General { vertices, normal } => {
-                assert!(vertices.len() >= 3);
-
-                let tr = Rotation::new((*normal).into(), Direction::z_direction(), Val(0.0));
-                let z = vertices[0].transform(&tr).z();
-                let vertices_2d = vertices
-                    .iter()
-                    .map(|v| v.transform(&tr))
-                    .map(|v| Point2::new(v.x().0, v.y().0))
-                    .collect();
-                let triangulation =
-                    DelaunayTriangulation::<Point2<WrappedVal>>::bulk_load_stable(vertices_2d)
-                        .unwrap();
-
-                let inv_tr = tr.inverse();
-                let mut triangles = Vec::with_capacity(triangulation.num_inner_faces());
-                for face in triangulation.inner_faces() {
-                    let [v0, v1, v2] = face.vertices().map(|vertex| {
-                        let pos = vertex.position();
-                        Point::new(Val(pos.x), Val(pos.y), z).transform(&inv_tr)
-                    });
-                    let triangle_normal = (v1 - v0).cross(v2 - v0);
-                    if triangle_normal.dot(*normal) > Val(0.0) {
-                        triangles.push(Triangle::new(v0, v1, v2).unwrap());
-                    } else {
-                        triangles.push(Triangle::new(v0, v2, v1).unwrap());
-                    }
-                }
-                triangles
-            }
+            PolygonInner::Triangle(triangle) => std::slice::from_ref(triangle),
+            PolygonInner::General { triangles, .. } => triangles,
         }
     }
 }
@@ -212,9 +259,14 @@ impl Shape for Polygon {
     fn hit_part<'a>(&self, ray: &'a Ray, range: DisRange) -> Option<RayIntersectionPart<'a>> {
         match &self.0 {
             PolygonInner::Triangle(triangle) => triangle.hit_part(ray, range),
-            PolygonInner::General { vertices, normal } => {
-                let vertices = vertices.iter().collect::<SmallVec<[&Point; 6]>>();
-                Self::calc_ray_intersection_part(ray, range, &vertices, normal)
+            PolygonInner::General {
+                vertices,
+                normal,
+                triangles,
+            } => {
+                let part = Plane::calc_ray_intersection_part(ray, range, &vertices[0], normal)?;
+                let position = part.ray().at(part.distance());
+                Self::is_inside_triangles(&position, triangles).then_some(part)
             }
         }
     }
@@ -231,15 +283,8 @@ impl Shape for Polygon {
     fn area(&self) -> Area {
         match &self.0 {
             PolygonInner::Triangle(triangle) => triangle.area(),
-            PolygonInner::General { vertices, normal } => {
-                let mut sum = Val(0.0);
-                for i in 1..(vertices.len() - 1) {
-                    let side1 = vertices[i] - vertices[0];
-                    let side2 = vertices[i + 1] - vertices[0];
-                    let cross = side1.cross(side2);
-                    sum += cross.norm() * cross.dot(*normal).signum();
-                }
-                Area::new(sum * Val(0.5)).unwrap()
+            PolygonInner::General { triangles, .. } => {
+                (triangles.iter()).fold(Area::zero(), |sum, triangle| sum + triangle.area())
             }
         }
     }
@@ -274,6 +319,9 @@ impl Sampleable for Polygon {
         match &self.0 {
             PolygonInner::Triangle(triangle) => triangle.get_light_sampler(shape_id),
             PolygonInner::General { .. } => {
+                if let Some(sampler) = RectangleLightSampler::new(shape_id, self.clone()) {
+                    return Some(Box::new(sampler));
+                }
                 let inner = PolygonPointSampler::new(shape_id, self.clone());
                 let sampler = LightSamplerAdapter::new(inner);
                 Some(Box::new(sampler))
@@ -308,11 +356,12 @@ pub enum TryNewPolygonError {
     ParallelAdjacentSides,
     #[snafu(display("polygon is not a flat shape"))]
     NotFlat,
+    #[snafu(display("polygon has self-intersecting sides"))]
+    SelfIntersecting,
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::math::algebra::Vector;
     use crate::domain::math::geometry::Distance;
     use crate::domain::ray::event::SurfaceSide;
 
@@ -452,6 +501,44 @@ mod tests {
         assert_eq!(polygon.area(), Area::new(Val(3.0)).unwrap());
     }
 
+    #[test]
+    fn polygon_hit_succeeds_excluding_concave_notch() {
+        let arrow = Polygon::new([
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Point::new(Val(2.0), Val(1.0), Val(0.0)),
+            Point::new(Val(0.0), Val(2.0), Val(0.0)),
+            Point::new(Val(1.0), Val(1.0), Val(0.0)),
+        ])
+        .unwrap();
+
+        let fire = |x: Val, y: Val| {
+            let ray = Ray::new(Point::new(x, y, Val(-1.0)), Direction::z_direction());
+            arrow.hit(&ray, DisRange::positive())
+        };
+        assert!(fire(Val(1.5), Val(1.0)).is_some());
+        assert!(fire(Val(0.5), Val(0.4)).is_some());
+        assert!(fire(Val(0.5), Val(1.0)).is_none());
+        assert!(fire(Val(0.1), Val(1.0)).is_none());
+        assert!(fire(Val(0.9), Val(1.0)).is_none());
+
+        let area =
+            (arrow.triangulate().iter()).fold(Area::zero(), |sum, triangle| sum + triangle.area());
+        assert_eq!(area, arrow.area());
+    }
+
+    #[test]
+    fn polygon_new_fails_when_sides_are_self_intersecting() {
+        assert!(matches!(
+            Polygon::new([
+                Point::new(Val(0.0), Val(0.0), Val(0.0)),
+                Point::new(Val(1.0), Val(0.0), Val(0.0)),
+                Point::new(Val(0.0), Val(1.0), Val(0.0)),
+                Point::new(Val(1.0), Val(1.0), Val(0.0)),
+            ]),
+            Err(TryNewPolygonError::SelfIntersecting),
+        ));
+    }
+
     #[test]
     fn polygon_bounding_box_succeeds() {
         let polygon = Polygon::new([
@@ -484,4 +571,50 @@ mod tests {
         let triangles = polygon.triangulate();
         assert_eq!(triangles.len(), 2);
     }
+
+    #[test]
+    fn polygon_triangulate_succeeds_returning_identical_geometry() {
+        let polygon = Polygon::new([
+            Point::new(Val(1.0), Val(0.0), Val(0.0)),
+            Point::new(Val(-1.0), Val(0.0), Val(0.0)),
+            Point::new(Val(-1.0), Val(2.0), Val(0.0)),
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+            Point::new(Val(1.0), Val(2.0), Val(0.0)),
+        ])
+        .unwrap();
+
+        let first = polygon.triangulate();
+        let second = polygon.triangulate();
+        assert_eq!(first.len(), 3);
+        assert_eq!(first, second);
+        assert_eq!(first.as_ptr(), second.as_ptr());
+    }
+
+    #[test]
+    fn polygon_to_rectangle_succeeds() {
+        let rectangle = Polygon::new([
+            Point::new(Val(0.0), Val(0.0), Val(1.0)),
+            Point::new(Val(2.0), Val(0.0), Val(1.0)),
+            Point::new(Val(2.0), Val(1.0), Val(1.0)),
+            Point::new(Val(0.0), Val(1.0), Val(1.0)),
+        ])
+        .unwrap();
+        assert_eq!(
+            rectangle.to_rectangle(),
+            Some((
+                Point::new(Val(0.0), Val(0.0), Val(1.0)),
+                Vector::new(Val(2.0), Val(0.0), Val(0.0)),
+                Vector::new(Val(0.0), Val(1.0), Val(0.0)),
+            )),
+        );
+
+        let trapezoid = Polygon::new([
+            Point::new(Val(0.0), Val(0.0), Val(1.0)),
+            Point::new(Val(2.0), Val(0.0), Val(1.0)),
+            Point::new(Val(1.5), Val(1.0), Val(1.0)),
+            Point::new(Val(0.0), Val(1.0), Val(1.0)),
+        ])
+        .unwrap();
+        assert_eq!(trapezoid.to_rectangle(), None);
+    }
 }
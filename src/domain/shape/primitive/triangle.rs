@@ -4,7 +4,7 @@ use getset::CopyGetters;
 use snafu::prelude::*;
 
 use crate::domain::material::primitive::Emissive;
-use crate::domain::math::algebra::Product;
+use crate::domain::math::algebra::{Product, Vector};
 use crate::domain::math::geometry::{Area, Distance, Normal, Point};
 use crate::domain::math::numeric::{DisRange, Val};
 use crate::domain::ray::Ray;
@@ -15,6 +15,7 @@ use crate::domain::sampling::photon::{PhotonSamplerAdapter, PhotonSampling};
 use crate::domain::sampling::point::{PointSampling, TrianglePointSampler};
 use crate::domain::shape::def::{BoundingBox, Shape, ShapeKind};
 use crate::domain::shape::util::ShapeId;
+use crate::domain::texture::def::UvCoordinate;
 
 #[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
 #[getset(get_copy = "pub")]
@@ -54,39 +55,87 @@ impl Triangle {
         Ok(())
     }
 
+    /// Edges flagged in `shared_edges` are assigned to one of their triangles by a top-left rule.
     pub fn calc_ray_intersection_part<'a>(
         ray: &'a Ray,
         range: DisRange,
         vertex0: &Point,
         vertex1: &Point,
         vertex2: &Point,
+        shared_edges: [bool; 3],
     ) -> Option<RayIntersectionPart<'a>> {
-        let side1 = *vertex1 - *vertex0;
-        let side2 = *vertex2 - *vertex0;
-        let vec0 = ray.direction().cross(side2);
-        let det = side1.dot(vec0);
-        if det == Val(0.0) {
-            return None;
-        }
+        let direction = ray.direction().to_vector();
+        let kz = (0..3)
+            .max_by(|&a, &b| direction.axis(a).abs().cmp(&direction.axis(b).abs()))
+            .unwrap();
+        let (kx, ky) = if direction.axis(kz) < Val(0.0) {
+            ((kz + 2) % 3, (kz + 1) % 3)
+        } else {
+            ((kz + 1) % 3, (kz + 2) % 3)
+        };
+        let dz = direction.axis(kz);
+        let (sx, sy, sz) = (direction.axis(kx) / dz, direction.axis(ky) / dz, dz.recip());
 
-        let inv_det = det.recip();
-        let vec1 = ray.start() - *vertex0;
-        let u = vec0.dot(vec1) * inv_det;
-        if !(Val(0.0)..=Val(1.0)).contains(&u) {
+        let shear = |vertex: &Point| {
+            let offset = *vertex - ray.start();
+            let z = offset.axis(kz);
+            Vector::new(offset.axis(kx) - sx * z, offset.axis(ky) - sy * z, sz * z)
+        };
+        let (a, b, c) = (shear(vertex0), shear(vertex1), shear(vertex2));
+
+        // The signs are taken exactly, so that rays near an edge can't miss both triangles.
+        let edge = |from: Vector, to: Vector| from.x() * to.y() - from.y() * to.x();
+        let (u, v, w) = (edge(c, b), edge(a, c), edge(b, a));
+        let det = u + v + w;
+        if det.0 == 0.0 {
             return None;
         }
 
-        let vec2 = vec1.cross(side1);
-        let v = ray.direction().dot(vec2) * inv_det;
-        if !(Val(0.0)..=(Val(1.0) - u)).contains(&v) {
+        let is_top_left = |from: Vector, to: Vector| {
+            let (dx, dy) = ((to.x() - from.x()).0, (to.y() - from.y()).0);
+            (dy > 0.0 || (dy == 0.0 && dx < 0.0)) != (det.0 < 0.0)
+        };
+        let is_inside = |edge: Val, from, to, shared: bool| {
+            (edge * det).0 > 0.0 || (edge.0 == 0.0 && (!shared || is_top_left(from, to)))
+        };
+        if !(is_inside(w, a, b, shared_edges[0])
+            && is_inside(u, b, c, shared_edges[1])
+            && is_inside(v, c, a, shared_edges[2]))
+        {
             return None;
         }
 
-        let distance = side2.dot(vec2) * inv_det;
+        let distance = (u * a.z() + v * b.z() + w * c.z()) / det;
         let distance = Distance::new(distance).ok().filter(|d| range.contains(d))?;
         Some(RayIntersectionPart::new(distance, ray))
     }
 
+    pub fn calc_barycentric(
+        position: &Point,
+        vertex0: &Point,
+        vertex1: &Point,
+        vertex2: &Point,
+    ) -> (Val, Val, Val) {
+        let (side1, side2) = (*vertex1 - *vertex0, *vertex2 - *vertex0);
+        let offset = *position - *vertex0;
+        let (d11, d12, d22) = (side1.dot(side1), side1.dot(side2), side2.dot(side2));
+        let (d1, d2) = (offset.dot(side1), offset.dot(side2));
+        let denom = d11 * d22 - d12 * d12;
+        let w1 = (d22 * d1 - d12 * d2) / denom;
+        let w2 = (d11 * d2 - d12 * d1) / denom;
+        (Val(1.0) - w1 - w2, w1, w2)
+    }
+
+    pub fn calc_uv_tangent(
+        (vertex0, vertex1, vertex2): (&Point, &Point, &Point),
+        (uv0, uv1, uv2): (UvCoordinate, UvCoordinate, UvCoordinate),
+    ) -> Option<Vector> {
+        let (du1, dv1) = (uv1.u() - uv0.u(), uv1.v() - uv0.v());
+        let (du2, dv2) = (uv2.u() - uv0.u(), uv2.v() - uv0.v());
+        let det = du1 * dv2 - du2 * dv1;
+        (det != Val(0.0)).then(|| (dv2 * (*vertex1 - *vertex0) - dv1 * (*vertex2 - *vertex0)) / det)
+    }
+
     pub fn complete_ray_intersection_part(
         part: RayIntersectionPart,
         vertex0: &Point,
@@ -111,7 +160,8 @@ impl Shape for Triangle {
     }
 
     fn hit_part<'a>(&self, ray: &'a Ray, range: DisRange) -> Option<RayIntersectionPart<'a>> {
-        Self::calc_ray_intersection_part(ray, range, &self.vertex0, &self.vertex1, &self.vertex2)
+        let (v0, v1, v2) = (&self.vertex0, &self.vertex1, &self.vertex2);
+        Self::calc_ray_intersection_part(ray, range, v0, v1, v2, [false; 3])
     }
 
     fn complete_part(&self, part: RayIntersectionPart) -> RayIntersection {
@@ -256,6 +306,71 @@ mod tests {
         assert!(intersection.is_none());
     }
 
+    #[test]
+    fn triangle_hit_succeeds_including_edges_and_vertices() {
+        let triangle = Triangle::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Point::new(Val(1.0), Val(0.0), Val(0.0)),
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+        )
+        .unwrap();
+
+        for (x, y) in [
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (0.0, 1.0),
+            (0.5, 0.0),
+            (0.0, 0.5),
+            (0.5, 0.5),
+        ] {
+            for dz in [Val(1.0), Val(-1.0)] {
+                let start = Point::new(Val(x), Val(y), dz);
+                let ray = Ray::new(
+                    start,
+                    Direction::normalize(Vector::new(Val(0.0), Val(0.0), -dz)).unwrap(),
+                );
+                assert!(
+                    triangle.hit(&ray, DisRange::positive()).is_some(),
+                    "{ray:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn triangle_calc_ray_intersection_part_succeeds_hitting_exactly_one_side_of_shared_edge() {
+        let (v0, v1) = (
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Point::new(Val(1.0), Val(1.0), Val(0.0)),
+        );
+        let (lower, upper) = (
+            Point::new(Val(1.0), Val(0.0), Val(0.0)),
+            Point::new(Val(0.0), Val(1.0), Val(0.0)),
+        );
+        let shared = [false, false, true];
+
+        for i in 1..100 {
+            let t = Val::from(i) / Val(100.0);
+            let target = Point::new(t, t, Val(0.0));
+            for start in [
+                Point::new(Val(0.5), Val(0.5), Val(1.0)),
+                Point::new(t, t, Val(-1.0)),
+                Point::new(Val(-0.3), Val(0.7), Val(0.9)),
+                Point::new(Val(1.3), Val(0.1), Val(-0.7)),
+            ] {
+                let ray = Ray::new(start, Direction::normalize(target - start).unwrap());
+                let range = DisRange::positive();
+                let hits = [(&v0, &lower, &v1), (&v1, &upper, &v0)]
+                    .into_iter()
+                    .filter_map(|(a, b, c)| {
+                        Triangle::calc_ray_intersection_part(&ray, range, a, b, c, shared)
+                    })
+                    .count();
+                assert_eq!(hits, 1, "{ray:?}");
+            }
+        }
+    }
+
     #[test]
     fn triangle_area_succeeds() {
         let triangle = Triangle::new(
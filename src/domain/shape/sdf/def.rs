@@ -0,0 +1,26 @@
+use std::fmt::Debug;
+
+use enum_dispatch::enum_dispatch;
+
+use crate::domain::math::geometry::{Area, Point};
+use crate::domain::math::numeric::Val;
+use crate::domain::shape::def::BoundingBox;
+
+use super::{SdfBox, SdfSphere, SdfTorus};
+
+#[enum_dispatch]
+pub trait SdfShape: Debug + Send + Sync {
+    fn distance(&self, point: Point) -> Val;
+
+    fn area(&self) -> Area;
+
+    fn bounding_box(&self) -> Option<BoundingBox>;
+}
+
+#[enum_dispatch(SdfShape)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynSdf {
+    Box(SdfBox),
+    Sphere(SdfSphere),
+    Torus(SdfTorus),
+}
@@ -0,0 +1,11 @@
+mod def;
+mod sdf_box;
+mod shape;
+mod sphere;
+mod torus;
+
+pub use def::{DynSdf, SdfShape};
+pub use sdf_box::{SdfBox, TryNewSdfBoxError};
+pub use shape::{Sdf, SdfConfig};
+pub use sphere::{SdfSphere, TryNewSdfSphereError};
+pub use torus::{SdfTorus, TryNewSdfTorusError};
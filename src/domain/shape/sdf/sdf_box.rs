@@ -0,0 +1,70 @@
+use getset::CopyGetters;
+use snafu::prelude::*;
+
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::geometry::{Area, Point};
+use crate::domain::math::numeric::Val;
+use crate::domain::shape::def::BoundingBox;
+
+use super::SdfShape;
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct SdfBox {
+    center: Point,
+    half_extents: Vector,
+}
+
+impl SdfBox {
+    pub fn new(center: Point, half_extents: Vector) -> Result<Self, TryNewSdfBoxError> {
+        ensure!(
+            (0..3).all(|axis| half_extents.axis(axis) > Val(0.0)),
+            InvalidSizeSnafu
+        );
+        Ok(Self {
+            center,
+            half_extents,
+        })
+    }
+}
+
+impl SdfShape for SdfBox {
+    fn distance(&self, point: Point) -> Val {
+        let p = point - self.center;
+        let q = Vector::new(
+            p.x().abs() - self.half_extents.x(),
+            p.y().abs() - self.half_extents.y(),
+            p.z().abs() - self.half_extents.z(),
+        );
+        let outside = Vector::new(
+            q.x().max(Val(0.0)),
+            q.y().max(Val(0.0)),
+            q.z().max(Val(0.0)),
+        );
+        let inside = q.x().max(q.y()).max(q.z()).min(Val(0.0));
+        outside.norm() + inside
+    }
+
+    fn area(&self) -> Area {
+        let (a, b, c) = (
+            self.half_extents.x(),
+            self.half_extents.y(),
+            self.half_extents.z(),
+        );
+        Area::new(Val(8.0) * (a * b + b * c + c * a)).unwrap()
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        Some(BoundingBox::new(
+            self.center - self.half_extents,
+            self.center + self.half_extents,
+        ))
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewSdfBoxError {
+    #[snafu(display("half extents of the box are not all positive"))]
+    InvalidSize,
+}
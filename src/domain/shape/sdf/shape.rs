@@ -0,0 +1,279 @@
+use std::ops::{Bound, RangeBounds};
+
+use getset::{CopyGetters, Getters};
+
+use crate::domain::material::primitive::Emissive;
+use crate::domain::math::algebra::{Product, Vector};
+use crate::domain::math::geometry::{Area, Distance, Normal, Point};
+use crate::domain::math::numeric::{DisRange, Val};
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, RayIntersectionPart, SurfaceSide};
+use crate::domain::sampling::Sampleable;
+use crate::domain::sampling::light::LightSampling;
+use crate::domain::sampling::photon::PhotonSampling;
+use crate::domain::sampling::point::PointSampling;
+use crate::domain::shape::def::{BoundingBox, Shape, ShapeKind};
+use crate::domain::shape::util::ShapeId;
+
+use super::{DynSdf, SdfShape};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct SdfConfig {
+    max_iterations: usize,
+    epsilon: Val,
+}
+
+impl SdfConfig {
+    pub fn with_max_iterations(self, max_iterations: usize) -> Self {
+        Self {
+            max_iterations: max_iterations.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_epsilon(self, epsilon: Val) -> Self {
+        Self {
+            epsilon: epsilon.max(Val(Val::PRECISION * 10.0)),
+            ..self
+        }
+    }
+}
+
+impl Default for SdfConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: 256,
+            epsilon: Val(1e-6),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+pub struct Sdf<S = DynSdf>
+where
+    S: SdfShape,
+{
+    #[getset(get = "pub")]
+    field: S,
+    #[getset(get_copy = "pub")]
+    config: SdfConfig,
+}
+
+impl<S> Sdf<S>
+where
+    S: SdfShape,
+{
+    pub fn new(field: S) -> Self {
+        Self {
+            field,
+            config: SdfConfig::default(),
+        }
+    }
+
+    pub fn with_config(self, config: SdfConfig) -> Self {
+        Self { config, ..self }
+    }
+
+    fn trace(&self, ray: &Ray, range: DisRange) -> Option<Distance> {
+        let start = match range.start_bound() {
+            Bound::Included(start) | Bound::Excluded(start) => start.value(),
+            Bound::Unbounded => Val(0.0),
+        };
+        let range = match self.field.bounding_box() {
+            Some(bbox) => {
+                let entry = bbox.try_hit(ray, range)?;
+                range.intersect(DisRange::inclusive(entry, Distance::infinity()))
+            }
+            None => range,
+        };
+        let mut t = match range.start_bound() {
+            Bound::Included(start) | Bound::Excluded(start) => start.value(),
+            Bound::Unbounded => Val(0.0),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) | Bound::Excluded(end) => end.value(),
+            Bound::Unbounded => Val::INFINITY,
+        };
+
+        // A ray starting on the surface has to leave it before another hit is reported.
+        let mut leaving = t <= start;
+        let epsilon = self.config.epsilon;
+        for _ in 0..self.config.max_iterations {
+            if t > end {
+                return None;
+            }
+            let distance = Distance::new(t).ok()?;
+            let field = self.field.distance(ray.at(distance)).abs();
+            if field < epsilon {
+                if !leaving && range.contains(&distance) {
+                    return Some(distance);
+                }
+            } else {
+                leaving = false;
+            }
+            t += field.max(epsilon);
+        }
+        None
+    }
+
+    fn gradient(&self, position: Point) -> Vector {
+        let h = self.config.epsilon;
+        let diff = |offset: Vector| {
+            let delta =
+                self.field.distance(position + offset) - self.field.distance(position - offset);
+            delta / (Val(2.0) * h)
+        };
+        Vector::new(
+            diff(Vector::new(h, Val(0.0), Val(0.0))),
+            diff(Vector::new(Val(0.0), h, Val(0.0))),
+            diff(Vector::new(Val(0.0), Val(0.0), h)),
+        )
+    }
+}
+
+impl<S> Shape for Sdf<S>
+where
+    S: SdfShape,
+{
+    fn kind(&self) -> ShapeKind {
+        ShapeKind::Sdf
+    }
+
+    fn hit_part<'a>(&self, ray: &'a Ray, range: DisRange) -> Option<RayIntersectionPart<'a>> {
+        self.trace(ray, range)
+            .map(|distance| RayIntersectionPart::new(distance, ray))
+    }
+
+    fn complete_part(&self, part: RayIntersectionPart) -> RayIntersection {
+        let position = part.ray().at(part.distance());
+        let normal = self.normal(position);
+        let (normal, side) = if part.ray().direction().dot(normal) < Val(0.0) {
+            (normal, SurfaceSide::Front)
+        } else {
+            (-normal, SurfaceSide::Back)
+        };
+        RayIntersection::new(part.distance(), position, normal, side)
+    }
+
+    fn area(&self) -> Area {
+        self.field.area()
+    }
+
+    fn normal(&self, position: Point) -> Normal {
+        Normal::normalize(self.gradient(position)).unwrap_or(Normal::x_direction())
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        self.field.bounding_box()
+    }
+}
+
+impl<S> Sampleable for Sdf<S>
+where
+    S: SdfShape,
+{
+    fn get_point_sampler(&self, _shape_id: ShapeId) -> Option<Box<dyn PointSampling>> {
+        None
+    }
+
+    fn get_light_sampler(&self, _shape_id: ShapeId) -> Option<Box<dyn LightSampling>> {
+        None
+    }
+
+    fn get_photon_sampler(
+        &self,
+        _shape_id: ShapeId,
+        _emissive: Emissive,
+    ) -> Option<Box<dyn PhotonSampling>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::Direction;
+    use crate::domain::shape::primitive::Sphere;
+    use crate::domain::shape::sdf::{SdfBox, SdfSphere, SdfTorus};
+
+    use super::*;
+
+    fn assert_close(a: Distance, b: Val) {
+        assert!((a.value() - b).abs() < Val(1e-4), "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn sdf_hit_succeeds_matching_analytic_sphere() {
+        let center = Point::new(Val(0.0), Val(1.0), Val(0.0));
+        let analytic = Sphere::new(center, Val(1.0)).unwrap();
+        let sdf = Sdf::new(SdfSphere::new(center, Val(1.0)).unwrap());
+
+        let directions = [
+            Vector::new(Val(-1.0), Val(1.0), Val(0.0)),
+            Vector::new(Val(-1.0), Val(0.8), Val(0.3)),
+            Vector::new(Val(-1.0), Val(0.5), Val(-0.2)),
+        ];
+        for direction in directions {
+            let ray = Ray::new(
+                Point::new(Val(2.0), Val(0.0), Val(0.0)),
+                Direction::normalize(direction).unwrap(),
+            );
+            let expected = analytic.hit(&ray, DisRange::positive()).unwrap();
+            let actual = sdf.hit(&ray, DisRange::positive()).unwrap();
+            assert_close(actual.distance(), expected.distance().value());
+            assert_eq!(actual.side(), SurfaceSide::Front);
+            assert!(actual.normal().dot(expected.normal()) > Val(0.999));
+        }
+    }
+
+    #[test]
+    fn sdf_hit_all_succeeds_leaving_surface_after_each_hit() {
+        let sdf = Sdf::new(SdfSphere::new(Point::default(), Val(1.0)).unwrap());
+        let ray = Ray::new(
+            Point::new(Val(0.0), Val(0.0), Val(-3.0)),
+            Direction::z_direction(),
+        );
+
+        let hits = sdf.hit_all(&ray, DisRange::positive());
+        assert_eq!(hits.len(), 2);
+        assert_close(hits[0].distance(), Val(2.0));
+        assert_eq!(hits[0].side(), SurfaceSide::Front);
+        assert_close(hits[1].distance(), Val(4.0));
+        assert_eq!(hits[1].side(), SurfaceSide::Back);
+    }
+
+    #[test]
+    fn sdf_hit_fails_when_surface_is_beyond_range() {
+        let sdf = Sdf::new(SdfSphere::new(Point::default(), Val(1.0)).unwrap());
+        let ray = Ray::new(
+            Point::new(Val(0.0), Val(0.0), Val(-3.0)),
+            Direction::z_direction(),
+        );
+
+        let range = DisRange::inclusive(Distance::zero(), Distance::new(Val(1.5)).unwrap());
+        assert!(sdf.hit(&ray, range).is_none());
+    }
+
+    #[test]
+    fn sdf_hit_succeeds_with_box_and_torus() {
+        let ray = Ray::new(
+            Point::new(Val(-5.0), Val(0.0), Val(0.0)),
+            Direction::x_direction(),
+        );
+
+        let cuboid =
+            SdfBox::new(Point::default(), Vector::new(Val(1.0), Val(2.0), Val(3.0))).unwrap();
+        let hits = Sdf::new(cuboid).hit_all(&ray, DisRange::positive());
+        assert_eq!(hits.len(), 2);
+        assert_close(hits[0].distance(), Val(4.0));
+        assert_close(hits[1].distance(), Val(6.0));
+
+        let torus = SdfTorus::new(Point::default(), Val(2.0), Val(0.5)).unwrap();
+        let hits = Sdf::new(torus).hit_all(&ray, DisRange::positive());
+        let distances = hits.iter().map(|hit| hit.distance()).collect::<Vec<_>>();
+        assert_eq!(distances.len(), 4);
+        for (distance, expected) in distances.into_iter().zip([2.5, 3.5, 6.5, 7.5]) {
+            assert_close(distance, Val(expected));
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use getset::CopyGetters;
+use snafu::prelude::*;
+
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::geometry::{Area, Point};
+use crate::domain::math::numeric::Val;
+use crate::domain::shape::def::BoundingBox;
+
+use super::SdfShape;
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct SdfSphere {
+    center: Point,
+    radius: Val,
+}
+
+impl SdfSphere {
+    pub fn new(center: Point, radius: Val) -> Result<Self, TryNewSdfSphereError> {
+        ensure!(radius > Val(0.0), InvalidRadiusSnafu);
+        Ok(Self { center, radius })
+    }
+}
+
+impl SdfShape for SdfSphere {
+    fn distance(&self, point: Point) -> Val {
+        (point - self.center).norm() - self.radius
+    }
+
+    fn area(&self) -> Area {
+        Area::new(Val(4.0) * Val::PI * self.radius.powi(2)).unwrap()
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        let d = Vector::broadcast(self.radius);
+        Some(BoundingBox::new(self.center - d, self.center + d))
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewSdfSphereError {
+    #[snafu(display("radius is not positive"))]
+    InvalidRadius,
+}
@@ -0,0 +1,60 @@
+use getset::CopyGetters;
+use snafu::prelude::*;
+
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::geometry::{Area, Point};
+use crate::domain::math::numeric::Val;
+use crate::domain::shape::def::BoundingBox;
+
+use super::SdfShape;
+
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct SdfTorus {
+    center: Point,
+    major_radius: Val,
+    minor_radius: Val,
+}
+
+impl SdfTorus {
+    pub fn new(
+        center: Point,
+        major_radius: Val,
+        minor_radius: Val,
+    ) -> Result<Self, TryNewSdfTorusError> {
+        ensure!(minor_radius > Val(0.0), InvalidMinorRadiusSnafu);
+        ensure!(major_radius > minor_radius, InvalidMajorRadiusSnafu);
+        Ok(Self {
+            center,
+            major_radius,
+            minor_radius,
+        })
+    }
+}
+
+impl SdfShape for SdfTorus {
+    fn distance(&self, point: Point) -> Val {
+        let p = point - self.center;
+        let ring = (p.x().powi(2) + p.z().powi(2)).sqrt() - self.major_radius;
+        (ring.powi(2) + p.y().powi(2)).sqrt() - self.minor_radius
+    }
+
+    fn area(&self) -> Area {
+        Area::new(Val(4.0) * Val::PI.powi(2) * self.major_radius * self.minor_radius).unwrap()
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        let r = self.major_radius + self.minor_radius;
+        let d = Vector::new(r, self.minor_radius, r);
+        Some(BoundingBox::new(self.center - d, self.center + d))
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewSdfTorusError {
+    #[snafu(display("minor radius is not positive"))]
+    InvalidMinorRadius,
+    #[snafu(display("major radius is not greater than minor radius"))]
+    InvalidMajorRadius,
+}
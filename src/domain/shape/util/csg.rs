@@ -0,0 +1,259 @@
+use std::ops::RangeBounds;
+use std::sync::Arc;
+
+use getset::{CopyGetters, Getters};
+
+use crate::domain::material::primitive::Emissive;
+use crate::domain::math::geometry::{Area, Normal, Point};
+use crate::domain::math::numeric::DisRange;
+use crate::domain::ray::Ray;
+use crate::domain::ray::event::{RayIntersection, RayIntersectionPart, SurfaceSide};
+use crate::domain::sampling::Sampleable;
+use crate::domain::sampling::light::LightSampling;
+use crate::domain::sampling::photon::PhotonSampling;
+use crate::domain::sampling::point::PointSampling;
+use crate::domain::shape::def::{BoundingBox, DynShape, Shape, ShapeKind};
+use crate::domain::shape::util::ShapeId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CsgOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl CsgOperation {
+    fn contains(&self, in_left: bool, in_right: bool) -> bool {
+        match self {
+            Self::Union => in_left || in_right,
+            Self::Intersection => in_left && in_right,
+            Self::Difference => in_left && !in_right,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+pub struct Csg {
+    #[getset(get = "pub")]
+    left: Arc<DynShape>,
+    #[getset(get = "pub")]
+    right: Arc<DynShape>,
+    #[getset(get_copy = "pub")]
+    operation: CsgOperation,
+}
+
+impl Csg {
+    pub fn new<L, R>(left: L, right: R, operation: CsgOperation) -> Self
+    where
+        L: Into<DynShape>,
+        R: Into<DynShape>,
+    {
+        Self {
+            left: Arc::new(left.into()),
+            right: Arc::new(right.into()),
+            operation,
+        }
+    }
+
+    pub fn union<L: Into<DynShape>, R: Into<DynShape>>(left: L, right: R) -> Self {
+        Self::new(left, right, CsgOperation::Union)
+    }
+
+    pub fn intersection<L: Into<DynShape>, R: Into<DynShape>>(left: L, right: R) -> Self {
+        Self::new(left, right, CsgOperation::Intersection)
+    }
+
+    pub fn difference<L: Into<DynShape>, R: Into<DynShape>>(left: L, right: R) -> Self {
+        Self::new(left, right, CsgOperation::Difference)
+    }
+
+    fn find_boundaries(&self, ray: &Ray) -> Vec<RayIntersection> {
+        let left = self.left.hit_all(ray, DisRange::positive());
+        let right = self.right.hit_all(ray, DisRange::positive());
+
+        let starts_inside = |hits: &[RayIntersection]| {
+            hits.first()
+                .is_some_and(|hit| hit.side() == SurfaceSide::Back)
+        };
+        let (mut in_left, mut in_right) = (starts_inside(&left), starts_inside(&right));
+        let mut inside = self.operation.contains(in_left, in_right);
+
+        let mut events = (left.into_iter().map(|hit| (hit, true)))
+            .chain(right.into_iter().map(|hit| (hit, false)))
+            .collect::<Vec<_>>();
+        events.sort_by_key(|(hit, _)| hit.distance());
+
+        let mut res = Vec::new();
+        for (hit, from_left) in events {
+            let entering = hit.side() == SurfaceSide::Front;
+            if from_left {
+                in_left = entering;
+            } else {
+                in_right = entering;
+            }
+
+            let inside_next = self.operation.contains(in_left, in_right);
+            if inside_next != inside {
+                let side = if inside_next {
+                    SurfaceSide::Front
+                } else {
+                    SurfaceSide::Back
+                };
+                res.push(hit.with_side(side));
+                inside = inside_next;
+            }
+        }
+        res
+    }
+}
+
+impl Shape for Csg {
+    fn kind(&self) -> ShapeKind {
+        ShapeKind::Csg
+    }
+
+    fn hit_part<'a>(&self, ray: &'a Ray, range: DisRange) -> Option<RayIntersectionPart<'a>> {
+        self.hit(ray, range)
+            .map(|intersection| RayIntersectionPart::new(intersection.distance(), ray))
+    }
+
+    fn hit(&self, ray: &Ray, range: DisRange) -> Option<RayIntersection> {
+        (self.find_boundaries(ray).into_iter()).find(|hit| range.contains(&hit.distance()))
+    }
+
+    fn hit_all(&self, ray: &Ray, range: DisRange) -> Vec<RayIntersection> {
+        (self.find_boundaries(ray).into_iter())
+            .filter(|hit| range.contains(&hit.distance()))
+            .collect()
+    }
+
+    fn complete_part(&self, part: RayIntersectionPart) -> RayIntersection {
+        let boundaries = self.find_boundaries(part.ray());
+        (boundaries.into_iter())
+            .min_by_key(|hit| (hit.distance() - part.distance()).abs())
+            .expect("part should be produced by Csg::hit_part")
+    }
+
+    fn area(&self) -> Area {
+        match self.operation {
+            CsgOperation::Union => self.left.area() + self.right.area(),
+            CsgOperation::Intersection | CsgOperation::Difference => self.left.area(),
+        }
+    }
+
+    fn normal(&self, position: Point) -> Normal {
+        self.left.normal(position)
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        match self.operation {
+            CsgOperation::Union => {
+                let left = self.left.bounding_box()?;
+                let right = self.right.bounding_box()?;
+                Some(left.merge(&right))
+            }
+            CsgOperation::Intersection => {
+                (self.left.bounding_box()).or_else(|| self.right.bounding_box())
+            }
+            CsgOperation::Difference => self.left.bounding_box(),
+        }
+    }
+}
+
+impl Sampleable for Csg {
+    fn get_point_sampler(&self, _shape_id: ShapeId) -> Option<Box<dyn PointSampling>> {
+        None
+    }
+
+    fn get_light_sampler(&self, _shape_id: ShapeId) -> Option<Box<dyn LightSampling>> {
+        None
+    }
+
+    fn get_photon_sampler(
+        &self,
+        _shape_id: ShapeId,
+        _emissive: Emissive,
+    ) -> Option<Box<dyn PhotonSampling>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::{Direction, Distance};
+    use crate::domain::math::numeric::Val;
+    use crate::domain::shape::primitive::{Aabb, Sphere};
+
+    use super::*;
+
+    fn hollow_box() -> Csg {
+        Csg::difference(
+            Aabb::new(
+                Point::new(Val(-1.0), Val(-1.0), Val(-1.0)),
+                Point::new(Val(1.0), Val(1.0), Val(1.0)),
+            ),
+            Sphere::new(Point::new(Val(0.0), Val(0.0), Val(0.0)), Val(0.5)).unwrap(),
+        )
+    }
+
+    #[test]
+    fn csg_hit_all_succeeds_seeing_hollow_interior_of_difference() {
+        let csg = hollow_box();
+        let ray = Ray::new(
+            Point::new(Val(0.0), Val(0.0), Val(-3.0)),
+            Direction::z_direction(),
+        );
+
+        let hits = csg.hit_all(&ray, DisRange::positive());
+        let distances = hits.iter().map(|hit| hit.distance()).collect::<Vec<_>>();
+        let expected = [Val(2.0), Val(2.5), Val(3.5), Val(4.0)].map(|d| Distance::new(d).unwrap());
+        assert_eq!(distances, expected);
+
+        let sides = hits.iter().map(|hit| hit.side()).collect::<Vec<_>>();
+        assert_eq!(
+            sides,
+            [
+                SurfaceSide::Front,
+                SurfaceSide::Back,
+                SurfaceSide::Front,
+                SurfaceSide::Back,
+            ],
+        );
+        assert_eq!(hits[1].normal(), -Normal::z_direction());
+    }
+
+    #[test]
+    fn csg_hit_succeeds_from_inside_hollow() {
+        let csg = hollow_box();
+        let ray = Ray::new(
+            Point::new(Val(0.0), Val(0.0), Val(0.0)),
+            Direction::x_direction(),
+        );
+
+        let intersection = csg.hit(&ray, DisRange::positive()).unwrap();
+        assert_eq!(intersection.distance(), Distance::new(Val(0.5)).unwrap());
+        assert_eq!(intersection.side(), SurfaceSide::Front);
+    }
+
+    #[test]
+    fn csg_hit_succeeds_with_union_and_intersection() {
+        let left = Sphere::new(Point::new(Val(-0.5), Val(0.0), Val(0.0)), Val(1.0)).unwrap();
+        let right = Sphere::new(Point::new(Val(0.5), Val(0.0), Val(0.0)), Val(1.0)).unwrap();
+        let ray = Ray::new(
+            Point::new(Val(-3.0), Val(0.0), Val(0.0)),
+            Direction::x_direction(),
+        );
+
+        let union = Csg::union(left.clone(), right.clone());
+        let hits = union.hit_all(&ray, DisRange::positive());
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].distance(), Distance::new(Val(1.5)).unwrap());
+        assert_eq!(hits[1].distance(), Distance::new(Val(4.5)).unwrap());
+
+        let intersection = Csg::intersection(left, right);
+        let hits = intersection.hit_all(&ray, DisRange::positive());
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].distance(), Distance::new(Val(2.5)).unwrap());
+        assert_eq!(hits[1].distance(), Distance::new(Val(3.5)).unwrap());
+    }
+}
@@ -1,11 +1,13 @@
+use std::borrow::Cow;
 use std::ops::RangeBounds;
 use std::sync::Arc;
 
 use getset::Getters;
 
 use crate::domain::material::primitive::Emissive;
-use crate::domain::math::geometry::{Area, Normal, Point};
-use crate::domain::math::numeric::DisRange;
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::geometry::{Area, Distance, Normal, Point};
+use crate::domain::math::numeric::{DisRange, Val};
 use crate::domain::math::transformation::*;
 use crate::domain::ray::Ray;
 use crate::domain::ray::event::{RayIntersection, RayIntersectionPart};
@@ -22,23 +24,34 @@ pub struct Instance {
     prototype: Arc<DynShape>,
     #[getset(get = "pub")]
     transformation: Sequential,
+    #[getset(get = "pub")]
+    end_transformation: Option<Sequential>,
 }
 
 impl Instance {
+    const MOTION_BOUNDS_STEPS: usize = 8;
+
     pub fn new(prototype: Arc<DynShape>, transformation: Sequential) -> Self {
         Self {
             prototype,
             transformation,
+            end_transformation: None,
         }
     }
 
-    pub fn of(prototype: Arc<DynShape>) -> Self {
+    /// Light sampling on the instance always uses `start`.
+    pub fn animated(prototype: Arc<DynShape>, start: Sequential, end: Sequential) -> Self {
         Self {
             prototype,
-            transformation: Sequential::default(),
+            transformation: start,
+            end_transformation: Some(end),
         }
     }
 
+    pub fn of(prototype: Arc<DynShape>) -> Self {
+        Self::new(prototype, Sequential::default())
+    }
+
     pub fn wrap<S: Into<DynShape>>(prototype: S) -> Self {
         Self::of(Arc::new(prototype.into()))
     }
@@ -50,6 +63,13 @@ impl Instance {
         }
     }
 
+    pub fn shear(self, shear: Shear) -> Self {
+        Self {
+            transformation: self.transformation.with_shear(shear),
+            ..self
+        }
+    }
+
     pub fn rotate(self, rotation: Rotation) -> Self {
         Self {
             transformation: self.transformation.with_rotation(rotation),
@@ -63,6 +83,91 @@ impl Instance {
             ..self
         }
     }
+
+    pub fn transformation_at(&self, time: Val) -> Cow<'_, Sequential> {
+        match &self.end_transformation {
+            Some(end) => Cow::Owned(self.transformation.interpolate(end, time)),
+            None => Cow::Borrowed(&self.transformation),
+        }
+    }
+
+    fn calc_swept_bounds(bbox: &BoundingBox, start: &Sequential, end: &Sequential) -> BoundingBox {
+        let linear = Self::calc_linear_bounds(bbox, start, end);
+
+        let (q0, q1) = (start.rotation().quaternion(), end.rotation().quaternion());
+        let (min, max) = if q0 == q1 {
+            let rotated = linear.transform(start.rotation());
+            (rotated.min(), rotated.max())
+        } else {
+            let center = linear.centroid();
+            let radius = (linear.max() - center).norm();
+            let poses = (0..=Self::MOTION_BOUNDS_STEPS)
+                .map(|step| q0.slerp(q1, Val::from(step) / Val::from(Self::MOTION_BOUNDS_STEPS)))
+                .collect::<Vec<_>>();
+            let step_angle = (poses.windows(2))
+                .map(|w| Val(2.0) * w[0].dot(w[1]).abs().min(Val(1.0)).acos())
+                .fold(Val(0.0), Val::max);
+            let sagitta =
+                (center - Point::default()).norm() * (Val(1.0) - (step_angle / Val(2.0)).cos());
+            let padding = Vector::broadcast(radius + sagitta);
+
+            let centers = poses
+                .into_iter()
+                .map(|q| center.transform(&Rotation::from(q)));
+            let (min, max) = centers.fold(
+                (
+                    center.transform(start.rotation()),
+                    center.transform(start.rotation()),
+                ),
+                |(min, max), c| (min.component_min(&c), max.component_max(&c)),
+            );
+            (min - padding, max + padding)
+        };
+
+        let (d0, d1) = (
+            start.translation().displacement(),
+            end.translation().displacement(),
+        );
+        let low = Vector::new(d0.x().min(d1.x()), d0.y().min(d1.y()), d0.z().min(d1.z()));
+        let high = Vector::new(d0.x().max(d1.x()), d0.y().max(d1.y()), d0.z().max(d1.z()));
+        BoundingBox::new(min + low, max + high)
+    }
+
+    fn calc_linear_bounds(bbox: &BoundingBox, start: &Sequential, end: &Sequential) -> BoundingBox {
+        let span = |a: Val, b: Val| (a.min(b), a.max(b));
+        let mul = |(a0, a1): (Val, Val), (b0, b1): (Val, Val)| {
+            let products = [a0 * b0, a0 * b1, a1 * b0, a1 * b1];
+            (
+                products.into_iter().fold(Val::INFINITY, Val::min),
+                products.into_iter().fold(-Val::INFINITY, Val::max),
+            )
+        };
+
+        let (s0, s1) = (start.scaling().factors(), end.scaling().factors());
+        let mut bounds: [(Val, Val); 3] = std::array::from_fn(|axis| {
+            let factor = span(s0.axis(axis), s1.axis(axis));
+            mul(factor, (bbox.min().axis(axis), bbox.max().axis(axis)))
+        });
+
+        let (shear0, shear1) = (start.shear(), end.shear());
+        let factor = if (shear0.axis(), shear0.source()) == (shear1.axis(), shear1.source()) {
+            span(shear0.factor(), shear1.factor())
+        } else {
+            (shear0.factor(), shear0.factor())
+        };
+        let offset = mul(factor, bounds[shear0.source()]);
+        let target = &mut bounds[shear0.axis()];
+        *target = (target.0 + offset.0, target.1 + offset.1);
+
+        BoundingBox::new(
+            Point::new(bounds[0].0, bounds[1].0, bounds[2].0),
+            Point::new(bounds[0].1, bounds[1].1, bounds[2].1),
+        )
+    }
+
+    fn calc_stretch(ray: &Ray, inv_tr: &Sequential) -> Val {
+        ray.direction().to_vector().transform(inv_tr).norm()
+    }
 }
 
 impl Shape for Instance {
@@ -71,45 +176,56 @@ impl Shape for Instance {
     }
 
     fn hit_part<'a>(&self, ray: &'a Ray, range: DisRange) -> Option<RayIntersectionPart<'a>> {
-        let inv_tr = self.transformation.clone().inverse();
+        let tr = self.transformation_at(ray.time());
+        let inv_tr = tr.clone().into_owned().inverse();
 
         let ray_tr = ray.clone().transform(&inv_tr);
+        let stretch = Self::calc_stretch(ray, &inv_tr);
         let range_tr = DisRange::from((
-            range.start_bound().map(|d| d.transform(&inv_tr)),
-            range.end_bound().map(|d| d.transform(&inv_tr)),
+            range
+                .start_bound()
+                .map(|d| Distance::clamp(d.value() * stretch)),
+            range
+                .end_bound()
+                .map(|d| Distance::clamp(d.value() * stretch)),
         ));
 
         let part_tr = self.prototype.hit_part(&ray_tr, range_tr)?;
-        Some(RayIntersectionPart::new(
-            part_tr.distance().transform(&self.transformation),
-            ray,
-        ))
+        let distance = Distance::clamp(part_tr.distance().value() / stretch);
+        Some(RayIntersectionPart::new(distance, ray).with_face(part_tr.face()))
     }
 
     fn complete_part(&self, part: RayIntersectionPart) -> RayIntersection {
-        let inv_tr = self.transformation.clone().inverse();
+        let tr = self.transformation_at(part.ray().time());
+        let inv_tr = tr.clone().into_owned().inverse();
 
-        let distance_tr = part.distance().transform(&inv_tr);
+        let stretch = Self::calc_stretch(part.ray(), &inv_tr);
+        let distance_tr = Distance::clamp(part.distance().value() * stretch);
         let ray_tr = part.ray().clone().transform(&inv_tr);
-        let part_tr = RayIntersectionPart::new(distance_tr, &ray_tr);
+        let part_tr = RayIntersectionPart::new(distance_tr, &ray_tr).with_face(part.face());
 
         let intersection_tr = self.prototype.complete_part(part_tr);
-        intersection_tr.transform(&self.transformation)
+        (intersection_tr.transform(tr.as_ref())).with_distance(part.distance())
     }
 
+    /// Non-uniform scaling is approximated by the mean scale.
     fn area(&self) -> Area {
         self.prototype.area().transform(&self.transformation)
     }
 
     fn normal(&self, position: Point) -> Normal {
+        let inv_tr = self.transformation.clone().inverse();
         self.prototype
-            .normal(position)
+            .normal(position.transform(&inv_tr))
             .transform(&self.transformation)
     }
 
     fn bounding_box(&self) -> Option<BoundingBox> {
         let bbox = self.prototype.bounding_box()?;
-        Some(bbox.transform(&self.transformation))
+        match &self.end_transformation {
+            Some(end) => Some(Self::calc_swept_bounds(&bbox, &self.transformation, end)),
+            None => Some(bbox.transform(&self.transformation)),
+        }
     }
 }
 
@@ -137,11 +253,10 @@ impl Sampleable for Instance {
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::math::algebra::Vector;
-    use crate::domain::math::geometry::{Direction, Distance, Point};
+    use crate::domain::math::geometry::Direction;
     use crate::domain::math::numeric::Val;
     use crate::domain::ray::event::SurfaceSide;
-    use crate::domain::shape::primitive::Polygon;
+    use crate::domain::shape::primitive::{Aabb, Polygon, Sphere};
 
     use super::*;
 
@@ -178,4 +293,137 @@ mod tests {
         assert_eq!(intersection.normal(), -Normal::z_direction());
         assert_eq!(intersection.side(), SurfaceSide::Front);
     }
+
+    #[test]
+    fn instance_hit_succeeds_transforming_normals_of_rotated_box() {
+        let prototype = Aabb::new(
+            Point::new(Val(-0.5), Val(-0.5), Val(-0.5)),
+            Point::new(Val(0.5), Val(0.5), Val(0.5)),
+        );
+        let diagonal = Direction::normalize(Vector::new(Val(1.0), Val(0.0), Val(1.0))).unwrap();
+        let instance = Instance::wrap(prototype).rotate(Rotation::new(
+            Direction::z_direction(),
+            diagonal,
+            Val(0.0),
+        ));
+
+        for y in [Val(0.0), Val(0.13), Val(-0.31), Val(0.4471)] {
+            let ray = Ray::new(
+                Point::new(Val(0.237), y, Val(-3.0)),
+                Direction::z_direction(),
+            );
+            let intersection = instance.hit(&ray, DisRange::positive()).unwrap();
+
+            let expected = Normal::normalize(Vector::new(Val(1.0), Val(0.0), Val(-1.0))).unwrap();
+            assert_eq!(intersection.normal(), expected);
+            assert_eq!(intersection.side(), SurfaceSide::Front);
+        }
+    }
+
+    #[test]
+    fn instance_hit_succeeds_keeping_normals_of_ellipsoid_perpendicular() {
+        let sphere = Sphere::new(Point::default(), Val(1.0)).unwrap();
+        let instance =
+            Instance::wrap(sphere).scale(Scaling::new(Val(2.0), Val(1.0), Val(1.0)).unwrap());
+
+        let ray = Ray::new(
+            Point::new(Val(1.0), Val(0.0), Val(-3.0)),
+            Direction::z_direction(),
+        );
+        let intersection = instance.hit(&ray, DisRange::positive()).unwrap();
+
+        let z = -Val(0.75).sqrt();
+        assert_eq!(intersection.position(), Point::new(Val(1.0), Val(0.0), z));
+        assert_eq!(
+            intersection.distance(),
+            Distance::new(Val(3.0) + z).unwrap()
+        );
+
+        let expected = Normal::normalize(Vector::new(Val(0.25), Val(0.0), z)).unwrap();
+        assert_eq!(intersection.normal(), expected);
+        assert_eq!(instance.normal(intersection.position()), expected);
+        assert_eq!(intersection.side(), SurfaceSide::Front);
+    }
+
+    #[test]
+    fn instance_area_succeeds_given_uniform_scaling() {
+        let sphere = Sphere::new(Point::default(), Val(1.0)).unwrap();
+        let instance = Instance::wrap(sphere).scale(Scaling::uniform(Val(2.0)).unwrap());
+        assert_eq!(instance.area(), Area::new(Val(16.0) * Val::PI).unwrap());
+    }
+
+    #[test]
+    fn instance_bounding_box_succeeds_containing_every_pose_of_motion() {
+        let prototype = Aabb::new(
+            Point::new(Val(1.9), Val(-0.1), Val(-0.1)),
+            Point::new(Val(2.1), Val(0.1), Val(0.1)),
+        );
+        let angle = Val(100.0).to_radians();
+        let end_dir =
+            Direction::normalize(Vector::new(angle.cos(), angle.sin(), Val(0.0))).unwrap();
+        let instance = Instance::animated(
+            Arc::new(prototype.clone().into()),
+            Sequential::default(),
+            Sequential::default()
+                .with_scaling(Scaling::uniform(Val(1.5)).unwrap())
+                .with_rotation(Rotation::new(Direction::x_direction(), end_dir, Val(0.0)))
+                .with_translation(Translation::new(Vector::new(Val(0.0), Val(0.0), Val(1.0)))),
+        );
+        let bbox = instance.bounding_box().unwrap();
+
+        let corners = prototype.bounding_box().unwrap();
+        for i in 0..=1000 {
+            let transformation = instance.transformation_at(Val::from(i) / Val(1000.0));
+            let posed = corners.clone().transform(transformation.as_ref());
+            for axis in 0..3 {
+                assert!(bbox.min().axis(axis) <= posed.min().axis(axis), "{i}");
+                assert!(posed.max().axis(axis) <= bbox.max().axis(axis), "{i}");
+            }
+        }
+    }
+
+    #[test]
+    fn instance_hit_succeeds_smearing_moving_sphere_across_shutter() {
+        let sphere = Sphere::new(Point::default(), Val(0.5)).unwrap();
+        let start = Translation::new(Vector::new(Val(-2.0), Val(0.0), Val(0.0)));
+        let end = Translation::new(Vector::new(Val(2.0), Val(0.0), Val(0.0)));
+        let instance = Instance::animated(
+            Arc::new(sphere.into()),
+            Sequential::default().with_translation(start),
+            Sequential::default().with_translation(end),
+        );
+
+        let bbox = instance.bounding_box().unwrap();
+        assert_eq!(bbox.min().x(), Val(-2.5));
+        assert_eq!(bbox.max().x(), Val(2.5));
+
+        let times = (0..16)
+            .map(|i| Val::from(i) / Val(15.0))
+            .collect::<Vec<_>>();
+        let count_hits = |x: Val| {
+            (times.iter())
+                .filter(|time| {
+                    let ray =
+                        Ray::new(Point::new(x, Val(0.0), Val(-3.0)), Direction::z_direction())
+                            .with_time(**time);
+                    instance.hit(&ray, DisRange::positive()).is_some()
+                })
+                .count()
+        };
+
+        let streak = [Val(-2.0), Val(-1.0), Val(0.0), Val(1.0), Val(2.0)].map(count_hits);
+        assert!(streak.iter().all(|&hits| 0 < hits && hits < times.len()));
+        assert_eq!(count_hits(Val(3.0)), 0);
+
+        let ray = Ray::new(
+            Point::new(Val(2.0), Val(0.0), Val(-3.0)),
+            Direction::z_direction(),
+        )
+        .with_time(Val(1.0));
+        let intersection = instance.hit(&ray, DisRange::positive()).unwrap();
+        assert_eq!(
+            intersection.position(),
+            Point::new(Val(2.0), Val(0.0), Val(-0.5))
+        );
+    }
 }
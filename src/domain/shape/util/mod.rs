@@ -1,5 +1,7 @@
 mod container;
+mod csg;
 mod instance;
 
 pub use container::{ShapeConstructor, ShapeContainer, ShapeId};
+pub use csg::{Csg, CsgOperation};
 pub use instance::Instance;
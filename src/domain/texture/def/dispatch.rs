@@ -1,6 +1,7 @@
 use enum_dispatch::enum_dispatch;
 
 use crate::domain::color::core::{Albedo, Spectrum};
+use crate::domain::math::numeric::Val;
 use crate::domain::ray::event::RayIntersection;
 use crate::domain::texture::primitive::*;
 
@@ -11,8 +12,10 @@ use super::{Texture, TextureKind};
 pub enum DynTexture {
     Checkerboard(Checkerboard),
     Constant(Constant),
+    Gradient(Gradient),
     ImageMap(ImageMap),
     Noise(Noise),
+    TransformedUv(TransformedUv),
     VisibleNormal(VisibieNormal),
     VisibleUvCoordinate(VisibleUvCoordinate),
 }
@@ -41,6 +44,13 @@ impl DynAlbedoTexture {
         }
     }
 
+    pub fn uses_footprint(&self) -> bool {
+        match self {
+            Self::Constant(_) => false,
+            Self::Dyn(s) => s.uses_footprint(),
+        }
+    }
+
     #[inline]
     pub fn lookup(&self, intersection: &RayIntersection) -> Albedo {
         match self {
@@ -61,3 +71,56 @@ where
         }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynScalarTexture {
+    Constant(Val),
+    Dyn(Box<DynTexture>),
+}
+
+impl DynScalarTexture {
+    pub fn kind(&self) -> TextureKind {
+        match self {
+            Self::Constant(_) => TextureKind::Constant,
+            Self::Dyn(s) => s.kind(),
+        }
+    }
+
+    pub fn uses_footprint(&self) -> bool {
+        match self {
+            Self::Constant(_) => false,
+            Self::Dyn(s) => s.uses_footprint(),
+        }
+    }
+
+    #[inline]
+    pub fn lookup(&self, intersection: &RayIntersection) -> Val {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Dyn(s) => s.lookup(intersection).red(),
+        }
+    }
+
+    pub fn constant(&self) -> Option<Val> {
+        match self {
+            Self::Constant(value) => Some(*value),
+            Self::Dyn(_) => None,
+        }
+    }
+}
+
+impl From<Val> for DynScalarTexture {
+    #[inline]
+    fn from(value: Val) -> Self {
+        Self::Constant(value)
+    }
+}
+
+impl From<DynTexture> for DynScalarTexture {
+    fn from(value: DynTexture) -> Self {
+        match value {
+            DynTexture::Constant(constant) => Self::Constant(constant.value().red()),
+            texture => Self::Dyn(Box::new(texture)),
+        }
+    }
+}
@@ -2,6 +2,6 @@ mod dispatch;
 mod texture;
 mod uv;
 
-pub use dispatch::{DynAlbedoTexture, DynTexture};
+pub use dispatch::{DynAlbedoTexture, DynScalarTexture, DynTexture};
 pub use texture::{Texture, TextureKind};
-pub use uv::{TryNewUvCoordinateError, UvCoordinate, UvCoordinateInterpolation};
+pub use uv::{TryNewUvCoordinateError, UvCoordinate, UvCoordinateInterpolation, WrapMode};
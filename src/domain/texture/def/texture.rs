@@ -10,6 +10,8 @@ use super::DynTexture;
 pub trait Texture: Send + Sync {
     fn kind(&self) -> TextureKind;
 
+    fn uses_footprint(&self) -> bool;
+
     fn lookup(&self, intersection: &RayIntersection) -> Spectrum;
 }
 
@@ -17,8 +19,10 @@ pub trait Texture: Send + Sync {
 pub enum TextureKind {
     Checkerboard,
     Constant,
+    Gradient,
     ImageMap,
     Noise,
+    TransformedUv,
     VisibleNormal,
     VisibleUvCoordinate,
 }
@@ -19,6 +19,16 @@ impl UvCoordinate {
         Self(u.clamp(Val(0.0), Val(1.0)), v.clamp(Val(0.0), Val(1.0)))
     }
 
+    #[inline]
+    pub fn unbounded(u: Val, v: Val) -> Self {
+        Self(u, v)
+    }
+
+    #[inline]
+    pub fn wrap(&self, wrap_u: WrapMode, wrap_v: WrapMode) -> Self {
+        Self(wrap_u.apply(self.0), wrap_v.apply(self.1))
+    }
+
     #[inline]
     pub fn u(&self) -> Val {
         self.0
@@ -30,6 +40,26 @@ impl UvCoordinate {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WrapMode {
+    Repeat,
+    Clamp,
+    Mirror,
+}
+
+impl WrapMode {
+    pub fn apply(&self, x: Val) -> Val {
+        match self {
+            Self::Repeat => x.rem_euclid(Val(1.0)),
+            Self::Clamp => x.clamp(Val(0.0), Val(1.0)),
+            Self::Mirror => {
+                let x = x.rem_euclid(Val(2.0));
+                if x > Val(1.0) { Val(2.0) - x } else { x }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Snafu, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum TryNewUvCoordinateError {
@@ -86,7 +116,7 @@ impl UvCoordinateInterpolation {
         let w2 = pos.dot(basis2) / basis2.norm_squared();
         let u = uv0.u() + w1 * uv_basis1.0 + w2 * uv_basis2.0;
         let v = uv0.v() + w1 * uv_basis1.1 + w2 * uv_basis2.1;
-        UvCoordinate::clamp(u, v)
+        UvCoordinate::unbounded(u, v)
     }
 }
 
@@ -106,6 +136,16 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn wrap_mode_apply_succeeds() {
+        assert_eq!(WrapMode::Repeat.apply(Val(1.25)), Val(0.25));
+        assert_eq!(WrapMode::Repeat.apply(Val(-0.25)), Val(0.75));
+        assert_eq!(WrapMode::Clamp.apply(Val(1.25)), Val(1.0));
+        assert_eq!(WrapMode::Clamp.apply(Val(-0.25)), Val(0.0));
+        assert_eq!(WrapMode::Mirror.apply(Val(1.25)), Val(0.75));
+        assert_eq!(WrapMode::Mirror.apply(Val(-0.25)), Val(0.25));
+    }
+
     #[test]
     fn uv_coordinate_interpolation_interpolate_succeeds() {
         let interpolation = UvCoordinateInterpolation::new()
@@ -1,7 +1,9 @@
 mod def;
 mod fbm;
 mod perlin;
+mod worley;
 
 pub use def::NoiseGenerator;
 pub use fbm::{FbmNoiseGenerator, TryNewFbmNoiseGeneratorError};
 pub use perlin::PerlinNoiseGenerator;
+pub use worley::{TryNewWorleyNoiseGeneratorError, WorleyDistance, WorleyNoiseGenerator};
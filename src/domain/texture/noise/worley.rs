@@ -0,0 +1,167 @@
+use snafu::prelude::*;
+
+use crate::domain::math::geometry::Point;
+use crate::domain::math::numeric::Val;
+
+use super::NoiseGenerator;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorleyDistance {
+    F1,
+    F2,
+    F2MinusF1,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorleyNoiseGenerator {
+    density: usize,
+    distance: WorleyDistance,
+    seed: u64,
+    period: Option<i64>,
+}
+
+impl WorleyNoiseGenerator {
+    pub fn new(
+        density: usize,
+        distance: WorleyDistance,
+        seed: u64,
+    ) -> Result<Self, TryNewWorleyNoiseGeneratorError> {
+        ensure!(density > 0, InvalidDensitySnafu);
+        Ok(Self {
+            density,
+            distance,
+            seed,
+            period: None,
+        })
+    }
+
+    pub fn with_period(self, period: usize) -> Result<Self, TryNewWorleyNoiseGeneratorError> {
+        ensure!(period > 0, InvalidPeriodSnafu);
+        let period = Some(period as i64);
+        Ok(Self { period, ..self })
+    }
+
+    pub fn feature_distances(&self, position: Point) -> (Val, Val) {
+        let (xi, yi, zi) = (
+            i64::from(position.x().floor()),
+            i64::from(position.y().floor()),
+            i64::from(position.z().floor()),
+        );
+
+        let (mut f1, mut f2) = (Val::INFINITY, Val::INFINITY);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    for point in self.feature_points((xi + dx, yi + dy, zi + dz)) {
+                        let distance = (point - position).norm();
+                        if distance < f1 {
+                            f2 = f1;
+                            f1 = distance;
+                        } else if distance < f2 {
+                            f2 = distance;
+                        }
+                    }
+                }
+            }
+        }
+        (f1, f2)
+    }
+
+    fn feature_points(&self, cell: (i64, i64, i64)) -> impl Iterator<Item = Point> {
+        let (x, y, z) = cell;
+        let wrapped = match self.period {
+            Some(period) => (
+                x.rem_euclid(period),
+                y.rem_euclid(period),
+                z.rem_euclid(period),
+            ),
+            None => cell,
+        };
+        let origin = Point::new(Val::from(x), Val::from(y), Val::from(z));
+
+        let seed = self.seed;
+        (0..self.density).map(move |i| {
+            let base = Self::hash(seed, wrapped, i as u64);
+            let offset = |salt: u64| {
+                let bits = Self::mix(base ^ salt) >> 11;
+                Val(bits as f64 / (1u64 << 53) as f64)
+            };
+            Point::new(
+                origin.x() + offset(0x1),
+                origin.y() + offset(0x2),
+                origin.z() + offset(0x3),
+            )
+        })
+    }
+
+    fn hash(seed: u64, cell: (i64, i64, i64), index: u64) -> u64 {
+        let mut h = Self::mix(seed);
+        h = Self::mix(h ^ cell.0 as u64);
+        h = Self::mix(h ^ cell.1 as u64);
+        h = Self::mix(h ^ cell.2 as u64);
+        Self::mix(h ^ index)
+    }
+
+    fn mix(mut x: u64) -> u64 {
+        x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        x ^ (x >> 31)
+    }
+}
+
+impl NoiseGenerator for WorleyNoiseGenerator {
+    fn evaluate(&self, position: Point) -> Val {
+        let (f1, f2) = self.feature_distances(position);
+        let value = match self.distance {
+            WorleyDistance::F1 => f1,
+            WorleyDistance::F2 => f2,
+            WorleyDistance::F2MinusF1 => f2 - f1,
+        };
+        Val(2.0) * value.clamp(Val(0.0), Val(1.0)) - Val(1.0)
+    }
+}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewWorleyNoiseGeneratorError {
+    #[snafu(display("feature point density should be positive"))]
+    InvalidDensity,
+    #[snafu(display("tiling period should be positive"))]
+    InvalidPeriod,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn worley_noise_generator_new_fails_when_density_is_invalid() {
+        assert!(matches!(
+            WorleyNoiseGenerator::new(0, WorleyDistance::F1, 0),
+            Err(TryNewWorleyNoiseGeneratorError::InvalidDensity),
+        ));
+    }
+
+    #[test]
+    fn worley_noise_generator_feature_distances_succeeds_at_feature_points() {
+        let generator = WorleyNoiseGenerator::new(2, WorleyDistance::F1, 42).unwrap();
+        for point in generator.feature_points((3, -1, 0)) {
+            let (f1, f2) = generator.feature_distances(point);
+            assert!(f1 < Val(1e-6));
+            assert!(f2 > f1);
+            assert_eq!(generator.evaluate(point), Val(-1.0));
+        }
+    }
+
+    #[test]
+    fn worley_noise_generator_evaluate_succeeds_tiling_with_period() {
+        let generator = WorleyNoiseGenerator::new(1, WorleyDistance::F2MinusF1, 7)
+            .unwrap()
+            .with_period(4)
+            .unwrap();
+        let a = Point::new(Val(0.3), Val(1.7), Val(2.2));
+        let b = Point::new(Val(4.3), Val(-2.3), Val(6.2));
+        assert_eq!(generator.evaluate(a), generator.evaluate(b));
+    }
+}
@@ -1,6 +1,6 @@
 use snafu::prelude::*;
 
-use crate::domain::color::core::Spectrum;
+use crate::domain::color::core::{Color, Spectrum};
 use crate::domain::math::numeric::Val;
 use crate::domain::ray::event::RayIntersection;
 use crate::domain::texture::def::{DynTexture, Texture, TextureKind};
@@ -29,6 +29,31 @@ impl Checkerboard {
             frequency: scale.recip(),
         })
     }
+
+    fn filter_square_wave(x: Val, width: Val) -> Val {
+        let phase = |x: Val| Val(0.5) * x - (Val(0.5) * x).floor();
+        if width <= Val(0.0) {
+            return if phase(x) < Val(0.5) {
+                Val(1.0)
+            } else {
+                Val(-1.0)
+            };
+        }
+        let g = |x: Val| (phase(x) - Val(0.5)).abs();
+        let (lower, upper) = (x - Val(0.5) * width, x + Val(0.5) * width);
+        Val(2.0) * (g(lower) - g(upper)) / width
+    }
+
+    fn calc_coverage(&self, intersection: &RayIntersection, footprint: Val) -> Val {
+        let position = intersection.position();
+        let normal = intersection.normal();
+        let width =
+            |n: Val| footprint * self.frequency * (Val(1.0) - n.powi(2)).max(Val(0.0)).sqrt();
+        let sx = Self::filter_square_wave(self.frequency * position.x(), width(normal.x()));
+        let sy = Self::filter_square_wave(self.frequency * position.y(), width(normal.y()));
+        let sz = Self::filter_square_wave(self.frequency * position.z(), width(normal.z()));
+        Val(0.5) + Val(0.5) * sx * sy * sz
+    }
 }
 
 impl Texture for Checkerboard {
@@ -36,7 +61,18 @@ impl Texture for Checkerboard {
         TextureKind::Checkerboard
     }
 
+    fn uses_footprint(&self) -> bool {
+        self.texture0.uses_footprint() || self.texture1.uses_footprint()
+    }
+
     fn lookup(&self, intersection: &RayIntersection) -> Spectrum {
+        if let Some(footprint) = intersection.footprint() {
+            let coverage = self.calc_coverage(intersection, footprint);
+            let c0 = self.texture0.lookup(intersection);
+            let c1 = self.texture1.lookup(intersection);
+            return Spectrum::lerp(c1, c0, coverage);
+        }
+
         let position = intersection.position();
         let x = usize::from((self.frequency * position.x()).floor());
         let y = usize::from((self.frequency * position.y()).floor());
@@ -55,3 +91,53 @@ pub enum TryNewCheckerboardError {
     #[snafu(display("scale of the checkerboard should be positive"))]
     NonPositiveScale,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::math::geometry::{Distance, Normal, Point};
+    use crate::domain::ray::event::SurfaceSide;
+
+    use super::*;
+
+    fn checkerboard() -> Checkerboard {
+        Checkerboard::new(Spectrum::broadcast(Val(1.0)), Spectrum::zero(), Val(0.5)).unwrap()
+    }
+
+    fn intersection_at(x: Val) -> RayIntersection {
+        RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            Point::new(x, Val(0.1), Val(0.0)),
+            Normal::z_direction(),
+            SurfaceSide::Front,
+        )
+    }
+
+    #[test]
+    fn checkerboard_lookup_succeeds_without_footprint() {
+        let checkerboard = checkerboard();
+        let bright = checkerboard.lookup(&intersection_at(Val(0.2)));
+        assert_eq!(bright, Spectrum::broadcast(Val(1.0)));
+        let dark = checkerboard.lookup(&intersection_at(Val(0.7)));
+        assert_eq!(dark, Spectrum::zero());
+    }
+
+    #[test]
+    fn checkerboard_lookup_succeeds_averaging_full_period() {
+        let checkerboard = checkerboard();
+        for x in [Val(0.0), Val(0.2), Val(0.6), Val(1.3)] {
+            let intersection = intersection_at(x).with_footprint(Val(1.0));
+            let color = checkerboard.lookup(&intersection);
+            assert_eq!(color, Spectrum::broadcast(Val(0.5)));
+        }
+    }
+
+    #[test]
+    fn checkerboard_lookup_succeeds_keeping_edges_with_small_footprint() {
+        let checkerboard = checkerboard();
+        let intersection = intersection_at(Val(0.2)).with_footprint(Val(0.1));
+        assert_eq!(
+            checkerboard.lookup(&intersection),
+            Spectrum::broadcast(Val(1.0))
+        );
+    }
+}
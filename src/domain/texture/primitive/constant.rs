@@ -28,6 +28,10 @@ impl Texture for Constant {
         TextureKind::Constant
     }
 
+    fn uses_footprint(&self) -> bool {
+        false
+    }
+
     #[inline]
     fn lookup(&self, _intersection: &RayIntersection) -> Spectrum {
         self.value
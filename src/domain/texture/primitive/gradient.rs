@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use snafu::prelude::*;
+
+use crate::domain::color::core::Spectrum;
+use crate::domain::color::map::Colormap;
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::event::RayIntersection;
+use crate::domain::texture::def::{Texture, TextureKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GradientAxis {
+    U,
+    V,
+    X,
+    Y,
+    Z,
+}
+
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    colormap: Arc<dyn Colormap>,
+    axis: GradientAxis,
+    start: Val,
+    end: Val,
+}
+
+impl Gradient {
+    pub fn new<CM, CMI>(
+        colormap: CMI,
+        axis: GradientAxis,
+        start: Val,
+        end: Val,
+    ) -> Result<Self, TryNewGradientError>
+    where
+        CM: Colormap + 'static,
+        CMI: Into<Arc<CM>>,
+    {
+        ensure!(start < end, InvalidRangeSnafu);
+        Ok(Self {
+            colormap: colormap.into(),
+            axis,
+            start,
+            end,
+        })
+    }
+
+    fn coordinate(&self, intersection: &RayIntersection) -> Val {
+        let uv =
+            || (intersection.uv()).expect("`Gradient` along a UV axis expects a UV coordinate");
+        match self.axis {
+            GradientAxis::U => uv().u(),
+            GradientAxis::V => uv().v(),
+            GradientAxis::X => intersection.position().x(),
+            GradientAxis::Y => intersection.position().y(),
+            GradientAxis::Z => intersection.position().z(),
+        }
+    }
+}
+
+impl Texture for Gradient {
+    fn kind(&self) -> TextureKind {
+        TextureKind::Gradient
+    }
+
+    fn uses_footprint(&self) -> bool {
+        false
+    }
+
+    fn lookup(&self, intersection: &RayIntersection) -> Spectrum {
+        let t = (self.coordinate(intersection) - self.start) / (self.end - self.start);
+        self.colormap.lookup(t.clamp(Val(0.0), Val(1.0)))
+    }
+}
+
+impl PartialEq for Gradient {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.colormap, &other.colormap)
+            && self.axis == other.axis
+            && self.start == other.start
+            && self.end == other.end
+    }
+}
+
+impl Eq for Gradient {}
+
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryNewGradientError {
+    #[snafu(display("start of the gradient should be less than its end"))]
+    InvalidRange,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::map::PaletteColormap;
+    use crate::domain::math::geometry::{Distance, Normal, Point};
+    use crate::domain::ray::event::SurfaceSide;
+    use crate::domain::texture::def::UvCoordinate;
+
+    use super::*;
+
+    fn intersection_at(position: Point, uv: UvCoordinate) -> RayIntersection {
+        RayIntersection::new(
+            Distance::new(Val(1.0)).unwrap(),
+            position,
+            Normal::z_direction(),
+            SurfaceSide::Front,
+        )
+        .with_uv(uv)
+    }
+
+    #[test]
+    fn gradient_lookup_succeeds_blending_stops() {
+        let red = Spectrum::new(Val(1.0), Val(0.0), Val(0.0));
+        let blue = Spectrum::new(Val(0.0), Val(0.0), Val(1.0));
+        let palette = PaletteColormap::new([(red, Val(0.0)), (blue, Val(1.0))]).unwrap();
+        let gradient = Gradient::new(palette, GradientAxis::U, Val(0.0), Val(1.0)).unwrap();
+
+        let uv = UvCoordinate::new(Val(0.5), Val(0.0)).unwrap();
+        let purple = Spectrum::new(Val(0.5), Val(0.0), Val(0.5));
+        assert_eq!(
+            gradient.lookup(&intersection_at(Point::default(), uv)),
+            purple
+        );
+    }
+
+    #[test]
+    fn gradient_lookup_succeeds_along_world_axis() {
+        let red = Spectrum::new(Val(1.0), Val(0.0), Val(0.0));
+        let blue = Spectrum::new(Val(0.0), Val(0.0), Val(1.0));
+        let palette = PaletteColormap::new([(red, Val(0.0)), (blue, Val(1.0))]).unwrap();
+        let gradient = Gradient::new(palette, GradientAxis::Y, Val(-2.0), Val(2.0)).unwrap();
+
+        let uv = UvCoordinate::new(Val(0.0), Val(0.0)).unwrap();
+        let below = Point::new(Val(0.0), Val(-5.0), Val(0.0));
+        assert_eq!(gradient.lookup(&intersection_at(below, uv)), red);
+        let middle = Point::new(Val(3.0), Val(1.0), Val(0.0));
+        let expected = Spectrum::new(Val(0.25), Val(0.0), Val(0.75));
+        assert_eq!(gradient.lookup(&intersection_at(middle, uv)), expected);
+    }
+
+    #[test]
+    fn gradient_new_fails_when_range_is_empty() {
+        let palette = PaletteColormap::new([
+            (Spectrum::zero(), Val(0.0)),
+            (Spectrum::broadcast(Val(1.0)), Val(1.0)),
+        ])
+        .unwrap();
+        assert!(matches!(
+            Gradient::new(palette, GradientAxis::X, Val(1.0), Val(1.0)),
+            Err(TryNewGradientError::InvalidRange),
+        ));
+    }
+}
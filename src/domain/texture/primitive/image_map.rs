@@ -1,14 +1,27 @@
 use std::sync::Arc;
 
-use crate::domain::color::core::{Color, Spectrum};
+use crate::domain::camera::Resolution;
+use crate::domain::color::core::{Color, ColorSpace, Spectrum};
 use crate::domain::image::core::Image;
 use crate::domain::math::numeric::Val;
 use crate::domain::ray::event::RayIntersection;
-use crate::domain::texture::def::{Texture, TextureKind};
+use crate::domain::texture::def::{Texture, TextureKind, UvCoordinate, WrapMode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterMode {
+    Nearest,
+    Bilinear,
+    Trilinear,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ImageMap {
+    source: Arc<Image>,
     image: Arc<Image>,
+    mipmaps: Arc<Vec<Image>>,
+    color_space: ColorSpace,
+    filtering: FilterMode,
+    wrap: (WrapMode, WrapMode),
 }
 
 impl ImageMap {
@@ -17,21 +30,147 @@ impl ImageMap {
     where
         I: Into<Arc<Image>>,
     {
-        let image = image.into();
-        Self { image }
+        let source = image.into();
+        let color_space = source.color_space();
+        let (image, mipmaps) = Self::prepare(&source, color_space);
+        Self {
+            source,
+            image,
+            mipmaps,
+            color_space,
+            filtering: FilterMode::Trilinear,
+            wrap: (WrapMode::Clamp, WrapMode::Clamp),
+        }
     }
-}
 
-impl Texture for ImageMap {
-    fn kind(&self) -> TextureKind {
-        TextureKind::ImageMap
+    #[inline]
+    pub fn with_color_space(self, color_space: ColorSpace) -> Self {
+        if color_space == self.color_space {
+            return self;
+        }
+        let (image, mipmaps) = Self::prepare(&self.source, color_space);
+        Self {
+            image,
+            mipmaps,
+            color_space,
+            ..self
+        }
     }
 
-    fn lookup(&self, intersection: &RayIntersection) -> Spectrum {
-        let uv = (intersection.uv()).expect("`ImageMap` expects a UV coordinate to be provided");
+    #[inline]
+    pub fn with_filtering(self, filtering: FilterMode) -> Self {
+        Self { filtering, ..self }
+    }
+
+    #[inline]
+    pub fn with_wrap(self, wrap_u: WrapMode, wrap_v: WrapMode) -> Self {
+        let wrap = (wrap_u, wrap_v);
+        Self { wrap, ..self }
+    }
+
+    #[inline]
+    pub fn resolution(&self) -> &Resolution {
+        self.image.resolution()
+    }
+
+    #[inline]
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+
+    fn prepare(source: &Arc<Image>, color_space: ColorSpace) -> (Arc<Image>, Arc<Vec<Image>>) {
+        let image = match color_space {
+            ColorSpace::Linear => source.clone(),
+            ColorSpace::Srgb => {
+                let resolution = source.resolution();
+                let mut image = Image::new(resolution.clone());
+                for r in 0..resolution.height() {
+                    for c in 0..resolution.width() {
+                        image.set(r, c, color_space.to_linear(source.get(r, c).unwrap()));
+                    }
+                }
+                Arc::new(image)
+            }
+        };
+        let mipmaps = Arc::new(Self::build_mipmaps(&image));
+        (image, mipmaps)
+    }
+
+    fn build_mipmaps(image: &Image) -> Vec<Image> {
+        let mut mipmaps: Vec<Image> = Vec::new();
+        loop {
+            let prev = mipmaps.last().unwrap_or(image);
+            let (height, width) = (prev.resolution().height(), prev.resolution().width());
+            if height == 1 && width == 1 {
+                return mipmaps;
+            }
+
+            let (next_height, next_width) = ((height / 2).max(1), (width / 2).max(1));
+            let resolution = Resolution::new(next_height, (next_width, next_height)).unwrap();
+            let mut next = Image::new(resolution);
+            for r in 0..next_height {
+                for c in 0..next_width {
+                    let (r0, r1) = ((2 * r).min(height - 1), (2 * r + 1).min(height - 1));
+                    let (c0, c1) = ((2 * c).min(width - 1), (2 * c + 1).min(width - 1));
+                    let sum = prev.get(r0, c0).unwrap()
+                        + prev.get(r0, c1).unwrap()
+                        + prev.get(r1, c0).unwrap()
+                        + prev.get(r1, c1).unwrap();
+                    next.set(r, c, sum * Val(0.25));
+                }
+            }
+            mipmaps.push(next);
+        }
+    }
+
+    fn level(&self, index: usize) -> &Image {
+        if index == 0 {
+            &self.image
+        } else {
+            &self.mipmaps[(index - 1).min(self.mipmaps.len() - 1)]
+        }
+    }
+
+    pub fn lookup_uv(&self, uv: UvCoordinate) -> Spectrum {
+        let uv = uv.wrap(self.wrap.0, self.wrap.1);
+        match self.filtering {
+            FilterMode::Nearest => Self::lookup_nearest(&self.image, uv),
+            FilterMode::Bilinear | FilterMode::Trilinear => Self::lookup_bilinear(&self.image, uv),
+        }
+    }
+
+    pub fn lookup_uv_with_footprint(&self, uv: UvCoordinate, footprint: Val) -> Spectrum {
+        if self.filtering != FilterMode::Trilinear || self.mipmaps.is_empty() {
+            return self.lookup_uv(uv);
+        }
 
-        let height = self.image.resolution().height() - 1;
-        let width = self.image.resolution().width() - 1;
+        let uv = uv.wrap(self.wrap.0, self.wrap.1);
+        let resolution = self.image.resolution();
+        let extent = Val::from(resolution.height().max(resolution.width()));
+        let texels = (footprint * extent).max(Val(1.0));
+        let lod = texels.log2().min(Val::from(self.mipmaps.len()));
+
+        let (lod_i, lod_f) = (usize::from(lod.trunc()), lod.fract());
+        let lower = Self::lookup_bilinear(self.level(lod_i), uv);
+        if lod_f == Val(0.0) {
+            return lower;
+        }
+        let upper = Self::lookup_bilinear(self.level(lod_i + 1), uv);
+        Spectrum::lerp(lower, upper, lod_f)
+    }
+
+    fn lookup_nearest(image: &Image, uv: UvCoordinate) -> Spectrum {
+        let height = image.resolution().height() - 1;
+        let width = image.resolution().width() - 1;
+
+        let r = usize::from(((Val(1.0) - uv.v()) * Val::from(height)).round());
+        let c = usize::from((uv.u() * Val::from(width)).round());
+        image.get(r.min(height), c.min(width)).unwrap()
+    }
+
+    fn lookup_bilinear(image: &Image, uv: UvCoordinate) -> Spectrum {
+        let height = image.resolution().height() - 1;
+        let width = image.resolution().width() - 1;
 
         let r = (Val(1.0) - uv.v()) * Val::from(height);
         let c = uv.u() * Val::from(width);
@@ -42,10 +181,10 @@ impl Texture for ImageMap {
         let (r0, r1) = (ri, (ri + 1).min(height));
         let (c0, c1) = (ci, (ci + 1).min(width));
 
-        let s00 = self.image.get(r0, c0).unwrap();
-        let s01 = self.image.get(r0, c1).unwrap();
-        let s10 = self.image.get(r1, c0).unwrap();
-        let s11 = self.image.get(r1, c1).unwrap();
+        let s00 = image.get(r0, c0).unwrap();
+        let s01 = image.get(r0, c1).unwrap();
+        let s10 = image.get(r1, c0).unwrap();
+        let s11 = image.get(r1, c1).unwrap();
 
         Spectrum::lerp(
             Spectrum::lerp(s00, s01, cf),
@@ -54,3 +193,90 @@ impl Texture for ImageMap {
         )
     }
 }
+
+impl Texture for ImageMap {
+    fn kind(&self) -> TextureKind {
+        TextureKind::ImageMap
+    }
+
+    fn uses_footprint(&self) -> bool {
+        self.filtering == FilterMode::Trilinear && !self.mipmaps.is_empty()
+    }
+
+    fn lookup(&self, intersection: &RayIntersection) -> Spectrum {
+        let uv = (intersection.uv()).expect("`ImageMap` expects a UV coordinate to be provided");
+        match intersection.uv_footprint() {
+            Some(footprint) => self.lookup_uv_with_footprint(uv, footprint),
+            None => self.lookup_uv(uv),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard(size: usize) -> Image {
+        let mut image = Image::new(Resolution::new(size, (1, 1)).unwrap());
+        for r in 0..size {
+            for c in 0..size {
+                let value = if (r + c) % 2 == 0 { Val(1.0) } else { Val(0.0) };
+                image.set(r, c, Spectrum::broadcast(value));
+            }
+        }
+        image
+    }
+
+    #[test]
+    fn image_map_new_succeeds_building_mipmaps() {
+        let map = ImageMap::new(checkerboard(8));
+        assert_eq!(map.mipmaps.len(), 3);
+        let top = map.level(3);
+        assert_eq!(top.resolution().width(), 1);
+        assert_eq!(top.get(0, 0).unwrap(), Spectrum::broadcast(Val(0.5)));
+    }
+
+    #[test]
+    fn image_map_lookup_uv_succeeds_decoding_srgb_texels() {
+        let mut image = Image::new(Resolution::new(2, (1, 1)).unwrap());
+        for r in 0..2 {
+            for c in 0..2 {
+                image.set(r, c, Spectrum::broadcast(Val(0.5)));
+            }
+        }
+        let image = Arc::new(image.with_color_space(ColorSpace::Srgb));
+        let uv = UvCoordinate::new(Val(0.3), Val(0.6)).unwrap();
+
+        let color = ImageMap::new(image.clone());
+        assert_eq!(color.color_space(), ColorSpace::Srgb);
+        assert!((color.lookup_uv(uv).red() - Val(0.214)).abs() < Val(1e-3));
+
+        let data = ImageMap::new(image).with_color_space(ColorSpace::Linear);
+        assert_eq!(data.lookup_uv(uv).red(), Val(0.5));
+    }
+
+    #[test]
+    fn image_map_lookup_uv_succeeds_applying_wrap_modes() {
+        let mut image = Image::new(Resolution::new(1, (5, 1)).unwrap());
+        for c in 0..5 {
+            image.set(0, c, Spectrum::broadcast(Val::from(c)));
+        }
+        let map = ImageMap::new(image).with_filtering(FilterMode::Nearest);
+        let column_at = |map: &ImageMap, u: Val| {
+            let uv = UvCoordinate::unbounded(u, Val(0.5));
+            map.lookup_uv(uv).red()
+        };
+
+        let repeat = map.clone().with_wrap(WrapMode::Repeat, WrapMode::Repeat);
+        assert_eq!(column_at(&repeat, Val(1.25)), Val(1.0));
+        assert_eq!(column_at(&repeat, Val(-0.25)), Val(3.0));
+
+        let clamp = map.clone().with_wrap(WrapMode::Clamp, WrapMode::Clamp);
+        assert_eq!(column_at(&clamp, Val(1.25)), Val(4.0));
+        assert_eq!(column_at(&clamp, Val(-0.25)), Val(0.0));
+
+        let mirror = map.with_wrap(WrapMode::Mirror, WrapMode::Mirror);
+        assert_eq!(column_at(&mirror, Val(1.25)), Val(3.0));
+        assert_eq!(column_at(&mirror, Val(-0.25)), Val(1.0));
+    }
+}
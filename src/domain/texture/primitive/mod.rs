@@ -1,13 +1,17 @@
 mod checkerboard;
 mod constant;
+mod gradient;
 mod image_map;
 mod noise;
+mod transformed_uv;
 mod vis_normal;
 mod vis_uv;
 
 pub use checkerboard::{Checkerboard, TryNewCheckerboardError};
 pub use constant::Constant;
-pub use image_map::ImageMap;
+pub use gradient::{Gradient, GradientAxis, TryNewGradientError};
+pub use image_map::{FilterMode, ImageMap};
 pub use noise::{Noise, TryNewNoiseError};
+pub use transformed_uv::TransformedUv;
 pub use vis_normal::VisibieNormal;
 pub use vis_uv::VisibleUvCoordinate;
@@ -48,6 +48,10 @@ impl Texture for Noise {
         TextureKind::Noise
     }
 
+    fn uses_footprint(&self) -> bool {
+        false
+    }
+
     fn lookup(&self, intersection: &RayIntersection) -> Spectrum {
         let position = (intersection.position().into_vector() * self.frequency).into();
         let value = self.generator.evaluate(position) * Val(0.5) + Val(0.5);
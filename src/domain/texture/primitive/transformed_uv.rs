@@ -0,0 +1,124 @@
+use crate::domain::color::core::Spectrum;
+use crate::domain::math::numeric::Val;
+use crate::domain::ray::event::RayIntersection;
+use crate::domain::texture::def::{DynTexture, Texture, TextureKind, UvCoordinate};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransformedUv {
+    texture: Box<DynTexture>,
+    scale: (Val, Val),
+    offset: (Val, Val),
+    rotation: Val,
+}
+
+impl TransformedUv {
+    pub fn new<T>(texture: T) -> Self
+    where
+        T: Into<DynTexture>,
+    {
+        Self {
+            texture: Box::new(texture.into()),
+            scale: (Val(1.0), Val(1.0)),
+            offset: (Val(0.0), Val(0.0)),
+            rotation: Val(0.0),
+        }
+    }
+
+    #[inline]
+    pub fn with_scale(self, scale_u: Val, scale_v: Val) -> Self {
+        let scale = (scale_u, scale_v);
+        Self { scale, ..self }
+    }
+
+    #[inline]
+    pub fn with_offset(self, offset_u: Val, offset_v: Val) -> Self {
+        let offset = (offset_u, offset_v);
+        Self { offset, ..self }
+    }
+
+    #[inline]
+    pub fn with_rotation(self, rotation: Val) -> Self {
+        Self { rotation, ..self }
+    }
+
+    pub fn transform_uv(&self, uv: UvCoordinate) -> UvCoordinate {
+        let (u, v) = (uv.u() * self.scale.0, uv.v() * self.scale.1);
+        let (sin, cos) = self.rotation.sin_cos();
+        let (u, v) = (cos * u - sin * v, sin * u + cos * v);
+        UvCoordinate::unbounded(u + self.offset.0, v + self.offset.1)
+    }
+}
+
+impl Texture for TransformedUv {
+    fn kind(&self) -> TextureKind {
+        TextureKind::TransformedUv
+    }
+
+    fn uses_footprint(&self) -> bool {
+        self.texture.uses_footprint()
+    }
+
+    fn lookup(&self, intersection: &RayIntersection) -> Spectrum {
+        match intersection.uv() {
+            Some(uv) => {
+                let intersection = intersection.clone().with_uv(self.transform_uv(uv));
+                self.texture.lookup(&intersection)
+            }
+            None => self.texture.lookup(intersection),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::camera::Resolution;
+    use crate::domain::image::core::Image;
+    use crate::domain::math::geometry::{Distance, Normal, Point};
+    use crate::domain::ray::event::SurfaceSide;
+    use crate::domain::texture::def::WrapMode;
+    use crate::domain::texture::primitive::{FilterMode, ImageMap};
+
+    use super::*;
+
+    fn count_transitions<T: Texture>(texture: &T) -> usize {
+        let values = (0..200)
+            .map(|i| {
+                let uv = UvCoordinate::new((Val::from(i) + Val(0.5)) / Val(200.0), Val(0.5));
+                let intersection = RayIntersection::new(
+                    Distance::new(Val(1.0)).unwrap(),
+                    Point::default(),
+                    Normal::z_direction(),
+                    SurfaceSide::Front,
+                )
+                .with_uv(uv.unwrap());
+                texture.lookup(&intersection).red()
+            })
+            .collect::<Vec<_>>();
+        values.windows(2).filter(|w| w[0] != w[1]).count()
+    }
+
+    #[test]
+    fn transformed_uv_lookup_succeeds_doubling_checkerboard_frequency() {
+        let mut image = Image::new(Resolution::new(1, (4, 1)).unwrap());
+        for c in 0..4 {
+            let value = if c % 2 == 0 { Val(1.0) } else { Val(0.0) };
+            image.set(0, c, Spectrum::broadcast(value));
+        }
+        let checkerboard = ImageMap::new(image)
+            .with_filtering(FilterMode::Nearest)
+            .with_wrap(WrapMode::Repeat, WrapMode::Repeat);
+
+        let original = count_transitions(&checkerboard);
+        let scaled = TransformedUv::new(checkerboard).with_scale(Val(2.0), Val(2.0));
+        assert_eq!(count_transitions(&scaled), 2 * original + 1);
+    }
+
+    #[test]
+    fn transformed_uv_transform_uv_succeeds() {
+        let texture = TransformedUv::new(Spectrum::zero())
+            .with_rotation(Val::PI / Val(2.0))
+            .with_offset(Val(1.0), Val(0.0));
+        let uv = texture.transform_uv(UvCoordinate::new(Val(0.5), Val(0.0)).unwrap());
+        assert_eq!(uv, UvCoordinate::unbounded(Val(1.0), Val(0.5)));
+    }
+}
@@ -20,6 +20,10 @@ impl Texture for VisibieNormal {
         TextureKind::VisibleNormal
     }
 
+    fn uses_footprint(&self) -> bool {
+        false
+    }
+
     #[inline]
     fn lookup(&self, intersection: &RayIntersection) -> Spectrum {
         let val = intersection.normal() * Val(0.5) + Vector::broadcast(Val(0.5));
@@ -19,6 +19,10 @@ impl Texture for VisibleUvCoordinate {
         TextureKind::VisibleUvCoordinate
     }
 
+    fn uses_footprint(&self) -> bool {
+        false
+    }
+
     #[inline]
     fn lookup(&self, intersection: &RayIntersection) -> Spectrum {
         let uv = (intersection.uv())
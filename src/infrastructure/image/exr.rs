@@ -0,0 +1,485 @@
+use std::fs::File;
+use std::io::{BufWriter, ErrorKind as IoErrorKind, Read, Write};
+use std::path::PathBuf;
+
+use snafu::prelude::*;
+
+use crate::domain::camera::Resolution;
+use crate::domain::color::core::Spectrum;
+use crate::domain::image::core::Image;
+use crate::domain::image::external::*;
+use crate::domain::math::numeric::Val;
+
+/// Only uncompressed files are supported.
+#[derive(Debug, Clone)]
+pub struct ExrImageResource {
+    path: PathBuf,
+}
+
+impl ExrImageResource {
+    const MAGIC: [u8; 4] = [0x76, 0x2f, 0x31, 0x01];
+
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self { path: path.into() }
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Image, LoadImageError> {
+        let mut reader = ExrReader { bytes, offset: 0 };
+        ensure_whatever!(
+            reader.take(4)? == Self::MAGIC,
+            "data is not in the OpenEXR format"
+        );
+        let version = reader.read_u32()?;
+        ensure_whatever!(
+            version & 0xff == 2 && version & !0xff & !0x400 == 0,
+            "only single-part scanline OpenEXR images are supported"
+        );
+
+        let header = ExrHeader::decode(&mut reader)?;
+        let (width, height) = (header.width, header.height);
+        let pixel_size = (header.channels.iter())
+            .map(|channel| channel.pixel_type.size())
+            .sum::<usize>();
+        let size = (width.checked_mul(pixel_size))
+            .and_then(|line| line.checked_add(16))
+            .and_then(|line| line.checked_mul(height));
+        ensure_whatever!(
+            size.is_some_and(|size| size <= bytes.len().saturating_sub(reader.offset)),
+            "OpenEXR data is truncated"
+        );
+        let Ok(resolution) = Resolution::from_dimensions(width, height) else {
+            whatever!("OpenEXR data window is empty");
+        };
+        let mut image = Image::new(resolution);
+
+        let offsets = (0..height)
+            .map(|_| reader.read_u64())
+            .collect::<Result<Vec<_>, _>>()?;
+        for offset in offsets {
+            reader.offset = usize::try_from(offset).unwrap_or(usize::MAX);
+            let y = i64::from(reader.read_i32()?);
+            let _size = reader.read_u32()?;
+            let row = usize::try_from(y - i64::from(header.data_window[1])).ok();
+            let Some(row) = row.filter(|&row| row < height) else {
+                whatever!("OpenEXR scanline {} is out of the data window", y);
+            };
+
+            let mut pixels = vec![[Val(0.0); 3]; width];
+            for channel in &header.channels {
+                for pixel in &mut pixels {
+                    let value = channel.pixel_type.read(&mut reader)?;
+                    if let Some(index) = channel.target {
+                        pixel[index] = value;
+                    }
+                }
+            }
+            for (column, [r, g, b]) in pixels.into_iter().enumerate() {
+                let spectrum = if header.is_grayscale {
+                    Spectrum::broadcast(r)
+                } else {
+                    Spectrum::new(r, g, b)
+                };
+                image.set(row, column, spectrum);
+            }
+        }
+        Ok(image)
+    }
+
+    pub fn encode<W>(image: &Image, writer: W) -> Result<(), SaveImageError>
+    where
+        W: Write,
+    {
+        whatever!(
+            Self::write_exr(image, writer),
+            "could not write OpenEXR data to the buffer",
+        );
+        Ok(())
+    }
+
+    fn write_exr<W>(image: &Image, mut writer: W) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        let width = image.resolution().width();
+        let height = image.resolution().height();
+        let (xmax, ymax) = (width as i32 - 1, height as i32 - 1);
+
+        let mut header = Vec::new();
+        let mut attribute = |name: &str, kind: &str, value: &[u8]| {
+            header.extend(name.as_bytes());
+            header.push(0);
+            header.extend(kind.as_bytes());
+            header.push(0);
+            header.extend((value.len() as u32).to_le_bytes());
+            header.extend(value);
+        };
+
+        let mut channels = Vec::new();
+        for name in ["B", "G", "R"] {
+            channels.extend(name.as_bytes());
+            channels.push(0);
+            channels.extend(2i32.to_le_bytes());
+            channels.extend([0, 0, 0, 0]);
+            channels.extend(1i32.to_le_bytes());
+            channels.extend(1i32.to_le_bytes());
+        }
+        channels.push(0);
+        let window = [0, 0, xmax, ymax]
+            .into_iter()
+            .flat_map(i32::to_le_bytes)
+            .collect::<Vec<_>>();
+        attribute("channels", "chlist", &channels);
+        attribute("compression", "compression", &[0]);
+        attribute("dataWindow", "box2i", &window);
+        attribute("displayWindow", "box2i", &window);
+        attribute("lineOrder", "lineOrder", &[0]);
+        attribute("pixelAspectRatio", "float", &1.0f32.to_le_bytes());
+        attribute("screenWindowCenter", "v2f", &[0; 8]);
+        attribute("screenWindowWidth", "float", &1.0f32.to_le_bytes());
+        header.push(0);
+
+        writer.write_all(&Self::MAGIC)?;
+        writer.write_all(&2u32.to_le_bytes())?;
+        writer.write_all(&header)?;
+
+        let block_size = 8 + width * 3 * 4;
+        let table_end = 8 + header.len() + height * 8;
+        for row in 0..height {
+            writer.write_all(&((table_end + row * block_size) as u64).to_le_bytes())?;
+        }
+        for row in 0..height {
+            writer.write_all(&(row as i32).to_le_bytes())?;
+            writer.write_all(&((width * 3 * 4) as u32).to_le_bytes())?;
+            let pixels = (0..width)
+                .map(|column| image.get(row, column).unwrap())
+                .collect::<Vec<_>>();
+            for channel in [Spectrum::blue, Spectrum::green, Spectrum::red] {
+                for pixel in &pixels {
+                    writer.write_all(&(channel(pixel).0 as f32).to_le_bytes())?;
+                }
+            }
+        }
+        writer.flush()
+    }
+}
+
+impl ImageResource for ExrImageResource {
+    fn load(&self) -> Result<Image, LoadImageError> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == IoErrorKind::NotFound => {
+                return NotFoundLoadSnafu {
+                    path: self.path.clone(),
+                }
+                .fail();
+            }
+            Err(err) => {
+                return Err(err).context(IoLoadSnafu {
+                    path: self.path.clone(),
+                });
+            }
+        };
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).context(IoLoadSnafu {
+            path: self.path.clone(),
+        })?;
+        Self::decode(&buffer)
+    }
+
+    fn save(&self, image: &Image) -> Result<(), SaveImageError> {
+        let file = File::create(&self.path).context(IoSaveSnafu {
+            path: self.path.clone(),
+        })?;
+        Self::write_exr(image, BufWriter::new(file)).context(IoSaveSnafu {
+            path: self.path.clone(),
+        })
+    }
+}
+
+struct ExrReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ExrReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], LoadImageError> {
+        let end = self.offset.saturating_add(len);
+        ensure_whatever!(end <= self.bytes.len(), "OpenEXR data is truncated");
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], LoadImageError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn read_u32(&mut self) -> Result<u32, LoadImageError> {
+        self.take_array().map(u32::from_le_bytes)
+    }
+
+    fn read_i32(&mut self) -> Result<i32, LoadImageError> {
+        self.take_array().map(i32::from_le_bytes)
+    }
+
+    fn read_u64(&mut self) -> Result<u64, LoadImageError> {
+        self.take_array().map(u64::from_le_bytes)
+    }
+
+    fn read_string(&mut self) -> Result<String, LoadImageError> {
+        let rest = &self.bytes[self.offset.min(self.bytes.len())..];
+        let Some(len) = rest.iter().position(|&b| b == 0) else {
+            whatever!("OpenEXR string is not terminated");
+        };
+        let string = String::from_utf8_lossy(&rest[..len]).into_owned();
+        self.offset += len + 1;
+        Ok(string)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExrPixelType {
+    Uint,
+    Half,
+    Float,
+}
+
+impl ExrPixelType {
+    fn size(&self) -> usize {
+        match self {
+            Self::Uint | Self::Float => 4,
+            Self::Half => 2,
+        }
+    }
+
+    fn read(&self, reader: &mut ExrReader) -> Result<Val, LoadImageError> {
+        match self {
+            Self::Uint => reader.read_u32().map(|v| Val(f64::from(v))),
+            Self::Half => reader
+                .take_array()
+                .map(|b| Val(half_to_f64(u16::from_le_bytes(b)))),
+            Self::Float => reader
+                .take_array()
+                .map(|b| Val(f64::from(f32::from_le_bytes(b)))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ExrChannel {
+    pixel_type: ExrPixelType,
+    target: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct ExrHeader {
+    channels: Vec<ExrChannel>,
+    data_window: [i32; 4],
+    width: usize,
+    height: usize,
+    is_grayscale: bool,
+}
+
+impl ExrHeader {
+    fn decode(reader: &mut ExrReader) -> Result<Self, LoadImageError> {
+        let (mut channels, mut data_window, mut compression) = (None, None, None);
+        loop {
+            let name = reader.read_string()?;
+            if name.is_empty() {
+                break;
+            }
+            let _kind = reader.read_string()?;
+            let size = reader.read_u32()? as usize;
+            let mut value = ExrReader {
+                bytes: reader.take(size)?,
+                offset: 0,
+            };
+            match name.as_str() {
+                "channels" => channels = Some(Self::decode_channels(&mut value)?),
+                "compression" => compression = Some(value.take(1)?[0]),
+                "dataWindow" => {
+                    let mut window = [0; 4];
+                    for v in &mut window {
+                        *v = value.read_i32()?;
+                    }
+                    data_window = Some(window);
+                }
+                _ => {}
+            }
+        }
+
+        ensure_whatever!(
+            compression == Some(0),
+            "compressed OpenEXR images are unsupported"
+        );
+        let (Some(channels), Some(data_window)) = (channels, data_window) else {
+            whatever!("OpenEXR header misses channels or data window");
+        };
+        let [xmin, ymin, xmax, ymax] = data_window;
+        ensure_whatever!(xmin <= xmax && ymin <= ymax, "OpenEXR data window is empty");
+        ensure_whatever!(!channels.is_empty(), "OpenEXR image has no channels");
+        let extent = |min: i32, max: i32| usize::try_from(i64::from(max) - i64::from(min) + 1);
+        let (Ok(width), Ok(height)) = (extent(xmin, xmax), extent(ymin, ymax)) else {
+            whatever!("OpenEXR data window is too large");
+        };
+
+        let is_grayscale = channels.iter().all(|(name, _)| name != "R")
+            && channels.iter().any(|(name, _)| name == "Y");
+        let channels = (channels.into_iter())
+            .map(|(name, pixel_type)| {
+                let target = match (name.as_str(), is_grayscale) {
+                    ("Y", true) | ("R", false) => Some(0),
+                    ("G", false) => Some(1),
+                    ("B", false) => Some(2),
+                    _ => None,
+                };
+                ExrChannel { pixel_type, target }
+            })
+            .collect();
+        Ok(Self {
+            channels,
+            data_window,
+            width,
+            height,
+            is_grayscale,
+        })
+    }
+
+    fn decode_channels(
+        reader: &mut ExrReader,
+    ) -> Result<Vec<(String, ExrPixelType)>, LoadImageError> {
+        let mut channels = Vec::new();
+        loop {
+            let name = reader.read_string()?;
+            if name.is_empty() {
+                break;
+            }
+            let pixel_type = match reader.read_i32()? {
+                0 => ExrPixelType::Uint,
+                1 => ExrPixelType::Half,
+                2 => ExrPixelType::Float,
+                other => whatever!("OpenEXR pixel type {} is unknown", other),
+            };
+            let _linear_and_reserved = reader.take(4)?;
+            let (x_sampling, y_sampling) = (reader.read_i32()?, reader.read_i32()?);
+            ensure_whatever!(
+                x_sampling == 1 && y_sampling == 1,
+                "subsampled OpenEXR channels are unsupported"
+            );
+            channels.push((name, pixel_type));
+        }
+        Ok(channels)
+    }
+}
+
+fn half_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f64::from(bits & 0x3ff);
+    match exponent {
+        0 => sign * mantissa * 2.0f64.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f64::INFINITY,
+        0x1f => f64::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2.0f64.powi(exponent - 15),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::texture::def::UvCoordinate;
+    use crate::domain::texture::primitive::{FilterMode, ImageMap};
+
+    use super::*;
+
+    fn half_exr() -> Vec<u8> {
+        half_exr_with_window([0, 0, 1, 0])
+    }
+
+    fn half_exr_with_window(window: [i32; 4]) -> Vec<u8> {
+        let mut bytes = ExrImageResource::MAGIC.to_vec();
+        bytes.extend(2u32.to_le_bytes());
+        let mut attribute = |name: &str, kind: &str, value: &[u8]| {
+            bytes.extend(name.as_bytes());
+            bytes.push(0);
+            bytes.extend(kind.as_bytes());
+            bytes.push(0);
+            bytes.extend((value.len() as u32).to_le_bytes());
+            bytes.extend(value);
+        };
+        let mut channels = Vec::new();
+        for name in [b"B", b"G", b"R"] {
+            channels.extend(name);
+            channels.push(0);
+            channels.extend(1i32.to_le_bytes());
+            channels.extend([0; 4]);
+            channels.extend(1i32.to_le_bytes());
+            channels.extend(1i32.to_le_bytes());
+        }
+        channels.push(0);
+        let window = window.into_iter().flat_map(i32::to_le_bytes);
+        let window = window.collect::<Vec<_>>();
+        attribute("channels", "chlist", &channels);
+        attribute("compression", "compression", &[0]);
+        attribute("dataWindow", "box2i", &window);
+        attribute("displayWindow", "box2i", &window);
+        bytes.push(0);
+
+        let block_offset = bytes.len() as u64 + 8;
+        bytes.extend(block_offset.to_le_bytes());
+        bytes.extend(0i32.to_le_bytes());
+        bytes.extend(12u32.to_le_bytes());
+        for value in [0x0000u16, 0x3800, 0x0000, 0x4100, 0x0000, 0x4400] {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn exr_image_resource_decode_succeeds_preserving_super_white() {
+        let image = ExrImageResource::decode(&half_exr()).unwrap();
+        assert_eq!(
+            image.get(0, 1).unwrap(),
+            Spectrum::new(Val(4.0), Val(2.5), Val(0.5))
+        );
+        assert_eq!(image.get(0, 0).unwrap(), Spectrum::zero());
+
+        let map = ImageMap::new(image).with_filtering(FilterMode::Nearest);
+        let color = map.lookup_uv(UvCoordinate::new(Val(1.0), Val(0.5)).unwrap());
+        assert_eq!(color.red(), Val(4.0));
+    }
+
+    #[test]
+    fn exr_image_resource_decode_succeeds_reading_encoded_image() {
+        let mut image = Image::new(Resolution::from_dimensions(3, 2).unwrap());
+        image.set(0, 2, Spectrum::new(Val(16.0), Val(0.25), Val(1.5)));
+        image.set(1, 0, Spectrum::broadcast(Val(0.125)));
+
+        let mut buffer = Vec::new();
+        ExrImageResource::encode(&image, &mut buffer).unwrap();
+        let decoded = ExrImageResource::decode(&buffer).unwrap();
+        assert_eq!(decoded.resolution(), image.resolution());
+        assert_eq!(decoded.get(0, 2), image.get(0, 2));
+        assert_eq!(decoded.get(1, 0), image.get(1, 0));
+    }
+
+    #[test]
+    fn exr_image_resource_decode_fails_when_data_window_exceeds_data() {
+        for window in [
+            [0, 0, 1 << 20, 1 << 20],
+            [i32::MIN, 0, i32::MAX, 0],
+            [0, i32::MIN, 1, i32::MAX],
+        ] {
+            assert!(ExrImageResource::decode(&half_exr_with_window(window)).is_err());
+        }
+    }
+
+    #[test]
+    fn exr_image_resource_decode_fails_when_magic_is_wrong() {
+        let mut bytes = half_exr();
+        bytes[0] = 0;
+        assert!(ExrImageResource::decode(&bytes).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+use std::fs::File;
+use std::io::{BufWriter, ErrorKind as IoErrorKind, Read, Write};
+use std::path::PathBuf;
+
+use snafu::prelude::*;
+
+use crate::domain::camera::Resolution;
+use crate::domain::color::core::Spectrum;
+use crate::domain::image::core::Image;
+use crate::domain::image::external::*;
+use crate::domain::math::numeric::Val;
+
+#[derive(Debug, Clone)]
+pub struct HdrImageResource {
+    path: PathBuf,
+}
+
+impl HdrImageResource {
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self { path: path.into() }
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Image, LoadImageError> {
+        let (mut cursor, width, height) = Self::decode_header(bytes)?;
+        let flat_size = width.checked_mul(4);
+        let min_line_size = if (8..0x8000).contains(&width) {
+            Some(4 + 8 * width.div_ceil(127))
+        } else {
+            flat_size
+        };
+        ensure_whatever!(
+            (min_line_size.and_then(|min| min.checked_mul(height)))
+                .is_some_and(|min| min <= cursor.len()),
+            "HDR pixel data is truncated"
+        );
+        let Ok(resolution) = Resolution::from_dimensions(width, height) else {
+            whatever!("HDR image is empty");
+        };
+        let mut image = Image::new(resolution);
+
+        let mut scanline = vec![[0u8; 4]; width];
+        for row in 0..height {
+            cursor = Self::decode_scanline(cursor, &mut scanline)?;
+            for (column, rgbe) in scanline.iter().enumerate() {
+                image.set(row, column, Self::rgbe_to_spectrum(*rgbe));
+            }
+        }
+        Ok(image)
+    }
+
+    pub fn encode<W>(image: &Image, writer: W) -> Result<(), SaveImageError>
+    where
+        W: Write,
+    {
+        whatever!(
+            Self::write_hdr(image, writer),
+            "could not write HDR data to the buffer",
+        );
+        Ok(())
+    }
+
+    fn decode_header(bytes: &[u8]) -> Result<(&[u8], usize, usize), LoadImageError> {
+        let mut lines = bytes.split(|&b| b == b'\n');
+        let mut consumed = 0;
+        let mut next_line = || {
+            let line = lines.next()?;
+            consumed += line.len() + 1;
+            Some(String::from_utf8_lossy(line).trim().to_string())
+        };
+
+        let magic = next_line().unwrap_or_default();
+        ensure_whatever!(
+            magic.starts_with("#?"),
+            "data is not in the Radiance HDR format"
+        );
+        loop {
+            let Some(line) = next_line() else {
+                whatever!("HDR header is not terminated");
+            };
+            if line.is_empty() {
+                break;
+            }
+            if let Some(format) = line.strip_prefix("FORMAT=") {
+                ensure_whatever!(
+                    format == "32-bit_rle_rgbe",
+                    "HDR pixel format `{}` is unsupported",
+                    format
+                );
+            }
+        }
+
+        let Some(dims) = next_line() else {
+            whatever!("HDR resolution is missing");
+        };
+        let tokens = dims.split_whitespace().collect::<Vec<_>>();
+        let (height, width) = match tokens.as_slice() {
+            ["-Y", height, "+X", width] => (height.parse().ok(), width.parse().ok()),
+            _ => whatever!("HDR orientation `{}` is unsupported", dims),
+        };
+        let (Some(height), Some(width)) = (height, width) else {
+            whatever!("HDR resolution `{}` is invalid", dims);
+        };
+        ensure_whatever!(width > 0 && height > 0, "HDR image is empty");
+
+        Ok((&bytes[consumed.min(bytes.len())..], width, height))
+    }
+
+    fn decode_scanline<'a>(
+        cursor: &'a [u8],
+        scanline: &mut [[u8; 4]],
+    ) -> Result<&'a [u8], LoadImageError> {
+        let width = scanline.len();
+        let is_rle = (8..0x8000).contains(&width)
+            && cursor.len() >= 4
+            && cursor[0] == 2
+            && cursor[1] == 2
+            && (usize::from(cursor[2]) << 8 | usize::from(cursor[3])) == width;
+        if !is_rle {
+            ensure_whatever!(
+                width
+                    .checked_mul(4)
+                    .is_some_and(|size| cursor.len() >= size),
+                "HDR pixel data is truncated"
+            );
+            for (pixel, rgbe) in scanline.iter_mut().zip(cursor.chunks_exact(4)) {
+                pixel.copy_from_slice(rgbe);
+            }
+            return Ok(&cursor[width * 4..]);
+        }
+
+        let mut cursor = &cursor[4..];
+        for channel in 0..4 {
+            let mut column = 0;
+            while column < width {
+                ensure_whatever!(!cursor.is_empty(), "HDR pixel data is truncated");
+                let count = usize::from(cursor[0]);
+                if count > 128 {
+                    let count = count - 128;
+                    ensure_whatever!(
+                        cursor.len() >= 2 && column + count <= width,
+                        "HDR run length is invalid"
+                    );
+                    for pixel in &mut scanline[column..column + count] {
+                        pixel[channel] = cursor[1];
+                    }
+                    (cursor, column) = (&cursor[2..], column + count);
+                } else {
+                    ensure_whatever!(
+                        count > 0 && cursor.len() > count && column + count <= width,
+                        "HDR run length is invalid"
+                    );
+                    for (pixel, value) in scanline[column..column + count]
+                        .iter_mut()
+                        .zip(&cursor[1..=count])
+                    {
+                        pixel[channel] = *value;
+                    }
+                    (cursor, column) = (&cursor[count + 1..], column + count);
+                }
+            }
+        }
+        Ok(cursor)
+    }
+
+    fn rgbe_to_spectrum([r, g, b, e]: [u8; 4]) -> Spectrum {
+        if e == 0 {
+            return Spectrum::zero();
+        }
+        let scale = Val(2.0).powi(i32::from(e) - 136);
+        Spectrum::new(
+            Val::from(usize::from(r)) * scale,
+            Val::from(usize::from(g)) * scale,
+            Val::from(usize::from(b)) * scale,
+        )
+    }
+
+    fn spectrum_to_rgbe(spectrum: Spectrum) -> [u8; 4] {
+        let max = spectrum.red().max(spectrum.green()).max(spectrum.blue());
+        if max.0 < 1e-32 {
+            return [0; 4];
+        }
+        let exponent = max.0.log2().floor() as i32 + 1;
+        let scale = 256.0 / 2.0f64.powi(exponent);
+        let quantize = |v: Val| (v.0 * scale).clamp(0.0, 255.0) as u8;
+        [
+            quantize(spectrum.red()),
+            quantize(spectrum.green()),
+            quantize(spectrum.blue()),
+            (exponent + 128).clamp(0, 255) as u8,
+        ]
+    }
+
+    fn write_hdr<W>(image: &Image, mut writer: W) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        let width = image.resolution().width();
+        let height = image.resolution().height();
+
+        writeln!(writer, "#?RADIANCE")?;
+        writeln!(writer, "FORMAT=32-bit_rle_rgbe")?;
+        writeln!(writer)?;
+        writeln!(writer, "-Y {height} +X {width}")?;
+        for row in 0..height {
+            for column in 0..width {
+                let rgbe = Self::spectrum_to_rgbe(image.get(row, column).unwrap());
+                writer.write_all(&rgbe)?;
+            }
+        }
+        writer.flush()
+    }
+}
+
+impl ImageResource for HdrImageResource {
+    fn load(&self) -> Result<Image, LoadImageError> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == IoErrorKind::NotFound => {
+                return NotFoundLoadSnafu {
+                    path: self.path.clone(),
+                }
+                .fail();
+            }
+            Err(err) => {
+                return Err(err).context(IoLoadSnafu {
+                    path: self.path.clone(),
+                });
+            }
+        };
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).context(IoLoadSnafu {
+            path: self.path.clone(),
+        })?;
+        Self::decode(&buffer)
+    }
+
+    fn save(&self, image: &Image) -> Result<(), SaveImageError> {
+        let file = File::create(&self.path).context(IoSaveSnafu {
+            path: self.path.clone(),
+        })?;
+        Self::write_hdr(image, BufWriter::new(file)).context(IoSaveSnafu {
+            path: self.path.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hdr_image_resource_decode_succeeds_reading_encoded_image() {
+        let mut image = Image::new(Resolution::from_dimensions(3, 2).unwrap());
+        image.set(0, 1, Spectrum::new(Val(12.0), Val(0.5), Val(0.0)));
+        image.set(1, 2, Spectrum::broadcast(Val(0.25)));
+
+        let mut buffer = Vec::new();
+        HdrImageResource::encode(&image, &mut buffer).unwrap();
+        let decoded = HdrImageResource::decode(&buffer).unwrap();
+
+        assert_eq!(decoded.resolution(), image.resolution());
+        assert_eq!(decoded.get(0, 1).unwrap().red(), Val(12.0));
+        assert_eq!(decoded.get(0, 1).unwrap().green(), Val(0.5));
+        assert_eq!(decoded.get(1, 2).unwrap(), Spectrum::broadcast(Val(0.25)));
+        assert_eq!(decoded.get(1, 0).unwrap(), Spectrum::zero());
+    }
+
+    #[test]
+    fn hdr_image_resource_decode_fails_when_resolution_exceeds_data() {
+        for dims in ["-Y 100000 +X 100000", "-Y 2 +X 18446744073709551615"] {
+            let mut bytes = format!("#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n{dims}\n").into_bytes();
+            bytes.extend([0; 16]);
+            assert!(HdrImageResource::decode(&bytes).is_err(), "{dims}");
+        }
+    }
+
+    #[test]
+    fn hdr_image_resource_decode_succeeds_expanding_run_length_encoding() {
+        let mut bytes = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 1 +X 8\n".to_vec();
+        bytes.extend([2, 2, 0, 8]);
+        for value in [128, 64, 32] {
+            bytes.extend([128 + 8, value]);
+        }
+        bytes.extend([4, 130, 130, 131, 131, 128 + 4, 129]);
+
+        let image = HdrImageResource::decode(&bytes).unwrap();
+        let expected = |e: i32| Spectrum::new(Val(128.0), Val(64.0), Val(32.0)) * Val(2.0).powi(e);
+        assert_eq!(image.get(0, 0).unwrap(), expected(130 - 136));
+        assert_eq!(image.get(0, 3).unwrap(), expected(131 - 136));
+        assert_eq!(image.get(0, 7).unwrap(), expected(129 - 136));
+    }
+}
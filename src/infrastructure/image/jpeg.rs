@@ -0,0 +1,1086 @@
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+use std::fs::File;
+use std::io::{BufWriter, ErrorKind as IoErrorKind, Read, Write};
+use std::path::PathBuf;
+
+use snafu::prelude::*;
+
+use crate::domain::camera::Resolution;
+use crate::domain::color::core::ColorSpace;
+use crate::domain::color::external::SRgbColor;
+use crate::domain::image::core::Image;
+use crate::domain::image::external::*;
+
+/// Saving refuses values above 1, so images must be tone mapped first.
+#[derive(Debug, Clone)]
+pub struct JpegImageResource {
+    path: PathBuf,
+    quality: u8,
+}
+
+impl JpegImageResource {
+    const DEFAULT_QUALITY: u8 = 90;
+
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            path: path.into(),
+            quality: Self::DEFAULT_QUALITY,
+        }
+    }
+
+    pub fn with_quality(self, quality: u8) -> Self {
+        let quality = quality.clamp(1, 100);
+        Self { quality, ..self }
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Image, LoadImageError> {
+        JpegDecoder::new(bytes).decode()
+    }
+
+    pub fn encode<W>(image: &Image, quality: u8, writer: W) -> Result<(), SaveImageError>
+    where
+        W: Write,
+    {
+        Self::ensure_encodable(image)?;
+        whatever!(
+            JpegEncoder::new(quality).write(image, writer),
+            "could not write JPEG data to the buffer",
+        );
+        Ok(())
+    }
+
+    fn ensure_encodable(image: &Image) -> Result<(), SaveImageError> {
+        let (height, width) = (image.resolution().height(), image.resolution().width());
+        ensure_whatever!(
+            height <= 0xFFFF && width <= 0xFFFF,
+            "image is too large to be saved as JPEG"
+        );
+        for row in 0..height {
+            for column in 0..width {
+                let color = image.get_linear(row, column).unwrap();
+                let max = color.red().max(color.green()).max(color.blue());
+                ensure_whatever!(
+                    max.0 <= 1.0 + 1e-6,
+                    "image has values above 1 and must be tone mapped before saving as JPEG"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ImageResource for JpegImageResource {
+    fn load(&self) -> Result<Image, LoadImageError> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == IoErrorKind::NotFound => {
+                return NotFoundLoadSnafu {
+                    path: self.path.clone(),
+                }
+                .fail();
+            }
+            Err(err) => {
+                return Err(err).context(IoLoadSnafu {
+                    path: self.path.clone(),
+                });
+            }
+        };
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).context(IoLoadSnafu {
+            path: self.path.clone(),
+        })?;
+        Self::decode(&buffer)
+    }
+
+    fn save(&self, image: &Image) -> Result<(), SaveImageError> {
+        Self::ensure_encodable(image)?;
+        let file = File::create(&self.path).context(IoSaveSnafu {
+            path: self.path.clone(),
+        })?;
+        let encoder = JpegEncoder::new(self.quality);
+        encoder
+            .write(image, BufWriter::new(file))
+            .context(IoSaveSnafu {
+                path: self.path.clone(),
+            })
+    }
+}
+
+const ZIGZAG: [usize; 64] = [
+    0, 1, 8, 16, 9, 2, 3, 10, 17, 24, 32, 25, 18, 11, 4, 5, 12, 19, 26, 33, 40, 48, 41, 34, 27, 20,
+    13, 6, 7, 14, 21, 28, 35, 42, 49, 56, 57, 50, 43, 36, 29, 22, 15, 23, 30, 37, 44, 51, 58, 59,
+    52, 45, 38, 31, 39, 46, 53, 60, 61, 54, 47, 55, 62, 63,
+];
+
+const LUMINANCE_QUANTIZATION: [u16; 64] = [
+    16, 11, 10, 16, 24, 40, 51, 61, 12, 12, 14, 19, 26, 58, 60, 55, 14, 13, 16, 24, 40, 57, 69, 56,
+    14, 17, 22, 29, 51, 87, 80, 62, 18, 22, 37, 56, 68, 109, 103, 77, 24, 35, 55, 64, 81, 104, 113,
+    92, 49, 64, 78, 87, 103, 121, 120, 101, 72, 92, 95, 98, 112, 100, 103, 99,
+];
+
+const CHROMINANCE_QUANTIZATION: [u16; 64] = [
+    17, 18, 24, 47, 99, 99, 99, 99, 18, 21, 26, 66, 99, 99, 99, 99, 24, 26, 56, 99, 99, 99, 99, 99,
+    47, 66, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99,
+    99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99,
+];
+
+const DC_LUMINANCE_BITS: [u8; 16] = [0, 1, 5, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0];
+const DC_CHROMINANCE_BITS: [u8; 16] = [0, 3, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0];
+const DC_VALUES: [u8; 12] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+
+const AC_LUMINANCE_BITS: [u8; 16] = [0, 2, 1, 3, 3, 2, 4, 3, 5, 5, 4, 4, 0, 0, 1, 0x7d];
+const AC_LUMINANCE_VALUES: [u8; 162] = [
+    0x01, 0x02, 0x03, 0x00, 0x04, 0x11, 0x05, 0x12, 0x21, 0x31, 0x41, 0x06, 0x13, 0x51, 0x61, 0x07,
+    0x22, 0x71, 0x14, 0x32, 0x81, 0x91, 0xa1, 0x08, 0x23, 0x42, 0xb1, 0xc1, 0x15, 0x52, 0xd1, 0xf0,
+    0x24, 0x33, 0x62, 0x72, 0x82, 0x09, 0x0a, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x25, 0x26, 0x27, 0x28,
+    0x29, 0x2a, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3a, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49,
+    0x4a, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5a, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69,
+    0x6a, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89,
+    0x8a, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7,
+    0xa8, 0xa9, 0xaa, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7, 0xb8, 0xb9, 0xba, 0xc2, 0xc3, 0xc4, 0xc5,
+    0xc6, 0xc7, 0xc8, 0xc9, 0xca, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6, 0xd7, 0xd8, 0xd9, 0xda, 0xe1, 0xe2,
+    0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, 0xea, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8,
+    0xf9, 0xfa,
+];
+
+const AC_CHROMINANCE_BITS: [u8; 16] = [0, 2, 1, 2, 4, 4, 3, 4, 7, 5, 4, 4, 0, 1, 2, 0x77];
+const AC_CHROMINANCE_VALUES: [u8; 162] = [
+    0x00, 0x01, 0x02, 0x03, 0x11, 0x04, 0x05, 0x21, 0x31, 0x06, 0x12, 0x41, 0x51, 0x07, 0x61, 0x71,
+    0x13, 0x22, 0x32, 0x81, 0x08, 0x14, 0x42, 0x91, 0xa1, 0xb1, 0xc1, 0x09, 0x23, 0x33, 0x52, 0xf0,
+    0x15, 0x62, 0x72, 0xd1, 0x0a, 0x16, 0x24, 0x34, 0xe1, 0x25, 0xf1, 0x17, 0x18, 0x19, 0x1a, 0x26,
+    0x27, 0x28, 0x29, 0x2a, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3a, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48,
+    0x49, 0x4a, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5a, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68,
+    0x69, 0x6a, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+    0x88, 0x89, 0x8a, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0xa2, 0xa3, 0xa4, 0xa5,
+    0xa6, 0xa7, 0xa8, 0xa9, 0xaa, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7, 0xb8, 0xb9, 0xba, 0xc2, 0xc3,
+    0xc4, 0xc5, 0xc6, 0xc7, 0xc8, 0xc9, 0xca, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6, 0xd7, 0xd8, 0xd9, 0xda,
+    0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, 0xea, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8,
+    0xf9, 0xfa,
+];
+
+fn dct_basis() -> [[f64; 8]; 8] {
+    let mut basis = [[0.0; 8]; 8];
+    for (x, row) in basis.iter_mut().enumerate() {
+        for (u, weight) in row.iter_mut().enumerate() {
+            let c = if u == 0 { FRAC_1_SQRT_2 } else { 1.0 };
+            *weight = c / 2.0 * ((2 * x + 1) as f64 * u as f64 * PI / 16.0).cos();
+        }
+    }
+    basis
+}
+
+#[derive(Debug)]
+struct JpegEncoder {
+    quantization: [[u16; 64]; 2],
+    dc_tables: [HuffmanEncoder; 2],
+    ac_tables: [HuffmanEncoder; 2],
+    basis: [[f64; 8]; 8],
+}
+
+impl JpegEncoder {
+    fn new(quality: u8) -> Self {
+        let quality = u32::from(quality.clamp(1, 100));
+        let scale = if quality < 50 {
+            5000 / quality
+        } else {
+            200 - 2 * quality
+        };
+        let scale_table = |base: &[u16; 64]| {
+            base.map(|q| ((u32::from(q) * scale + 50) / 100).clamp(1, 255) as u16)
+        };
+        Self {
+            quantization: [
+                scale_table(&LUMINANCE_QUANTIZATION),
+                scale_table(&CHROMINANCE_QUANTIZATION),
+            ],
+            dc_tables: [
+                HuffmanEncoder::new(&DC_LUMINANCE_BITS, &DC_VALUES),
+                HuffmanEncoder::new(&DC_CHROMINANCE_BITS, &DC_VALUES),
+            ],
+            ac_tables: [
+                HuffmanEncoder::new(&AC_LUMINANCE_BITS, &AC_LUMINANCE_VALUES),
+                HuffmanEncoder::new(&AC_CHROMINANCE_BITS, &AC_CHROMINANCE_VALUES),
+            ],
+            basis: dct_basis(),
+        }
+    }
+
+    fn write<W>(&self, image: &Image, mut writer: W) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        let (height, width) = (image.resolution().height(), image.resolution().width());
+        self.write_headers(&mut writer, height, width)?;
+
+        let mut ycbcr = Vec::with_capacity(height * width);
+        for row in 0..height {
+            for column in 0..width {
+                let color = SRgbColor::from(image.get_linear(row, column).unwrap());
+                let (r, g, b) = (
+                    f64::from(color.red()),
+                    f64::from(color.green()),
+                    f64::from(color.blue()),
+                );
+                ycbcr.push([
+                    0.299 * r + 0.587 * g + 0.114 * b - 128.0,
+                    -0.168736 * r - 0.331264 * g + 0.5 * b,
+                    0.5 * r - 0.418688 * g - 0.081312 * b,
+                ]);
+            }
+        }
+
+        let mut bits = BitWriter::new(&mut writer);
+        let mut predictions = [0; 3];
+        for block_row in (0..height).step_by(8) {
+            for block_column in (0..width).step_by(8) {
+                for (component, prediction) in predictions.iter_mut().enumerate() {
+                    let mut block = [0.0; 64];
+                    for y in 0..8 {
+                        for x in 0..8 {
+                            let row = (block_row + y).min(height - 1);
+                            let column = (block_column + x).min(width - 1);
+                            block[y * 8 + x] = ycbcr[row * width + column][component];
+                        }
+                    }
+                    let table = usize::from(component != 0);
+                    let coefficients = self.quantize(&self.forward_dct(&block), table);
+                    self.write_block(&mut bits, &coefficients, prediction, table)?;
+                }
+            }
+        }
+        bits.flush()?;
+
+        writer.write_all(&[0xFF, 0xD9])?;
+        writer.flush()
+    }
+
+    fn write_headers<W>(&self, writer: &mut W, height: usize, width: usize) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        writer.write_all(&[0xFF, 0xD8])?;
+        writer.write_all(&[0xFF, 0xE0, 0, 16])?;
+        writer.write_all(b"JFIF\0")?;
+        writer.write_all(&[1, 1, 0, 0, 1, 0, 1, 0, 0])?;
+
+        writer.write_all(&[0xFF, 0xDB, 0, 132])?;
+        for (id, table) in self.quantization.iter().enumerate() {
+            writer.write_all(&[id as u8])?;
+            for index in ZIGZAG {
+                writer.write_all(&[table[index] as u8])?;
+            }
+        }
+
+        let (height, width) = (height as u16, width as u16);
+        writer.write_all(&[0xFF, 0xC0, 0, 17, 8])?;
+        writer.write_all(&height.to_be_bytes())?;
+        writer.write_all(&width.to_be_bytes())?;
+        writer.write_all(&[3, 1, 0x11, 0, 2, 0x11, 1, 3, 0x11, 1])?;
+
+        let tables: [(u8, &[u8; 16], &[u8]); 4] = [
+            (0x00, &DC_LUMINANCE_BITS, &DC_VALUES),
+            (0x10, &AC_LUMINANCE_BITS, &AC_LUMINANCE_VALUES),
+            (0x01, &DC_CHROMINANCE_BITS, &DC_VALUES),
+            (0x11, &AC_CHROMINANCE_BITS, &AC_CHROMINANCE_VALUES),
+        ];
+        let length = 2 + tables.iter().map(|t| 17 + t.2.len()).sum::<usize>();
+        writer.write_all(&[0xFF, 0xC4])?;
+        writer.write_all(&(length as u16).to_be_bytes())?;
+        for (class_id, bits, values) in tables {
+            writer.write_all(&[class_id])?;
+            writer.write_all(bits)?;
+            writer.write_all(values)?;
+        }
+
+        writer.write_all(&[0xFF, 0xDA, 0, 12, 3, 1, 0x00, 2, 0x11, 3, 0x11, 0, 63, 0])
+    }
+
+    fn forward_dct(&self, block: &[f64; 64]) -> [f64; 64] {
+        let mut rows = [0.0; 64];
+        for y in 0..8 {
+            for u in 0..8 {
+                rows[y * 8 + u] = (0..8).map(|x| block[y * 8 + x] * self.basis[x][u]).sum();
+            }
+        }
+        let mut coefficients = [0.0; 64];
+        for v in 0..8 {
+            for u in 0..8 {
+                coefficients[v * 8 + u] = (0..8).map(|y| rows[y * 8 + u] * self.basis[y][v]).sum();
+            }
+        }
+        coefficients
+    }
+
+    fn quantize(&self, coefficients: &[f64; 64], table: usize) -> [i32; 64] {
+        let quantization = &self.quantization[table];
+        let mut zigzag = [0; 64];
+        for (k, &index) in ZIGZAG.iter().enumerate() {
+            let value = (coefficients[index] / f64::from(quantization[index])).round() as i32;
+            zigzag[k] = if k == 0 {
+                value
+            } else {
+                value.clamp(-1023, 1023)
+            };
+        }
+        zigzag
+    }
+
+    fn write_block<W>(
+        &self,
+        bits: &mut BitWriter<W>,
+        zigzag: &[i32; 64],
+        prediction: &mut i32,
+        table: usize,
+    ) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        let (dc_table, ac_table) = (&self.dc_tables[table], &self.ac_tables[table]);
+
+        let (size, magnitude) = Self::encode_magnitude(zigzag[0] - *prediction);
+        *prediction = zigzag[0];
+        dc_table.write(bits, size)?;
+        bits.write(magnitude, size)?;
+
+        let mut run = 0;
+        for &value in &zigzag[1..] {
+            if value == 0 {
+                run += 1;
+                continue;
+            }
+            while run >= 16 {
+                ac_table.write(bits, 0xF0)?;
+                run -= 16;
+            }
+            let (size, magnitude) = Self::encode_magnitude(value);
+            ac_table.write(bits, (run << 4) | size)?;
+            bits.write(magnitude, size)?;
+            run = 0;
+        }
+        if run > 0 {
+            ac_table.write(bits, 0x00)?;
+        }
+        Ok(())
+    }
+
+    fn encode_magnitude(value: i32) -> (u8, u16) {
+        if value == 0 {
+            return (0, 0);
+        }
+        let size = 32 - value.unsigned_abs().leading_zeros();
+        let magnitude = if value > 0 {
+            value
+        } else {
+            value + (1 << size) - 1
+        };
+        (size as u8, magnitude as u16)
+    }
+}
+
+#[derive(Debug)]
+struct HuffmanEncoder {
+    codes: [(u16, u8); 256],
+}
+
+impl HuffmanEncoder {
+    fn new(bits: &[u8; 16], values: &[u8]) -> Self {
+        let mut codes = [(0, 0); 256];
+        let (mut code, mut values) = (0u16, values.iter());
+        for (length, &count) in (1..=16).zip(bits) {
+            for _ in 0..count {
+                codes[usize::from(*values.next().unwrap())] = (code, length);
+                code += 1;
+            }
+            code <<= 1;
+        }
+        Self { codes }
+    }
+
+    fn write<W>(&self, bits: &mut BitWriter<W>, symbol: u8) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        let (code, length) = self.codes[usize::from(symbol)];
+        bits.write(code, length)
+    }
+}
+
+#[derive(Debug)]
+struct BitWriter<W> {
+    writer: W,
+    buffer: u32,
+    count: u8,
+}
+
+impl<W> BitWriter<W>
+where
+    W: Write,
+{
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buffer: 0,
+            count: 0,
+        }
+    }
+
+    fn write(&mut self, bits: u16, length: u8) -> std::io::Result<()> {
+        let mask = (1u32 << length) - 1;
+        self.buffer = (self.buffer << length) | (u32::from(bits) & mask);
+        self.count += length;
+        while self.count >= 8 {
+            let byte = (self.buffer >> (self.count - 8)) as u8;
+            self.writer.write_all(&[byte])?;
+            if byte == 0xFF {
+                self.writer.write_all(&[0x00])?;
+            }
+            self.count -= 8;
+        }
+        self.buffer &= (1u32 << self.count) - 1;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.count > 0 {
+            let padding = 8 - self.count;
+            self.write((1 << padding) - 1, padding)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct JpegDecoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    quantization: [[u16; 64]; 4],
+    dc_tables: [Option<HuffmanDecoder>; 4],
+    ac_tables: [Option<HuffmanDecoder>; 4],
+    restart_interval: usize,
+    frame: Option<JpegFrame>,
+    basis: [[f64; 8]; 8],
+}
+
+#[derive(Debug)]
+struct JpegFrame {
+    height: usize,
+    width: usize,
+    max_sampling: (usize, usize),
+    mcus: (usize, usize),
+    components: Vec<JpegComponent>,
+}
+
+#[derive(Debug)]
+struct JpegComponent {
+    id: u8,
+    sampling: (usize, usize),
+    quantization: usize,
+    tables: (usize, usize),
+    prediction: i32,
+    stride: usize,
+    samples: Vec<u8>,
+}
+
+impl<'a> JpegDecoder<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            quantization: [[1; 64]; 4],
+            dc_tables: [None, None, None, None],
+            ac_tables: [None, None, None, None],
+            restart_interval: 0,
+            frame: None,
+            basis: dct_basis(),
+        }
+    }
+
+    fn decode(mut self) -> Result<Image, LoadImageError> {
+        ensure_whatever!(
+            self.bytes.starts_with(&[0xFF, 0xD8]),
+            "data is not in the JPEG format"
+        );
+        self.pos = 2;
+
+        loop {
+            match self.next_marker()? {
+                0xC0 | 0xC1 => self.parse_frame()?,
+                0xC4 => self.parse_huffman_tables()?,
+                0xDB => self.parse_quantization_tables()?,
+                0xDD => {
+                    let segment = self.read_segment()?;
+                    ensure_whatever!(segment.len() >= 2, "JPEG restart interval is truncated");
+                    self.restart_interval = usize::from(segment[0]) << 8 | usize::from(segment[1]);
+                }
+                0xDA => self.parse_scan()?,
+                0xD9 => break,
+                0xC2 | 0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF => {
+                    whatever!("only baseline Huffman-coded JPEG is supported");
+                }
+                _ => {
+                    self.read_segment()?;
+                }
+            }
+        }
+
+        let Some(frame) = self.frame else {
+            whatever!("JPEG frame header is missing");
+        };
+        Ok(Self::convert_frame(&frame))
+    }
+
+    fn next_marker(&mut self) -> Result<u8, LoadImageError> {
+        while self.pos < self.bytes.len() && self.bytes[self.pos] != 0xFF {
+            self.pos += 1;
+        }
+        while self.pos < self.bytes.len() && self.bytes[self.pos] == 0xFF {
+            self.pos += 1;
+        }
+        ensure_whatever!(self.pos < self.bytes.len(), "JPEG data is truncated");
+        self.pos += 1;
+        Ok(self.bytes[self.pos - 1])
+    }
+
+    fn read_segment(&mut self) -> Result<&'a [u8], LoadImageError> {
+        ensure_whatever!(self.pos + 2 <= self.bytes.len(), "JPEG data is truncated");
+        let length = usize::from(self.bytes[self.pos]) << 8 | usize::from(self.bytes[self.pos + 1]);
+        ensure_whatever!(
+            length >= 2 && self.pos + length <= self.bytes.len(),
+            "JPEG segment length is invalid"
+        );
+        let segment = &self.bytes[self.pos + 2..self.pos + length];
+        self.pos += length;
+        Ok(segment)
+    }
+
+    fn parse_quantization_tables(&mut self) -> Result<(), LoadImageError> {
+        let mut segment = self.read_segment()?;
+        while !segment.is_empty() {
+            let (precision, id) = (segment[0] >> 4, usize::from(segment[0] & 0x0F));
+            let size = if precision == 0 { 64 } else { 128 };
+            ensure_whatever!(
+                id < 4 && segment.len() > size,
+                "JPEG quantization table is invalid"
+            );
+            for (k, &index) in ZIGZAG.iter().enumerate() {
+                self.quantization[id][index] = if precision == 0 {
+                    u16::from(segment[1 + k])
+                } else {
+                    u16::from(segment[1 + 2 * k]) << 8 | u16::from(segment[2 + 2 * k])
+                };
+            }
+            segment = &segment[1 + size..];
+        }
+        Ok(())
+    }
+
+    fn parse_huffman_tables(&mut self) -> Result<(), LoadImageError> {
+        let mut segment = self.read_segment()?;
+        while !segment.is_empty() {
+            ensure_whatever!(segment.len() >= 17, "JPEG Huffman table is truncated");
+            let (class, id) = (segment[0] >> 4, usize::from(segment[0] & 0x0F));
+            let bits: [u8; 16] = segment[1..17].try_into().unwrap();
+            let count = bits.iter().map(|&b| usize::from(b)).sum::<usize>();
+            ensure_whatever!(
+                id < 4 && class < 2 && segment.len() >= 17 + count,
+                "JPEG Huffman table is invalid"
+            );
+            let values = &segment[17..17 + count];
+            // Baseline DC differences take at most 11 bits and AC coefficients at most 10.
+            ensure_whatever!(
+                values.iter().all(|&value| match class {
+                    0 => value <= 11,
+                    _ => value & 0x0F <= 10,
+                }),
+                "JPEG Huffman table has symbols out of range"
+            );
+            let table = HuffmanDecoder::new(&bits, values);
+            if class == 0 {
+                self.dc_tables[id] = Some(table);
+            } else {
+                self.ac_tables[id] = Some(table);
+            }
+            segment = &segment[17 + count..];
+        }
+        Ok(())
+    }
+
+    fn parse_frame(&mut self) -> Result<(), LoadImageError> {
+        let segment = self.read_segment()?;
+        ensure_whatever!(segment.len() >= 6, "JPEG frame header is truncated");
+        ensure_whatever!(segment[0] == 8, "only 8-bit JPEG is supported");
+        let height = usize::from(segment[1]) << 8 | usize::from(segment[2]);
+        let width = usize::from(segment[3]) << 8 | usize::from(segment[4]);
+        let count = usize::from(segment[5]);
+        ensure_whatever!(height > 0 && width > 0, "JPEG image is empty");
+        ensure_whatever!(
+            (count == 1 || count == 3) && segment.len() >= 6 + 3 * count,
+            "only grayscale and YCbCr JPEG is supported"
+        );
+
+        let mut components = Vec::with_capacity(count);
+        for info in segment[6..6 + 3 * count].chunks_exact(3) {
+            let sampling = (usize::from(info[1] >> 4), usize::from(info[1] & 0x0F));
+            ensure_whatever!(
+                (1..=4).contains(&sampling.0) && (1..=4).contains(&sampling.1) && info[2] < 4,
+                "JPEG component is invalid"
+            );
+            components.push(JpegComponent {
+                id: info[0],
+                sampling,
+                quantization: usize::from(info[2]),
+                tables: (0, 0),
+                prediction: 0,
+                stride: 0,
+                samples: Vec::new(),
+            });
+        }
+        if count == 1 {
+            components[0].sampling = (1, 1);
+        }
+
+        let max_sampling = components.iter().fold((1, 1), |(h, v), c| {
+            (h.max(c.sampling.0), v.max(c.sampling.1))
+        });
+        let mcus = (
+            width.div_ceil(8 * max_sampling.0),
+            height.div_ceil(8 * max_sampling.1),
+        );
+        for component in &mut components {
+            component.stride = mcus.0 * component.sampling.0 * 8;
+            let rows = mcus.1 * component.sampling.1 * 8;
+            component.samples = vec![0; component.stride * rows];
+        }
+
+        self.frame = Some(JpegFrame {
+            height,
+            width,
+            max_sampling,
+            mcus,
+            components,
+        });
+        Ok(())
+    }
+
+    fn parse_scan(&mut self) -> Result<(), LoadImageError> {
+        let segment = self.read_segment()?;
+        let Some(frame) = self.frame.as_mut() else {
+            whatever!("JPEG scan precedes the frame header");
+        };
+        ensure_whatever!(!segment.is_empty(), "JPEG scan header is truncated");
+        let count = usize::from(segment[0]);
+        ensure_whatever!(
+            count >= 1 && segment.len() > 2 * count,
+            "JPEG scan header is invalid"
+        );
+
+        let mut selected = Vec::with_capacity(count);
+        for info in segment[1..1 + 2 * count].chunks_exact(2) {
+            let Some(index) = frame.components.iter().position(|c| c.id == info[0]) else {
+                whatever!("JPEG scan refers to unknown component {}", info[0]);
+            };
+            let tables = (usize::from(info[1] >> 4), usize::from(info[1] & 0x0F));
+            ensure_whatever!(
+                tables.0 < 4
+                    && tables.1 < 4
+                    && self.dc_tables[tables.0].is_some()
+                    && self.ac_tables[tables.1].is_some(),
+                "JPEG scan refers to missing Huffman tables"
+            );
+            frame.components[index].tables = tables;
+            frame.components[index].prediction = 0;
+            selected.push(index);
+        }
+
+        let mut reader = BitReader::new(self.bytes, self.pos);
+        let context = ScanContext {
+            quantization: &self.quantization,
+            dc_tables: &self.dc_tables,
+            ac_tables: &self.ac_tables,
+            basis: &self.basis,
+        };
+
+        let units = if selected.len() == 1 {
+            let component = &frame.components[selected[0]];
+            let blocks_x = (frame.width * component.sampling.0).div_ceil(8 * frame.max_sampling.0);
+            let blocks_y = (frame.height * component.sampling.1).div_ceil(8 * frame.max_sampling.1);
+            (blocks_x, blocks_y)
+        } else {
+            frame.mcus
+        };
+
+        for unit in 0..units.0 * units.1 {
+            if self.restart_interval > 0 && unit > 0 && unit % self.restart_interval == 0 {
+                reader.restart();
+                for &index in &selected {
+                    frame.components[index].prediction = 0;
+                }
+            }
+            let (unit_x, unit_y) = (unit % units.0, unit / units.0);
+            if selected.len() == 1 {
+                let component = &mut frame.components[selected[0]];
+                context.decode_block(&mut reader, component, unit_x, unit_y)?;
+            } else {
+                for &index in &selected {
+                    let component = &mut frame.components[index];
+                    let (h, v) = component.sampling;
+                    for block_y in 0..v {
+                        for block_x in 0..h {
+                            let (x, y) = (unit_x * h + block_x, unit_y * v + block_y);
+                            context.decode_block(&mut reader, component, x, y)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.pos = reader.pos;
+        Ok(())
+    }
+
+    fn convert_frame(frame: &JpegFrame) -> Image {
+        let resolution = Resolution::from_dimensions(frame.width, frame.height).unwrap();
+        let mut image = Image::new(resolution);
+        let sample = |component: &JpegComponent, row: usize, column: usize| {
+            let y = row * component.sampling.1 / frame.max_sampling.1;
+            let x = column * component.sampling.0 / frame.max_sampling.0;
+            f64::from(component.samples[y * component.stride + x])
+        };
+
+        for row in 0..frame.height {
+            for column in 0..frame.width {
+                let color = match frame.components.as_slice() {
+                    [gray] => {
+                        let value = sample(gray, row, column) as u8;
+                        SRgbColor::new(value, value, value)
+                    }
+                    [y, cb, cr] => {
+                        let y = sample(y, row, column);
+                        let cb = sample(cb, row, column) - 128.0;
+                        let cr = sample(cr, row, column) - 128.0;
+                        let quantize = |v: f64| v.round().clamp(0.0, 255.0) as u8;
+                        SRgbColor::new(
+                            quantize(y + 1.402 * cr),
+                            quantize(y - 0.344136 * cb - 0.714136 * cr),
+                            quantize(y + 1.772 * cb),
+                        )
+                    }
+                    _ => unreachable!("component count should be checked by the frame header"),
+                };
+                image.set(row, column, color.to_encoded());
+            }
+        }
+        image.with_color_space(ColorSpace::Srgb)
+    }
+}
+
+#[derive(Debug)]
+struct ScanContext<'a> {
+    quantization: &'a [[u16; 64]; 4],
+    dc_tables: &'a [Option<HuffmanDecoder>; 4],
+    ac_tables: &'a [Option<HuffmanDecoder>; 4],
+    basis: &'a [[f64; 8]; 8],
+}
+
+impl ScanContext<'_> {
+    fn decode_block(
+        &self,
+        reader: &mut BitReader,
+        component: &mut JpegComponent,
+        block_x: usize,
+        block_y: usize,
+    ) -> Result<(), LoadImageError> {
+        let quantization = &self.quantization[component.quantization];
+        let dc_table = self.dc_tables[component.tables.0].as_ref().unwrap();
+        let ac_table = self.ac_tables[component.tables.1].as_ref().unwrap();
+
+        let mut coefficients = [0.0; 64];
+        let size = dc_table.decode(reader)?;
+        component.prediction += reader.read_signed(size);
+        ensure_whatever!(
+            component.prediction.abs() <= 2047,
+            "JPEG DC coefficient is out of range"
+        );
+        coefficients[0] = f64::from(component.prediction) * f64::from(quantization[0]);
+
+        let mut k = 1;
+        while k < 64 {
+            let symbol = ac_table.decode(reader)?;
+            let (run, size) = (usize::from(symbol >> 4), symbol & 0x0F);
+            if size == 0 {
+                if run != 15 {
+                    break;
+                }
+                k += 16;
+                continue;
+            }
+            k += run;
+            ensure_whatever!(k < 64, "JPEG coefficient index is out of range");
+            let index = ZIGZAG[k];
+            let value = reader.read_signed(size);
+            coefficients[index] = f64::from(value) * f64::from(quantization[index]);
+            k += 1;
+        }
+
+        let mut columns = [0.0; 64];
+        for v in 0..8 {
+            for x in 0..8 {
+                columns[v * 8 + x] = (0..8)
+                    .map(|u| coefficients[v * 8 + u] * self.basis[x][u])
+                    .sum();
+            }
+        }
+        for y in 0..8 {
+            for x in 0..8 {
+                let value = (0..8)
+                    .map(|v| columns[v * 8 + x] * self.basis[y][v])
+                    .sum::<f64>();
+                let offset = (block_y * 8 + y) * component.stride + block_x * 8 + x;
+                component.samples[offset] = (value + 128.0).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct HuffmanDecoder {
+    min_codes: [i32; 17],
+    max_codes: [i32; 17],
+    offsets: [usize; 17],
+    values: Vec<u8>,
+}
+
+impl HuffmanDecoder {
+    fn new(bits: &[u8; 16], values: &[u8]) -> Self {
+        let (mut min_codes, mut max_codes, mut offsets) = ([0; 17], [-1; 17], [0; 17]);
+        let (mut code, mut offset) = (0, 0);
+        for length in 1..=16 {
+            let count = bits[length - 1];
+            offsets[length] = offset;
+            min_codes[length] = code;
+            if count > 0 {
+                max_codes[length] = code + i32::from(count) - 1;
+            }
+            code = (code + i32::from(count)) << 1;
+            offset += usize::from(count);
+        }
+        Self {
+            min_codes,
+            max_codes,
+            offsets,
+            values: values.to_vec(),
+        }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u8, LoadImageError> {
+        let mut code = 0;
+        for length in 1..=16 {
+            code = (code << 1) | reader.read(1) as i32;
+            if code <= self.max_codes[length] {
+                let index = self.offsets[length] + (code - self.min_codes[length]) as usize;
+                if let Some(&value) = self.values.get(index) {
+                    return Ok(value);
+                }
+            }
+        }
+        whatever!("JPEG Huffman code is invalid");
+    }
+}
+
+#[derive(Debug)]
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    buffer: u32,
+    count: u8,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8], pos: usize) -> Self {
+        Self {
+            bytes,
+            pos,
+            buffer: 0,
+            count: 0,
+        }
+    }
+
+    fn fill(&mut self) {
+        while self.count <= 24 {
+            let byte = match self.bytes.get(self.pos..self.pos + 2) {
+                Some([0xFF, 0x00]) => {
+                    self.pos += 2;
+                    0xFF
+                }
+                Some([0xFF, _]) => 0,
+                _ if self.pos < self.bytes.len() && self.bytes[self.pos] != 0xFF => {
+                    self.pos += 1;
+                    self.bytes[self.pos - 1]
+                }
+                _ => 0,
+            };
+            self.buffer |= u32::from(byte) << (24 - self.count);
+            self.count += 8;
+        }
+    }
+
+    fn read(&mut self, length: u8) -> u32 {
+        if length == 0 {
+            return 0;
+        }
+        self.fill();
+        let value = self.buffer >> (32 - length);
+        self.buffer <<= length;
+        self.count -= length;
+        value
+    }
+
+    fn read_signed(&mut self, size: u8) -> i32 {
+        if size == 0 {
+            return 0;
+        }
+        let value = self.read(size) as i32;
+        if value < 1 << (size - 1) {
+            value - (1 << size) + 1
+        } else {
+            value
+        }
+    }
+
+    fn restart(&mut self) {
+        (self.buffer, self.count) = (0, 0);
+        if let Some([0xFF, 0xD0..=0xD7]) = self.bytes.get(self.pos..self.pos + 2) {
+            self.pos += 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::core::Spectrum;
+    use crate::domain::math::numeric::Val;
+
+    use super::*;
+
+    // Hand-assembled baseline files whose blocks are all flat.
+    const SUBSAMPLED_JPEG: [u8; 165] = [
+        0xFF, 0xD8, 0xFF, 0xDB, 0x00, 0x43, 0x00, 0x08, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0xFF, 0xC0, 0x00, 0x11,
+        0x08, 0x00, 0x10, 0x00, 0x10, 0x03, 0x01, 0x22, 0x00, 0x02, 0x11, 0x00, 0x03, 0x11, 0x00,
+        0xFF, 0xC4, 0x00, 0x31, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+        0x09, 0x0A, 0x0B, 0x10, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xDA, 0x00, 0x0C, 0x03, 0x01, 0x00, 0x02, 0x00,
+        0x03, 0x00, 0x00, 0x3F, 0x00, 0x77, 0x66, 0xA0, 0xD4, 0x1A, 0x80, 0x1A, 0x87, 0xFF, 0xD9,
+    ];
+    const RESTART_JPEG: [u8; 159] = [
+        0xFF, 0xD8, 0xFF, 0xDB, 0x00, 0x43, 0x00, 0x08, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0xFF, 0xC0, 0x00, 0x0B,
+        0x08, 0x00, 0x08, 0x00, 0x10, 0x01, 0x01, 0x11, 0x00, 0xFF, 0xC4, 0x00, 0x31, 0x00, 0x00,
+        0x00, 0x00, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x10, 0x01, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0xFF, 0xDD, 0x00, 0x04, 0x00, 0x01, 0xFF, 0xDA, 0x00, 0x08, 0x01, 0x01, 0x00, 0x00, 0x3F,
+        0x00, 0x79, 0x0F, 0xFF, 0xD0, 0x74, 0xEF, 0xFF, 0xD9,
+    ];
+
+    fn gradient(height: usize, width: usize) -> Image {
+        let mut image = Image::new(Resolution::from_dimensions(width, height).unwrap());
+        for row in 0..height {
+            for column in 0..width {
+                let (r, g) = ((column * 255 / width) as u8, (row * 255 / height) as u8);
+                let color = SRgbColor::new(r, g, 255 - r / 2 - g / 2);
+                image.set(row, column, Spectrum::from(color));
+            }
+        }
+        image
+    }
+
+    #[test]
+    fn jpeg_image_resource_encode_succeeds_round_tripping_pixels() {
+        let image = gradient(10, 13);
+        let mut buffer = Vec::new();
+        JpegImageResource::encode(&image, 90, &mut buffer).unwrap();
+        assert_eq!(&buffer[..3], &[0xFF, 0xD8, 0xFF]);
+        assert_eq!(&buffer[buffer.len() - 2..], &[0xFF, 0xD9]);
+
+        let decoded = JpegImageResource::decode(&buffer).unwrap();
+        assert_eq!(decoded.resolution(), image.resolution());
+        assert_eq!(decoded.color_space(), ColorSpace::Srgb);
+        for row in 0..10 {
+            for column in 0..13 {
+                let expected = SRgbColor::from(image.get(row, column).unwrap());
+                let actual = SRgbColor::from(decoded.get_linear(row, column).unwrap());
+                let diff = |a: u8, b: u8| a.abs_diff(b);
+                assert!(diff(expected.red(), actual.red()) <= 8);
+                assert!(diff(expected.green(), actual.green()) <= 8);
+                assert!(diff(expected.blue(), actual.blue()) <= 8);
+            }
+        }
+    }
+
+    #[test]
+    fn jpeg_image_resource_encode_fails_when_image_is_not_tone_mapped() {
+        let mut image = gradient(4, 4);
+        image.set(2, 1, Spectrum::new(Val(4.0), Val(0.5), Val(0.5)));
+        let mut buffer = Vec::new();
+        assert!(JpegImageResource::encode(&image, 90, &mut buffer).is_err());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn jpeg_image_resource_decode_fails_when_magic_is_invalid() {
+        assert!(JpegImageResource::decode(b"\x89PNG\r\n\x1a\n").is_err());
+    }
+
+    fn decoded_color(bytes: &[u8], row: usize, column: usize) -> (u8, u8, u8) {
+        let image = JpegImageResource::decode(bytes).unwrap();
+        let color = SRgbColor::from(image.get_linear(row, column).unwrap());
+        (color.red(), color.green(), color.blue())
+    }
+
+    fn patch_huffman_value(value: u8, offset: usize) -> Vec<u8> {
+        let mut bytes = RESTART_JPEG.to_vec();
+        let marker = bytes.windows(2).position(|w| w == [0xFF, 0xC4]).unwrap();
+        bytes[marker + offset] = value;
+        bytes
+    }
+
+    #[test]
+    fn jpeg_image_resource_decode_succeeds_upsampling_chroma() {
+        assert_eq!(decoded_color(&SUBSAMPLED_JPEG, 0, 0), (116, 31, 60));
+        assert_eq!(decoded_color(&SUBSAMPLED_JPEG, 7, 8), (156, 71, 100));
+        assert_eq!(decoded_color(&SUBSAMPLED_JPEG, 8, 7), (196, 111, 140));
+        assert_eq!(decoded_color(&SUBSAMPLED_JPEG, 15, 15), (236, 151, 180));
+    }
+
+    #[test]
+    fn jpeg_image_resource_decode_succeeds_resetting_prediction_at_restarts() {
+        assert_eq!(decoded_color(&RESTART_JPEG, 3, 3), (200, 200, 200));
+        assert_eq!(decoded_color(&RESTART_JPEG, 3, 12), (40, 40, 40));
+    }
+
+    #[test]
+    fn jpeg_image_resource_decode_fails_when_dc_size_is_out_of_range() {
+        // Replaces DC symbol 11, the last one of the first table.
+        let bytes = patch_huffman_value(16, 4 + 17 + 11);
+        assert!(JpegImageResource::decode(&bytes).is_err());
+    }
+
+    #[test]
+    fn jpeg_image_resource_decode_fails_when_ac_size_is_out_of_range() {
+        // Replaces the end-of-block symbol of the second table.
+        let bytes = patch_huffman_value(0x0B, 4 + 29 + 17);
+        assert!(JpegImageResource::decode(&bytes).is_err());
+    }
+}
@@ -1,7 +1,13 @@
+mod exr;
+mod hdr;
+mod jpeg;
 mod png;
 mod ppm;
 mod registry;
 
+pub use exr::ExrImageResource;
+pub use hdr::HdrImageResource;
+pub use jpeg::JpegImageResource;
 pub use png::PngImageResource;
 pub use ppm::PpmImageResource;
 pub use registry::{
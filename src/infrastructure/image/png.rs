@@ -1,18 +1,20 @@
 use std::fs::File;
-use std::io::{BufReader, BufWriter, ErrorKind as IoErrorKind};
+use std::io::{BufReader, BufWriter, ErrorKind as IoErrorKind, Write};
 use std::path::PathBuf;
 
 use png::{BitDepth, ColorType, Decoder, Encoder, OutputInfo, SrgbRenderingIntent};
 use snafu::prelude::*;
 
 use crate::domain::camera::Resolution;
-use crate::domain::color::external::SRgbColor;
+use crate::domain::color::core::ColorSpace;
+use crate::domain::color::external::{BlueNoiseDither, SRgbColor};
 use crate::domain::image::core::Image;
 use crate::domain::image::external::*;
 
 #[derive(Debug, Clone)]
 pub struct PngImageResource {
     path: PathBuf,
+    dithering: bool,
 }
 
 impl PngImageResource {
@@ -20,18 +22,44 @@ impl PngImageResource {
     where
         P: Into<PathBuf>,
     {
-        Self { path: path.into() }
+        Self {
+            path: path.into(),
+            dithering: false,
+        }
+    }
+
+    pub fn with_dithering(self, dithering: bool) -> Self {
+        Self { dithering, ..self }
     }
 
-    fn convert_image(image: &Image) -> Vec<u8> {
+    fn quantize(image: &Image, row: usize, column: usize, dithering: bool) -> SRgbColor {
+        let color = image.get_linear(row, column).unwrap();
+        if dithering {
+            SRgbColor::from_dithered(color, BlueNoiseDither::threshold(row, column))
+        } else {
+            SRgbColor::from(color)
+        }
+    }
+
+    pub fn encode<W>(image: &Image, writer: W) -> Result<(), SaveImageError>
+    where
+        W: Write,
+    {
+        Self::encode_png(image, writer, "the buffer", false)
+    }
+
+    pub fn to_rgb_bytes(image: &Image) -> Vec<u8> {
+        Self::collect_rgb_bytes(image, false)
+    }
+
+    fn collect_rgb_bytes(image: &Image, dithering: bool) -> Vec<u8> {
         let height = image.resolution().height();
         let width = image.resolution().width();
         let mut data = Vec::with_capacity(height * width * 3);
 
         for row in 0..height {
             for column in 0..width {
-                let color = image.get(row, column).unwrap();
-                let color = SRgbColor::from(color);
+                let color = Self::quantize(image, row, column, dithering);
                 data.push(color.red());
                 data.push(color.green());
                 data.push(color.blue());
@@ -86,7 +114,7 @@ impl PngImageResource {
     fn decode_image(&self, info: OutputInfo, buf: &[u8]) -> Result<Image, LoadImageError> {
         let width = info.width as usize;
         let height = info.height as usize;
-        let mut image = Image::new(Resolution::new(height, (width, height)).unwrap());
+        let mut image = Image::new(Resolution::from_dimensions(width, height).unwrap());
         let bytes = &buf[..info.buffer_size()];
 
         for row in 0..height {
@@ -110,11 +138,11 @@ impl PngImageResource {
                     }
                     _ => unreachable!("other unsupported color types should be checked"),
                 };
-                image.set(row, column, color.into());
+                image.set(row, column, color.to_encoded());
             }
         }
 
-        Ok(image)
+        Ok(image.with_color_space(ColorSpace::Srgb))
     }
 
     fn open_file_for_save(&self) -> Result<File, SaveImageError> {
@@ -123,24 +151,30 @@ impl PngImageResource {
         })
     }
 
-    fn encode_png(&self, image: &Image, file: File) -> Result<(), SaveImageError> {
+    fn encode_png<W>(
+        image: &Image,
+        writer: W,
+        target: &str,
+        dithering: bool,
+    ) -> Result<(), SaveImageError>
+    where
+        W: Write,
+    {
         let height = image.resolution().height() as u32;
         let width = image.resolution().width() as u32;
-        let mut encoder = Encoder::new(BufWriter::new(file), width, height);
+        let mut encoder = Encoder::new(writer, width, height);
         encoder.set_color(ColorType::Rgb);
         encoder.set_depth(BitDepth::Eight);
         encoder.set_source_srgb(SrgbRenderingIntent::RelativeColorimetric);
 
         let mut writer = whatever!(
             encoder.write_header(),
-            "could not write metadata to `{}`",
-            self.path.display()
+            "could not write metadata to {target}",
         );
-        let data = Self::convert_image(image);
+        let data = Self::collect_rgb_bytes(image, dithering);
         whatever!(
             writer.write_image_data(&data),
-            "could not write all data to `{}`",
-            self.path.display()
+            "could not write all data to {target}",
         );
         Ok(())
     }
@@ -155,6 +189,35 @@ impl ImageResource for PngImageResource {
 
     fn save(&self, image: &Image) -> Result<(), SaveImageError> {
         let file = self.open_file_for_save()?;
-        self.encode_png(image, file)
+        let target = format!("`{}`", self.path.display());
+        Self::encode_png(image, BufWriter::new(file), &target, self.dithering)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::domain::color::core::Spectrum;
+    use crate::domain::math::numeric::Val;
+
+    use super::*;
+
+    #[test]
+    fn png_image_resource_encode_succeeds_writing_to_memory() {
+        let mut image = Image::new(Resolution::new(4, (1, 1)).unwrap());
+        image.set(1, 2, Spectrum::broadcast(Val(1.0)));
+
+        let mut buffer = Cursor::new(Vec::new());
+        PngImageResource::encode(&image, &mut buffer).unwrap();
+        let bytes = buffer.into_inner();
+        assert_eq!(
+            &bytes[..8],
+            &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]
+        );
+
+        let pixels = PngImageResource::to_rgb_bytes(&image);
+        assert_eq!(pixels.len(), 4 * 4 * 3);
+        assert_eq!(&pixels[(4 + 2) * 3..(4 + 3) * 3], &[255, 255, 255]);
     }
 }
@@ -5,13 +5,15 @@ use std::path::PathBuf;
 use snafu::prelude::*;
 
 use crate::domain::camera::Resolution;
-use crate::domain::color::external::SRgbColor;
+use crate::domain::color::core::ColorSpace;
+use crate::domain::color::external::{BlueNoiseDither, SRgbColor};
 use crate::domain::image::core::Image;
 use crate::domain::image::external::*;
 
 #[derive(Debug, Clone)]
 pub struct PpmImageResource {
     path: PathBuf,
+    dithering: bool,
 }
 
 impl PpmImageResource {
@@ -19,7 +21,23 @@ impl PpmImageResource {
     where
         P: Into<PathBuf>,
     {
-        Self { path: path.into() }
+        Self {
+            path: path.into(),
+            dithering: false,
+        }
+    }
+
+    pub fn with_dithering(self, dithering: bool) -> Self {
+        Self { dithering, ..self }
+    }
+
+    fn quantize(image: &Image, row: usize, column: usize, dithering: bool) -> SRgbColor {
+        let color = image.get_linear(row, column).unwrap();
+        if dithering {
+            SRgbColor::from_dithered(color, BlueNoiseDither::threshold(row, column))
+        } else {
+            SRgbColor::from(color)
+        }
     }
 
     fn open_and_read_file(&self) -> Result<Vec<u8>, LoadImageError> {
@@ -111,6 +129,39 @@ impl PpmImageResource {
         Ok(pixels)
     }
 
+    pub fn encode<W>(image: &Image, writer: W) -> Result<(), SaveImageError>
+    where
+        W: Write,
+    {
+        whatever!(
+            Self::write_ppm(image, writer, false),
+            "could not write PPM data to the buffer",
+        );
+        Ok(())
+    }
+
+    fn write_ppm<W>(image: &Image, mut writer: W, dithering: bool) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        let width = image.resolution().width();
+        let height = image.resolution().height();
+
+        writeln!(writer, "P3")?;
+        writeln!(writer, "{width} {height}")?;
+        writeln!(writer, "255")?;
+
+        for row in 0..height {
+            for column in 0..width {
+                let color = Self::quantize(image, row, column, dithering);
+                let (r, g, b) = (color.red(), color.green(), color.blue());
+                write!(writer, "{r} {g} {b} ")?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
     fn create_and_write_file(&self, buffer: Vec<u8>) -> Result<(), SaveImageError> {
         let mut file = File::create(&self.path).context(IoSaveSnafu {
             path: self.path.clone(),
@@ -132,40 +183,45 @@ impl ImageResource for PpmImageResource {
         let max_color = self.parse_max_color(&mut cursor);
         let pixels = self.parse_pixel_data(&mut cursor, width, height)?;
 
-        let mut image = Image::new(Resolution::new(height, (width, height)).unwrap());
+        let mut image = Image::new(Resolution::from_dimensions(width, height).unwrap());
         for row in 0..height {
             for col in 0..width {
                 let idx = (row * width + col) * 3;
                 let red = (pixels[idx] as f32 / max_color as f32 * 255.0).floor() as u8;
                 let green = (pixels[idx + 1] as f32 / max_color as f32 * 255.0).floor() as u8;
                 let blue = (pixels[idx + 2] as f32 / max_color as f32 * 255.0).floor() as u8;
-                image.set(row, col, SRgbColor::new(red, green, blue).into());
+                image.set(row, col, SRgbColor::new(red, green, blue).to_encoded());
             }
         }
 
-        Ok(image)
+        Ok(image.with_color_space(ColorSpace::Srgb))
     }
 
     fn save(&self, image: &Image) -> Result<(), SaveImageError> {
         let mut buffer = Vec::new();
+        Self::write_ppm(image, &mut buffer, self.dithering).unwrap();
+        self.create_and_write_file(buffer)?;
+        Ok(())
+    }
+}
 
-        let width = image.resolution().width();
-        let height = image.resolution().height();
+#[cfg(test)]
+mod tests {
+    use crate::domain::color::core::Spectrum;
+    use crate::domain::math::numeric::Val;
 
-        writeln!(buffer, "P3").unwrap();
-        writeln!(buffer, "{width} {height}").unwrap();
-        writeln!(buffer, "255").unwrap();
+    use super::*;
 
-        for row in 0..height {
-            for column in 0..width {
-                let color = SRgbColor::from(image.get(row, column).unwrap());
-                let (r, g, b) = (color.red(), color.green(), color.blue());
-                write!(buffer, "{r} {g} {b} ").unwrap();
-            }
-            writeln!(buffer).unwrap();
-        }
+    #[test]
+    fn ppm_image_resource_encode_succeeds_writing_to_memory() {
+        let mut image = Image::new(Resolution::from_dimensions(2, 1).unwrap());
+        image.set(0, 1, Spectrum::broadcast(Val(1.0)));
 
-        self.create_and_write_file(buffer)?;
-        Ok(())
+        let mut buffer = Vec::new();
+        PpmImageResource::encode(&image, &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "P3\n2 1\n255\n0 0 0 255 255 255 \n"
+        );
     }
 }
@@ -7,7 +7,9 @@ use snafu::prelude::*;
 use crate::domain::image::core::Image;
 use crate::domain::image::external::{ImageRegistry, ImageResource, LoadImageError};
 
-use super::{PngImageResource, PpmImageResource};
+use super::{
+    ExrImageResource, HdrImageResource, JpegImageResource, PngImageResource, PpmImageResource,
+};
 
 #[derive(Debug)]
 pub struct FileSystemImageRegistry {
@@ -33,6 +35,12 @@ impl ImageRegistry for FileSystemImageRegistry {
             Arc::new(PngImageResource::new(name).load()?)
         } else if name_lowercase.ends_with(".ppm") {
             Arc::new(PpmImageResource::new(name).load()?)
+        } else if name_lowercase.ends_with(".jpg") || name_lowercase.ends_with(".jpeg") {
+            Arc::new(JpegImageResource::new(name).load()?)
+        } else if name_lowercase.ends_with(".exr") {
+            Arc::new(ExrImageResource::new(name).load()?)
+        } else if name_lowercase.ends_with(".hdr") {
+            Arc::new(HdrImageResource::new(name).load()?)
         } else {
             whatever!("the type of image `{}` is unsupported", name);
         };
@@ -0,0 +1,141 @@
+use std::fs;
+use std::io::Error as IoError;
+use std::path::PathBuf;
+
+use snafu::prelude::*;
+
+use crate::domain::material::util::{IesProfile, TryNewIesProfileError};
+use crate::domain::math::numeric::Val;
+
+#[derive(Debug, Clone)]
+pub struct IesProfileLoader {
+    path: PathBuf,
+}
+
+impl IesProfileLoader {
+    const TYPE_C_PHOTOMETRY: usize = 1;
+
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self { path: path.into() }
+    }
+
+    pub fn load(&self) -> Result<IesProfile, ParseIesProfileError> {
+        let path = self.path.as_path();
+        let content = fs::read_to_string(path).context(IoSnafu { path })?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<IesProfile, ParseIesProfileError> {
+        let mut lines = content.lines();
+        let tilt = lines
+            .by_ref()
+            .map(str::trim)
+            .find(|line| line.starts_with("TILT="))
+            .context(MissingTiltSnafu)?;
+        ensure!(tilt == "TILT=NONE", UnsupportedTiltSnafu);
+
+        let mut tokens = lines.flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ','));
+        let mut next = || -> Result<Val, ParseIesProfileError> {
+            let token = tokens
+                .by_ref()
+                .find(|token| !token.is_empty())
+                .context(UnexpectedEndSnafu)?;
+            let value = token
+                .parse::<f64>()
+                .ok()
+                .context(InvalidNumberSnafu { token })?;
+            Ok(Val(value))
+        };
+
+        let _num_lamps = next()?;
+        let _lumens = next()?;
+        let multiplier = next()?;
+        let num_vertical = Self::to_count(next()?)?;
+        let num_horizontal = Self::to_count(next()?)?;
+        let photometric_type = Self::to_count(next()?)?;
+        ensure!(
+            photometric_type == Self::TYPE_C_PHOTOMETRY,
+            UnsupportedPhotometricTypeSnafu { photometric_type }
+        );
+        for _ in 0..7 {
+            next()?;
+        }
+
+        let vertical = (0..num_vertical)
+            .map(|_| next())
+            .collect::<Result<_, _>>()?;
+        let horizontal = (0..num_horizontal)
+            .map(|_| next())
+            .collect::<Result<_, _>>()?;
+        let candela = (0..(num_vertical * num_horizontal))
+            .map(|_| next().map(|c| c * multiplier))
+            .collect::<Result<_, _>>()?;
+
+        IesProfile::new(vertical, horizontal, candela).context(InvalidProfileSnafu)
+    }
+
+    fn to_count(value: Val) -> Result<usize, ParseIesProfileError> {
+        ensure!(
+            value >= Val(0.0) && value.fract() == Val(0.0),
+            InvalidCountSnafu
+        );
+        Ok(value.0 as usize)
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum ParseIesProfileError {
+    #[snafu(display("could not read IES file `{}`", path.display()))]
+    Io { path: PathBuf, source: IoError },
+    #[snafu(display("IES file has no TILT line"))]
+    MissingTilt,
+    #[snafu(display("only IES files with TILT=NONE are supported"))]
+    UnsupportedTilt,
+    #[snafu(display("IES file ended unexpectedly"))]
+    UnexpectedEnd,
+    #[snafu(display("could not parse `{token}` as a number"))]
+    InvalidNumber { token: String },
+    #[snafu(display("counts in IES file should be non-negative integers"))]
+    InvalidCount,
+    #[snafu(display("photometric type {photometric_type} is not supported (only type C)"))]
+    UnsupportedPhotometricType { photometric_type: usize },
+    #[snafu(display("IES file describes an invalid profile"))]
+    InvalidProfile { source: TryNewIesProfileError },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "IESNA:LM-63-2002
+[TEST] downlight
+TILT=NONE
+1 1000 1 3 1 1 2 0.1 0.1 0
+1 1 10
+0 45 90
+0
+200 100 0
+";
+
+    #[test]
+    fn ies_profile_loader_parse_succeeds() {
+        let profile = IesProfileLoader::parse(CONTENT).unwrap();
+        assert_eq!(profile.vertical().len(), 3);
+        assert_eq!(profile.lookup_angles(Val(0.0), Val(123.0)), Val(1.0));
+        assert_eq!(profile.lookup_angles(Val(45.0), Val(0.0)), Val(0.5));
+        assert_eq!(profile.lookup_angles(Val(90.0), Val(0.0)), Val(0.0));
+    }
+
+    #[test]
+    fn ies_profile_loader_parse_fails_when_file_is_truncated() {
+        let content = &CONTENT[..CONTENT.len() - 4];
+        assert!(matches!(
+            IesProfileLoader::parse(content),
+            Err(ParseIesProfileError::UnexpectedEnd),
+        ));
+    }
+}
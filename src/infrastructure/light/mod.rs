@@ -0,0 +1,3 @@
+mod ies;
+
+pub use ies::{IesProfileLoader, ParseIesProfileError};
@@ -8,7 +8,9 @@ use snafu::prelude::*;
 use crate::domain::material::def::{DynMaterial, MaterialKind};
 use crate::domain::math::transformation::Sequential;
 use crate::domain::scene::entity::EntitySceneBuilder;
-use crate::domain::shape::mesh::{TryAddMeshUvCoordinateError, TryNewMeshError};
+use crate::domain::shape::mesh::{
+    TryAddMeshNormalError, TryAddMeshUvCoordinateError, TryNewMeshError,
+};
 
 pub trait EntityModelLoader: Send + Sync {
     fn load(
@@ -59,6 +61,15 @@ pub enum LoadEntityModelError {
         mesh_name: String,
         source: TryAddMeshUvCoordinateError,
     },
+    #[snafu(display(
+        "encountered invalid normal in mesh `{mesh_name}` from `{}`",
+        display_optional_path(path)
+    ))]
+    InvalidMeshNormal {
+        path: Option<PathBuf>,
+        mesh_name: String,
+        source: TryAddMeshNormalError,
+    },
     #[snafu(display(
         "parameters of material {material_name} ({material_kind:?}) are incorrectly configured"
     ))]
@@ -7,7 +7,8 @@ use snafu::prelude::*;
 
 use crate::domain::image::external::ImageRegistry;
 use crate::domain::material::def::DynMaterial;
-use crate::domain::math::geometry::Point;
+use crate::domain::math::algebra::Vector;
+use crate::domain::math::geometry::{Normal, Point};
 use crate::domain::math::numeric::{Val, WrappedVal};
 use crate::domain::math::transformation::Transformation;
 use crate::domain::scene::entity::{EntitySceneBuilder, TypedEntitySceneBuilder};
@@ -18,7 +19,7 @@ use crate::infrastructure::model::def::{
     InvalidMeshSnafu, MissingMaterialSnafu, UnspecifiedMaterialSnafu,
 };
 
-use super::def::InvalidMeshUvCoordinateSnafu;
+use super::def::{InvalidMeshNormalSnafu, InvalidMeshUvCoordinateSnafu};
 use super::obj_material::ObjMaterialConverterChain;
 use super::{EntityModelLoader, EntityModelLoaderConfiguration, LoadEntityModelError};
 
@@ -28,6 +29,7 @@ pub struct EntityObjModelLoader {
     path: Option<PathBuf>,
     vertices: Arc<[Point]>,
     uvs: Arc<[UvCoordinate]>,
+    normals: Arc<[Normal]>,
     converter: ObjMaterialConverterChain,
     material_cache: Arc<RwLock<HashMap<String, DynMaterial>>>,
 }
@@ -58,7 +60,13 @@ impl EntityObjModelLoader {
             .into();
         let uvs = (obj.texture.iter())
             .map(Self::map_f32_pair)
-            .map(|[u, v]| UvCoordinate::clamp(u, v))
+            .map(|[u, v]| UvCoordinate::unbounded(u, v))
+            .collect::<Vec<_>>()
+            .into();
+        let normals = (obj.normal.iter())
+            .map(Self::map_f32_array)
+            .map(|[x, y, z]| Vector::new(x, y, z))
+            .map(|n| Normal::normalize(n).unwrap_or(Normal::z_direction()))
             .collect::<Vec<_>>()
             .into();
 
@@ -76,6 +84,7 @@ impl EntityObjModelLoader {
             path,
             vertices,
             uvs,
+            normals,
             converter: ObjMaterialConverterChain::new(image_registry),
             material_cache: Arc::new(RwLock::new(HashMap::new())),
         }
@@ -113,6 +122,22 @@ impl EntityObjModelLoader {
                 })?
         };
 
+        let normals = Arc::clone(&self.normals);
+        let normal_indices = (group.polys.iter())
+            .map(|poly| &poly.0)
+            .map(|indices| indices.iter().flat_map(|i| i.2).collect::<Vec<_>>())
+            .filter(|indices| !indices.is_empty())
+            .collect::<Vec<_>>();
+        let mesh = if normal_indices.is_empty() {
+            mesh
+        } else {
+            mesh.with_normals(normals, normal_indices)
+                .with_context(|_| InvalidMeshNormalSnafu {
+                    path: self.path.clone(),
+                    mesh_name: Self::generate_mesh_name(object, group),
+                })?
+        };
+
         Ok(mesh)
     }
 
@@ -220,3 +245,136 @@ pub enum ParseObjModelError {
         source: MtlLibsLoadError,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::domain::color::core::Albedo;
+    use crate::domain::light::def::DynLight;
+    use crate::domain::light::primitive::Portal;
+    use crate::domain::material::primitive::Diffuse;
+    use crate::domain::math::geometry::Direction;
+    use crate::domain::math::numeric::DisRange;
+    use crate::domain::math::transformation::{Scaling, Sequential};
+    use crate::domain::ray::Ray;
+    use crate::domain::scene::entity::{EntityScene, SceneWarning, Visibility};
+    use crate::domain::scene::pool::ShapePool;
+    use crate::domain::shape::def::{BoundingBox, DynShape, Shape};
+    use crate::domain::shape::util::{ShapeConstructor, ShapeContainer, ShapeId};
+    use crate::infrastructure::image::FileSystemImageRegistry;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct RecordingSceneBuilder {
+        shapes: ShapePool,
+        ids: Vec<ShapeId>,
+    }
+
+    impl RecordingSceneBuilder {
+        fn bounding_box(&self) -> BoundingBox {
+            (self.ids.iter())
+                .filter_map(|id| self.shapes.get_shape(*id)?.bounding_box())
+                .reduce(|a, b| a.merge(&b))
+                .unwrap()
+        }
+    }
+
+    impl EntitySceneBuilder for RecordingSceneBuilder {
+        fn add_dyn_with_visibility(
+            &mut self,
+            shape: DynShape,
+            _material: DynMaterial,
+            _visibility: Visibility,
+        ) {
+            self.ids.push(self.shapes.add_shape(shape));
+        }
+
+        fn add_constructor_dyn(
+            &mut self,
+            constructor: Box<dyn ShapeConstructor>,
+            _material: DynMaterial,
+        ) {
+            self.ids.extend(constructor.construct(&mut self.shapes));
+        }
+
+        fn add_light_dyn(&mut self, _light: DynLight) {}
+
+        fn add_portal(&mut self, _portal: Portal) {}
+
+        fn build_with_warnings(self: Box<Self>) -> (Box<dyn EntityScene>, Vec<SceneWarning>) {
+            unreachable!()
+        }
+    }
+
+    fn load_square(transformation: Sequential) -> BoundingBox {
+        let source = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nusemtl square\nf 1 2 3\nf 1 3 4\n";
+        let obj = ObjData::load_buf(Cursor::new(source)).unwrap();
+        let loader = EntityObjModelLoader::in_memory(obj, Arc::new(FileSystemImageRegistry::new()));
+
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap());
+        let config = EntityModelLoaderConfiguration::default()
+            .with_transformation(transformation)
+            .add_material("square", diffuse);
+        let mut builder = RecordingSceneBuilder::default();
+        loader.load(&mut builder, config).unwrap();
+        builder.bounding_box()
+    }
+
+    #[test]
+    fn entity_obj_model_loader_load_succeeds_applying_scaling() {
+        let original = load_square(Sequential::default());
+        let scaled =
+            load_square(Sequential::default().with_scaling(Scaling::uniform(Val(2.0)).unwrap()));
+        assert_eq!(scaled.min(), Point::new(Val(0.0), Val(0.0), Val(0.0)));
+        assert_eq!(
+            scaled.max().into_vector(),
+            original.max().into_vector() * Val(2.0),
+        );
+    }
+
+    #[test]
+    fn entity_obj_model_loader_load_succeeds_reading_vertex_normals() {
+        let source = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 1 0 1\nusemtl tri\nf 1//1 2//1 3//1\n";
+        let obj = ObjData::load_buf(Cursor::new(source)).unwrap();
+        let loader = EntityObjModelLoader::in_memory(obj, Arc::new(FileSystemImageRegistry::new()));
+
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap());
+        let config = EntityModelLoaderConfiguration::default().add_material("tri", diffuse);
+        let mut builder = RecordingSceneBuilder::default();
+        loader.load(&mut builder, config).unwrap();
+
+        let shape = builder.shapes.get_shape(builder.ids[0]).unwrap();
+        let normal = shape.normal(Point::new(Val(0.2), Val(0.2), Val(0.0)));
+        let expected = Normal::normalize(Vector::new(Val(1.0), Val(0.0), Val(1.0))).unwrap();
+        assert_eq!(normal, expected);
+    }
+
+    #[test]
+    fn entity_obj_model_loader_load_succeeds_mapping_textured_quad_corners() {
+        let source = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n\
+                      vt 0 0\nvt 1 0\nvt 1 1\nvt 0 1\nvn 0 0 1\n\
+                      usemtl quad\nf 1/1/1 2/2/1 3/3/1 4/4/1\n";
+        let obj = ObjData::load_buf(Cursor::new(source)).unwrap();
+        let loader = EntityObjModelLoader::in_memory(obj, Arc::new(FileSystemImageRegistry::new()));
+
+        let diffuse = Diffuse::new(Albedo::broadcast(Val(0.5)).unwrap());
+        let config = EntityModelLoaderConfiguration::default().add_material("quad", diffuse);
+        let mut builder = RecordingSceneBuilder::default();
+        loader.load(&mut builder, config).unwrap();
+        let shape = builder.shapes.get_shape(builder.ids[0]).unwrap();
+
+        let eps = 1e-3;
+        for (x, y) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+            let (px, py) = (
+                Val(x).clamp(Val(eps), Val(1.0 - eps)),
+                Val(y).clamp(Val(eps), Val(1.0 - eps)),
+            );
+            let ray = Ray::new(Point::new(px, py, Val(1.0)), -Direction::z_direction());
+            let uv = shape.hit(&ray, DisRange::positive()).unwrap().uv().unwrap();
+            assert!((uv.u() - Val(x)).abs() < Val(2.0 * eps));
+            assert!((uv.v() - Val(y)).abs() < Val(2.0 * eps));
+        }
+    }
+}